@@ -0,0 +1,145 @@
+//! Model-free benchmarks for the audio pipeline stages.
+//!
+//! Run with `cargo bench --bench pipeline`. Stages are measured on 60 s of
+//! synthetic 48 kHz audio (tone bursts separated by pauses, plus noise).
+//!
+//! Environment variables:
+//! - `S2T_BENCH_SAVE=path.json` — write results as a baseline
+//! - `S2T_BENCH_BASELINE=path.json` — compare against a baseline and exit
+//!   non-zero on regressions
+//! - `S2T_BENCH_THRESHOLD=0.10` — allowed relative slowdown (default 10%)
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+use voice_dictation::cli::args::ChannelMode;
+use voice_dictation::cli::bench::{find_regressions, load_baseline, BenchReport};
+use voice_dictation::cli::wav_reader::{prepare_for_whisper, WavAudio};
+use voice_dictation::infrastructure::perf::{peak_rss_bytes, real_time_factor};
+use voice_dictation::recording::denoise::NnnoiselessDenoiser;
+use voice_dictation::recording::split::SplitConfig;
+use voice_dictation::transcription::chunker::{AudioChunker, ChunkerConfig};
+
+const AUDIO_SECS: usize = 60;
+const SOURCE_RATE: u32 = 48000;
+const ITERATIONS: u32 = 5;
+
+/// Tone bursts (2 s on, 1 s off) with low-level deterministic noise.
+fn synthetic_audio(sample_rate: u32, secs: usize) -> Vec<f32> {
+    let mut seed: u32 = 12345;
+    (0..sample_rate as usize * secs)
+        .map(|i| {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12345);
+            let noise = ((seed >> 16) as f32 / 32768.0 - 1.0) * 0.01;
+            let t = i as f32 / sample_rate as f32;
+            let voiced = (t % 3.0) < 2.0;
+            let tone = if voiced {
+                0.3 * (2.0 * std::f32::consts::PI * 220.0 * t).sin()
+            } else {
+                0.0
+            };
+            tone + noise
+        })
+        .collect()
+}
+
+fn bench(name: &str, mut f: impl FnMut()) -> f64 {
+    f(); // warm-up
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let mean_ms = start.elapsed().as_secs_f64() * 1000.0 / ITERATIONS as f64;
+    println!("  {:16} {:>10.2} ms", name, mean_ms);
+    mean_ms
+}
+
+fn main() {
+    println!(
+        "Pipeline benchmarks ({} s audio, {} iterations)",
+        AUDIO_SECS, ITERATIONS
+    );
+
+    let wav = WavAudio {
+        sample_rate: SOURCE_RATE,
+        channels: 1,
+        duration_secs: AUDIO_SECS as f64,
+        samples: synthetic_audio(SOURCE_RATE, AUDIO_SECS),
+    };
+    let samples_16k = prepare_for_whisper(&wav, ChannelMode::Mix, None)
+        .expect("resampling failed")
+        .samples;
+
+    let mut stages = BTreeMap::new();
+
+    stages.insert(
+        "resample".to_string(),
+        bench("resample", || {
+            prepare_for_whisper(&wav, ChannelMode::Mix, None).expect("resampling failed");
+        }),
+    );
+
+    let denoiser = NnnoiselessDenoiser::new();
+    stages.insert(
+        "denoise".to_string(),
+        bench("denoise", || {
+            denoiser.denoise_buffer(&samples_16k).expect("denoising failed");
+        }),
+    );
+
+    // Force VAD scanning by keeping segments shorter than the audio
+    let chunker = AudioChunker::new(ChunkerConfig {
+        split: SplitConfig {
+            max_segment_secs: 10,
+            ..SplitConfig::default()
+        },
+        ..ChunkerConfig::default()
+    });
+    stages.insert(
+        "vad_segment".to_string(),
+        bench("vad_segment", || {
+            chunker.segment(&samples_16k).expect("segmentation failed");
+        }),
+    );
+
+    let total_ms: f64 = stages.values().sum();
+    let report = BenchReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        input_file: "synthetic".to_string(),
+        audio_secs: AUDIO_SECS as f64,
+        backend: "none".to_string(),
+        model: "none".to_string(),
+        iterations: ITERATIONS,
+        stages,
+        rtf: real_time_factor(Duration::from_secs_f64(total_ms / 1000.0), AUDIO_SECS as f64),
+        peak_rss_bytes: peak_rss_bytes(),
+    };
+    println!("  {:16} {:>10.4}", "RTF", report.rtf);
+
+    if let Ok(path) = std::env::var("S2T_BENCH_SAVE") {
+        let json = serde_json::to_string_pretty(&report).expect("serialize report");
+        std::fs::write(&path, json).expect("write baseline");
+        println!("Baseline written to: {}", path);
+    }
+
+    if let Ok(path) = std::env::var("S2T_BENCH_BASELINE") {
+        let threshold = std::env::var("S2T_BENCH_THRESHOLD")
+            .ok()
+            .and_then(|t| t.parse().ok())
+            .unwrap_or(0.10);
+        let baseline = load_baseline(path.as_ref()).expect("load baseline");
+        let regressions = find_regressions(&baseline, &report, threshold);
+        for r in &regressions {
+            println!(
+                "REGRESSION {:20} {:.3} -> {:.3} ({:+.1}%)",
+                r.metric,
+                r.baseline,
+                r.current,
+                r.change_pct()
+            );
+        }
+        if !regressions.is_empty() {
+            std::process::exit(1);
+        }
+        println!("No regressions against {}", path);
+    }
+}
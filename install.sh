@@ -1,6 +1,14 @@
 #!/bin/bash
 set -e
 
+# whisper.cpp leaves AVX-512 off unless asked; enable it where the CPU has it
+if grep -qw avx512f /proc/cpuinfo 2>/dev/null; then
+    echo "CPU supports AVX-512, enabling it in whisper.cpp..."
+    export WHISPER_NO_AVX512=OFF
+    grep -qw avx512_vnni /proc/cpuinfo && export WHISPER_NO_AVX512_VNNI=OFF
+    grep -qw avx512_vbmi /proc/cpuinfo && export WHISPER_NO_AVX512_VBMI=OFF
+fi
+
 echo "Building release version..."
 cargo build --release
 
@@ -1,3 +1,4 @@
+use crate::infrastructure::remote::RemoteCommand;
 use async_channel::{Receiver, Sender};
 
 /// Bundled UI communication channels for tray, hotkey, and dialog interactions
@@ -8,6 +9,7 @@ pub struct UIChannels {
     pub toggle_recording: (Sender<()>, Receiver<()>),
     pub reload_hotkeys: (Sender<()>, Receiver<()>),
     pub model_ready: (Sender<bool>, Receiver<bool>),
+    pub remote_command: (Sender<RemoteCommand>, Receiver<RemoteCommand>),
 }
 
 impl UIChannels {
@@ -20,6 +22,7 @@ impl UIChannels {
             toggle_recording: async_channel::bounded(1),
             reload_hotkeys: async_channel::bounded(1),
             model_ready: async_channel::bounded(1),
+            remote_command: async_channel::bounded(1),
         }
     }
 
@@ -82,6 +85,16 @@ impl UIChannels {
     pub fn model_ready_rx(&self) -> &Receiver<bool> {
         &self.model_ready.1
     }
+
+    /// Get sender for remote_command channel
+    pub fn remote_command_tx(&self) -> &Sender<RemoteCommand> {
+        &self.remote_command.0
+    }
+
+    /// Get receiver for remote_command channel
+    pub fn remote_command_rx(&self) -> &Receiver<RemoteCommand> {
+        &self.remote_command.1
+    }
 }
 
 impl Default for UIChannels {
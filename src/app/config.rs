@@ -1,5 +1,6 @@
 use anyhow::{bail, Context, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::PathBuf;
 
@@ -11,46 +12,223 @@ pub struct Config {
     pub history_max_entries: usize,
     #[serde(default = "default_history_max_age_days")]
     pub history_max_age_days: i64,
+    /// Days deleted history entries stay in the trash
+    #[serde(default = "default_history_trash_days")]
+    pub history_trash_days: i64,
+    /// Backups of the history store kept, made before a save at most hourly; 0 turns them off
+    #[serde(default = "default_history_backups")]
+    pub history_backups: usize,
+    #[serde(default = "default_history_backend")]
+    pub history_backend: String,
+    /// Kiosk mode: nothing of the sessions is kept on disk or uploaded
+    #[serde(default = "default_read_only_history")]
+    pub read_only_history: bool,
     #[serde(default = "default_auto_copy")]
     pub auto_copy: bool,
     #[serde(default = "default_hotkey_enabled")]
     pub hotkey_enabled: bool,
     #[serde(default = "default_hotkey")]
     pub hotkey: String,
+    #[serde(default = "default_hotkey_layout_independent")]
+    pub hotkey_layout_independent: bool,
     #[serde(default = "default_auto_paste")]
     pub auto_paste: bool,
+    #[serde(default = "default_start_after_model_load")]
+    pub start_after_model_load: bool,
     #[serde(default = "default_recording_mode")]
     pub recording_mode: String,
     #[serde(default = "default_diarization_method")]
     pub diarization_method: String,
     #[serde(default)]
     pub sortformer_model_path: Option<String>,
+    #[serde(default = "default_diarization_max_speakers")]
+    pub diarization_max_speakers: usize,
+    #[serde(default)]
+    pub diarization_onset: Option<f32>,
+    #[serde(default)]
+    pub diarization_offset: Option<f32>,
+    #[serde(default = "default_speaker_attribution")]
+    pub speaker_attribution: String,
+    #[serde(default = "default_live_diarization")]
+    pub live_diarization: bool,
+    #[serde(default = "default_mic_diarization")]
+    pub mic_diarization: bool,
+    #[serde(default = "default_speaker_reidentification")]
+    pub speaker_reidentification: bool,
+    #[serde(default = "default_speaker_match_threshold")]
+    pub speaker_match_threshold: f32,
     #[serde(default = "default_continuous_mode")]
     pub continuous_mode: bool,
     #[serde(default = "default_segment_interval_secs")]
     pub segment_interval_secs: u32,
+    #[serde(default = "default_segment_retry_attempts")]
+    pub segment_retry_attempts: u32,
     #[serde(default = "default_use_vad")]
     pub use_vad: bool,
     #[serde(default = "default_vad_silence_threshold_ms")]
     pub vad_silence_threshold_ms: u32,
     #[serde(default = "default_vad_min_speech_ms")]
     pub vad_min_speech_ms: u32,
+    /// VAD timing by recognition language; languages not listed use the values above
+    #[serde(default)]
+    pub vad_language_profiles: BTreeMap<String, VadProfile>,
     #[serde(default = "default_denoise_enabled")]
     pub denoise_enabled: bool,
+    /// "rnnoise" or "deepfilternet"
+    #[serde(default = "default_denoise_backend")]
+    pub denoise_backend: String,
+    /// DeepFilterNet streaming ONNX model, required by the "deepfilternet" backend
+    #[serde(default)]
+    pub deepfilter_model_path: Option<String>,
+    /// High-pass filter of the microphone against rumble and DC offset
+    #[serde(default = "default_high_pass_enabled")]
+    pub high_pass_enabled: bool,
+    #[serde(default = "default_high_pass_cutoff_hz")]
+    pub high_pass_cutoff_hz: f32,
+    /// "high" (sinc) or "fast" (linear) conversion of the microphone rate to 16 kHz
+    #[serde(default = "default_resampler_quality")]
+    pub resampler_quality: String,
+    /// Seconds of microphone audio kept while idle and put before each recording
+    #[serde(default = "default_pre_record_secs")]
+    pub pre_record_secs: f32,
+    /// Recordings stop by themselves after this many minutes; 0 never stops them
+    #[serde(default = "default_max_recording_minutes")]
+    pub max_recording_minutes: u32,
+    /// Conference audio beyond this many minutes goes to disk; 0 keeps it all in memory
+    #[serde(default = "default_conference_spool_minutes")]
+    pub conference_spool_minutes: u32,
+    /// Cancel the loopback's echo in the microphone in conference mode
+    #[serde(default = "default_echo_cancellation")]
+    pub echo_cancellation: bool,
+    /// Automatic gain control of the microphone
+    #[serde(default = "default_agc_enabled")]
+    pub agc_enabled: bool,
+    #[serde(default = "default_agc_target_dbfs")]
+    pub agc_target_dbfs: f32,
+    #[serde(default = "default_agc_max_gain_db")]
+    pub agc_max_gain_db: f32,
+    /// Play the microphone to the default output while recording, for headsets
+    #[serde(default)]
+    pub monitor_enabled: bool,
+    #[serde(default = "default_monitor_volume")]
+    pub monitor_volume: f32,
+    #[serde(default)]
+    pub loopback_source: Option<String>,
+    #[serde(default = "default_recording_normalization")]
+    pub recording_normalization: String,
+    #[serde(default = "default_recording_peak_dbfs")]
+    pub recording_peak_dbfs: f32,
+    #[serde(default = "default_recording_target_lufs")]
+    pub recording_target_lufs: f32,
+    /// Name of saved recordings: {date}, {time}, {mode} and {title} are filled in
+    #[serde(default = "default_recording_filename_template")]
+    pub recording_filename_template: String,
+    /// "wav", "flac" or "opus"; FLAC and Opus are encoded with ffmpeg
+    #[serde(default = "default_recording_format")]
+    pub recording_format: String,
+    /// Keep the audio of dictation as well, linked from its history entry
+    #[serde(default = "default_save_recordings")]
+    pub save_recordings: bool,
     #[serde(default = "default_vad_engine")]
     pub vad_engine: String,
     #[serde(default = "default_silero_threshold")]
     pub silero_threshold: f32,
+    #[serde(default)]
+    pub silero_model_path: Option<String>,
+    #[serde(default = "default_vad_aggressiveness")]
+    pub vad_aggressiveness: u8,
+    #[serde(default = "default_vad_adaptive_noise_floor")]
+    pub vad_adaptive_noise_floor: bool,
+    #[serde(default)]
+    pub vad_noise_floor_dbfs: Option<f32>,
+    #[serde(default)]
+    pub vad_speech_level_dbfs: Option<f32>,
+    #[serde(default = "default_vad_pre_roll_ms")]
+    pub vad_pre_roll_ms: u32,
+    #[serde(default = "default_vad_post_roll_ms")]
+    pub vad_post_roll_ms: u32,
+    #[serde(default = "default_speech_gated_recording")]
+    pub speech_gated_recording: bool,
+    #[serde(default = "default_trim_dictation_silence")]
+    pub trim_dictation_silence: bool,
+    #[serde(default = "default_dictation_max_pause_ms")]
+    pub dictation_max_pause_ms: u32,
     #[serde(default = "default_stt_backend")]
     pub stt_backend: String,
     #[serde(default)]
     pub tdt_model_path: Option<String>,
     #[serde(default = "default_max_segment_secs")]
     pub max_segment_secs: u32,
+    #[serde(default = "default_phonetic_hints")]
+    pub phonetic_hints: bool,
+    #[serde(default = "default_punctuation_restoration")]
+    pub punctuation_restoration: String,
+    /// Keep hesitations and cut-off words, marked up as `[еее]` and `сло-`
+    #[serde(default = "default_verbatim_transcription")]
+    pub verbatim_transcription: bool,
+    #[serde(default = "default_translation_captions")]
+    pub translation_captions: bool,
+    #[serde(default)]
+    pub webdav_url: Option<String>,
+    #[serde(default)]
+    pub webdav_username: Option<String>,
+    #[serde(default = "default_webdav_modes")]
+    pub webdav_modes: Vec<String>,
+    #[serde(default = "default_webdav_upload_transcripts")]
+    pub webdav_upload_transcripts: bool,
+    #[serde(default = "default_webdav_upload_recordings")]
+    pub webdav_upload_recordings: bool,
+    #[serde(default = "default_remote_control_enabled")]
+    pub remote_control_enabled: bool,
+    #[serde(default = "default_remote_control_port")]
+    pub remote_control_port: u16,
+    #[serde(default = "default_anki_front_template")]
+    pub anki_front_template: String,
+    #[serde(default = "default_anki_back_template")]
+    pub anki_back_template: String,
+    #[serde(default = "default_transcription_cache_max_mb")]
+    pub transcription_cache_max_mb: u64,
+    #[serde(default = "default_transcription_cache_ttl_days")]
+    pub transcription_cache_ttl_days: u32,
+    #[serde(default)]
+    pub action_items_llm_url: Option<String>,
+    #[serde(default = "default_action_items_llm_model")]
+    pub action_items_llm_model: String,
+    #[serde(default = "default_onboarding_completed")]
+    pub onboarding_completed: bool,
+    /// Apply the preset of the active workspace when recording starts
+    #[serde(default = "default_workspace_presets_enabled")]
+    pub workspace_presets_enabled: bool,
+    #[serde(default)]
+    pub workspace_presets: Vec<WorkspacePreset>,
 }
 
 fn default_diarization_method() -> String {
-    "channel".to_string() // "channel" or "sortformer"
+    "channel".to_string() // "channel", "sortformer" or "hybrid"
+}
+
+fn default_diarization_max_speakers() -> usize {
+    4 // Sortformer model limit
+}
+
+fn default_speaker_attribution() -> String {
+    "words".to_string() // "words" (one pass, word timestamps) or "segments"
+}
+
+fn default_live_diarization() -> bool {
+    true // Diarize conference recordings while recording (Sortformer only)
+}
+
+fn default_mic_diarization() -> bool {
+    false // Sortformer speaker labels for single-microphone recordings
+}
+
+fn default_speaker_reidentification() -> bool {
+    false // Keep Sortformer speaker labels stable across sessions
+}
+
+fn default_speaker_match_threshold() -> f32 {
+    0.85 // Voiceprint cosine similarity to treat two speakers as the same person
 }
 
 fn default_continuous_mode() -> bool {
@@ -61,6 +239,10 @@ fn default_segment_interval_secs() -> u32 {
     5 // 5 seconds for more responsive feedback
 }
 
+fn default_segment_retry_attempts() -> u32 {
+    2 // Extra attempts for a continuous-mode segment that fails to transcribe
+}
+
 fn default_use_vad() -> bool {
     true
 }
@@ -73,18 +255,146 @@ fn default_vad_min_speech_ms() -> u32 {
     500 // Minimum 500ms of speech for a valid segment
 }
 
+/// VAD overrides for one language. Unset values keep the global ones.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct VadProfile {
+    #[serde(default)]
+    pub silence_threshold_ms: Option<u32>,
+    #[serde(default)]
+    pub min_speech_ms: Option<u32>,
+}
+
+/// Overrides applied when recording starts on a workspace. Unset values keep the global ones.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WorkspacePreset {
+    /// KDE activity, virtual desktop name or desktop number (from 1)
+    pub workspace: String,
+    #[serde(default)]
+    pub recording_mode: Option<String>,
+    #[serde(default)]
+    pub auto_copy: Option<bool>,
+    #[serde(default)]
+    pub auto_paste: Option<bool>,
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+fn default_workspace_presets_enabled() -> bool {
+    false // Detection runs helper programs on every start
+}
+
 fn default_denoise_enabled() -> bool {
     false // Disabled by default for backward compatibility
 }
 
+fn default_denoise_backend() -> String {
+    "rnnoise".to_string() // Built in; DeepFilterNet needs a model file
+}
+
+fn default_high_pass_enabled() -> bool {
+    true // Speech has nothing below the cutoff
+}
+
+fn default_high_pass_cutoff_hz() -> f32 {
+    80.0 // Below the lowest male voice fundamentals
+}
+
+fn default_resampler_quality() -> String {
+    "high".to_string() // Linear interpolation aliases above 8 kHz into the speech band
+}
+
+fn default_pre_record_secs() -> f32 {
+    0.0 // Off: keeps the microphone open while idle
+}
+
+fn default_max_recording_minutes() -> u32 {
+    0 // Off: a multi-hour conference must not be cut short
+}
+
+fn default_conference_spool_minutes() -> u32 {
+    30 // About 115 MB of RAM; shorter calls never touch the disk
+}
+
+fn default_echo_cancellation() -> bool {
+    true // Skipped by itself when no echo is heard, e.g. with headphones
+}
+
+fn default_agc_enabled() -> bool {
+    false // Most microphones record at a usable level
+}
+
+fn default_agc_target_dbfs() -> f32 {
+    -20.0 // RMS level of speech after the AGC
+}
+
+fn default_agc_max_gain_db() -> f32 {
+    30.0 // More would mostly raise the noise
+}
+
+fn default_monitor_volume() -> f32 {
+    1.0 // The microphone as captured
+}
+
+fn default_recording_normalization() -> String {
+    "off".to_string() // "off", "peak" or "lufs" (saved conference recordings only)
+}
+
+fn default_recording_peak_dbfs() -> f32 {
+    -1.0 // Headroom left below full scale
+}
+
+fn default_recording_target_lufs() -> f32 {
+    -16.0 // Typical loudness for spoken-word audio
+}
+
+fn default_recording_filename_template() -> String {
+    "{date}_{time}_{mode}_{title}.wav".to_string() // Sorts by date in file managers
+}
+
+fn default_recording_format() -> String {
+    "wav".to_string() // Needs no external encoder
+}
+
+fn default_save_recordings() -> bool {
+    false // Conference audio is always kept; dictation audio only on request
+}
+
 fn default_vad_engine() -> String {
-    "webrtc".to_string() // WebRTC VAD by default for backward compatibility
+    "webrtc".to_string() // "webrtc" (default, for backward compatibility), "silero" or "earshot"
 }
 
 fn default_silero_threshold() -> f32 {
     0.5 // Default speech probability threshold for Silero VAD
 }
 
+fn default_vad_aggressiveness() -> u8 {
+    2 // WebRTC "aggressive" mode, the level used before it became configurable
+}
+
+fn default_vad_adaptive_noise_floor() -> bool {
+    true // Follow changes in background noise during long sessions
+}
+
+fn default_vad_pre_roll_ms() -> u32 {
+    300 // Keeps soft word onsets the detector reacts to late
+}
+
+fn default_vad_post_roll_ms() -> u32 {
+    300 // Keeps trailing consonants after the last speech frame
+}
+
+fn default_speech_gated_recording() -> bool {
+    false // Keep the full recording, pauses included
+}
+
+fn default_trim_dictation_silence() -> bool {
+    false // Transcribe the dictation exactly as recorded
+}
+
+fn default_dictation_max_pause_ms() -> u32 {
+    2000 // Longer than a pause for thought within a sentence
+}
+
 fn default_stt_backend() -> String {
     "whisper".to_string() // "whisper" (default) or "tdt"
 }
@@ -93,6 +403,66 @@ fn default_max_segment_secs() -> u32 {
     300 // 5 minutes default max segment for chunked processing
 }
 
+fn default_phonetic_hints() -> bool {
+    false // Annotate names with alternative spellings from corrections.toml
+}
+
+fn default_punctuation_restoration() -> String {
+    "none".to_string() // "none" or "rules" (applied after TDT output)
+}
+
+fn default_verbatim_transcription() -> bool {
+    false // Dictation wants clean text
+}
+
+fn default_translation_captions() -> bool {
+    false // Live English captions overlay in continuous mode (Whisper only)
+}
+
+fn default_webdav_modes() -> Vec<String> {
+    vec!["conference".to_string()] // Any of "dictation", "continuous", "conference"
+}
+
+fn default_webdav_upload_transcripts() -> bool {
+    true
+}
+
+fn default_webdav_upload_recordings() -> bool {
+    false // Recordings are large; opt in
+}
+
+fn default_remote_control_enabled() -> bool {
+    false // Opens a port on the local network; opt in
+}
+
+fn default_remote_control_port() -> u16 {
+    8765
+}
+
+fn default_anki_front_template() -> String {
+    "{term}".to_string() // Text before the first " — " / " - " / ": " separator
+}
+
+fn default_anki_back_template() -> String {
+    "{definition}".to_string() // Text after the separator
+}
+
+fn default_transcription_cache_max_mb() -> u64 {
+    100 // 0 disables the CLI transcription cache
+}
+
+fn default_transcription_cache_ttl_days() -> u32 {
+    30
+}
+
+fn default_action_items_llm_model() -> String {
+    "llama3.1".to_string() // Used only when action_items_llm_url is set
+}
+
+fn default_onboarding_completed() -> bool {
+    true // Configs written before the wizard existed belong to set-up installs
+}
+
 fn default_history_max_entries() -> usize {
     500
 }
@@ -101,6 +471,22 @@ fn default_history_max_age_days() -> i64 {
     90
 }
 
+fn default_history_trash_days() -> i64 {
+    30
+}
+
+fn default_history_backups() -> usize {
+    5
+}
+
+fn default_history_backend() -> String {
+    "json".to_string() // "json", "jsonl", "sqlite" or "encrypted-json"
+}
+
+fn default_read_only_history() -> bool {
+    false // Shared or demo machines turn it on
+}
+
 fn default_auto_copy() -> bool {
     false
 }
@@ -113,10 +499,21 @@ fn default_hotkey() -> String {
     "Control+Shift+Space".to_string()
 }
 
+fn default_hotkey_layout_independent() -> bool {
+    false
+}
+
 fn default_auto_paste() -> bool {
     false
 }
 
+fn default_start_after_model_load() -> bool {
+    true // A hotkey press during startup starts recording once the model is ready
+}
+
+/// Values of `recording_mode`.
+pub const RECORDING_MODES: &[&str] = &["dictation", "conference", "conference_file"];
+
 fn default_recording_mode() -> String {
     "dictation".to_string()
 }
@@ -128,24 +525,91 @@ impl Default for Config {
             language: "uk".to_string(),
             history_max_entries: default_history_max_entries(),
             history_max_age_days: default_history_max_age_days(),
+            history_trash_days: default_history_trash_days(),
+            history_backups: default_history_backups(),
+            history_backend: default_history_backend(),
+            read_only_history: default_read_only_history(),
             auto_copy: default_auto_copy(),
             hotkey_enabled: default_hotkey_enabled(),
             hotkey: default_hotkey(),
+            hotkey_layout_independent: default_hotkey_layout_independent(),
             auto_paste: default_auto_paste(),
+            start_after_model_load: default_start_after_model_load(),
             recording_mode: default_recording_mode(),
             diarization_method: default_diarization_method(),
             sortformer_model_path: None,
+            diarization_max_speakers: default_diarization_max_speakers(),
+            diarization_onset: None,
+            diarization_offset: None,
+            speaker_attribution: default_speaker_attribution(),
+            live_diarization: default_live_diarization(),
+            mic_diarization: default_mic_diarization(),
+            speaker_reidentification: default_speaker_reidentification(),
+            speaker_match_threshold: default_speaker_match_threshold(),
             continuous_mode: default_continuous_mode(),
             segment_interval_secs: default_segment_interval_secs(),
+            segment_retry_attempts: default_segment_retry_attempts(),
             use_vad: default_use_vad(),
             vad_silence_threshold_ms: default_vad_silence_threshold_ms(),
             vad_min_speech_ms: default_vad_min_speech_ms(),
+            vad_language_profiles: BTreeMap::new(),
             denoise_enabled: default_denoise_enabled(),
+            denoise_backend: default_denoise_backend(),
+            deepfilter_model_path: None,
+            high_pass_enabled: default_high_pass_enabled(),
+            high_pass_cutoff_hz: default_high_pass_cutoff_hz(),
+            resampler_quality: default_resampler_quality(),
+            pre_record_secs: default_pre_record_secs(),
+            max_recording_minutes: default_max_recording_minutes(),
+            conference_spool_minutes: default_conference_spool_minutes(),
+            echo_cancellation: default_echo_cancellation(),
+            agc_enabled: default_agc_enabled(),
+            agc_target_dbfs: default_agc_target_dbfs(),
+            agc_max_gain_db: default_agc_max_gain_db(),
+            monitor_enabled: false,
+            monitor_volume: default_monitor_volume(),
+            loopback_source: None,
+            recording_normalization: default_recording_normalization(),
+            recording_peak_dbfs: default_recording_peak_dbfs(),
+            recording_target_lufs: default_recording_target_lufs(),
+            recording_filename_template: default_recording_filename_template(),
+            recording_format: default_recording_format(),
+            save_recordings: default_save_recordings(),
             vad_engine: default_vad_engine(),
             silero_threshold: default_silero_threshold(),
+            silero_model_path: None,
+            vad_aggressiveness: default_vad_aggressiveness(),
+            vad_adaptive_noise_floor: default_vad_adaptive_noise_floor(),
+            vad_noise_floor_dbfs: None,
+            vad_speech_level_dbfs: None,
+            vad_pre_roll_ms: default_vad_pre_roll_ms(),
+            vad_post_roll_ms: default_vad_post_roll_ms(),
+            speech_gated_recording: default_speech_gated_recording(),
+            trim_dictation_silence: default_trim_dictation_silence(),
+            dictation_max_pause_ms: default_dictation_max_pause_ms(),
             stt_backend: default_stt_backend(),
             tdt_model_path: None,
             max_segment_secs: default_max_segment_secs(),
+            phonetic_hints: default_phonetic_hints(),
+            punctuation_restoration: default_punctuation_restoration(),
+            verbatim_transcription: default_verbatim_transcription(),
+            translation_captions: default_translation_captions(),
+            webdav_url: None,
+            webdav_username: None,
+            webdav_modes: default_webdav_modes(),
+            webdav_upload_transcripts: default_webdav_upload_transcripts(),
+            webdav_upload_recordings: default_webdav_upload_recordings(),
+            remote_control_enabled: default_remote_control_enabled(),
+            remote_control_port: default_remote_control_port(),
+            anki_front_template: default_anki_front_template(),
+            anki_back_template: default_anki_back_template(),
+            transcription_cache_max_mb: default_transcription_cache_max_mb(),
+            transcription_cache_ttl_days: default_transcription_cache_ttl_days(),
+            action_items_llm_url: None,
+            action_items_llm_model: default_action_items_llm_model(),
+            onboarding_completed: default_onboarding_completed(),
+            workspace_presets_enabled: default_workspace_presets_enabled(),
+            workspace_presets: Vec::new(),
         }
     }
 }
@@ -161,28 +625,165 @@ impl Config {
 
         // Clamp numeric fields to sane ranges
         self.segment_interval_secs = self.segment_interval_secs.clamp(1, 300);
+        self.segment_retry_attempts = self.segment_retry_attempts.min(5);
         self.history_max_entries = self.history_max_entries.clamp(1, 10_000);
         self.history_max_age_days = self.history_max_age_days.clamp(1, 3650);
+        self.history_trash_days = self.history_trash_days.clamp(1, 365);
+        self.history_backups = self.history_backups.min(50);
         self.silero_threshold = self.silero_threshold.clamp(0.0, 1.0);
+        self.vad_aggressiveness = self.vad_aggressiveness.min(3);
+        self.vad_noise_floor_dbfs = self.vad_noise_floor_dbfs.map(|v| v.clamp(-100.0, 0.0));
+        self.vad_speech_level_dbfs = self.vad_speech_level_dbfs.map(|v| v.clamp(-100.0, 0.0));
+        self.vad_pre_roll_ms = self.vad_pre_roll_ms.min(2000);
+        self.vad_post_roll_ms = self.vad_post_roll_ms.min(2000);
+        self.dictation_max_pause_ms = self.dictation_max_pause_ms.clamp(500, 60_000);
+        self.high_pass_cutoff_hz = self.high_pass_cutoff_hz.clamp(20.0, 300.0);
+        self.agc_target_dbfs = self.agc_target_dbfs.clamp(-40.0, -6.0);
+        self.agc_max_gain_db = self.agc_max_gain_db.clamp(0.0, 40.0);
+        self.monitor_volume = self.monitor_volume.clamp(0.0, 2.0);
+        self.remote_control_port = self.remote_control_port.max(1024);
         self.max_segment_secs = self.max_segment_secs.clamp(30, 1800);
+        self.diarization_max_speakers = self.diarization_max_speakers.clamp(1, 4);
+        self.diarization_onset = self.diarization_onset.map(|v| v.clamp(0.0, 1.0));
+        self.diarization_offset = self.diarization_offset.map(|v| v.clamp(0.0, 1.0));
+        self.speaker_match_threshold = self.speaker_match_threshold.clamp(0.5, 0.99);
+        self.transcription_cache_max_mb = self.transcription_cache_max_mb.min(100_000);
+        self.transcription_cache_ttl_days = self.transcription_cache_ttl_days.clamp(1, 3650);
+        self.recording_peak_dbfs = self.recording_peak_dbfs.clamp(-20.0, 0.0);
+        self.pre_record_secs = self.pre_record_secs.clamp(0.0, 10.0);
+        self.max_recording_minutes = self.max_recording_minutes.min(24 * 60);
+        self.conference_spool_minutes = self.conference_spool_minutes.min(24 * 60);
+        self.recording_target_lufs = self.recording_target_lufs.clamp(-40.0, -5.0);
+        for profile in self.vad_language_profiles.values_mut() {
+            profile.silence_threshold_ms = profile.silence_threshold_ms.map(|v| v.clamp(100, 10_000));
+            profile.min_speech_ms = profile.min_speech_ms.map(|v| v.min(5000));
+        }
+
+        // Recording names stay inside the recordings directory and use known placeholders
+        let template = &self.recording_filename_template;
+        let mut rest = template.as_str();
+        let mut placeholders_known = true;
+        while let Some(start) = rest.find('{') {
+            let end = rest[start..].find('}').map_or(rest.len(), |end| start + end + 1);
+            placeholders_known &= crate::infrastructure::recordings::FILENAME_PLACEHOLDERS.contains(&&rest[start..end]);
+            rest = &rest[end..];
+        }
+        if template.trim().is_empty() || template.contains(['/', '\\']) || !placeholders_known {
+            self.recording_filename_template = default_recording_filename_template();
+        }
+
+        if !crate::infrastructure::recordings::RecordingFormat::NAMES.contains(&self.recording_format.as_str()) {
+            self.recording_format = default_recording_format();
+        }
+
+        if !crate::recording::resample::ResamplerQuality::NAMES.contains(&self.resampler_quality.as_str()) {
+            self.resampler_quality = default_resampler_quality();
+        }
 
         // Validate recording_mode
-        if !["dictation", "conference", "conference_file"].contains(&self.recording_mode.as_str()) {
+        if !RECORDING_MODES.contains(&self.recording_mode.as_str()) {
             self.recording_mode = default_recording_mode();
         }
 
+        // Drop presets without a workspace and unknown preset modes
+        self.workspace_presets
+            .retain(|preset| !preset.workspace.trim().is_empty());
+        for preset in &mut self.workspace_presets {
+            if preset
+                .recording_mode
+                .as_deref()
+                .is_some_and(|mode| !RECORDING_MODES.contains(&mode))
+            {
+                preset.recording_mode = None;
+            }
+        }
+
         // Validate stt_backend
         if !["whisper", "tdt"].contains(&self.stt_backend.as_str()) {
             self.stt_backend = default_stt_backend();
         }
 
+        // Validate speaker_attribution
+        if !["words", "segments"].contains(&self.speaker_attribution.as_str()) {
+            self.speaker_attribution = default_speaker_attribution();
+        }
+
+        // Validate recording_normalization
+        if !["off", "peak", "lufs"].contains(&self.recording_normalization.as_str()) {
+            self.recording_normalization = default_recording_normalization();
+        }
+
+        // Validate history_backend
+        if !["json", "jsonl", "sqlite", "encrypted-json"].contains(&self.history_backend.as_str()) {
+            self.history_backend = default_history_backend();
+        }
+
+        // Validate denoise_backend
+        if !["rnnoise", "deepfilternet"].contains(&self.denoise_backend.as_str()) {
+            self.denoise_backend = default_denoise_backend();
+        }
+
         // Validate vad_engine
-        if !["webrtc", "silero"].contains(&self.vad_engine.as_str()) {
+        if !["webrtc", "silero", "earshot"].contains(&self.vad_engine.as_str()) {
             self.vad_engine = default_vad_engine();
         }
 
+        // Validate punctuation_restoration
+        if !["none", "rules"].contains(&self.punctuation_restoration.as_str()) {
+            self.punctuation_restoration = default_punctuation_restoration();
+        }
+
+        // Drop unknown upload modes and a blank server URL
+        self.webdav_modes
+            .retain(|m| crate::infrastructure::webdav::WEBDAV_MODES.contains(&m.as_str()));
+        if self.webdav_url.as_deref().is_some_and(|url| url.trim().is_empty()) {
+            self.webdav_url = None;
+        }
+
+        // A blank LLM endpoint means the rule-based action item detector
+        if self
+            .action_items_llm_url
+            .as_deref()
+            .is_some_and(|url| url.trim().is_empty())
+        {
+            self.action_items_llm_url = None;
+        }
+        if self.action_items_llm_model.trim().is_empty() {
+            self.action_items_llm_model = default_action_items_llm_model();
+        }
+
+        // An empty front template would produce notes Anki rejects
+        if self.anki_front_template.trim().is_empty() {
+            self.anki_front_template = default_anki_front_template();
+        }
+
         Ok(())
     }
+
+    /// Silence that ends a segment, for the current language.
+    pub fn effective_vad_silence_threshold_ms(&self) -> u32 {
+        self.vad_profile()
+            .and_then(|p| p.silence_threshold_ms)
+            .unwrap_or(self.vad_silence_threshold_ms)
+    }
+
+    /// Shortest speech that counts, for the current language.
+    pub fn effective_vad_min_speech_ms(&self) -> u32 {
+        self.vad_profile()
+            .and_then(|p| p.min_speech_ms)
+            .unwrap_or(self.vad_min_speech_ms)
+    }
+
+    /// First preset for one of the `workspace_names` the desktop reported.
+    pub fn workspace_preset(&self, workspace_names: &[String]) -> Option<&WorkspacePreset> {
+        self.workspace_presets
+            .iter()
+            .find(|preset| crate::infrastructure::workspace::matches_workspace(&preset.workspace, workspace_names))
+    }
+
+    fn vad_profile(&self) -> Option<&VadProfile> {
+        self.vad_language_profiles.get(&self.language)
+    }
 }
 
 pub fn config_dir() -> PathBuf {
@@ -195,6 +796,21 @@ pub fn config_path() -> PathBuf {
     config_dir().join("config.toml")
 }
 
+pub fn draft_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("voice-dictation")
+        .join("draft.txt")
+}
+
+pub fn corrections_path() -> PathBuf {
+    config_dir().join("corrections.toml")
+}
+
+pub fn templates_path() -> PathBuf {
+    config_dir().join("templates.toml")
+}
+
 pub fn models_dir() -> PathBuf {
     dirs::data_local_dir()
         .unwrap_or_else(|| PathBuf::from("."))
@@ -215,6 +831,54 @@ pub fn sortformer_models_dir() -> PathBuf {
         .join("sortformer")
 }
 
+pub fn speakers_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("voice-dictation")
+        .join("speakers.json")
+}
+
+pub fn transcription_cache_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("voice-dictation")
+        .join("transcription-cache")
+}
+
+/// Conference audio moved out of memory while recording; emptied as recordings finish.
+pub fn spool_dir() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("voice-dictation")
+        .join("spool")
+}
+
+pub fn webdav_outbox_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("voice-dictation")
+        .join("webdav-outbox")
+}
+
+/// Socket through which CLI commands reach the running app.
+pub fn ipc_socket_path() -> PathBuf {
+    dirs::runtime_dir()
+        .or_else(dirs::data_local_dir)
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("voice-dictation.sock")
+}
+
+pub fn remote_token_path() -> PathBuf {
+    config_dir().join("remote.token")
+}
+
+pub fn webdav_queue_path() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("voice-dictation")
+        .join("webdav-queue.json")
+}
+
 pub fn tdt_models_dir() -> PathBuf {
     dirs::data_local_dir()
         .unwrap_or_else(|| PathBuf::from("."))
@@ -222,11 +886,29 @@ pub fn tdt_models_dir() -> PathBuf {
         .join("tdt")
 }
 
+pub fn vad_models_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("voice-dictation")
+        .join("vad")
+}
+
+pub fn samples_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("voice-dictation")
+        .join("samples")
+}
+
 pub fn load_config() -> Result<Config> {
     let path = config_path();
 
     if !path.exists() {
-        return Ok(Config::default());
+        // First launch: the GUI shows the onboarding wizard
+        return Ok(Config {
+            onboarding_completed: false,
+            ..Config::default()
+        });
     }
 
     let content =
@@ -359,6 +1041,12 @@ mod tests {
         assert!(dir.to_string_lossy().contains("tdt"));
     }
 
+    #[test]
+    fn test_vad_models_dir_not_empty() {
+        let dir = vad_models_dir();
+        assert!(dir.to_string_lossy().contains("vad"));
+    }
+
     #[test]
     fn test_default_stt_backend() {
         let config = Config::default();
@@ -405,6 +1093,16 @@ mod tests {
         assert_eq!(config.segment_interval_secs, 300);
     }
 
+    #[test]
+    fn test_validate_clamps_segment_retry_attempts() {
+        let mut config = Config {
+            segment_retry_attempts: 100,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.segment_retry_attempts, 5);
+    }
+
     #[test]
     fn test_validate_clamps_history_max_entries() {
         let mut config = Config {
@@ -419,6 +1117,174 @@ mod tests {
         assert_eq!(config.history_max_entries, 10_000);
     }
 
+    #[test]
+    fn test_validate_rejects_privileged_remote_control_port() {
+        let mut config = Config {
+            remote_control_port: 80,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.remote_control_port, 1024);
+
+        config.remote_control_port = 9000;
+        config.validate().unwrap();
+        assert_eq!(config.remote_control_port, 9000);
+    }
+
+    #[test]
+    fn test_validate_clamps_high_pass_cutoff() {
+        let mut config = Config {
+            high_pass_cutoff_hz: 5.0,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.high_pass_cutoff_hz, 20.0);
+
+        config.high_pass_cutoff_hz = 1000.0;
+        config.validate().unwrap();
+        assert_eq!(config.high_pass_cutoff_hz, 300.0);
+    }
+
+    #[test]
+    fn test_validate_clamps_pre_record_secs() {
+        let mut config = Config {
+            pre_record_secs: -1.0,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.pre_record_secs, 0.0);
+
+        config.pre_record_secs = 60.0;
+        config.validate().unwrap();
+        assert_eq!(config.pre_record_secs, 10.0);
+    }
+
+    #[test]
+    fn test_validate_clamps_max_recording_minutes() {
+        let mut config = Config {
+            max_recording_minutes: 100_000,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.max_recording_minutes, 1440);
+
+        config.max_recording_minutes = 0;
+        config.validate().unwrap();
+        assert_eq!(config.max_recording_minutes, 0, "0 turns the limit off");
+    }
+
+    #[test]
+    fn test_validate_clamps_conference_spool_minutes() {
+        let mut config = Config {
+            conference_spool_minutes: 5000,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.conference_spool_minutes, 1440);
+    }
+
+    #[test]
+    fn test_validate_clamps_agc_levels() {
+        let mut config = Config {
+            agc_target_dbfs: 0.0,
+            agc_max_gain_db: 100.0,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.agc_target_dbfs, -6.0);
+        assert_eq!(config.agc_max_gain_db, 40.0);
+
+        config.agc_target_dbfs = -90.0;
+        config.agc_max_gain_db = -5.0;
+        config.validate().unwrap();
+        assert_eq!(config.agc_target_dbfs, -40.0);
+        assert_eq!(config.agc_max_gain_db, 0.0);
+    }
+
+    #[test]
+    fn test_validate_clamps_monitor_volume() {
+        let mut config = Config {
+            monitor_volume: 5.0,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.monitor_volume, 2.0);
+
+        config.monitor_volume = -1.0;
+        config.validate().unwrap();
+        assert_eq!(config.monitor_volume, 0.0);
+    }
+
+    #[test]
+    fn test_vad_profile_of_current_language() {
+        let mut config = Config {
+            vad_silence_threshold_ms: 800,
+            vad_min_speech_ms: 300,
+            ..Config::default()
+        };
+        config.vad_language_profiles.insert(
+            "uk".to_string(),
+            VadProfile {
+                silence_threshold_ms: Some(1500),
+                min_speech_ms: None,
+            },
+        );
+
+        config.language = "uk".to_string();
+        assert_eq!(config.effective_vad_silence_threshold_ms(), 1500);
+        assert_eq!(config.effective_vad_min_speech_ms(), 300);
+
+        config.language = "en".to_string();
+        assert_eq!(config.effective_vad_silence_threshold_ms(), 800);
+    }
+
+    #[test]
+    fn test_workspace_preset_lookup_and_validation() {
+        let preset = |workspace: &str, mode: Option<&str>| WorkspacePreset {
+            workspace: workspace.to_string(),
+            recording_mode: mode.map(str::to_string),
+            auto_paste: Some(false),
+            ..Default::default()
+        };
+        let mut config = Config {
+            workspace_presets: vec![
+                preset(" ", Some("conference")),
+                preset("Work", Some("conference")),
+                preset("3", Some("karaoke")),
+            ],
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.workspace_presets.len(), 2);
+        assert_eq!(config.workspace_presets[1].recording_mode, None);
+
+        let names = vec!["work".to_string(), "2".to_string()];
+        let found = config.workspace_preset(&names).unwrap();
+        assert_eq!(found.recording_mode.as_deref(), Some("conference"));
+        assert_eq!(found.auto_paste, Some(false));
+        assert!(config.workspace_preset(&["1".to_string()]).is_none());
+    }
+
+    #[test]
+    fn test_validate_clamps_vad_profiles() {
+        let mut config = Config::default();
+        config.vad_language_profiles.insert(
+            "uk".to_string(),
+            VadProfile {
+                silence_threshold_ms: Some(10),
+                min_speech_ms: Some(60_000),
+            },
+        );
+        config.validate().unwrap();
+        assert_eq!(
+            config.vad_language_profiles["uk"],
+            VadProfile {
+                silence_threshold_ms: Some(100),
+                min_speech_ms: Some(5000),
+            }
+        );
+    }
+
     #[test]
     fn test_validate_clamps_history_max_age_days() {
         let mut config = Config {
@@ -433,6 +1299,30 @@ mod tests {
         assert_eq!(config.history_max_age_days, 3650);
     }
 
+    #[test]
+    fn test_validate_clamps_history_trash_days() {
+        let mut config = Config {
+            history_trash_days: 0,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.history_trash_days, 1);
+
+        config.history_trash_days = 1000;
+        config.validate().unwrap();
+        assert_eq!(config.history_trash_days, 365);
+    }
+
+    #[test]
+    fn test_validate_caps_history_backups() {
+        let mut config = Config {
+            history_backups: 1000,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.history_backups, 50);
+    }
+
     #[test]
     fn test_validate_clamps_silero_threshold() {
         let mut config = Config {
@@ -447,6 +1337,84 @@ mod tests {
         assert_eq!(config.silero_threshold, 1.0);
     }
 
+    #[test]
+    fn test_validate_clamps_vad_aggressiveness() {
+        let mut config = Config {
+            vad_aggressiveness: 7,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.vad_aggressiveness, 3);
+
+        config.vad_aggressiveness = 0;
+        config.validate().unwrap();
+        assert_eq!(config.vad_aggressiveness, 0);
+    }
+
+    #[test]
+    fn test_validate_clamps_vad_calibration_levels() {
+        let mut config = Config {
+            vad_noise_floor_dbfs: Some(-140.0),
+            vad_speech_level_dbfs: Some(3.0),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.vad_noise_floor_dbfs, Some(-100.0));
+        assert_eq!(config.vad_speech_level_dbfs, Some(0.0));
+    }
+
+    #[test]
+    fn test_validate_clamps_vad_padding() {
+        let mut config = Config {
+            vad_pre_roll_ms: 10_000,
+            vad_post_roll_ms: 2500,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.vad_pre_roll_ms, 2000);
+        assert_eq!(config.vad_post_roll_ms, 2000);
+    }
+
+    #[test]
+    fn test_validate_clamps_dictation_max_pause() {
+        let mut config = Config {
+            dictation_max_pause_ms: 0,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.dictation_max_pause_ms, 500);
+    }
+
+    #[test]
+    fn test_validate_clamps_diarization_settings() {
+        let mut config = Config {
+            diarization_max_speakers: 0,
+            diarization_onset: Some(2.0),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.diarization_max_speakers, 1);
+        assert_eq!(config.diarization_onset, Some(1.0));
+
+        config.diarization_max_speakers = 10;
+        config.validate().unwrap();
+        assert_eq!(config.diarization_max_speakers, 4);
+    }
+
+    #[test]
+    fn test_validate_clamps_speaker_match_threshold() {
+        let mut config = Config {
+            speaker_match_threshold: 0.1,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.speaker_match_threshold, 0.5);
+
+        config.speaker_match_threshold = 1.5;
+        config.validate().unwrap();
+        assert_eq!(config.speaker_match_threshold, 0.99);
+    }
+
     #[test]
     fn test_validate_resets_invalid_recording_mode() {
         let mut config = Config {
@@ -467,6 +1435,86 @@ mod tests {
         assert_eq!(config.stt_backend, "whisper");
     }
 
+    #[test]
+    fn test_validate_resets_invalid_history_backend() {
+        let mut config = Config {
+            history_backend: "postgres".to_string(),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.history_backend, "json");
+
+        for backend in ["json", "jsonl", "sqlite", "encrypted-json"] {
+            let mut config = Config {
+                history_backend: backend.to_string(),
+                ..Config::default()
+            };
+            config.validate().unwrap();
+            assert_eq!(config.history_backend, backend);
+        }
+    }
+
+    #[test]
+    fn test_validate_resets_invalid_denoise_backend() {
+        let mut config = Config {
+            denoise_backend: "speexdsp".to_string(),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.denoise_backend, "rnnoise");
+
+        config.denoise_backend = "deepfilternet".to_string();
+        config.validate().unwrap();
+        assert_eq!(config.denoise_backend, "deepfilternet");
+    }
+
+    #[test]
+    fn test_validate_resets_invalid_recording_filename_template() {
+        for template in ["", "../{date}", "{date}_{user}", "{date"] {
+            let mut config = Config {
+                recording_filename_template: template.to_string(),
+                ..Config::default()
+            };
+            config.validate().unwrap();
+            assert_eq!(config.recording_filename_template, "{date}_{time}_{mode}_{title}.wav");
+        }
+
+        let mut config = Config {
+            recording_filename_template: "{title} {date}".to_string(),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.recording_filename_template, "{title} {date}");
+    }
+
+    #[test]
+    fn test_validate_resets_invalid_recording_format() {
+        let mut config = Config {
+            recording_format: "mp3".to_string(),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.recording_format, "wav");
+
+        config.recording_format = "opus".to_string();
+        config.validate().unwrap();
+        assert_eq!(config.recording_format, "opus");
+    }
+
+    #[test]
+    fn test_validate_resets_invalid_resampler_quality() {
+        let mut config = Config {
+            resampler_quality: "best".to_string(),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.resampler_quality, "high");
+
+        config.resampler_quality = "fast".to_string();
+        config.validate().unwrap();
+        assert_eq!(config.resampler_quality, "fast");
+    }
+
     #[test]
     fn test_validate_resets_invalid_vad_engine() {
         let mut config = Config {
@@ -477,6 +1525,98 @@ mod tests {
         assert_eq!(config.vad_engine, "webrtc");
     }
 
+    #[test]
+    fn test_validate_resets_invalid_speaker_attribution() {
+        let mut config = Config {
+            speaker_attribution: "tokens".to_string(),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.speaker_attribution, "words");
+    }
+
+    #[test]
+    fn test_validate_recording_normalization() {
+        let mut config = Config {
+            recording_normalization: "rms".to_string(),
+            recording_peak_dbfs: 3.0,
+            recording_target_lufs: -70.0,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.recording_normalization, "off");
+        assert_eq!(config.recording_peak_dbfs, 0.0);
+        assert_eq!(config.recording_target_lufs, -40.0);
+    }
+
+    #[test]
+    fn test_validate_resets_invalid_punctuation_restoration() {
+        let mut config = Config {
+            punctuation_restoration: "neural".to_string(),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.punctuation_restoration, "none");
+    }
+
+    #[test]
+    fn test_validate_filters_webdav_settings() {
+        let mut config = Config {
+            webdav_url: Some("  ".to_string()),
+            webdav_modes: vec!["dictation".to_string(), "meeting".to_string()],
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.webdav_url, None);
+        assert_eq!(config.webdav_modes, vec!["dictation"]);
+    }
+
+    #[test]
+    fn test_validate_resets_empty_anki_front_template() {
+        let mut config = Config {
+            anki_front_template: "  ".to_string(),
+            anki_back_template: String::new(),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.anki_front_template, "{term}");
+        assert_eq!(config.anki_back_template, "");
+    }
+
+    #[test]
+    fn test_validate_clamps_transcription_cache_settings() {
+        let mut config = Config {
+            transcription_cache_max_mb: 0,
+            transcription_cache_ttl_days: 0,
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.transcription_cache_max_mb, 0);
+        assert_eq!(config.transcription_cache_ttl_days, 1);
+
+        config.transcription_cache_max_mb = u64::MAX;
+        config.validate().unwrap();
+        assert_eq!(config.transcription_cache_max_mb, 100_000);
+    }
+
+    #[test]
+    fn test_validate_resets_blank_action_items_llm_settings() {
+        let mut config = Config {
+            action_items_llm_url: Some(" ".to_string()),
+            action_items_llm_model: String::new(),
+            ..Config::default()
+        };
+        config.validate().unwrap();
+        assert_eq!(config.action_items_llm_url, None);
+        assert_eq!(config.action_items_llm_model, "llama3.1");
+    }
+
+    #[test]
+    fn test_config_without_onboarding_key_skips_wizard() {
+        let parsed: Config = toml::from_str("default_model = \"ggml-base.bin\"\nlanguage = \"uk\"\n").unwrap();
+        assert!(parsed.onboarding_completed);
+    }
+
     #[test]
     fn test_validate_accepts_valid_enum_values() {
         for mode in ["dictation", "conference", "conference_file"] {
@@ -497,7 +1637,7 @@ mod tests {
             assert_eq!(config.stt_backend, backend);
         }
 
-        for engine in ["webrtc", "silero"] {
+        for engine in ["webrtc", "silero", "earshot"] {
             let mut config = Config {
                 vad_engine: engine.to_string(),
                 ..Config::default()
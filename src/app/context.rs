@@ -5,14 +5,20 @@
 //! main.rs, ui/, whisper.rs, and config.rs.
 
 use crate::app::channels::UIChannels;
-use crate::app::config::Config;
+use crate::app::config::{Config, WorkspacePreset};
 use crate::domain::traits::{ConfigProvider, Transcription};
 use crate::history::History;
+use crate::infrastructure::models::resolve_silero_model_path;
+use crate::infrastructure::remote::SharedRemoteStatus;
+use crate::infrastructure::workspace::current_workspace_names;
+use crate::recording::denoise::DenoiseSettings;
+use crate::recording::file_source::{FileAudioSource, AUDIO_SOURCE_ENV};
+use crate::recording::microphone::CaptureProcessing;
 use crate::recording::segmentation::SegmentationConfig;
 use crate::recording::service::AudioService;
 use crate::transcription::diarization::DiarizationEngine;
 use crate::transcription::TranscriptionService;
-use crate::vad::VadEngine;
+use crate::vad::{SilenceTrim, VadConfig, VadEngine};
 use anyhow::Result;
 use parking_lot::Mutex;
 use std::sync::Arc;
@@ -47,12 +53,19 @@ pub struct AppContext {
 
     /// UI communication channels (tray, hotkeys, dialogs)
     pub channels: Arc<UIChannels>,
+
+    /// State shown by the phone remote control
+    pub remote: SharedRemoteStatus,
+
+    /// Preset of the workspace the current or last recording started on
+    pub workspace_preset: Mutex<Option<WorkspacePreset>>,
 }
 
 impl AppContext {
     /// Create a new `AppContext` with all services.
     ///
-    /// The `AudioService` is created internally using configuration from `config`.
+    /// The `AudioService` is created internally using configuration from `config`,
+    /// with the microphone replaced by a file if [`AUDIO_SOURCE_ENV`] names one.
     pub fn new(
         config: Arc<Mutex<Config>>,
         history: Arc<Mutex<History>>,
@@ -64,15 +77,36 @@ impl AppContext {
             SegmentationConfig {
                 use_vad: cfg.use_vad,
                 segment_interval_secs: cfg.segment_interval_secs,
-                vad_silence_threshold_ms: cfg.vad_silence_threshold_ms,
-                vad_min_speech_ms: cfg.vad_min_speech_ms,
+                vad_silence_threshold_ms: cfg.effective_vad_silence_threshold_ms(),
+                vad_min_speech_ms: cfg.effective_vad_min_speech_ms(),
                 vad_engine: VadEngine::parse(&cfg.vad_engine),
                 silero_threshold: cfg.silero_threshold,
+                silero_model_path: resolve_silero_model_path(cfg.silero_model_path.as_deref()),
+                vad_aggressiveness: cfg.vad_aggressiveness,
                 max_segment_secs: cfg.max_segment_secs,
+                adaptive_noise_floor: cfg.vad_adaptive_noise_floor,
+                initial_noise_floor_dbfs: cfg.vad_noise_floor_dbfs,
+                pre_roll_ms: cfg.vad_pre_roll_ms,
+                post_roll_ms: cfg.vad_post_roll_ms,
+                speech_gated: cfg.speech_gated_recording,
             }
         };
 
-        let audio = AudioService::new(seg_config).unwrap_or_else(|_| AudioService::new_default());
+        let processing = CaptureProcessing::from_config(&config.lock());
+        let spool_after_minutes = config.lock().conference_spool_minutes;
+        let audio = match FileAudioSource::from_env() {
+            Some(Ok(source)) => {
+                eprintln!("Замість мікрофона записується файл з {}", AUDIO_SOURCE_ENV);
+                AudioService::with_file_source(source, seg_config, processing, spool_after_minutes)
+            }
+            file_source => {
+                if let Some(Err(e)) = file_source {
+                    eprintln!("{:#}; записується мікрофон", e);
+                }
+                AudioService::new(seg_config, processing, spool_after_minutes)
+                    .unwrap_or_else(|_| AudioService::new_default())
+            }
+        };
 
         Ok(Self {
             audio: Arc::new(audio),
@@ -81,6 +115,8 @@ impl AppContext {
             history,
             diarization: Arc::new(Mutex::new(diarization)),
             channels: Arc::new(UIChannels::new()),
+            remote: SharedRemoteStatus::default(),
+            workspace_preset: Mutex::new(None),
         })
     }
 
@@ -89,6 +125,9 @@ impl AppContext {
 
     /// Get current language setting
     pub fn language(&self) -> String {
+        if let Some(language) = self.workspace_preset.lock().as_ref().and_then(|p| p.language.clone()) {
+            return language;
+        }
         ConfigProvider::language(&*self.config.lock())
     }
 
@@ -99,22 +138,115 @@ impl AppContext {
 
     /// Check if auto-copy is enabled
     pub fn auto_copy(&self) -> bool {
+        if let Some(auto_copy) = self.workspace_preset.lock().as_ref().and_then(|p| p.auto_copy) {
+            return auto_copy;
+        }
         ConfigProvider::auto_copy(&*self.config.lock())
     }
 
     /// Check if auto-paste is enabled
     pub fn auto_paste(&self) -> bool {
+        if let Some(auto_paste) = self.workspace_preset.lock().as_ref().and_then(|p| p.auto_paste) {
+            return auto_paste;
+        }
         ConfigProvider::auto_paste(&*self.config.lock())
     }
 
+    /// Make the preset of the active workspace current, or none if no preset
+    /// matches. Called when recording starts.
+    pub fn select_workspace_preset(&self) -> Option<WorkspacePreset> {
+        let enabled = {
+            let cfg = self.config.lock();
+            cfg.workspace_presets_enabled && !cfg.workspace_presets.is_empty()
+        };
+        // Detection runs helper programs; the config stays unlocked meanwhile
+        let preset = if enabled {
+            let names = current_workspace_names();
+            self.config.lock().workspace_preset(&names).cloned()
+        } else {
+            None
+        };
+        *self.workspace_preset.lock() = preset.clone();
+        preset
+    }
+
     /// Get diarization method
     pub fn diarization_method(&self) -> String {
         self.config.lock().diarization_method.clone()
     }
 
-    /// Check if denoising is enabled
-    pub fn denoise_enabled(&self) -> bool {
-        self.config.lock().denoise_enabled
+    /// Check if conference recordings should be diarized while recording
+    pub fn live_diarization(&self) -> bool {
+        let cfg = self.config.lock();
+        cfg.live_diarization && cfg.diarization_method == "sortformer"
+    }
+
+    /// Check if microphone recordings should be labelled by speaker
+    pub fn mic_diarization(&self) -> bool {
+        self.config.lock().mic_diarization
+    }
+
+    /// Monitor source captured in conference mode; `None` for the default one
+    pub fn loopback_source(&self) -> Option<String> {
+        self.config.lock().loopback_source.clone()
+    }
+
+    /// Check if dictation audio should be kept with its history entry
+    pub fn save_dictation_recordings(&self) -> bool {
+        let cfg = self.config.lock();
+        cfg.save_recordings && !cfg.read_only_history
+    }
+
+    /// Check if the loopback's echo should be removed from the microphone
+    pub fn echo_cancellation(&self) -> bool {
+        self.config.lock().echo_cancellation
+    }
+
+    /// Denoiser to run on recordings, `None` when denoising is off
+    pub fn denoise_settings(&self) -> Option<DenoiseSettings> {
+        DenoiseSettings::if_enabled(&self.config.lock())
+    }
+
+    /// Kiosk mode: nothing is added to or removed from history, recordings are not kept
+    pub fn read_only_history(&self) -> bool {
+        self.config.lock().read_only_history
+    }
+
+    /// Silence trimming for plain dictation, if enabled
+    pub fn dictation_silence_trim(&self) -> Option<SilenceTrim> {
+        let cfg = self.config.lock();
+        cfg.trim_dictation_silence.then(|| SilenceTrim {
+            pre_roll_ms: cfg.vad_pre_roll_ms,
+            post_roll_ms: cfg.vad_post_roll_ms,
+            max_pause_ms: cfg.dictation_max_pause_ms,
+        })
+    }
+
+    /// VAD settings for checks outside the recording pipeline
+    pub fn vad_config(&self) -> VadConfig {
+        let cfg = self.config.lock();
+        VadConfig {
+            engine: VadEngine::parse(&cfg.vad_engine),
+            silence_threshold_ms: cfg.effective_vad_silence_threshold_ms(),
+            min_speech_ms: cfg.effective_vad_min_speech_ms(),
+            silero_threshold: cfg.silero_threshold,
+            webrtc_aggressiveness: cfg.vad_aggressiveness,
+            silero_model_path: resolve_silero_model_path(cfg.silero_model_path.as_deref()),
+        }
+    }
+
+    pub fn phonetic_hints(&self) -> bool {
+        self.config.lock().phonetic_hints
+    }
+
+    /// Check if live translated captions are enabled for continuous mode
+    pub fn translation_captions(&self) -> bool {
+        self.config.lock().translation_captions
+    }
+
+    /// Check if a start requested during model loading should run once it is loaded
+    pub fn start_after_model_load(&self) -> bool {
+        self.config.lock().start_after_model_load
     }
 
     // === Transcription convenience methods ===
@@ -143,6 +275,8 @@ impl AppContext {
             history,
             diarization: Arc::new(Mutex::new(DiarizationEngine::default())),
             channels: Arc::new(UIChannels::new()),
+            remote: SharedRemoteStatus::default(),
+            workspace_preset: Mutex::new(None),
         }
     }
 }
@@ -190,10 +324,42 @@ mod tests {
         assert!(ctx.auto_copy());
         assert!(!ctx.auto_paste());
         assert!(ctx.continuous_mode());
-        assert!(ctx.denoise_enabled());
+        assert!(ctx.denoise_settings().is_some());
         assert_eq!(ctx.diarization_method(), "sortformer");
     }
 
+    #[test]
+    fn test_workspace_preset_overrides_config() {
+        let cfg = Config {
+            auto_copy: true,
+            auto_paste: true,
+            ..Config::default()
+        };
+        let config = Arc::new(Mutex::new(cfg));
+        let history = Arc::new(Mutex::new(History::default()));
+        let mock = Arc::new(MockAudioRecorder::new());
+        let samples = mock.samples_buffer();
+        let audio = Arc::new(AudioService::with_recorder(mock, samples, None).unwrap());
+        let transcription = Arc::new(Mutex::new(TranscriptionService::new()));
+
+        let ctx = AppContext::for_testing(config, history, audio, transcription);
+        *ctx.workspace_preset.lock() = Some(WorkspacePreset {
+            workspace: "Робота".to_string(),
+            auto_paste: Some(false),
+            language: Some("en".to_string()),
+            ..Default::default()
+        });
+
+        assert!(ctx.auto_copy());
+        assert!(!ctx.auto_paste());
+        assert_eq!(ctx.language(), "en");
+
+        // Presets are off by default, so starting a recording clears it
+        assert!(ctx.select_workspace_preset().is_none());
+        assert!(ctx.auto_paste());
+        assert_eq!(ctx.language(), "uk");
+    }
+
     #[test]
     fn test_for_testing_channels_work() {
         let config = Arc::new(Mutex::new(Config::default()));
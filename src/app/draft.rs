@@ -0,0 +1,77 @@
+//! Unsaved edits of the result text.
+//!
+//! Corrections typed into the result view live only in the widget until the
+//! user copies them. When the window is closed or the app quits with such
+//! edits, the text is kept in `draft.txt` and put back into the result view
+//! the next time the window is built.
+
+use crate::app::config::set_owner_only_permissions;
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+/// Keep `text` as the draft; blank text removes it.
+pub fn save_draft(path: &Path, text: &str) -> Result<()> {
+    if text.trim().is_empty() {
+        return clear_draft(path);
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("Не вдалося створити директорію: {}", parent.display()))?;
+    }
+    fs::write(path, text).with_context(|| format!("Не вдалося записати чернетку: {}", path.display()))?;
+    // Transcripts are as private as the history
+    set_owner_only_permissions(path)
+}
+
+/// The saved draft, if there is one.
+pub fn load_draft(path: &Path) -> Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let text =
+        fs::read_to_string(path).with_context(|| format!("Не вдалося прочитати чернетку: {}", path.display()))?;
+    Ok(Some(text).filter(|text| !text.trim().is_empty()))
+}
+
+pub fn clear_draft(path: &Path) -> Result<()> {
+    if path.exists() {
+        fs::remove_file(path).with_context(|| format!("Не вдалося видалити чернетку: {}", path.display()))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn draft_file(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("s2t-draft-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir.join("draft.txt")
+    }
+
+    #[test]
+    fn test_draft_roundtrip() {
+        let path = draft_file("roundtrip");
+        assert_eq!(load_draft(&path).unwrap(), None);
+
+        save_draft(&path, "Виправлений текст\nз двома рядками").unwrap();
+        assert_eq!(
+            load_draft(&path).unwrap().as_deref(),
+            Some("Виправлений текст\nз двома рядками")
+        );
+
+        clear_draft(&path).unwrap();
+        assert_eq!(load_draft(&path).unwrap(), None);
+        clear_draft(&path).unwrap();
+    }
+
+    #[test]
+    fn test_blank_draft_removes_saved_one() {
+        let path = draft_file("blank");
+        save_draft(&path, "Текст").unwrap();
+        save_draft(&path, "  \n").unwrap();
+        assert!(!path.exists());
+    }
+}
@@ -1,3 +1,5 @@
 pub mod channels;
 pub mod config;
 pub mod context;
+pub mod draft;
+pub mod templates;
@@ -0,0 +1,219 @@
+//! Session templates for recurring meetings.
+//!
+//! Templates live in `templates.toml` next to the config. One picked before a
+//! conference recording prefills its history entry: the title, tags and the
+//! expected participants, whose names replace the generic "Спікер N" labels
+//! in the order the speakers first talk. A template can also set the
+//! recognition language for the session.
+//!
+//! ```toml
+//! [[template]]
+//! name = "Щотижневий синк"
+//! title = "Синк {date}"
+//! tags = ["синк", "команда"]
+//! participants = ["Андрій", "Олена", "Марко"]
+//! language = "uk"
+//! ```
+
+use crate::domain::types::{rename_speaker_labels, HistoryEntry};
+use crate::transcription::service::ConferenceTranscript;
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Placeholder in a template title replaced with the recording date.
+const DATE_PLACEHOLDER: &str = "{date}";
+
+/// Label prefix Sortformer gives speakers without a known name.
+const GENERIC_SPEAKER_PREFIX: &str = "Спікер ";
+
+/// A reusable preset for a recurring meeting.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionTemplate {
+    /// Name shown in the template picker.
+    pub name: String,
+    /// Title of the history entry; `{date}` becomes the recording date.
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Expected participants, in the order they usually speak first.
+    #[serde(default)]
+    pub participants: Vec<String>,
+    /// Recognition language for the session instead of the configured one.
+    #[serde(default)]
+    pub language: Option<String>,
+}
+
+/// All user templates (`templates.toml`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SessionTemplates {
+    #[serde(default, rename = "template")]
+    pub templates: Vec<SessionTemplate>,
+}
+
+impl SessionTemplates {
+    /// Load templates from a TOML file. A missing file yields no templates.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Не вдалося прочитати шаблони сесій: {}", path.display()))?;
+        toml::from_str(&content).context("Не вдалося розпарсити шаблони сесій")
+    }
+
+    /// Load templates from the default location, logging errors.
+    pub fn load_default() -> Self {
+        Self::load(&crate::app::config::templates_path()).unwrap_or_else(|e| {
+            eprintln!("Помилка завантаження шаблонів сесій: {}", e);
+            Self::default()
+        })
+    }
+
+    pub fn find(&self, name: &str) -> Option<&SessionTemplate> {
+        self.templates.iter().find(|t| t.name == name)
+    }
+}
+
+impl SessionTemplate {
+    /// Entry title for a recording made on `date`.
+    pub fn title_for(&self, date: NaiveDate) -> Option<String> {
+        let title = self.title.as_deref()?.trim();
+        (!title.is_empty()).then(|| title.replace(DATE_PLACEHOLDER, &date.format("%Y-%m-%d").to_string()))
+    }
+
+    /// Renames of generic speaker labels to the expected participants.
+    ///
+    /// `speakers` are in order of first appearance. Participants already
+    /// recognized by name (speaker re-identification) are not assigned twice;
+    /// speakers beyond the participant list keep their labels.
+    pub fn speaker_renames(&self, speakers: &[String]) -> Vec<(String, String)> {
+        let mut available = self
+            .participants
+            .iter()
+            .filter(|p| !p.trim().is_empty() && !speakers.contains(p));
+        speakers
+            .iter()
+            .filter(|s| is_generic_speaker(s))
+            .map_while(|s| available.next().map(|p| (s.clone(), p.clone())))
+            .collect()
+    }
+
+    /// Name the speakers of a diarized transcript after the participants.
+    ///
+    /// Renames the labels in the text and the speakers of the turns and segments.
+    pub fn apply_participants(&self, transcript: &mut ConferenceTranscript) {
+        let mut speakers: Vec<String> = Vec::new();
+        for turn in &transcript.turns {
+            if !speakers.contains(&turn.speaker) {
+                speakers.push(turn.speaker.clone());
+            }
+        }
+        let renames = self.speaker_renames(&speakers);
+        let rename = |speaker: &mut String| {
+            if let Some((_, name)) = renames.iter().find(|(old, _)| *old == *speaker) {
+                *speaker = name.clone();
+            }
+        };
+        transcript.turns.iter_mut().for_each(|turn| rename(&mut turn.speaker));
+        transcript
+            .segments
+            .iter_mut()
+            .for_each(|segment| rename(&mut segment.speaker));
+        transcript.text = rename_speaker_labels(&transcript.text, &renames);
+    }
+
+    /// Set the title and add the tags of the template to `entry`.
+    pub fn apply_metadata(&self, entry: &mut HistoryEntry) {
+        let date = entry.timestamp.with_timezone(&chrono::Local).date_naive();
+        if let Some(title) = self.title_for(date) {
+            entry.title = Some(title);
+        }
+        for tag in &self.tags {
+            if !entry.tags.contains(tag) {
+                entry.tags.push(tag.clone());
+            }
+        }
+    }
+}
+
+/// Whether `label` is an unnamed speaker ("Спікер 2").
+fn is_generic_speaker(label: &str) -> bool {
+    label
+        .strip_prefix(GENERIC_SPEAKER_PREFIX)
+        .is_some_and(|n| n.parse::<usize>().is_ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template() -> SessionTemplate {
+        SessionTemplate {
+            name: "Щотижневий синк".to_string(),
+            title: Some("Синк {date}".to_string()),
+            tags: vec!["синк".to_string()],
+            participants: vec!["Андрій".to_string(), "Олена".to_string()],
+            language: None,
+        }
+    }
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_speaker_renames_map_generic_labels_in_order() {
+        let renames = template().speaker_renames(&labels(&["Спікер 2", "Спікер 1", "Спікер 3"]));
+        assert_eq!(
+            renames,
+            vec![
+                ("Спікер 2".to_string(), "Андрій".to_string()),
+                ("Спікер 1".to_string(), "Олена".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_speaker_renames_skip_known_names() {
+        let renames = template().speaker_renames(&labels(&["Андрій", "Спікер 2", "Ви"]));
+        assert_eq!(renames, vec![("Спікер 2".to_string(), "Олена".to_string())]);
+    }
+
+    #[test]
+    fn test_apply_metadata_and_title() {
+        let date = NaiveDate::from_ymd_opt(2026, 3, 9).unwrap();
+        assert_eq!(template().title_for(date).as_deref(), Some("Синк 2026-03-09"));
+
+        let mut entry = HistoryEntry::new("Текст".to_string(), 60.0, "uk".to_string());
+        entry.tags.push("синк".to_string());
+        template().apply_metadata(&mut entry);
+        assert!(entry.title.as_deref().unwrap().starts_with("Синк "));
+        assert_eq!(entry.tags, vec!["синк"]);
+    }
+
+    #[test]
+    fn test_load_templates() {
+        let templates: SessionTemplates = toml::from_str(
+            r#"
+            [[template]]
+            name = "Щотижневий синк"
+            title = "Синк {date}"
+            tags = ["синк"]
+            participants = ["Андрій", "Олена"]
+
+            [[template]]
+            name = "Інтерв'ю"
+            language = "en"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(templates.templates.len(), 2);
+        assert_eq!(templates.find("Щотижневий синк"), Some(&template()));
+        assert_eq!(templates.find("Інтерв'ю").unwrap().language.as_deref(), Some("en"));
+        assert!(templates.find("Інше").is_none());
+    }
+}
@@ -0,0 +1,51 @@
+//! Subtitle re-alignment CLI command.
+//!
+//! Re-times an existing SRT file by the audio it belongs to: the audio is
+//! transcribed with word timestamps and each cue takes the times of its
+//! words. The cue text is left as it is; fixing it is done in the GUI.
+
+use crate::cli::args::{AlignSrtArgs, ChannelMode};
+use crate::cli::transcribe::{load_config_from, resolve_whisper_model};
+use crate::cli::wav_reader::{prepare_for_whisper, read_audio};
+use crate::recording::denoise::DenoiseSettings;
+use crate::transcription::subtitles::{align_cues, format_srt, parse_srt};
+use crate::transcription::word_attribution::{transcribe_words_windowed, DEFAULT_WORD_WINDOW_SECS};
+use crate::transcription::TranscriptionService;
+use anyhow::{Context, Result};
+use std::fs;
+use std::io::{self, Write};
+
+/// Run the align-srt command.
+pub fn run(args: AlignSrtArgs) -> Result<()> {
+    let config = load_config_from(args.config.as_deref())?;
+    let language = args.language.clone().unwrap_or_else(|| config.language.clone());
+
+    let content = fs::read_to_string(&args.subtitles)
+        .with_context(|| format!("Failed to read subtitles: {}", args.subtitles.display()))?;
+    let cues = parse_srt(&content)?;
+
+    eprintln!("Reading: {}", args.input.display());
+    let audio = read_audio(&args.input)?;
+    let prepared = prepare_for_whisper(&audio, ChannelMode::Mix, DenoiseSettings::if_enabled(&config).as_ref())?;
+
+    let model_path = resolve_whisper_model(args.model.as_deref(), &config)?;
+    eprintln!("Loading Whisper model: {}", model_path.display());
+    let service = TranscriptionService::with_model(&model_path.to_string_lossy())?;
+
+    eprintln!("Aligning {} cues...", cues.len());
+    let words = transcribe_words_windowed(&service, &prepared.samples, &language, DEFAULT_WORD_WINDOW_SECS * 16000)?;
+    let alignment = align_cues(&cues, &words);
+    eprintln!("Aligned {} of {} cues", alignment.aligned, cues.len());
+
+    let output_text = format_srt(&alignment.cues);
+    if let Some(ref output_path) = args.output {
+        fs::write(output_path, &output_text)
+            .with_context(|| format!("Failed to write output: {}", output_path.display()))?;
+        eprintln!("Output written to: {}", output_path.display());
+    } else {
+        let stdout = io::stdout();
+        let mut handle = stdout.lock();
+        write!(handle, "{}", output_text)?;
+    }
+    Ok(())
+}
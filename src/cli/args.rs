@@ -1,6 +1,7 @@
 //! CLI argument definitions using clap.
 
-use clap::{Parser, Subcommand, ValueEnum};
+use crate::vad::VadEngine;
+use clap::{Args, Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 /// STT backend selection.
@@ -13,6 +14,24 @@ pub enum SttBackend {
     Tdt,
 }
 
+/// VAD engine selection for `vad-eval`.
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum VadEngineArg {
+    Webrtc,
+    Silero,
+    Earshot,
+}
+
+impl VadEngineArg {
+    pub fn engine(self) -> VadEngine {
+        match self {
+            Self::Webrtc => VadEngine::WebRTC,
+            Self::Silero => VadEngine::Silero,
+            Self::Earshot => VadEngine::Earshot,
+        }
+    }
+}
+
 /// Diarization method selection.
 #[derive(Clone, Copy, ValueEnum, Default, Debug)]
 pub enum DiarizationMethod {
@@ -25,6 +44,37 @@ pub enum DiarizationMethod {
     Sortformer,
 }
 
+/// How text is split between Sortformer speakers.
+#[derive(Clone, Copy, ValueEnum, Debug)]
+pub enum SpeakerAttributionMode {
+    /// Transcribe once with word timestamps and assign words by overlap
+    Words,
+    /// Transcribe each speaker segment separately
+    Segments,
+}
+
+/// Recording mode to open the GUI in.
+#[derive(Clone, Copy, ValueEnum, Debug, PartialEq)]
+pub enum LaunchMode {
+    /// Microphone dictation
+    Dictation,
+    /// Microphone + system audio with diarization
+    Conference,
+    /// Microphone + system audio recorded to a file only
+    ConferenceFile,
+}
+
+impl LaunchMode {
+    /// Name used for `recording_mode` in the config.
+    pub fn config_name(self) -> &'static str {
+        match self {
+            Self::Dictation => "dictation",
+            Self::Conference => "conference",
+            Self::ConferenceFile => "conference_file",
+        }
+    }
+}
+
 /// Voice Dictation - Offline speech-to-text for Linux
 #[derive(Parser)]
 #[command(name = "voice-dictation")]
@@ -33,21 +83,170 @@ pub enum DiarizationMethod {
 pub struct Cli {
     #[command(subcommand)]
     pub command: Option<Commands>,
+
+    /// Open the GUI in this recording mode (forwarded to the running instance, if any)
+    #[arg(long, value_enum)]
+    pub mode: Option<LaunchMode>,
+
+    /// Start recording right away, once the model is loaded
+    #[arg(long)]
+    pub start: bool,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
-    /// Transcribe a WAV file to text
+    /// Transcribe an audio file (WAV, MP3, OGG, FLAC, M4A) to text
     Transcribe(TranscribeArgs),
-    /// List available and downloaded models
-    Models,
-    /// Evaluate denoiser effectiveness on a WAV file
+    /// Record from the microphone without the GUI, optionally transcribing it
+    Record(RecordArgs),
+    /// Find who speaks when in an audio file (Sortformer only, no Whisper model needed)
+    Diarize(DiarizeArgs),
+    /// List available and downloaded models, or add custom ones
+    Models(ModelsArgs),
+    /// Evaluate denoiser effectiveness on an audio file
     DenoiseEval(DenoiseEvalArgs),
+    /// Run a VAD engine over a WAV file or dataset directory and score it against reference labels
+    VadEval(VadEvalArgs),
+    /// Measure pipeline performance and compare against a baseline
+    Bench(BenchArgs),
+    /// Compare all downloaded models on an audio file (RTF, memory, WER/CER)
+    Benchmark(BenchmarkArgs),
+    /// Run Rhai automation scripts against history and transcription
+    Script(ScriptArgs),
+    /// Manage the dictation history storage
+    History(HistoryArgs),
+    /// Show usage statistics from the history (time, words per day, languages, models, RTF)
+    Stats(StatsArgs),
+    /// Re-time an SRT file by its audio (word timestamps of the transcript)
+    AlignSrt(AlignSrtArgs),
+    /// Show hardware, build and the running app's failure counters
+    Doctor,
+}
+
+/// History storage backend.
+#[derive(Clone, Copy, ValueEnum, Debug, PartialEq)]
+pub enum HistoryFormat {
+    /// One JSON file (default)
+    Json,
+    /// One JSON object per line
+    Jsonl,
+    /// SQLite database
+    Sqlite,
+    /// Encrypted JSON file
+    EncryptedJson,
+}
+
+impl HistoryFormat {
+    /// Name used for `history_backend` in the config.
+    pub fn config_name(self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Jsonl => "jsonl",
+            Self::Sqlite => "sqlite",
+            Self::EncryptedJson => "encrypted-json",
+        }
+    }
+}
+
+#[derive(Parser)]
+pub struct HistoryArgs {
+    #[command(subcommand)]
+    pub command: HistoryCommand,
+}
+
+#[derive(Subcommand)]
+pub enum HistoryCommand {
+    /// Copy the history to another storage backend and switch to it
+    Convert {
+        /// Backend to convert to
+        #[arg(long, value_enum)]
+        to: HistoryFormat,
+
+        /// Backend to convert from (defaults to the configured one)
+        #[arg(long, value_enum)]
+        from: Option<HistoryFormat>,
+    },
+    /// Export the history as text, Markdown, JSON or CSV
+    Export {
+        /// Export format
+        #[arg(short, long, value_enum, default_value_t = HistoryExportFormat::Text)]
+        format: HistoryExportFormat,
+
+        /// Output file (stdout if omitted)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Merge another history file (any backend or a JSON export) into the history,
+    /// skipping entries already in it
+    Import {
+        /// History file, e.g. from another machine or a backup
+        file: PathBuf,
+    },
+}
+
+/// History export format.
+#[derive(Clone, Copy, ValueEnum, Debug, PartialEq, Default)]
+pub enum HistoryExportFormat {
+    /// Plain text (default)
+    #[default]
+    Text,
+    /// Markdown, one heading per entry
+    Markdown,
+    /// JSON array with every field of the entries
+    Json,
+    /// CSV, one row per entry
+    Csv,
+}
+
+#[derive(Parser)]
+pub struct StatsArgs {
+    /// Only count entries from the last N days
+    #[arg(long, value_name = "N")]
+    pub days: Option<u32>,
+
+    /// Print the statistics as JSON
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Parser)]
+pub struct ModelsArgs {
+    #[command(subcommand)]
+    pub command: Option<ModelsCommand>,
+}
+
+#[derive(Subcommand)]
+pub enum ModelsCommand {
+    /// List available and downloaded models (default)
+    List,
+    /// Import a local GGML/GGUF model file into the models directory
+    Import {
+        /// Path to the model file (.bin or .gguf)
+        path: PathBuf,
+
+        /// Display name (defaults to the file name)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Download a GGML/GGUF model from a URL
+    Add {
+        /// HTTP(S) URL of the model file
+        url: String,
+
+        /// Display name (defaults to the file name)
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Remove a custom model from the list, deleting its file
+    Remove {
+        /// File name of the model, as shown by `models list`
+        filename: String,
+    },
 }
 
 #[derive(Parser)]
 pub struct TranscribeArgs {
-    /// Path to WAV file to transcribe
+    /// Path to audio file to transcribe (WAV, MP3, OGG, FLAC, M4A)
     pub input: PathBuf,
 
     /// Output file (stdout if omitted)
@@ -82,9 +281,12 @@ pub struct TranscribeArgs {
     #[arg(long, value_enum, default_value_t = DiarizationMethod::None)]
     pub diarization: DiarizationMethod,
 
-    /// Path to Sortformer model (optional, uses default location if not specified)
-    #[arg(long)]
-    pub sortformer_model: Option<PathBuf>,
+    #[command(flatten)]
+    pub sortformer: SortformerArgs,
+
+    /// How text is split between Sortformer speakers (default from config)
+    #[arg(long, value_enum)]
+    pub speaker_attribution: Option<SpeakerAttributionMode>,
 
     /// Path to TDT model directory (optional, uses default location if not specified)
     #[arg(long)]
@@ -98,6 +300,10 @@ pub struct TranscribeArgs {
     #[arg(long)]
     pub denoise: bool,
 
+    /// Keep hesitations and cut-off words, marked up as [uh] and wo-
+    #[arg(long)]
+    pub verbatim: bool,
+
     /// Maximum segment length in seconds for chunked processing (default: 300)
     #[arg(long, default_value_t = 300)]
     pub max_segment_secs: u32,
@@ -105,9 +311,125 @@ pub struct TranscribeArgs {
     /// Disable audio chunking (process entire file at once)
     #[arg(long)]
     pub no_chunking: bool,
+
+    /// WebRTC VAD aggressiveness for chunking, 0 (keeps quiet speech) to 3 (rejects most noise)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=3))]
+    pub vad_aggressiveness: Option<u8>,
+
+    /// Only run diarization, skipping transcription (outputs RTTM unless --format=json)
+    #[arg(long)]
+    pub diarize_only: bool,
+
+    /// Write one WAV per speaker (their turns concatenated) into this directory
+    #[arg(long, value_name = "DIR")]
+    pub split_speakers: Option<PathBuf>,
+
+    /// Always transcribe, ignoring and not updating the transcription cache
+    #[arg(long)]
+    pub no_cache: bool,
+
+    /// Load the model here even if the running app has the same one loaded
+    #[arg(long)]
+    pub standalone: bool,
 }
 
-#[derive(Clone, Copy, ValueEnum, Default)]
+#[derive(Parser)]
+pub struct RecordArgs {
+    /// Stop after this many seconds (default: record until Ctrl+C)
+    #[arg(short, long)]
+    pub duration: Option<f64>,
+
+    /// Where to save the recording; the extension picks the format (wav, flac, opus)
+    #[arg(short, long, required_unless_present = "transcribe")]
+    pub output: Option<PathBuf>,
+
+    /// Transcribe the recording once it stops
+    #[arg(long)]
+    pub transcribe: bool,
+
+    /// Transcript file for --transcribe (stdout if omitted)
+    #[arg(long, requires = "transcribe")]
+    pub transcript: Option<PathBuf>,
+
+    /// Config file path (default: ~/.config/voice-dictation/config.toml)
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Override model path or filename for --transcribe
+    #[arg(short, long)]
+    pub model: Option<String>,
+
+    /// Override language for --transcribe (uk, en, auto, etc.)
+    #[arg(short, long)]
+    pub language: Option<String>,
+
+    /// STT backend for --transcribe (whisper or tdt)
+    #[arg(long, value_enum, default_value_t = SttBackend::Whisper)]
+    pub backend: SttBackend,
+
+    /// Transcript format for --transcribe
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Enable noise suppression for --transcribe
+    #[arg(long)]
+    pub denoise: bool,
+}
+
+/// Sortformer model and tuning overrides.
+#[derive(Args, Default)]
+pub struct SortformerArgs {
+    /// Path to Sortformer model (optional, uses default location if not specified)
+    #[arg(long)]
+    pub sortformer_model: Option<PathBuf>,
+
+    /// Maximum number of speakers for Sortformer (1-4, default from config)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=4))]
+    pub max_speakers: Option<u8>,
+
+    /// Sortformer speech onset threshold (0.0-1.0, default from config)
+    #[arg(long)]
+    pub onset: Option<f32>,
+
+    /// Sortformer speech offset threshold (0.0-1.0, default from config)
+    #[arg(long)]
+    pub offset: Option<f32>,
+}
+
+#[derive(Parser)]
+pub struct DiarizeArgs {
+    /// Path to audio file to diarize
+    pub input: PathBuf,
+
+    /// Output file (stdout if omitted)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Config file path (default: ~/.config/voice-dictation/config.toml)
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Channel handling mode for stereo files
+    #[arg(long, value_enum, default_value_t = ChannelMode::Mix)]
+    pub channel: ChannelMode,
+
+    #[command(flatten)]
+    pub sortformer: SortformerArgs,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Enable noise suppression
+    #[arg(long)]
+    pub denoise: bool,
+
+    /// Write one WAV per speaker (their turns concatenated) into this directory
+    #[arg(long, value_name = "DIR")]
+    pub split_speakers: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, ValueEnum, Default, Debug)]
 pub enum ChannelMode {
     /// Mix both channels to mono (default)
     #[default]
@@ -127,11 +449,13 @@ pub enum OutputFormat {
     Text,
     /// JSON output with metadata
     Json,
+    /// RTTM speaker segments (requires diarization)
+    Rttm,
 }
 
 #[derive(Parser)]
 pub struct DenoiseEvalArgs {
-    /// Path to WAV file to evaluate
+    /// Path to audio file to evaluate
     pub input: PathBuf,
 
     /// Channel to evaluate (for stereo files)
@@ -158,7 +482,198 @@ pub struct DenoiseEvalArgs {
     #[arg(short, long)]
     pub language: Option<String>,
 
+    /// DeepFilterNet ONNX model to compare as well (default: deepfilter_model_path from config)
+    #[arg(long)]
+    pub deepfilter_model: Option<PathBuf>,
+
+    /// Config file path
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Parser)]
+pub struct VadEvalArgs {
+    /// WAV file, or a directory of WAV files with reference labels next to them
+    pub input: PathBuf,
+
+    /// Reference speech labels for a single WAV: RTTM or Audacity labels (start end [label])
+    #[arg(long)]
+    pub labels: Option<PathBuf>,
+
+    /// VAD engine (default from config)
+    #[arg(long, value_enum)]
+    pub engine: Option<VadEngineArg>,
+
+    /// WebRTC VAD aggressiveness, 0-3 (default from config)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(0..=3))]
+    pub aggressiveness: Option<u8>,
+
+    /// Silero speech probability threshold (default from config)
+    #[arg(long)]
+    pub silero_threshold: Option<f32>,
+
+    /// Pauses shorter than this are bridged when forming speech regions
+    #[arg(long, default_value_t = 300)]
+    pub merge_gap_ms: u32,
+
+    /// Channel to evaluate (for stereo files)
+    #[arg(long, value_enum, default_value_t = ChannelMode::Mix)]
+    pub channel: ChannelMode,
+
     /// Config file path
     #[arg(short, long)]
     pub config: Option<PathBuf>,
 }
+
+#[derive(Parser)]
+pub struct BenchArgs {
+    /// Path to audio file used as benchmark input
+    pub input: PathBuf,
+
+    /// Config file path (default: ~/.config/voice-dictation/config.toml)
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Override model path or filename
+    #[arg(short, long)]
+    pub model: Option<String>,
+
+    /// Override language (uk, en, auto, etc.)
+    #[arg(short, long)]
+    pub language: Option<String>,
+
+    /// STT backend (whisper or tdt)
+    #[arg(long, value_enum, default_value_t = SttBackend::Whisper)]
+    pub backend: SttBackend,
+
+    /// Path to TDT model directory
+    #[arg(long)]
+    pub tdt_model: Option<PathBuf>,
+
+    /// Number of timed iterations
+    #[arg(long, default_value_t = 3)]
+    pub iterations: u32,
+
+    /// Skip the untimed warm-up run
+    #[arg(long)]
+    pub no_warmup: bool,
+
+    /// Baseline JSON to compare against; exits non-zero on regressions
+    #[arg(long)]
+    pub baseline: Option<PathBuf>,
+
+    /// Write this run's results as a baseline JSON
+    #[arg(long)]
+    pub save_baseline: Option<PathBuf>,
+
+    /// Allowed relative increase before a metric counts as a regression (0.10 = 10%)
+    #[arg(long, default_value_t = 0.10)]
+    pub threshold: f64,
+}
+
+#[derive(Parser)]
+pub struct BenchmarkArgs {
+    /// Path to audio file to transcribe with every model
+    pub input: PathBuf,
+
+    /// Reference transcript (text file) for WER/CER
+    #[arg(short, long)]
+    pub reference: Option<PathBuf>,
+
+    /// Config file path (default: ~/.config/voice-dictation/config.toml)
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Only benchmark these models (filename, or "tdt"; repeatable)
+    #[arg(short, long)]
+    pub model: Vec<String>,
+
+    /// Override language (uk, en, auto, etc.)
+    #[arg(short, long)]
+    pub language: Option<String>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Include transcribed text in JSON output
+    #[arg(long)]
+    pub include_text: bool,
+}
+
+#[derive(Parser)]
+pub struct AlignSrtArgs {
+    /// Path to the audio file the subtitles belong to
+    pub input: PathBuf,
+
+    /// SRT file to re-time
+    pub subtitles: PathBuf,
+
+    /// Output SRT file (stdout if omitted)
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Config file path (default: ~/.config/voice-dictation/config.toml)
+    #[arg(short, long)]
+    pub config: Option<PathBuf>,
+
+    /// Override model path or filename
+    #[arg(short, long)]
+    pub model: Option<String>,
+
+    /// Override language (uk, en, auto, etc.)
+    #[arg(short, long)]
+    pub language: Option<String>,
+}
+
+#[derive(Parser)]
+pub struct ScriptArgs {
+    #[command(subcommand)]
+    pub command: ScriptCommand,
+}
+
+#[derive(Subcommand)]
+pub enum ScriptCommand {
+    /// Run a script file
+    Run {
+        /// Path to the .rhai script
+        path: PathBuf,
+
+        #[command(flatten)]
+        permissions: ScriptPermissionArgs,
+
+        /// Config file path (default: ~/.config/voice-dictation/config.toml)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Arguments passed to the script as ARGS (after `--`)
+        #[arg(last = true)]
+        args: Vec<String>,
+    },
+    /// Interactive developer console
+    #[command(hide = true)]
+    Console {
+        #[command(flatten)]
+        permissions: ScriptPermissionArgs,
+
+        /// Config file path (default: ~/.config/voice-dictation/config.toml)
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+    },
+}
+
+/// Access granted to scripts; everything is denied by default.
+#[derive(Args)]
+pub struct ScriptPermissionArgs {
+    /// Allow reading files under this directory (repeatable)
+    #[arg(long, value_name = "DIR")]
+    pub allow_read: Vec<PathBuf>,
+
+    /// Allow reading and writing files under this directory (repeatable)
+    #[arg(long, value_name = "DIR")]
+    pub allow_write: Vec<PathBuf>,
+
+    /// Allow HTTP requests
+    #[arg(long)]
+    pub allow_net: bool,
+}
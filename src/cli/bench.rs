@@ -0,0 +1,306 @@
+//! Performance benchmark CLI command.
+//!
+//! Runs a WAV file through the transcription pipeline several times,
+//! reports per-stage timings, real-time factor and peak memory, and
+//! optionally compares them against a stored baseline. Any metric that
+//! got worse by more than the threshold fails the command, so it can
+//! gate releases in CI.
+
+use crate::cli::args::{BenchArgs, ChannelMode, SttBackend};
+use crate::cli::transcribe::{build_chunker, load_config_from, resolve_tdt_model, resolve_whisper_model};
+use crate::cli::wav_reader::{prepare_for_whisper, read_audio};
+use crate::infrastructure::hardware::CpuFeatures;
+use crate::infrastructure::perf::{peak_rss_bytes, real_time_factor, timed};
+use crate::recording::denoise::DenoiseSettings;
+use crate::transcription::whisper::build_cpu_features;
+use crate::transcription::TranscriptionService;
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+/// Stage timings below this are too noisy to flag as regressions.
+const MIN_STAGE_DELTA_MS: f64 = 5.0;
+
+/// Results of one benchmark run, also used as the baseline file format.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchReport {
+    pub version: String,
+    pub input_file: String,
+    pub audio_secs: f64,
+    pub backend: String,
+    pub model: String,
+    pub iterations: u32,
+    /// Mean wall time per pipeline stage in milliseconds.
+    pub stages: BTreeMap<String, f64>,
+    /// Mean end-to-end real-time factor (lower is faster).
+    pub rtf: f64,
+    #[serde(default)]
+    pub peak_rss_bytes: Option<u64>,
+}
+
+/// A metric that got worse than the allowed threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub metric: String,
+    pub baseline: f64,
+    pub current: f64,
+}
+
+impl Regression {
+    /// Relative change in percent (positive means slower / larger).
+    pub fn change_pct(&self) -> f64 {
+        if self.baseline == 0.0 {
+            return 0.0;
+        }
+        (self.current / self.baseline - 1.0) * 100.0
+    }
+}
+
+/// Compare a run against a baseline.
+///
+/// `threshold` is the allowed relative increase (0.10 = 10%).
+pub fn find_regressions(baseline: &BenchReport, current: &BenchReport, threshold: f64) -> Vec<Regression> {
+    let exceeds = |base: f64, cur: f64| base > 0.0 && cur > base * (1.0 + threshold);
+    let mut regressions = Vec::new();
+
+    if exceeds(baseline.rtf, current.rtf) {
+        regressions.push(Regression {
+            metric: "rtf".to_string(),
+            baseline: baseline.rtf,
+            current: current.rtf,
+        });
+    }
+
+    for (stage, &base_ms) in &baseline.stages {
+        let Some(&cur_ms) = current.stages.get(stage) else {
+            continue;
+        };
+        if exceeds(base_ms, cur_ms) && cur_ms - base_ms >= MIN_STAGE_DELTA_MS {
+            regressions.push(Regression {
+                metric: format!("stage.{}_ms", stage),
+                baseline: base_ms,
+                current: cur_ms,
+            });
+        }
+    }
+
+    if let (Some(base_rss), Some(cur_rss)) = (baseline.peak_rss_bytes, current.peak_rss_bytes) {
+        if exceeds(base_rss as f64, cur_rss as f64) {
+            regressions.push(Regression {
+                metric: "peak_rss_bytes".to_string(),
+                baseline: base_rss as f64,
+                current: cur_rss as f64,
+            });
+        }
+    }
+
+    regressions
+}
+
+/// Load a baseline report from JSON.
+pub fn load_baseline(path: &Path) -> Result<BenchReport> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read baseline: {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse baseline: {}", path.display()))
+}
+
+/// Run the bench command.
+pub fn run(args: BenchArgs) -> Result<()> {
+    if args.iterations == 0 {
+        bail!("--iterations must be at least 1");
+    }
+
+    let config = load_config_from(args.config.as_deref())?;
+    let language = args.language.clone().unwrap_or_else(|| config.language.clone());
+
+    eprintln!("Reading: {}", args.input.display());
+    let audio = read_audio(&args.input)?;
+
+    let (service, backend, model) = match args.backend {
+        SttBackend::Whisper => {
+            let model_path = resolve_whisper_model(args.model.as_deref(), &config)?;
+            eprintln!("Loading Whisper model: {}", model_path.display());
+            let unused = CpuFeatures::detect().unused_by(&build_cpu_features());
+            if !unused.is_empty() {
+                // Timings of such a build understate what this machine can do
+                eprintln!("Note: whisper.cpp is built without {}", unused.join(", "));
+            }
+            let service = TranscriptionService::with_model(&model_path.to_string_lossy())?;
+            (service, "whisper", file_name(&model_path))
+        }
+        SttBackend::Tdt => {
+            let model_dir = resolve_tdt_model(args.tdt_model.as_deref(), &config)?;
+            eprintln!("Loading TDT model from: {}", model_dir.display());
+            let service = TranscriptionService::with_tdt(&model_dir.to_string_lossy())?;
+            (service, "tdt", file_name(&model_dir))
+        }
+    };
+
+    let chunker = build_chunker(config.max_segment_secs, &config);
+    let run_once = || -> Result<[Duration; 3]> {
+        let (prepared, prepare_time) =
+            timed(|| prepare_for_whisper(&audio, ChannelMode::Mix, DenoiseSettings::if_enabled(&config).as_ref()));
+        let prepared = prepared?;
+        let (chunks, segment_time) = timed(|| chunker.segment(&prepared.samples));
+        chunks?;
+        let (text, transcribe_time) = timed(|| chunker.transcribe_chunked(&prepared.samples, &language, &service));
+        text?;
+        Ok([prepare_time, segment_time, transcribe_time])
+    };
+
+    if !args.no_warmup {
+        eprintln!("Warm-up run...");
+        run_once()?;
+    }
+
+    let mut totals = [Duration::ZERO; 3];
+    for i in 1..=args.iterations {
+        eprintln!("Iteration {}/{}...", i, args.iterations);
+        let times = run_once()?;
+        for (total, time) in totals.iter_mut().zip(times) {
+            *total += time;
+        }
+    }
+
+    let mean_ms = |total: Duration| total.as_secs_f64() * 1000.0 / args.iterations as f64;
+    let stages: BTreeMap<String, f64> = ["prepare", "segment", "transcribe"]
+        .iter()
+        .zip(totals)
+        .map(|(name, total)| (name.to_string(), mean_ms(total)))
+        .collect();
+    let mean_total = totals.iter().sum::<Duration>() / args.iterations;
+
+    let report = BenchReport {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        input_file: args.input.to_string_lossy().to_string(),
+        audio_secs: audio.duration_secs,
+        backend: backend.to_string(),
+        model,
+        iterations: args.iterations,
+        stages,
+        rtf: real_time_factor(mean_total, audio.duration_secs),
+        peak_rss_bytes: peak_rss_bytes(),
+    };
+
+    print_report(&report);
+
+    if let Some(ref path) = args.save_baseline {
+        let json = serde_json::to_string_pretty(&report).context("Failed to serialize report")?;
+        fs::write(path, json).with_context(|| format!("Failed to write baseline: {}", path.display()))?;
+        eprintln!("Baseline written to: {}", path.display());
+    }
+
+    if let Some(ref path) = args.baseline {
+        let baseline = load_baseline(path)?;
+        if baseline.input_file != report.input_file || baseline.model != report.model {
+            eprintln!(
+                "Warning: baseline was recorded with {} / {}, comparing anyway",
+                baseline.input_file, baseline.model
+            );
+        }
+
+        let regressions = find_regressions(&baseline, &report, args.threshold);
+        if !regressions.is_empty() {
+            println!();
+            println!("Regressions (threshold {:.0}%):", args.threshold * 100.0);
+            for r in &regressions {
+                println!(
+                    "  {:24} {:>12.3} -> {:>12.3}  ({:+.1}%)",
+                    r.metric,
+                    r.baseline,
+                    r.current,
+                    r.change_pct()
+                );
+            }
+            bail!("Performance regression in {} metric(s)", regressions.len());
+        }
+        println!();
+        println!("No regressions against {}", path.display());
+    }
+
+    Ok(())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+fn print_report(report: &BenchReport) {
+    println!(
+        "Benchmark: {} ({:.1}s audio), {} / {}, {} iteration(s)",
+        report.input_file, report.audio_secs, report.backend, report.model, report.iterations
+    );
+    for (stage, ms) in &report.stages {
+        println!("  {:12} {:>10.1} ms", stage, ms);
+    }
+    println!("  {:12} {:>10.3}", "RTF", report.rtf);
+    if let Some(rss) = report.peak_rss_bytes {
+        println!("  {:12} {:>10.1} MB", "Peak RSS", rss as f64 / (1024.0 * 1024.0));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(rtf: f64, transcribe_ms: f64, rss: Option<u64>) -> BenchReport {
+        BenchReport {
+            version: "0.3.0".to_string(),
+            input_file: "test.wav".to_string(),
+            audio_secs: 10.0,
+            backend: "whisper".to_string(),
+            model: "ggml-base.bin".to_string(),
+            iterations: 3,
+            stages: BTreeMap::from([("prepare".to_string(), 2.0), ("transcribe".to_string(), transcribe_ms)]),
+            rtf,
+            peak_rss_bytes: rss,
+        }
+    }
+
+    #[test]
+    fn test_no_regression_within_threshold() {
+        let baseline = report(0.50, 5000.0, Some(500_000_000));
+        let current = report(0.54, 5400.0, Some(520_000_000));
+        assert!(find_regressions(&baseline, &current, 0.10).is_empty());
+    }
+
+    #[test]
+    fn test_rtf_and_stage_regression() {
+        let baseline = report(0.50, 5000.0, None);
+        let current = report(0.60, 6000.0, None);
+        let regressions = find_regressions(&baseline, &current, 0.10);
+        let metrics: Vec<_> = regressions.iter().map(|r| r.metric.as_str()).collect();
+        assert_eq!(metrics, vec!["rtf", "stage.transcribe_ms"]);
+        assert!((regressions[0].change_pct() - 20.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_memory_regression() {
+        let baseline = report(0.5, 5000.0, Some(100_000_000));
+        let current = report(0.5, 5000.0, Some(200_000_000));
+        let regressions = find_regressions(&baseline, &current, 0.10);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].metric, "peak_rss_bytes");
+    }
+
+    #[test]
+    fn test_tiny_stage_changes_ignored() {
+        let mut baseline = report(0.5, 5000.0, None);
+        let mut current = baseline.clone();
+        baseline.stages.insert("segment".to_string(), 1.0);
+        current.stages.insert("segment".to_string(), 3.0);
+        assert!(find_regressions(&baseline, &current, 0.10).is_empty());
+    }
+
+    #[test]
+    fn test_baseline_json_roundtrip() {
+        let original = report(0.5, 5000.0, Some(1));
+        let json = serde_json::to_string(&original).unwrap();
+        let parsed: BenchReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, original);
+    }
+}
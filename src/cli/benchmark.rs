@@ -0,0 +1,257 @@
+//! Model comparison benchmark CLI command.
+//!
+//! Runs one WAV file through every downloaded Whisper model and the TDT
+//! backend (if installed), reporting load time, real-time factor, peak
+//! memory and, given a reference transcript, WER/CER — to help users pick
+//! a model for their hardware.
+
+use crate::app::config::tdt_models_dir;
+use crate::cli::args::{BenchmarkArgs, ChannelMode, OutputFormat};
+use crate::cli::transcribe::{build_chunker, load_config_from};
+use crate::cli::wav_reader::{prepare_for_whisper, read_audio};
+use crate::infrastructure::models::{get_model_path, is_tdt_model_downloaded, list_downloaded_models};
+use crate::infrastructure::perf::{peak_rss_bytes, real_time_factor, reset_peak_rss, timed};
+use crate::recording::denoise::DenoiseSettings;
+use crate::transcription::accuracy::{char_error_rate, word_error_rate};
+use crate::transcription::chunker::AudioChunker;
+use crate::transcription::TranscriptionService;
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+
+/// A model/backend to benchmark.
+struct Candidate {
+    backend: &'static str,
+    model: String,
+    path: String,
+}
+
+/// Benchmark results for one model.
+#[derive(Debug, Clone, Serialize, Default)]
+pub struct ModelResult {
+    pub backend: String,
+    pub model: String,
+    pub load_secs: f64,
+    pub transcribe_secs: f64,
+    pub rtf: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_rss_bytes: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wer: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cer: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+struct BenchmarkOutput {
+    version: String,
+    input_file: String,
+    audio_secs: f64,
+    language: String,
+    results: Vec<ModelResult>,
+}
+
+/// Run the benchmark command.
+pub fn run(args: BenchmarkArgs) -> Result<()> {
+    if matches!(args.format, OutputFormat::Rttm) {
+        bail!("--format=rttm is only supported by the transcribe command");
+    }
+
+    let config = load_config_from(args.config.as_deref())?;
+    let language = args.language.clone().unwrap_or_else(|| config.language.clone());
+
+    let reference = match args.reference {
+        Some(ref path) => {
+            Some(fs::read_to_string(path).with_context(|| format!("Failed to read reference: {}", path.display()))?)
+        }
+        None => None,
+    };
+
+    eprintln!("Reading: {}", args.input.display());
+    let audio = read_audio(&args.input)?;
+    let prepared = prepare_for_whisper(&audio, ChannelMode::Mix, DenoiseSettings::if_enabled(&config).as_ref())?;
+
+    let candidates: Vec<Candidate> = collect_candidates()
+        .into_iter()
+        .filter(|c| args.model.is_empty() || args.model.iter().any(|m| m == &c.model))
+        .collect();
+    if candidates.is_empty() {
+        bail!("No downloaded models to benchmark. Use `models` to list available models.");
+    }
+
+    let chunker = build_chunker(config.max_segment_secs, &config);
+    let mut results = Vec::with_capacity(candidates.len());
+    for (i, candidate) in candidates.iter().enumerate() {
+        eprintln!(
+            "[{}/{}] {} ({})...",
+            i + 1,
+            candidates.len(),
+            candidate.model,
+            candidate.backend
+        );
+        let mut result = benchmark_candidate(candidate, &prepared.samples, &language, &chunker, audio.duration_secs);
+        if let (Some(reference), Some(text)) = (&reference, &result.text) {
+            result.wer = Some(word_error_rate(reference, text));
+            result.cer = Some(char_error_rate(reference, text));
+        }
+        if !args.include_text {
+            result.text = None;
+        }
+        results.push(result);
+    }
+
+    let stdout = std::io::stdout();
+    let mut handle = stdout.lock();
+    match args.format {
+        OutputFormat::Text | OutputFormat::Rttm => write_table(&mut handle, &results)?,
+        OutputFormat::Json => {
+            let output = BenchmarkOutput {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                input_file: args.input.to_string_lossy().to_string(),
+                audio_secs: audio.duration_secs,
+                language,
+                results,
+            };
+            let json = serde_json::to_string_pretty(&output).context("Failed to serialize results")?;
+            writeln!(handle, "{}", json)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// All downloaded Whisper models, plus TDT if installed.
+fn collect_candidates() -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = list_downloaded_models()
+        .into_iter()
+        .map(|m| Candidate {
+            backend: "whisper",
+            path: get_model_path(&m.filename).to_string_lossy().to_string(),
+            model: m.filename,
+        })
+        .collect();
+    if is_tdt_model_downloaded() {
+        candidates.push(Candidate {
+            backend: "tdt",
+            model: "tdt".to_string(),
+            path: tdt_models_dir().to_string_lossy().to_string(),
+        });
+    }
+    candidates
+}
+
+/// Load one model, transcribe, and measure. Failures are recorded, not propagated.
+fn benchmark_candidate(
+    candidate: &Candidate,
+    samples: &[f32],
+    language: &str,
+    chunker: &AudioChunker,
+    audio_secs: f64,
+) -> ModelResult {
+    let mut result = ModelResult {
+        backend: candidate.backend.to_string(),
+        model: candidate.model.clone(),
+        ..ModelResult::default()
+    };
+
+    // Measure each model's own peak; the previous model is already dropped
+    let rss_supported = reset_peak_rss();
+
+    let (service, load_time) = timed(|| match candidate.backend {
+        "tdt" => TranscriptionService::with_tdt(&candidate.path),
+        _ => TranscriptionService::with_model(&candidate.path),
+    });
+    result.load_secs = load_time.as_secs_f64();
+    let service = match service {
+        Ok(service) => service,
+        Err(e) => {
+            result.error = Some(format!("{:#}", e));
+            return result;
+        }
+    };
+
+    let (text, transcribe_time) = timed(|| chunker.transcribe_chunked(samples, language, &service));
+    result.transcribe_secs = transcribe_time.as_secs_f64();
+    result.rtf = real_time_factor(transcribe_time, audio_secs);
+    result.peak_rss_bytes = if rss_supported { peak_rss_bytes() } else { None };
+    match text {
+        Ok(text) => result.text = Some(text.trim().to_string()),
+        Err(e) => result.error = Some(format!("{:#}", e)),
+    }
+
+    result
+}
+
+fn write_table(out: &mut impl Write, results: &[ModelResult]) -> Result<()> {
+    writeln!(
+        out,
+        "{:32} {:8} {:>8} {:>8} {:>10} {:>7} {:>7}",
+        "MODEL", "BACKEND", "LOAD s", "RTF", "PEAK RSS", "WER", "CER"
+    )?;
+    for r in results {
+        if let Some(ref error) = r.error {
+            writeln!(out, "{:32} {:8} error: {}", r.model, r.backend, error)?;
+            continue;
+        }
+        writeln!(
+            out,
+            "{:32} {:8} {:>8.2} {:>8.3} {:>10} {:>7} {:>7}",
+            r.model,
+            r.backend,
+            r.load_secs,
+            r.rtf,
+            r.peak_rss_bytes
+                .map(|b| format!("{:.0} MB", b as f64 / (1024.0 * 1024.0)))
+                .unwrap_or_else(|| "-".to_string()),
+            format_rate(r.wer),
+            format_rate(r.cer),
+        )?;
+    }
+    Ok(())
+}
+
+fn format_rate(rate: Option<f64>) -> String {
+    rate.map(|r| format!("{:.1}%", r * 100.0))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_rate() {
+        assert_eq!(format_rate(Some(0.125)), "12.5%");
+        assert_eq!(format_rate(None), "-");
+    }
+
+    #[test]
+    fn test_write_table_includes_errors() {
+        let results = vec![
+            ModelResult {
+                backend: "whisper".to_string(),
+                model: "ggml-base.bin".to_string(),
+                rtf: 0.25,
+                wer: Some(0.1),
+                ..ModelResult::default()
+            },
+            ModelResult {
+                backend: "tdt".to_string(),
+                model: "tdt".to_string(),
+                error: Some("load failed".to_string()),
+                ..ModelResult::default()
+            },
+        ];
+        let mut out = Vec::new();
+        write_table(&mut out, &results).unwrap();
+        let table = String::from_utf8(out).unwrap();
+        assert!(table.contains("ggml-base.bin"));
+        assert!(table.contains("10.0%"));
+        assert!(table.contains("error: load failed"));
+    }
+}
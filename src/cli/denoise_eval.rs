@@ -1,11 +1,15 @@
 //! Denoise evaluation CLI command.
 //!
 //! Compares original vs denoised audio using signal metrics, VAD analysis,
-//! and optional transcription A/B testing.
+//! and optional transcription A/B testing. RNNoise is always evaluated;
+//! DeepFilterNet is evaluated too when a model is given or configured.
 
+use crate::app::config::{load_config, Config};
 use crate::cli::args::{ChannelMode, DenoiseEvalArgs};
-use crate::cli::wav_reader::{prepare_for_whisper, read_wav};
-use crate::recording::denoise::NnnoiselessDenoiser;
+use crate::cli::wav_reader::{prepare_for_whisper, read_audio};
+use crate::domain::text_metrics::word_count;
+use crate::recording::denoise::{denoise_samples, DenoiseBackend, DenoiseSettings};
+use crate::transcription::TranscriptionService;
 use anyhow::{Context, Result};
 use serde::Serialize;
 use std::io::Write;
@@ -45,12 +49,11 @@ struct TranscriptionMetrics {
     denoised_words: usize,
 }
 
-/// Full evaluation report.
+/// Results of one denoiser.
 #[derive(Serialize)]
-struct DenoiseReport {
-    input_file: String,
-    channel: String,
-    duration_secs: f64,
+struct BackendReport {
+    backend: String,
+    output_file: String,
     signal: SignalMetrics,
     #[serde(skip_serializing_if = "Option::is_none")]
     vad: Option<VadMetrics>,
@@ -58,11 +61,33 @@ struct DenoiseReport {
     transcription: Option<TranscriptionMetrics>,
 }
 
+/// Full evaluation report.
+#[derive(Serialize)]
+struct DenoiseReport {
+    input_file: String,
+    channel: String,
+    duration_secs: f64,
+    backends: Vec<BackendReport>,
+}
+
+/// Speech percentages of the original audio, measured once for all backends.
+struct OriginalVad {
+    webrtc: f32,
+    silero: f32,
+}
+
+/// Loaded model and the original transcript, shared by all backends.
+struct TranscriptionBaseline {
+    service: TranscriptionService,
+    language: String,
+    original_text: String,
+}
+
 /// Run the denoise-eval command.
 pub fn run(args: DenoiseEvalArgs) -> Result<()> {
     // 1. Read and prepare audio
     eprintln!("Reading: {}", args.input.display());
-    let audio = read_wav(&args.input)?;
+    let audio = read_audio(&args.input)?;
     eprintln!(
         "  {} channels, {}Hz, {:.1}s",
         audio.channels, audio.sample_rate, audio.duration_secs
@@ -76,24 +101,12 @@ pub fn run(args: DenoiseEvalArgs) -> Result<()> {
     };
 
     // Prepare without denoising to get original 16kHz samples
-    let prepared = prepare_for_whisper(&audio, args.channel, false)?;
+    let prepared = prepare_for_whisper(&audio, args.channel, None)?;
     let original = &prepared.samples;
 
-    // 2. Denoise
-    eprintln!("Denoising...");
-    let denoiser = NnnoiselessDenoiser::new();
-    let denoised = denoiser.denoise_buffer(original).context("Denoising failed")?;
+    let config = load_eval_config(&args)?;
+    let backends = eval_backends(&config, &args);
 
-    // 3. Signal metrics
-    let signal = compute_signal_metrics(original, &denoised);
-    eprintln!(
-        "  RMS: {:.4} -> {:.4} ({:.1}% reduction)",
-        signal.original_rms, signal.denoised_rms, signal.rms_reduction_pct
-    );
-    eprintln!("  Peak: {:.4} -> {:.4}", signal.original_peak, signal.denoised_peak);
-    eprintln!("  Length diff: {} samples", signal.length_diff_samples);
-
-    // 4. Write denoised WAV
     let output_dir = args
         .output_dir
         .as_deref()
@@ -106,34 +119,70 @@ pub fn run(args: DenoiseEvalArgs) -> Result<()> {
         .file_stem()
         .map(|s| s.to_string_lossy().to_string())
         .unwrap_or_else(|| "audio".to_string());
-    let output_path = output_dir.join(format!("{}_{}_denoised.wav", stem, channel_name));
-    write_wav_mono_f32(&output_path, &denoised, 16000)?;
-    eprintln!("Wrote: {}", output_path.display());
-
-    // 5. Optional VAD comparison
-    let vad_metrics = if args.vad {
-        eprintln!("Running VAD comparison...");
-        Some(compute_vad_metrics(original, &denoised)?)
+
+    let original_vad = if args.vad {
+        eprintln!("Running VAD on original...");
+        Some(compute_original_vad(original)?)
     } else {
         None
     };
 
-    // 6. Optional transcription A/B
-    let transcription_metrics = if args.transcribe {
-        eprintln!("Running transcription A/B...");
-        Some(compute_transcription_metrics(original, &denoised, &args)?)
+    let baseline = if args.transcribe {
+        eprintln!("Transcribing original...");
+        Some(transcription_baseline(original, &config, &args)?)
     } else {
         None
     };
 
+    let mut reports = Vec::with_capacity(backends.len());
+    for settings in &backends {
+        let backend = settings.backend.name();
+
+        // 2. Denoise
+        eprintln!("Denoising with {}...", backend);
+        let denoised = denoise_samples(settings, original).context("Denoising failed")?;
+
+        // 3. Signal metrics
+        let signal = compute_signal_metrics(original, &denoised);
+        eprintln!(
+            "  RMS: {:.4} -> {:.4} ({:.1}% reduction)",
+            signal.original_rms, signal.denoised_rms, signal.rms_reduction_pct
+        );
+        eprintln!("  Peak: {:.4} -> {:.4}", signal.original_peak, signal.denoised_peak);
+        eprintln!("  Length diff: {} samples", signal.length_diff_samples);
+
+        // 4. Write denoised WAV
+        let output_path = output_dir.join(denoised_file_name(&stem, channel_name, settings.backend));
+        write_wav_mono_f32(&output_path, &denoised, 16000)?;
+        eprintln!("Wrote: {}", output_path.display());
+
+        // 5. Optional VAD comparison
+        let vad = match &original_vad {
+            Some(original_vad) => Some(compute_vad_metrics(original_vad, &denoised)?),
+            None => None,
+        };
+
+        // 6. Optional transcription A/B
+        let transcription = match &baseline {
+            Some(baseline) => Some(compute_transcription_metrics(baseline, &denoised)?),
+            None => None,
+        };
+
+        reports.push(BackendReport {
+            backend: backend.to_string(),
+            output_file: output_path.to_string_lossy().to_string(),
+            signal,
+            vad,
+            transcription,
+        });
+    }
+
     // 7. Build and output JSON report
     let report = DenoiseReport {
         input_file: args.input.to_string_lossy().to_string(),
         channel: channel_name.to_string(),
         duration_secs: audio.duration_secs,
-        signal,
-        vad: vad_metrics,
-        transcription: transcription_metrics,
+        backends: reports,
     };
 
     let json = serde_json::to_string_pretty(&report).context("Failed to serialize report")?;
@@ -144,6 +193,44 @@ pub fn run(args: DenoiseEvalArgs) -> Result<()> {
     Ok(())
 }
 
+fn load_eval_config(args: &DenoiseEvalArgs) -> Result<Config> {
+    if let Some(ref custom_path) = args.config {
+        let content = std::fs::read_to_string(custom_path)
+            .with_context(|| format!("Failed to read config: {}", custom_path.display()))?;
+        toml::from_str(&content).context("Failed to parse config")
+    } else {
+        Ok(load_config().unwrap_or_else(|_| Config::default()))
+    }
+}
+
+/// RNNoise, plus DeepFilterNet when a model is given or configured.
+fn eval_backends(config: &Config, args: &DenoiseEvalArgs) -> Vec<DenoiseSettings> {
+    let mut backends = vec![DenoiseSettings {
+        backend: DenoiseBackend::Rnnoise,
+        deepfilter_model_path: None,
+    }];
+    let model = args
+        .deepfilter_model
+        .clone()
+        .or_else(|| DenoiseSettings::from_config(config).deepfilter_model_path);
+    match model {
+        Some(path) => backends.push(DenoiseSettings {
+            backend: DenoiseBackend::DeepFilterNet,
+            deepfilter_model_path: Some(path),
+        }),
+        None => eprintln!("DeepFilterNet skipped: no model (--deepfilter-model or deepfilter_model_path)"),
+    }
+    backends
+}
+
+/// `<stem>_<channel>_denoised.wav` for RNNoise, with the backend name appended for others.
+fn denoised_file_name(stem: &str, channel: &str, backend: DenoiseBackend) -> String {
+    match backend {
+        DenoiseBackend::Rnnoise => format!("{}_{}_denoised.wav", stem, channel),
+        other => format!("{}_{}_denoised_{}.wav", stem, channel, other.name()),
+    }
+}
+
 /// Compute RMS and peak for a buffer of f32 samples.
 fn compute_signal_metrics(original: &[f32], denoised: &[f32]) -> SignalMetrics {
     let original_rms = rms(original);
@@ -176,28 +263,33 @@ fn peak(samples: &[f32]) -> f32 {
     samples.iter().map(|s| s.abs()).fold(0.0f32, f32::max)
 }
 
-/// Compute VAD speech percentage for both WebRTC and Silero on original and denoised audio.
-fn compute_vad_metrics(original: &[f32], denoised: &[f32]) -> Result<VadMetrics> {
+/// Compute VAD speech percentage for both WebRTC and Silero on the original audio.
+fn compute_original_vad(original: &[f32]) -> Result<OriginalVad> {
     use crate::vad::{SileroVoiceDetector, WebRtcVoiceDetector};
 
-    // WebRTC VAD
-    let webrtc_orig = speech_percentage::<WebRtcVoiceDetector>(original, 480)?;
-    let webrtc_den = speech_percentage::<WebRtcVoiceDetector>(denoised, 480)?;
+    Ok(OriginalVad {
+        webrtc: speech_percentage::<WebRtcVoiceDetector>(original, 480)?,
+        silero: speech_percentage::<SileroVoiceDetector>(original, 512)?,
+    })
+}
 
-    // Silero VAD
-    let silero_orig = speech_percentage::<SileroVoiceDetector>(original, 512)?;
+/// Compare VAD speech percentage of denoised audio with the original.
+fn compute_vad_metrics(original: &OriginalVad, denoised: &[f32]) -> Result<VadMetrics> {
+    use crate::vad::{SileroVoiceDetector, WebRtcVoiceDetector};
+
+    let webrtc_den = speech_percentage::<WebRtcVoiceDetector>(denoised, 480)?;
     let silero_den = speech_percentage::<SileroVoiceDetector>(denoised, 512)?;
 
-    eprintln!("  WebRTC: {:.1}% -> {:.1}%", webrtc_orig, webrtc_den);
-    eprintln!("  Silero: {:.1}% -> {:.1}%", silero_orig, silero_den);
+    eprintln!("  WebRTC: {:.1}% -> {:.1}%", original.webrtc, webrtc_den);
+    eprintln!("  Silero: {:.1}% -> {:.1}%", original.silero, silero_den);
 
     Ok(VadMetrics {
         webrtc: VadResult {
-            original_speech_pct: webrtc_orig,
+            original_speech_pct: original.webrtc,
             denoised_speech_pct: webrtc_den,
         },
         silero: VadResult {
-            original_speech_pct: silero_orig,
+            original_speech_pct: original.silero,
             denoised_speech_pct: silero_den,
         },
     })
@@ -242,25 +334,10 @@ fn speech_percentage<V: DefaultVad>(samples: &[f32], frame_size: usize) -> Resul
     Ok(speech_frames as f32 / total_frames as f32 * 100.0)
 }
 
-/// Run transcription on both original and denoised audio, return comparison.
-fn compute_transcription_metrics(
-    original: &[f32],
-    denoised: &[f32],
-    args: &DenoiseEvalArgs,
-) -> Result<TranscriptionMetrics> {
-    use crate::app::config::{load_config, Config};
+/// Load the model and transcribe the original audio.
+fn transcription_baseline(original: &[f32], config: &Config, args: &DenoiseEvalArgs) -> Result<TranscriptionBaseline> {
     use crate::domain::traits::Transcription;
     use crate::infrastructure::models::{get_model_path, list_downloaded_models};
-    use crate::transcription::TranscriptionService;
-
-    // Load config
-    let config = if let Some(ref custom_path) = args.config {
-        let content = std::fs::read_to_string(custom_path)
-            .with_context(|| format!("Failed to read config: {}", custom_path.display()))?;
-        toml::from_str(&content).context("Failed to parse config")?
-    } else {
-        load_config().unwrap_or_else(|_| Config::default())
-    };
 
     // Resolve model
     let model_path = if let Some(ref model_arg) = args.model {
@@ -292,18 +369,29 @@ fn compute_transcription_metrics(
     eprintln!("  Loading model: {}", model_path.display());
     let service = TranscriptionService::with_model(&model_path.to_string_lossy())?;
 
-    let language = args.language.as_deref().unwrap_or(&config.language);
+    let language = args.language.clone().unwrap_or_else(|| config.language.clone());
 
     eprintln!("  Transcribing original...");
-    let original_text = service.transcribe(original, language)?;
+    let original_text = service.transcribe(original, &language)?;
     let original_text = original_text.trim().to_string();
 
+    Ok(TranscriptionBaseline {
+        service,
+        language,
+        original_text,
+    })
+}
+
+/// Transcribe denoised audio and compare it with the original transcript.
+fn compute_transcription_metrics(baseline: &TranscriptionBaseline, denoised: &[f32]) -> Result<TranscriptionMetrics> {
+    use crate::domain::traits::Transcription;
+
     eprintln!("  Transcribing denoised...");
-    let denoised_text = service.transcribe(denoised, language)?;
+    let denoised_text = baseline.service.transcribe(denoised, &baseline.language)?;
     let denoised_text = denoised_text.trim().to_string();
 
-    let original_words = original_text.split_whitespace().count();
-    let denoised_words = denoised_text.split_whitespace().count();
+    let original_words = word_count(&baseline.original_text);
+    let denoised_words = word_count(&denoised_text);
 
     eprintln!(
         "  Words: {} (original) vs {} (denoised)",
@@ -311,7 +399,7 @@ fn compute_transcription_metrics(
     );
 
     Ok(TranscriptionMetrics {
-        original_text,
+        original_text: baseline.original_text.clone(),
         denoised_text,
         original_words,
         denoised_words,
@@ -380,6 +468,18 @@ mod tests {
         assert_eq!(metrics.length_diff_samples, 0);
     }
 
+    #[test]
+    fn test_denoised_file_name_per_backend() {
+        assert_eq!(
+            denoised_file_name("talk", "mix", DenoiseBackend::Rnnoise),
+            "talk_mix_denoised.wav"
+        );
+        assert_eq!(
+            denoised_file_name("talk", "left", DenoiseBackend::DeepFilterNet),
+            "talk_left_denoised_deepfilternet.wav"
+        );
+    }
+
     #[test]
     fn test_signal_metrics_quieter() {
         let original = vec![0.5f32; 100];
@@ -0,0 +1,200 @@
+//! Standalone diarization: who speaks when, without transcription.
+//!
+//! Runs only the Sortformer engine, so no Whisper or TDT model has to be
+//! downloaded. Speaker turns are printed as a timestamped list, JSON or RTTM.
+
+use crate::cli::args::{DiarizationMethod, DiarizeArgs, OutputFormat};
+use crate::cli::transcribe::{load_config_from, load_sortformer, sortformer_turns, write_speaker_tracks};
+use crate::cli::wav_reader::{prepare_for_whisper, read_audio};
+use crate::recording::denoise::DenoiseSettings;
+use crate::transcription::diarization::overlap_regions;
+use crate::transcription::rttm::{format_rttm, rttm_file_id, SpeakerTurn};
+use anyhow::{Context, Result};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Write};
+
+/// JSON output of the `diarize` command.
+#[derive(Serialize)]
+struct DiarizationOutput {
+    version: String,
+    input_file: String,
+    duration_secs: f64,
+    speaker_count: usize,
+    speakers: Vec<SpeakerSummary>,
+    turns: Vec<SpeakerTurn>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    overlaps: Vec<OverlapOutput>,
+}
+
+/// Total speaking time of one speaker.
+#[derive(Serialize, Debug, PartialEq)]
+struct SpeakerSummary {
+    speaker: String,
+    talk_time_secs: f64,
+    turn_count: usize,
+}
+
+#[derive(Serialize)]
+struct OverlapOutput {
+    start: f64,
+    end: f64,
+}
+
+/// Run the diarize command.
+pub fn run(args: DiarizeArgs) -> Result<()> {
+    let config = load_config_from(args.config.as_deref())?;
+
+    eprintln!("Reading: {}", args.input.display());
+    let audio = read_audio(&args.input)?;
+    eprintln!(
+        "  {} channels, {}Hz, {:.1}s",
+        audio.channels, audio.sample_rate, audio.duration_secs
+    );
+
+    let denoise = (args.denoise || config.denoise_enabled).then(|| DenoiseSettings::from_config(&config));
+    let prepared = prepare_for_whisper(&audio, args.channel, denoise.as_ref())?;
+
+    let mut engine = load_sortformer(&args.sortformer, &config)?;
+    eprintln!("Diarizing...");
+    let segments = engine.diarize(&prepared.samples)?;
+    let turns = sortformer_turns(&segments);
+    let overlaps = overlap_regions(&segments);
+    let speakers = summarize_speakers(&turns);
+    eprintln!("Speakers detected: {}", speakers.len());
+
+    let output_text = match args.format {
+        OutputFormat::Text => format_turns(&turns),
+        OutputFormat::Rttm => format_rttm(&rttm_file_id(&args.input), &turns),
+        OutputFormat::Json => {
+            let output = DiarizationOutput {
+                version: env!("CARGO_PKG_VERSION").to_string(),
+                input_file: args.input.to_string_lossy().to_string(),
+                duration_secs: audio.duration_secs,
+                speaker_count: speakers.len(),
+                speakers,
+                turns: turns.clone(),
+                overlaps: overlaps
+                    .iter()
+                    .map(|r| OverlapOutput {
+                        start: r.start_time,
+                        end: r.end_time,
+                    })
+                    .collect(),
+            };
+            serde_json::to_string_pretty(&output).context("Failed to serialize JSON")?
+        }
+    };
+
+    if let Some(ref output_path) = args.output {
+        fs::write(output_path, &output_text)
+            .with_context(|| format!("Failed to write output: {}", output_path.display()))?;
+        eprintln!("Output written to: {}", output_path.display());
+    } else {
+        let stdout = io::stdout();
+        let mut handle = stdout.lock();
+        writeln!(handle, "{}", output_text.trim_end())?;
+    }
+
+    if let Some(ref dir) = args.split_speakers {
+        let paths = write_speaker_tracks(&prepared, &turns, DiarizationMethod::Sortformer, dir, &args.input)?;
+        eprintln!("Wrote {} speaker track(s) to {}", paths.len(), dir.display());
+    }
+
+    Ok(())
+}
+
+/// One line per turn, in time order: `00:01:02.500 - 00:01:05.000  Speaker 1`.
+fn format_turns(turns: &[SpeakerTurn]) -> String {
+    let mut sorted: Vec<&SpeakerTurn> = turns.iter().collect();
+    sorted.sort_by(|a, b| a.start.total_cmp(&b.start));
+    sorted
+        .iter()
+        .map(|t| {
+            format!(
+                "{} - {}  {}\n",
+                format_timestamp(t.start),
+                format_timestamp(t.end),
+                t.speaker
+            )
+        })
+        .collect()
+}
+
+/// `HH:MM:SS.mmm`
+fn format_timestamp(secs: f64) -> String {
+    let millis = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}.{:03}",
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+/// Talk time and turn count per speaker, ordered by speaker name.
+fn summarize_speakers(turns: &[SpeakerTurn]) -> Vec<SpeakerSummary> {
+    let mut totals: BTreeMap<&str, (f64, usize)> = BTreeMap::new();
+    for turn in turns {
+        let entry = totals.entry(turn.speaker.as_str()).or_default();
+        entry.0 += (turn.end - turn.start).max(0.0);
+        entry.1 += 1;
+    }
+    totals
+        .into_iter()
+        .map(|(speaker, (talk_time_secs, turn_count))| SpeakerSummary {
+            speaker: speaker.to_string(),
+            talk_time_secs,
+            turn_count,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(speaker: &str, start: f64, end: f64) -> SpeakerTurn {
+        SpeakerTurn {
+            speaker: speaker.to_string(),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn test_format_turns_sorted_with_timestamps() {
+        let turns = vec![turn("Speaker 2", 62.5, 65.0), turn("Speaker 1", 0.0, 1.25)];
+        assert_eq!(
+            format_turns(&turns),
+            "00:00:00.000 - 00:00:01.250  Speaker 1\n00:01:02.500 - 00:01:05.000  Speaker 2\n"
+        );
+        assert_eq!(format_timestamp(3723.0), "01:02:03.000");
+    }
+
+    #[test]
+    fn test_summarize_speakers() {
+        let turns = vec![
+            turn("Speaker 1", 0.0, 2.0),
+            turn("Speaker 2", 2.0, 3.0),
+            turn("Speaker 1", 4.0, 5.5),
+        ];
+        assert_eq!(
+            summarize_speakers(&turns),
+            vec![
+                SpeakerSummary {
+                    speaker: "Speaker 1".to_string(),
+                    talk_time_secs: 3.5,
+                    turn_count: 2,
+                },
+                SpeakerSummary {
+                    speaker: "Speaker 2".to_string(),
+                    talk_time_secs: 1.0,
+                    turn_count: 1,
+                },
+            ]
+        );
+    }
+}
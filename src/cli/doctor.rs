@@ -0,0 +1,67 @@
+//! `doctor` subcommand: what the app knows about its own health.
+//!
+//! Prints the hardware and whisper.cpp build, and, when the app is running,
+//! its loaded model and the failures it counted this session (pastes,
+//! history saves, segments and clipboard writes that went wrong without an
+//! error dialog).
+
+use crate::infrastructure::failures::FailureCounts;
+use crate::infrastructure::hardware::{CpuFeatures, HardwareInfo};
+use crate::infrastructure::ipc::IpcClient;
+use crate::transcription::whisper::build_cpu_features;
+use anyhow::Result;
+
+/// Run the doctor subcommand.
+pub fn run() -> Result<()> {
+    let hardware = HardwareInfo::detect();
+    let cpu = CpuFeatures::detect();
+    let build = build_cpu_features();
+    println!(
+        "Hardware: {} cores, {:.1} GB RAM",
+        hardware.cpu_cores,
+        hardware.total_ram_bytes as f64 / (1024.0 * 1024.0 * 1024.0)
+    );
+    println!("CPU extensions: {}", feature_list(&cpu));
+    println!("whisper.cpp build: {}", feature_list(&build));
+    let unused = cpu.unused_by(&build);
+    if !unused.is_empty() {
+        println!(
+            "  Not used by the build: {} (rebuild with install.sh)",
+            unused.join(", ")
+        );
+    }
+
+    let Some(client) = IpcClient::connect() else {
+        println!("App: not running (failure counters exist only while it runs)");
+        return Ok(());
+    };
+    let status = client.status()?;
+    println!(
+        "App: running, model {}",
+        status.model.as_deref().unwrap_or("not loaded")
+    );
+    let failures = client.failures()?;
+    println!("Silent failures this session: {}", failures.total());
+    for (name, count) in failure_lines(&failures) {
+        println!("  {:<20} {}", name, count);
+    }
+    Ok(())
+}
+
+fn feature_list(features: &CpuFeatures) -> String {
+    let names = features.names();
+    if names.is_empty() {
+        "baseline only".to_string()
+    } else {
+        names.join(", ")
+    }
+}
+
+fn failure_lines(failures: &FailureCounts) -> [(&'static str, u64); 4] {
+    [
+        ("paste", failures.paste),
+        ("history save", failures.history_save),
+        ("segment send", failures.segment_send),
+        ("clipboard", failures.clipboard),
+    ]
+}
@@ -0,0 +1,99 @@
+//! `history` subcommand: move the history between storage backends, export
+//! it and import other history files.
+
+use crate::app::config::{load_config, save_config};
+use crate::cli::args::{HistoryArgs, HistoryCommand, HistoryExportFormat};
+use crate::domain::traits::HistoryRepository;
+use crate::history::{
+    convert_history, create_store, format_history, load_history, read_history_file, save_history, ExportFormat,
+    HistoryBackend,
+};
+use crate::infrastructure::ipc::IpcClient;
+use anyhow::{Context, Result};
+use std::fs;
+
+/// Run the history subcommand.
+pub fn run(args: HistoryArgs) -> Result<()> {
+    match args.command {
+        HistoryCommand::Convert { to, from } => {
+            // The app would keep saving to the old backend and lose the converted entries
+            if IpcClient::connect().is_some() {
+                anyhow::bail!("The app is running; quit it before converting the history");
+            }
+            let mut config = load_config()?;
+            let from = match from {
+                Some(format) => HistoryBackend::parse(format.config_name()),
+                None => HistoryBackend::parse(&config.history_backend),
+            };
+            let to = HistoryBackend::parse(to.config_name());
+            if from == to {
+                anyhow::bail!("History is already stored as {}", to.as_str());
+            }
+
+            let source = create_store(from);
+            let target = create_store(to);
+            let count = convert_history(source.as_ref(), target.as_ref())?;
+            println!(
+                "Converted {} entries: {} -> {}",
+                count,
+                source.path().display(),
+                target.path().display()
+            );
+
+            config.history_backend = to.as_str().to_string();
+            save_config(&config)?;
+            println!("history_backend set to \"{}\"", to.as_str());
+            println!("The old history file was kept; delete it once the conversion is checked.");
+            Ok(())
+        }
+        HistoryCommand::Export { format, output } => {
+            let config = load_config()?;
+            let history = load_history(HistoryBackend::parse(&config.history_backend))?;
+            let entries: Vec<_> = history.entries.iter().collect();
+            let content = format_history(&entries, export_format(format))?;
+            match output {
+                Some(path) => {
+                    fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+                    eprintln!("Exported {} entries to {}", entries.len(), path.display());
+                }
+                None => print!("{}", content),
+            }
+            Ok(())
+        }
+        HistoryCommand::Import { file } => {
+            // The app would overwrite the imported entries on its next save
+            if IpcClient::connect().is_some() {
+                anyhow::bail!("The app is running; quit it before importing history");
+            }
+            let config = load_config()?;
+            if config.read_only_history {
+                anyhow::bail!("The history is read-only (read_only_history = true)");
+            }
+            let entries = read_history_file(&file)?;
+            let total = entries.len();
+            let mut history = load_history(HistoryBackend::parse(&config.history_backend))?;
+            history.backups = config.history_backups;
+            let added = history.import(entries);
+            if added > 0 {
+                save_history(&history)?;
+            }
+            println!(
+                "Imported {} of {} entries from {} ({} already in the history)",
+                added,
+                total,
+                file.display(),
+                total - added
+            );
+            Ok(())
+        }
+    }
+}
+
+fn export_format(format: HistoryExportFormat) -> ExportFormat {
+    match format {
+        HistoryExportFormat::Text => ExportFormat::Text,
+        HistoryExportFormat::Markdown => ExportFormat::Markdown,
+        HistoryExportFormat::Json => ExportFormat::Json,
+        HistoryExportFormat::Csv => ExportFormat::Csv,
+    }
+}
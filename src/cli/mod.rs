@@ -1,10 +1,21 @@
 //! CLI mode for voice-dictation.
 //!
-//! Provides command-line transcription of WAV files without requiring GTK/display server.
+//! Provides command-line transcription of audio files without requiring GTK/display server.
 
+pub mod align_srt;
 pub mod args;
+pub mod bench;
+pub mod benchmark;
 pub mod denoise_eval;
+pub mod diarize;
+pub mod doctor;
+pub mod history;
+pub mod models;
+pub mod record;
+pub mod script;
+pub mod stats;
 pub mod transcribe;
+pub mod vad_eval;
 pub mod wav_reader;
 
 pub use args::Cli;
@@ -0,0 +1,42 @@
+//! `models` subcommand: list catalog models and add or remove custom ones.
+
+use crate::cli::args::{ModelsArgs, ModelsCommand};
+use crate::infrastructure::models::{download_custom_model, format_size, import_local_model, remove_custom_model};
+use anyhow::{Context, Result};
+use std::io::Write;
+
+/// Run the models subcommand.
+pub fn run(args: ModelsArgs) -> Result<()> {
+    match args.command {
+        None | Some(ModelsCommand::List) => crate::cli::transcribe::list_models(),
+        Some(ModelsCommand::Import { path, name }) => {
+            if !path.exists() {
+                anyhow::bail!("Model file not found: {}", path.display());
+            }
+            eprintln!("Importing and validating {}...", path.display());
+            let info = import_local_model(&path, name.as_deref())?;
+            println!("Imported {} ({})", info.filename, format_size(info.size_bytes));
+            Ok(())
+        }
+        Some(ModelsCommand::Add { url, name }) => {
+            let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
+            let info = rt.block_on(download_custom_model(&url, name.as_deref(), |downloaded, total| {
+                if total > 0 {
+                    eprint!("\rDownloading: {:>3}%", downloaded * 100 / total);
+                } else {
+                    eprint!("\rDownloading: {}", format_size(downloaded));
+                }
+                let _ = std::io::stderr().flush();
+            }));
+            eprintln!();
+            let info = info?;
+            println!("Added {} ({})", info.filename, format_size(info.size_bytes));
+            Ok(())
+        }
+        Some(ModelsCommand::Remove { filename }) => {
+            remove_custom_model(&filename)?;
+            println!("Removed {}", filename);
+            Ok(())
+        }
+    }
+}
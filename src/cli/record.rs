@@ -0,0 +1,213 @@
+//! `record` subcommand: capture the microphone without the GUI.
+//!
+//! Records for a fixed time or until Ctrl+C with the same capture chain as
+//! the app (high-pass filter, AGC, resampler from the config), saves the
+//! audio and, with `--transcribe`, passes the file on to the `transcribe`
+//! pipeline. Meant for headless machines and scripts.
+
+use crate::cli::args::{RecordArgs, TranscribeArgs};
+use crate::cli::transcribe::{self, load_config_from};
+use crate::domain::types::{AudioQuality, CaptureDeviceStatus, LevelReading};
+use crate::infrastructure::recordings::save_mono_recording;
+use crate::recording::core::WHISPER_SAMPLE_RATE;
+use crate::recording::microphone::{AudioRecorder, CaptureProcessing};
+use anyhow::{bail, Context, Result};
+use clap::Parser;
+use std::ffi::OsStr;
+use std::fs;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often the level line and the device status are refreshed
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Run the record subcommand.
+pub fn run(args: RecordArgs) -> Result<()> {
+    if args.duration.is_some_and(|secs| secs <= 0.0 || !secs.is_finite()) {
+        bail!("--duration must be a positive number of seconds");
+    }
+    let config = load_config_from(args.config.as_deref())?;
+
+    let samples = record(&args, CaptureProcessing::from_config(&config))?;
+    if samples.is_empty() {
+        bail!("Nothing was recorded");
+    }
+    let duration_secs = samples.len() as f64 / WHISPER_SAMPLE_RATE as f64;
+
+    let Some(output) = &args.output else {
+        // --transcribe without -o: the recording is only needed until transcribed
+        let path = std::env::temp_dir().join(format!("s2t-record-{}.wav", uuid::Uuid::new_v4()));
+        save_mono_recording(&samples, &path)?;
+        drop(samples);
+        let result = transcribe::run(transcribe_args(&args, &path));
+        let _ = fs::remove_file(&path);
+        return result;
+    };
+
+    save_mono_recording(&samples, output)?;
+    eprintln!("Saved {:.1}s to {}", duration_secs, output.display());
+    if args.transcribe {
+        drop(samples);
+        transcribe::run(transcribe_args(&args, output))?;
+    }
+    Ok(())
+}
+
+/// Record until the duration is up or Ctrl+C, showing the level on a terminal.
+fn record(args: &RecordArgs, processing: CaptureProcessing) -> Result<Vec<f32>> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let stop_for_signal = stop.clone();
+    ctrlc::set_handler(move || stop_for_signal.store(true, Ordering::SeqCst))
+        .context("Failed to set Ctrl+C handler")?;
+
+    let recorder = AudioRecorder::with_processing(processing);
+    recorder.start_recording()?;
+    match args.duration {
+        Some(secs) => eprintln!("Recording for {}s (Ctrl+C to stop early)...", secs),
+        None => eprintln!("Recording... press Ctrl+C to stop"),
+    }
+
+    let started = Instant::now();
+    let limit = args.duration.map(Duration::from_secs_f64);
+    let show_meter = std::io::stderr().is_terminal();
+    let mut device_status = CaptureDeviceStatus::Active;
+    while !stop.load(Ordering::SeqCst) && limit.is_none_or(|limit| started.elapsed() < limit) {
+        std::thread::sleep(POLL_INTERVAL);
+
+        let status = recorder.device_status();
+        if status != device_status {
+            if show_meter {
+                eprintln!();
+            }
+            match &status {
+                CaptureDeviceStatus::Active => {}
+                CaptureDeviceStatus::Switched(name) => {
+                    eprintln!("Microphone disconnected, continuing with \"{}\"", name)
+                }
+                CaptureDeviceStatus::Lost => {
+                    eprintln!("Microphone disconnected, recording paused until it is back (Ctrl+C to stop)")
+                }
+            }
+            device_status = status;
+        }
+        if show_meter {
+            eprint!("\r{}", meter_line(started.elapsed(), recorder.level()));
+            let _ = std::io::stderr().flush();
+        }
+    }
+    if show_meter {
+        eprintln!();
+    }
+
+    let (samples, completion_rx) = recorder.stop_recording();
+    if let Some(completion_rx) = completion_rx {
+        let _ = completion_rx.recv_blocking();
+    }
+    if let Some(quality) = recorder.audio_quality() {
+        eprintln!("Warning: {}", quality_warning(&quality));
+    }
+    Ok(samples)
+}
+
+/// One line of progress: elapsed time, peak level and the clipping flag.
+fn meter_line(elapsed: Duration, level: LevelReading) -> String {
+    let peak = if level.peak_dbfs <= LevelReading::FLOOR_DBFS {
+        format!("<{:.0}", LevelReading::FLOOR_DBFS)
+    } else {
+        format!("{:.0}", level.peak_dbfs)
+    };
+    format!(
+        "{:>7.1}s  peak {:>4} dBFS  {:<4}",
+        elapsed.as_secs_f64(),
+        peak,
+        if level.clipped { "CLIP" } else { "" }
+    )
+}
+
+fn quality_warning(quality: &AudioQuality) -> String {
+    let mut problems = Vec::new();
+    if let Some(percent) = quality.clipped_percent {
+        problems.push(format!("input clipped in {:.0}% of the recording", percent));
+    }
+    if let Some(dbfs) = quality.too_quiet_dbfs {
+        problems.push(format!("input very quiet (loudest {:.0} dBFS)", dbfs));
+    }
+    problems.join(", ")
+}
+
+/// `transcribe` arguments for the saved recording, with its defaults for
+/// everything `record` does not set.
+fn transcribe_args(args: &RecordArgs, recording: &Path) -> TranscribeArgs {
+    let mut transcribe = TranscribeArgs::parse_from([OsStr::new("transcribe"), recording.as_os_str()]);
+    transcribe.output = args.transcript.clone();
+    transcribe.config = args.config.clone();
+    transcribe.model = args.model.clone();
+    transcribe.language = args.language.clone();
+    transcribe.backend = args.backend;
+    transcribe.format = args.format;
+    transcribe.denoise = args.denoise;
+    // A new recording is never in the cache
+    transcribe.no_cache = true;
+    transcribe
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::args::{OutputFormat, SttBackend};
+
+    #[test]
+    fn test_meter_line() {
+        let level = LevelReading {
+            peak_dbfs: -12.4,
+            rms_dbfs: -20.0,
+            clipped: false,
+        };
+        assert_eq!(
+            meter_line(Duration::from_millis(3300), level),
+            "    3.3s  peak  -12 dBFS      "
+        );
+
+        let clipped = LevelReading {
+            peak_dbfs: 0.0,
+            rms_dbfs: -3.0,
+            clipped: true,
+        };
+        assert!(meter_line(Duration::ZERO, clipped).ends_with("CLIP"));
+        assert!(meter_line(Duration::ZERO, LevelReading::SILENT).contains("<-60"));
+    }
+
+    #[test]
+    fn test_quality_warning_lists_problems() {
+        let quality = AudioQuality {
+            clipped_percent: Some(12.3),
+            too_quiet_dbfs: None,
+        };
+        assert_eq!(quality_warning(&quality), "input clipped in 12% of the recording");
+    }
+
+    #[test]
+    fn test_transcribe_args_carry_record_options() {
+        let args = RecordArgs::parse_from([
+            "record",
+            "--transcribe",
+            "-l",
+            "en",
+            "-f",
+            "json",
+            "--transcript",
+            "out.json",
+        ]);
+        let transcribe = transcribe_args(&args, Path::new("/tmp/rec.wav"));
+        assert_eq!(transcribe.input, Path::new("/tmp/rec.wav"));
+        assert_eq!(transcribe.language.as_deref(), Some("en"));
+        assert!(matches!(transcribe.format, OutputFormat::Json));
+        assert!(matches!(transcribe.backend, SttBackend::Whisper));
+        assert_eq!(transcribe.output.as_deref(), Some(Path::new("out.json")));
+        assert!(transcribe.no_cache);
+        assert_eq!(transcribe.max_segment_secs, 300);
+    }
+}
@@ -0,0 +1,477 @@
+//! `script` subcommand: Rhai automation scripts.
+//!
+//! Scripts can query history, transcribe audio files with the configured
+//! model and run the text post-processors — custom automation without
+//! recompiling. The engine itself has no file or network access; the
+//! functions that touch either are refused unless the user grants
+//! permission on the command line (`--allow-read`, `--allow-write`,
+//! `--allow-net`). `import` of other script files is disabled.
+//!
+//! Script API:
+//! - `history()`, `history_search(query)` — entries, newest first, as maps
+//!   with `id`, `text`, `timestamp`, `duration`, `language`, `speakers`, `recording`
+//! - `transcribe(path)`, `transcribe(path, language)` — needs read access;
+//!   results are cached like the `transcribe` command's
+//! - `postprocess(text, language)` — configured punctuation restoration
+//! - `punctuate(text, language)` — rule-based punctuator regardless of config
+//! - `annotate_names(text)` — alternative spellings from the correction dictionary
+//! - `read_file(path)` / `write_file(path, text)` — need read/write access
+//! - `http_post(url, body)` — needs `--allow-net`
+//! - `ARGS` — arguments after `--`
+
+use crate::app::config::Config;
+use crate::cli::args::{ChannelMode, ScriptArgs, ScriptCommand, ScriptPermissionArgs};
+use crate::cli::transcribe::{build_chunker, load_config_from, resolve_tdt_model, resolve_whisper_model};
+use crate::cli::wav_reader::{prepare_for_whisper, read_audio};
+use crate::history::{load_history, HistoryBackend, HistoryEntry};
+use crate::infrastructure::ipc::IpcClient;
+use crate::recording::denoise::{denoise_label, DenoiseSettings};
+use crate::transcription::cache::{cache_key, TranscriptionCache};
+use crate::transcription::corrections::CorrectionDictionary;
+use crate::transcription::postprocess::{create_post_processor, RuleBasedPunctuator, TextPostProcessor};
+use crate::transcription::TranscriptionService;
+use anyhow::{anyhow, Context, Result};
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, Map, Scope};
+use std::cell::RefCell;
+use std::fs;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// File and network access granted to a script.
+#[derive(Debug, Clone, Default)]
+pub struct Permissions {
+    read: Vec<PathBuf>,
+    write: Vec<PathBuf>,
+    net: bool,
+}
+
+impl Permissions {
+    /// Build from command-line flags. Granted directories must exist.
+    pub fn from_args(args: &ScriptPermissionArgs) -> Result<Self> {
+        let canonical = |dirs: &[PathBuf]| -> Result<Vec<PathBuf>> {
+            dirs.iter()
+                .map(|d| {
+                    d.canonicalize()
+                        .with_context(|| format!("Cannot grant access to {}", d.display()))
+                })
+                .collect()
+        };
+        Ok(Self {
+            read: canonical(&args.allow_read)?,
+            write: canonical(&args.allow_write)?,
+            net: args.allow_net,
+        })
+    }
+
+    /// Resolve `path` for reading. Writable directories are readable too.
+    pub fn check_read(&self, path: &str) -> Result<PathBuf> {
+        let resolved = Path::new(path)
+            .canonicalize()
+            .with_context(|| format!("File not found: {}", path))?;
+        if within(&resolved, &self.read) || within(&resolved, &self.write) {
+            Ok(resolved)
+        } else {
+            Err(anyhow!("Read access denied: {} (grant it with --allow-read)", path))
+        }
+    }
+
+    /// Resolve `path` for writing. The file may not exist yet, its directory must.
+    ///
+    /// A symlink is refused: writing through it could leave the granted directories.
+    pub fn check_write(&self, path: &str) -> Result<PathBuf> {
+        let path = Path::new(path);
+        let name = path
+            .file_name()
+            .ok_or_else(|| anyhow!("Not a file path: {}", path.display()))?;
+        let parent = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p,
+            _ => Path::new("."),
+        };
+        let resolved = parent
+            .canonicalize()
+            .with_context(|| format!("Directory not found: {}", parent.display()))?
+            .join(name);
+        if fs::symlink_metadata(&resolved).is_ok_and(|meta| meta.file_type().is_symlink()) {
+            return Err(anyhow!("Write access denied: {} is a symlink", path.display()));
+        }
+        if within(&resolved, &self.write) {
+            Ok(resolved)
+        } else {
+            Err(anyhow!(
+                "Write access denied: {} (grant it with --allow-write)",
+                path.display()
+            ))
+        }
+    }
+
+    pub fn check_net(&self, url: &str) -> Result<()> {
+        if self.net {
+            Ok(())
+        } else {
+            Err(anyhow!("Network access denied: {} (grant it with --allow-net)", url))
+        }
+    }
+}
+
+/// Operations a script may run: about ten seconds of work
+const MAX_OPERATIONS: u64 = 50_000_000;
+
+/// Longest string a script may build, in bytes; long transcripts fit easily
+const MAX_STRING_SIZE: usize = 16 * 1024 * 1024;
+
+fn within(path: &Path, roots: &[PathBuf]) -> bool {
+    roots.iter().any(|root| path.starts_with(root))
+}
+
+fn script_error(e: anyhow::Error) -> Box<EvalAltResult> {
+    format!("{:#}", e).into()
+}
+
+/// Run the script subcommand.
+pub fn run(args: ScriptArgs) -> Result<()> {
+    match args.command {
+        ScriptCommand::Run {
+            path,
+            permissions,
+            config,
+            args,
+        } => {
+            let engine = build_engine(
+                load_config_from(config.as_deref())?,
+                Permissions::from_args(&permissions)?,
+            );
+            let mut scope = Scope::new();
+            let script_args: Array = args.into_iter().map(Dynamic::from).collect();
+            scope.push_constant("ARGS", script_args);
+
+            engine
+                .run_file_with_scope(&mut scope, path.clone())
+                .map_err(|e| anyhow!("{}: {}", path.display(), e))
+        }
+        ScriptCommand::Console { permissions, config } => {
+            let engine = build_engine(
+                load_config_from(config.as_deref())?,
+                Permissions::from_args(&permissions)?,
+            );
+            console(&engine)
+        }
+    }
+}
+
+/// Interactive console: evaluate one line at a time, keeping variables.
+fn console(engine: &Engine) -> Result<()> {
+    let mut scope = Scope::new();
+    scope.push_constant("ARGS", Array::new());
+    let stdin = io::stdin();
+
+    loop {
+        eprint!("s2t> ");
+        io::stderr().flush()?;
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line)? == 0 {
+            eprintln!();
+            return Ok(());
+        }
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if line == "quit" || line == "exit" {
+            return Ok(());
+        }
+
+        match engine.eval_with_scope::<Dynamic>(&mut scope, line) {
+            Ok(value) if value.is_unit() => {}
+            Ok(value) => println!("{}", value),
+            Err(e) => eprintln!("Error: {}", e),
+        }
+    }
+}
+
+/// Create an engine with the s2t API registered.
+///
+/// Runaway scripts are stopped by the engine's limits instead of hanging
+/// or exhausting memory.
+fn build_engine(config: Config, permissions: Permissions) -> Engine {
+    let mut engine = Engine::new();
+    engine.set_module_resolver(DummyModuleResolver::new());
+    engine.set_max_operations(MAX_OPERATIONS);
+    engine.set_max_call_levels(64);
+    engine.set_max_expr_depths(64, 32);
+    engine.set_max_string_size(MAX_STRING_SIZE);
+    engine.set_max_array_size(100_000);
+    engine.set_max_map_size(10_000);
+
+    let config = Rc::new(config);
+    let permissions = Rc::new(permissions);
+
+    // History
+    let backend = HistoryBackend::parse(&config.history_backend);
+    engine.register_fn("history", move || -> ScriptResult<Array> {
+        let entries = history_entries(backend).map_err(script_error)?;
+        Ok(entries.iter().map(entry_to_map).collect())
+    });
+    engine.register_fn("history_search", move |query: &str| -> ScriptResult<Array> {
+        let entries = history_entries(backend).map_err(script_error)?;
+        let query = query.to_lowercase();
+        Ok(entries
+            .iter()
+            .filter(|e| e.text.to_lowercase().contains(&query))
+            .map(entry_to_map)
+            .collect())
+    });
+
+    // Transcription; the model is loaded on first use
+    let service: Rc<RefCell<Option<TranscriptionService>>> = Rc::new(RefCell::new(None));
+    let transcribe = {
+        let (config, permissions, service) = (config.clone(), permissions.clone(), service.clone());
+        move |path: &str, language: &str| -> ScriptResult<String> {
+            let path = permissions.check_read(path).map_err(script_error)?;
+            transcribe_file(&config, &service, &path, language).map_err(script_error)
+        }
+    };
+    let transcribe_default = {
+        let (config, transcribe) = (config.clone(), transcribe.clone());
+        move |path: &str| transcribe(path, &config.language)
+    };
+    engine.register_fn("transcribe", transcribe);
+    engine.register_fn("transcribe", transcribe_default);
+
+    // Post-processing
+    let punctuation = config.punctuation_restoration.clone();
+    engine.register_fn("postprocess", move |text: &str, language: &str| -> String {
+        match create_post_processor(&punctuation) {
+            Some(processor) => processor.process(text, language),
+            None => text.to_string(),
+        }
+    });
+    engine.register_fn("punctuate", |text: &str, language: &str| -> String {
+        RuleBasedPunctuator::new().process(text, language)
+    });
+    engine.register_fn("annotate_names", |text: &str| -> ScriptResult<String> {
+        let dictionary = CorrectionDictionary::load_default().map_err(script_error)?;
+        Ok(dictionary.annotate_alternatives(text))
+    });
+
+    // Files and network, behind permissions
+    let read_permissions = permissions.clone();
+    engine.register_fn("read_file", move |path: &str| -> ScriptResult<String> {
+        let path = read_permissions.check_read(path).map_err(script_error)?;
+        fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))
+            .map_err(script_error)
+    });
+    let write_permissions = permissions.clone();
+    engine.register_fn("write_file", move |path: &str, text: &str| -> ScriptResult<()> {
+        let path = write_permissions.check_write(path).map_err(script_error)?;
+        fs::write(&path, text)
+            .with_context(|| format!("Failed to write {}", path.display()))
+            .map_err(script_error)
+    });
+    engine.register_fn("http_post", move |url: &str, body: &str| -> ScriptResult<String> {
+        permissions.check_net(url).map_err(script_error)?;
+        http_post(url, body).map_err(script_error)
+    });
+
+    engine
+}
+
+/// Script-facing representation of a history entry.
+/// History entries; from the running app when there is one, as it may not have saved yet.
+fn history_entries(backend: HistoryBackend) -> Result<Vec<HistoryEntry>> {
+    match IpcClient::connect() {
+        Some(client) => client.history(),
+        None => Ok(load_history(backend)?.entries),
+    }
+}
+
+fn entry_to_map(entry: &HistoryEntry) -> Dynamic {
+    let mut map = Map::new();
+    map.insert("id".into(), entry.id.clone().into());
+    map.insert("text".into(), entry.text.clone().into());
+    map.insert("timestamp".into(), entry.timestamp.to_rfc3339().into());
+    map.insert("duration".into(), (entry.duration_secs as f64).into());
+    map.insert("language".into(), entry.language.clone().into());
+    let speakers: Array = entry.speakers.iter().cloned().map(Dynamic::from).collect();
+    map.insert("speakers".into(), speakers.into());
+    map.insert(
+        "recording".into(),
+        entry.recording_path.clone().map(Dynamic::from).unwrap_or(Dynamic::UNIT),
+    );
+    map.into()
+}
+
+fn transcribe_file(
+    config: &Config,
+    service: &RefCell<Option<TranscriptionService>>,
+    path: &Path,
+    language: &str,
+) -> Result<String> {
+    let cache = TranscriptionCache::from_config(config);
+    let key = match cache {
+        Some(_) => Some(cache_key(path, &model_id(config)?, language, &cache_params(config))?),
+        None => None,
+    };
+    if let (Some(cache), Some(key)) = (&cache, &key) {
+        if let Some(text) = cache.get::<String>(key) {
+            return Ok(text);
+        }
+    }
+
+    let audio = read_audio(path)?;
+    let prepared = prepare_for_whisper(&audio, ChannelMode::Mix, DenoiseSettings::if_enabled(&config).as_ref())?;
+
+    let mut service = service.borrow_mut();
+    if service.is_none() {
+        *service = Some(load_service(config)?);
+    }
+    let service = service.as_ref().expect("service loaded above");
+
+    let chunker = build_chunker(config.max_segment_secs, config);
+    let text = chunker
+        .transcribe_chunked(&prepared.samples, language, service)?
+        .trim()
+        .to_string();
+    if let (Some(cache), Some(key)) = (&cache, &key) {
+        if let Err(e) = cache.put(key, &text) {
+            eprintln!("Warning: failed to cache transcription: {:#}", e);
+        }
+    }
+    Ok(text)
+}
+
+/// Model identity for the cache key: the resolved model path.
+fn model_id(config: &Config) -> Result<String> {
+    let path = if config.stt_backend == "tdt" {
+        resolve_tdt_model(None, config)?
+    } else {
+        resolve_whisper_model(None, config)?
+    };
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Settings besides model and language that change a script transcription.
+fn cache_params(config: &Config) -> String {
+    format!(
+        "script backend={} denoise={} max_segment={} punctuation={} verbatim={} vad={}/{}/{}/{}/{}",
+        config.stt_backend,
+        denoise_label(DenoiseSettings::if_enabled(config).as_ref()),
+        config.max_segment_secs,
+        config.punctuation_restoration,
+        config.verbatim_transcription,
+        config.vad_engine,
+        config.effective_vad_silence_threshold_ms(),
+        config.effective_vad_min_speech_ms(),
+        config.silero_threshold,
+        config.vad_aggressiveness,
+    )
+}
+
+/// Load the configured backend, as the GUI would.
+fn load_service(config: &Config) -> Result<TranscriptionService> {
+    if config.stt_backend == "tdt" {
+        let model_dir = resolve_tdt_model(None, config)?;
+        eprintln!("Loading TDT model from: {}", model_dir.display());
+        let mut service = TranscriptionService::with_tdt(&model_dir.to_string_lossy())?;
+        if let Some(processor) = create_post_processor(&config.punctuation_restoration) {
+            service.add_post_processor(processor);
+        }
+        service.set_verbatim(config.verbatim_transcription);
+        Ok(service)
+    } else {
+        let model_path = resolve_whisper_model(None, config)?;
+        eprintln!("Loading Whisper model: {}", model_path.display());
+        let mut service = TranscriptionService::with_model(&model_path.to_string_lossy())?;
+        service.set_verbatim(config.verbatim_transcription);
+        Ok(service)
+    }
+}
+
+fn http_post(url: &str, body: &str) -> Result<String> {
+    let rt = tokio::runtime::Runtime::new().context("Failed to create tokio runtime")?;
+    rt.block_on(async {
+        let response = reqwest::Client::new()
+            .post(url)
+            .body(body.to_string())
+            .send()
+            .await
+            .with_context(|| format!("Request failed: {}", url))?
+            .error_for_status()?;
+        response.text().await.context("Failed to read response")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("s2t_script_{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir.canonicalize().unwrap()
+    }
+
+    #[test]
+    fn test_permissions_deny_by_default() {
+        let dir = temp_dir();
+        let file = dir.join("a.txt");
+        fs::write(&file, "x").unwrap();
+        let perms = Permissions::default();
+
+        assert!(perms.check_read(file.to_str().unwrap()).is_err());
+        assert!(perms.check_write(file.to_str().unwrap()).is_err());
+        assert!(perms.check_net("https://example.com").is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_permissions_grant_directories() {
+        let dir = temp_dir();
+        let file = dir.join("a.txt");
+        fs::write(&file, "x").unwrap();
+        let perms = Permissions {
+            read: Vec::new(),
+            write: vec![dir.clone()],
+            net: true,
+        };
+
+        assert_eq!(perms.check_read(file.to_str().unwrap()).unwrap(), file);
+        let new_file = dir.join("new.txt");
+        assert_eq!(perms.check_write(new_file.to_str().unwrap()).unwrap(), new_file);
+        assert!(perms.check_net("https://example.com").is_ok());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_permissions_block_escaping_the_granted_directory() {
+        let dir = temp_dir();
+        let inner = dir.join("inner");
+        fs::create_dir_all(&inner).unwrap();
+        fs::write(dir.join("secret.txt"), "x").unwrap();
+        let perms = Permissions {
+            read: vec![inner.clone()],
+            write: vec![inner.clone()],
+            net: false,
+        };
+
+        let escaped = inner.join("../secret.txt");
+        assert!(perms.check_read(escaped.to_str().unwrap()).is_err());
+        assert!(perms.check_write(escaped.to_str().unwrap()).is_err());
+
+        let link = inner.join("link.txt");
+        std::os::unix::fs::symlink(dir.join("secret.txt"), &link).unwrap();
+        assert!(perms.check_write(link.to_str().unwrap()).is_err());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_engine_stops_endless_loops() {
+        let engine = build_engine(Config::default(), Permissions::default());
+        assert!(engine.run("loop {}").is_err());
+        assert!(engine.run("let s = \"x\"; loop { s += s; }").is_err());
+    }
+}
@@ -0,0 +1,95 @@
+//! `stats` subcommand: usage statistics computed from the history.
+
+use crate::app::config::load_config;
+use crate::cli::args::StatsArgs;
+use crate::history::{load_history, HistoryBackend, HistoryStats};
+use anyhow::Result;
+use chrono::{Duration, Utc};
+
+/// Days with entries shown in the words-per-day chart
+const CHART_DAYS: usize = 14;
+
+/// Width of the longest bar in the chart
+const BAR_WIDTH: usize = 40;
+
+/// Run the stats subcommand.
+pub fn run(args: StatsArgs) -> Result<()> {
+    let config = load_config()?;
+    let history = load_history(HistoryBackend::parse(&config.history_backend))?;
+    let since = args.days.map(|days| Utc::now() - Duration::days(days.into()));
+    let stats = HistoryStats::from_entries(
+        history
+            .entries
+            .iter()
+            .filter(|e| since.is_none_or(|since| e.timestamp >= since)),
+    );
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        print!("{}", format_stats(&stats));
+    }
+    Ok(())
+}
+
+fn format_stats(stats: &HistoryStats) -> String {
+    if stats.entries == 0 {
+        return "No history entries\n".to_string();
+    }
+    let mut out = format!("Entries:         {}\n", stats.entries);
+    out.push_str(&format!("Dictation time:  {}\n", stats.formatted_total_time()));
+    out.push_str(&format!("Words:           {}", stats.total_words));
+    if let Some(average) = stats.average_words_per_day() {
+        out.push_str(&format!(" ({:.0} per day with entries)", average));
+    }
+    out.push('\n');
+    if let Some((language, count)) = stats.most_used_language() {
+        out.push_str(&format!("Top language:    {} ({} entries)\n", language, count));
+    }
+    if let Some((model, count)) = stats.most_used_model() {
+        out.push_str(&format!("Top model:       {} ({} entries)\n", model, count));
+    }
+    match stats.average_rtf {
+        Some(rtf) => out.push_str(&format!(
+            "Average RTF:     {:.2} ({} entries measured)\n",
+            rtf, stats.measured_entries
+        )),
+        None => out.push_str("Average RTF:     not measured\n"),
+    }
+
+    let days = stats.recent_days(CHART_DAYS);
+    let most = days.iter().map(|(_, words)| *words).max().unwrap_or(0).max(1);
+    out.push_str(&format!("\nWords per day (last {} days with entries):\n", days.len()));
+    for (day, words) in days {
+        out.push_str(&format!(
+            "  {}  {:>6} {}\n",
+            day,
+            words,
+            "#".repeat(words * BAR_WIDTH / most)
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::HistoryEntry;
+
+    #[test]
+    fn test_format_stats() {
+        let mut entry = HistoryEntry::new("раз два три чотири".to_string(), 3725.0, "uk".to_string());
+        entry.model = Some("Base".to_string());
+        entry.processing_secs = Some(745.0);
+        let stats = HistoryStats::from_entries([&entry]);
+
+        let text = format_stats(&stats);
+        assert!(text.contains("Dictation time:  1:02:05"));
+        assert!(text.contains("Words:           4 (4 per day with entries)"));
+        assert!(text.contains("Top model:       Base (1 entries)"));
+        assert!(text.contains("Average RTF:     0.20 (1 entries measured)"));
+        assert!(text.ends_with(&format!("     4 {}\n", "#".repeat(BAR_WIDTH))));
+
+        assert_eq!(format_stats(&HistoryStats::default()), "No history entries\n");
+    }
+}
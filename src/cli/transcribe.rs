@@ -1,22 +1,46 @@
 //! CLI transcription command implementation.
 
 use crate::app::config::{load_config, models_dir, sortformer_models_dir, tdt_models_dir, Config};
-use crate::cli::args::{DiarizationMethod, OutputFormat, SttBackend, TranscribeArgs};
-use crate::cli::wav_reader::{prepare_for_whisper, read_wav, PreparedAudio};
+use crate::cli::args::{
+    DiarizationMethod, OutputFormat, SortformerArgs, SpeakerAttributionMode, SttBackend, TranscribeArgs,
+};
+use crate::cli::wav_reader::{prepare_for_whisper, read_audio, PreparedAudio};
+use crate::domain::text_metrics::{grapheme_count, word_count, words_per_minute};
 use crate::domain::traits::Transcription;
-use crate::infrastructure::models::{get_model_path, list_downloaded_models};
-use crate::recording::split::SplitConfig;
+use crate::domain::types::SpeakerStats;
+use crate::infrastructure::ipc::{IpcClient, RemoteTranscriber, MAX_REQUEST_SAMPLES};
+use crate::infrastructure::models::{get_model_path, list_downloaded_models, resolve_silero_model_path};
+use crate::recording::denoise::{denoise_label, DenoiseSettings};
+use crate::recording::speaker_tracks::{collect_tracks, write_tracks};
+use crate::recording::split::{SplitConfig, SplitFinder};
+use crate::transcription::cache::{cache_key, TranscriptionCache};
 use crate::transcription::chunker::{AudioChunker, ChunkerConfig};
-use crate::transcription::diarization::DiarizationEngine;
+use crate::transcription::confidence::speaker_confidence;
+use crate::transcription::diarization::{
+    has_overlap, overlap_regions, DiarizationEngine, DiarizationSegment, DiarizationSettings, OverlapRegion,
+};
+use crate::transcription::postprocess::create_post_processor;
+use crate::transcription::rttm::{format_rttm, rttm_file_id, speech_turns, SpeakerTurn};
+use crate::transcription::talk_time::talk_time;
+use crate::transcription::word_attribution::SpeakerAttribution;
 use crate::transcription::TranscriptionService;
-use crate::vad::{VadConfig, VadEngine};
+use crate::vad::{create_vad, VadConfig, VadEngine};
 use anyhow::{bail, Context, Result};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 use std::fs;
 use std::io::{self, Write};
 use std::path::{Path, PathBuf};
 use std::time::Instant;
 
+/// Sample rate of prepared audio.
+const WHISPER_SAMPLE_RATE: u32 = 16000;
+
+/// Silences shorter than this do not end a speaker turn in channel diarization.
+const CHANNEL_TURN_GAP_MS: u32 = 500;
+
+/// Text-output marker for segments where speakers talk over each other.
+const CLI_OVERLAP_MARKER: &str = "[overlap]";
+
 /// JSON output structure for transcription results.
 #[derive(Serialize)]
 struct TranscriptionOutput {
@@ -27,14 +51,21 @@ struct TranscriptionOutput {
     model: String,
     backend: String,
     diarization: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    speaker_count: Option<usize>,
+    /// Talk time and turn count per speaker (from diarization turns)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    speaker_stats: Vec<SpeakerStats>,
     denoise: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    denoise_backend: Option<String>,
     transcription: String,
     #[serde(skip_serializing_if = "Vec::is_empty")]
     segments: Vec<TranscriptionSegment>,
     metrics: TranscriptionMetrics,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 struct TranscriptionSegment {
     speaker: String,
     text: String,
@@ -42,6 +73,12 @@ struct TranscriptionSegment {
     start_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     end_time: Option<f64>,
+    /// Another speaker talks during this segment.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    overlap: bool,
+    /// Confidence of the speaker assignment (Sortformer only), 0 to 1.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    confidence: Option<f32>,
 }
 
 /// Metrics for transcription performance analysis.
@@ -55,8 +92,11 @@ struct TranscriptionMetrics {
     rtf: f64,
     /// Number of words in output
     word_count: usize,
-    /// Number of characters in output
+    /// Number of characters (graphemes) in output
     char_count: usize,
+    /// Words per minute of input audio
+    #[serde(skip_serializing_if = "Option::is_none")]
+    words_per_minute: Option<f32>,
     /// Number of speaker segments (from diarization)
     segment_count: usize,
 }
@@ -71,62 +111,70 @@ pub fn run(args: TranscribeArgs) -> Result<()> {
         args.diarization
     };
 
-    // Validate backend + diarization combinations
-    if matches!(args.backend, SttBackend::Tdt) && !matches!(effective_diarization, DiarizationMethod::None) {
+    // Validate backend + diarization combinations; --diarize-only never runs the backend
+    if matches!(args.backend, SttBackend::Tdt)
+        && !args.diarize_only
+        && !matches!(effective_diarization, DiarizationMethod::None)
+    {
         bail!("TDT backend does not support diarization. TDT is a pure STT backend without speaker identification. Use --diarization=none with --backend=tdt");
     }
+    let needs_diarization =
+        args.diarize_only || args.split_speakers.is_some() || matches!(args.format, OutputFormat::Rttm);
+    if needs_diarization && matches!(effective_diarization, DiarizationMethod::None) {
+        bail!(
+            "RTTM output, --diarize-only and --split-speakers require diarization. Use --diarization=channel or --diarization=sortformer"
+        );
+    }
 
     // 1. Load config (custom path or default)
     let config = load_config_cascade(&args)?;
 
     // 2. Read WAV file
     eprintln!("Reading: {}", args.input.display());
-    let audio = read_wav(&args.input)?;
+    let audio = read_audio(&args.input)?;
     eprintln!(
         "  {} channels, {}Hz, {:.1}s",
         audio.channels, audio.sample_rate, audio.duration_secs
     );
 
     // 3. Prepare audio for transcription
-    let denoise = args.denoise || config.denoise_enabled;
-    let prepared = prepare_for_whisper(&audio, args.channel, denoise)?;
+    let denoise = (args.denoise || config.denoise_enabled).then(|| DenoiseSettings::from_config(&config));
+    let prepared = prepare_for_whisper(&audio, args.channel, denoise.as_ref())?;
 
     let language = args.language.as_deref().unwrap_or(&config.language);
 
-    // 4. Run transcription based on backend
+    // 4. Run transcription based on backend, unless the result is cached
     let start_time = Instant::now();
-    let result = match args.backend {
-        SttBackend::Whisper => {
-            let model_path = resolve_whisper_model(&args, &config)?;
-            eprintln!("Loading Whisper model: {}", model_path.display());
-            let service = TranscriptionService::with_model(&model_path.to_string_lossy())?;
-
-            eprintln!(
-                "Transcribing (backend: whisper, diarization: {:?}, language: {})...",
-                effective_diarization, language
-            );
-            transcribe_with_whisper(&service, &prepared, language, &args, effective_diarization, &config)?
+    let cache = if args.no_cache {
+        None
+    } else {
+        TranscriptionCache::from_config(&config)
+    };
+    let cache_key = match cache {
+        Some(_) => {
+            let model = cache_model_id(&args, &config)?;
+            let params = cache_params(&args, &config, effective_diarization, denoise.as_ref());
+            Some(cache_key(&args.input, &model, language, &params)?)
         }
-        SttBackend::Tdt => {
-            let model_dir = resolve_tdt_model(&args, &config)?;
-            eprintln!("Loading TDT model from: {}", model_dir.display());
-            let service = TranscriptionService::with_tdt(&model_dir.to_string_lossy())?;
-
-            eprintln!("Transcribing (backend: tdt, language: {})...", language);
-            let text = if args.no_chunking {
-                service.transcribe(&prepared.samples, language)?
-            } else {
-                let chunker = build_chunker(&args, &config);
-                chunker.transcribe_chunked(&prepared.samples, language, &service)?
-            };
-            TranscriptionResult {
-                text: text.trim().to_string(),
-                segments: Vec::new(),
-                model_name: model_dir
-                    .file_name()
-                    .map(|n| n.to_string_lossy().to_string())
-                    .unwrap_or_else(|| "tdt".to_string()),
+        None => None,
+    };
+    let cached = match (&cache, &cache_key) {
+        (Some(cache), Some(key)) => cache.get::<TranscriptionResult>(key),
+        _ => None,
+    };
+    let result = match cached {
+        Some(result) => {
+            eprintln!("Using cached transcription (--no-cache to re-run)");
+            result
+        }
+        None => {
+            let result = run_backend(&args, &config, &prepared, language, effective_diarization)?;
+            if let (Some(cache), Some(key)) = (&cache, &cache_key) {
+                if let Err(e) = cache.put(key, &result) {
+                    eprintln!("Warning: failed to cache transcription: {:#}", e);
+                }
             }
+            result
         }
     };
     let execution_time_ms = start_time.elapsed().as_millis() as u64;
@@ -141,8 +189,9 @@ pub fn run(args: TranscribeArgs) -> Result<()> {
         } else {
             0.0
         },
-        word_count: result.text.split_whitespace().count(),
-        char_count: result.text.chars().count(),
+        word_count: word_count(&result.text),
+        char_count: grapheme_count(&result.text),
+        words_per_minute: words_per_minute(&result.text, audio.duration_secs as f32),
         segment_count: result.segments.len(),
     };
 
@@ -151,6 +200,9 @@ pub fn run(args: TranscribeArgs) -> Result<()> {
         execution_time_ms as f64 / 1000.0,
         metrics.rtf
     );
+    if !matches!(effective_diarization, DiarizationMethod::None) {
+        eprintln!("Speakers detected: {}", result.speaker_count());
+    }
 
     // 6. Output result
     output_result(
@@ -159,16 +211,251 @@ pub fn run(args: TranscribeArgs) -> Result<()> {
         &audio.duration_secs,
         language,
         effective_diarization,
-        denoise,
+        denoise.as_ref(),
         &metrics,
     )?;
 
+    if let Some(ref dir) = args.split_speakers {
+        let paths = write_speaker_tracks(&prepared, &result.turns, effective_diarization, dir, &args.input)?;
+        eprintln!("Wrote {} speaker track(s) to {}", paths.len(), dir.display());
+    }
+
     Ok(())
 }
 
+/// Transcribe (or only diarize) prepared audio with the selected backend.
+fn run_backend(
+    args: &TranscribeArgs,
+    config: &Config,
+    prepared: &PreparedAudio,
+    language: &str,
+    effective_diarization: DiarizationMethod,
+) -> Result<TranscriptionResult> {
+    // The app takes requests of limited length; unchunked long audio runs here
+    let fits_app = !args.no_chunking || prepared.samples.len() <= MAX_REQUEST_SAMPLES;
+    if let Some((remote, model_name)) = app_transcriber(args, config, effective_diarization).filter(|_| fits_app) {
+        eprintln!(
+            "Using the model loaded in the running app: {}",
+            remote.model_name().unwrap_or_default()
+        );
+        eprintln!("Transcribing (language: {})...", language);
+        let text = if args.no_chunking {
+            remote.transcribe(&prepared.samples, language)?
+        } else {
+            let chunker = build_chunker(args.max_segment_secs, config);
+            chunker.transcribe_chunked(&prepared.samples, language, &remote)?
+        };
+        return Ok(TranscriptionResult {
+            text: text.trim().to_string(),
+            segments: Vec::new(),
+            turns: Vec::new(),
+            model_name,
+        });
+    }
+
+    Ok(match args.backend {
+        _ if args.diarize_only => {
+            eprintln!("Diarizing (diarization: {:?})...", effective_diarization);
+            let turns = diarize_turns(prepared, effective_diarization, args, config)?;
+            TranscriptionResult {
+                text: String::new(),
+                segments: turns
+                    .iter()
+                    .map(|t| TranscriptionSegment {
+                        speaker: t.speaker.clone(),
+                        text: String::new(),
+                        start_time: Some(t.start),
+                        end_time: Some(t.end),
+                        overlap: false,
+                        confidence: None,
+                    })
+                    .collect(),
+                turns,
+                model_name: "none".to_string(),
+            }
+        }
+        SttBackend::Whisper => {
+            let model_path = resolve_whisper_model(args.model.as_deref(), config)?;
+            eprintln!("Loading Whisper model: {}", model_path.display());
+            let mut service = TranscriptionService::with_model(&model_path.to_string_lossy())?;
+            service.set_verbatim(config.verbatim_transcription);
+
+            eprintln!(
+                "Transcribing (backend: whisper, diarization: {:?}, language: {})...",
+                effective_diarization, language
+            );
+            transcribe_with_whisper(&service, prepared, language, args, effective_diarization, config)?
+        }
+        SttBackend::Tdt => {
+            let model_dir = resolve_tdt_model(args.tdt_model.as_deref(), config)?;
+            eprintln!("Loading TDT model from: {}", model_dir.display());
+            let mut service = TranscriptionService::with_tdt(&model_dir.to_string_lossy())?;
+            if let Some(processor) = create_post_processor(&config.punctuation_restoration) {
+                eprintln!("Punctuation restoration: {}", processor.name());
+                service.add_post_processor(processor);
+            }
+            service.set_verbatim(config.verbatim_transcription);
+
+            eprintln!("Transcribing (backend: tdt, language: {})...", language);
+            let text = if args.no_chunking {
+                service.transcribe(&prepared.samples, language)?
+            } else {
+                let chunker = build_chunker(args.max_segment_secs, config);
+                chunker.transcribe_chunked(&prepared.samples, language, &service)?
+            };
+            TranscriptionResult {
+                text: text.trim().to_string(),
+                segments: Vec::new(),
+                turns: Vec::new(),
+                model_name: model_dir
+                    .file_name()
+                    .map(|n| n.to_string_lossy().to_string())
+                    .unwrap_or_else(|| "tdt".to_string()),
+            }
+        }
+    })
+}
+
+/// The running app's model, when it is the one this command would load.
+///
+/// Saves loading a second copy; diarized transcription still runs locally.
+/// Returns the transcriber and the model name for the result.
+fn app_transcriber(
+    args: &TranscribeArgs,
+    config: &Config,
+    diarization: DiarizationMethod,
+) -> Option<(RemoteTranscriber, String)> {
+    // The app decodes verbatim only when its own config says so
+    if args.standalone || args.diarize_only || args.verbatim || !matches!(diarization, DiarizationMethod::None) {
+        return None;
+    }
+    let client = IpcClient::connect()?;
+    let status = client.status().ok()?;
+    let loaded = status.model.as_deref()?;
+
+    let model_name = match args.backend {
+        SttBackend::Whisper => {
+            let path = resolve_whisper_model(args.model.as_deref(), config).ok()?;
+            if status.backend.as_deref() != Some("whisper") || Path::new(loaded) != path {
+                return None;
+            }
+            args.model.clone().unwrap_or_else(|| config.default_model.clone())
+        }
+        SttBackend::Tdt => {
+            let dir = resolve_tdt_model(args.tdt_model.as_deref(), config).ok()?;
+            // TDT reports "Parakeet TDT v3 (<dir>)"
+            if status.backend.as_deref() != Some("tdt") || !loaded.contains(&*dir.to_string_lossy()) {
+                return None;
+            }
+            dir.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| "tdt".to_string())
+        }
+    };
+    Some((RemoteTranscriber::new(client, status), model_name))
+}
+
+/// Model identity for the cache key: the resolved model path ("none" for diarize-only).
+fn cache_model_id(args: &TranscribeArgs, config: &Config) -> Result<String> {
+    let path = match args.backend {
+        _ if args.diarize_only => return Ok("none".to_string()),
+        SttBackend::Whisper => resolve_whisper_model(args.model.as_deref(), config)?,
+        SttBackend::Tdt => resolve_tdt_model(args.tdt_model.as_deref(), config)?,
+    };
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Every setting besides model and language that changes the result.
+fn cache_params(
+    args: &TranscribeArgs,
+    config: &Config,
+    diarization: DiarizationMethod,
+    denoise: Option<&DenoiseSettings>,
+) -> String {
+    format!(
+        "backend={:?} channel={:?} diarization={:?} diarize_only={} denoise={} chunking={} max_segment={} \
+         sortformer={:?}/{:?} speakers={:?}/{} onset={:?}/{:?} offset={:?}/{:?} attribution={:?} \
+         punctuation={} verbatim={} vad={}/{}/{}/{}/{}",
+        args.backend,
+        args.channel,
+        diarization,
+        args.diarize_only,
+        denoise_label(denoise),
+        !args.no_chunking,
+        args.max_segment_secs,
+        args.sortformer.sortformer_model,
+        config.sortformer_model_path,
+        args.sortformer.max_speakers,
+        config.diarization_max_speakers,
+        args.sortformer.onset,
+        config.diarization_onset,
+        args.sortformer.offset,
+        config.diarization_offset,
+        speaker_attribution(args, config),
+        config.punctuation_restoration,
+        config.verbatim_transcription,
+        config.vad_engine,
+        config.effective_vad_silence_threshold_ms(),
+        config.effective_vad_min_speech_ms(),
+        config.silero_threshold,
+        config.vad_aggressiveness,
+    )
+}
+
+/// Speaker attribution from `--speaker-attribution`, falling back to the config.
+fn speaker_attribution(args: &TranscribeArgs, config: &Config) -> SpeakerAttribution {
+    match args.speaker_attribution {
+        Some(SpeakerAttributionMode::Words) => SpeakerAttribution::Words,
+        Some(SpeakerAttributionMode::Segments) => SpeakerAttribution::Segments,
+        None => SpeakerAttribution::from_config(&config.speaker_attribution),
+    }
+}
+
+/// Write one WAV per speaker. Channel turns are cut from their own channel,
+/// Sortformer turns from the mixed audio.
+pub(crate) fn write_speaker_tracks(
+    prepared: &PreparedAudio,
+    turns: &[SpeakerTurn],
+    diarization: DiarizationMethod,
+    dir: &Path,
+    input: &Path,
+) -> Result<Vec<PathBuf>> {
+    let tracks = match diarization {
+        DiarizationMethod::Channel => {
+            let mut tracks = Vec::new();
+            for (speaker, samples) in [("Mic", &prepared.left), ("Loopback", &prepared.right)] {
+                let Some(samples) = samples else {
+                    continue;
+                };
+                let channel_turns: Vec<SpeakerTurn> = turns.iter().filter(|t| t.speaker == speaker).cloned().collect();
+                tracks.extend(collect_tracks(samples, &channel_turns, WHISPER_SAMPLE_RATE));
+            }
+            tracks
+        }
+        _ => collect_tracks(&prepared.samples, turns, WHISPER_SAMPLE_RATE),
+    };
+    write_tracks(&tracks, dir, &rttm_file_id(input), WHISPER_SAMPLE_RATE)
+}
+
 /// Load config with cascade: custom path -> default path -> defaults.
 fn load_config_cascade(args: &TranscribeArgs) -> Result<Config> {
-    if let Some(ref custom_path) = args.config {
+    let mut config = load_config_from(args.config.as_deref())?;
+    if let Some(level) = args.vad_aggressiveness {
+        config.vad_aggressiveness = level;
+    }
+    if args.verbatim {
+        config.verbatim_transcription = true;
+    }
+    // The VAD profile follows the language actually transcribed
+    if let Some(language) = &args.language {
+        config.language = language.clone();
+    }
+    Ok(config)
+}
+
+/// Load config from a custom path, or the default config (falling back to defaults).
+pub(crate) fn load_config_from(custom_path: Option<&Path>) -> Result<Config> {
+    if let Some(custom_path) = custom_path {
         // Load from custom path
         let content = fs::read_to_string(custom_path)
             .with_context(|| format!("Failed to read config: {}", custom_path.display()))?;
@@ -180,8 +467,8 @@ fn load_config_cascade(args: &TranscribeArgs) -> Result<Config> {
 }
 
 /// Resolve Whisper model path from CLI args or config.
-fn resolve_whisper_model(args: &TranscribeArgs, config: &Config) -> Result<PathBuf> {
-    if let Some(ref model_arg) = args.model {
+pub(crate) fn resolve_whisper_model(model: Option<&str>, config: &Config) -> Result<PathBuf> {
+    if let Some(model_arg) = model {
         let model_path = Path::new(model_arg);
 
         // If it's an absolute path or relative path that exists, use it directly
@@ -232,11 +519,11 @@ fn resolve_whisper_model(args: &TranscribeArgs, config: &Config) -> Result<PathB
 }
 
 /// Resolve TDT model directory from CLI args or config.
-fn resolve_tdt_model(args: &TranscribeArgs, config: &Config) -> Result<PathBuf> {
+pub(crate) fn resolve_tdt_model(tdt_model: Option<&Path>, config: &Config) -> Result<PathBuf> {
     // 1. CLI argument takes priority
-    if let Some(ref tdt_path) = args.tdt_model {
+    if let Some(tdt_path) = tdt_model {
         if tdt_path.exists() {
-            return Ok(tdt_path.clone());
+            return Ok(tdt_path.to_path_buf());
         }
         bail!("TDT model directory not found: {}", tdt_path.display());
     }
@@ -270,7 +557,7 @@ fn resolve_tdt_model(args: &TranscribeArgs, config: &Config) -> Result<PathBuf>
 }
 
 /// Resolve Sortformer model path from CLI args or config.
-fn resolve_sortformer_model(args: &TranscribeArgs, config: &Config) -> Result<PathBuf> {
+fn resolve_sortformer_model(args: &SortformerArgs, config: &Config) -> Result<PathBuf> {
     // 1. CLI argument takes priority
     if let Some(ref sf_path) = args.sortformer_model {
         if sf_path.exists() {
@@ -301,13 +588,106 @@ fn resolve_sortformer_model(args: &TranscribeArgs, config: &Config) -> Result<Pa
     );
 }
 
+/// Resolve and load the Sortformer diarization engine.
+pub(crate) fn load_sortformer(args: &SortformerArgs, config: &Config) -> Result<DiarizationEngine> {
+    let sortformer_path = resolve_sortformer_model(args, config)?;
+
+    let settings = DiarizationSettings {
+        max_speakers: args
+            .max_speakers
+            .map(usize::from)
+            .unwrap_or(config.diarization_max_speakers),
+        onset: args.onset.or(config.diarization_onset),
+        offset: args.offset.or(config.diarization_offset),
+    };
+    let mut engine = DiarizationEngine::new(Some(sortformer_path)).with_settings(settings);
+    engine.load_model().context("Failed to load Sortformer model")?;
+
+    if !engine.is_available() {
+        bail!("Sortformer diarization not available. Load the Sortformer model first.");
+    }
+    Ok(engine)
+}
+
 /// Transcription result with text, segments, and model info.
+#[derive(Serialize, Deserialize)]
 struct TranscriptionResult {
     text: String,
     segments: Vec<TranscriptionSegment>,
+    /// Speaker turns for RTTM output (empty without diarization).
+    turns: Vec<SpeakerTurn>,
     model_name: String,
 }
 
+impl TranscriptionResult {
+    /// Number of distinct speakers found by diarization.
+    fn speaker_count(&self) -> usize {
+        self.turns
+            .iter()
+            .map(|t| t.speaker.as_str())
+            .collect::<std::collections::BTreeSet<_>>()
+            .len()
+    }
+}
+
+/// Run diarization only, without transcription.
+fn diarize_turns(
+    prepared: &PreparedAudio,
+    diarization: DiarizationMethod,
+    args: &TranscribeArgs,
+    config: &Config,
+) -> Result<Vec<SpeakerTurn>> {
+    match diarization {
+        DiarizationMethod::None => Ok(Vec::new()),
+        DiarizationMethod::Channel => {
+            if prepared.left.is_none() || prepared.right.is_none() {
+                bail!("Channel diarization requires a stereo file (left=mic, right=loopback)");
+            }
+            channel_turns(prepared, config)
+        }
+        DiarizationMethod::Sortformer => {
+            let mut engine = load_sortformer(&args.sortformer, config)?;
+            let diar_segments = engine.diarize(&prepared.samples)?;
+            Ok(sortformer_turns(&diar_segments))
+        }
+    }
+}
+
+/// Speech turns of each channel (left = "Mic", right = "Loopback") found with VAD.
+fn channel_turns(prepared: &PreparedAudio, config: &Config) -> Result<Vec<SpeakerTurn>> {
+    let chunker_config = build_chunker_config(config.max_segment_secs, config);
+    let sample_rate = chunker_config.split.sample_rate;
+    let vad = create_vad(&chunker_config.vad)?;
+    let finder = SplitFinder::new(chunker_config.split);
+
+    let mut turns = Vec::new();
+    for (speaker, samples) in [("Mic", &prepared.left), ("Loopback", &prepared.right)] {
+        let Some(samples) = samples else {
+            continue;
+        };
+        vad.reset();
+        let silences: Vec<_> = finder
+            .scan_silences(samples, vad.as_ref())
+            .into_iter()
+            .filter(|s| s.duration_ms >= CHANNEL_TURN_GAP_MS)
+            .collect();
+        turns.extend(speech_turns(speaker, &silences, samples.len(), sample_rate));
+    }
+    Ok(turns)
+}
+
+/// Speaker turns from Sortformer segments ("Speaker 1", "Speaker 2", ...).
+pub(crate) fn sortformer_turns(diar_segments: &[DiarizationSegment]) -> Vec<SpeakerTurn> {
+    diar_segments
+        .iter()
+        .map(|seg| SpeakerTurn {
+            speaker: format!("Speaker {}", seg.speaker_id + 1),
+            start: seg.start_time,
+            end: seg.end_time,
+        })
+        .collect()
+}
+
 /// Transcribe audio using the active backend with specified diarization method.
 fn transcribe_with_whisper(
     service: &TranscriptionService,
@@ -325,18 +705,19 @@ fn transcribe_with_whisper(
             let text = if args.no_chunking {
                 Transcription::transcribe(service, &prepared.samples, language)?
             } else {
-                let chunker = build_chunker(args, config);
+                let chunker = build_chunker(args.max_segment_secs, config);
                 chunker.transcribe_chunked(&prepared.samples, language, service)?
             };
             Ok(TranscriptionResult {
                 text: text.trim().to_string(),
                 segments: Vec::new(),
+                turns: Vec::new(),
                 model_name,
             })
         }
         DiarizationMethod::Channel => {
             // Channel-based diarization (stereo: left=mic, right=loopback)
-            transcribe_channel_diarization(service, prepared, language, model_name)
+            transcribe_channel_diarization(service, prepared, language, config, model_name)
         }
         DiarizationMethod::Sortformer => {
             // Sortformer neural diarization
@@ -350,6 +731,7 @@ fn transcribe_channel_diarization(
     service: &TranscriptionService,
     prepared: &PreparedAudio,
     language: &str,
+    config: &Config,
     model_name: String,
 ) -> Result<TranscriptionResult> {
     match (&prepared.left, &prepared.right) {
@@ -368,6 +750,8 @@ fn transcribe_channel_diarization(
                     text: left_text.trim().to_string(),
                     start_time: None,
                     end_time: None,
+                    overlap: false,
+                    confidence: None,
                 });
             }
 
@@ -382,12 +766,15 @@ fn transcribe_channel_diarization(
                     text: right_text.trim().to_string(),
                     start_time: None,
                     end_time: None,
+                    overlap: false,
+                    confidence: None,
                 });
             }
 
             Ok(TranscriptionResult {
                 text: full_text,
                 segments,
+                turns: channel_turns(prepared, config)?,
                 model_name,
             })
         }
@@ -397,6 +784,7 @@ fn transcribe_channel_diarization(
             Ok(TranscriptionResult {
                 text: text.trim().to_string(),
                 segments: Vec::new(),
+                turns: Vec::new(),
                 model_name,
             })
         }
@@ -412,18 +800,8 @@ fn transcribe_sortformer_diarization(
     config: &Config,
     model_name: String,
 ) -> Result<TranscriptionResult> {
-    // Resolve Sortformer model path
-    let sortformer_path = resolve_sortformer_model(args, config)?;
-
-    // Initialize and load diarization engine
-    let mut engine = DiarizationEngine::new(Some(sortformer_path));
-    engine.load_model().context("Failed to load Sortformer model")?;
-
-    if !engine.is_available() {
-        bail!("Sortformer diarization not available. Load the Sortformer model first.");
-    }
-
-    // Run diarization to get speaker segments
+    // Load Sortformer and run diarization to get speaker segments
+    let mut engine = load_sortformer(&args.sortformer, config)?;
     let diar_segments = engine.diarize(&prepared.samples)?;
 
     if diar_segments.is_empty() {
@@ -432,15 +810,49 @@ fn transcribe_sortformer_diarization(
         return Ok(TranscriptionResult {
             text: text.trim().to_string(),
             segments: Vec::new(),
+            turns: Vec::new(),
             model_name,
         });
     }
 
-    // Transcribe each speaker segment
+    let overlaps = overlap_regions(&diar_segments);
     let sample_rate = 16000.0; // Whisper expects 16kHz
     let mut segments = Vec::new();
     let mut full_text = String::new();
 
+    // Transcribe once and split by word timestamps
+    if speaker_attribution(args, config) == SpeakerAttribution::Words {
+        let window = if args.no_chunking {
+            0
+        } else {
+            args.max_segment_secs as usize * sample_rate as usize
+        };
+        match service.transcribe_attributed_words(&prepared.samples, &diar_segments, language, window) {
+            Ok(pieces) => {
+                for piece in pieces {
+                    push_speaker_segment(
+                        &mut full_text,
+                        &mut segments,
+                        &diar_segments,
+                        &overlaps,
+                        piece.speaker_id,
+                        piece.text.trim(),
+                        piece.start,
+                        piece.end,
+                    );
+                }
+                return Ok(TranscriptionResult {
+                    text: full_text,
+                    segments,
+                    turns: sortformer_turns(&diar_segments),
+                    model_name,
+                });
+            }
+            Err(e) => eprintln!("Word timestamps unavailable, transcribing segments separately: {}", e),
+        }
+    }
+
+    // Transcribe each speaker segment
     for seg in &diar_segments {
         let start_sample = (seg.start_time * sample_rate) as usize;
         let end_sample = (seg.end_time * sample_rate).min(prepared.samples.len() as f64) as usize;
@@ -458,37 +870,68 @@ fn transcribe_sortformer_diarization(
 
         // Use chunker for long speaker segments to avoid OOM
         let text = if !args.no_chunking && segment_audio.len() > args.max_segment_secs as usize * sample_rate as usize {
-            let chunker = build_chunker(args, config);
+            let chunker = build_chunker(args.max_segment_secs, config);
             chunker.transcribe_chunked(segment_audio, language, service)?
         } else {
             Transcription::transcribe(service, segment_audio, language)?
         };
-        let text = text.trim();
-
-        if !text.is_empty() {
-            let speaker = format!("Speaker {}", seg.speaker_id + 1);
-
-            if !full_text.is_empty() {
-                full_text.push('\n');
-            }
-            full_text.push_str(&format!("[{}] {}", speaker, text));
-
-            segments.push(TranscriptionSegment {
-                speaker,
-                text: text.to_string(),
-                start_time: Some(seg.start_time),
-                end_time: Some(seg.end_time),
-            });
-        }
+        push_speaker_segment(
+            &mut full_text,
+            &mut segments,
+            &diar_segments,
+            &overlaps,
+            seg.speaker_id,
+            text.trim(),
+            seg.start_time,
+            seg.end_time,
+        );
     }
 
     Ok(TranscriptionResult {
         text: full_text,
         segments,
+        turns: sortformer_turns(&diar_segments),
         model_name,
     })
 }
 
+/// Append one speaker's text to the transcript and the JSON segments.
+#[allow(clippy::too_many_arguments)]
+fn push_speaker_segment(
+    full_text: &mut String,
+    segments: &mut Vec<TranscriptionSegment>,
+    diar_segments: &[DiarizationSegment],
+    overlaps: &[OverlapRegion],
+    speaker_id: usize,
+    text: &str,
+    start: f64,
+    end: f64,
+) {
+    if text.is_empty() {
+        return;
+    }
+    let speaker = format!("Speaker {}", speaker_id + 1);
+    let overlap = has_overlap(overlaps, start, end);
+
+    if !full_text.is_empty() {
+        full_text.push('\n');
+    }
+    if overlap {
+        full_text.push_str(&format!("[{}] {} {}", speaker, CLI_OVERLAP_MARKER, text));
+    } else {
+        full_text.push_str(&format!("[{}] {}", speaker, text));
+    }
+
+    segments.push(TranscriptionSegment {
+        speaker,
+        text: text.to_string(),
+        start_time: Some(start),
+        end_time: Some(end),
+        overlap,
+        confidence: Some(speaker_confidence(diar_segments, speaker_id, start, end)),
+    });
+}
+
 /// Output result in requested format.
 fn output_result(
     result: &TranscriptionResult,
@@ -496,11 +939,14 @@ fn output_result(
     duration_secs: &f64,
     language: &str,
     diarization: DiarizationMethod,
-    denoise: bool,
+    denoise: Option<&DenoiseSettings>,
     metrics: &TranscriptionMetrics,
 ) -> Result<()> {
     let output_text = match args.format {
+        // Diarize-only has no text to print; RTTM is its natural output
+        OutputFormat::Text if args.diarize_only => format_rttm(&rttm_file_id(&args.input), &result.turns),
         OutputFormat::Text => result.text.clone(),
+        OutputFormat::Rttm => format_rttm(&rttm_file_id(&args.input), &result.turns),
         OutputFormat::Json => {
             let backend_str = match args.backend {
                 SttBackend::Whisper => "whisper",
@@ -520,7 +966,10 @@ fn output_result(
                 model: result.model_name.clone(),
                 backend: backend_str.to_string(),
                 diarization: diarization_str.to_string(),
-                denoise,
+                speaker_count: (!matches!(diarization, DiarizationMethod::None)).then(|| result.speaker_count()),
+                speaker_stats: talk_time(&result.turns),
+                denoise: denoise.is_some(),
+                denoise_backend: denoise.map(|settings| settings.backend.name().to_string()),
                 transcription: result.text.clone(),
                 segments: result
                     .segments
@@ -530,6 +979,8 @@ fn output_result(
                         text: s.text.clone(),
                         start_time: s.start_time,
                         end_time: s.end_time,
+                        overlap: s.overlap,
+                        confidence: s.confidence,
                     })
                     .collect(),
                 metrics: TranscriptionMetrics {
@@ -538,6 +989,7 @@ fn output_result(
                     rtf: metrics.rtf,
                     word_count: metrics.word_count,
                     char_count: metrics.char_count,
+                    words_per_minute: metrics.words_per_minute,
                     segment_count: metrics.segment_count,
                 },
             };
@@ -561,7 +1013,7 @@ fn output_result(
 
 /// List available models.
 pub fn list_models() -> Result<()> {
-    use crate::infrastructure::models::{format_size, get_available_models};
+    use crate::infrastructure::models::{format_size, get_available_models, load_custom_models};
 
     let available = get_available_models();
     let downloaded = list_downloaded_models();
@@ -585,6 +1037,27 @@ pub fn list_models() -> Result<()> {
         );
     }
 
+    let custom = load_custom_models();
+    if !custom.is_empty() {
+        println!();
+        println!("Custom models:");
+        println!();
+        for model in &custom {
+            let status = if downloaded_names.contains(&model.filename) {
+                "[downloaded]"
+            } else {
+                ""
+            };
+            println!(
+                "  {:30} {:>10}  {}  ({})",
+                model.filename,
+                format_size(model.size_bytes),
+                status,
+                model.source
+            );
+        }
+    }
+
     println!();
     println!("Models directory: {}", models_dir().display());
     println!();
@@ -593,27 +1066,34 @@ pub fn list_models() -> Result<()> {
     if downloaded.is_empty() {
         println!();
         println!("No models downloaded. Use the GUI to download models,");
-        println!("or manually place .bin files in the models directory.");
+        println!("or import a local file with `models import <path>`.");
     }
 
     Ok(())
 }
 
-/// Build an AudioChunker from CLI args and config.
-fn build_chunker(args: &TranscribeArgs, config: &Config) -> AudioChunker {
-    AudioChunker::new(ChunkerConfig {
+/// Build an AudioChunker from the max segment length and config.
+pub(crate) fn build_chunker(max_segment_secs: u32, config: &Config) -> AudioChunker {
+    AudioChunker::new(build_chunker_config(max_segment_secs, config))
+}
+
+/// Chunker split and VAD settings from the max segment length and config.
+fn build_chunker_config(max_segment_secs: u32, config: &Config) -> ChunkerConfig {
+    ChunkerConfig {
         split: SplitConfig {
-            max_segment_secs: args.max_segment_secs,
-            vad_silence_ms: config.vad_silence_threshold_ms,
+            max_segment_secs,
+            vad_silence_ms: config.effective_vad_silence_threshold_ms(),
             ..SplitConfig::default()
         },
         vad: VadConfig {
             engine: VadEngine::parse(&config.vad_engine),
-            silence_threshold_ms: config.vad_silence_threshold_ms,
-            min_speech_ms: config.vad_min_speech_ms,
+            silence_threshold_ms: config.effective_vad_silence_threshold_ms(),
+            min_speech_ms: config.effective_vad_min_speech_ms(),
             silero_threshold: config.silero_threshold,
+            webrtc_aggressiveness: config.vad_aggressiveness,
+            silero_model_path: resolve_silero_model_path(config.silero_model_path.as_deref()),
         },
-    })
+    }
 }
 
 #[cfg(test)]
@@ -623,7 +1103,7 @@ mod tests {
 
     #[test]
     fn test_load_config_cascade_uses_defaults() {
-        let args = TranscribeArgs {
+        let mut args = TranscribeArgs {
             input: PathBuf::from("test.wav"),
             output: None,
             config: None,
@@ -633,16 +1113,31 @@ mod tests {
             diarize: false,
             backend: SttBackend::Whisper,
             diarization: DiarizationMethod::None,
-            sortformer_model: None,
+            sortformer: SortformerArgs::default(),
+            speaker_attribution: None,
             tdt_model: None,
             format: OutputFormat::Text,
             denoise: false,
+            verbatim: false,
             max_segment_secs: 300,
             no_chunking: false,
+            vad_aggressiveness: None,
+            diarize_only: false,
+            split_speakers: None,
+            no_cache: false,
+            standalone: false,
         };
 
         let config = load_config_cascade(&args).unwrap();
         assert_eq!(config.language, "uk");
+
+        args.vad_aggressiveness = Some(0);
+        let config = load_config_cascade(&args).unwrap();
+        assert_eq!(config.vad_aggressiveness, 0);
+
+        args.language = Some("en".to_string());
+        let config = load_config_cascade(&args).unwrap();
+        assert_eq!(config.language, "en");
     }
 
     #[test]
@@ -653,6 +1148,7 @@ mod tests {
             rtf: 0.5,
             word_count: 100,
             char_count: 500,
+            words_per_minute: Some(600.0),
             segment_count: 2,
         };
         assert!((metrics.rtf - 0.5).abs() < 0.001);
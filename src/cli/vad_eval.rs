@@ -0,0 +1,390 @@
+//! VAD evaluation CLI command.
+//!
+//! Runs one VAD engine over a WAV file (or every WAV in a directory), turns
+//! its frame decisions into speech regions and, when reference labels are
+//! available, scores them: precision is the share of detected speech that is
+//! labeled speech, recall the share of labeled speech that was detected.
+
+use crate::app::config::Config;
+use crate::cli::args::VadEvalArgs;
+use crate::cli::transcribe::load_config_from;
+use crate::cli::wav_reader::{prepare_for_whisper, read_audio};
+use crate::infrastructure::models::resolve_silero_model_path;
+use crate::transcription::rttm::parse_rttm;
+use crate::vad::{create_vad, VadConfig, VadEngine};
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const SAMPLE_RATE_HZ: f64 = 16000.0;
+
+/// Reference label files looked up next to a WAV, in order of preference.
+const LABEL_EXTENSIONS: [&str; 3] = ["rttm", "txt", "lab"];
+
+/// A span of speech, in seconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+struct Region {
+    start: f64,
+    end: f64,
+}
+
+/// Agreement between detected and reference speech.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize)]
+struct Scores {
+    precision: f64,
+    recall: f64,
+    f1: f64,
+    detected_secs: f64,
+    reference_secs: f64,
+    overlap_secs: f64,
+}
+
+/// Results for one WAV file.
+#[derive(Serialize)]
+struct FileReport {
+    input_file: String,
+    duration_secs: f64,
+    speech_secs: f64,
+    regions: Vec<Region>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    labels_file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scores: Option<Scores>,
+}
+
+/// Full evaluation report.
+#[derive(Serialize)]
+struct VadEvalReport {
+    engine: String,
+    files: Vec<FileReport>,
+    /// Scores over all labeled files together.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total: Option<Scores>,
+}
+
+/// Run the vad-eval command.
+pub fn run(args: VadEvalArgs) -> Result<()> {
+    let config = load_config_from(args.config.as_deref())?;
+    let vad_config = vad_config(&args, &config);
+    eprintln!("Engine: {:?}", vad_config.engine);
+
+    let inputs = if args.input.is_dir() {
+        if args.labels.is_some() {
+            bail!("--labels is for a single WAV; in a directory, put <name>.rttm or <name>.txt next to each WAV");
+        }
+        dataset_files(&args.input)?
+    } else {
+        let labels = args.labels.clone().or_else(|| find_labels(&args.input));
+        vec![(args.input.clone(), labels)]
+    };
+    if inputs.is_empty() {
+        bail!("No WAV files found in {}", args.input.display());
+    }
+
+    let mut files = Vec::new();
+    for (wav, labels) in &inputs {
+        let report = evaluate_file(wav, labels.as_deref(), &args, &vad_config)?;
+        eprintln!(
+            "{}: {} regions, {:.1}s speech",
+            wav.display(),
+            report.regions.len(),
+            report.speech_secs
+        );
+        for region in &report.regions {
+            eprintln!("  {:>9.2} - {:>9.2}", region.start, region.end);
+        }
+        if let Some(scores) = &report.scores {
+            eprintln!(
+                "  precision {:.3}, recall {:.3}, F1 {:.3}",
+                scores.precision, scores.recall, scores.f1
+            );
+        }
+        files.push(report);
+    }
+
+    let labeled: Vec<Scores> = files.iter().filter_map(|f| f.scores).collect();
+    let total = (!labeled.is_empty()).then(|| {
+        let sum = |f: fn(&Scores) -> f64| labeled.iter().map(f).sum::<f64>();
+        scores_from_totals(
+            sum(|s| s.detected_secs),
+            sum(|s| s.reference_secs),
+            sum(|s| s.overlap_secs),
+        )
+    });
+    if let (Some(total), true) = (&total, files.len() > 1) {
+        eprintln!(
+            "Total ({} labeled files): precision {:.3}, recall {:.3}, F1 {:.3}",
+            labeled.len(),
+            total.precision,
+            total.recall,
+            total.f1
+        );
+    }
+
+    let report = VadEvalReport {
+        engine: format!("{:?}", vad_config.engine),
+        files,
+        total,
+    };
+    let json = serde_json::to_string_pretty(&report).context("Failed to serialize report")?;
+    writeln!(std::io::stdout().lock(), "{}", json)?;
+    Ok(())
+}
+
+/// VAD settings from the arguments, falling back to the config.
+fn vad_config(args: &VadEvalArgs, config: &Config) -> VadConfig {
+    VadConfig {
+        engine: args
+            .engine
+            .map(|e| e.engine())
+            .unwrap_or_else(|| VadEngine::parse(&config.vad_engine)),
+        silence_threshold_ms: config.effective_vad_silence_threshold_ms(),
+        min_speech_ms: config.effective_vad_min_speech_ms(),
+        silero_threshold: args.silero_threshold.unwrap_or(config.silero_threshold),
+        webrtc_aggressiveness: args.aggressiveness.unwrap_or(config.vad_aggressiveness),
+        silero_model_path: resolve_silero_model_path(config.silero_model_path.as_deref()),
+    }
+}
+
+fn evaluate_file(wav: &Path, labels: Option<&Path>, args: &VadEvalArgs, vad_config: &VadConfig) -> Result<FileReport> {
+    let audio = read_audio(wav)?;
+    let prepared = prepare_for_whisper(&audio, args.channel, None)?;
+
+    let frame_size = vad_config.engine.frame_samples();
+    let vad = create_vad(vad_config)?;
+    let decisions = prepared
+        .samples
+        .chunks_exact(frame_size)
+        .map(|frame| vad.is_speech(frame))
+        .collect::<Result<Vec<bool>>>()?;
+    let regions = frames_to_regions(
+        &decisions,
+        frame_size as f64 / SAMPLE_RATE_HZ,
+        args.merge_gap_ms as f64 / 1000.0,
+    );
+
+    let scores = match labels {
+        Some(path) => {
+            let content =
+                fs::read_to_string(path).with_context(|| format!("Failed to read labels: {}", path.display()))?;
+            let reference = merge_regions(parse_labels(&content, path));
+            Some(score(&regions, &reference))
+        }
+        None => None,
+    };
+
+    Ok(FileReport {
+        input_file: wav.to_string_lossy().to_string(),
+        duration_secs: audio.duration_secs,
+        speech_secs: total_secs(&regions),
+        regions,
+        labels_file: labels.map(|p| p.to_string_lossy().to_string()),
+        scores,
+    })
+}
+
+/// WAV files of a dataset directory with their label files, sorted by name.
+fn dataset_files(dir: &Path) -> Result<Vec<(PathBuf, Option<PathBuf>)>> {
+    let mut wavs: Vec<PathBuf> = fs::read_dir(dir)
+        .with_context(|| format!("Failed to read directory: {}", dir.display()))?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| p.extension().is_some_and(|e| e.eq_ignore_ascii_case("wav")))
+        .collect();
+    wavs.sort();
+    Ok(wavs
+        .into_iter()
+        .map(|wav| {
+            let labels = find_labels(&wav);
+            (wav, labels)
+        })
+        .collect())
+}
+
+fn find_labels(wav: &Path) -> Option<PathBuf> {
+    LABEL_EXTENSIONS
+        .iter()
+        .map(|ext| wav.with_extension(ext))
+        .find(|p| p.exists())
+}
+
+/// Speech regions from labels: RTTM for `.rttm` files, otherwise Audacity
+/// labels (`start end [label]`, seconds, tab or space separated).
+fn parse_labels(content: &str, path: &Path) -> Vec<Region> {
+    if path.extension().is_some_and(|e| e.eq_ignore_ascii_case("rttm")) {
+        return parse_rttm(content)
+            .into_iter()
+            .map(|t| Region {
+                start: t.start,
+                end: t.end,
+            })
+            .collect();
+    }
+    content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let start: f64 = fields.next()?.parse().ok()?;
+            let end: f64 = fields.next()?.parse().ok()?;
+            (end > start).then_some(Region { start, end })
+        })
+        .collect()
+}
+
+/// Sort regions and join overlapping ones (overlapping speakers in RTTM).
+fn merge_regions(mut regions: Vec<Region>) -> Vec<Region> {
+    regions.sort_by(|a, b| a.start.total_cmp(&b.start));
+    let mut merged: Vec<Region> = Vec::with_capacity(regions.len());
+    for region in regions {
+        match merged.last_mut() {
+            Some(last) if region.start <= last.end => last.end = last.end.max(region.end),
+            _ => merged.push(region),
+        }
+    }
+    merged
+}
+
+/// Speech regions from per-frame decisions, bridging pauses up to `merge_gap_secs`.
+fn frames_to_regions(decisions: &[bool], frame_secs: f64, merge_gap_secs: f64) -> Vec<Region> {
+    let mut regions: Vec<Region> = Vec::new();
+    let mut start = None;
+    for (i, &speech) in decisions.iter().chain(std::iter::once(&false)).enumerate() {
+        match (speech, start) {
+            (true, None) => start = Some(i),
+            (false, Some(first)) => {
+                let region = Region {
+                    start: first as f64 * frame_secs,
+                    end: i as f64 * frame_secs,
+                };
+                match regions.last_mut() {
+                    Some(last) if region.start - last.end <= merge_gap_secs => last.end = region.end,
+                    _ => regions.push(region),
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    regions
+}
+
+fn total_secs(regions: &[Region]) -> f64 {
+    regions.iter().map(|r| r.end - r.start).sum()
+}
+
+/// Total time covered by both sorted, non-overlapping region lists.
+fn overlap_secs(a: &[Region], b: &[Region]) -> f64 {
+    let (mut i, mut j) = (0, 0);
+    let mut overlap = 0.0;
+    while i < a.len() && j < b.len() {
+        let start = a[i].start.max(b[j].start);
+        let end = a[i].end.min(b[j].end);
+        if end > start {
+            overlap += end - start;
+        }
+        if a[i].end < b[j].end {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    overlap
+}
+
+fn score(detected: &[Region], reference: &[Region]) -> Scores {
+    scores_from_totals(
+        total_secs(detected),
+        total_secs(reference),
+        overlap_secs(detected, reference),
+    )
+}
+
+fn scores_from_totals(detected_secs: f64, reference_secs: f64, overlap_secs: f64) -> Scores {
+    let precision = if detected_secs > 0.0 {
+        overlap_secs / detected_secs
+    } else {
+        0.0
+    };
+    let recall = if reference_secs > 0.0 {
+        overlap_secs / reference_secs
+    } else {
+        0.0
+    };
+    let f1 = if precision + recall > 0.0 {
+        2.0 * precision * recall / (precision + recall)
+    } else {
+        0.0
+    };
+    Scores {
+        precision,
+        recall,
+        f1,
+        detected_secs,
+        reference_secs,
+        overlap_secs,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(start: f64, end: f64) -> Region {
+        Region { start, end }
+    }
+
+    #[test]
+    fn test_frames_to_regions_bridges_short_pauses() {
+        let decisions = [false, true, true, false, true, false, false, false, true];
+        assert_eq!(
+            frames_to_regions(&decisions, 0.25, 0.25),
+            vec![region(0.25, 1.25), region(2.0, 2.25)]
+        );
+        assert_eq!(
+            frames_to_regions(&decisions, 0.25, 0.0),
+            vec![region(0.25, 0.75), region(1.0, 1.25), region(2.0, 2.25)]
+        );
+        assert!(frames_to_regions(&[false; 4], 0.1, 0.3).is_empty());
+    }
+
+    #[test]
+    fn test_overlap_secs() {
+        let a = [region(0.0, 2.0), region(3.0, 5.0)];
+        let b = [region(1.0, 4.0)];
+        assert!((overlap_secs(&a, &b) - 2.0).abs() < 1e-9);
+        assert_eq!(overlap_secs(&a, &[]), 0.0);
+    }
+
+    #[test]
+    fn test_score() {
+        let detected = [region(0.0, 4.0)];
+        let reference = [region(1.0, 3.0), region(5.0, 7.0)];
+        let scores = score(&detected, &reference);
+        assert!((scores.precision - 0.5).abs() < 1e-9);
+        assert!((scores.recall - 0.5).abs() < 1e-9);
+        assert!((scores.f1 - 0.5).abs() < 1e-9);
+
+        let empty = score(&[], &reference);
+        assert_eq!(empty.precision, 0.0);
+        assert_eq!(empty.f1, 0.0);
+    }
+
+    #[test]
+    fn test_parse_audacity_labels() {
+        let content = "0.5\t1.5\tspeech\n2.000000\t3.250000\n# comment\n4.0 3.0\n";
+        assert_eq!(
+            parse_labels(content, Path::new("a.txt")),
+            vec![region(0.5, 1.5), region(2.0, 3.25)]
+        );
+    }
+
+    #[test]
+    fn test_rttm_labels_are_merged() {
+        let content = "SPEAKER a 1 0.000 2.000 <NA> <NA> A <NA> <NA>\n\
+                       SPEAKER a 1 1.500 1.000 <NA> <NA> B <NA> <NA>\n\
+                       SPEAKER a 1 4.000 1.000 <NA> <NA> A <NA> <NA>\n";
+        let reference = merge_regions(parse_labels(content, Path::new("a.rttm")));
+        assert_eq!(reference, vec![region(0.0, 2.5), region(4.0, 5.0)]);
+    }
+}
@@ -1,10 +1,22 @@
-//! WAV file reading and audio preparation utilities.
+//! Audio file reading and audio preparation utilities.
+//!
+//! WAV files are read with hound; MP3, OGG Vorbis, FLAC, M4A (AAC/ALAC) and
+//! other containers are decoded with symphonia, so recordings can be
+//! transcribed without converting them first.
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use rubato::{FftFixedIn, Resampler};
+use std::fs::File;
 use std::path::Path;
-
-/// Audio data read from a WAV file.
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// Audio data read from a file.
 pub struct WavAudio {
     /// Sample rate in Hz
     pub sample_rate: u32,
@@ -68,6 +80,92 @@ pub fn read_wav(path: &Path) -> Result<WavAudio> {
     })
 }
 
+/// Read an audio file in any supported format and convert to f32 samples.
+///
+/// WAV goes through [`read_wav`]; a WAV hound cannot read (e.g. ADPCM) and
+/// every other format is decoded with symphonia.
+pub fn read_audio(path: &Path) -> Result<WavAudio> {
+    let is_wav = path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("wav") || e.eq_ignore_ascii_case("wave"));
+    if !is_wav {
+        return decode_audio(path);
+    }
+    read_wav(path).or_else(|wav_error| decode_audio(path).map_err(|_| wav_error))
+}
+
+/// Decode the first audio track of a file with symphonia.
+fn decode_audio(path: &Path) -> Result<WavAudio> {
+    let file = File::open(path).with_context(|| format!("Failed to open audio file: {}", path.display()))?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(extension) = path.extension().and_then(|e| e.to_str()) {
+        hint.with_extension(extension);
+    }
+    let probed = symphonia::default::get_probe()
+        .format(&hint, stream, &FormatOptions::default(), &MetadataOptions::default())
+        .with_context(|| format!("Unsupported or unrecognized audio format: {}", path.display()))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .with_context(|| format!("No audio track in {}", path.display()))?;
+    let track_id = track.id;
+    let mut sample_rate = track.codec_params.sample_rate.unwrap_or(0);
+    let mut channels = track.codec_params.channels.map_or(0, |c| c.count());
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .context("Unsupported audio codec")?;
+
+    let mut samples = Vec::new();
+    let mut buffer: Option<SampleBuffer<f32>> = None;
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            // End of stream
+            Err(SymphoniaError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(e) => return Err(e).context("Failed to read audio packet"),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            Err(SymphoniaError::DecodeError(e)) => {
+                eprintln!("Warning: skipping undecodable audio packet: {}", e);
+                continue;
+            }
+            Err(e) => return Err(e).context("Failed to decode audio"),
+        };
+
+        let spec = *decoded.spec();
+        sample_rate = spec.rate;
+        channels = spec.channels.count();
+        // Reused between packets, replaced when a packet does not fit
+        let mut packet_buffer = buffer
+            .take()
+            .filter(|b| b.capacity() >= decoded.capacity() * channels)
+            .unwrap_or_else(|| SampleBuffer::new(decoded.capacity() as u64, spec));
+        packet_buffer.copy_interleaved_ref(decoded);
+        samples.extend_from_slice(packet_buffer.samples());
+        buffer = Some(packet_buffer);
+    }
+
+    if sample_rate == 0 || channels == 0 {
+        bail!("No audio decoded from {}", path.display());
+    }
+    let duration_secs = (samples.len() / channels) as f64 / sample_rate as f64;
+    Ok(WavAudio {
+        sample_rate,
+        channels: channels as u16,
+        duration_secs,
+        samples,
+    })
+}
+
 /// Extract a single channel from interleaved stereo samples.
 fn extract_channel(samples: &[f32], channel_index: usize, num_channels: usize) -> Vec<f32> {
     samples
@@ -148,20 +246,18 @@ fn resample_to_16khz(samples: &[f32], input_rate: u32) -> Result<Vec<f32>> {
     Ok(output)
 }
 
-/// Apply noise suppression using nnnoiseless with proper resampling.
-fn apply_denoise(samples: &[f32]) -> Result<Vec<f32>> {
-    use crate::recording::denoise::NnnoiselessDenoiser;
-
-    let denoiser = NnnoiselessDenoiser::new();
-    denoiser.denoise_buffer(samples)
-}
-
 use crate::cli::args::ChannelMode;
+use crate::recording::denoise::{denoise_samples, DenoiseSettings};
 
 /// Prepare audio for Whisper transcription.
 ///
-/// Handles channel selection, resampling to 16kHz, and optional denoising.
-pub fn prepare_for_whisper(audio: &WavAudio, channel_mode: ChannelMode, denoise: bool) -> Result<PreparedAudio> {
+/// Handles channel selection, resampling to 16kHz, and optional denoising
+/// with the given backend.
+pub fn prepare_for_whisper(
+    audio: &WavAudio,
+    channel_mode: ChannelMode,
+    denoise: Option<&DenoiseSettings>,
+) -> Result<PreparedAudio> {
     let is_stereo = audio.channels == 2;
 
     // Extract channels based on mode
@@ -210,8 +306,8 @@ pub fn prepare_for_whisper(audio: &WavAudio, channel_mode: ChannelMode, denoise:
     };
 
     // Apply denoising if requested
-    if denoise {
-        samples = apply_denoise(&samples).context("Denoising failed")?;
+    if let Some(settings) = denoise {
+        samples = denoise_samples(settings, &samples).context("Denoising failed")?;
     }
 
     Ok(PreparedAudio {
@@ -254,6 +350,44 @@ mod tests {
         assert_eq!(result, mono);
     }
 
+    #[test]
+    fn test_decode_audio_matches_wav_reader() {
+        let path = std::env::temp_dir().join(format!("wav_reader_decode_{}.wav", std::process::id()));
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 22050,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for i in 0..4410i32 {
+            writer.write_sample((i * 7 % 20000 - 10000) as i16).unwrap();
+            writer.write_sample((-i * 3 % 20000) as i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let wav = read_wav(&path).unwrap();
+        let decoded = decode_audio(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(decoded.sample_rate, 22050);
+        assert_eq!(decoded.channels, 2);
+        assert!((decoded.duration_secs - 0.2).abs() < 1e-6);
+        assert_eq!(decoded.samples.len(), wav.samples.len());
+        for (a, b) in decoded.samples.iter().zip(&wav.samples) {
+            assert!((a - b).abs() < 1e-4, "{} vs {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_read_audio_rejects_unknown_format() {
+        let path = std::env::temp_dir().join(format!("wav_reader_unknown_{}.mp3", std::process::id()));
+        std::fs::write(&path, b"not audio at all").unwrap();
+        let result = read_audio(&path);
+        std::fs::remove_file(&path).unwrap();
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_resample_same_rate() {
         let samples = vec![1.0, 2.0, 3.0, 4.0];
@@ -0,0 +1,121 @@
+//! Help dialog with searchable troubleshooting topics.
+//!
+//! Topics live in [`topics`]; each is shown as an expandable row with the
+//! steps to check and a button that opens the part of the app to fix it in.
+
+pub mod topics;
+
+use crate::ui::a11y;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, Expander, Label, ListBox, Orientation, ScrolledWindow, SearchEntry, Window};
+use std::rc::Rc;
+use topics::{search, HelpLink, HelpTopic};
+
+/// Show the Help dialog.
+///
+/// `on_link` opens what a topic's button leads to; the dialog closes first.
+pub fn show_help_dialog(parent: &impl IsA<Window>, on_link: impl Fn(HelpLink) + 'static) {
+    let dialog = Window::builder()
+        .title("Довідка")
+        .modal(true)
+        .transient_for(parent)
+        .default_width(520)
+        .default_height(560)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+
+    let search_entry = SearchEntry::new();
+    search_entry.set_placeholder_text(Some("Опишіть проблему: гаряча клавіша, вставка, модель…"));
+    a11y::set_name(&search_entry, "Пошук у довідці");
+    main_box.append(&search_entry);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(gtk4::SelectionMode::None);
+    let scrolled = ScrolledWindow::builder().vexpand(true).child(&list_box).build();
+    main_box.append(&scrolled);
+
+    let empty_label = Label::new(Some("Нічого не знайдено. Спробуйте інші слова."));
+    empty_label.add_css_class("dim-label");
+    main_box.append(&empty_label);
+
+    let on_link: Rc<dyn Fn(HelpLink)> = Rc::new(on_link);
+    let show_topics = {
+        let list_box = list_box.clone();
+        let empty_label = empty_label.clone();
+        let dialog_weak = dialog.downgrade();
+        move |query: &str| {
+            while let Some(row) = list_box.first_child() {
+                list_box.remove(&row);
+            }
+            let found = search(query);
+            // One match is shown open, so its steps are visible right away
+            let expanded = found.len() == 1;
+            for topic in &found {
+                list_box.append(&topic_row(topic, expanded, &dialog_weak, &on_link));
+            }
+            empty_label.set_visible(found.is_empty());
+        }
+    };
+    show_topics("");
+
+    search_entry.connect_search_changed(move |entry| show_topics(entry.text().as_str()));
+
+    let close_button = Button::with_label("Закрити");
+    close_button.set_halign(Align::End);
+    let dialog_weak = dialog.downgrade();
+    close_button.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+    main_box.append(&close_button);
+
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+    search_entry.grab_focus();
+}
+
+/// Expandable row with the steps of `topic` and its button.
+fn topic_row(
+    topic: &HelpTopic,
+    expanded: bool,
+    dialog_weak: &gtk4::glib::WeakRef<Window>,
+    on_link: &Rc<dyn Fn(HelpLink)>,
+) -> Expander {
+    let body = GtkBox::new(Orientation::Vertical, 6);
+    body.set_margin_start(12);
+    body.set_margin_top(6);
+    body.set_margin_bottom(6);
+
+    for (i, step) in topic.steps.iter().enumerate() {
+        let label = Label::new(Some(&format!("{}. {}", i + 1, step)));
+        label.set_wrap(true);
+        label.set_xalign(0.0);
+        label.set_selectable(true);
+        body.append(&label);
+    }
+
+    if let Some((label, link)) = topic.link {
+        let button = Button::with_label(label);
+        button.set_halign(Align::Start);
+        let dialog_weak = dialog_weak.clone();
+        let on_link = on_link.clone();
+        button.connect_clicked(move |_| {
+            if let Some(dialog) = dialog_weak.upgrade() {
+                dialog.close();
+            }
+            on_link(link);
+        });
+        body.append(&button);
+    }
+
+    let expander = Expander::new(Some(topic.title));
+    expander.set_expanded(expanded);
+    expander.set_child(Some(&body));
+    expander
+}
@@ -0,0 +1,252 @@
+//! Help topics shown in the Help dialog.
+//!
+//! Each topic answers one problem users run into: what to check, in order,
+//! and a button to the place in the app where it is fixed. Search matches
+//! the title, keywords and steps, so a topic is found by the words the user
+//! would describe the problem with.
+
+use crate::dialogs::settings::SettingsSection;
+
+/// Where the button of a topic leads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HelpLink {
+    Settings(SettingsSection),
+    Models,
+}
+
+/// One troubleshooting topic.
+#[derive(Debug)]
+pub struct HelpTopic {
+    pub title: &'static str,
+    /// Words users search with that are not in the title, in lowercase
+    pub keywords: &'static [&'static str],
+    /// What to check, in order
+    pub steps: &'static [&'static str],
+    /// Label of the button under the steps and where it leads
+    pub link: Option<(&'static str, HelpLink)>,
+}
+
+pub const TOPICS: &[HelpTopic] = &[
+    HelpTopic {
+        title: "Гаряча клавіша не працює",
+        keywords: &["hotkey", "shortcut", "комбінація", "клавіші", "не реагує", "wayland"],
+        steps: &[
+            "Перевірте, що глобальні гарячі клавіші увімкнено в налаштуваннях і зміни збережено.",
+            "Комбінація не має бути зайнята системою чи іншою програмою: запишіть іншу кнопкою «Записати».",
+            "У сеансі Wayland програма не може перехопити клавіші глобально. Призначте в налаштуваннях \
+             клавіатури GNOME чи KDE власну комбінацію з командою `voice-dictation --start`.",
+            "Гаряча клавіша діє, поки програма працює, навіть із закритим вікном (іконка в треї).",
+        ],
+        link: Some(("Відкрити гарячі клавіші", HelpLink::Settings(SettingsSection::Hotkeys))),
+    },
+    HelpTopic {
+        title: "Порожній результат або «Не вдалося розпізнати мову»",
+        keywords: &[
+            "порожньо",
+            "нічого",
+            "тиша",
+            "мікрофон",
+            "empty",
+            "не розпізнає",
+            "закороткий",
+        ],
+        steps: &[
+            "Під час запису індикатор рівня має рухатися й зеленіти на мовленні. Якщо він стоїть, \
+             у системі вибрано інший мікрофон або його вимкнено.",
+            "Записи коротші за секунду не розпізнаються: говоріть після натискання кнопки.",
+            "Перевірте мову розпізнавання: модель погано розпізнає мову, відмінну від вибраної.",
+            "Якщо мікрофон тихий, увімкніть AGC. У неперервному режимі відкалібруйте VAD, \
+             щоб тихе мовлення не вважалося тишею.",
+        ],
+        link: Some((
+            "Відкрити налаштування запису",
+            HelpLink::Settings(SettingsSection::Recording),
+        )),
+    },
+    HelpTopic {
+        title: "Текст не вставляється автоматично (Wayland)",
+        keywords: &[
+            "вставка",
+            "paste",
+            "ctrl+v",
+            "xdotool",
+            "wayland",
+            "автовставка",
+            "буфер",
+        ],
+        steps: &[
+            "Автовставка натискає Ctrl+V через xdotool, який працює лише в X11 і для програм XWayland.",
+            "У сеансі Wayland вимкніть автовставку й залиште автокопіювання: текст буде в буфері \
+             обміну, вставте його самі Ctrl+V.",
+            "У X11 встановіть xdotool (sudo dnf install xdotool), якщо його немає.",
+            "Невдалі вставки за сеанс видно у вікні «Моделі», у списку прихованих збоїв.",
+        ],
+        link: Some((
+            "Відкрити налаштування запису",
+            HelpLink::Settings(SettingsSection::Recording),
+        )),
+    },
+    HelpTopic {
+        title: "Яку модель вибрати",
+        keywords: &[
+            "модель",
+            "model",
+            "whisper",
+            "швидкість",
+            "якість",
+            "повільно",
+            "rtf",
+            "large",
+            "turbo",
+        ],
+        steps: &[
+            "Base Q5 — швидка й достатня для диктування на більшості комп'ютерів.",
+            "Small чи Medium точніші для української та шумних записів, але повільніші.",
+            "Large v3 Turbo майже така ж точна, як Large v3, і значно швидша; їй потрібно більше пам'яті.",
+            "Якщо в неперервному режимі RTF червоний (понад 1), модель не встигає — візьміть меншу.",
+            "Parakeet TDT — окремий рушій розпізнавання; вибирається в налаштуваннях після завантаження.",
+        ],
+        link: Some(("Відкрити моделі", HelpLink::Models)),
+    },
+    HelpTopic {
+        title: "Розпізнано не тією мовою",
+        keywords: &[
+            "мова",
+            "language",
+            "переклад",
+            "англійська",
+            "російська",
+            "транслітерація",
+        ],
+        steps: &[
+            "Виберіть мову розпізнавання в налаштуваннях; модель не вгадує її сама.",
+            "Шаблон сесії може задавати власну мову для конференцій.",
+            "У змішаному мовленні виберіть основну мову: окремі слова іншою мовою можуть вийти транслітерованими.",
+        ],
+        link: Some((
+            "Відкрити мову розпізнавання",
+            HelpLink::Settings(SettingsSection::Language),
+        )),
+    },
+    HelpTopic {
+        title: "Конференція не записує співрозмовників",
+        keywords: &[
+            "конференція",
+            "системне аудіо",
+            "loopback",
+            "монітор",
+            "дзвінок",
+            "навушники",
+            "учасник",
+        ],
+        steps: &[
+            "Системне аудіо береться з монітора виходу. Якщо дзвінок звучить у навушниках, \
+             виберіть у налаштуваннях монітор саме навушників.",
+            "Від'єднаний пристрій замінюється монітором за замовчуванням — перевірте вибір після \
+             підключення гарнітури.",
+            "Назви джерел показує `pactl list sources short`.",
+        ],
+        link: Some((
+            "Відкрити налаштування запису",
+            HelpLink::Settings(SettingsSection::Recording),
+        )),
+    },
+    HelpTopic {
+        title: "Мікрофон від'єднався під час запису",
+        keywords: &[
+            "bluetooth",
+            "навушники",
+            "гарнітура",
+            "розрядився",
+            "призупинено",
+            "пристрій",
+        ],
+        steps: &[
+            "Запис продовжується з мікрофона, який система тепер вважає типовим; рядок стану називає його.",
+            "Якщо іншого мікрофона немає, запис призупиняється: підключіть гарнітуру знову, і він продовжиться сам.",
+            "Зупинка призупиненого запису розпізнає все, що встигло записатися до від'єднання.",
+            "Щоб запис продовжувався з потрібного мікрофона, виберіть його типовим у налаштуваннях звуку системи.",
+        ],
+        link: None,
+    },
+    HelpTopic {
+        title: "Іконки в треї не видно",
+        keywords: &["трей", "tray", "gnome", "іконка", "appindicator"],
+        steps: &[
+            "GNOME не показує трей без розширення: sudo dnf install gnome-shell-extension-appindicator.",
+            "Після встановлення перелогіньтеся.",
+        ],
+        link: None,
+    },
+];
+
+/// Topics matching every word of `query`, best matches first; all topics
+/// for an empty query.
+pub fn search(query: &str) -> Vec<&'static HelpTopic> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    let mut found: Vec<(u32, &'static HelpTopic)> = TOPICS
+        .iter()
+        .filter_map(|topic| {
+            let title = topic.title.to_lowercase();
+            let steps = topic.steps.join(" ").to_lowercase();
+            let mut score = 0;
+            for word in &words {
+                let word_score = if title.contains(word.as_str()) {
+                    3
+                } else if topic.keywords.iter().any(|k| k.contains(word.as_str())) {
+                    2
+                } else if steps.contains(word.as_str()) {
+                    1
+                } else {
+                    return None;
+                };
+                score += word_score;
+            }
+            Some((score, topic))
+        })
+        .collect();
+    // Stable: equal scores keep the order of TOPICS
+    found.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    found.into_iter().map(|(_, topic)| topic).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn titles(query: &str) -> Vec<&'static str> {
+        search(query).iter().map(|t| t.title).collect()
+    }
+
+    #[test]
+    fn test_empty_query_lists_all_topics() {
+        assert_eq!(search("").len(), TOPICS.len());
+        assert_eq!(search("   ").len(), TOPICS.len());
+    }
+
+    #[test]
+    fn test_search_by_title_keyword_and_steps() {
+        assert_eq!(titles("Гаряча")[0], "Гаряча клавіша не працює");
+        assert_eq!(titles("hotkey")[0], "Гаряча клавіша не працює");
+        assert_eq!(titles("xdotool")[0], "Текст не вставляється автоматично (Wayland)");
+        assert!(titles("pactl").contains(&"Конференція не записує співрозмовників"));
+    }
+
+    #[test]
+    fn test_every_word_must_match_and_title_ranks_first() {
+        assert!(search("гаряча модель").is_empty());
+        // "wayland" is in two topics; the one with it in the title comes first
+        let found = titles("wayland");
+        assert_eq!(found[0], "Текст не вставляється автоматично (Wayland)");
+        assert!(found.contains(&"Гаряча клавіша не працює"));
+    }
+
+    #[test]
+    fn test_keywords_are_lowercase() {
+        for topic in TOPICS {
+            for keyword in topic.keywords {
+                assert_eq!(*keyword, keyword.to_lowercase(), "{}", topic.title);
+            }
+        }
+    }
+}
@@ -0,0 +1,157 @@
+//! Correcting the text of a history entry.
+//!
+//! The full text opens in an editable view; saving stores it in the entry
+//! while the transcribed text stays in `original_text`. It is shown below
+//! the editor together with the texts replaced by re-transcriptions, so
+//! any of them can be brought back.
+
+use super::list::HistoryList;
+use crate::domain::types::HistoryEntry;
+use crate::ui::a11y;
+use chrono::Local;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, Expander, Label, Orientation, ScrolledWindow, TextView, Window, WrapMode};
+
+/// Show a window editing the text of `entry`, saved into `list`'s history.
+pub fn show_text_editor(parent: &impl IsA<Window>, entry: &HistoryEntry, list: &HistoryList) {
+    let dialog = Window::builder()
+        .title(format!("Текст запису від {}", entry.formatted_timestamp()))
+        .modal(true)
+        .transient_for(parent)
+        .default_width(520)
+        .default_height(420)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+
+    let text_view = TextView::builder()
+        .wrap_mode(WrapMode::WordChar)
+        .left_margin(6)
+        .right_margin(6)
+        .top_margin(6)
+        .bottom_margin(6)
+        .build();
+    text_view.buffer().set_text(&entry.text);
+    a11y::set_name(&text_view, "Текст запису");
+    let scrolled = ScrolledWindow::builder()
+        .vexpand(true)
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .child(&text_view)
+        .build();
+    main_box.append(&scrolled);
+
+    // The transcription as it came out, once the text has been corrected
+    if let Some(original) = &entry.original_text {
+        let original_box = GtkBox::new(Orientation::Vertical, 6);
+        let original_label = Label::new(Some(original));
+        original_label.set_halign(Align::Start);
+        original_label.set_wrap(true);
+        original_label.set_selectable(true);
+        original_label.add_css_class("dim-label");
+        original_box.append(&original_label);
+
+        let restore_button = Button::with_label("Відновити");
+        restore_button.set_halign(Align::Start);
+        restore_button.set_tooltip_text(Some("Повернути розпізнаний текст у редактор"));
+        let buffer = text_view.buffer();
+        let original = original.clone();
+        restore_button.connect_clicked(move |_| buffer.set_text(&original));
+        original_box.append(&restore_button);
+
+        let expander = Expander::builder()
+            .label("Розпізнаний текст")
+            .child(&original_box)
+            .build();
+        main_box.append(&expander);
+    }
+
+    // Texts replaced by transcribing the recording again, newest first
+    if !entry.previous_versions.is_empty() {
+        let versions_box = GtkBox::new(Orientation::Vertical, 6);
+        for version in entry.previous_versions.iter().rev() {
+            let heading = format!(
+                "{} · {}",
+                version.replaced_at.with_timezone(&Local).format("%Y-%m-%d %H:%M"),
+                version.model.as_deref().unwrap_or("модель невідома")
+            );
+            let heading_label = Label::new(Some(&heading));
+            heading_label.set_halign(Align::Start);
+            heading_label.add_css_class("heading");
+            versions_box.append(&heading_label);
+
+            let text_label = Label::new(Some(&version.text));
+            text_label.set_halign(Align::Start);
+            text_label.set_wrap(true);
+            text_label.set_selectable(true);
+            text_label.add_css_class("dim-label");
+            versions_box.append(&text_label);
+
+            let restore_button = Button::with_label("Відновити");
+            restore_button.set_halign(Align::Start);
+            restore_button.set_tooltip_text(Some("Повернути цю версію в редактор"));
+            a11y::set_name(&restore_button, &format!("Відновити версію від {}", heading));
+            let buffer = text_view.buffer();
+            let text = version.text.clone();
+            restore_button.connect_clicked(move |_| buffer.set_text(&text));
+            versions_box.append(&restore_button);
+        }
+
+        let expander = Expander::builder()
+            .label(format!("Попередні версії ({})", entry.previous_versions.len()))
+            .child(&versions_box)
+            .build();
+        main_box.append(&expander);
+    }
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 8);
+    button_box.set_halign(Align::End);
+
+    let cancel_button = Button::with_label("Скасувати");
+    let dialog_weak = dialog.downgrade();
+    cancel_button.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+    button_box.append(&cancel_button);
+
+    let save_button = Button::with_label("Зберегти");
+    save_button.add_css_class("suggested-action");
+    if list.read_only {
+        save_button.set_sensitive(false);
+        save_button.set_tooltip_text(Some("Історія лише для читання"));
+        text_view.set_editable(false);
+    }
+    let id = entry.id.clone();
+    let list = list.clone();
+    let dialog_weak = dialog.downgrade();
+    save_button.connect_clicked(move |_| {
+        let buffer = text_view.buffer();
+        let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+        if text.trim().is_empty() {
+            return;
+        }
+        {
+            let mut h = list.history.lock();
+            if !h.set_text(&id, &text) {
+                return;
+            }
+            if let Err(e) = h.save() {
+                eprintln!("Помилка збереження історії: {}", e);
+            }
+        }
+        list.populate();
+    });
+    button_box.append(&save_button);
+
+    main_box.append(&button_box);
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
@@ -1,18 +1,29 @@
 //! History export functionality.
 
-use crate::domain::types::SharedHistory;
-use chrono::{DateTime, Utc};
+use super::HistoryFilter;
+use crate::app::config::{save_config, Config};
+use crate::domain::types::{HistoryEntry, SharedHistory};
+use crate::history::{AnkiTemplate, ExportFormat};
+use crate::ui::a11y;
 use gtk4::prelude::*;
-use gtk4::{FileChooserNative, Window};
+use gtk4::{Align, Box as GtkBox, Button, Entry, FileChooserNative, Label, Orientation, Window};
+use parking_lot::Mutex;
 use std::cell::RefCell;
+use std::collections::HashSet;
+use std::path::PathBuf;
 use std::rc::Rc;
+use std::sync::Arc;
 
+/// ID of the format choice in the export file dialog
+const FORMAT_CHOICE: &str = "format";
+
+/// Export the selected entries (or all filtered ones if none are selected)
+/// in the format chosen in the file dialog.
 pub fn export_history(
     parent: &Window,
     history: SharedHistory,
-    search_query: &Rc<RefCell<String>>,
-    date_from: &Rc<RefCell<Option<DateTime<Utc>>>>,
-    date_to: &Rc<RefCell<Option<DateTime<Utc>>>>,
+    selected: &Rc<RefCell<HashSet<String>>>,
+    filter: &Rc<RefCell<HistoryFilter>>,
 ) {
     let dialog = FileChooserNative::builder()
         .title("Експортувати історію")
@@ -21,6 +32,10 @@ pub fn export_history(
         .transient_for(parent)
         .build();
 
+    let formats: Vec<(&str, &str)> = ExportFormat::ALL.iter().map(|f| (f.extension(), f.label())).collect();
+    dialog.add_choice(FORMAT_CHOICE, "Формат", &formats);
+    dialog.set_choice(FORMAT_CHOICE, ExportFormat::Text.extension());
+
     // Set default filename
     let default_name = format!(
         "voice-dictation-history-{}.txt",
@@ -29,35 +44,28 @@ pub fn export_history(
     dialog.set_current_name(&default_name);
 
     let history_for_export = history.clone();
-    let search_query_for_export = search_query.clone();
-    let date_from_for_export = date_from.clone();
-    let date_to_for_export = date_to.clone();
+    let selected_for_export = selected.clone();
+    let filter_for_export = filter.clone();
 
     dialog.connect_response(move |dialog, response| {
         if response == gtk4::ResponseType::Accept {
             if let Some(file) = dialog.file() {
                 if let Some(path) = file.path() {
+                    let format = dialog
+                        .choice(FORMAT_CHOICE)
+                        .map(|id| ExportFormat::from_extension(&id))
+                        .unwrap_or_default();
+                    let path = with_format_extension(path, format);
                     let history_guard = history_for_export.lock();
-                    let query = search_query_for_export.borrow();
-                    let from_date = date_from_for_export.borrow();
-                    let to_date = date_to_for_export.borrow();
-
-                    // Get filtered entries
-                    let date_filtered: Vec<_> = history_guard
-                        .filter_by_date_range(*from_date, *to_date)
+                    let selected = selected_for_export.borrow();
+                    let entries: Vec<&HistoryEntry> = filter_for_export
+                        .borrow()
+                        .apply(&*history_guard)
                         .into_iter()
+                        .filter(|e| selected.is_empty() || selected.contains(&e.id))
                         .collect();
 
-                    let entries: Vec<_> = if query.is_empty() {
-                        date_filtered
-                    } else {
-                        date_filtered
-                            .into_iter()
-                            .filter(|e| e.text.to_lowercase().contains(&query.to_lowercase()))
-                            .collect()
-                    };
-
-                    if let Err(e) = crate::history::export_to_text(&entries, &path) {
+                    if let Err(e) = crate::history::export_history(&entries, &path, format) {
                         eprintln!("Помилка експорту: {}", e);
                         // TODO: Show error dialog
                     }
@@ -69,3 +77,234 @@ pub fn export_history(
 
     dialog.show();
 }
+
+/// `path` with the extension of `format`, unless it already has it.
+fn with_format_extension(path: PathBuf, format: ExportFormat) -> PathBuf {
+    let matches = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(format.extension()));
+    // The default name ends in .txt whatever format is chosen
+    if matches {
+        path
+    } else if path.extension().is_some_and(|ext| ext == "txt") {
+        path.with_extension(format.extension())
+    } else {
+        let mut name = path.into_os_string();
+        name.push(".");
+        name.push(format.extension());
+        PathBuf::from(name)
+    }
+}
+
+/// Export one WAV per speaker of a conference recording into a chosen folder.
+pub fn export_speaker_tracks(parent: &Window, recording: PathBuf) {
+    let dialog = FileChooserNative::builder()
+        .title("Тека для аудіо мовців")
+        .action(gtk4::FileChooserAction::SelectFolder)
+        .modal(true)
+        .transient_for(parent)
+        .build();
+
+    dialog.connect_response(move |dialog, response| {
+        if response == gtk4::ResponseType::Accept {
+            if let Some(dir) = dialog.file().and_then(|f| f.path()) {
+                let recording = recording.clone();
+                // Decoding and writing long recordings takes a while
+                std::thread::spawn(move || {
+                    let stem = recording
+                        .file_stem()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_else(|| "recording".to_string());
+                    let result = crate::infrastructure::recordings::speaker_tracks(&recording)
+                        .and_then(|tracks| crate::recording::speaker_tracks::write_tracks(&tracks, &dir, &stem, 16000));
+                    match result {
+                        Ok(paths) => println!("Збережено аудіо мовців: {} файл(ів) у {}", paths.len(), dir.display()),
+                        Err(e) => eprintln!("Помилка експорту аудіо мовців: {}", e),
+                    }
+                });
+            }
+        }
+        dialog.destroy();
+    });
+
+    dialog.show();
+}
+
+/// Export selected entries (or all filtered ones if none are selected) as Anki flashcards.
+///
+/// Shows the front/back templates for editing first; edited templates are
+/// saved to the config for the next export.
+pub fn export_anki(
+    parent: &Window,
+    history: SharedHistory,
+    config: Arc<Mutex<Config>>,
+    selected: &Rc<RefCell<HashSet<String>>>,
+    filter: &Rc<RefCell<HistoryFilter>>,
+) {
+    // Snapshot the scope now, so it matches what the dialog says
+    let entry_ids: Vec<String> = {
+        let history_guard = history.lock();
+        let selected = selected.borrow();
+        filter
+            .borrow()
+            .apply(&*history_guard)
+            .into_iter()
+            .filter(|e| selected.is_empty() || selected.contains(&e.id))
+            .map(|e| e.id.clone())
+            .collect()
+    };
+    let scope = if selected.borrow().is_empty() {
+        format!("Буде експортовано всі показані записи: {}", entry_ids.len())
+    } else {
+        format!("Буде експортовано вибрані записи: {}", entry_ids.len())
+    };
+
+    let template = {
+        let cfg = config.lock();
+        AnkiTemplate {
+            front: cfg.anki_front_template.clone(),
+            back: cfg.anki_back_template.clone(),
+        }
+    };
+
+    let window = Window::builder()
+        .title("Експорт в Anki")
+        .modal(true)
+        .transient_for(parent)
+        .default_width(420)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 8);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+
+    let front_label = Label::new(Some("Лицьова сторона:"));
+    front_label.set_halign(Align::Start);
+    main_box.append(&front_label);
+    let front_entry = Entry::builder().text(template.front.as_str()).build();
+    a11y::set_labelled_by(&front_entry, &front_label);
+    main_box.append(&front_entry);
+
+    let back_label = Label::new(Some("Зворотна сторона:"));
+    back_label.set_halign(Align::Start);
+    main_box.append(&back_label);
+    let back_entry = Entry::builder().text(template.back.as_str()).build();
+    a11y::set_labelled_by(&back_entry, &back_label);
+    main_box.append(&back_entry);
+
+    let hint_label = Label::new(Some(
+        "Підстановки: {term} і {definition} — текст до і після першого \"—\", \"-\" або \":\"; \
+         {text}, {date}, {language}, {duration}, {tags}. Дозволено HTML.",
+    ));
+    hint_label.set_wrap(true);
+    hint_label.set_xalign(0.0);
+    hint_label.add_css_class("dim-label");
+    main_box.append(&hint_label);
+
+    let scope_label = Label::new(Some(&scope));
+    scope_label.set_halign(Align::Start);
+    main_box.append(&scope_label);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 12);
+    button_box.set_halign(Align::End);
+    button_box.set_margin_top(6);
+
+    let cancel_button = Button::with_label("Скасувати");
+    let window_weak = window.downgrade();
+    cancel_button.connect_clicked(move |_| {
+        if let Some(window) = window_weak.upgrade() {
+            window.close();
+        }
+    });
+    button_box.append(&cancel_button);
+
+    let export_button = Button::with_label("Експортувати...");
+    export_button.set_sensitive(!entry_ids.is_empty());
+    let window_weak = window.downgrade();
+    export_button.connect_clicked(move |_| {
+        let Some(window) = window_weak.upgrade() else {
+            return;
+        };
+        let template = AnkiTemplate {
+            front: front_entry.text().to_string(),
+            back: back_entry.text().to_string(),
+        };
+        {
+            let mut cfg = config.lock();
+            cfg.anki_front_template = template.front.clone();
+            cfg.anki_back_template = template.back.clone();
+            if let Err(e) = cfg.validate().and_then(|_| save_config(&cfg)) {
+                eprintln!("Помилка збереження конфігу: {}", e);
+            }
+        }
+        choose_anki_file(&window, history.clone(), entry_ids.clone(), template);
+    });
+    button_box.append(&export_button);
+
+    main_box.append(&button_box);
+    window.set_child(Some(&main_box));
+    window.present();
+}
+
+fn choose_anki_file(window: &Window, history: SharedHistory, entry_ids: Vec<String>, template: AnkiTemplate) {
+    let dialog = FileChooserNative::builder()
+        .title("Зберегти картки Anki")
+        .action(gtk4::FileChooserAction::Save)
+        .modal(true)
+        .transient_for(window)
+        .build();
+    dialog.set_current_name(&format!("anki-cards-{}.txt", chrono::Local::now().format("%Y-%m-%d")));
+
+    let window_weak = window.downgrade();
+    dialog.connect_response(move |dialog, response| {
+        if response == gtk4::ResponseType::Accept {
+            if let Some(path) = dialog.file().and_then(|f| f.path()) {
+                let history_guard = history.lock();
+                let entries: Vec<&HistoryEntry> = history_guard
+                    .entries()
+                    .iter()
+                    .filter(|e| entry_ids.contains(&e.id))
+                    .collect();
+
+                match crate::history::export_to_anki(&entries, &path, &template) {
+                    Ok(count) => eprintln!("Експортовано карток Anki: {} ({})", count, path.display()),
+                    Err(e) => eprintln!("Помилка експорту: {}", e),
+                }
+                if let Some(window) = window_weak.upgrade() {
+                    window.close();
+                }
+            }
+        }
+        dialog.destroy();
+    });
+
+    dialog.show();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_format_extension() {
+        let csv = ExportFormat::Csv;
+        assert_eq!(
+            with_format_extension(PathBuf::from("/tmp/h.txt"), csv),
+            PathBuf::from("/tmp/h.csv")
+        );
+        assert_eq!(
+            with_format_extension(PathBuf::from("/tmp/h.CSV"), csv),
+            PathBuf::from("/tmp/h.CSV")
+        );
+        assert_eq!(
+            with_format_extension(PathBuf::from("/tmp/h"), csv),
+            PathBuf::from("/tmp/h.csv")
+        );
+        assert_eq!(
+            with_format_extension(PathBuf::from("/tmp/h.2025"), ExportFormat::Markdown),
+            PathBuf::from("/tmp/h.2025.md")
+        );
+    }
+}
@@ -0,0 +1,62 @@
+//! Import of another history file into the history.
+
+use super::list::HistoryList;
+use crate::history::read_history_file;
+use gtk4::prelude::*;
+use gtk4::{FileChooserNative, Window};
+
+/// Pick a history file and add its entries that are not in the history yet.
+pub fn import_history(parent: &Window, list: &HistoryList) {
+    let chooser = FileChooserNative::builder()
+        .title("Імпортувати історію")
+        .action(gtk4::FileChooserAction::Open)
+        .modal(true)
+        .transient_for(parent)
+        .build();
+
+    let filter = gtk4::FileFilter::new();
+    filter.set_name(Some("Файли історії"));
+    for pattern in ["*.json", "*.jsonl", "*.sqlite", "*.db", "*.enc"] {
+        filter.add_pattern(pattern);
+    }
+    chooser.add_filter(&filter);
+
+    let list = list.clone();
+    chooser.connect_response(move |chooser, response| {
+        if response == gtk4::ResponseType::Accept {
+            if let Some(path) = chooser.file().and_then(|f| f.path()) {
+                import_file(&list, &path);
+            }
+        }
+        chooser.destroy();
+    });
+    chooser.show();
+}
+
+fn import_file(list: &HistoryList, path: &std::path::Path) {
+    let entries = match read_history_file(path) {
+        Ok(entries) => entries,
+        Err(e) => {
+            eprintln!("Помилка імпорту історії: {:#}", e);
+            list.toast.notify("Не вдалося прочитати файл історії");
+            return;
+        }
+    };
+    let total = entries.len();
+    let added = {
+        let mut h = list.history.lock();
+        let added = h.import(entries);
+        if added > 0 {
+            if let Err(e) = h.save() {
+                eprintln!("Помилка збереження історії: {}", e);
+            }
+        }
+        added
+    };
+    list.fill_tag_combo();
+    list.populate();
+    list.toast.notify(&format!(
+        "Імпортовано записів: {} із {} (решта вже є в історії)",
+        added, total
+    ));
+}
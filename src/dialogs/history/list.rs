@@ -1,84 +1,213 @@
 //! History list population and row creation.
 
-use crate::domain::types::SharedHistory;
-use chrono::{DateTime, Utc};
+use super::trash::{self, UndoToast};
+use super::HistoryFilter;
+use crate::app::config::Config;
+use crate::domain::types::{parse_tags, HistoryEntry, SharedHistory};
+use crate::transcription::confidence::LOW_SPEAKER_CONFIDENCE;
+use crate::ui::{a11y, copy_to_clipboard};
 use gtk4::prelude::*;
-use gtk4::{glib, Align, Box as GtkBox, Button, Label, ListBox, ListBoxRow, Orientation};
-use std::cell::RefCell;
+use gtk4::{
+    glib, Align, Box as GtkBox, Button, CheckButton, ComboBoxText, DropDown, Entry, Label, ListBox, ListBoxRow,
+    MediaFile, MenuButton, Orientation, Popover, ToggleButton,
+};
+use parking_lot::Mutex;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 use std::rc::Rc;
+use std::sync::Arc;
 
-/// Context for creating history rows, reducing parameter count
-struct HistoryRowContext {
-    history: SharedHistory,
-    list_box: ListBox,
-    search_query: Rc<RefCell<String>>,
+/// Rows added at a time, so thousands of entries do not stall the dialog
+pub const PAGE_SIZE: usize = 50;
+
+/// The history list with the state it is built from, shared by its rows
+#[derive(Clone)]
+pub struct HistoryList {
+    pub history: SharedHistory,
+    pub list_box: ListBox,
+    pub filter: Rc<RefCell<HistoryFilter>>,
+    pub selected: Rc<RefCell<HashSet<String>>>,
+    /// Tag filter, refilled when tags are edited
+    pub tag_combo: ComboBoxText,
+    /// Read once, as rows are built while the history is locked
+    pub read_only: bool,
+    /// For transcribing recordings again
+    pub config: Arc<Mutex<Config>>,
+    /// Offers to undo the last deletion
+    pub toast: UndoToast,
+    /// Rows built so far; "Показати ще" adds the next page
+    pub shown: Rc<Cell<usize>>,
+    /// Number of selected entries
+    pub selection_label: Label,
 }
 
-pub fn populate_list(
-    list_box: &ListBox,
-    history: SharedHistory,
-    search_query: &Rc<RefCell<String>>,
-    date_from: &Rc<RefCell<Option<DateTime<Utc>>>>,
-    date_to: &Rc<RefCell<Option<DateTime<Utc>>>>,
-) {
-    // Remove all existing rows
-    while let Some(child) = list_box.first_child() {
-        list_box.remove(&child);
-    }
-
-    let history_guard = history.lock();
-    let query = search_query.borrow();
-    let from_date = date_from.borrow();
-    let to_date = date_to.borrow();
-
-    // First filter by date
-    let date_filtered: Vec<_> = history_guard
-        .filter_by_date_range(*from_date, *to_date)
-        .into_iter()
-        .collect();
-
-    // Then filter by search query
-    let entries: Vec<_> = if query.is_empty() {
-        date_filtered
-    } else {
-        date_filtered
-            .into_iter()
-            .filter(|e| e.text.to_lowercase().contains(&query.to_lowercase()))
-            .collect()
-    };
+impl HistoryList {
+    /// Rebuild the rows from the entries passing the filter, as many as
+    /// were shown before.
+    pub fn populate(&self) {
+        // Remove all existing rows
+        while let Some(child) = self.list_box.first_child() {
+            self.list_box.remove(&child);
+        }
+        self.append_rows(0, self.shown.get().max(PAGE_SIZE));
+    }
 
-    let row_ctx = HistoryRowContext {
-        history: history.clone(),
-        list_box: list_box.clone(),
-        search_query: search_query.clone(),
-    };
+    /// Rebuild the rows for a changed filter, starting again from the first page.
+    pub fn populate_first_page(&self) {
+        self.shown.set(PAGE_SIZE);
+        self.populate();
+    }
 
-    for entry in entries {
-        let row = create_history_row(
-            &entry.id,
-            &entry.text,
-            &entry.formatted_timestamp(),
-            &entry.formatted_duration(),
-            &entry.preview(),
-            &row_ctx,
-        );
-        list_box.append(&row);
+    /// Append the rows `start..end` of the entries passing the filter, pinned
+    /// ones first under their own header, and a "load more" row if some are left.
+    fn append_rows(&self, start: usize, end: usize) {
+        let history_guard = self.history.lock();
+        let entries = self.filter.borrow().apply(&*history_guard);
+
+        // Drop selections that no longer pass the filter
+        self.selected
+            .borrow_mut()
+            .retain(|id| entries.iter().any(|e| &e.id == id));
+        self.update_selection_label();
+
+        let (pinned, others): (Vec<_>, Vec<_>) = entries.into_iter().partition(|e| e.pinned);
+        let pinned_count = pinned.len();
+        let entries: Vec<&HistoryEntry> = pinned.into_iter().chain(others).collect();
+        let end = end.min(entries.len());
+        for (index, entry) in entries.iter().enumerate().take(end).skip(start) {
+            if pinned_count > 0 && index == 0 {
+                self.list_box.append(&section_header("Закріплені"));
+            }
+            if pinned_count > 0 && index == pinned_count {
+                self.list_box.append(&section_header("Інші записи"));
+            }
+            let row = create_history_row(
+                &entry.id,
+                &entry.text,
+                &entry.formatted_timestamp(),
+                &entry.formatted_duration(),
+                &entry.preview(),
+                entry.recording_path.as_deref(),
+                &entry.speakers,
+                entry,
+                self,
+            );
+            self.list_box.append(&row);
+        }
+        self.shown.set(end);
+        if end < entries.len() {
+            self.list_box.append(&self.load_more_row(entries.len() - end));
+        }
+    }
+
+    /// Select every entry passing the filter, shown or not yet.
+    pub fn select_all_matching(&self) {
+        {
+            let history_guard = self.history.lock();
+            let entries = self.filter.borrow().apply(&*history_guard);
+            self.selected
+                .borrow_mut()
+                .extend(entries.into_iter().map(|e| e.id.clone()));
+        }
+        self.populate();
+    }
+
+    pub fn clear_selection(&self) {
+        self.selected.borrow_mut().clear();
+        self.populate();
+    }
+
+    pub fn update_selection_label(&self) {
+        self.selection_label
+            .set_text(&format!("Вибрано: {}", self.selected.borrow().len()));
+    }
+
+    /// Row with a button appending the next page in its place.
+    fn load_more_row(&self, remaining: usize) -> ListBoxRow {
+        let button = Button::with_label(&format!("Показати ще ({})", remaining));
+        button.set_halign(Align::Center);
+        button.set_margin_top(6);
+        button.set_margin_bottom(6);
+        a11y::set_name(&button, &format!("Показати ще записи, лишилося {}", remaining));
+        let row = ListBoxRow::new();
+        row.set_activatable(false);
+        row.set_selectable(false);
+        row.set_child(Some(&button));
+
+        let list = self.clone();
+        let row_weak = row.downgrade();
+        button.connect_clicked(move |_| {
+            if let Some(row) = row_weak.upgrade() {
+                list.list_box.remove(&row);
+            }
+            let start = list.shown.get();
+            list.append_rows(start, start + PAGE_SIZE);
+        });
+        row
+    }
+
+    /// Refresh the tag filter and populate once the current signal handler
+    /// has returned, as that may belong to a row the rebuild destroys.
+    fn populate_later(&self) {
+        let list = self.clone();
+        glib::idle_add_local_once(move || {
+            list.fill_tag_combo();
+            list.populate();
+        });
+    }
+
+    /// Offer every tag of the history in the tag filter, keeping the
+    /// chosen one if it is still used.
+    pub fn fill_tag_combo(&self) {
+        let tags = self.history.lock().all_tags();
+        let current = self.filter.borrow().tag.clone();
+        self.tag_combo.remove_all();
+        self.tag_combo.append(None, "Усі");
+        for tag in &tags {
+            self.tag_combo.append(Some(tag), tag);
+        }
+        match current.and_then(|tag| tags.iter().find(|t| t.to_lowercase() == tag.to_lowercase())) {
+            Some(tag) => {
+                self.tag_combo.set_active_id(Some(tag));
+            }
+            None => self.tag_combo.set_active(Some(0)),
+        }
+        self.tag_combo.set_sensitive(!tags.is_empty());
     }
 }
 
+/// Non-interactive heading row between sections of the list.
+fn section_header(title: &str) -> ListBoxRow {
+    let label = Label::new(Some(title));
+    label.set_halign(Align::Start);
+    label.add_css_class("heading");
+    label.set_margin_top(12);
+    label.set_margin_start(12);
+    label.set_margin_bottom(6);
+    let row = ListBoxRow::new();
+    row.set_activatable(false);
+    row.set_selectable(false);
+    row.set_child(Some(&label));
+    row
+}
+
+#[allow(clippy::too_many_arguments)]
 fn create_history_row(
     id: &str,
     full_text: &str,
     timestamp: &str,
     duration: &str,
     preview: &str,
-    ctx: &HistoryRowContext,
+    recording_path: Option<&str>,
+    speakers: &[String],
+    entry: &HistoryEntry,
+    list: &HistoryList,
 ) -> ListBoxRow {
-    let history = ctx.history.clone();
-    let list_box = ctx.list_box.clone();
-    let search_query = ctx.search_query.clone();
+    let list_box = list.list_box.clone();
+    let selected = list.selected.clone();
     let row = ListBoxRow::new();
     row.set_activatable(false);
+    a11y::set_name(&row, &format!("{}, {}: {}", timestamp, duration, preview));
 
     let content_box = GtkBox::new(Orientation::Vertical, 6);
     content_box.set_margin_top(12);
@@ -86,9 +215,27 @@ fn create_history_row(
     content_box.set_margin_start(12);
     content_box.set_margin_end(12);
 
-    // Top row: timestamp and duration
+    // Top row: selection, timestamp and duration
     let top_row = GtkBox::new(Orientation::Horizontal, 12);
 
+    let select_check = CheckButton::new();
+    select_check.set_active(selected.borrow().contains(id));
+    a11y::set_name(&select_check, &format!("Вибрати запис від {}", timestamp));
+    let id_for_select = id.to_string();
+    let list_for_select = list.clone();
+    select_check.connect_toggled(move |check| {
+        {
+            let mut selected = list_for_select.selected.borrow_mut();
+            if check.is_active() {
+                selected.insert(id_for_select.clone());
+            } else {
+                selected.remove(&id_for_select);
+            }
+        }
+        list_for_select.update_selection_label();
+    });
+    top_row.append(&select_check);
+
     let timestamp_label = Label::new(Some(timestamp));
     timestamp_label.set_hexpand(true);
     timestamp_label.set_halign(Align::Start);
@@ -102,64 +249,355 @@ fn create_history_row(
 
     content_box.append(&top_row);
 
+    // Title and tags from a session template
+    if let Some(title) = &entry.title {
+        let title_label = Label::new(Some(title));
+        title_label.set_halign(Align::Start);
+        title_label.add_css_class("heading");
+        content_box.append(&title_label);
+    }
+    if !entry.tags.is_empty() {
+        let tags_label = Label::new(Some(&format!("Теги: {}", entry.tags.join(", "))));
+        tags_label.set_halign(Align::Start);
+        tags_label.add_css_class("dim-label");
+        content_box.append(&tags_label);
+    }
+
+    // Clipping or a too quiet microphone noticed while recording
+    if let Some(quality) = &entry.audio_quality {
+        let quality_label = Label::new(Some(&format!("⚠ {}", quality.note())));
+        quality_label.set_halign(Align::Start);
+        quality_label.add_css_class("warning");
+        quality_label.set_tooltip_text(Some("Якість розпізнавання могла постраждати через рівень звуку"));
+        content_box.append(&quality_label);
+    }
+
     // Text preview
-    let text_label = Label::new(Some(preview));
+    let preview = if entry.original_text.is_some() {
+        format!("{} (виправлено)", preview)
+    } else {
+        preview.to_string()
+    };
+    let text_label = Label::new(Some(&preview));
     text_label.set_halign(Align::Start);
     text_label.set_wrap(true);
     text_label.set_max_width_chars(60);
     content_box.append(&text_label);
 
+    // Speaker turns for review, shown on demand
+    let review_box = create_review_box(entry, list);
+    if let Some(review_box) = &review_box {
+        content_box.append(review_box);
+    }
+
     // Button row
     let button_box = GtkBox::new(Orientation::Horizontal, 8);
     button_box.set_halign(Align::End);
     button_box.set_margin_top(6);
 
     let copy_button = Button::with_label("Копіювати");
+    a11y::set_name(&copy_button, &format!("Копіювати запис від {}", timestamp));
     let full_text_owned = full_text.to_string();
-    copy_button.connect_clicked(move |_| {
-        if let Some(display) = gtk4::gdk::Display::default() {
-            let clipboard = display.clipboard();
-            clipboard.set_text(&full_text_owned);
-        }
-    });
+    copy_button.connect_clicked(move |_| copy_to_clipboard(&full_text_owned));
 
     let delete_button = Button::with_label("Видалити");
     delete_button.add_css_class("destructive-action");
+    a11y::set_name(&delete_button, &format!("Видалити запис від {}", timestamp));
+    if list.read_only {
+        delete_button.set_sensitive(false);
+        delete_button.set_tooltip_text(Some("Історія лише для читання"));
+    }
+    delete_button.set_tooltip_text(Some("Перемістити запис до кошика"));
     let id_owned = id.to_string();
-    let history_for_delete = history.clone();
     let list_box_for_delete = list_box.clone();
-    let search_query_for_delete = search_query.clone();
+    let list_for_delete = list.clone();
     let row_weak = row.downgrade();
     delete_button.connect_clicked(move |_| {
-        {
-            let mut h = history_for_delete.lock();
-            h.remove(&id_owned);
-            if let Err(e) = h.save() {
-                eprintln!("Помилка збереження історії: {}", e);
-            }
-        }
+        trash::delete(&list_for_delete, std::slice::from_ref(&id_owned));
         // Remove this row from the list
         if let Some(row) = row_weak.upgrade() {
             list_box_for_delete.remove(&row);
         }
-        // Refresh to update placeholder visibility
-        glib::idle_add_local_once({
-            let list_box = list_box_for_delete.clone();
-            let history = history_for_delete.clone();
-            let search_query = search_query_for_delete.clone();
-            // We need to get date filters from somewhere - for now, use None
-            let date_from: Rc<RefCell<Option<DateTime<Utc>>>> = Rc::new(RefCell::new(None));
-            let date_to: Rc<RefCell<Option<DateTime<Utc>>>> = Rc::new(RefCell::new(None));
-            move || {
-                populate_list(&list_box, history, &search_query, &date_from, &date_to);
-            }
-        });
+        // Refresh to update placeholder visibility and the tags on offer
+        list_for_delete.populate_later();
+    });
+
+    let edit_button = Button::with_label("Текст…");
+    edit_button.set_tooltip_text(Some("Переглянути й виправити повний текст"));
+    a11y::set_name(&edit_button, &format!("Редагувати текст запису від {}", timestamp));
+    let entry_for_edit = entry.clone();
+    let list_for_edit = list.clone();
+    edit_button.connect_clicked(move |button| {
+        if let Some(window) = button.root().and_downcast::<gtk4::Window>() {
+            super::editor::show_text_editor(&window, &entry_for_edit, &list_for_edit);
+        }
     });
 
     button_box.append(&copy_button);
+    button_box.append(&edit_button);
+    button_box.append(&create_pin_button(id, timestamp, entry.pinned, list));
+    button_box.append(&create_tags_button(id, timestamp, entry, list));
+    if let Some(review_box) = review_box {
+        let review_button = ToggleButton::with_label("Перевірка");
+        review_button.set_tooltip_text(Some("Показати репліки з сумнівним визначенням мовця"));
+        a11y::set_name(&review_button, &format!("Перевірити мовців запису від {}", timestamp));
+        review_button.connect_toggled(move |button| review_box.set_visible(button.is_active()));
+        button_box.append(&review_button);
+    }
+    if let Some(recording) = recording_path.map(std::path::PathBuf::from).filter(|p| p.exists()) {
+        // Player, built on first listen so the list does not open every recording
+        let player_slot = GtkBox::new(Orientation::Vertical, 0);
+        player_slot.set_visible(false);
+        content_box.append(&player_slot);
+        let media: Rc<RefCell<Option<MediaFile>>> = Rc::new(RefCell::new(None));
+
+        let listen_button = ToggleButton::with_label("Слухати");
+        listen_button.set_tooltip_text(Some("Прослухати запис під час читання тексту"));
+        a11y::set_name(&listen_button, &format!("Прослухати запис від {}", timestamp));
+        let recording_for_player = recording.clone();
+        let entry_for_player = entry.clone();
+        listen_button.connect_toggled(move |button| {
+            if button.is_active() {
+                if player_slot.first_child().is_none() {
+                    let (player, stream) = super::player::create_player(&recording_for_player, &entry_for_player);
+                    player_slot.append(&player);
+                    *media.borrow_mut() = Some(stream);
+                }
+                player_slot.set_visible(true);
+            } else {
+                if let Some(media) = media.borrow().as_ref() {
+                    media.pause();
+                }
+                player_slot.set_visible(false);
+            }
+        });
+        button_box.append(&listen_button);
+
+        let recording_for_retranscribe = recording.clone();
+        let tracks_button = Button::with_label("Мовці → WAV");
+        tracks_button.set_tooltip_text(Some("Зберегти окремий WAV для кожного мовця"));
+        a11y::set_name(
+            &tracks_button,
+            &format!("Експорт аудіо мовців запису від {}", timestamp),
+        );
+        tracks_button.connect_clicked(move |button| {
+            if let Some(window) = button.root().and_downcast::<gtk4::Window>() {
+                super::export::export_speaker_tracks(&window, recording.clone());
+            }
+        });
+        button_box.append(&tracks_button);
+
+        let retranscribe_button = Button::with_label("Розпізнати заново…");
+        retranscribe_button.set_tooltip_text(Some("Розпізнати запис іншою моделлю"));
+        a11y::set_name(
+            &retranscribe_button,
+            &format!("Розпізнати заново запис від {}", timestamp),
+        );
+        if list.read_only {
+            retranscribe_button.set_sensitive(false);
+            retranscribe_button.set_tooltip_text(Some("Історія лише для читання"));
+        }
+        let entry_for_retranscribe = entry.clone();
+        let list_for_retranscribe = list.clone();
+        retranscribe_button.connect_clicked(move |button| {
+            if let Some(window) = button.root().and_downcast::<gtk4::Window>() {
+                super::retranscribe::show_retranscribe_dialog(
+                    &window,
+                    &entry_for_retranscribe,
+                    recording_for_retranscribe.clone(),
+                    &list_for_retranscribe,
+                );
+            }
+        });
+        button_box.append(&retranscribe_button);
+    }
+    if !speakers.is_empty() {
+        let rename_button = Button::with_label("Мовці…");
+        rename_button.set_tooltip_text(Some("Перейменувати мовців у тексті"));
+        a11y::set_name(
+            &rename_button,
+            &format!("Перейменувати мовців запису від {}", timestamp),
+        );
+        let id_owned = id.to_string();
+        let speakers = speakers.to_vec();
+        let list = list.clone();
+        rename_button.connect_clicked(move |button| {
+            let Some(window) = button.root().and_downcast::<gtk4::Window>() else {
+                return;
+            };
+            let id = id_owned.clone();
+            let list = list.clone();
+            crate::dialogs::show_rename_speakers_dialog(&window, speakers.clone(), None, move |renames| {
+                {
+                    let mut h = list.history.lock();
+                    if !h.rename_speakers(&id, &renames) {
+                        return;
+                    }
+                    if let Err(e) = h.save() {
+                        eprintln!("Помилка збереження історії: {}", e);
+                    }
+                }
+                list.populate();
+            });
+        });
+        button_box.append(&rename_button);
+    }
     button_box.append(&delete_button);
     content_box.append(&button_box);
 
     row.set_child(Some(&content_box));
     row
 }
+
+/// Button pinning an entry to the top of the list and out of reach of
+/// the history limits, or unpinning it.
+fn create_pin_button(id: &str, timestamp: &str, pinned: bool, list: &HistoryList) -> Button {
+    let (label, tooltip, name) = if pinned {
+        (
+            "Відкріпити",
+            "Повернути запис до загального списку",
+            "Відкріпити запис від",
+        )
+    } else {
+        (
+            "Закріпити",
+            "Показувати запис угорі й не видаляти його за лімітами історії",
+            "Закріпити запис від",
+        )
+    };
+    let pin_button = Button::with_label(label);
+    pin_button.set_tooltip_text(Some(tooltip));
+    a11y::set_name(&pin_button, &format!("{} {}", name, timestamp));
+    if list.read_only {
+        pin_button.set_sensitive(false);
+        pin_button.set_tooltip_text(Some("Історія лише для читання"));
+    }
+
+    let id = id.to_string();
+    let list = list.clone();
+    pin_button.connect_clicked(move |_| {
+        {
+            let mut h = list.history.lock();
+            if !h.set_pinned(&id, !pinned) {
+                return;
+            }
+            if let Err(e) = h.save() {
+                eprintln!("Помилка збереження історії: {}", e);
+            }
+        }
+        list.populate_later();
+    });
+    pin_button
+}
+
+/// Button editing the tags of an entry in a popover: a comma-separated
+/// list, saved with Enter.
+fn create_tags_button(id: &str, timestamp: &str, entry: &HistoryEntry, list: &HistoryList) -> MenuButton {
+    let tags_entry = Entry::builder()
+        .text(entry.tags.join(", "))
+        .placeholder_text("проєкт, клієнт")
+        .width_chars(30)
+        .build();
+    a11y::set_name(&tags_entry, &format!("Теги запису від {}", timestamp));
+    let hint_label = Label::new(Some("Через кому, Enter — зберегти"));
+    hint_label.set_halign(Align::Start);
+    hint_label.add_css_class("dim-label");
+
+    let popover_box = GtkBox::new(Orientation::Vertical, 6);
+    popover_box.append(&tags_entry);
+    popover_box.append(&hint_label);
+    let popover = Popover::new();
+    popover.set_child(Some(&popover_box));
+
+    let tags_button = MenuButton::builder().label("Теги…").popover(&popover).build();
+    tags_button.set_tooltip_text(Some("Теги для впорядкування записів за проєктами чи клієнтами"));
+    a11y::set_name(&tags_button, &format!("Змінити теги запису від {}", timestamp));
+    if list.read_only {
+        tags_button.set_sensitive(false);
+        tags_button.set_tooltip_text(Some("Історія лише для читання"));
+    }
+
+    let id = id.to_string();
+    let list = list.clone();
+    let popover_weak = popover.downgrade();
+    tags_entry.connect_activate(move |tags_entry| {
+        if let Some(popover) = popover_weak.upgrade() {
+            popover.popdown();
+        }
+        let tags = parse_tags(&tags_entry.text());
+        {
+            let mut h = list.history.lock();
+            if !h.set_tags(&id, &tags) {
+                return;
+            }
+            if let Err(e) = h.save() {
+                eprintln!("Помилка збереження історії: {}", e);
+            }
+        }
+        list.populate_later();
+    });
+    tags_button
+}
+
+/// List of speaker turns with low-confidence assignments highlighted.
+///
+/// Each doubtful turn gets a speaker choice to fix it in place. Returns
+/// `None` (no review needed) if every assignment is confident or the stored
+/// confidence no longer matches the turns of the text.
+fn create_review_box(entry: &HistoryEntry, list: &HistoryList) -> Option<GtkBox> {
+    let turns = entry.speaker_turns();
+    if turns.len() != entry.speaker_confidence.len()
+        || entry.speaker_confidence.iter().all(|&c| c >= LOW_SPEAKER_CONFIDENCE)
+    {
+        return None;
+    }
+
+    let review_box = GtkBox::new(Orientation::Vertical, 4);
+    review_box.set_visible(false);
+    let speaker_names: Vec<&str> = entry.speakers.iter().map(String::as_str).collect();
+
+    for (index, ((speaker, text), &confidence)) in turns.iter().zip(&entry.speaker_confidence).enumerate() {
+        let turn_row = GtkBox::new(Orientation::Horizontal, 8);
+        let turn_label = Label::new(Some(&format!("[{}] {}", speaker, text)));
+        turn_label.set_halign(Align::Start);
+        turn_label.set_hexpand(true);
+        turn_label.set_wrap(true);
+        turn_label.set_xalign(0.0);
+        turn_row.append(&turn_label);
+
+        if confidence >= LOW_SPEAKER_CONFIDENCE {
+            turn_label.add_css_class("dim-label");
+        } else {
+            turn_label.add_css_class("warning");
+            turn_label.set_tooltip_text(Some(&format!("Мовця визначено непевно ({:.0}%)", confidence * 100.0)));
+
+            let speaker_choice = DropDown::from_strings(&speaker_names);
+            if let Some(position) = entry.speakers.iter().position(|s| s == speaker) {
+                speaker_choice.set_selected(position as u32);
+            }
+            speaker_choice.set_valign(Align::Center);
+            a11y::set_name(&speaker_choice, &format!("Мовець репліки {}", index + 1));
+            let id = entry.id.clone();
+            let speakers = entry.speakers.clone();
+            let list = list.clone();
+            speaker_choice.connect_selected_notify(move |choice| {
+                let Some(speaker) = speakers.get(choice.selected() as usize) else {
+                    return;
+                };
+                {
+                    let mut h = list.history.lock();
+                    if !h.reassign_turn(&id, index, speaker) {
+                        return;
+                    }
+                    if let Err(e) = h.save() {
+                        eprintln!("Помилка збереження історії: {}", e);
+                    }
+                }
+                list.populate_later();
+            });
+            turn_row.append(&speaker_choice);
+        }
+        review_box.append(&turn_row);
+    }
+    Some(review_box)
+}
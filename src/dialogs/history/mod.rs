@@ -1,19 +1,91 @@
 //! History browser dialog.
 //!
-//! Provides UI for viewing, searching, filtering, and exporting
-//! transcription history.
+//! Provides UI for viewing, searching, filtering, tagging, correcting,
+//! exporting and importing transcription history, for listening to saved
+//! recordings, for restoring deleted entries from the trash and for usage
+//! statistics.
 
+mod editor;
 mod export;
+mod import;
 mod list;
+mod player;
+mod retranscribe;
+mod stats;
+mod trash;
 
-use crate::domain::types::SharedHistory;
+use crate::app::config::Config;
+use crate::domain::traits::HistoryRepository;
+use crate::domain::types::{HistoryEntry, HistoryOrder, SharedHistory};
+use crate::ui::a11y;
+use crate::ui::field_dictation::FieldDictation;
 use chrono::{DateTime, Local, NaiveDate, TimeZone, Utc};
 use gtk4::prelude::*;
-use gtk4::{Align, Box as GtkBox, Button, Entry, Label, ListBox, Orientation, ScrolledWindow, SelectionMode, Window};
-use std::cell::RefCell;
+use gtk4::{
+    Align, Box as GtkBox, Button, ComboBoxText, Entry, Label, ListBox, Orientation, ScrolledWindow, SelectionMode,
+    Window,
+};
+use parking_lot::Mutex;
+use std::cell::{Cell, RefCell};
+use std::collections::HashSet;
 use std::rc::Rc;
+use std::sync::Arc;
 
-pub fn show_history_dialog(parent: &impl IsA<Window>, history: SharedHistory) {
+/// Separators offered when merging entries: (separator, label).
+const MERGE_SEPARATORS: &[(&str, &str)] = &[
+    ("\n\n", "Порожній рядок"),
+    ("\n", "Новий рядок"),
+    (" ", "Пробіл"),
+    ("\n\n---\n\n", "Лінія (---)"),
+];
+
+/// Search, date and tag filters of the history dialog, with the order of
+/// the entries.
+#[derive(Debug, Clone, Default)]
+struct HistoryFilter {
+    query: String,
+    from: Option<DateTime<Utc>>,
+    to: Option<DateTime<Utc>>,
+    tag: Option<String>,
+    order: HistoryOrder,
+}
+
+impl HistoryFilter {
+    /// Entries passing every filter in the chosen order; with a search
+    /// query, newest first keeps the best matches first.
+    fn apply<'a>(&self, history: &'a dyn HistoryRepository<Entry = HistoryEntry>) -> Vec<&'a HistoryEntry> {
+        let in_range = history.filter_by_date_range(self.from, self.to);
+        let ids: HashSet<&str> = in_range.iter().map(|e| e.id.as_str()).collect();
+        let entries: Vec<&HistoryEntry> = if self.query.trim().is_empty() {
+            history
+                .sorted(self.order)
+                .into_iter()
+                .filter(|e| ids.contains(e.id.as_str()))
+                .collect()
+        } else {
+            let mut found: Vec<&HistoryEntry> = history
+                .search(&self.query)
+                .into_iter()
+                .filter(|e| ids.contains(e.id.as_str()))
+                .collect();
+            if self.order != HistoryOrder::Newest {
+                self.order.sort(&mut found);
+            }
+            found
+        };
+        match &self.tag {
+            Some(tag) => entries.into_iter().filter(|e| e.has_tag(tag)).collect(),
+            None => entries,
+        }
+    }
+}
+
+pub fn show_history_dialog(
+    parent: &impl IsA<Window>,
+    history: SharedHistory,
+    config: Arc<Mutex<Config>>,
+    dictation: &FieldDictation,
+) {
     let dialog = Window::builder()
         .title("Історія диктовок")
         .modal(true)
@@ -33,6 +105,8 @@ pub fn show_history_dialog(parent: &impl IsA<Window>, history: SharedHistory) {
 
     // Search entry
     let search_entry = Entry::builder().placeholder_text("Пошук...").build();
+    a11y::set_name(&search_entry, "Пошук в історії");
+    dictation.attach(&search_entry);
     filter_box.append(&search_entry);
 
     // Date filter row
@@ -46,6 +120,7 @@ pub fn show_history_dialog(parent: &impl IsA<Window>, history: SharedHistory) {
         .placeholder_text("YYYY-MM-DD")
         .tooltip_text("Дата початку (YYYY-MM-DD) або порожньо")
         .build();
+    a11y::set_labelled_by(&date_from_entry, &date_from_label);
     date_filter_row.append(&date_from_entry);
 
     let date_to_label = Label::new(Some("До:"));
@@ -55,6 +130,7 @@ pub fn show_history_dialog(parent: &impl IsA<Window>, history: SharedHistory) {
         .placeholder_text("YYYY-MM-DD")
         .tooltip_text("Дата кінця (YYYY-MM-DD) або порожньо")
         .build();
+    a11y::set_labelled_by(&date_to_entry, &date_to_label);
     date_filter_row.append(&date_to_entry);
 
     date_filter_row.set_hexpand(true);
@@ -62,6 +138,18 @@ pub fn show_history_dialog(parent: &impl IsA<Window>, history: SharedHistory) {
 
     main_box.append(&filter_box);
 
+    // Selection row: count and bulk actions
+    let selection_row = GtkBox::new(Orientation::Horizontal, 8);
+    selection_row.set_margin_bottom(6);
+    selection_row.set_margin_start(12);
+    selection_row.set_margin_end(12);
+    let selection_label = Label::new(Some("Вибрано: 0"));
+    selection_label.set_hexpand(true);
+    selection_label.set_halign(Align::Start);
+    selection_label.add_css_class("dim-label");
+    selection_row.append(&selection_label);
+    main_box.append(&selection_row);
+
     // Scrolled window with list
     let scrolled = ScrolledWindow::builder()
         .vexpand(true)
@@ -71,6 +159,7 @@ pub fn show_history_dialog(parent: &impl IsA<Window>, history: SharedHistory) {
     let list_box = ListBox::new();
     list_box.set_selection_mode(SelectionMode::None);
     list_box.add_css_class("boxed-list");
+    a11y::set_name(&list_box, "Записи історії");
     list_box.set_margin_start(12);
     list_box.set_margin_end(12);
 
@@ -79,71 +168,135 @@ pub fn show_history_dialog(parent: &impl IsA<Window>, history: SharedHistory) {
     placeholder.add_css_class("dim-label");
     list_box.set_placeholder(Some(&placeholder));
 
+    // Tag filter, filled once the list exists
+    let tag_label = Label::new(Some("Тег:"));
+    date_filter_row.append(&tag_label);
+    let tag_combo = ComboBoxText::new();
+    tag_combo.set_tooltip_text(Some("Лише записи з цим тегом"));
+    a11y::set_labelled_by(&tag_combo, &tag_label);
+    date_filter_row.append(&tag_combo);
+
+    let order_label = Label::new(Some("Порядок:"));
+    date_filter_row.append(&order_label);
+    let order_combo = ComboBoxText::new();
+    for order in HistoryOrder::ALL {
+        order_combo.append_text(order.label());
+    }
+    order_combo.set_active(Some(0));
+    order_combo.set_tooltip_text(Some("Порядок записів у списку та в експорті"));
+    a11y::set_labelled_by(&order_combo, &order_label);
+    date_filter_row.append(&order_combo);
+
     // Shared state for filtering
-    let search_query: Rc<RefCell<String>> = Rc::new(RefCell::new(String::new()));
-    let date_from: Rc<RefCell<Option<DateTime<Utc>>>> = Rc::new(RefCell::new(None));
-    let date_to: Rc<RefCell<Option<DateTime<Utc>>>> = Rc::new(RefCell::new(None));
+    let list = list::HistoryList {
+        history: history.clone(),
+        list_box: list_box.clone(),
+        filter: Rc::new(RefCell::new(HistoryFilter::default())),
+        selected: Rc::new(RefCell::new(HashSet::new())),
+        tag_combo: tag_combo.clone(),
+        read_only: history.lock().is_read_only(),
+        config: config.clone(),
+        toast: trash::UndoToast::new(),
+        shown: Rc::new(Cell::new(list::PAGE_SIZE)),
+        selection_label,
+    };
+    list.toast.connect_undo(&list);
+    let filter = list.filter.clone();
+    let selected = list.selected.clone();
 
     // Populate list
-    list::populate_list(&list_box, history.clone(), &search_query, &date_from, &date_to);
+    list.fill_tag_combo();
+    list.populate();
 
     scrolled.set_child(Some(&list_box));
     main_box.append(&scrolled);
+    main_box.append(&list.toast.bar);
 
-    // Search and date filtering
-    let list_box_for_search = list_box.clone();
-    let history_for_search = history.clone();
-    let search_query_for_search = search_query.clone();
-    let date_from_for_search = date_from.clone();
-    let date_to_for_search = date_to.clone();
+    let select_all_button = Button::with_label("Вибрати всі");
+    select_all_button.set_tooltip_text(Some("Вибрати всі записи, що відповідають пошуку й фільтрам"));
+    select_all_button.connect_clicked({
+        let list = list.clone();
+        move |_| list.select_all_matching()
+    });
+    selection_row.append(&select_all_button);
+
+    let clear_selection_button = Button::with_label("Зняти вибір");
+    clear_selection_button.connect_clicked({
+        let list = list.clone();
+        move |_| list.clear_selection()
+    });
+    selection_row.append(&clear_selection_button);
+
+    let delete_selected_button = Button::with_label("Видалити вибрані");
+    delete_selected_button.add_css_class("destructive-action");
+    delete_selected_button.set_tooltip_text(Some("Перемістити вибрані записи до кошика"));
+    if list.read_only {
+        delete_selected_button.set_sensitive(false);
+        delete_selected_button.set_tooltip_text(Some("Історія лише для читання"));
+    }
+    delete_selected_button.connect_clicked({
+        let list = list.clone();
+        move |_| {
+            let ids: Vec<String> = list.selected.borrow_mut().drain().collect();
+            if ids.is_empty() {
+                return;
+            }
+            trash::delete(&list, &ids);
+            list.fill_tag_combo();
+            list.populate();
+        }
+    });
+    selection_row.append(&delete_selected_button);
+
+    // Search, date and tag filtering
     search_entry.connect_changed({
-        let list_box = list_box_for_search.clone();
-        let history = history_for_search.clone();
-        let search_query = search_query_for_search.clone();
-        let date_from = date_from_for_search.clone();
-        let date_to = date_to_for_search.clone();
+        let list = list.clone();
         move |entry| {
-            let query = entry.text().to_string();
-            *search_query.borrow_mut() = query;
-            list::populate_list(&list_box, history.clone(), &search_query, &date_from, &date_to);
+            list.filter.borrow_mut().query = entry.text().to_string();
+            list.populate_first_page();
         }
     });
 
-    // Date filtering - from
-    let list_box_for_from = list_box.clone();
-    let history_for_from = history.clone();
-    let search_query_for_from = search_query.clone();
-    let date_from_for_from = date_from.clone();
-    let date_to_for_from = date_to.clone();
     date_from_entry.connect_changed({
-        let list_box = list_box_for_from.clone();
-        let history = history_for_from.clone();
-        let search_query = search_query_for_from.clone();
-        let date_from = date_from_for_from.clone();
-        let date_to = date_to_for_from.clone();
+        let list = list.clone();
         move |entry| {
-            let text = entry.text().to_string();
-            *date_from.borrow_mut() = parse_date(&text);
-            list::populate_list(&list_box, history.clone(), &search_query, &date_from, &date_to);
+            list.filter.borrow_mut().from = parse_date(&entry.text());
+            list.populate_first_page();
         }
     });
 
-    // Date filtering - to
-    let list_box_for_to = list_box.clone();
-    let history_for_to = history.clone();
-    let search_query_for_to = search_query.clone();
-    let date_from_for_to = date_from.clone();
-    let date_to_for_to = date_to.clone();
     date_to_entry.connect_changed({
-        let list_box = list_box_for_to.clone();
-        let history = history_for_to.clone();
-        let search_query = search_query_for_to.clone();
-        let date_from = date_from_for_to.clone();
-        let date_to = date_to_for_to.clone();
+        let list = list.clone();
         move |entry| {
-            let text = entry.text().to_string();
-            *date_to.borrow_mut() = parse_date(&text);
-            list::populate_list(&list_box, history.clone(), &search_query, &date_from, &date_to);
+            list.filter.borrow_mut().to = parse_date(&entry.text());
+            list.populate_first_page();
+        }
+    });
+
+    tag_combo.connect_changed({
+        let list = list.clone();
+        move |combo| {
+            // Nothing is active only while the choices are refilled
+            if combo.active().is_none() {
+                return;
+            }
+            let tag = combo.active_id().map(|id| id.to_string());
+            if list.filter.borrow().tag != tag {
+                list.filter.borrow_mut().tag = tag;
+                list.populate_first_page();
+            }
+        }
+    });
+
+    order_combo.connect_changed({
+        let list = list.clone();
+        move |combo| {
+            let order = combo
+                .active()
+                .and_then(|i| HistoryOrder::ALL.get(i as usize).copied())
+                .unwrap_or_default();
+            list.filter.borrow_mut().order = order;
+            list.populate_first_page();
         }
     });
 
@@ -155,26 +308,146 @@ pub fn show_history_dialog(parent: &impl IsA<Window>, history: SharedHistory) {
     button_box.set_margin_start(12);
     button_box.set_margin_end(12);
 
+    // Merge selected entries into one
+    let separator_combo = ComboBoxText::new();
+    for (_, label) in MERGE_SEPARATORS {
+        separator_combo.append_text(label);
+    }
+    separator_combo.set_active(Some(0));
+    separator_combo.set_tooltip_text(Some("Роздільник між об'єднаними записами"));
+    a11y::set_name(&separator_combo, "Роздільник для об'єднання");
+    button_box.append(&separator_combo);
+
+    let merge_button = Button::with_label("Об'єднати вибрані");
+    merge_button.set_tooltip_text(Some("Об'єднати вибрані записи в один (за часом)"));
+    if list.read_only {
+        merge_button.set_sensitive(false);
+        merge_button.set_tooltip_text(Some("Історія лише для читання"));
+    }
+    {
+        let list = list.clone();
+        let separator_combo = separator_combo.clone();
+        merge_button.connect_clicked(move |_| {
+            let ids: Vec<String> = list.selected.borrow().iter().cloned().collect();
+            if ids.len() < 2 {
+                return;
+            }
+            let separator = separator_combo
+                .active()
+                .and_then(|i| MERGE_SEPARATORS.get(i as usize))
+                .map(|(sep, _)| *sep)
+                .unwrap_or(MERGE_SEPARATORS[0].0);
+            {
+                let mut h = list.history.lock();
+                let recordings = h
+                    .entries()
+                    .iter()
+                    .filter(|e| ids.contains(&e.id) && e.recording_path.is_some())
+                    .count();
+                if recordings > 1 {
+                    drop(h);
+                    list.toast
+                        .notify("Не можна об'єднати записи, з яких кілька мають аудіо: аудіо буде втрачено");
+                    return;
+                }
+                if h.merge(&ids, separator).is_some() {
+                    if let Err(e) = h.save() {
+                        eprintln!("Помилка збереження історії: {}", e);
+                    }
+                }
+            }
+            list.selected.borrow_mut().clear();
+            list.populate();
+        });
+    }
+    button_box.append(&merge_button);
+
     // Export button
     let export_button = Button::with_label("Експортувати...");
+    export_button.set_tooltip_text(Some(
+        "Експортувати вибрані записи або, якщо нічого не вибрано, всі показані",
+    ));
     let dialog_weak_for_export = dialog.downgrade();
     let history_for_export = history.clone();
-    let search_query_for_export = search_query.clone();
-    let date_from_for_export = date_from.clone();
-    let date_to_for_export = date_to.clone();
+    let selected_for_export = selected.clone();
+    let filter_for_export = filter.clone();
     export_button.connect_clicked(move |_| {
         if let Some(dialog) = dialog_weak_for_export.upgrade() {
             export::export_history(
                 &dialog,
                 history_for_export.clone(),
-                &search_query_for_export,
-                &date_from_for_export,
-                &date_to_for_export,
+                &selected_for_export,
+                &filter_for_export,
             );
         }
     });
     button_box.append(&export_button);
 
+    let import_button = Button::with_label("Імпортувати...");
+    import_button.set_tooltip_text(Some(
+        "Додати записи з файлу історії з іншого комп'ютера чи резервної копії",
+    ));
+    if list.read_only {
+        import_button.set_sensitive(false);
+        import_button.set_tooltip_text(Some("Історія лише для читання"));
+    }
+    {
+        let dialog_weak = dialog.downgrade();
+        let list = list.clone();
+        import_button.connect_clicked(move |_| {
+            if let Some(dialog) = dialog_weak.upgrade() {
+                import::import_history(&dialog, &list);
+            }
+        });
+    }
+    button_box.append(&import_button);
+
+    // Flashcard export: selected entries, or everything shown
+    let anki_button = Button::with_label("Anki...");
+    anki_button.set_tooltip_text(Some("Експортувати вибрані записи як картки Anki"));
+    {
+        let dialog_weak = dialog.downgrade();
+        let history = history.clone();
+        let selected = selected.clone();
+        let filter = filter.clone();
+        anki_button.connect_clicked(move |_| {
+            if let Some(dialog) = dialog_weak.upgrade() {
+                export::export_anki(&dialog, history.clone(), config.clone(), &selected, &filter);
+            }
+        });
+    }
+    button_box.append(&anki_button);
+
+    let stats_button = Button::with_label("Статистика");
+    stats_button.set_tooltip_text(Some("Час диктовок, слова за днями, мови й моделі"));
+    {
+        let dialog_weak = dialog.downgrade();
+        let history = history.clone();
+        stats_button.connect_clicked(move |_| {
+            if let Some(dialog) = dialog_weak.upgrade() {
+                stats::show_stats_dialog(&dialog, &history);
+            }
+        });
+    }
+    button_box.append(&stats_button);
+
+    let trash_button = Button::with_label("Кошик...");
+    trash_button.set_tooltip_text(Some("Відновити видалені записи"));
+    if list.read_only {
+        trash_button.set_sensitive(false);
+        trash_button.set_tooltip_text(Some("Історія лише для читання"));
+    }
+    {
+        let dialog_weak = dialog.downgrade();
+        let list = list.clone();
+        trash_button.connect_clicked(move |_| {
+            if let Some(dialog) = dialog_weak.upgrade() {
+                trash::show_trash_dialog(&dialog, &list);
+            }
+        });
+    }
+    button_box.append(&trash_button);
+
     let close_button = Button::with_label("Закрити");
     let dialog_weak = dialog.downgrade();
     close_button.connect_clicked(move |_| {
@@ -0,0 +1,169 @@
+//! Playback of a history entry's recording.
+//!
+//! Play/pause and the seek bar come from GTK's media controls. If speaker
+//! turns were saved next to the recording, they are listed under the
+//! controls; clicking one plays the recording from where the turn starts.
+
+use crate::domain::text_metrics::truncate_graphemes;
+use crate::domain::types::HistoryEntry;
+use crate::infrastructure::recordings::load_turns;
+use crate::transcription::rttm::SpeakerTurn;
+use crate::ui::a11y;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, Label, MediaControls, MediaFile, Orientation};
+use std::path::Path;
+
+/// Longest turn text shown on a seek button, in characters
+const MAX_TURN_PREVIEW: usize = 60;
+
+/// A point in the recording to play from.
+#[derive(Debug, Clone, PartialEq)]
+struct SeekPoint {
+    /// Seconds from the start of the recording
+    start: f64,
+    label: String,
+}
+
+/// Player for `recording` with the turns of `entry` to seek to.
+///
+/// Returns the player and its media stream, to pause it when hidden.
+pub fn create_player(recording: &Path, entry: &HistoryEntry) -> (GtkBox, MediaFile) {
+    let player = GtkBox::new(Orientation::Vertical, 4);
+    let media = MediaFile::for_filename(recording);
+
+    let controls = MediaControls::new(Some(&media));
+    controls.set_hexpand(true);
+    a11y::set_name(&controls, "Відтворення запису");
+    player.append(&controls);
+
+    let turns = match load_turns(recording) {
+        Ok(turns) => turns.unwrap_or_default(),
+        Err(e) => {
+            eprintln!("Не вдалося прочитати репліки запису: {}", e);
+            Vec::new()
+        }
+    };
+    for point in seek_points(&turns, &entry.speaker_turns()) {
+        let button = Button::with_label(&format!("{}  {}", format_position(point.start), point.label));
+        button.add_css_class("flat");
+        button.set_halign(Align::Start);
+        if let Some(label) = button.child().and_downcast::<Label>() {
+            label.set_wrap(true);
+            label.set_xalign(0.0);
+        }
+        a11y::set_name(
+            &button,
+            &format!("Відтворити з {}: {}", format_position(point.start), point.label),
+        );
+        let media = media.clone();
+        button.connect_clicked(move |_| {
+            if media.is_seekable() {
+                media.seek((point.start * 1_000_000.0) as i64);
+            }
+            media.play();
+        });
+        player.append(&button);
+    }
+
+    (player, media)
+}
+
+/// Where each speaker turn starts, labelled with the speaker and, when the
+/// turns line up with the text, what was said.
+///
+/// Consecutive turns of one speaker are a single turn of the text, so they
+/// are merged first. Speaker names come from the text, which has the names
+/// the user renamed them to; the saved turns keep the original ones.
+fn seek_points(turns: &[SpeakerTurn], text_turns: &[(String, String)]) -> Vec<SeekPoint> {
+    let mut sorted: Vec<&SpeakerTurn> = turns.iter().filter(|t| t.end > t.start).collect();
+    sorted.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut merged: Vec<(f64, &str)> = Vec::new();
+    for turn in sorted {
+        if merged.last().is_none_or(|(_, speaker)| *speaker != turn.speaker) {
+            merged.push((turn.start, &turn.speaker));
+        }
+    }
+
+    let aligned = merged.len() == text_turns.len();
+    merged
+        .into_iter()
+        .enumerate()
+        .map(|(i, (start, speaker))| {
+            let label = if aligned {
+                let (speaker, text) = &text_turns[i];
+                format!("{}: {}", speaker, truncate_graphemes(text, MAX_TURN_PREVIEW))
+            } else {
+                speaker.to_string()
+            };
+            SeekPoint { start, label }
+        })
+        .collect()
+}
+
+/// Position in the recording as `m:ss`.
+fn format_position(secs: f64) -> String {
+    let secs = secs.max(0.0) as u64;
+    format!("{}:{:02}", secs / 60, secs % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(speaker: &str, start: f64, end: f64) -> SpeakerTurn {
+        SpeakerTurn {
+            speaker: speaker.to_string(),
+            start,
+            end,
+        }
+    }
+
+    fn text_turn(speaker: &str, text: &str) -> (String, String) {
+        (speaker.to_string(), text.to_string())
+    }
+
+    #[test]
+    fn test_seek_points_merge_turns_and_take_text() {
+        let turns = [
+            turn("Мовець 2", 4.0, 6.5),
+            turn("Мовець 1", 0.5, 2.0),
+            turn("Мовець 1", 2.2, 3.8),
+        ];
+        let text = [text_turn("Олена", "Доброго ранку"), text_turn("Мовець 2", "Привіт")];
+        let points = seek_points(&turns, &text);
+        assert_eq!(
+            points,
+            vec![
+                SeekPoint {
+                    start: 0.5,
+                    label: "Олена: Доброго ранку".to_string()
+                },
+                SeekPoint {
+                    start: 4.0,
+                    label: "Мовець 2: Привіт".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_seek_points_without_matching_text_show_speakers() {
+        let turns = [
+            turn("Мовець 1", 0.0, 1.0),
+            turn("Мовець 2", 1.0, 2.0),
+            turn("Мовець 1", 2.0, 0.5),
+        ];
+        let points = seek_points(&turns, &[text_turn("Ви", "усе одним шматком")]);
+        let labels: Vec<&str> = points.iter().map(|p| p.label.as_str()).collect();
+        // The empty turn is skipped
+        assert_eq!(labels, ["Мовець 1", "Мовець 2"]);
+    }
+
+    #[test]
+    fn test_format_position() {
+        assert_eq!(format_position(0.0), "0:00");
+        assert_eq!(format_position(65.9), "1:05");
+        assert_eq!(format_position(3600.0), "60:00");
+    }
+}
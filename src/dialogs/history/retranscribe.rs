@@ -0,0 +1,228 @@
+//! Transcribing a history entry's recording again with another model.
+//!
+//! The chosen model is loaded next to the one in use, only for this run.
+//! A dictation recording is transcribed as it is; a conference recording
+//! (microphone and system audio) is split into speakers by channel. The
+//! new text replaces the entry's text, which stays among its versions.
+
+use super::list::HistoryList;
+use crate::app::config::{tdt_models_dir, Config};
+use crate::domain::traits::Transcription;
+use crate::domain::types::{HistoryEntry, TranscriptSegment};
+use crate::infrastructure::models::{
+    get_model_path, is_tdt_model_downloaded, list_downloaded_models, TDT_DISPLAY_NAME,
+};
+use crate::infrastructure::recordings::load_recording;
+use crate::recording::denoise::DenoiseSettings;
+use crate::transcription::postprocess::create_post_processor;
+use crate::transcription::service::ConferenceTranscript;
+use crate::transcription::word_attribution::SpeakerAttribution;
+use crate::transcription::TranscriptionService;
+use crate::ui::a11y::{self, Urgency};
+use crate::ui::shared::maybe_denoise;
+use anyhow::{bail, Result};
+use gtk4::prelude::*;
+use gtk4::{glib, Align, Box as GtkBox, Button, ComboBoxText, Label, Orientation, Spinner, Window};
+use std::path::{Path, PathBuf};
+
+/// ID of the Parakeet TDT choice among the Whisper model files
+const TDT_ID: &str = "tdt";
+
+/// Show a window choosing a model to transcribe `recording` of `entry` again.
+pub fn show_retranscribe_dialog(
+    parent: &impl IsA<Window>,
+    entry: &HistoryEntry,
+    recording: PathBuf,
+    list: &HistoryList,
+) {
+    let dialog = Window::builder()
+        .title("Розпізнати заново")
+        .modal(true)
+        .transient_for(parent)
+        .default_width(420)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+
+    let hint_label = Label::new(Some(
+        "Запис буде розпізнано обраною моделлю. Поточний текст збережеться як попередня версія",
+    ));
+    hint_label.set_halign(Align::Start);
+    hint_label.set_wrap(true);
+    main_box.append(&hint_label);
+
+    let model_row = GtkBox::new(Orientation::Horizontal, 8);
+    let model_label = Label::new(Some("Модель:"));
+    model_row.append(&model_label);
+    let model_combo = ComboBoxText::new();
+    model_combo.set_hexpand(true);
+    a11y::set_labelled_by(&model_combo, &model_label);
+    for model in list_downloaded_models() {
+        model_combo.append(Some(&model.filename), &model.display_name);
+    }
+    if is_tdt_model_downloaded() {
+        model_combo.append(Some(TDT_ID), TDT_DISPLAY_NAME);
+    }
+    let current = list.config.lock().default_model.clone();
+    if !model_combo.set_active_id(Some(&current)) {
+        model_combo.set_active(Some(0));
+    }
+    model_row.append(&model_combo);
+    main_box.append(&model_row);
+
+    let status_box = GtkBox::new(Orientation::Horizontal, 8);
+    let spinner = Spinner::new();
+    spinner.set_visible(false);
+    let status_label = Label::new(None);
+    status_label.set_halign(Align::Start);
+    status_label.set_wrap(true);
+    status_box.append(&spinner);
+    status_box.append(&status_label);
+    main_box.append(&status_box);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 8);
+    button_box.set_halign(Align::End);
+
+    let cancel_button = Button::with_label("Скасувати");
+    let dialog_weak = dialog.downgrade();
+    cancel_button.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+    button_box.append(&cancel_button);
+
+    let start_button = Button::with_label("Розпізнати");
+    start_button.add_css_class("suggested-action");
+    if model_combo.active_id().is_none() {
+        start_button.set_sensitive(false);
+        status_label.set_text("Немає завантажених моделей");
+    }
+    let id = entry.id.clone();
+    let language = entry.language.clone();
+    let list = list.clone();
+    let dialog_weak = dialog.downgrade();
+    start_button.connect_clicked(move |button| {
+        let Some(model_id) = model_combo.active_id().map(|id| id.to_string()) else {
+            return;
+        };
+        let model_name = model_combo.active_text().map(|t| t.to_string()).unwrap_or_default();
+        button.set_sensitive(false);
+        model_combo.set_sensitive(false);
+        spinner.set_visible(true);
+        spinner.start();
+        status_label.set_text("Розпізнавання...");
+
+        let (tx, rx) = async_channel::bounded::<Result<Retranscription>>(1);
+        let recording = recording.clone();
+        let language = language.clone();
+        let config = list.config.lock().clone();
+        std::thread::spawn(move || {
+            let _ = tx.send_blocking(retranscribe(&recording, &language, &model_id, &config));
+        });
+
+        let id = id.clone();
+        let list = list.clone();
+        let dialog_weak = dialog_weak.clone();
+        let button = button.clone();
+        let model_combo = model_combo.clone();
+        let spinner = spinner.clone();
+        let status_label = status_label.clone();
+        glib::spawn_future_local(async move {
+            let Ok(result) = rx.recv().await else {
+                return;
+            };
+            spinner.stop();
+            spinner.set_visible(false);
+            match result {
+                Ok(new) => {
+                    {
+                        let mut h = list.history.lock();
+                        if h.replace_transcription(&id, &new.text, &new.speakers, &new.segments, &model_name) {
+                            if let Err(e) = h.save() {
+                                eprintln!("Помилка збереження історії: {}", e);
+                            }
+                        }
+                    }
+                    list.populate();
+                    if let Some(dialog) = dialog_weak.upgrade() {
+                        dialog.close();
+                    }
+                }
+                Err(e) => {
+                    let message = format!("Помилка: {:#}", e);
+                    status_label.set_text(&message);
+                    a11y::announce(&status_label, &message, Urgency::Polite);
+                    button.set_sensitive(true);
+                    model_combo.set_sensitive(true);
+                }
+            }
+        });
+    });
+    button_box.append(&start_button);
+
+    main_box.append(&button_box);
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Text of a new transcription with its speakers and speaker segments.
+struct Retranscription {
+    text: String,
+    speakers: Vec<String>,
+    segments: Vec<TranscriptSegment>,
+}
+
+/// Load the model chosen by `model_id`: a Whisper model file or [`TDT_ID`].
+fn load_service(model_id: &str, config: &Config) -> Result<TranscriptionService> {
+    let mut service = if model_id == TDT_ID {
+        let mut service = TranscriptionService::with_tdt(&tdt_models_dir().to_string_lossy())?;
+        if let Some(processor) = create_post_processor(&config.punctuation_restoration) {
+            service.add_post_processor(processor);
+        }
+        service
+    } else {
+        TranscriptionService::with_model(&get_model_path(model_id).to_string_lossy())?
+    };
+    service.set_verbatim(config.verbatim_transcription);
+    Ok(service)
+}
+
+/// Transcribe a saved recording. Runs in a worker thread.
+fn retranscribe(recording: &Path, language: &str, model_id: &str, config: &Config) -> Result<Retranscription> {
+    let (mic, loopback) = load_recording(recording)?;
+    let service = load_service(model_id, config)?;
+    let denoise = DenoiseSettings::if_enabled(config);
+    let mic = maybe_denoise(&mic, denoise.as_ref());
+
+    let transcription = if loopback.is_empty() {
+        Retranscription {
+            text: service.transcribe(&mic, language)?.trim().to_string(),
+            speakers: Vec::new(),
+            segments: Vec::new(),
+        }
+    } else {
+        let loopback = maybe_denoise(&loopback, denoise.as_ref());
+        let ConferenceTranscript { text, segments, .. } = service.transcribe_conference(
+            &mic,
+            &loopback,
+            language,
+            "channel",
+            SpeakerAttribution::from_config(&config.speaker_attribution),
+            None,
+        )?;
+        Retranscription {
+            text,
+            speakers: vec!["Ви".to_string(), "Учасник".to_string()],
+            segments,
+        }
+    };
+    if transcription.text.is_empty() {
+        bail!("Не вдалося розпізнати мову");
+    }
+    Ok(transcription)
+}
@@ -0,0 +1,154 @@
+//! Usage statistics of the whole history.
+//!
+//! Totals come first, then the words of the last days with entries as
+//! bars, then how often each language and model was used.
+
+use crate::domain::types::SharedHistory;
+use crate::history::HistoryStats;
+use crate::ui::a11y;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, Grid, Label, Orientation, ProgressBar, ScrolledWindow, Window};
+
+/// Days with entries shown in the words-per-day chart
+const CHART_DAYS: usize = 14;
+
+/// Languages and models listed at most
+const TOP_ITEMS: usize = 5;
+
+/// Show a window with the statistics of `history`.
+pub fn show_stats_dialog(parent: &impl IsA<Window>, history: &SharedHistory) {
+    let stats = HistoryStats::from_entries(history.lock().entries());
+
+    let dialog = Window::builder()
+        .title("Статистика")
+        .modal(true)
+        .transient_for(parent)
+        .default_width(460)
+        .default_height(520)
+        .build();
+
+    let content = GtkBox::new(Orientation::Vertical, 12);
+    content.set_margin_top(12);
+    content.set_margin_bottom(12);
+    content.set_margin_start(12);
+    content.set_margin_end(12);
+
+    if stats.entries == 0 {
+        let empty_label = Label::new(Some("Історія порожня"));
+        empty_label.add_css_class("dim-label");
+        content.append(&empty_label);
+    } else {
+        let average_words = stats
+            .average_words_per_day()
+            .map_or_else(String::new, |average| format!(" ({:.0} на день із записами)", average));
+        let rtf = match stats.average_rtf {
+            Some(rtf) => format!("{:.2} (виміряно для {} записів)", rtf, stats.measured_entries),
+            None => "не виміряно".to_string(),
+        };
+        let rows = vec![
+            ("Записів", stats.entries.to_string()),
+            ("Час диктовок", stats.formatted_total_time()),
+            ("Слів", format!("{}{}", stats.total_words, average_words)),
+            ("Мова", top_item(stats.most_used_language())),
+            ("Модель", top_item(stats.most_used_model())),
+            ("Середній RTF", rtf),
+        ];
+        content.append(&table(&rows));
+
+        let days = stats.recent_days(CHART_DAYS);
+        content.append(&heading(&format!("Слова за днями (останні {})", days.len())));
+        let most = days.iter().map(|(_, words)| *words).max().unwrap_or(0).max(1);
+        let chart = Grid::new();
+        chart.set_row_spacing(4);
+        chart.set_column_spacing(12);
+        for (row, (day, words)) in days.iter().enumerate() {
+            let day_label = Label::new(Some(&day.format("%Y-%m-%d").to_string()));
+            day_label.set_halign(Align::Start);
+            chart.attach(&day_label, 0, row as i32, 1, 1);
+
+            let bar = ProgressBar::new();
+            bar.set_fraction(*words as f64 / most as f64);
+            bar.set_hexpand(true);
+            bar.set_valign(Align::Center);
+            a11y::set_name(&bar, &format!("{}: {} слів", day.format("%Y-%m-%d"), words));
+            chart.attach(&bar, 1, row as i32, 1, 1);
+
+            let words_label = Label::new(Some(&words.to_string()));
+            words_label.set_halign(Align::End);
+            chart.attach(&words_label, 2, row as i32, 1, 1);
+        }
+        content.append(&chart);
+
+        content.append(&heading("Мови"));
+        content.append(&table(&counts(&stats.languages)));
+        if !stats.models.is_empty() {
+            content.append(&heading("Моделі"));
+            content.append(&table(&counts(&stats.models)));
+        }
+    }
+
+    let scrolled = ScrolledWindow::builder()
+        .vexpand(true)
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .child(&content)
+        .build();
+
+    let close_button = Button::with_label("Закрити");
+    close_button.set_halign(Align::End);
+    close_button.set_margin_bottom(12);
+    close_button.set_margin_end(12);
+    let dialog_weak = dialog.downgrade();
+    close_button.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+
+    let main_box = GtkBox::new(Orientation::Vertical, 0);
+    main_box.append(&scrolled);
+    main_box.append(&close_button);
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+fn heading(text: &str) -> Label {
+    let label = Label::new(Some(text));
+    label.set_halign(Align::Start);
+    label.add_css_class("heading");
+    label
+}
+
+/// Two columns: names on the left, values on the right.
+fn table<N: AsRef<str>>(rows: &[(N, String)]) -> Grid {
+    let grid = Grid::new();
+    grid.set_row_spacing(4);
+    grid.set_column_spacing(12);
+    for (row, (name, value)) in rows.iter().enumerate() {
+        let name_label = Label::new(Some(name.as_ref()));
+        name_label.set_halign(Align::Start);
+        name_label.add_css_class("dim-label");
+        grid.attach(&name_label, 0, row as i32, 1, 1);
+
+        let value_label = Label::new(Some(value));
+        value_label.set_halign(Align::Start);
+        value_label.set_selectable(true);
+        grid.attach(&value_label, 1, row as i32, 1, 1);
+    }
+    grid
+}
+
+/// The most used items with their number of entries.
+fn counts(items: &[(String, usize)]) -> Vec<(String, String)> {
+    items
+        .iter()
+        .take(TOP_ITEMS)
+        .map(|(name, count)| (name.clone(), format!("{} записів", count)))
+        .collect()
+}
+
+fn top_item(item: Option<&(String, usize)>) -> String {
+    item.map_or_else(
+        || "—".to_string(),
+        |(name, count)| format!("{} ({} записів)", name, count),
+    )
+}
@@ -0,0 +1,278 @@
+//! Trash of the history dialog.
+//!
+//! Deleted entries go to the trash: an "Undo" bar shows right after a
+//! deletion, and the trash window restores older deletions or empties it.
+//! Entries are dropped for good after `history_trash_days`.
+
+use super::list::HistoryList;
+use crate::domain::types::HistoryEntry;
+use crate::ui::a11y::{self, Urgency};
+use gtk4::prelude::*;
+use gtk4::{glib, Align, Box as GtkBox, Button, Label, ListBox, Orientation, ScrolledWindow, SelectionMode, Window};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+
+/// How long the bar stays after a deletion or a message
+const TOAST_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// Bar offering to undo the last deletion, also used for short messages;
+/// hidden until there is something to show.
+#[derive(Clone)]
+pub struct UndoToast {
+    pub bar: GtkBox,
+    label: Label,
+    undo_button: Button,
+    /// IDs of the entries the bar would restore
+    pending: Rc<RefCell<Vec<String>>>,
+    /// Bumped whenever the bar is shown, so only the latest timeout hides it
+    shown: Rc<Cell<u32>>,
+}
+
+impl UndoToast {
+    pub fn new() -> Self {
+        let bar = GtkBox::new(Orientation::Horizontal, 12);
+        bar.set_margin_top(6);
+        bar.set_margin_start(12);
+        bar.set_margin_end(12);
+        bar.set_visible(false);
+
+        let label = Label::new(None);
+        label.set_hexpand(true);
+        label.set_halign(Align::Start);
+        bar.append(&label);
+
+        let undo_button = Button::with_label("Скасувати");
+        undo_button.set_tooltip_text(Some("Повернути видалений запис"));
+        bar.append(&undo_button);
+
+        Self {
+            bar,
+            label,
+            undo_button,
+            pending: Rc::new(RefCell::new(Vec::new())),
+            shown: Rc::new(Cell::new(0)),
+        }
+    }
+
+    /// Restore the pending entries into `list` when "Undo" is clicked.
+    pub fn connect_undo(&self, list: &HistoryList) {
+        let toast = self.clone();
+        let list = list.clone();
+        self.undo_button.connect_clicked(move |_| {
+            toast.bar.set_visible(false);
+            let ids = std::mem::take(&mut *toast.pending.borrow_mut());
+            restore(&list, &ids);
+        });
+    }
+
+    /// Offer to undo the deletion of the entries with the given IDs for a few seconds.
+    pub fn show(&self, ids: Vec<String>) {
+        let message = match ids.len() {
+            1 => "Запис переміщено до кошика".to_string(),
+            count => format!("Записів переміщено до кошика: {}", count),
+        };
+        *self.pending.borrow_mut() = ids;
+        self.undo_button.set_visible(true);
+        self.reveal(&message);
+    }
+
+    /// Show a message in the bar for a few seconds, with nothing to undo.
+    pub fn notify(&self, message: &str) {
+        self.pending.borrow_mut().clear();
+        self.undo_button.set_visible(false);
+        self.reveal(message);
+    }
+
+    fn reveal(&self, message: &str) {
+        let shown = self.shown.get().wrapping_add(1);
+        self.shown.set(shown);
+        self.label.set_text(message);
+        self.bar.set_visible(true);
+        a11y::announce(&self.label, message, Urgency::Polite);
+
+        let toast = self.clone();
+        glib::timeout_add_local_once(TOAST_TIMEOUT, move || {
+            if toast.shown.get() == shown {
+                toast.bar.set_visible(false);
+                toast.pending.borrow_mut().clear();
+            }
+        });
+    }
+}
+
+/// Move the entries to the trash and offer to undo it.
+pub fn delete(list: &HistoryList, ids: &[String]) {
+    let trashed: Vec<String> = {
+        let mut h = list.history.lock();
+        let trashed: Vec<String> = ids.iter().filter(|id| h.trash(id)).cloned().collect();
+        if trashed.is_empty() {
+            return;
+        }
+        if let Err(e) = h.save() {
+            eprintln!("Помилка збереження історії: {}", e);
+        }
+        trashed
+    };
+    list.toast.show(trashed);
+}
+
+/// Put trashed entries back and refresh the list.
+fn restore(list: &HistoryList, ids: &[String]) -> bool {
+    {
+        let mut h = list.history.lock();
+        let restored = ids.iter().filter(|id| h.restore(id)).count();
+        if restored == 0 {
+            return false;
+        }
+        if let Err(e) = h.save() {
+            eprintln!("Помилка збереження історії: {}", e);
+        }
+    }
+    list.fill_tag_combo();
+    list.populate();
+    true
+}
+
+/// Show a window with the trashed entries, each with a restore button.
+pub fn show_trash_dialog(parent: &impl IsA<Window>, list: &HistoryList) {
+    let dialog = Window::builder()
+        .title("Кошик")
+        .modal(true)
+        .transient_for(parent)
+        .default_width(500)
+        .default_height(420)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 6);
+    let days = list.config.lock().history_trash_days;
+    let hint_label = Label::new(Some(&format!("Записи видаляються остаточно через {} днів", days)));
+    hint_label.set_halign(Align::Start);
+    hint_label.add_css_class("dim-label");
+    hint_label.set_margin_top(12);
+    hint_label.set_margin_start(12);
+    main_box.append(&hint_label);
+
+    let trash_box = ListBox::new();
+    trash_box.set_selection_mode(SelectionMode::None);
+    trash_box.add_css_class("boxed-list");
+    a11y::set_name(&trash_box, "Записи в кошику");
+    trash_box.set_margin_start(12);
+    trash_box.set_margin_end(12);
+    let placeholder = Label::new(Some("Кошик порожній"));
+    placeholder.add_css_class("dim-label");
+    trash_box.set_placeholder(Some(&placeholder));
+    fill_trash(&trash_box, list);
+
+    let scrolled = ScrolledWindow::builder()
+        .vexpand(true)
+        .hscrollbar_policy(gtk4::PolicyType::Never)
+        .child(&trash_box)
+        .build();
+    main_box.append(&scrolled);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 12);
+    button_box.set_halign(Align::End);
+    button_box.set_margin_top(6);
+    button_box.set_margin_bottom(12);
+    button_box.set_margin_end(12);
+
+    let empty_button = Button::with_label("Очистити кошик");
+    empty_button.add_css_class("destructive-action");
+    empty_button.set_tooltip_text(Some("Остаточно видалити всі записи з кошика"));
+    {
+        let list = list.clone();
+        let trash_box = trash_box.clone();
+        empty_button.connect_clicked(move |_| {
+            {
+                let mut h = list.history.lock();
+                if h.purge_trash(0) == 0 {
+                    return;
+                }
+                if let Err(e) = h.save() {
+                    eprintln!("Помилка збереження історії: {}", e);
+                }
+            }
+            fill_trash(&trash_box, &list);
+        });
+    }
+    button_box.append(&empty_button);
+
+    let close_button = Button::with_label("Закрити");
+    let dialog_weak = dialog.downgrade();
+    close_button.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+    button_box.append(&close_button);
+
+    main_box.append(&button_box);
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Rebuild the rows of the trash window.
+fn fill_trash(trash_box: &ListBox, list: &HistoryList) {
+    while let Some(child) = trash_box.first_child() {
+        trash_box.remove(&child);
+    }
+    let entries: Vec<HistoryEntry> = list.history.lock().trashed().to_vec();
+    for entry in &entries {
+        trash_box.append(&trash_row(entry, trash_box, list));
+    }
+}
+
+fn trash_row(entry: &HistoryEntry, trash_box: &ListBox, list: &HistoryList) -> gtk4::ListBoxRow {
+    let row = gtk4::ListBoxRow::new();
+    row.set_activatable(false);
+    let timestamp = entry.formatted_timestamp();
+    a11y::set_name(&row, &format!("{}: {}", timestamp, entry.preview()));
+
+    let content_box = GtkBox::new(Orientation::Horizontal, 12);
+    content_box.set_margin_top(8);
+    content_box.set_margin_bottom(8);
+    content_box.set_margin_start(12);
+    content_box.set_margin_end(12);
+
+    let text_box = GtkBox::new(Orientation::Vertical, 4);
+    text_box.set_hexpand(true);
+    let deleted = entry
+        .deleted_at
+        .map(|deleted| {
+            deleted
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string()
+        })
+        .unwrap_or_default();
+    let time_label = Label::new(Some(&format!("{} · видалено {}", timestamp, deleted)));
+    time_label.set_halign(Align::Start);
+    time_label.add_css_class("dim-label");
+    text_box.append(&time_label);
+    let preview_label = Label::new(Some(&entry.preview()));
+    preview_label.set_halign(Align::Start);
+    preview_label.set_wrap(true);
+    preview_label.set_xalign(0.0);
+    text_box.append(&preview_label);
+    content_box.append(&text_box);
+
+    let restore_button = Button::with_label("Відновити");
+    restore_button.set_valign(Align::Center);
+    a11y::set_name(&restore_button, &format!("Відновити запис від {}", timestamp));
+    let id = entry.id.clone();
+    let trash_box = trash_box.clone();
+    let list = list.clone();
+    restore_button.connect_clicked(move |_| {
+        if restore(&list, std::slice::from_ref(&id)) {
+            let trash_box = trash_box.clone();
+            let list = list.clone();
+            // Rebuild once this row's handler has returned
+            glib::idle_add_local_once(move || fill_trash(&trash_box, &list));
+        }
+    });
+    content_box.append(&restore_button);
+
+    row.set_child(Some(&content_box));
+    row
+}
@@ -4,11 +4,27 @@
 //! - History browser and export
 //! - Whisper model download and management
 //! - Application settings
+//! - Searchable help with troubleshooting topics
+//! - First-run onboarding wizard
+//! - Speaker renaming
+//! - Phone remote pairing
+//! - VAD calibration
+//! - Subtitle correction
 
+pub mod help;
 pub mod history;
 pub mod model;
+pub mod onboarding;
+pub mod remote;
 pub mod settings;
+pub mod speakers;
+pub mod subtitles;
+pub mod vad_calibration;
 
+pub use help::show_help_dialog;
 pub use history::show_history_dialog;
 pub use model::show_model_dialog;
+pub use onboarding::show_onboarding_dialog;
 pub use settings::show_settings_dialog;
+pub use speakers::show_rename_speakers_dialog;
+pub use subtitles::show_subtitle_dialog;
@@ -0,0 +1,226 @@
+//! Adding custom Whisper models from a URL or a local file.
+
+use super::{list, ModelRowContext};
+use crate::infrastructure::models::{download_custom_model, format_size, import_local_model, ModelInfo};
+use gtk4::prelude::*;
+use gtk4::{
+    glib, Align, Box as GtkBox, Button, Entry, FileChooserNative, Label, ListBox, Orientation, ProgressBar, Window,
+};
+use std::rc::Rc;
+
+enum AddProgress {
+    Progress(u64, u64),
+    Validating,
+    Done(ModelInfo),
+    Error(String),
+}
+
+/// Build the "add custom model" card. Successfully added models are appended to `list_box`.
+pub fn create_custom_model_section(parent: &Window, list_box: &ListBox, ctx: Rc<ModelRowContext>) -> GtkBox {
+    let content_box = GtkBox::new(Orientation::Vertical, 6);
+    content_box.add_css_class("card");
+    content_box.set_margin_top(6);
+    content_box.set_margin_bottom(6);
+
+    let desc_label = Label::new(Some(
+        "Модель GGML/GGUF за посиланням або з локального файлу. Перед додаванням перевіряється, що whisper.cpp може її завантажити.",
+    ));
+    desc_label.set_halign(Align::Start);
+    desc_label.set_wrap(true);
+    desc_label.add_css_class("dim-label");
+    content_box.append(&desc_label);
+
+    let url_row = GtkBox::new(Orientation::Horizontal, 8);
+    let url_entry = Entry::new();
+    url_entry.set_placeholder_text(Some("https://.../ggml-model.bin"));
+    url_entry.set_hexpand(true);
+    url_row.append(&url_entry);
+
+    let download_button = Button::with_label("Завантажити");
+    url_row.append(&download_button);
+    content_box.append(&url_row);
+
+    let progress_bar = ProgressBar::new();
+    progress_bar.set_visible(false);
+    progress_bar.set_show_text(true);
+    content_box.append(&progress_bar);
+
+    let bottom_row = GtkBox::new(Orientation::Horizontal, 8);
+    let status_label = Label::new(None);
+    status_label.set_hexpand(true);
+    status_label.set_halign(Align::Start);
+    status_label.set_wrap(true);
+    status_label.add_css_class("dim-label");
+    bottom_row.append(&status_label);
+
+    let import_button = Button::with_label("Імпортувати файл...");
+    bottom_row.append(&import_button);
+    content_box.append(&bottom_row);
+
+    let widgets = AddWidgets {
+        download_button: download_button.clone(),
+        import_button: import_button.clone(),
+        progress_bar,
+        status_label,
+        list_box: list_box.clone(),
+        ctx,
+    };
+    let widgets = Rc::new(widgets);
+
+    let widgets_for_download = widgets.clone();
+    let url_entry_clone = url_entry.clone();
+    download_button.connect_clicked(move |_| {
+        let url = url_entry_clone.text().trim().to_string();
+        if url.is_empty() {
+            widgets_for_download
+                .status_label
+                .set_text("Вкажіть посилання на модель");
+            return;
+        }
+        widgets_for_download.start("Завантаження...");
+
+        let (tx, rx) = async_channel::bounded::<AddProgress>(100);
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            let tx_clone = tx.clone();
+            let result = rt.block_on(download_custom_model(&url, None, move |downloaded, total| {
+                let progress = if total > 0 && downloaded >= total {
+                    AddProgress::Validating
+                } else {
+                    AddProgress::Progress(downloaded, total)
+                };
+                let _ = tx_clone.send_blocking(progress);
+            }));
+            let _ = tx.send_blocking(match result {
+                Ok(info) => AddProgress::Done(info),
+                Err(e) => AddProgress::Error(format!("{:#}", e)),
+            });
+        });
+
+        let widgets = widgets_for_download.clone();
+        let url_entry = url_entry_clone.clone();
+        glib::spawn_future_local(async move {
+            while let Ok(progress) = rx.recv().await {
+                if widgets.handle(progress) {
+                    url_entry.set_text("");
+                    break;
+                }
+            }
+        });
+    });
+
+    let parent = parent.clone();
+    import_button.connect_clicked(move |_| {
+        let chooser = FileChooserNative::builder()
+            .title("Імпортувати модель")
+            .action(gtk4::FileChooserAction::Open)
+            .modal(true)
+            .transient_for(&parent)
+            .build();
+
+        let filter = gtk4::FileFilter::new();
+        filter.set_name(Some("Моделі GGML/GGUF"));
+        filter.add_pattern("*.bin");
+        filter.add_pattern("*.gguf");
+        chooser.add_filter(&filter);
+
+        let widgets = widgets.clone();
+        chooser.connect_response(move |chooser, response| {
+            if response != gtk4::ResponseType::Accept {
+                return;
+            }
+            let Some(path) = chooser.file().and_then(|f| f.path()) else {
+                return;
+            };
+            widgets.start("Перевірка моделі...");
+
+            let (tx, rx) = async_channel::bounded::<AddProgress>(1);
+            std::thread::spawn(move || {
+                let _ = tx.send_blocking(match import_local_model(&path, None) {
+                    Ok(info) => AddProgress::Done(info),
+                    Err(e) => AddProgress::Error(format!("{:#}", e)),
+                });
+            });
+
+            let widgets = widgets.clone();
+            glib::spawn_future_local(async move {
+                while let Ok(progress) = rx.recv().await {
+                    if widgets.handle(progress) {
+                        break;
+                    }
+                }
+            });
+        });
+        chooser.show();
+    });
+
+    content_box
+}
+
+struct AddWidgets {
+    download_button: Button,
+    import_button: Button,
+    progress_bar: ProgressBar,
+    status_label: Label,
+    list_box: ListBox,
+    ctx: Rc<ModelRowContext>,
+}
+
+impl AddWidgets {
+    fn start(&self, status: &str) {
+        self.download_button.set_sensitive(false);
+        self.import_button.set_sensitive(false);
+        self.progress_bar.set_visible(true);
+        self.progress_bar.set_fraction(0.0);
+        self.progress_bar.set_text(Some("Починаємо..."));
+        self.status_label.set_text(status);
+    }
+
+    fn finish(&self, status: &str) {
+        self.download_button.set_sensitive(true);
+        self.import_button.set_sensitive(true);
+        self.progress_bar.set_visible(false);
+        self.status_label.set_text(status);
+    }
+
+    /// Apply a progress update. Returns true when the operation finished successfully.
+    fn handle(&self, progress: AddProgress) -> bool {
+        match progress {
+            AddProgress::Progress(downloaded, total) => {
+                if total > 0 {
+                    let fraction = downloaded as f64 / total as f64;
+                    self.progress_bar.set_fraction(fraction);
+                    self.progress_bar.set_text(Some(&format!(
+                        "{} / {} ({:.0}%)",
+                        format_size(downloaded),
+                        format_size(total),
+                        fraction * 100.0
+                    )));
+                }
+                false
+            }
+            AddProgress::Validating => {
+                self.progress_bar.pulse();
+                self.progress_bar.set_text(Some("Перевірка моделі..."));
+                false
+            }
+            AddProgress::Done(info) => {
+                self.finish(&format!("Додано: {}", info.display_name));
+                let row = list::create_model_row(
+                    &info.filename,
+                    &info.display_name,
+                    info.size_bytes,
+                    &info.description,
+                    true,
+                    &self.ctx,
+                );
+                self.list_box.append(&row);
+                true
+            }
+            AddProgress::Error(e) => {
+                self.finish(&format!("Помилка: {}", e));
+                false
+            }
+        }
+    }
+}
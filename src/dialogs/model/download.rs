@@ -1,18 +1,47 @@
-//! Sortformer diarization and TDT STT model download and management.
+//! Sortformer diarization, Silero VAD and TDT STT model download and management.
 
 use super::DownloadProgress;
 use crate::infrastructure::models::{
-    delete_sortformer_model, delete_tdt_model, download_sortformer_model, download_tdt_model, format_size,
-    get_sortformer_model_info, get_tdt_model_info, get_tdt_total_size, is_sortformer_model_downloaded,
-    is_tdt_model_downloaded,
+    delete_silero_model, delete_sortformer_model, delete_tdt_model, download_silero_model, download_sortformer_model,
+    download_tdt_model, format_size, get_silero_model_info, get_sortformer_model_info, get_tdt_model_info,
+    get_tdt_total_size, is_silero_model_downloaded, is_sortformer_model_downloaded, is_tdt_model_downloaded, ModelInfo,
 };
+use futures_util::future::{FutureExt, LocalBoxFuture};
 use gtk4::prelude::*;
 use gtk4::{glib, Align, Box as GtkBox, Button, Label, Orientation, ProgressBar};
 
+/// Progress callback handed to a model download.
+type ProgressCallback = Box<dyn Fn(u64, u64) + Send + Sync>;
+
+/// Starts a model download on the download thread's runtime.
+type DownloadFn = fn(ProgressCallback) -> LocalBoxFuture<'static, anyhow::Result<()>>;
+
 pub fn create_sortformer_row() -> GtkBox {
-    let model_info = get_sortformer_model_info();
-    let is_downloaded = is_sortformer_model_downloaded();
+    create_single_model_row(
+        get_sortformer_model_info(),
+        is_sortformer_model_downloaded(),
+        |progress| download_sortformer_model(progress).boxed_local(),
+        delete_sortformer_model,
+    )
+}
 
+/// Row for the Silero VAD model; without it the copy built into the app is used.
+pub fn create_silero_row() -> GtkBox {
+    create_single_model_row(
+        get_silero_model_info(),
+        is_silero_model_downloaded(),
+        |progress| download_silero_model(progress).boxed_local(),
+        delete_silero_model,
+    )
+}
+
+/// Download/delete row for a model stored as one file.
+fn create_single_model_row(
+    model_info: ModelInfo,
+    is_downloaded: bool,
+    download: DownloadFn,
+    delete: fn() -> anyhow::Result<()>,
+) -> GtkBox {
     let content_box = GtkBox::new(Orientation::Vertical, 6);
     content_box.add_css_class("card");
     content_box.set_margin_top(6);
@@ -80,9 +109,9 @@ pub fn create_sortformer_row() -> GtkBox {
         std::thread::spawn(move || {
             let rt = tokio::runtime::Runtime::new().unwrap();
             let tx_clone = tx.clone();
-            let result = rt.block_on(download_sortformer_model(move |downloaded, total| {
+            let result = rt.block_on(download(Box::new(move |downloaded, total| {
                 let _ = tx_clone.send_blocking(DownloadProgress::Progress(downloaded, total));
-            }));
+            })));
 
             match result {
                 Ok(()) => {
@@ -129,10 +158,11 @@ pub fn create_sortformer_row() -> GtkBox {
     // Delete button handler
     let download_button_clone = download_button.clone();
     let status_label_clone = status_label.clone();
+    let display_name = model_info.display_name.clone();
 
     delete_button.connect_clicked(move |btn| {
-        if let Err(e) = delete_sortformer_model() {
-            eprintln!("Помилка видалення Sortformer: {}", e);
+        if let Err(e) = delete() {
+            eprintln!("Помилка видалення {}: {}", display_name, e);
             return;
         }
 
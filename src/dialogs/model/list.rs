@@ -2,15 +2,51 @@
 
 use super::{DownloadProgress, DownloadState, ModelRowContext, RowWidgets};
 use crate::app::config::save_config;
-use crate::infrastructure::models::{delete_model, download_model, format_size, get_model_path, is_model_downloaded};
+use crate::infrastructure::models::{
+    delete_model, download_model, format_size, get_model_path, is_model_downloaded, remove_custom_model, ModelFamily,
+};
 use gtk4::prelude::*;
 use gtk4::{glib, Align, Box as GtkBox, Button, Label, ListBoxRow, Orientation, ProgressBar};
 
+/// Non-interactive header row introducing a model family.
+pub fn create_family_header(family: ModelFamily, too_heavy: bool) -> ListBoxRow {
+    create_section_header(family.display_name(), too_heavy.then_some("(може бракувати пам'яті)"))
+}
+
+/// Non-interactive header row with a title and an optional dimmed note.
+pub fn create_section_header(title: &str, note: Option<&str>) -> ListBoxRow {
+    let row = ListBoxRow::new();
+    row.set_activatable(false);
+    row.set_selectable(false);
+
+    let header_box = GtkBox::new(Orientation::Horizontal, 8);
+    header_box.set_margin_top(8);
+    header_box.set_margin_bottom(4);
+    header_box.set_margin_start(12);
+    header_box.set_margin_end(12);
+
+    let title = Label::new(Some(title));
+    title.add_css_class("heading");
+    title.set_halign(Align::Start);
+    header_box.append(&title);
+
+    if let Some(note) = note {
+        let note = Label::new(Some(note));
+        note.add_css_class("dim-label");
+        header_box.append(&note);
+    }
+
+    row.set_child(Some(&header_box));
+    row
+}
+
+/// Row of one model. A `custom` model also gets a button removing it from the list.
 pub fn create_model_row(
     filename: &str,
     display_name: &str,
     size_bytes: u64,
     description: &str,
+    custom: bool,
     ctx: &ModelRowContext,
 ) -> ListBoxRow {
     let config = ctx.config.clone();
@@ -256,8 +292,38 @@ pub fn create_model_row(
     button_box.append(&set_default_button);
     button_box.append(&download_button);
     button_box.append(&delete_button);
+    if custom {
+        button_box.append(&remove_button(filename, &row, ctx));
+    }
     content_box.append(&button_box);
 
     row.set_child(Some(&content_box));
     row
 }
+
+/// "Remove from list" button of a custom model row.
+fn remove_button(filename: &str, row: &ListBoxRow, ctx: &ModelRowContext) -> Button {
+    let button = Button::with_label("Прибрати");
+    button.set_tooltip_text(Some("Видалити модель і прибрати її зі списку"));
+    let filename = filename.to_string();
+    let config = ctx.config.clone();
+    let row_widgets = ctx.row_widgets.clone();
+    let row = row.downgrade();
+    button.connect_clicked(move |_| {
+        if config.lock().default_model == filename {
+            eprintln!("Не можна прибрати модель за замовчуванням");
+            return;
+        }
+        if let Err(e) = remove_custom_model(&filename) {
+            eprintln!("Помилка видалення: {}", e);
+            return;
+        }
+        row_widgets.borrow_mut().remove(&filename);
+        if let Some(row) = row.upgrade() {
+            if let Some(list_box) = row.parent().and_downcast::<gtk4::ListBox>() {
+                list_box.remove(&row);
+            }
+        }
+    });
+    button
+}
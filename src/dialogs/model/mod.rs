@@ -1,14 +1,18 @@
 //! Model management dialog.
 //!
 //! Provides UI for downloading, managing, and selecting Whisper speech
-//! recognition models and Sortformer diarization models.
+//! recognition models, Sortformer diarization models and the Silero VAD model.
 
+mod custom;
 mod download;
 mod list;
 
 use crate::app::config::Config;
 use crate::domain::traits::Transcription;
-use crate::infrastructure::models::get_available_models;
+use crate::infrastructure::failures;
+use crate::infrastructure::hardware::{CpuFeatures, HardwareInfo};
+use crate::infrastructure::models::{get_available_models, group_models_by_family, load_custom_models};
+use crate::transcription::whisper::build_cpu_features;
 use gtk4::prelude::*;
 use gtk4::{
     Align, Box as GtkBox, Button, Label, ListBox, Orientation, ScrolledWindow, SelectionMode, Separator, Window,
@@ -41,7 +45,7 @@ enum DownloadState {
     Downloading,
 }
 
-enum DownloadProgress {
+pub(super) enum DownloadProgress {
     Progress(u64, u64),
     Done,
     Error(String),
@@ -86,20 +90,82 @@ pub fn show_model_dialog(
         row_widgets: row_widgets.clone(),
     };
 
-    for model in get_available_models() {
-        let row = list::create_model_row(
-            &model.filename,
-            &model.display_name,
-            model.size_bytes,
-            &model.description,
-            &model_ctx,
-        );
-        list_box.append(&row);
+    let hardware = HardwareInfo::detect();
+    let recommended = hardware.recommended_model();
+
+    let hardware_label = Label::new(Some(&format!(
+        "Система: {}. Рекомендовано: {}",
+        hardware.summary(),
+        get_available_models()
+            .iter()
+            .find(|m| m.filename == recommended)
+            .map(|m| m.display_name.clone())
+            .unwrap_or_else(|| recommended.to_string())
+    )));
+    hardware_label.add_css_class("dim-label");
+    hardware_label.set_halign(Align::Start);
+    hardware_label.set_wrap(true);
+    hardware_label.set_margin_top(12);
+    hardware_label.set_margin_start(12);
+    hardware_label.set_margin_end(12);
+    main_box.append(&hardware_label);
+    main_box.append(&create_cpu_features_label());
+    main_box.append(&create_failures_label());
+
+    for (family, models) in group_models_by_family(get_available_models()) {
+        list_box.append(&list::create_family_header(family, hardware.is_too_heavy(family)));
+        for model in models {
+            let display_name = if model.filename == recommended {
+                format!("★ {}", model.display_name)
+            } else {
+                model.display_name.clone()
+            };
+            let row = list::create_model_row(
+                &model.filename,
+                &display_name,
+                model.size_bytes,
+                &model.description,
+                false,
+                &model_ctx,
+            );
+            list_box.append(&row);
+        }
+    }
+
+    let custom_models = load_custom_models();
+    if !custom_models.is_empty() {
+        list_box.append(&list::create_section_header("Користувацькі моделі", None));
+        for model in custom_models {
+            let info = model.to_model_info();
+            let row = list::create_model_row(
+                &info.filename,
+                &info.display_name,
+                info.size_bytes,
+                &info.description,
+                true,
+                &model_ctx,
+            );
+            list_box.append(&row);
+        }
     }
 
     scrolled.set_child(Some(&list_box));
     main_box.append(&scrolled);
 
+    // Custom model section
+    let custom_header = Label::new(Some("Додати модель"));
+    custom_header.add_css_class("heading");
+    custom_header.set_halign(Align::Start);
+    custom_header.set_margin_start(12);
+    custom_header.set_margin_bottom(6);
+    main_box.append(&custom_header);
+
+    let custom_section = custom::create_custom_model_section(dialog.upcast_ref(), &list_box, Rc::new(model_ctx));
+    custom_section.set_margin_start(12);
+    custom_section.set_margin_end(12);
+    custom_section.set_margin_bottom(12);
+    main_box.append(&custom_section);
+
     // Add separator and Sortformer section
     let separator = Separator::new(Orientation::Horizontal);
     separator.set_margin_top(12);
@@ -122,6 +188,27 @@ pub fn show_model_dialog(
     sortformer_row.set_margin_bottom(12);
     main_box.append(&sortformer_row);
 
+    // Silero VAD section
+    let vad_separator = Separator::new(Orientation::Horizontal);
+    vad_separator.set_margin_top(6);
+    vad_separator.set_margin_bottom(6);
+    vad_separator.set_margin_start(12);
+    vad_separator.set_margin_end(12);
+    main_box.append(&vad_separator);
+
+    let vad_header = Label::new(Some("Детектор мовлення (VAD)"));
+    vad_header.add_css_class("heading");
+    vad_header.set_halign(Align::Start);
+    vad_header.set_margin_start(12);
+    vad_header.set_margin_bottom(6);
+    main_box.append(&vad_header);
+
+    let silero_row = download::create_silero_row();
+    silero_row.set_margin_start(12);
+    silero_row.set_margin_end(12);
+    silero_row.set_margin_bottom(12);
+    main_box.append(&silero_row);
+
     // TDT section separator
     let tdt_separator = Separator::new(Orientation::Horizontal);
     tdt_separator.set_margin_top(6);
@@ -165,3 +252,41 @@ pub fn show_model_dialog(
 
     dialog.present();
 }
+
+/// Processor extensions next to the ones whisper.cpp was built with.
+///
+/// The build decides the speed: an extension the processor has but the build
+/// lacks (AVX-512 is off in whisper.cpp by default) is named with a hint.
+fn create_cpu_features_label() -> Label {
+    let cpu = CpuFeatures::detect();
+    let build = build_cpu_features();
+    let mut text = format!("Процесор: {}. Збірка whisper.cpp: {}", cpu.summary(), build.summary());
+    let unused = cpu.unused_by(&build);
+    if !unused.is_empty() {
+        text.push_str(&format!(
+            "\nЗбірка не використовує {} — перезберіть програму через install.sh, щоб розпізнавання було швидшим",
+            unused.join(", ")
+        ));
+    }
+
+    let label = Label::new(Some(&text));
+    label.add_css_class("dim-label");
+    label.set_halign(Align::Start);
+    label.set_wrap(true);
+    label.set_selectable(true);
+    label.set_margin_start(12);
+    label.set_margin_end(12);
+    label
+}
+
+/// Failures the app only logged this session, so they are not lost in stderr.
+fn create_failures_label() -> Label {
+    let label = Label::new(Some(&failures::snapshot().summary()));
+    label.add_css_class("dim-label");
+    label.set_halign(Align::Start);
+    label.set_wrap(true);
+    label.set_selectable(true);
+    label.set_margin_start(12);
+    label.set_margin_end(12);
+    label
+}
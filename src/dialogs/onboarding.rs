@@ -0,0 +1,615 @@
+//! First-run onboarding wizard.
+//!
+//! Shown once when the app starts without a config file: picks the
+//! recognition language, downloads the model recommended for this machine
+//! and checks it on a sample recording, tests the microphone, sets up the
+//! global hotkey and the auto-copy behaviour. The wizard speaks Ukrainian on Ukrainian desktops and English
+//! everywhere else, so a new user is never left with an empty window.
+
+use super::model::DownloadProgress;
+use super::settings::{attach_hotkey_capture, LANGUAGES};
+use crate::app::config::save_config;
+use crate::app::context::AppContext;
+use crate::domain::traits::Transcription;
+use crate::domain::types::LevelReading;
+use crate::infrastructure::hardware::HardwareInfo;
+use crate::infrastructure::models::{
+    download_model, download_self_test_sample, format_size, get_available_models, get_model_path,
+    get_self_test_sample_path, is_model_downloaded, is_self_test_sample_downloaded,
+};
+use crate::transcription::self_test::{run_self_test, SelfTestResult};
+use crate::ui::a11y;
+use crate::ui::level_meter::LevelMeterView;
+use gtk4::prelude::*;
+use gtk4::{
+    glib, Align, Box as GtkBox, Button, CheckButton, ComboBoxText, Label, Orientation, ProgressBar, Stack, Window,
+};
+use std::cell::Cell;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Wizard strings in one interface language.
+struct Texts {
+    title: &'static str,
+    welcome: &'static str,
+    language_label: &'static str,
+    model_title: &'static str,
+    model_hint: &'static str,
+    download: &'static str,
+    downloading: &'static str,
+    downloaded: &'static str,
+    starting: &'static str,
+    error: &'static str,
+    self_test: &'static str,
+    self_test_hint: &'static str,
+    self_test_running: &'static str,
+    self_test_passed: &'static str,
+    self_test_failed: &'static str,
+    mic_title: &'static str,
+    mic_hint: &'static str,
+    mic_start: &'static str,
+    mic_stop: &'static str,
+    hotkey_title: &'static str,
+    hotkey_enable: &'static str,
+    hotkey_label: &'static str,
+    hotkey_capture: &'static str,
+    hotkey_waiting: &'static str,
+    copy_title: &'static str,
+    auto_copy: &'static str,
+    auto_paste: &'static str,
+    back: &'static str,
+    next: &'static str,
+    skip: &'static str,
+    finish: &'static str,
+}
+
+const UK_TEXTS: Texts = Texts {
+    title: "Ласкаво просимо",
+    welcome: "Кілька кроків, і можна диктувати.",
+    language_label: "Мова, якою ви говорите:",
+    model_title: "Модель розпізнавання",
+    model_hint: "Рекомендовано для цього комп'ютера",
+    download: "Завантажити",
+    downloading: "Завантаження...",
+    downloaded: "Завантажено",
+    starting: "Починаємо...",
+    error: "Помилка",
+    self_test: "Перевірити розпізнавання",
+    self_test_hint: "Розпізнає короткий англійський зразок і порівняє з відомим текстом.",
+    self_test_running: "Розпізнавання зразка...",
+    self_test_passed: "Розпізнавання працює",
+    self_test_failed: "Розпізнано погано, перевірте модель",
+    mic_title: "Перевірка мікрофона",
+    mic_hint: "Скажіть кілька слів — індикатор має рухатися.",
+    mic_start: "Перевірити",
+    mic_stop: "Зупинити",
+    hotkey_title: "Гаряча клавіша",
+    hotkey_enable: "Починати і зупиняти запис комбінацією клавіш з будь-якого вікна",
+    hotkey_label: "Комбінація клавіш:",
+    hotkey_capture: "Записати",
+    hotkey_waiting: "Натисніть комбінацію…",
+    copy_title: "Що робити з результатом",
+    auto_copy: "Автоматично копіювати в буфер обміну",
+    auto_paste: "Автоматично вставляти в активне вікно",
+    back: "Назад",
+    next: "Далі",
+    skip: "Пропустити",
+    finish: "Готово",
+};
+
+const EN_TEXTS: Texts = Texts {
+    title: "Welcome",
+    welcome: "A few steps and you are ready to dictate.",
+    language_label: "Language you speak:",
+    model_title: "Speech recognition model",
+    model_hint: "Recommended for this computer",
+    download: "Download",
+    downloading: "Downloading...",
+    downloaded: "Downloaded",
+    starting: "Starting...",
+    error: "Error",
+    self_test: "Test recognition",
+    self_test_hint: "Transcribes a short English sample and compares it with the known text.",
+    self_test_running: "Transcribing the sample...",
+    self_test_passed: "Recognition works",
+    self_test_failed: "Poor recognition, check the model",
+    mic_title: "Microphone test",
+    mic_hint: "Say a few words — the level bar should move.",
+    mic_start: "Test",
+    mic_stop: "Stop",
+    hotkey_title: "Hotkey",
+    hotkey_enable: "Start and stop recording with a key combination from any window",
+    hotkey_label: "Key combination:",
+    hotkey_capture: "Record",
+    hotkey_waiting: "Press the combination…",
+    copy_title: "What to do with the result",
+    auto_copy: "Copy to the clipboard automatically",
+    auto_paste: "Paste into the active window automatically",
+    back: "Back",
+    next: "Next",
+    skip: "Skip",
+    finish: "Done",
+};
+
+const PAGES: [&str; 5] = ["language", "model", "microphone", "hotkey", "copy"];
+
+/// Language code of the desktop session (`LC_ALL`, `LC_MESSAGES`, `LANG`).
+fn system_language() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty() && value != "C" && value != "POSIX")
+        .map(|value| value.split(['_', '.', '@']).next().unwrap_or_default().to_lowercase())
+}
+
+fn page_title(text: &str) -> Label {
+    let label = Label::new(Some(text));
+    label.add_css_class("title-2");
+    label.set_halign(Align::Start);
+    label
+}
+
+fn page_box() -> GtkBox {
+    let page = GtkBox::new(Orientation::Vertical, 12);
+    page.set_valign(Align::Start);
+    page
+}
+
+fn build_language_page(t: &Texts, language: &str) -> (GtkBox, ComboBoxText) {
+    let page = page_box();
+    page.append(&page_title(t.title));
+    let welcome = Label::new(Some(t.welcome));
+    welcome.set_halign(Align::Start);
+    page.append(&welcome);
+
+    let label = Label::new(Some(t.language_label));
+    label.set_halign(Align::Start);
+    label.set_margin_top(12);
+    page.append(&label);
+
+    let combo = ComboBoxText::new();
+    for (code, name) in LANGUAGES {
+        combo.append_text(&format!("{} - {}", code, name));
+    }
+    let active = LANGUAGES.iter().position(|(code, _)| *code == language).unwrap_or(0);
+    combo.set_active(Some(active as u32));
+    combo.set_halign(Align::Start);
+    a11y::set_labelled_by(&combo, &label);
+    page.append(&combo);
+
+    (page, combo)
+}
+
+fn build_model_page(t: &'static Texts, ctx: &Arc<AppContext>, on_model_ready: Rc<dyn Fn()>) -> GtkBox {
+    let page = page_box();
+    page.append(&page_title(t.model_title));
+
+    let filename = HardwareInfo::detect().recommended_model().to_string();
+    let info = get_available_models().into_iter().find(|m| m.filename == filename);
+    let description = match &info {
+        Some(info) => format!(
+            "{}: {} ({})",
+            t.model_hint,
+            info.display_name,
+            format_size(info.size_bytes)
+        ),
+        None => format!("{}: {}", t.model_hint, filename),
+    };
+    let description_label = Label::new(Some(&description));
+    description_label.set_halign(Align::Start);
+    description_label.set_wrap(true);
+    page.append(&description_label);
+
+    let progress_bar = ProgressBar::new();
+    progress_bar.set_show_text(true);
+    progress_bar.set_visible(false);
+    page.append(&progress_bar);
+
+    let row = GtkBox::new(Orientation::Horizontal, 12);
+    let download_button = Button::with_label(t.download);
+    download_button.add_css_class("suggested-action");
+    row.append(&download_button);
+    let status_label = Label::new(None);
+    status_label.add_css_class("dim-label");
+    row.append(&status_label);
+    page.append(&row);
+
+    let self_test_hint = Label::new(Some(t.self_test_hint));
+    self_test_hint.set_halign(Align::Start);
+    self_test_hint.set_wrap(true);
+    self_test_hint.add_css_class("dim-label");
+    page.append(&self_test_hint);
+
+    let self_test_row = GtkBox::new(Orientation::Horizontal, 12);
+    let self_test_button = Button::with_label(t.self_test);
+    self_test_button.set_sensitive(false);
+    self_test_row.append(&self_test_button);
+    let self_test_label = Label::new(None);
+    self_test_label.set_halign(Align::Start);
+    self_test_label.set_wrap(true);
+    self_test_row.append(&self_test_label);
+    page.append(&self_test_row);
+
+    if is_model_downloaded(&filename) {
+        download_button.set_sensitive(false);
+        status_label.set_text(t.downloaded);
+        self_test_button.set_sensitive(true);
+    }
+
+    {
+        let ctx = ctx.clone();
+        self_test_button.connect_clicked(move |button| start_self_test(t, &ctx, button, &self_test_label));
+    }
+
+    let ctx = ctx.clone();
+    download_button.connect_clicked(move |button| {
+        button.set_sensitive(false);
+        progress_bar.set_visible(true);
+        progress_bar.set_fraction(0.0);
+        progress_bar.set_text(Some(t.starting));
+        status_label.set_text(t.downloading);
+
+        let (tx, rx) = async_channel::bounded::<DownloadProgress>(100);
+        let filename_for_thread = filename.clone();
+        std::thread::spawn(move || {
+            let rt = tokio::runtime::Runtime::new().unwrap();
+            let tx_clone = tx.clone();
+            let result = rt.block_on(download_model(&filename_for_thread, move |downloaded, total| {
+                let _ = tx_clone.send_blocking(DownloadProgress::Progress(downloaded, total));
+            }));
+            let _ = tx.send_blocking(match result {
+                Ok(()) => DownloadProgress::Done,
+                Err(e) => DownloadProgress::Error(e.to_string()),
+            });
+        });
+
+        let button = button.clone();
+        let progress_bar = progress_bar.clone();
+        let status_label = status_label.clone();
+        let filename = filename.clone();
+        let ctx = ctx.clone();
+        let on_model_ready = on_model_ready.clone();
+        let self_test_button = self_test_button.clone();
+        glib::spawn_future_local(async move {
+            while let Ok(progress) = rx.recv().await {
+                match progress {
+                    DownloadProgress::Progress(downloaded, total) => {
+                        if total > 0 {
+                            let fraction = downloaded as f64 / total as f64;
+                            progress_bar.set_fraction(fraction);
+                            progress_bar.set_text(Some(&format!(
+                                "{} / {} ({:.0}%)",
+                                format_size(downloaded),
+                                format_size(total),
+                                fraction * 100.0
+                            )));
+                        }
+                    }
+                    DownloadProgress::Done => {
+                        progress_bar.set_visible(false);
+                        status_label.set_text(t.downloaded);
+                        use_downloaded_model(&ctx, &filename);
+                        self_test_button.set_sensitive(true);
+                        on_model_ready();
+                        break;
+                    }
+                    DownloadProgress::Error(e) => {
+                        progress_bar.set_visible(false);
+                        status_label.set_text(&format!("{}: {}", t.error, e));
+                        button.set_sensitive(true);
+                        break;
+                    }
+                }
+            }
+        });
+    });
+
+    page
+}
+
+/// Download the sample if needed, transcribe it and show how close it came.
+fn start_self_test(t: &'static Texts, ctx: &Arc<AppContext>, button: &Button, result_label: &Label) {
+    button.set_sensitive(false);
+    result_label.remove_css_class("error");
+    result_label.set_text(t.self_test_running);
+
+    let (tx, rx) = async_channel::bounded::<anyhow::Result<SelfTestResult>>(1);
+    let ctx_for_thread = ctx.clone();
+    std::thread::spawn(move || {
+        let _ = tx.send_blocking(self_test(&ctx_for_thread));
+    });
+
+    let button = button.clone();
+    let result_label = result_label.clone();
+    glib::spawn_future_local(async move {
+        let Ok(result) = rx.recv().await else {
+            return;
+        };
+        button.set_sensitive(true);
+        let message = match result {
+            Ok(result) => {
+                let verdict = if result.passed() {
+                    t.self_test_passed
+                } else {
+                    result_label.add_css_class("error");
+                    t.self_test_failed
+                };
+                format!("{} ({}%): «{}»", verdict, result.accuracy_percent(), result.text)
+            }
+            Err(e) => {
+                result_label.add_css_class("error");
+                format!("{}: {:#}", t.error, e)
+            }
+        };
+        result_label.set_text(&message);
+        a11y::announce(&result_label, &message, a11y::Urgency::Polite);
+    });
+}
+
+/// Runs in a worker thread; the sample is downloaded on first use.
+fn self_test(ctx: &AppContext) -> anyhow::Result<SelfTestResult> {
+    let path = if is_self_test_sample_downloaded() {
+        get_self_test_sample_path()
+    } else {
+        let rt = tokio::runtime::Runtime::new()?;
+        rt.block_on(download_self_test_sample(|_, _| {}))?
+    };
+    run_self_test(&*ctx.transcription.lock(), &path)
+}
+
+/// Make the freshly downloaded model the default and load it.
+fn use_downloaded_model(ctx: &AppContext, filename: &str) {
+    // Save config first, then drop the lock before acquiring transcription lock
+    {
+        let mut cfg = ctx.config.lock();
+        cfg.default_model = filename.to_string();
+        if let Err(e) = save_config(&cfg) {
+            eprintln!("Помилка збереження конфігу: {}", e);
+        }
+    }
+    if let Err(e) = ctx.transcription.lock().load_model(&get_model_path(filename)) {
+        eprintln!("Помилка завантаження моделі: {}", e);
+    }
+}
+
+/// Microphone page; the returned flag is true while the test is running.
+fn build_microphone_page(t: &'static Texts, ctx: &Arc<AppContext>) -> (GtkBox, Rc<Cell<bool>>) {
+    let page = page_box();
+    page.append(&page_title(t.mic_title));
+    let hint = Label::new(Some(t.mic_hint));
+    hint.set_halign(Align::Start);
+    page.append(&hint);
+
+    let level_bar = LevelMeterView::new(t.mic_title);
+    level_bar.widget().set_size_request(300, -1);
+    level_bar.widget().set_halign(Align::Start);
+    page.append(level_bar.widget());
+
+    let test_button = Button::with_label(t.mic_start);
+    test_button.set_halign(Align::Start);
+    page.append(&test_button);
+
+    let error_label = Label::new(None);
+    error_label.set_halign(Align::Start);
+    error_label.add_css_class("error");
+    page.append(&error_label);
+
+    let testing = Rc::new(Cell::new(false));
+    let testing_for_click = testing.clone();
+    let ctx = ctx.clone();
+    test_button.connect_clicked(move |button| {
+        if testing_for_click.get() {
+            stop_microphone_test(&ctx, &testing_for_click);
+            button.set_label(t.mic_start);
+            level_bar.set(LevelReading::SILENT);
+            return;
+        }
+
+        if let Err(e) = ctx.audio.start_mic() {
+            error_label.set_text(&format!("{}: {}", t.error, e));
+            return;
+        }
+        error_label.set_text("");
+        testing_for_click.set(true);
+        button.set_label(t.mic_stop);
+
+        let ctx = ctx.clone();
+        let testing = testing_for_click.clone();
+        let level_bar = level_bar.clone();
+        glib::timeout_add_local(Duration::from_millis(50), move || {
+            if !testing.get() {
+                level_bar.set(LevelReading::SILENT);
+                return glib::ControlFlow::Break;
+            }
+            level_bar.set(ctx.audio.mic_level());
+            glib::ControlFlow::Continue
+        });
+    });
+
+    (page, testing)
+}
+
+fn stop_microphone_test(ctx: &AppContext, testing: &Cell<bool>) {
+    if testing.replace(false) {
+        // The test audio is discarded
+        let _ = ctx.audio.stop_mic();
+    }
+}
+
+fn build_hotkey_page(t: &'static Texts, hotkey: &str) -> (GtkBox, CheckButton, gtk4::Entry) {
+    let page = page_box();
+    page.append(&page_title(t.hotkey_title));
+
+    let enabled_check = CheckButton::with_label(t.hotkey_enable);
+    enabled_check.set_active(true);
+    enabled_check.set_halign(Align::Start);
+    page.append(&enabled_check);
+
+    let entry_label = Label::new(Some(t.hotkey_label));
+    entry_label.set_halign(Align::Start);
+    page.append(&entry_label);
+
+    let entry_row = GtkBox::new(Orientation::Horizontal, 6);
+    let entry = gtk4::Entry::new();
+    entry.set_text(hotkey);
+    a11y::set_labelled_by(&entry, &entry_label);
+    entry_row.append(&entry);
+    let capture_button = Button::with_label(t.hotkey_capture);
+    entry_row.append(&capture_button);
+    entry_row.set_halign(Align::Start);
+    page.append(&entry_row);
+    attach_hotkey_capture(&capture_button, &entry, t.hotkey_capture, t.hotkey_waiting);
+
+    enabled_check.connect_toggled(move |check| entry_row.set_sensitive(check.is_active()));
+
+    (page, enabled_check, entry)
+}
+
+fn build_copy_page(t: &Texts, auto_copy: bool, auto_paste: bool) -> (GtkBox, CheckButton, CheckButton) {
+    let page = page_box();
+    page.append(&page_title(t.copy_title));
+
+    let auto_copy_check = CheckButton::with_label(t.auto_copy);
+    auto_copy_check.set_active(auto_copy);
+    auto_copy_check.set_halign(Align::Start);
+    page.append(&auto_copy_check);
+
+    let auto_paste_check = CheckButton::with_label(t.auto_paste);
+    auto_paste_check.set_active(auto_paste);
+    auto_paste_check.set_halign(Align::Start);
+    page.append(&auto_paste_check);
+
+    (page, auto_copy_check, auto_paste_check)
+}
+
+/// Show the first-run wizard over the main window.
+///
+/// `on_model_ready` runs after the recommended model was downloaded and
+/// loaded, so the main window can enable recording.
+pub fn show_onboarding_dialog(parent: &impl IsA<Window>, ctx: Arc<AppContext>, on_model_ready: impl Fn() + 'static) {
+    let system_language = system_language();
+    let t: &'static Texts = match system_language.as_deref() {
+        Some("uk") => &UK_TEXTS,
+        _ => &EN_TEXTS,
+    };
+    let cfg = ctx.config.lock().clone();
+    let language = system_language
+        .filter(|code| LANGUAGES.iter().any(|(known, _)| known == code))
+        .unwrap_or_else(|| cfg.language.clone());
+
+    let dialog = Window::builder()
+        .title(t.title)
+        .modal(true)
+        .transient_for(parent)
+        .default_width(480)
+        .default_height(320)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_top(20);
+    main_box.set_margin_bottom(20);
+    main_box.set_margin_start(20);
+    main_box.set_margin_end(20);
+
+    let stack = Stack::new();
+    stack.set_vexpand(true);
+    stack.set_transition_type(gtk4::StackTransitionType::SlideLeftRight);
+
+    let (language_page, language_combo) = build_language_page(t, &language);
+    let model_page = build_model_page(t, &ctx, Rc::new(on_model_ready));
+    let (microphone_page, mic_testing) = build_microphone_page(t, &ctx);
+    let (hotkey_page, hotkey_check, hotkey_entry) = build_hotkey_page(t, &cfg.hotkey);
+    let (copy_page, auto_copy_check, auto_paste_check) = build_copy_page(t, cfg.auto_copy, cfg.auto_paste);
+    for (name, page) in PAGES
+        .into_iter()
+        .zip([language_page, model_page, microphone_page, hotkey_page, copy_page])
+    {
+        stack.add_named(&page, Some(name));
+    }
+    main_box.append(&stack);
+
+    // Navigation
+    let button_box = GtkBox::new(Orientation::Horizontal, 12);
+    let skip_button = Button::with_label(t.skip);
+    skip_button.set_hexpand(true);
+    skip_button.set_halign(Align::Start);
+    button_box.append(&skip_button);
+    let back_button = Button::with_label(t.back);
+    back_button.set_sensitive(false);
+    button_box.append(&back_button);
+    let next_button = Button::with_label(t.next);
+    next_button.add_css_class("suggested-action");
+    button_box.append(&next_button);
+    main_box.append(&button_box);
+
+    let page_index = Rc::new(Cell::new(0usize));
+    let show_page = {
+        let stack = stack.clone();
+        let back_button = back_button.clone();
+        let next_button = next_button.clone();
+        let page_index = page_index.clone();
+        let ctx = ctx.clone();
+        let mic_testing = mic_testing.clone();
+        move |index: usize| {
+            // Leaving the microphone page ends the test
+            stop_microphone_test(&ctx, &mic_testing);
+            page_index.set(index);
+            stack.set_visible_child_name(PAGES[index]);
+            back_button.set_sensitive(index > 0);
+            next_button.set_label(if index + 1 == PAGES.len() { t.finish } else { t.next });
+        }
+    };
+
+    let show_page_back = show_page.clone();
+    let page_index_back = page_index.clone();
+    back_button.connect_clicked(move |_| {
+        show_page_back(page_index_back.get().saturating_sub(1));
+    });
+
+    let dialog_weak = dialog.downgrade();
+    let ctx_for_finish = ctx.clone();
+    next_button.connect_clicked(move |_| {
+        let index = page_index.get();
+        if index + 1 < PAGES.len() {
+            show_page(index + 1);
+            return;
+        }
+
+        {
+            let mut cfg = ctx_for_finish.config.lock();
+            if let Some((code, _)) = language_combo.active().and_then(|i| LANGUAGES.get(i as usize)) {
+                cfg.language = code.to_string();
+            }
+            cfg.hotkey_enabled = hotkey_check.is_active();
+            cfg.hotkey = hotkey_entry.text().to_string();
+            cfg.auto_copy = auto_copy_check.is_active();
+            cfg.auto_paste = auto_paste_check.is_active();
+        }
+        let _ = ctx_for_finish.channels.reload_hotkeys_tx().try_send(());
+        if let Some(d) = dialog_weak.upgrade() {
+            d.close();
+        }
+    });
+
+    let dialog_weak = dialog.downgrade();
+    skip_button.connect_clicked(move |_| {
+        if let Some(d) = dialog_weak.upgrade() {
+            d.close();
+        }
+    });
+
+    // Finishing, skipping and closing the window all end onboarding for good
+    dialog.connect_close_request(move |_| {
+        stop_microphone_test(&ctx, &mic_testing);
+        let mut cfg = ctx.config.lock();
+        cfg.onboarding_completed = true;
+        if let Err(e) = save_config(&cfg) {
+            eprintln!("Помилка збереження конфігу: {}", e);
+        }
+        glib::Propagation::Proceed
+    });
+
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
@@ -0,0 +1,96 @@
+//! Phone remote pairing dialog.
+//!
+//! Shows the remote control URL with its token as a QR code to scan with
+//! the phone camera, and lets the token be replaced when a phone should
+//! lose access.
+
+use crate::infrastructure::remote::{load_or_create_token, local_ip, pairing_qr, pairing_url, regenerate_token};
+use crate::ui::a11y;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, Label, Orientation, Window};
+
+/// Show the pairing QR code for the remote control on `port`.
+pub fn show_remote_pairing_dialog(parent: &impl IsA<Window>, port: u16) {
+    let dialog = Window::builder()
+        .title("Пульт на телефоні")
+        .modal(true)
+        .transient_for(parent)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+
+    let content = GtkBox::new(Orientation::Vertical, 12);
+    fill_pairing(&content, port, load_or_create_token());
+    main_box.append(&content);
+
+    let plain_http = Label::new(Some(
+        "З'єднання не шифрується (звичайний HTTP): користуйтеся пультом лише в довіреній локальній мережі.",
+    ));
+    plain_http.set_wrap(true);
+    plain_http.set_halign(Align::Start);
+    plain_http.add_css_class("dim-label");
+    main_box.append(&plain_http);
+
+    let regenerate_button = Button::with_label("Створити новий токен");
+    regenerate_button.set_tooltip_text(Some("Телефони, під'єднані зі старим токеном, втратять доступ"));
+    regenerate_button.set_halign(Align::Start);
+    regenerate_button.connect_clicked(move |_| {
+        fill_pairing(&content, port, regenerate_token());
+    });
+    main_box.append(&regenerate_button);
+
+    let close_button = Button::with_label("Закрити");
+    close_button.set_halign(Align::End);
+    let dialog_weak = dialog.downgrade();
+    close_button.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+    main_box.append(&close_button);
+
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Replace the contents of `content` with the pairing QR code and URL for `token`.
+fn fill_pairing(content: &GtkBox, port: u16, token: anyhow::Result<String>) {
+    while let Some(child) = content.first_child() {
+        content.remove(&child);
+    }
+
+    match token.and_then(|token| {
+        let url = pairing_url(local_ip(), port, &token);
+        pairing_qr(&url).map(|qr| (url, qr))
+    }) {
+        Ok((url, qr)) => {
+            let hint = Label::new(Some(
+                "Відскануйте код камерою телефону в тій самій мережі.\nНе показуйте його стороннім: він дає керування записом.",
+            ));
+            hint.set_halign(Align::Start);
+            content.append(&hint);
+
+            let qr_label = Label::new(None);
+            qr_label.set_markup(&format!(
+                "<span font_family=\"monospace\" line_height=\"0.85\">{}</span>",
+                gtk4::glib::markup_escape_text(&qr)
+            ));
+            a11y::set_name(&qr_label, "QR-код для під'єднання телефону");
+            content.append(&qr_label);
+
+            let url_label = Label::new(Some(&url));
+            url_label.set_selectable(true);
+            url_label.add_css_class("dim-label");
+            content.append(&url_label);
+        }
+        Err(e) => {
+            let error = Label::new(Some(&format!("Не вдалося підготувати під'єднання: {}", e)));
+            error.set_wrap(true);
+            content.append(&error);
+        }
+    }
+}
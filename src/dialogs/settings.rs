@@ -1,11 +1,27 @@
-use crate::app::config::{save_config, Config};
+use crate::app::config::{save_config, Config, WorkspacePreset, RECORDING_MODES};
+use crate::dialogs::remote::show_remote_pairing_dialog;
+use crate::dialogs::vad_calibration::show_vad_calibration_dialog;
+use crate::domain::types::SharedHistory;
+use crate::history::{create_store, list_backups, read_history_file, HistoryBackend};
+use crate::infrastructure::hotkeys::{describe_hotkey, format_hotkey};
+use crate::infrastructure::keygrab::X11_KEYCODE_OFFSET;
+use crate::infrastructure::webdav::{self, WebDavSettings};
+use crate::infrastructure::workspace::current_workspace_names;
+use crate::recording::loopback::list_monitor_sources;
+use crate::ui::a11y;
+use crate::vad::VadCalibration;
+use global_hotkey::hotkey::Modifiers;
 use gtk4::prelude::*;
-use gtk4::{Align, Box as GtkBox, Button, CheckButton, ComboBoxText, Label, Orientation, SpinButton, Window};
+use gtk4::{
+    gdk, glib, Align, Box as GtkBox, Button, CheckButton, ComboBoxText, Label, Orientation, SpinButton, Window,
+};
 use parking_lot::Mutex;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
 use std::sync::Arc;
 
 /// Supported languages for the speech recognition dropdown.
-const LANGUAGES: &[(&str, &str)] = &[
+pub(super) const LANGUAGES: &[(&str, &str)] = &[
     ("uk", "Українська"),
     ("en", "English"),
     ("ru", "Русский"),
@@ -26,15 +42,35 @@ struct SettingsWidgets {
     backend_combo: ComboBoxText,
     mode_combo: ComboBoxText,
     diarization_combo: ComboBoxText,
+    mic_diarization_check: CheckButton,
+    speaker_reid_check: CheckButton,
+    loopback_combo: ComboBoxText,
     auto_copy_check: CheckButton,
     auto_paste_check: CheckButton,
     continuous_check: CheckButton,
     vad_check: CheckButton,
+    vad_engine_combo: ComboBoxText,
+    vad_aggressiveness_combo: ComboBoxText,
+    vad_calibration: Rc<RefCell<Option<VadCalibration>>>,
+    captions_check: CheckButton,
     denoise_check: CheckButton,
-    hotkey_enabled_check: CheckButton,
-    hotkey_entry: gtk4::Entry,
-    max_entries_spin: SpinButton,
-    max_age_spin: SpinButton,
+    denoise_backend_combo: ComboBoxText,
+    high_pass_check: CheckButton,
+    fast_resampler_check: CheckButton,
+    pre_record_spin: SpinButton,
+    max_recording_spin: SpinButton,
+    echo_check: CheckButton,
+    save_recordings_check: CheckButton,
+    recording_format_combo: ComboBoxText,
+    agc_check: CheckButton,
+    monitor_check: CheckButton,
+    phonetic_hints_check: CheckButton,
+    verbatim_check: CheckButton,
+    hotkey: HotkeyWidgets,
+    history: HistoryWidgets,
+    webdav: WebDavWidgets,
+    remote: RemoteWidgets,
+    workspace: WorkspaceWidgets,
 }
 
 impl SettingsWidgets {
@@ -43,16 +79,65 @@ impl SettingsWidgets {
         cfg.language = self.read_language();
         cfg.stt_backend = combo_to_value(&self.backend_combo, &[("whisper", 0), ("tdt", 1)]);
         cfg.recording_mode = combo_to_value(&self.mode_combo, &[("dictation", 0), ("conference", 1)]);
-        cfg.diarization_method = combo_to_value(&self.diarization_combo, &[("channel", 0), ("sortformer", 1)]);
+        cfg.diarization_method = combo_to_value(
+            &self.diarization_combo,
+            &[("channel", 0), ("sortformer", 1), ("hybrid", 2)],
+        );
+        cfg.mic_diarization = self.mic_diarization_check.is_active();
+        cfg.speaker_reidentification = self.speaker_reid_check.is_active();
+        cfg.loopback_source = self.loopback_combo.active_id().map(|id| id.to_string());
         cfg.auto_copy = self.auto_copy_check.is_active();
         cfg.auto_paste = self.auto_paste_check.is_active();
         cfg.continuous_mode = self.continuous_check.is_active();
         cfg.use_vad = self.vad_check.is_active();
+        cfg.vad_engine = combo_to_value(&self.vad_engine_combo, &[("webrtc", 0), ("silero", 1), ("earshot", 2)]);
+        cfg.vad_aggressiveness = self.vad_aggressiveness_combo.active().unwrap_or(2) as u8;
+        if let Some(calibration) = self.vad_calibration.borrow().as_ref() {
+            cfg.silero_threshold = calibration.silero_threshold;
+            cfg.vad_silence_threshold_ms = calibration.silence_threshold_ms;
+            cfg.vad_noise_floor_dbfs = Some(calibration.noise_dbfs);
+            cfg.vad_speech_level_dbfs = Some(calibration.speech_dbfs);
+        }
+        cfg.translation_captions = self.captions_check.is_active();
         cfg.denoise_enabled = self.denoise_check.is_active();
-        cfg.hotkey_enabled = self.hotkey_enabled_check.is_active();
-        cfg.hotkey = self.hotkey_entry.text().to_string();
-        cfg.history_max_entries = self.max_entries_spin.value() as usize;
-        cfg.history_max_age_days = self.max_age_spin.value() as i64;
+        cfg.denoise_backend = combo_to_value(&self.denoise_backend_combo, &[("rnnoise", 0), ("deepfilternet", 1)]);
+        cfg.high_pass_enabled = self.high_pass_check.is_active();
+        cfg.resampler_quality = if self.fast_resampler_check.is_active() {
+            "fast".to_string()
+        } else {
+            "high".to_string()
+        };
+        cfg.pre_record_secs = self.pre_record_spin.value() as f32;
+        cfg.max_recording_minutes = self.max_recording_spin.value() as u32;
+        cfg.echo_cancellation = self.echo_check.is_active();
+        cfg.save_recordings = self.save_recordings_check.is_active();
+        cfg.recording_format = combo_to_value(&self.recording_format_combo, &[("wav", 0), ("flac", 1), ("opus", 2)]);
+        cfg.agc_enabled = self.agc_check.is_active();
+        cfg.monitor_enabled = self.monitor_check.is_active();
+        cfg.phonetic_hints = self.phonetic_hints_check.is_active();
+        cfg.verbatim_transcription = self.verbatim_check.is_active();
+        cfg.hotkey_enabled = self.hotkey.enabled_check.is_active();
+        cfg.hotkey = self.hotkey.entry.text().to_string();
+        cfg.hotkey_layout_independent = self.hotkey.layout_independent_check.is_active();
+        cfg.history_max_entries = self.history.max_entries_spin.value() as usize;
+        cfg.history_max_age_days = self.history.max_age_spin.value() as i64;
+        cfg.history_trash_days = self.history.trash_days_spin.value() as i64;
+        cfg.history_backups = self.history.backups_spin.value() as usize;
+        cfg.webdav_url = non_empty(&self.webdav.url_entry.text());
+        cfg.webdav_username = non_empty(&self.webdav.username_entry.text());
+        cfg.webdav_modes = self
+            .webdav
+            .mode_checks
+            .iter()
+            .filter(|(_, check)| check.is_active())
+            .map(|(mode, _)| mode.to_string())
+            .collect();
+        cfg.webdav_upload_transcripts = self.webdav.transcripts_check.is_active();
+        cfg.webdav_upload_recordings = self.webdav.recordings_check.is_active();
+        cfg.remote_control_enabled = self.remote.enabled_check.is_active();
+        cfg.remote_control_port = self.remote.port_spin.value() as u16;
+        cfg.workspace_presets_enabled = self.workspace.enabled_check.is_active();
+        cfg.workspace_presets = self.workspace.read_presets();
     }
 
     fn read_language(&self) -> String {
@@ -78,11 +163,113 @@ impl SettingsWidgets {
 struct RecordingWidgets {
     mode_combo: ComboBoxText,
     diarization_combo: ComboBoxText,
+    mic_diarization_check: CheckButton,
+    speaker_reid_check: CheckButton,
+    loopback_combo: ComboBoxText,
     auto_copy_check: CheckButton,
     auto_paste_check: CheckButton,
     continuous_check: CheckButton,
     vad_check: CheckButton,
+    vad_engine_combo: ComboBoxText,
+    vad_aggressiveness_combo: ComboBoxText,
+    vad_calibration: Rc<RefCell<Option<VadCalibration>>>,
+    captions_check: CheckButton,
     denoise_check: CheckButton,
+    denoise_backend_combo: ComboBoxText,
+    high_pass_check: CheckButton,
+    fast_resampler_check: CheckButton,
+    pre_record_spin: SpinButton,
+    max_recording_spin: SpinButton,
+    echo_check: CheckButton,
+    save_recordings_check: CheckButton,
+    recording_format_combo: ComboBoxText,
+    agc_check: CheckButton,
+    monitor_check: CheckButton,
+    phonetic_hints_check: CheckButton,
+    verbatim_check: CheckButton,
+}
+
+/// Widgets returned by `build_hotkey_section`.
+struct HotkeyWidgets {
+    enabled_check: CheckButton,
+    layout_independent_check: CheckButton,
+    entry: gtk4::Entry,
+}
+
+/// Widgets returned by `build_history_section`.
+struct HistoryWidgets {
+    max_entries_spin: SpinButton,
+    max_age_spin: SpinButton,
+    trash_days_spin: SpinButton,
+    backups_spin: SpinButton,
+}
+
+/// Widgets returned by `build_webdav_section`.
+struct WebDavWidgets {
+    url_entry: gtk4::Entry,
+    username_entry: gtk4::Entry,
+    password_entry: gtk4::PasswordEntry,
+    mode_checks: Vec<(&'static str, CheckButton)>,
+    transcripts_check: CheckButton,
+    recordings_check: CheckButton,
+}
+
+/// Widgets returned by `build_remote_section`.
+struct RemoteWidgets {
+    enabled_check: CheckButton,
+    port_spin: SpinButton,
+}
+
+/// Widgets returned by `build_workspace_section`.
+struct WorkspaceWidgets {
+    enabled_check: CheckButton,
+    rows: Rc<RefCell<Vec<PresetRow>>>,
+}
+
+/// One row of the workspace preset table.
+struct PresetRow {
+    row: GtkBox,
+    /// Preset as loaded; keeps the values the table has no column for
+    preset: WorkspacePreset,
+    workspace_entry: gtk4::Entry,
+    mode_combo: ComboBoxText,
+    paste_combo: ComboBoxText,
+}
+
+impl WorkspaceWidgets {
+    /// Presets from the table, skipping rows without a workspace.
+    fn read_presets(&self) -> Vec<WorkspacePreset> {
+        self.rows
+            .borrow()
+            .iter()
+            .filter_map(|row| {
+                let workspace = non_empty(&row.workspace_entry.text())?;
+                let recording_mode = row
+                    .mode_combo
+                    .active()
+                    .filter(|&index| index > 0)
+                    .and_then(|index| RECORDING_MODES.get(index as usize - 1))
+                    .map(|mode| mode.to_string());
+                let auto_paste = match row.paste_combo.active() {
+                    Some(1) => Some(true),
+                    Some(2) => Some(false),
+                    _ => None,
+                };
+                Some(WorkspacePreset {
+                    workspace,
+                    recording_mode,
+                    auto_paste,
+                    ..row.preset.clone()
+                })
+            })
+            .collect()
+    }
+}
+
+/// Trimmed entry text, or `None` if blank.
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
 }
 
 /// Map a ComboBoxText active index to one of the known string values.
@@ -126,6 +313,7 @@ fn build_language_section(parent: &GtkBox, cfg: &Config) -> ComboBoxText {
         }
     }
 
+    a11y::set_labelled_by(&combo, &label);
     parent.append(&combo);
     combo
 }
@@ -153,6 +341,7 @@ fn build_backend_section(parent: &GtkBox, cfg: &Config) -> ComboBoxText {
         parent.append(&info);
     }
 
+    a11y::set_labelled_by(&combo, &label);
     parent.append(&combo);
     combo
 }
@@ -173,6 +362,7 @@ fn build_recording_section(parent: &GtkBox, cfg: &Config) -> RecordingWidgets {
         mode_combo.set_active(Some(0));
     }
     mode_combo.set_halign(Align::Start);
+    a11y::set_labelled_by(&mode_combo, &mode_label);
     parent.append(&mode_combo);
 
     // Diarization method
@@ -184,21 +374,52 @@ fn build_recording_section(parent: &GtkBox, cfg: &Config) -> RecordingWidgets {
     let diarization_combo = ComboBoxText::new();
     diarization_combo.append_text("За каналами (2 мовці)");
     diarization_combo.append_text("Sortformer (до 4 мовців)");
-    if cfg.diarization_method == "sortformer" {
-        diarization_combo.set_active(Some(1));
-    } else {
-        diarization_combo.set_active(Some(0));
+    diarization_combo.append_text("Гібридний (ви + Sortformer для співрозмовників)");
+    match cfg.diarization_method.as_str() {
+        "sortformer" => diarization_combo.set_active(Some(1)),
+        "hybrid" => diarization_combo.set_active(Some(2)),
+        _ => diarization_combo.set_active(Some(0)),
     }
     diarization_combo.set_halign(Align::Start);
 
+    let mic_diarization_check = CheckButton::with_label("Розпізнавати мовців і в режимі диктовки (Sortformer)");
+    mic_diarization_check.set_active(cfg.mic_diarization);
+    mic_diarization_check.set_tooltip_text(Some(
+        "Для інтерв'ю, записаних на один мікрофон: текст ділиться на репліки [Спікер 1], [Спікер 2]…",
+    ));
+    mic_diarization_check.set_halign(Align::Start);
+
+    let speaker_reid_check = CheckButton::with_label("Впізнавати постійних учасників між сесіями");
+    speaker_reid_check.set_active(cfg.speaker_reidentification);
+    speaker_reid_check.set_tooltip_text(Some(
+        "Зберігає відбитки голосів (~/.local/share/voice-dictation/speakers.json), \
+         щоб той самий учасник отримував ту саму мітку в різних записах",
+    ));
+    speaker_reid_check.set_halign(Align::Start);
+
     if !crate::infrastructure::models::is_sortformer_model_downloaded() {
         diarization_combo.set_sensitive(false);
+        mic_diarization_check.set_sensitive(false);
+        speaker_reid_check.set_sensitive(false);
         let info = Label::new(Some("(Завантажте модель Sortformer через меню 'Моделі')"));
         info.add_css_class("dim-label");
         info.set_halign(Align::Start);
         parent.append(&info);
     }
+    a11y::set_labelled_by(&diarization_combo, &diarization_label);
     parent.append(&diarization_combo);
+    parent.append(&mic_diarization_check);
+    parent.append(&speaker_reid_check);
+
+    // System audio captured in conference mode
+    let loopback_label = Label::new(Some("Системне аудіо для конференції:"));
+    loopback_label.set_halign(Align::Start);
+    loopback_label.set_margin_top(12);
+    parent.append(&loopback_label);
+
+    let loopback_combo = build_loopback_combo(cfg);
+    a11y::set_labelled_by(&loopback_combo, &loopback_label);
+    parent.append(&loopback_combo);
 
     // Auto-copy, auto-paste
     let auto_copy_check = CheckButton::with_label("Автоматично копіювати результат");
@@ -225,30 +446,295 @@ fn build_recording_section(parent: &GtkBox, cfg: &Config) -> RecordingWidgets {
     vad_check.set_margin_start(20);
     parent.append(&vad_check);
 
+    let vad_engine_combo = ComboBoxText::new();
+    vad_engine_combo.append_text("WebRTC (найшвидший)");
+    vad_engine_combo.append_text("Silero (нейромережа, найточніший)");
+    vad_engine_combo.append_text("Earshot (легкий, стійкіший до шуму)");
+    match cfg.vad_engine.as_str() {
+        "silero" => vad_engine_combo.set_active(Some(1)),
+        "earshot" => vad_engine_combo.set_active(Some(2)),
+        _ => vad_engine_combo.set_active(Some(0)),
+    }
+    vad_engine_combo.set_sensitive(cfg.continuous_mode);
+    vad_engine_combo.set_tooltip_text(Some("Детектор мовлення для пауз між сегментами"));
+    vad_engine_combo.set_halign(Align::Start);
+    vad_engine_combo.set_margin_start(20);
+    a11y::set_name(&vad_engine_combo, "Детектор мовлення (VAD)");
+    parent.append(&vad_engine_combo);
+
+    let vad_aggressiveness_combo = ComboBoxText::new();
+    vad_aggressiveness_combo.append_text("0 — не пропускати тихе мовлення");
+    vad_aggressiveness_combo.append_text("1 — м'яко");
+    vad_aggressiveness_combo.append_text("2 — агресивно (типово)");
+    vad_aggressiveness_combo.append_text("3 — найсильніше відсіювати шум");
+    vad_aggressiveness_combo.set_active(Some(cfg.vad_aggressiveness.min(3) as u32));
+    vad_aggressiveness_combo.set_sensitive(cfg.continuous_mode && vad_engine_combo.active() == Some(0));
+    vad_aggressiveness_combo.set_tooltip_text(Some(
+        "Агресивність WebRTC VAD: вищі рівні допомагають у галасливому офісі",
+    ));
+    vad_aggressiveness_combo.set_halign(Align::Start);
+    vad_aggressiveness_combo.set_margin_start(20);
+    a11y::set_name(&vad_aggressiveness_combo, "Агресивність WebRTC VAD");
+    parent.append(&vad_aggressiveness_combo);
+
+    // Calibration is applied with the other settings on save
+    let vad_calibration: Rc<RefCell<Option<VadCalibration>>> = Rc::new(RefCell::new(None));
+    let calibration_row = GtkBox::new(Orientation::Horizontal, 8);
+    calibration_row.set_margin_start(20);
+    let calibrate_button = Button::with_label("Калібрувати VAD…");
+    calibrate_button.set_sensitive(cfg.continuous_mode);
+    calibrate_button.set_tooltip_text(Some(
+        "Записати кілька секунд тиші й мовлення та підібрати пороги детектора мовлення",
+    ));
+    calibration_row.append(&calibrate_button);
+    let calibration_label = Label::new(
+        match (cfg.vad_noise_floor_dbfs, cfg.vad_speech_level_dbfs) {
+            (Some(noise), Some(speech)) => Some(format!("Шум {:.0} дБFS, мовлення {:.0} дБFS", noise, speech)),
+            _ => None,
+        }
+        .as_deref(),
+    );
+    calibration_label.add_css_class("dim-label");
+    calibration_row.append(&calibration_label);
+    parent.append(&calibration_row);
+
+    let vad_calibration_clone = vad_calibration.clone();
+    let aggressiveness_combo_clone = vad_aggressiveness_combo.clone();
+    calibrate_button.connect_clicked(move |button| {
+        let Some(window) = button.root().and_downcast::<Window>() else {
+            return;
+        };
+        let vad_calibration = vad_calibration_clone.clone();
+        let aggressiveness_combo = aggressiveness_combo_clone.clone();
+        let calibration_label = calibration_label.clone();
+        show_vad_calibration_dialog(&window, move |calibration| {
+            aggressiveness_combo.set_active(Some(calibration.webrtc_aggressiveness as u32));
+            calibration_label.set_text(&format!(
+                "Шум {:.0} дБFS, мовлення {:.0} дБFS — збережеться після «Зберегти»",
+                calibration.noise_dbfs, calibration.speech_dbfs
+            ));
+            vad_calibration.replace(Some(calibration));
+        });
+    });
+
+    let aggressiveness_combo_clone = vad_aggressiveness_combo.clone();
+    let continuous_check_clone = continuous_check.clone();
+    vad_engine_combo.connect_changed(move |combo| {
+        aggressiveness_combo_clone.set_sensitive(continuous_check_clone.is_active() && combo.active() == Some(0));
+    });
+
+    let captions_check = CheckButton::with_label("Живі субтитри англійською (переклад Whisper)");
+    captions_check.set_active(cfg.translation_captions);
+    captions_check.set_sensitive(cfg.continuous_mode);
+    captions_check.set_tooltip_text(Some("Показує перекладені субтитри у окремому вікні під час запису"));
+    captions_check.set_halign(Align::Start);
+    captions_check.set_margin_start(20);
+    parent.append(&captions_check);
+
     let vad_check_clone = vad_check.clone();
+    let vad_engine_combo_clone = vad_engine_combo.clone();
+    let aggressiveness_combo_clone = vad_aggressiveness_combo.clone();
+    let captions_check_clone = captions_check.clone();
+    let calibrate_button_clone = calibrate_button.clone();
     continuous_check.connect_toggled(move |check| {
         vad_check_clone.set_sensitive(check.is_active());
+        calibrate_button_clone.set_sensitive(check.is_active());
+        vad_engine_combo_clone.set_sensitive(check.is_active());
+        aggressiveness_combo_clone.set_sensitive(check.is_active() && vad_engine_combo_clone.active() == Some(0));
+        captions_check_clone.set_sensitive(check.is_active());
     });
 
     // Denoise
-    let denoise_check = CheckButton::with_label("Шумоподавлення (RNNoise)");
+    let denoise_check = CheckButton::with_label("Шумоподавлення");
     denoise_check.set_active(cfg.denoise_enabled);
     denoise_check.set_halign(Align::Start);
     denoise_check.set_margin_top(12);
     parent.append(&denoise_check);
 
+    let denoise_backend_combo = ComboBoxText::new();
+    denoise_backend_combo.append_text("RNNoise (швидке, вбудоване)");
+    denoise_backend_combo.append_text("DeepFilterNet (якісніше, потрібна модель)");
+    denoise_backend_combo.set_active(Some(if cfg.denoise_backend == "deepfilternet" { 1 } else { 0 }));
+    denoise_backend_combo.set_sensitive(cfg.denoise_enabled);
+    denoise_backend_combo.set_tooltip_text(Some(
+        "Модель DeepFilterNet (ONNX) задається в config.toml: deepfilter_model_path",
+    ));
+    denoise_backend_combo.set_halign(Align::Start);
+    denoise_backend_combo.set_margin_start(20);
+    a11y::set_name(&denoise_backend_combo, "Алгоритм шумоподавлення");
+    parent.append(&denoise_backend_combo);
+
+    let denoise_backend_combo_clone = denoise_backend_combo.clone();
+    denoise_check.connect_toggled(move |check| {
+        denoise_backend_combo_clone.set_sensitive(check.is_active());
+    });
+
+    let high_pass_check = CheckButton::with_label("Прибирати низькочастотний гул");
+    high_pass_check.set_active(cfg.high_pass_enabled);
+    high_pass_check.set_tooltip_text(Some(&format!(
+        "Фільтр нижче {:.0} Гц: гул, удари по столу, постійна складова. Діє після перезапуску",
+        cfg.high_pass_cutoff_hz
+    )));
+    high_pass_check.set_halign(Align::Start);
+    parent.append(&high_pass_check);
+
+    let fast_resampler_check = CheckButton::with_label("Швидке перетворення частоти мікрофона");
+    fast_resampler_check.set_active(cfg.resampler_quality == "fast");
+    fast_resampler_check.set_tooltip_text(Some(
+        "Менше навантаження на процесор, але свистячі звуки розпізнаються гірше. Діє після перезапуску",
+    ));
+    fast_resampler_check.set_halign(Align::Start);
+    parent.append(&fast_resampler_check);
+
+    let pre_record_label = Label::new(Some("Попередній запис (секунд до натискання, 0 — вимкнено):"));
+    pre_record_label.set_halign(Align::Start);
+    parent.append(&pre_record_label);
+
+    let pre_record_spin = SpinButton::new(Some(&gtk4::Adjustment::new(0.0, 0.0, 10.0, 0.5, 1.0, 0.0)), 0.5, 1);
+    pre_record_spin.set_value(cfg.pre_record_secs as f64);
+    pre_record_spin.set_tooltip_text(Some(
+        "Запис починається з кількох секунд перед гарячою клавішею. Мікрофон лишається відкритим між записами. Діє після перезапуску",
+    ));
+    pre_record_spin.set_halign(Align::Start);
+    a11y::set_labelled_by(&pre_record_spin, &pre_record_label);
+    parent.append(&pre_record_spin);
+
+    let max_recording_label = Label::new(Some("Зупиняти запис через (хвилин, 0 — ніколи):"));
+    max_recording_label.set_halign(Align::Start);
+    parent.append(&max_recording_label);
+
+    let max_recording_spin = SpinButton::new(
+        Some(&gtk4::Adjustment::new(180.0, 0.0, 1440.0, 10.0, 60.0, 0.0)),
+        1.0,
+        0,
+    );
+    max_recording_spin.set_value(cfg.max_recording_minutes as f64);
+    max_recording_spin.set_tooltip_text(Some(
+        "Захист від забутого запису: після цього часу запис зупиняється, а записане обробляється як звичайно",
+    ));
+    max_recording_spin.set_halign(Align::Start);
+    a11y::set_labelled_by(&max_recording_spin, &max_recording_label);
+    parent.append(&max_recording_spin);
+
+    let echo_check = CheckButton::with_label("Ехоподавлення в режимі конференції");
+    echo_check.set_active(cfg.echo_cancellation);
+    echo_check.set_tooltip_text(Some(
+        "Прибирає з мікрофона голоси співрозмовників, почуті з динаміків. З навушниками не потрібне",
+    ));
+    echo_check.set_halign(Align::Start);
+    parent.append(&echo_check);
+
+    let save_recordings_check = CheckButton::with_label("Зберігати аудіо диктування");
+    save_recordings_check.set_active(cfg.save_recordings);
+    save_recordings_check.set_tooltip_text(Some(
+        "Запис диктування зберігається в теці записів і прив'язується до історії",
+    ));
+    save_recordings_check.set_halign(Align::Start);
+    parent.append(&save_recordings_check);
+
+    let recording_format_label = Label::new(Some("Формат записів:"));
+    recording_format_label.set_halign(Align::Start);
+    parent.append(&recording_format_label);
+
+    let recording_format_combo = ComboBoxText::new();
+    recording_format_combo.append_text("WAV (без стиснення)");
+    recording_format_combo.append_text("FLAC (без втрат)");
+    recording_format_combo.append_text("Opus (найменший)");
+    recording_format_combo.set_active(Some(match cfg.recording_format.as_str() {
+        "flac" => 1,
+        "opus" => 2,
+        _ => 0,
+    }));
+    recording_format_combo.set_tooltip_text(Some("FLAC і Opus потребують ffmpeg"));
+    recording_format_combo.set_halign(Align::Start);
+    a11y::set_labelled_by(&recording_format_combo, &recording_format_label);
+    parent.append(&recording_format_combo);
+
+    let agc_check = CheckButton::with_label("Підсилювати тихий мікрофон (AGC)");
+    agc_check.set_active(cfg.agc_enabled);
+    agc_check.set_tooltip_text(Some(
+        "Вирівнює рівень мовлення для детектора й розпізнавання. Діє після перезапуску",
+    ));
+    agc_check.set_halign(Align::Start);
+    parent.append(&agc_check);
+
+    let monitor_check = CheckButton::with_label("Чути мікрофон під час запису");
+    monitor_check.set_active(cfg.monitor_enabled);
+    monitor_check.set_tooltip_text(Some(
+        "Для навушників: перевірити рівень перед довгою зустріччю. З динаміками звук потрапить назад у мікрофон. Діє після перезапуску",
+    ));
+    monitor_check.set_halign(Align::Start);
+    parent.append(&monitor_check);
+
+    // Phonetic hints from the correction dictionary
+    let phonetic_hints_check = CheckButton::with_label("Підказки альтернативних написань імен");
+    phonetic_hints_check.set_active(cfg.phonetic_hints);
+    phonetic_hints_check.set_tooltip_text(Some("Словник: ~/.config/voice-dictation/corrections.toml"));
+    phonetic_hints_check.set_halign(Align::Start);
+    parent.append(&phonetic_hints_check);
+
+    let verbatim_check = CheckButton::with_label("Дослівно: вагання й обірвані слова");
+    verbatim_check.set_active(cfg.verbatim_transcription);
+    verbatim_check.set_tooltip_text(Some(
+        "Лишає в тексті [еее], [ммм] і обірвані слова (сло-). Діє після перезапуску",
+    ));
+    verbatim_check.set_halign(Align::Start);
+    parent.append(&verbatim_check);
+
     RecordingWidgets {
         mode_combo,
         diarization_combo,
+        mic_diarization_check,
+        speaker_reid_check,
+        loopback_combo,
         auto_copy_check,
         auto_paste_check,
         continuous_check,
         vad_check,
+        vad_engine_combo,
+        vad_aggressiveness_combo,
+        vad_calibration,
+        captions_check,
         denoise_check,
+        denoise_backend_combo,
+        high_pass_check,
+        fast_resampler_check,
+        pre_record_spin,
+        max_recording_spin,
+        echo_check,
+        save_recordings_check,
+        recording_format_combo,
+        agc_check,
+        monitor_check,
+        phonetic_hints_check,
+        verbatim_check,
     }
 }
 
-fn build_hotkey_section(parent: &GtkBox, cfg: &Config) -> (CheckButton, gtk4::Entry) {
+/// Monitor sources by description, with the source name as the row id.
+///
+/// A configured source that is not connected now keeps its row, so saving
+/// the settings without a headset plugged in does not forget it.
+fn build_loopback_combo(cfg: &Config) -> ComboBoxText {
+    let combo = ComboBoxText::new();
+    combo.append(None, "Монітор за замовчуванням");
+    combo.set_active(Some(0));
+
+    let sources = list_monitor_sources();
+    for source in &sources {
+        combo.append(Some(&source.name), &source.description);
+    }
+    if let Some(configured) = cfg.loopback_source.as_deref() {
+        if !sources.iter().any(|source| source.name == configured) {
+            combo.append(Some(configured), &format!("{} (не під'єднано)", configured));
+        }
+        combo.set_active_id(Some(configured));
+    }
+    combo.set_halign(Align::Start);
+    combo
+}
+
+fn build_hotkey_section(parent: &GtkBox, cfg: &Config) -> HotkeyWidgets {
     let label = Label::new(Some("Гарячі клавіші:"));
     label.set_halign(Align::Start);
     label.set_margin_top(12);
@@ -264,21 +750,125 @@ fn build_hotkey_section(parent: &GtkBox, cfg: &Config) -> (CheckButton, gtk4::En
     entry_label.set_margin_top(6);
     parent.append(&entry_label);
 
+    let entry_row = GtkBox::new(Orientation::Horizontal, 6);
     let entry = gtk4::Entry::new();
     entry.set_text(&cfg.hotkey);
-    entry.set_halign(Align::Start);
-    entry.set_sensitive(cfg.hotkey_enabled);
-    parent.append(&entry);
+    a11y::set_labelled_by(&entry, &entry_label);
+    entry_row.append(&entry);
+
+    let capture_button = Button::with_label("Записати");
+    capture_button.set_tooltip_text(Some("Натисніть, а потім потрібну комбінацію клавіш"));
+    entry_row.append(&capture_button);
+    entry_row.set_halign(Align::Start);
+    entry_row.set_sensitive(cfg.hotkey_enabled);
+    parent.append(&entry_row);
+
+    // Show what the combination is in both layouts
+    let layouts_label = Label::new(None);
+    layouts_label.set_halign(Align::Start);
+    layouts_label.add_css_class("dim-label");
+    parent.append(&layouts_label);
+    let update_layouts = {
+        let layouts_label = layouts_label.clone();
+        move |text: &str| match describe_hotkey(text) {
+            Ok(description) => layouts_label.set_text(&format!("Розкладки: {}", description)),
+            Err(_) => layouts_label.set_text("Невідома комбінація клавіш"),
+        }
+    };
+    update_layouts(&cfg.hotkey);
+    entry.connect_changed(move |entry| update_layouts(&entry.text()));
+
+    attach_hotkey_capture(&capture_button, &entry, "Записати", "Натисніть комбінацію…");
 
-    let entry_clone = entry.clone();
+    let layout_independent_check = CheckButton::with_label("Працювати в будь-якій розкладці (за фізичною клавішею)");
+    layout_independent_check.set_active(cfg.hotkey_layout_independent);
+    layout_independent_check.set_halign(Align::Start);
+    layout_independent_check.set_sensitive(cfg.hotkey_enabled);
+    parent.append(&layout_independent_check);
+
+    let entry_row_clone = entry_row.clone();
+    let layout_independent_clone = layout_independent_check.clone();
     enabled_check.connect_toggled(move |check| {
-        entry_clone.set_sensitive(check.is_active());
+        entry_row_clone.set_sensitive(check.is_active());
+        layout_independent_clone.set_sensitive(check.is_active());
+    });
+
+    HotkeyWidgets {
+        enabled_check,
+        layout_independent_check,
+        entry,
+    }
+}
+
+/// Capture the next key combination pressed while `button` is focused.
+///
+/// The key is recorded by its hardware keycode, so the result is the same
+/// whichever layout is active. Escape without modifiers cancels. The button
+/// shows `waiting_label` while capturing and `idle_label` afterwards.
+pub(super) fn attach_hotkey_capture(
+    button: &Button,
+    entry: &gtk4::Entry,
+    idle_label: &'static str,
+    waiting_label: &'static str,
+) {
+    let capturing = Rc::new(Cell::new(false));
+
+    let capturing_for_click = capturing.clone();
+    button.connect_clicked(move |button| {
+        capturing_for_click.set(true);
+        button.set_label(waiting_label);
+        button.grab_focus();
+    });
+
+    let key_controller = gtk4::EventControllerKey::new();
+    key_controller.set_propagation_phase(gtk4::PropagationPhase::Capture);
+    let button_weak = button.downgrade();
+    let entry = entry.clone();
+    key_controller.connect_key_pressed(move |_, keyval, keycode, state| {
+        if !capturing.get() {
+            return glib::Propagation::Proceed;
+        }
+
+        let modifiers = hotkey_modifiers(state);
+        let finished = if keyval == gdk::Key::Escape && modifiers.is_empty() {
+            true
+        } else if let Some(hotkey) = format_hotkey(modifiers, keycode.saturating_sub(X11_KEYCODE_OFFSET)) {
+            entry.set_text(&hotkey);
+            true
+        } else {
+            // Modifier-only press or unsupported key: keep waiting
+            false
+        };
+
+        if finished {
+            capturing.set(false);
+            if let Some(button) = button_weak.upgrade() {
+                button.set_label(idle_label);
+            }
+        }
+        glib::Propagation::Stop
     });
+    button.add_controller(key_controller);
+}
 
-    (enabled_check, entry)
+fn hotkey_modifiers(state: gdk::ModifierType) -> Modifiers {
+    let mut modifiers = Modifiers::empty();
+    if state.contains(gdk::ModifierType::CONTROL_MASK) {
+        modifiers |= Modifiers::CONTROL;
+    }
+    if state.contains(gdk::ModifierType::SHIFT_MASK) {
+        modifiers |= Modifiers::SHIFT;
+    }
+    if state.contains(gdk::ModifierType::ALT_MASK) {
+        modifiers |= Modifiers::ALT;
+    }
+    if state.contains(gdk::ModifierType::SUPER_MASK) {
+        modifiers |= Modifiers::META;
+    }
+    modifiers
 }
 
-fn build_history_section(parent: &GtkBox, cfg: &Config) -> (SpinButton, SpinButton) {
+fn build_history_section(parent: &GtkBox, cfg: &Config, history: &SharedHistory) -> HistoryWidgets {
     let label = Label::new(Some("Налаштування історії:"));
     label.set_halign(Align::Start);
     label.set_margin_top(12);
@@ -295,6 +885,7 @@ fn build_history_section(parent: &GtkBox, cfg: &Config) -> (SpinButton, SpinButt
     );
     max_entries_spin.set_value(cfg.history_max_entries as f64);
     max_entries_spin.set_halign(Align::Start);
+    a11y::set_labelled_by(&max_entries_spin, &max_entries_label);
     parent.append(&max_entries_spin);
 
     let max_age_label = Label::new(Some("Максимальний вік (дні):"));
@@ -305,17 +896,360 @@ fn build_history_section(parent: &GtkBox, cfg: &Config) -> (SpinButton, SpinButt
     let max_age_spin = SpinButton::new(Some(&gtk4::Adjustment::new(90.0, 1.0, 3650.0, 1.0, 10.0, 0.0)), 1.0, 0);
     max_age_spin.set_value(cfg.history_max_age_days as f64);
     max_age_spin.set_halign(Align::Start);
+    a11y::set_labelled_by(&max_age_spin, &max_age_label);
     parent.append(&max_age_spin);
 
-    (max_entries_spin, max_age_spin)
+    let trash_days_label = Label::new(Some("Зберігати видалені в кошику (дні):"));
+    trash_days_label.set_halign(Align::Start);
+    trash_days_label.set_margin_top(6);
+    parent.append(&trash_days_label);
+
+    let trash_days_spin = SpinButton::new(Some(&gtk4::Adjustment::new(30.0, 1.0, 365.0, 1.0, 10.0, 0.0)), 1.0, 0);
+    trash_days_spin.set_value(cfg.history_trash_days as f64);
+    trash_days_spin.set_halign(Align::Start);
+    a11y::set_labelled_by(&trash_days_spin, &trash_days_label);
+    parent.append(&trash_days_spin);
+
+    let backups_label = Label::new(Some("Резервних копій (0 — не робити):"));
+    backups_label.set_halign(Align::Start);
+    backups_label.set_margin_top(6);
+    parent.append(&backups_label);
+
+    let backups_spin = SpinButton::new(Some(&gtk4::Adjustment::new(5.0, 0.0, 50.0, 1.0, 5.0, 0.0)), 1.0, 0);
+    backups_spin.set_value(cfg.history_backups as f64);
+    backups_spin.set_halign(Align::Start);
+    backups_spin.set_tooltip_text(Some(
+        "Копія робиться перед кожним збереженням. Зміни діють після перезапуску",
+    ));
+    a11y::set_labelled_by(&backups_spin, &backups_label);
+    parent.append(&backups_spin);
+
+    build_backup_restore_row(parent, cfg, history);
+
+    HistoryWidgets {
+        max_entries_spin,
+        max_age_spin,
+        trash_days_spin,
+        backups_spin,
+    }
+}
+
+/// Choice of a history backup with a button replacing the history by it.
+fn build_backup_restore_row(parent: &GtkBox, cfg: &Config, history: &SharedHistory) {
+    let row = GtkBox::new(Orientation::Horizontal, 8);
+    let backup_label = Label::new(Some("Копія:"));
+    row.append(&backup_label);
+
+    let store = create_store(HistoryBackend::parse(&cfg.history_backend));
+    let backups = list_backups(store.as_ref());
+    let backup_combo = ComboBoxText::new();
+    for (path, time) in &backups {
+        backup_combo.append(
+            Some(&path.to_string_lossy()),
+            &time
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string(),
+        );
+    }
+    backup_combo.set_active(Some(0));
+    a11y::set_labelled_by(&backup_combo, &backup_label);
+    row.append(&backup_combo);
+
+    let restore_button = Button::with_label("Відновити");
+    restore_button.set_tooltip_text(Some(
+        "Замінити історію вибраною копією; поточна історія теж зберігається як копія",
+    ));
+    let read_only = history.lock().is_read_only();
+    backup_combo.set_sensitive(!backups.is_empty() && !read_only);
+    restore_button.set_sensitive(!backups.is_empty() && !read_only);
+    if backups.is_empty() {
+        backup_combo.append(None, "Копій ще немає");
+        backup_combo.set_active(Some(0));
+    } else if read_only {
+        restore_button.set_tooltip_text(Some("Історія лише для читання"));
+    }
+    row.append(&restore_button);
+    parent.append(&row);
+
+    let status_label = Label::new(None);
+    status_label.set_halign(Align::Start);
+    status_label.add_css_class("dim-label");
+    parent.append(&status_label);
+
+    let history = history.clone();
+    restore_button.connect_clicked(move |_| {
+        let Some(path) = backup_combo.active_id() else {
+            return;
+        };
+        let message = match read_history_file(std::path::Path::new(path.as_str())) {
+            Ok(entries) => {
+                let mut h = history.lock();
+                if h.replace_entries(entries) {
+                    match h.save() {
+                        Ok(()) => format!("Відновлено записів: {}", h.entries().len()),
+                        Err(e) => format!("Помилка збереження історії: {}", e),
+                    }
+                } else {
+                    "Історія лише для читання".to_string()
+                }
+            }
+            Err(e) => format!("Не вдалося прочитати копію: {}", e),
+        };
+        status_label.set_text(&message);
+        a11y::announce(&status_label, &message, a11y::Urgency::Polite);
+    });
+}
+
+fn build_webdav_section(parent: &GtkBox, cfg: &Config) -> WebDavWidgets {
+    let label = Label::new(Some("Вивантаження на WebDAV (Nextcloud):"));
+    label.set_halign(Align::Start);
+    label.set_margin_top(12);
+    parent.append(&label);
+
+    let url_entry = gtk4::Entry::builder()
+        .placeholder_text("https://cloud.example.com/remote.php/dav/files/user/Meetings")
+        .text(cfg.webdav_url.as_deref().unwrap_or_default())
+        .build();
+    a11y::set_name(&url_entry, "Адреса папки WebDAV");
+    parent.append(&url_entry);
+
+    let credentials_row = GtkBox::new(Orientation::Horizontal, 8);
+    let username_entry = gtk4::Entry::builder()
+        .placeholder_text("Користувач")
+        .text(cfg.webdav_username.as_deref().unwrap_or_default())
+        .hexpand(true)
+        .build();
+    a11y::set_name(&username_entry, "Користувач WebDAV");
+    credentials_row.append(&username_entry);
+
+    // Never prefilled: the password lives in the keyring and is only replaced when typed
+    let password_entry = gtk4::PasswordEntry::builder()
+        .placeholder_text("Пароль (у сховищі ключів)")
+        .show_peek_icon(true)
+        .hexpand(true)
+        .build();
+    a11y::set_name(&password_entry, "Пароль WebDAV");
+    credentials_row.append(&password_entry);
+    parent.append(&credentials_row);
+
+    let modes_row = GtkBox::new(Orientation::Horizontal, 12);
+    let mode_checks: Vec<(&'static str, CheckButton)> = [
+        ("dictation", "Диктовка"),
+        ("continuous", "Неперервний"),
+        ("conference", "Конференція"),
+    ]
+    .into_iter()
+    .map(|(mode, title)| {
+        let check = CheckButton::with_label(title);
+        check.set_active(cfg.webdav_modes.iter().any(|m| m == mode));
+        modes_row.append(&check);
+        (mode, check)
+    })
+    .collect();
+    parent.append(&modes_row);
+
+    let content_row = GtkBox::new(Orientation::Horizontal, 12);
+    let transcripts_check = CheckButton::with_label("Транскрипти");
+    transcripts_check.set_active(cfg.webdav_upload_transcripts);
+    content_row.append(&transcripts_check);
+    let recordings_check = CheckButton::with_label("Аудіозаписи");
+    recordings_check.set_active(cfg.webdav_upload_recordings);
+    recordings_check.set_tooltip_text(Some("Лише для конференцій — інші режими не зберігають аудіо"));
+    content_row.append(&recordings_check);
+    parent.append(&content_row);
+
+    WebDavWidgets {
+        url_entry,
+        username_entry,
+        password_entry,
+        mode_checks,
+        transcripts_check,
+        recordings_check,
+    }
+}
+
+fn build_remote_section(parent: &GtkBox, cfg: &Config) -> RemoteWidgets {
+    let label = Label::new(Some("Пульт на телефоні:"));
+    label.set_halign(Align::Start);
+    label.set_margin_top(12);
+    parent.append(&label);
+
+    let enabled_check = CheckButton::with_label("Керування записом з браузера телефону");
+    enabled_check.set_active(cfg.remote_control_enabled);
+    enabled_check.set_tooltip_text(Some(
+        "Відкриває порт у локальній мережі. З'єднання не шифрується (звичайний HTTP), \
+         тож вмикайте лише в довіреній мережі. Зміни діють після перезапуску",
+    ));
+    enabled_check.set_halign(Align::Start);
+    parent.append(&enabled_check);
+
+    let row = GtkBox::new(Orientation::Horizontal, 8);
+    row.set_margin_start(20);
+    let port_label = Label::new(Some("Порт:"));
+    row.append(&port_label);
+    let port_spin = SpinButton::new(
+        Some(&gtk4::Adjustment::new(8765.0, 1024.0, 65535.0, 1.0, 100.0, 0.0)),
+        1.0,
+        0,
+    );
+    port_spin.set_value(cfg.remote_control_port as f64);
+    a11y::set_labelled_by(&port_spin, &port_label);
+    row.append(&port_spin);
+
+    let pair_button = Button::with_label("Показати QR-код…");
+    pair_button.set_sensitive(cfg.remote_control_enabled);
+    let port_spin_for_pair = port_spin.clone();
+    pair_button.connect_clicked(move |button| {
+        if let Some(window) = button.root().and_downcast::<Window>() {
+            show_remote_pairing_dialog(&window, port_spin_for_pair.value() as u16);
+        }
+    });
+    row.append(&pair_button);
+    parent.append(&row);
+
+    let pair_button_clone = pair_button.clone();
+    enabled_check.connect_toggled(move |check| {
+        pair_button_clone.set_sensitive(check.is_active());
+    });
+
+    RemoteWidgets {
+        enabled_check,
+        port_spin,
+    }
+}
+
+fn build_workspace_section(parent: &GtkBox, cfg: &Config) -> WorkspaceWidgets {
+    let label = Label::new(Some("Пресети робочих просторів:"));
+    label.set_halign(Align::Start);
+    label.set_margin_top(12);
+    parent.append(&label);
+
+    let enabled_check = CheckButton::with_label("Застосовувати пресет активного простору на початку запису");
+    enabled_check.set_active(cfg.workspace_presets_enabled);
+    enabled_check.set_halign(Align::Start);
+    parent.append(&enabled_check);
+
+    let names = current_workspace_names();
+    let current_label = Label::new(Some(&if names.is_empty() {
+        "Активний простір визначити не вдалося (потрібні gdbus або xprop)".to_string()
+    } else {
+        format!("Зараз: {}", names.join(", "))
+    }));
+    current_label.add_css_class("dim-label");
+    current_label.set_halign(Align::Start);
+    current_label.set_wrap(true);
+    parent.append(&current_label);
+
+    let rows_box = GtkBox::new(Orientation::Vertical, 6);
+    parent.append(&rows_box);
+    let rows: Rc<RefCell<Vec<PresetRow>>> = Rc::new(RefCell::new(Vec::new()));
+    for preset in &cfg.workspace_presets {
+        append_preset_row(&rows_box, &rows, preset.clone());
+    }
+
+    let add_button = Button::with_label("Додати пресет");
+    add_button.set_halign(Align::Start);
+    let rows_for_add = rows.clone();
+    add_button.connect_clicked(move |_| {
+        append_preset_row(&rows_box, &rows_for_add, WorkspacePreset::default());
+    });
+    parent.append(&add_button);
+
+    WorkspaceWidgets { enabled_check, rows }
+}
+
+fn append_preset_row(rows_box: &GtkBox, rows: &Rc<RefCell<Vec<PresetRow>>>, preset: WorkspacePreset) {
+    let row = GtkBox::new(Orientation::Horizontal, 6);
+
+    let workspace_entry = gtk4::Entry::builder()
+        .placeholder_text("Простір або номер")
+        .text(preset.workspace.as_str())
+        .hexpand(true)
+        .build();
+    a11y::set_name(&workspace_entry, "Робочий простір");
+    row.append(&workspace_entry);
+
+    let mode_combo = ComboBoxText::new();
+    for title in ["Режим як є", "Диктовка", "Конференція", "Конференція у файл"]
+    {
+        mode_combo.append_text(title);
+    }
+    let mode_index = preset
+        .recording_mode
+        .as_deref()
+        .and_then(|mode| RECORDING_MODES.iter().position(|m| *m == mode))
+        .map_or(0, |index| index as u32 + 1);
+    mode_combo.set_active(Some(mode_index));
+    a11y::set_name(&mode_combo, "Режим запису пресета");
+    row.append(&mode_combo);
+
+    let paste_combo = ComboBoxText::new();
+    for title in ["Вставка як є", "Вставляти", "Не вставляти"] {
+        paste_combo.append_text(title);
+    }
+    paste_combo.set_active(Some(match preset.auto_paste {
+        None => 0,
+        Some(true) => 1,
+        Some(false) => 2,
+    }));
+    a11y::set_name(&paste_combo, "Автовставка пресета");
+    row.append(&paste_combo);
+
+    let remove_button = Button::from_icon_name("list-remove-symbolic");
+    remove_button.set_tooltip_text(Some("Видалити пресет"));
+    a11y::set_name(&remove_button, "Видалити пресет");
+    row.append(&remove_button);
+
+    let rows_box_weak = rows_box.downgrade();
+    let rows_weak = Rc::downgrade(rows);
+    let row_weak = row.downgrade();
+    remove_button.connect_clicked(move |_| {
+        let (Some(rows_box), Some(row)) = (rows_box_weak.upgrade(), row_weak.upgrade()) else {
+            return;
+        };
+        rows_box.remove(&row);
+        if let Some(rows) = rows_weak.upgrade() {
+            rows.borrow_mut().retain(|r| r.row != row);
+        }
+    });
+
+    rows_box.append(&row);
+    rows.borrow_mut().push(PresetRow {
+        row,
+        preset,
+        workspace_entry,
+        mode_combo,
+        paste_combo,
+    });
 }
 
 // ── Main dialog ───────────────────────────────────────────────────────
 
+/// Part of the settings dialog that can be opened directly, e.g. from Help.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsSection {
+    Language,
+    Recording,
+    Hotkeys,
+}
+
 pub fn show_settings_dialog(
     parent: &impl IsA<Window>,
     config: Arc<Mutex<Config>>,
+    history: SharedHistory,
+    reload_hotkeys_tx: async_channel::Sender<()>,
+) {
+    show_settings_dialog_at(parent, config, history, reload_hotkeys_tx, None);
+}
+
+/// Show the settings dialog with the first control of `section` focused.
+pub fn show_settings_dialog_at(
+    parent: &impl IsA<Window>,
+    config: Arc<Mutex<Config>>,
+    history: SharedHistory,
     reload_hotkeys_tx: async_channel::Sender<()>,
+    section: Option<SettingsSection>,
 ) {
     let dialog = Window::builder()
         .title("Налаштування")
@@ -337,8 +1271,17 @@ pub fn show_settings_dialog(
     let language_combo = build_language_section(&main_box, &cfg);
     let backend_combo = build_backend_section(&main_box, &cfg);
     let recording = build_recording_section(&main_box, &cfg);
-    let (hotkey_enabled_check, hotkey_entry) = build_hotkey_section(&main_box, &cfg);
-    let (max_entries_spin, max_age_spin) = build_history_section(&main_box, &cfg);
+    let hotkey = build_hotkey_section(&main_box, &cfg);
+    let history_widgets = build_history_section(&main_box, &cfg, &history);
+    let webdav = build_webdav_section(&main_box, &cfg);
+    let remote = build_remote_section(&main_box, &cfg);
+    let workspace = build_workspace_section(&main_box, &cfg);
+
+    let focus: Option<gtk4::Widget> = section.map(|section| match section {
+        SettingsSection::Language => language_combo.clone().upcast(),
+        SettingsSection::Recording => recording.mode_combo.clone().upcast(),
+        SettingsSection::Hotkeys => hotkey.enabled_check.clone().upcast(),
+    });
 
     // Buttons
     let button_box = GtkBox::new(Orientation::Horizontal, 12);
@@ -362,15 +1305,35 @@ pub fn show_settings_dialog(
         backend_combo,
         mode_combo: recording.mode_combo,
         diarization_combo: recording.diarization_combo,
+        mic_diarization_check: recording.mic_diarization_check,
+        speaker_reid_check: recording.speaker_reid_check,
+        loopback_combo: recording.loopback_combo,
         auto_copy_check: recording.auto_copy_check,
         auto_paste_check: recording.auto_paste_check,
         continuous_check: recording.continuous_check,
         vad_check: recording.vad_check,
+        vad_engine_combo: recording.vad_engine_combo,
+        vad_aggressiveness_combo: recording.vad_aggressiveness_combo,
+        vad_calibration: recording.vad_calibration,
+        captions_check: recording.captions_check,
         denoise_check: recording.denoise_check,
-        hotkey_enabled_check,
-        hotkey_entry,
-        max_entries_spin,
-        max_age_spin,
+        denoise_backend_combo: recording.denoise_backend_combo,
+        high_pass_check: recording.high_pass_check,
+        fast_resampler_check: recording.fast_resampler_check,
+        pre_record_spin: recording.pre_record_spin,
+        max_recording_spin: recording.max_recording_spin,
+        echo_check: recording.echo_check,
+        save_recordings_check: recording.save_recordings_check,
+        recording_format_combo: recording.recording_format_combo,
+        agc_check: recording.agc_check,
+        monitor_check: recording.monitor_check,
+        phonetic_hints_check: recording.phonetic_hints_check,
+        verbatim_check: recording.verbatim_check,
+        hotkey,
+        history: history_widgets,
+        webdav,
+        remote,
+        workspace,
     };
 
     let dialog_weak = dialog.downgrade();
@@ -378,6 +1341,16 @@ pub fn show_settings_dialog(
         let mut cfg = config.lock();
         widgets.apply_to_config(&mut cfg);
 
+        let password = widgets.webdav.password_entry.text().to_string();
+        if let Some(settings) = WebDavSettings::from_config(&cfg).filter(|_| !password.is_empty()) {
+            // secret-tool may wait for the keyring to be unlocked
+            std::thread::spawn(move || {
+                if let Err(e) = webdav::store_password(&settings, &password) {
+                    eprintln!("Помилка збереження пароля WebDAV: {}", e);
+                }
+            });
+        }
+
         if let Err(e) = save_config(&cfg) {
             eprintln!("Помилка збереження конфігу: {}", e);
         } else {
@@ -392,4 +1365,7 @@ pub fn show_settings_dialog(
     main_box.append(&button_box);
     dialog.set_child(Some(&main_box));
     dialog.present();
+    if let Some(widget) = focus {
+        widget.grab_focus();
+    }
 }
@@ -0,0 +1,90 @@
+//! Speaker renaming dialog.
+//!
+//! Lets the user replace generic labels such as "Спікер 1" with real names
+//! in a diarized transcript.
+
+use crate::ui::a11y;
+use crate::ui::field_dictation::FieldDictation;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, Entry, Grid, Label, Orientation, Window};
+
+/// Show a dialog with one name field per speaker.
+///
+/// On save, `on_save` receives `(old, new)` pairs for the speakers whose
+/// name was changed; empty fields keep the old name. With `dictation`, the
+/// names can also be dictated.
+pub fn show_rename_speakers_dialog(
+    parent: &impl IsA<Window>,
+    speakers: Vec<String>,
+    dictation: Option<&FieldDictation>,
+    on_save: impl Fn(Vec<(String, String)>) + 'static,
+) {
+    let dialog = Window::builder()
+        .title("Перейменувати мовців")
+        .modal(true)
+        .transient_for(parent)
+        .default_width(360)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+
+    let grid = Grid::new();
+    grid.set_row_spacing(8);
+    grid.set_column_spacing(12);
+
+    let mut entries: Vec<(String, Entry)> = Vec::new();
+    for (row, speaker) in speakers.into_iter().enumerate() {
+        let label = Label::new(Some(&speaker));
+        label.set_halign(Align::Start);
+        grid.attach(&label, 0, row as i32, 1, 1);
+
+        let entry = Entry::builder().text(speaker.as_str()).hexpand(true).build();
+        a11y::set_labelled_by(&entry, &label);
+        if let Some(dictation) = dictation {
+            dictation.attach(&entry);
+        }
+        grid.attach(&entry, 1, row as i32, 1, 1);
+        entries.push((speaker, entry));
+    }
+    main_box.append(&grid);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 8);
+    button_box.set_halign(Align::End);
+
+    let cancel_button = Button::with_label("Скасувати");
+    let dialog_weak = dialog.downgrade();
+    cancel_button.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+    button_box.append(&cancel_button);
+
+    let save_button = Button::with_label("Зберегти");
+    save_button.add_css_class("suggested-action");
+    let dialog_weak = dialog.downgrade();
+    save_button.connect_clicked(move |_| {
+        let renames: Vec<(String, String)> = entries
+            .iter()
+            .filter_map(|(old, entry)| {
+                let new = entry.text().trim().to_string();
+                (!new.is_empty() && new != *old).then(|| (old.clone(), new))
+            })
+            .collect();
+        if !renames.is_empty() {
+            on_save(renames);
+        }
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+    button_box.append(&save_button);
+
+    main_box.append(&button_box);
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
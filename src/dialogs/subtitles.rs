@@ -0,0 +1,268 @@
+//! Subtitle correction dialog.
+//!
+//! Imports an SRT file together with its audio, re-aligns the cues to the
+//! audio and shows one text field per cue. Cue times are shown but not
+//! edited: the user fixes the text, and the cues are saved as a new SRT file
+//! with their timing kept.
+
+use crate::app::context::AppContext;
+use crate::cli::args::ChannelMode;
+use crate::cli::wav_reader::{prepare_for_whisper, read_audio};
+use crate::transcription::subtitles::{align_cues, format_srt, format_timestamp, parse_srt, Alignment, SubtitleCue};
+use crate::transcription::word_attribution::{transcribe_words_windowed, DEFAULT_WORD_WINDOW_SECS};
+use crate::ui::a11y::{self, Urgency};
+use anyhow::{bail, Context, Result};
+use gtk4::prelude::*;
+use gtk4::{
+    glib, Align, Box as GtkBox, Button, FileChooserNative, Label, ListBox, Orientation, ScrolledWindow, SelectionMode,
+    Spinner, TextBuffer, TextView, Window, WrapMode,
+};
+use std::cell::RefCell;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Ask for an SRT file and its audio, then open the editor.
+pub fn show_subtitle_dialog(parent: &impl IsA<Window>, ctx: Arc<AppContext>) {
+    let parent = parent.clone().upcast::<Window>();
+    choose_file(
+        &parent,
+        "Відкрити субтитри",
+        "Субтитри SRT",
+        &["*.srt"],
+        {
+            let parent = parent.clone();
+            move |subtitles| {
+                let parent_for_editor = parent.clone();
+                let ctx = ctx.clone();
+                choose_file(
+                    &parent,
+                    "Аудіо до субтитрів",
+                    "Аудіо (WAV, MP3, OGG, FLAC, M4A)",
+                    &["*.wav", "*.mp3", "*.ogg", "*.oga", "*.flac", "*.m4a"],
+                    move |audio| {
+                        show_editor(&parent_for_editor, ctx.clone(), subtitles.clone(), audio);
+                    },
+                );
+            }
+        },
+    );
+}
+
+fn choose_file(
+    parent: &Window,
+    title: &str,
+    filter_name: &str,
+    patterns: &[&str],
+    on_chosen: impl Fn(PathBuf) + 'static,
+) {
+    let chooser = FileChooserNative::builder()
+        .title(title)
+        .action(gtk4::FileChooserAction::Open)
+        .modal(true)
+        .transient_for(parent)
+        .build();
+
+    let filter = gtk4::FileFilter::new();
+    filter.set_name(Some(filter_name));
+    for pattern in patterns {
+        filter.add_pattern(pattern);
+    }
+    chooser.add_filter(&filter);
+
+    chooser.connect_response(move |chooser, response| {
+        if response == gtk4::ResponseType::Accept {
+            if let Some(path) = chooser.file().and_then(|f| f.path()) {
+                on_chosen(path);
+            }
+        }
+        chooser.destroy();
+    });
+    chooser.show();
+}
+
+/// Cues with the buffers their text is edited in.
+type EditedCues = Rc<RefCell<Vec<(SubtitleCue, TextBuffer)>>>;
+
+fn show_editor(parent: &Window, ctx: Arc<AppContext>, subtitles: PathBuf, audio: PathBuf) {
+    let dialog = Window::builder()
+        .title("Субтитри")
+        .modal(false)
+        .transient_for(parent)
+        .default_width(700)
+        .default_height(600)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+
+    let status_box = GtkBox::new(Orientation::Horizontal, 8);
+    let spinner = Spinner::new();
+    spinner.start();
+    let status_label = Label::new(Some("Вирівнювання субтитрів за аудіо..."));
+    status_label.set_halign(Align::Start);
+    status_label.set_wrap(true);
+    status_box.append(&spinner);
+    status_box.append(&status_label);
+    main_box.append(&status_box);
+
+    let list_box = ListBox::new();
+    list_box.set_selection_mode(SelectionMode::None);
+    a11y::set_name(&list_box, "Репліки субтитрів");
+    let scrolled = ScrolledWindow::new();
+    scrolled.set_vexpand(true);
+    scrolled.set_child(Some(&list_box));
+    main_box.append(&scrolled);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 12);
+    button_box.set_halign(Align::End);
+    let save_button = Button::with_label("Зберегти як…");
+    save_button.add_css_class("suggested-action");
+    save_button.set_sensitive(false);
+    let close_button = Button::with_label("Закрити");
+    button_box.append(&save_button);
+    button_box.append(&close_button);
+    main_box.append(&button_box);
+
+    let dialog_weak = dialog.downgrade();
+    close_button.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+
+    let cues: EditedCues = Rc::new(RefCell::new(Vec::new()));
+    let cues_for_save = cues.clone();
+    let status_for_save = status_label.clone();
+    let dialog_weak = dialog.downgrade();
+    let default_name = corrected_file_name(&subtitles);
+    save_button.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            save_subtitles(&dialog, &default_name, cues_for_save.clone(), status_for_save.clone());
+        }
+    });
+
+    let (tx, rx) = async_channel::bounded::<Result<Alignment>>(1);
+    let language = ctx.language();
+    std::thread::spawn(move || {
+        let _ = tx.send_blocking(align_subtitles(&ctx, &subtitles, &audio, &language));
+    });
+
+    glib::spawn_future_local(async move {
+        let Ok(result) = rx.recv().await else {
+            return;
+        };
+        spinner.stop();
+        spinner.set_visible(false);
+        let message = match result {
+            Ok(alignment) => {
+                let total = alignment.cues.len();
+                for cue in alignment.cues {
+                    let (row, buffer) = create_cue_row(&cue);
+                    list_box.append(&row);
+                    cues.borrow_mut().push((cue, buffer));
+                }
+                save_button.set_sensitive(true);
+                format!(
+                    "Вирівняно за аудіо {} з {} реплік. Час реплік збережеться, виправляйте текст",
+                    alignment.aligned, total
+                )
+            }
+            Err(e) => format!("Помилка: {:#}", e),
+        };
+        status_label.set_text(&message);
+        a11y::announce(&status_label, &message, Urgency::Polite);
+    });
+
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Read both files and re-time the cues. Runs in a worker thread.
+fn align_subtitles(ctx: &AppContext, subtitles: &Path, audio: &Path, language: &str) -> Result<Alignment> {
+    let content = fs::read_to_string(subtitles)
+        .with_context(|| format!("Не вдалося прочитати субтитри: {}", subtitles.display()))?;
+    let cues = parse_srt(&content)?;
+    if !ctx.is_model_loaded() {
+        bail!("Модель ще не завантажена");
+    }
+    let prepared = prepare_for_whisper(&read_audio(audio)?, ChannelMode::Mix, ctx.denoise_settings().as_ref())?;
+    let words = transcribe_words_windowed(
+        &*ctx.transcription.lock(),
+        &prepared.samples,
+        language,
+        DEFAULT_WORD_WINDOW_SECS * 16000,
+    )?;
+    Ok(align_cues(&cues, &words))
+}
+
+fn create_cue_row(cue: &SubtitleCue) -> (GtkBox, TextBuffer) {
+    let row = GtkBox::new(Orientation::Horizontal, 12);
+    row.set_margin_top(4);
+    row.set_margin_bottom(4);
+
+    let time_label = Label::new(Some(&format!(
+        "{}\n{}",
+        format_timestamp(cue.start),
+        format_timestamp(cue.end)
+    )));
+    time_label.add_css_class("monospace");
+    time_label.add_css_class("dim-label");
+    time_label.set_valign(Align::Start);
+    row.append(&time_label);
+
+    let text_view = TextView::new();
+    text_view.set_wrap_mode(WrapMode::WordChar);
+    text_view.set_hexpand(true);
+    text_view.buffer().set_text(&cue.text);
+    a11y::set_name(&text_view, &format!("Репліка о {}", format_timestamp(cue.start)));
+    row.append(&text_view);
+
+    (row, text_view.buffer())
+}
+
+fn save_subtitles(parent: &Window, default_name: &str, cues: EditedCues, status_label: Label) {
+    let chooser = FileChooserNative::builder()
+        .title("Зберегти субтитри")
+        .action(gtk4::FileChooserAction::Save)
+        .modal(true)
+        .transient_for(parent)
+        .build();
+    chooser.set_current_name(default_name);
+
+    chooser.connect_response(move |chooser, response| {
+        if response == gtk4::ResponseType::Accept {
+            if let Some(path) = chooser.file().and_then(|f| f.path()) {
+                let edited: Vec<SubtitleCue> = cues
+                    .borrow()
+                    .iter()
+                    .map(|(cue, buffer)| SubtitleCue {
+                        text: buffer
+                            .text(&buffer.start_iter(), &buffer.end_iter(), false)
+                            .trim()
+                            .to_string(),
+                        ..cue.clone()
+                    })
+                    .collect();
+                let message = match fs::write(&path, format_srt(&edited)) {
+                    Ok(()) => format!("Збережено: {}", path.display()),
+                    Err(e) => format!("Не вдалося зберегти {}: {}", path.display(), e),
+                };
+                status_label.set_text(&message);
+                a11y::announce(&status_label, &message, Urgency::Polite);
+            }
+        }
+        chooser.destroy();
+    });
+    chooser.show();
+}
+
+/// `film.srt` → `film.corrected.srt`, so the original is not overwritten by default.
+fn corrected_file_name(subtitles: &Path) -> String {
+    let stem = subtitles.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    format!("{}.corrected.srt", stem)
+}
@@ -0,0 +1,181 @@
+//! VAD calibration dialog.
+//!
+//! Records a few seconds of room noise, then a few seconds of speech, and
+//! shows the thresholds recommended for this microphone and room.
+
+use crate::domain::types::LevelReading;
+use crate::recording::microphone::AudioRecorder;
+use crate::ui::level_meter::LevelMeterView;
+use crate::vad::{calibrate, VadCalibration};
+use gtk4::prelude::*;
+use gtk4::{glib, Align, Box as GtkBox, Button, Label, Orientation, Window};
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Length of the silent phase.
+const NOISE_SECS: f32 = 4.0;
+/// Length of the speech phase.
+const SPEECH_SECS: f32 = 6.0;
+/// Start of the speech phase skipped while the user starts talking.
+const REACTION_SAMPLES: usize = 8000;
+
+/// Show the calibration dialog; `on_apply` receives the accepted calibration.
+pub fn show_vad_calibration_dialog(parent: &impl IsA<Window>, on_apply: impl Fn(VadCalibration) + 'static) {
+    let dialog = Window::builder()
+        .title("Калібрування VAD")
+        .modal(true)
+        .transient_for(parent)
+        .default_width(420)
+        .build();
+
+    let main_box = GtkBox::new(Orientation::Vertical, 12);
+    main_box.set_margin_top(12);
+    main_box.set_margin_bottom(12);
+    main_box.set_margin_start(12);
+    main_box.set_margin_end(12);
+
+    let hint = Label::new(Some(&format!(
+        "Калібрування підбере пороги детектора мовлення під ваш мікрофон і кімнату.\n\
+         Спершу {:.0} с помовчіть, потім {:.0} с говоріть як зазвичай, з паузами.",
+        NOISE_SECS, SPEECH_SECS
+    )));
+    hint.set_wrap(true);
+    hint.set_halign(Align::Start);
+    main_box.append(&hint);
+
+    let step_label = Label::new(None);
+    step_label.set_halign(Align::Start);
+    step_label.add_css_class("heading");
+    main_box.append(&step_label);
+
+    let level_bar = LevelMeterView::new("Рівень мікрофона");
+    main_box.append(level_bar.widget());
+
+    let result_label = Label::new(None);
+    result_label.set_wrap(true);
+    result_label.set_halign(Align::Start);
+    result_label.set_selectable(true);
+    main_box.append(&result_label);
+
+    let button_box = GtkBox::new(Orientation::Horizontal, 12);
+    button_box.set_halign(Align::End);
+    let close_button = Button::with_label("Закрити");
+    let start_button = Button::with_label("Почати");
+    let apply_button = Button::with_label("Застосувати");
+    apply_button.add_css_class("suggested-action");
+    apply_button.set_sensitive(false);
+    button_box.append(&close_button);
+    button_box.append(&start_button);
+    button_box.append(&apply_button);
+    main_box.append(&button_box);
+
+    let calibration: Rc<RefCell<Option<VadCalibration>>> = Rc::new(RefCell::new(None));
+
+    let calibration_for_start = calibration.clone();
+    let apply_button_for_start = apply_button.clone();
+    start_button.connect_clicked(move |button| {
+        let recorder = Rc::new(AudioRecorder::new());
+        if let Err(e) = recorder.start_recording() {
+            result_label.set_text(&format!("Помилка: {}", e));
+            return;
+        }
+        button.set_sensitive(false);
+        apply_button_for_start.set_sensitive(false);
+        calibration_for_start.replace(None);
+        result_label.set_text("");
+        step_label.set_text("Помовчіть…");
+
+        let started = Instant::now();
+        let mut split = None;
+        let button = button.clone();
+        let step_label = step_label.clone();
+        let level_bar = level_bar.clone();
+        let result_label = result_label.clone();
+        let apply_button = apply_button_for_start.clone();
+        let calibration = calibration_for_start.clone();
+        glib::timeout_add_local(Duration::from_millis(50), move || {
+            level_bar.set(recorder.level());
+            let elapsed = started.elapsed().as_secs_f32();
+            if split.is_none() && elapsed >= NOISE_SECS {
+                split = Some(recorder.samples().lock().len());
+                step_label.set_text("Тепер говоріть…");
+            }
+            if elapsed < NOISE_SECS + SPEECH_SECS {
+                return glib::ControlFlow::Continue;
+            }
+
+            let (samples, _) = recorder.stop_recording();
+            level_bar.set(LevelReading::SILENT);
+            step_label.set_text("Аналіз…");
+            let split = split.unwrap_or_default().min(samples.len());
+
+            let (tx, rx) = async_channel::bounded(1);
+            std::thread::spawn(move || {
+                let (noise, speech) = samples.split_at(split);
+                let speech = speech.get(REACTION_SAMPLES..).unwrap_or_default();
+                let _ = tx.send_blocking(calibrate(noise, speech));
+            });
+
+            let button = button.clone();
+            let step_label = step_label.clone();
+            let result_label = result_label.clone();
+            let apply_button = apply_button.clone();
+            let calibration = calibration.clone();
+            glib::spawn_future_local(async move {
+                button.set_sensitive(true);
+                button.set_label("Повторити");
+                match rx.recv().await {
+                    Ok(Ok(result)) => {
+                        step_label.set_text("Готово");
+                        result_label.set_text(&describe(&result));
+                        calibration.replace(Some(result));
+                        apply_button.set_sensitive(true);
+                    }
+                    Ok(Err(e)) => {
+                        step_label.set_text("");
+                        result_label.set_text(&format!("Не вдалося відкалібрувати: {}", e));
+                    }
+                    Err(_) => step_label.set_text(""),
+                }
+            });
+            glib::ControlFlow::Break
+        });
+    });
+
+    let dialog_weak = dialog.downgrade();
+    close_button.connect_clicked(move |_| {
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+
+    let dialog_weak = dialog.downgrade();
+    apply_button.connect_clicked(move |_| {
+        if let Some(result) = calibration.borrow().clone() {
+            on_apply(result);
+        }
+        if let Some(dialog) = dialog_weak.upgrade() {
+            dialog.close();
+        }
+    });
+
+    dialog.set_child(Some(&main_box));
+    dialog.present();
+}
+
+/// Measured levels and the recommended settings, for display.
+fn describe(calibration: &VadCalibration) -> String {
+    format!(
+        "Шум: {:.0} дБFS, мовлення: {:.0} дБFS (різниця {:.0} дБ)\n\
+         Поріг Silero: {:.2}\n\
+         Пауза для завершення сегмента: {} мс\n\
+         Агресивність WebRTC: {}",
+        calibration.noise_dbfs,
+        calibration.speech_dbfs,
+        calibration.snr_db(),
+        calibration.silero_threshold,
+        calibration.silence_threshold_ms,
+        calibration.webrtc_aggressiveness
+    )
+}
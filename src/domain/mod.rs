@@ -1,2 +1,3 @@
+pub mod text_metrics;
 pub mod traits;
 pub mod types;
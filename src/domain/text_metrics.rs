@@ -0,0 +1,70 @@
+//! Text statistics following Unicode segmentation rules.
+//!
+//! Counting `char`s splits letters with combining marks (stress accents such
+//! as "а́") into two, and splitting on whitespace counts dashes and stray
+//! punctuation as words. These helpers use grapheme clusters and UAX #29
+//! word boundaries instead, which also keep apostrophe words ("п'ять",
+//! "м’ята", "обʼєкт") whole, so metrics, previews and exports agree.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Number of words (UAX #29 word boundaries, punctuation excluded).
+pub fn word_count(text: &str) -> usize {
+    text.unicode_words().count()
+}
+
+/// Number of user-perceived characters (extended grapheme clusters).
+pub fn grapheme_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// First `max` graphemes of `text`, with "..." appended if anything was cut.
+pub fn truncate_graphemes(text: &str, max: usize) -> String {
+    match text.grapheme_indices(true).nth(max) {
+        Some((byte_index, _)) => format!("{}...", &text[..byte_index]),
+        None => text.to_string(),
+    }
+}
+
+/// Speaking rate in words per minute; `None` without words or duration.
+pub fn words_per_minute(text: &str, duration_secs: f32) -> Option<f32> {
+    let words = word_count(text);
+    if words == 0 || duration_secs <= 0.0 {
+        return None;
+    }
+    Some(words as f32 * 60.0 / duration_secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_count_keeps_apostrophe_words_and_skips_punctuation() {
+        assert_eq!(word_count("П'ять м’ятних обʼєктів — і все."), 5);
+        assert_eq!(word_count(" — , ..."), 0);
+        assert_eq!(word_count(""), 0);
+    }
+
+    #[test]
+    fn test_grapheme_count_combines_stress_marks() {
+        // "за́мок" with a combining acute accent: 6 chars, 5 graphemes
+        let text = "за\u{301}мок";
+        assert_eq!(text.chars().count(), 6);
+        assert_eq!(grapheme_count(text), 5);
+    }
+
+    #[test]
+    fn test_truncate_graphemes_never_splits_a_cluster() {
+        assert_eq!(truncate_graphemes("за\u{301}мок", 2), "за\u{301}...");
+        assert_eq!(truncate_graphemes("замок", 5), "замок");
+        assert_eq!(truncate_graphemes("замок", 10), "замок");
+    }
+
+    #[test]
+    fn test_words_per_minute() {
+        assert_eq!(words_per_minute("раз два три", 30.0), Some(6.0));
+        assert_eq!(words_per_minute("раз два три", 0.0), None);
+        assert_eq!(words_per_minute("...", 30.0), None);
+    }
+}
@@ -6,6 +6,9 @@
 //! - Flexibility to swap implementations
 //! - Clear API boundaries
 
+use crate::domain::types::{
+    AudioQuality, CaptureDeviceStatus, HistoryOrder, LevelReading, LevelWarning, TimedWord, TranscriptSegment,
+};
 use anyhow::Result;
 use async_channel::Receiver;
 use chrono::{DateTime, Utc};
@@ -28,13 +31,36 @@ pub trait AudioRecording: Send + Sync {
     /// - Optional completion receiver for async notification when recording thread finishes
     fn stop(&self) -> (Vec<f32>, Option<Receiver<()>>);
 
-    /// Get current audio amplitude (0.0 - 1.0 range).
+    /// Current peak and RMS level in dBFS, with ballistics applied.
     ///
-    /// Used for real-time UI visualization of audio levels.
-    fn amplitude(&self) -> f32;
+    /// Used for the level meter; silent when not recording.
+    fn level(&self) -> LevelReading;
 
     /// Check if currently recording.
     fn is_recording(&self) -> bool;
+
+    /// Whether the capture device is still there.
+    ///
+    /// Sources that cannot disappear mid-recording always report `Active`.
+    fn device_status(&self) -> CaptureDeviceStatus {
+        CaptureDeviceStatus::Active
+    }
+
+    /// Clipping or a too quiet input right now, for a warning while recording.
+    fn level_warning(&self) -> Option<LevelWarning> {
+        None
+    }
+
+    /// Level problems of the recording, once stopped.
+    fn audio_quality(&self) -> Option<AudioQuality> {
+        None
+    }
+
+    /// Keep capturing between recordings so each starts with the last
+    /// `secs` before it. Recorders without a pre-roll ignore this.
+    fn start_pre_roll(&self, _secs: f32) -> Result<()> {
+        Ok(())
+    }
 }
 
 /// Speech-to-text transcription abstraction.
@@ -57,6 +83,20 @@ pub trait Transcription: Send + Sync {
 
     /// Load or replace the model from the given path.
     fn load_model(&mut self, path: &Path) -> Result<()>;
+
+    /// Transcribe audio and translate the result to English.
+    ///
+    /// Backends without a translation task return an error.
+    fn translate(&self, _samples: &[f32], _language: &str) -> Result<String> {
+        anyhow::bail!("Переклад не підтримується цим бекендом")
+    }
+
+    /// Transcribe audio into words with timestamps relative to `samples`.
+    ///
+    /// Backends without word timestamps return an error.
+    fn transcribe_words(&self, _samples: &[f32], _language: &str) -> Result<Vec<TimedWord>> {
+        anyhow::bail!("Мітки часу слів не підтримуються цим бекендом")
+    }
 }
 
 /// Voice activity detection abstraction.
@@ -99,7 +139,8 @@ pub trait HistoryRepository: Send + Sync {
     /// Get all entries (most recent first).
     fn entries(&self) -> &[Self::Entry];
 
-    /// Search entries by text content.
+    /// Entries whose text, title or tags contain the words of `query`,
+    /// best match first.
     fn search(&self, query: &str) -> Vec<&Self::Entry>;
 
     /// Remove entries older than max_age_days.
@@ -114,17 +155,122 @@ pub trait HistoryRepository: Send + Sync {
     /// Remove an entry by its ID.
     fn remove(&mut self, id: &str);
 
+    /// Move the entry with the given ID to the trash, from where it can be
+    /// restored until [`purge_trash`](Self::purge_trash) drops it.
+    ///
+    /// Returns `false` if the entry is missing.
+    fn trash(&mut self, id: &str) -> bool;
+
+    /// Put the trashed entry with the given ID back into the history.
+    ///
+    /// Returns `false` if it is not in the trash.
+    fn restore(&mut self, id: &str) -> bool;
+
+    /// Add the entries of another history that are not in this one yet,
+    /// matched by ID and placed by time; trashed ones go to the trash.
+    ///
+    /// Returns the number of entries added.
+    fn import(&mut self, entries: Vec<Self::Entry>) -> usize;
+
+    /// Replace the whole history, trash included, e.g. by a restored
+    /// backup; entries with `deleted_at` go to the trash.
+    ///
+    /// Returns `false` if the history is read-only.
+    fn replace_entries(&mut self, entries: Vec<Self::Entry>) -> bool;
+
+    /// Entries in the trash, most recently deleted first.
+    fn trashed(&self) -> &[Self::Entry];
+
+    /// Drop entries trashed more than max_age_days ago; 0 empties the trash.
+    fn purge_trash(&mut self, max_age_days: u32) -> usize;
+
+    /// All entries in the given order (see
+    /// [`HistoryOrder`](crate::domain::types::HistoryOrder)).
+    fn sorted(&self, order: HistoryOrder) -> Vec<&Self::Entry>;
+
     /// Filter entries by date range (inclusive).
     fn filter_by_date_range(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Vec<&Self::Entry>;
+
+    /// Replace the entries with the given IDs by a single combined entry.
+    ///
+    /// Returns the ID of the merged entry, or `None` if fewer than two
+    /// of the IDs were found or more than one of them has a recording.
+    fn merge(&mut self, ids: &[String], separator: &str) -> Option<String>;
+
+    /// Rename speakers of the entry with the given ID (see
+    /// [`HistoryEntry::rename_speakers`](crate::domain::types::HistoryEntry::rename_speakers)).
+    ///
+    /// Returns `false` if the entry is missing or nothing changed.
+    fn rename_speakers(&mut self, id: &str, renames: &[(String, String)]) -> bool;
+
+    /// Assign a speaker turn of the entry with the given ID to another
+    /// speaker (see
+    /// [`HistoryEntry::reassign_turn`](crate::domain::types::HistoryEntry::reassign_turn)).
+    ///
+    /// Returns `false` if the entry or turn is missing or nothing changed.
+    fn reassign_turn(&mut self, id: &str, index: usize, speaker: &str) -> bool;
+
+    /// Replace the tags of the entry with the given ID (see
+    /// [`HistoryEntry::set_tags`](crate::domain::types::HistoryEntry::set_tags)).
+    ///
+    /// Returns `false` if the entry is missing or nothing changed.
+    fn set_tags(&mut self, id: &str, tags: &[String]) -> bool;
+
+    /// Every tag used in the history, sorted, each once regardless of case.
+    fn all_tags(&self) -> Vec<String>;
+
+    /// Whether changes are refused (kiosk mode).
+    fn is_read_only(&self) -> bool;
+
+    /// Pin or unpin the entry with the given ID; pinned entries are kept
+    /// by [`cleanup_old`](Self::cleanup_old) and [`trim_to_limit`](Self::trim_to_limit).
+    ///
+    /// Returns `false` if the entry is missing or nothing changed.
+    fn set_pinned(&mut self, id: &str, pinned: bool) -> bool;
+
+    /// Replace the text of the entry with the given ID by a corrected one,
+    /// keeping the transcribed text (see
+    /// [`HistoryEntry::edit_text`](crate::domain::types::HistoryEntry::edit_text)).
+    ///
+    /// Returns `false` if the entry is missing or nothing changed.
+    fn set_text(&mut self, id: &str, text: &str) -> bool;
+
+    /// Replace the text of the entry with the given ID by a new
+    /// transcription, keeping the old text as a version (see
+    /// [`HistoryEntry::replace_transcription`](crate::domain::types::HistoryEntry::replace_transcription)).
+    ///
+    /// Returns `false` if the entry is missing or nothing changed.
+    fn replace_transcription(
+        &mut self,
+        id: &str,
+        text: &str,
+        speakers: &[String],
+        segments: &[TranscriptSegment],
+        model: &str,
+    ) -> bool;
+}
+
+/// Automatic gain control abstraction.
+///
+/// Implementors bring the level of captured audio towards a target, so a
+/// quiet microphone still gives the VAD and the transcriber usable input.
+/// The gain adapts from block to block, so blocks must be passed in order.
+pub trait GainControl: Send + Sync {
+    /// Apply the current gain to the next block of samples.
+    fn process(&self, samples: &[f32]) -> Vec<f32>;
+
+    /// Gain applied to the last block, in dB.
+    fn gain_db(&self) -> f32;
+
+    /// Reset internal state for new recording session.
+    fn reset(&self);
 }
 
 /// Audio denoising abstraction.
 ///
 /// Implementors apply noise suppression to audio samples.
-/// Typical use case: RNNoise-based denoising for cleaner STT input.
-///
-/// Only used in tests — production code calls `NnnoiselessDenoiser` directly.
-#[cfg(test)]
+/// Implemented by the RNNoise and DeepFilterNet denoisers; pick one with
+/// `DenoiseSettings::create`.
 pub trait AudioDenoising: Send + Sync {
     /// Apply noise suppression to audio samples.
     ///
@@ -213,8 +359,12 @@ mod tests {
             (self.samples.clone(), None)
         }
 
-        fn amplitude(&self) -> f32 {
-            0.5
+        fn level(&self) -> LevelReading {
+            LevelReading {
+                peak_dbfs: -12.0,
+                rms_dbfs: -18.0,
+                clipped: false,
+            }
         }
 
         fn is_recording(&self) -> bool {
@@ -240,9 +390,10 @@ mod tests {
     }
 
     #[test]
-    fn test_amplitude_range() {
+    fn test_level_range() {
         let recorder = TestRecorder::new(vec![]);
-        let amp = recorder.amplitude();
-        assert!((0.0..=1.0).contains(&amp));
+        let level = recorder.level();
+        assert!(level.rms_dbfs <= level.peak_dbfs && level.peak_dbfs <= 0.0);
+        assert!((0.0..=1.0).contains(&LevelReading::scale(level.peak_dbfs)));
     }
 }
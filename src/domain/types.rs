@@ -3,11 +3,13 @@
 //! This module contains common data structures that are used by multiple
 //! parts of the application to avoid duplication and circular dependencies.
 
+use crate::domain::text_metrics::{truncate_graphemes, word_count, words_per_minute};
 use crate::domain::traits::HistoryRepository;
 use async_channel::Receiver;
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::ops::Range;
 use std::sync::Arc;
 use std::time::Instant;
 use uuid::Uuid;
@@ -33,6 +35,189 @@ pub struct HistoryEntry {
     pub recording_path: Option<String>,
     #[serde(default)]
     pub speakers: Vec<String>,
+    #[serde(default)]
+    pub action_items: Vec<ActionItem>,
+    #[serde(default)]
+    pub speaker_stats: Vec<SpeakerStats>,
+    /// Confidence (0..1) of each speaker label in the text, in text order.
+    /// Empty unless the speakers come from Sortformer.
+    #[serde(default)]
+    pub speaker_confidence: Vec<f32>,
+    /// Title, e.g. from a session template.
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Clipping or a too quiet microphone noticed while recording.
+    #[serde(default)]
+    pub audio_quality: Option<AudioQuality>,
+    /// Favorite: listed first and never removed by the history limits.
+    #[serde(default)]
+    pub pinned: bool,
+    /// Text as transcribed, kept once `text` has been edited by hand.
+    #[serde(default)]
+    pub original_text: Option<String>,
+    /// Model the text was last transcribed with, if known.
+    #[serde(default)]
+    pub model: Option<String>,
+    /// Seconds the transcription took, for the real-time factor in the
+    /// statistics; `None` if not measured.
+    #[serde(default)]
+    pub processing_secs: Option<f32>,
+    /// Texts replaced by re-transcriptions, oldest first.
+    #[serde(default)]
+    pub previous_versions: Vec<TextVersion>,
+    /// Speaker turns of a diarized transcription with their times, as
+    /// transcribed; the text joins them with `[speaker]` labels.
+    #[serde(default)]
+    pub segments: Vec<TranscriptSegment>,
+    /// When the entry was moved to the trash; `None` while it is in the history.
+    #[serde(default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// One speaker turn of a diarized transcription.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TranscriptSegment {
+    pub speaker: String,
+    pub text: String,
+    /// Start time in seconds from the start of the recording
+    pub start: f64,
+    /// End time in seconds
+    pub end: f64,
+}
+
+/// Text of segments with a `[speaker]` label before each, as in history entries.
+pub fn join_segments(segments: &[TranscriptSegment]) -> String {
+    segments
+        .iter()
+        .map(|segment| format!("[{}] {}", segment.speaker, segment.text))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Text of a history entry before a re-transcription replaced it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextVersion {
+    pub text: String,
+    /// Model that produced it, if known
+    #[serde(default)]
+    pub model: Option<String>,
+    pub replaced_at: DateTime<Utc>,
+}
+
+/// Level problems of a recording, noted in its history entry.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AudioQuality {
+    /// Share of the recording that clipped, in percent, when enough to matter
+    pub clipped_percent: Option<f32>,
+    /// Level of the loudest 100 ms (dBFS), when even that was too quiet
+    pub too_quiet_dbfs: Option<f32>,
+}
+
+impl AudioQuality {
+    /// One line for the history, e.g. "Перевантаження у 12% запису".
+    pub fn note(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(percent) = self.clipped_percent {
+            parts.push(format!("Перевантаження у {:.0}% запису", percent));
+        }
+        if let Some(dbfs) = self.too_quiet_dbfs {
+            parts.push(format!("Тихий запис (найгучніше {:.0} дБFS)", dbfs));
+        }
+        parts.join("; ")
+    }
+}
+
+/// Level problem to warn about while recording.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LevelWarning {
+    /// Peaks hit full scale: the input gain is too high
+    Clipping,
+    /// Nothing has reached a usable level for several seconds
+    TooQuiet,
+}
+
+/// Input level for the meter: where the bar stands and whether it clipped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelReading {
+    /// Loudest sample, falling back slowly after a loud moment
+    pub peak_dbfs: f32,
+    /// Average level, falling back quickly
+    pub rms_dbfs: f32,
+    /// Samples hit full scale within the last moment
+    pub clipped: bool,
+}
+
+impl LevelReading {
+    /// Bottom of the meter; anything quieter reads as this
+    pub const FLOOR_DBFS: f32 = -60.0;
+
+    pub const SILENT: Self = Self {
+        peak_dbfs: Self::FLOOR_DBFS,
+        rms_dbfs: Self::FLOOR_DBFS,
+        clipped: false,
+    };
+
+    /// Position of `dbfs` on a meter from [`Self::FLOOR_DBFS`] to 0 dBFS, 0.0 - 1.0.
+    pub fn scale(dbfs: f32) -> f32 {
+        ((dbfs - Self::FLOOR_DBFS) / -Self::FLOOR_DBFS).clamp(0.0, 1.0)
+    }
+}
+
+impl Default for LevelReading {
+    fn default() -> Self {
+        Self::SILENT
+    }
+}
+
+/// Talk time and number of turns of one speaker in a diarized recording.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerStats {
+    pub speaker: String,
+    pub talk_secs: f64,
+    /// Times the speaker took the floor; consecutive segments count once.
+    pub turns: usize,
+}
+
+/// Whether a follow-up is something to do or something already agreed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ActionItemKind {
+    Action,
+    Decision,
+}
+
+/// An action item or decision extracted from a meeting transcript.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub kind: ActionItemKind,
+    /// The sentence the item was taken from.
+    pub text: String,
+    #[serde(default)]
+    pub assignee: Option<String>,
+    /// Deadline as spoken, e.g. "до п'ятниці".
+    #[serde(default)]
+    pub due: Option<String>,
+}
+
+impl ActionItem {
+    /// Single-line form used in the result view and exports.
+    pub fn formatted(&self) -> String {
+        let label = match self.kind {
+            ActionItemKind::Action => "Завдання",
+            ActionItemKind::Decision => "Рішення",
+        };
+        let details: Vec<&str> = [self.assignee.as_deref(), self.due.as_deref()]
+            .into_iter()
+            .flatten()
+            .collect();
+        if details.is_empty() {
+            format!("{}: {}", label, self.text)
+        } else {
+            format!("{}: {} [{}]", label, self.text, details.join(", "))
+        }
+    }
 }
 
 impl HistoryEntry {
@@ -45,6 +230,19 @@ impl HistoryEntry {
             language,
             recording_path: None,
             speakers: Vec::new(),
+            action_items: Vec::new(),
+            speaker_stats: Vec::new(),
+            speaker_confidence: Vec::new(),
+            title: None,
+            tags: Vec::new(),
+            audio_quality: None,
+            pinned: false,
+            original_text: None,
+            model: None,
+            processing_secs: None,
+            previous_versions: Vec::new(),
+            segments: Vec::new(),
+            deleted_at: None,
         }
     }
 
@@ -63,18 +261,35 @@ impl HistoryEntry {
             language,
             recording_path,
             speakers,
+            action_items: Vec::new(),
+            speaker_stats: Vec::new(),
+            speaker_confidence: Vec::new(),
+            title: None,
+            tags: Vec::new(),
+            audio_quality: None,
+            pinned: false,
+            original_text: None,
+            model: None,
+            processing_secs: None,
+            previous_versions: Vec::new(),
+            segments: Vec::new(),
+            deleted_at: None,
         }
     }
 
-    /// Returns a preview of the text (first 80 chars, single line)
+    /// Returns a preview of the text (first 80 graphemes, single line)
     pub fn preview(&self) -> String {
-        let text = self.text.replace('\n', " ");
-        let chars: Vec<char> = text.chars().collect();
-        if chars.len() > 80 {
-            format!("{}...", chars[..80].iter().collect::<String>())
-        } else {
-            text
-        }
+        truncate_graphemes(&self.text.replace('\n', " "), 80)
+    }
+
+    /// Number of words in the text.
+    pub fn word_count(&self) -> usize {
+        word_count(&self.text)
+    }
+
+    /// Speaking rate over the recording, if it has words and a duration.
+    pub fn words_per_minute(&self) -> Option<f32> {
+        words_per_minute(&self.text, self.duration_secs)
     }
 
     /// Returns formatted timestamp in local time (YYYY-MM-DD HH:MM)
@@ -89,6 +304,381 @@ impl HistoryEntry {
         let secs = (self.duration_secs % 60.0).floor() as u32;
         format!("{:02}:{:02}", mins, secs)
     }
+
+    /// Combine several entries into one, ordered by timestamp (oldest first).
+    ///
+    /// Texts are joined with `separator`, durations are summed, speakers are
+    /// merged without duplicates. The merged entry takes the earliest timestamp
+    /// and a new ID and keeps the recording path of the one entry that has it.
+    /// Returns `None` for an empty input, and if more than one entry has a
+    /// recording: the merged entry could keep only one of them.
+    pub fn merged(entries: &[&HistoryEntry], separator: &str) -> Option<Self> {
+        let mut sorted: Vec<&HistoryEntry> = entries.to_vec();
+        sorted.sort_by_key(|e| e.timestamp);
+        let first = sorted.first()?;
+
+        let text = sorted
+            .iter()
+            .map(|e| e.text.as_str())
+            .collect::<Vec<_>>()
+            .join(separator);
+        let duration_secs = sorted.iter().map(|e| e.duration_secs).sum();
+
+        let mut speakers: Vec<String> = Vec::new();
+        for speaker in sorted.iter().flat_map(|e| e.speakers.iter()) {
+            if !speakers.contains(speaker) {
+                speakers.push(speaker.clone());
+            }
+        }
+
+        // Confidence only stays matched to the labels if every entry has it
+        let aligned = sorted
+            .iter()
+            .all(|e| speaker_label_spans(&e.text, &e.speakers).len() == e.speaker_confidence.len());
+        let speaker_confidence = if aligned {
+            sorted
+                .iter()
+                .flat_map(|e| e.speaker_confidence.iter().copied())
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let mut tags: Vec<String> = Vec::new();
+        for tag in sorted.iter().flat_map(|e| e.tags.iter()) {
+            if !tags.contains(tag) {
+                tags.push(tag.clone());
+            }
+        }
+
+        let mut recordings = sorted.iter().filter_map(|e| e.recording_path.clone());
+        let recording_path = recordings.next();
+        if recordings.next().is_some() {
+            return None;
+        }
+
+        Some(Self {
+            id: Uuid::new_v4().to_string(),
+            text,
+            timestamp: first.timestamp,
+            duration_secs,
+            language: first.language.clone(),
+            recording_path,
+            speakers,
+            action_items: sorted.iter().flat_map(|e| e.action_items.iter().cloned()).collect(),
+            speaker_stats: sum_speaker_stats(sorted.iter().flat_map(|e| e.speaker_stats.iter().cloned())),
+            speaker_confidence,
+            title: first.title.clone(),
+            tags,
+            audio_quality: None,
+            pinned: sorted.iter().any(|e| e.pinned),
+            original_text: None,
+            model: first
+                .model
+                .clone()
+                .filter(|model| sorted.iter().all(|e| e.model.as_ref() == Some(model))),
+            processing_secs: sorted.iter().map(|e| e.processing_secs).sum(),
+            previous_versions: Vec::new(),
+            segments: Vec::new(),
+        })
+    }
+
+    /// Rename speakers in the text, the speaker list, talk-time statistics
+    /// and action item assignees.
+    ///
+    /// `renames` maps old labels to new ones and is applied in one pass, so
+    /// two speakers can swap names. Speakers that end up with the same name
+    /// are listed once. Returns whether anything changed.
+    pub fn rename_speakers(&mut self, renames: &[(String, String)]) -> bool {
+        let lookup = |name: &str| {
+            renames
+                .iter()
+                .find(|(old, _)| old == name)
+                .map_or(name, |(_, new)| new.as_str())
+                .to_string()
+        };
+
+        let text = rename_speaker_labels(&self.text, renames);
+        let mut speakers: Vec<String> = Vec::new();
+        for speaker in self.speakers.iter().map(|s| lookup(s)) {
+            if !speakers.contains(&speaker) {
+                speakers.push(speaker);
+            }
+        }
+        let speaker_stats = sum_speaker_stats(self.speaker_stats.iter().map(|stats| SpeakerStats {
+            speaker: lookup(&stats.speaker),
+            ..stats.clone()
+        }));
+        let mut changed = text != self.text || speakers != self.speakers || speaker_stats != self.speaker_stats;
+        self.text = text;
+        self.speakers = speakers;
+        self.speaker_stats = speaker_stats;
+        for segment in &mut self.segments {
+            let renamed = lookup(&segment.speaker);
+            if renamed != segment.speaker {
+                segment.speaker = renamed;
+                changed = true;
+            }
+        }
+
+        for item in &mut self.action_items {
+            if let Some(assignee) = item.assignee.as_mut() {
+                let renamed = lookup(assignee);
+                if renamed != *assignee {
+                    *assignee = renamed;
+                    changed = true;
+                }
+            }
+        }
+        changed
+    }
+
+    /// Speaker turns of the text as (speaker, spoken text), in text order.
+    ///
+    /// Text before the first label is not part of any turn.
+    pub fn speaker_turns(&self) -> Vec<(String, String)> {
+        let spans = speaker_label_spans(&self.text, &self.speakers);
+        spans
+            .iter()
+            .enumerate()
+            .map(|(i, span)| {
+                let end = spans.get(i + 1).map_or(self.text.len(), |next| next.start);
+                let speaker = self.text[span.start + 1..span.end - 1].to_string();
+                (speaker, self.text[span.end..end].trim().to_string())
+            })
+            .collect()
+    }
+
+    /// Assign turn `index` (see [`speaker_turns`](Self::speaker_turns)) to `speaker`.
+    ///
+    /// The turn counts as checked afterwards, with full confidence. Talk-time
+    /// statistics are left as they are. The matching segment follows while
+    /// the text has one turn per segment. Returns whether anything changed.
+    pub fn reassign_turn(&mut self, index: usize, speaker: &str) -> bool {
+        let spans = speaker_label_spans(&self.text, &self.speakers);
+        let segments_aligned = spans.len() == self.segments.len();
+        let Some(span) = spans.into_iter().nth(index) else {
+            return false;
+        };
+        let mut changed = false;
+        if self.text[span.start + 1..span.end - 1] != *speaker {
+            self.text.replace_range(span, &format!("[{}]", speaker));
+            if !self.speakers.iter().any(|s| s == speaker) {
+                self.speakers.push(speaker.to_string());
+            }
+            changed = true;
+        }
+        if let Some(confidence) = self.speaker_confidence.get_mut(index) {
+            changed |= *confidence != 1.0;
+            *confidence = 1.0;
+        }
+        if let Some(segment) = self.segments.get_mut(index).filter(|_| segments_aligned) {
+            if segment.speaker != speaker {
+                segment.speaker = speaker.to_string();
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// Replace the tags, trimmed and without empty or repeated ones
+    /// (compared ignoring case). Returns whether anything changed.
+    pub fn set_tags(&mut self, tags: &[String]) -> bool {
+        let mut cleaned: Vec<String> = Vec::new();
+        for tag in tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            if !cleaned.iter().any(|c| c.to_lowercase() == tag.to_lowercase()) {
+                cleaned.push(tag.to_string());
+            }
+        }
+        let changed = cleaned != self.tags;
+        self.tags = cleaned;
+        changed
+    }
+
+    /// Replace the text with a hand-corrected one, keeping the transcribed
+    /// text in `original_text`; editing back to it drops the copy.
+    /// Returns whether anything changed.
+    pub fn edit_text(&mut self, text: &str) -> bool {
+        if text == self.text {
+            return false;
+        }
+        let original = self.original_text.take().unwrap_or_else(|| self.text.clone());
+        if text != original {
+            self.original_text = Some(original);
+        }
+        self.text = text.to_string();
+        true
+    }
+
+    /// Replace the text by a new transcription of the recording with
+    /// `model`, keeping the current text, corrected or not, as a version.
+    ///
+    /// Speaker labels and segments come from the new transcription, so the
+    /// confidence of the old labels is dropped. Returns whether anything
+    /// changed.
+    pub fn replace_transcription(
+        &mut self,
+        text: &str,
+        speakers: &[String],
+        segments: &[TranscriptSegment],
+        model: &str,
+    ) -> bool {
+        if text == self.text && self.model.as_deref() == Some(model) {
+            return false;
+        }
+        self.previous_versions.push(TextVersion {
+            text: std::mem::replace(&mut self.text, text.to_string()),
+            model: self.model.replace(model.to_string()),
+            replaced_at: Utc::now(),
+        });
+        self.original_text = None;
+        self.speakers = speakers.to_vec();
+        self.segments = segments.to_vec();
+        self.speaker_confidence.clear();
+        // The time measured was for the old model
+        self.processing_secs = None;
+        true
+    }
+
+    /// Whether the entry has `tag`, ignoring case.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.to_lowercase() == tag.to_lowercase())
+    }
+}
+
+/// Order of history entries chosen in the history dialog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HistoryOrder {
+    #[default]
+    Newest,
+    Oldest,
+    /// Longest recordings first
+    Longest,
+    /// By language code, newest first within a language
+    Language,
+    /// Most speakers first, newest first among equals
+    Speakers,
+}
+
+impl HistoryOrder {
+    /// All orders, in the order they are listed to the user.
+    pub const ALL: [Self; 5] = [
+        Self::Newest,
+        Self::Oldest,
+        Self::Longest,
+        Self::Language,
+        Self::Speakers,
+    ];
+
+    /// Name shown in the history dialog.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Newest => "Спершу нові",
+            Self::Oldest => "Спершу старі",
+            Self::Longest => "Найдовші",
+            Self::Language => "За мовою",
+            Self::Speakers => "За кількістю мовців",
+        }
+    }
+
+    /// Sort `entries` in this order; the sort is stable.
+    pub fn sort(self, entries: &mut [&HistoryEntry]) {
+        match self {
+            Self::Newest => entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp)),
+            Self::Oldest => entries.sort_by(|a, b| a.timestamp.cmp(&b.timestamp)),
+            Self::Longest => entries.sort_by(|a, b| b.duration_secs.total_cmp(&a.duration_secs)),
+            Self::Language => {
+                entries.sort_by(|a, b| a.language.cmp(&b.language).then_with(|| b.timestamp.cmp(&a.timestamp)))
+            }
+            Self::Speakers => entries.sort_by(|a, b| {
+                b.speakers
+                    .len()
+                    .cmp(&a.speakers.len())
+                    .then_with(|| b.timestamp.cmp(&a.timestamp))
+            }),
+        }
+    }
+}
+
+/// Tags typed as a comma-separated list, e.g. "Клієнт А, звіт".
+pub fn parse_tags(text: &str) -> Vec<String> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Byte ranges of the `[Speaker]` labels in a transcript, in text order.
+///
+/// Only labels of `speakers` count, so markers like `[перекриття]` are skipped.
+pub fn speaker_label_spans(text: &str, speakers: &[String]) -> Vec<Range<usize>> {
+    let mut spans = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = text[offset..].find('[').map(|i| offset + i) {
+        let Some(close) = text[open..].find(']').map(|i| open + i) else {
+            break;
+        };
+        let label = &text[open + 1..close];
+        if label.contains('[') {
+            offset = open + 1;
+            continue;
+        }
+        if speakers.iter().any(|s| s == label) {
+            spans.push(open..close + 1);
+        }
+        offset = close + 1;
+    }
+    spans
+}
+
+/// Add up statistics of the same speaker, keeping first-seen order.
+pub fn sum_speaker_stats(stats: impl IntoIterator<Item = SpeakerStats>) -> Vec<SpeakerStats> {
+    let mut summed: Vec<SpeakerStats> = Vec::new();
+    for stats in stats {
+        match summed.iter_mut().find(|s| s.speaker == stats.speaker) {
+            Some(existing) => {
+                existing.talk_secs += stats.talk_secs;
+                existing.turns += stats.turns;
+            }
+            None => summed.push(stats),
+        }
+    }
+    summed
+}
+
+/// Replace `[Speaker]` labels in a transcript according to `renames`.
+///
+/// Only whole bracketed labels are replaced, never the same words inside
+/// spoken text. All renames apply at once, so `A -> B` and `B -> A` swap.
+pub fn rename_speaker_labels(text: &str, renames: &[(String, String)]) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(open) = rest.find('[') {
+        result.push_str(&rest[..open]);
+        let after_open = &rest[open + 1..];
+        let Some(close) = after_open.find(']') else {
+            rest = &rest[open..];
+            break;
+        };
+        let label = &after_open[..close];
+        if label.contains('[') {
+            result.push('[');
+            rest = after_open;
+            continue;
+        }
+        match renames.iter().find(|(old, _)| old == label) {
+            Some((_, new)) => {
+                result.push('[');
+                result.push_str(new);
+                result.push(']');
+            }
+            None => result.push_str(&rest[open..open + close + 2]),
+        }
+        rest = &after_open[close + 1..];
+    }
+    result.push_str(rest);
+    result
 }
 
 /// Application state for recording modes.
@@ -97,11 +687,25 @@ impl HistoryEntry {
 /// Used by both UI and recording handlers.
 #[derive(Clone, Copy, PartialEq, Debug)]
 pub enum AppState {
+    /// STT model is still loading at startup; a start request is queued.
+    Loading,
     Idle,
     Recording,
     Processing,
 }
 
+/// State of the capture device while recording.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum CaptureDeviceStatus {
+    /// Capturing from the device the recording started with
+    #[default]
+    Active,
+    /// The device was lost; capture continues on the named one
+    Switched(String),
+    /// The device was lost and no other is available; capture is paused
+    Lost,
+}
+
 /// Result from stopping conference recording.
 ///
 /// Contains audio samples from both microphone and system loopback channels,
@@ -149,11 +753,22 @@ impl ConferenceRecording {
 /// Segment of audio ready for transcription.
 ///
 /// Produced by `ContinuousRecorder` during automatic segmentation
-/// and consumed by the UI layer for parallel transcription.
+/// and consumed by the UI layer for parallel transcription. Samples are
+/// shared, so cloning a segment never copies the audio.
 #[derive(Debug, Clone)]
 pub struct AudioSegment {
-    pub samples: Vec<f32>,
+    pub samples: Arc<[f32]>,
+    /// Position of the first sample within the whole recording.
+    pub start_sample: usize,
     pub start_time: Instant,
     pub end_time: Instant,
     pub segment_id: usize,
 }
+
+/// Recognized word with its position in the audio, in seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimedWord {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+}
@@ -0,0 +1,253 @@
+//! Export history entries as Anki flashcards.
+//!
+//! Produces a tab-separated file with Anki's import headers, so it can be
+//! opened with File → Import without any field mapping. Each entry becomes
+//! one note whose front and back are rendered from templates.
+//!
+//! Template placeholders:
+//! - `{text}` — the whole transcription
+//! - `{term}` / `{definition}` — the text split at the first separator
+//!   (` — `, ` – `, ` - `, `: ` or a line break), for vocabulary dictations
+//!   like "яблуко — apple"; without a separator `{term}` is the whole text
+//! - `{date}`, `{language}`, `{duration}`
+//! - `{tags}` — the entry's tags, comma-separated
+//!
+//! Unknown placeholders are left as-is. Templates may contain HTML; the
+//! substituted values are escaped.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::HistoryEntry;
+
+/// Separators between a term and its definition, in order of preference at equal position.
+const TERM_SEPARATORS: [&str; 5] = [" — ", " – ", " - ", ": ", "\n"];
+
+/// Tag added to every exported note.
+const ANKI_TAG: &str = "s2t";
+
+/// Front/back templates for generated cards.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnkiTemplate {
+    pub front: String,
+    pub back: String,
+}
+
+impl Default for AnkiTemplate {
+    fn default() -> Self {
+        Self {
+            front: "{term}".to_string(),
+            back: "{definition}".to_string(),
+        }
+    }
+}
+
+/// Split a vocabulary dictation into (term, definition) at the first separator.
+pub fn split_term(text: &str) -> (&str, &str) {
+    let text = text.trim();
+    let split = TERM_SEPARATORS
+        .iter()
+        .filter_map(|sep| text.find(sep).map(|pos| (pos, sep.len())))
+        .min_by_key(|(pos, _)| *pos);
+
+    match split {
+        Some((pos, len)) => (text[..pos].trim(), text[pos + len..].trim()),
+        None => (text, ""),
+    }
+}
+
+/// Render a card template for one entry.
+pub fn render_template(template: &str, entry: &HistoryEntry) -> String {
+    let (term, definition) = split_term(&entry.text);
+    let mut out = String::with_capacity(template.len() + entry.text.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let Some(close) = after.find('}') else {
+            out.push_str(&rest[open..]);
+            return out;
+        };
+
+        let value = match &after[..close] {
+            "text" => Some(entry.text.trim().to_string()),
+            "term" => Some(term.to_string()),
+            "definition" => Some(definition.to_string()),
+            "date" => Some(
+                entry
+                    .timestamp
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d")
+                    .to_string(),
+            ),
+            "language" => Some(entry.language.clone()),
+            "duration" => Some(entry.formatted_duration()),
+            "tags" => Some(entry.tags.join(", ")),
+            _ => None,
+        };
+        match value {
+            Some(value) => out.push_str(&escape_field(&value)),
+            None => out.push_str(&rest[open..open + close + 2]),
+        }
+        rest = &after[close + 1..];
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Escape a value for an HTML-enabled Anki TSV field.
+fn escape_field(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '\n' => escaped.push_str("<br>"),
+            '\r' => {}
+            '\t' => escaped.push(' '),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Format entries as an Anki-importable TSV.
+///
+/// Returns the file contents and the number of notes. Entries whose front
+/// renders empty are skipped, since Anki rejects notes with an empty first field.
+pub fn format_anki_tsv(entries: &[&HistoryEntry], template: &AnkiTemplate) -> (String, usize) {
+    let mut out = String::from("#separator:tab\n#html:true\n#columns:Front\tBack\tTags\n#tags column:3\n");
+    let mut count = 0;
+
+    for entry in entries {
+        // Raw template text may still contain tabs or line breaks
+        let front = render_template(&template.front, entry).replace(['\t', '\n'], " ");
+        if front.trim().is_empty() {
+            continue;
+        }
+        let back = render_template(&template.back, entry).replace(['\t', '\n'], " ");
+        let mut tags = format!("{} {}", ANKI_TAG, entry.language.replace(char::is_whitespace, "_"));
+        // Anki tags are separated by spaces
+        for tag in &entry.tags {
+            tags.push(' ');
+            tags.push_str(&tag.replace(char::is_whitespace, "_"));
+        }
+
+        out.push_str(&format!("{}\t{}\t{}\n", front, back, tags.trim_end()));
+        count += 1;
+    }
+
+    (out, count)
+}
+
+/// Export history entries to an Anki TSV file. Returns the number of notes written.
+pub fn export_to_anki(entries: &[&HistoryEntry], path: &Path, template: &AnkiTemplate) -> Result<usize> {
+    let (content, count) = format_anki_tsv(entries, template);
+    fs::write(path, content).with_context(|| format!("Не вдалося записати файл: {}", path.display()))?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn entry(text: &str) -> HistoryEntry {
+        HistoryEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            text: text.to_string(),
+            timestamp: Utc.with_ymd_and_hms(2025, 3, 15, 12, 0, 0).unwrap(),
+            duration_secs: 4.0,
+            language: "uk".to_string(),
+            recording_path: None,
+            speakers: Vec::new(),
+            action_items: Vec::new(),
+            speaker_stats: Vec::new(),
+            speaker_confidence: Vec::new(),
+            title: None,
+            tags: Vec::new(),
+            audio_quality: None,
+            pinned: false,
+            original_text: None,
+            model: None,
+            processing_secs: None,
+            previous_versions: Vec::new(),
+            segments: Vec::new(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_split_term() {
+        assert_eq!(split_term("яблуко — apple"), ("яблуко", "apple"));
+        assert_eq!(split_term("кіт - cat, a small animal"), ("кіт", "cat, a small animal"));
+        assert_eq!(
+            split_term("слово: визначення - деталь"),
+            ("слово", "визначення - деталь")
+        );
+        assert_eq!(split_term("перший рядок\nдругий"), ("перший рядок", "другий"));
+        assert_eq!(split_term("  просто текст "), ("просто текст", ""));
+    }
+
+    #[test]
+    fn test_render_template_placeholders() {
+        let e = entry("яблуко — apple");
+        assert_eq!(render_template("<b>{term}</b>", &e), "<b>яблуко</b>");
+        assert_eq!(
+            render_template("{definition} ({language}, {duration})", &e),
+            "apple (uk, 00:04)"
+        );
+        assert_eq!(render_template("{unknown} {text", &e), "{unknown} {text");
+    }
+
+    #[test]
+    fn test_render_template_escapes_values() {
+        let e = entry("a < b & c\nd");
+        assert_eq!(render_template("{text}", &e), "a &lt; b &amp; c<br>d");
+    }
+
+    #[test]
+    fn test_format_anki_tsv() {
+        let first = entry("яблуко — apple");
+        let blank = entry("   ");
+        let second = entry("груша\tpear");
+        let (tsv, count) = format_anki_tsv(&[&first, &blank, &second], &AnkiTemplate::default());
+
+        assert_eq!(count, 2);
+        let lines: Vec<&str> = tsv.lines().collect();
+        assert_eq!(lines[0], "#separator:tab");
+        assert_eq!(lines[2], "#columns:Front\tBack\tTags");
+        assert_eq!(lines[4], "яблуко\tapple\ts2t uk");
+        assert_eq!(lines[5], "груша pear\t\ts2t uk");
+        assert_eq!(lines.len(), 6);
+    }
+
+    #[test]
+    fn test_entry_tags_become_anki_tags() {
+        let mut e = entry("кіт — cat");
+        e.tags = vec!["Клієнт А".to_string(), "тварини".to_string()];
+        assert_eq!(render_template("{tags}", &e), "Клієнт А, тварини");
+
+        let (tsv, _) = format_anki_tsv(&[&e], &AnkiTemplate::default());
+        assert!(tsv.ends_with("кіт\tcat\ts2t uk Клієнт_А тварини\n"));
+    }
+
+    #[test]
+    fn test_export_to_anki_writes_file() {
+        let e = entry("сонце — sun");
+        let path = std::env::temp_dir().join(format!("s2t_anki_{}.txt", uuid::Uuid::new_v4()));
+        let template = AnkiTemplate {
+            front: "{definition}".to_string(),
+            back: "{term}".to_string(),
+        };
+
+        assert_eq!(export_to_anki(&[&e], &path, &template).unwrap(), 1);
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.ends_with("sun\tсонце\ts2t uk\n"));
+        let _ = fs::remove_file(&path);
+    }
+}
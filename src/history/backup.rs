@@ -0,0 +1,173 @@
+//! Rotating backups of the history store, made before a save at most once
+//! an hour.
+//!
+//! A backup is a copy of the store file in `backups/` next to it, named
+//! after the store with the UTC time inserted
+//! (`history-20250314-120000.json`), so the names sort by age. A store that
+//! no longer loads is not copied: a truncated file must not push the good
+//! backups out. Saves come after every dictation, so without the hourly
+//! limit a busy session would rotate all the backups out within minutes.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::HistoryStore;
+use crate::app::config::set_owner_only_permissions;
+
+/// Time format inside backup file names
+const TIME_FORMAT: &str = "%Y%m%d-%H%M%S";
+
+/// A new backup is made only when the newest one is older than this
+const MIN_BACKUP_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Copy the store to a new backup, unless the newest is under an hour old,
+/// and drop all but the newest `keep`.
+///
+/// `readable` tells whether the store loaded; the caller has just loaded
+/// it anyway. Nothing is done if `keep` is 0 or the store is missing or
+/// unreadable.
+pub fn backup_store(store: &dyn HistoryStore, keep: usize, readable: bool) -> Result<()> {
+    let path = store.path();
+    if keep == 0 || !path.is_file() || !readable {
+        return Ok(());
+    }
+    let backups = list_backups(store);
+    let recent = backups
+        .first()
+        .is_some_and(|(_, time)| (Utc::now() - *time).to_std().unwrap_or_default() < MIN_BACKUP_INTERVAL);
+    if !recent {
+        let dir = backup_dir(path);
+        fs::create_dir_all(&dir).with_context(|| format!("Не вдалося створити директорію: {}", dir.display()))?;
+        let (prefix, suffix) = name_parts(path);
+        let target = dir.join(format!("{}{}{}", prefix, Utc::now().format(TIME_FORMAT), suffix));
+        fs::copy(path, &target)
+            .with_context(|| format!("Не вдалося створити резервну копію історії: {}", target.display()))?;
+        set_owner_only_permissions(&target)?;
+    }
+
+    for (old, _) in list_backups(store).into_iter().skip(keep) {
+        if let Err(e) = fs::remove_file(&old) {
+            eprintln!("Не вдалося видалити стару резервну копію {}: {}", old.display(), e);
+        }
+    }
+    Ok(())
+}
+
+/// Backups of the store with the times they were made, newest first.
+pub fn list_backups(store: &dyn HistoryStore) -> Vec<(PathBuf, DateTime<Utc>)> {
+    let path = store.path();
+    let (prefix, suffix) = name_parts(path);
+    let Ok(dir) = fs::read_dir(backup_dir(path)) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<(PathBuf, DateTime<Utc>)> = dir
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let time = name.strip_prefix(&prefix)?.strip_suffix(&suffix)?;
+            let time = NaiveDateTime::parse_from_str(time, TIME_FORMAT).ok()?;
+            Some((entry.path(), time.and_utc()))
+        })
+        .collect();
+    backups.sort_by(|a, b| b.1.cmp(&a.1));
+    backups
+}
+
+fn backup_dir(store_path: &Path) -> PathBuf {
+    store_path.parent().unwrap_or(Path::new(".")).join("backups")
+}
+
+/// The parts of a backup name around the time: `history.json.enc` gives
+/// `history-` and `.json.enc`.
+fn name_parts(store_path: &Path) -> (String, String) {
+    let name = store_path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    match name.split_once('.') {
+        Some((stem, extension)) => (format!("{}-", stem), format!(".{}", extension)),
+        None => (format!("{}-", name), String::new()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{create_store_in, HistoryBackend, HistoryEntry};
+
+    #[test]
+    fn test_backups_rotate_and_skip_broken_store() {
+        let dir = std::env::temp_dir().join("s2t_test_history_backups");
+        let _ = fs::remove_dir_all(&dir);
+        let store = create_store_in(HistoryBackend::Json, &dir, &dir);
+
+        // Nothing to back up yet
+        backup_store(store.as_ref(), 2, true).unwrap();
+        assert!(list_backups(store.as_ref()).is_empty());
+
+        store
+            .save(&[HistoryEntry::new("Запис".to_string(), 3.0, "uk".to_string())])
+            .unwrap();
+        backup_store(store.as_ref(), 2, true).unwrap();
+        let backups = list_backups(store.as_ref());
+        assert_eq!(backups.len(), 1);
+        let name = backups[0].0.file_name().unwrap().to_string_lossy().into_owned();
+        assert!(name.starts_with("history-") && name.ends_with(".json"), "{}", name);
+        assert_eq!(
+            crate::history::read_history_file(&backups[0].0).unwrap()[0].text,
+            "Запис"
+        );
+
+        // The next save within the hour makes no new backup
+        backup_store(store.as_ref(), 2, true).unwrap();
+        assert_eq!(list_backups(store.as_ref()), backups);
+
+        // Older backups beyond the limit are dropped
+        for time in ["20240101-000000", "20240102-000000"] {
+            fs::copy(
+                &backups[0].0,
+                backup_dir(store.path()).join(format!("history-{}.json", time)),
+            )
+            .unwrap();
+        }
+        let backups = list_backups(store.as_ref());
+        assert_eq!(backups.len(), 3);
+        assert!(backups.windows(2).all(|pair| pair[0].1 > pair[1].1));
+        backup_store(store.as_ref(), 1, true).unwrap();
+        let backups = list_backups(store.as_ref());
+        assert_eq!(backups.len(), 1);
+        assert!(!backups[0].0.file_name().unwrap().to_string_lossy().contains("2024"));
+
+        // Once the newest backup is over an hour old, a new one is made
+        let old = backup_dir(store.path()).join("history-20240103-000000.json");
+        fs::rename(&backups[0].0, &old).unwrap();
+        backup_store(store.as_ref(), 2, true).unwrap();
+        let backups = list_backups(store.as_ref());
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[1].0, old);
+
+        // A truncated store is not backed up
+        fs::rename(&backups[0].0, &old).unwrap();
+        fs::write(store.path(), "{\"entries\": [").unwrap();
+        let before = list_backups(store.as_ref());
+        backup_store(store.as_ref(), 2, store.load().is_ok()).unwrap();
+        assert_eq!(list_backups(store.as_ref()), before);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_name_parts() {
+        assert_eq!(
+            name_parts(Path::new("/data/history.json.enc")),
+            ("history-".to_string(), ".json.enc".to_string())
+        );
+        assert_eq!(
+            name_parts(Path::new("history.sqlite")),
+            ("history-".to_string(), ".sqlite".to_string())
+        );
+    }
+}
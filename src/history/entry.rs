@@ -3,6 +3,10 @@ pub use crate::domain::types::HistoryEntry;
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::types::{
+        join_segments, parse_tags, rename_speaker_labels, ActionItem, ActionItemKind, AudioQuality, SpeakerStats,
+        TranscriptSegment,
+    };
     use chrono::TimeZone;
     use chrono::Utc;
 
@@ -26,6 +30,23 @@ mod tests {
         assert_eq!(long_entry.preview().len(), 83); // 80 + "..."
     }
 
+    #[test]
+    fn test_history_entry_preview_counts_graphemes() {
+        // Stress marks are combining characters and must not eat into the limit
+        let long_text = "за\u{301}мок ".repeat(20);
+        let entry = HistoryEntry::new(long_text, 5.0, "uk".to_string());
+        let preview = entry.preview();
+        assert!(preview.ends_with("..."));
+        assert_eq!(preview.trim_end_matches("...").chars().count(), 80 + 14);
+    }
+
+    #[test]
+    fn test_history_entry_word_stats() {
+        let entry = HistoryEntry::new("Обʼєкт п'ять — м’ята.".to_string(), 30.0, "uk".to_string());
+        assert_eq!(entry.word_count(), 3);
+        assert_eq!(entry.words_per_minute(), Some(6.0));
+    }
+
     #[test]
     fn test_history_entry_preview_multiline() {
         let entry = HistoryEntry::new("Line 1\nLine 2\nLine 3".to_string(), 5.0, "uk".to_string());
@@ -56,6 +77,20 @@ mod tests {
         assert_eq!(entry.duration_secs, 120.0);
     }
 
+    #[test]
+    fn test_audio_quality_note() {
+        let clipped = AudioQuality {
+            clipped_percent: Some(12.4),
+            too_quiet_dbfs: None,
+        };
+        assert_eq!(clipped.note(), "Перевантаження у 12% запису");
+        let quiet = AudioQuality {
+            clipped_percent: None,
+            too_quiet_dbfs: Some(-47.6),
+        };
+        assert_eq!(quiet.note(), "Тихий запис (найгучніше -48 дБFS)");
+    }
+
     #[test]
     fn test_formatted_timestamp_contains_date() {
         let entry = HistoryEntry {
@@ -66,10 +101,289 @@ mod tests {
             language: "uk".to_string(),
             recording_path: None,
             speakers: Vec::new(),
+            action_items: Vec::new(),
+            speaker_stats: Vec::new(),
+            speaker_confidence: Vec::new(),
+            title: None,
+            tags: Vec::new(),
+            audio_quality: None,
+            pinned: false,
+            original_text: None,
+            model: None,
+            processing_secs: None,
+            previous_versions: Vec::new(),
+            segments: Vec::new(),
+            deleted_at: None,
         };
         let formatted = entry.formatted_timestamp();
         // The exact output depends on local timezone, but should contain a date pattern
         assert!(formatted.contains("2025"));
         assert!(formatted.contains("03") || formatted.contains("3"));
     }
+
+    fn entry_at(text: &str, hour: u32, duration_secs: f32, speakers: &[&str]) -> HistoryEntry {
+        HistoryEntry {
+            id: uuid::Uuid::new_v4().to_string(),
+            text: text.to_string(),
+            timestamp: Utc.with_ymd_and_hms(2025, 3, 15, hour, 0, 0).unwrap(),
+            duration_secs,
+            language: "uk".to_string(),
+            recording_path: None,
+            speakers: speakers.iter().map(|s| s.to_string()).collect(),
+            action_items: Vec::new(),
+            speaker_stats: Vec::new(),
+            speaker_confidence: Vec::new(),
+            title: None,
+            tags: Vec::new(),
+            audio_quality: None,
+            pinned: false,
+            original_text: None,
+            model: None,
+            processing_secs: None,
+            previous_versions: Vec::new(),
+            segments: Vec::new(),
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_merged_orders_by_timestamp() {
+        let later = entry_at("друге", 12, 5.0, &[]);
+        let earlier = entry_at("перше", 10, 3.0, &[]);
+
+        let merged = HistoryEntry::merged(&[&later, &earlier], "\n\n").unwrap();
+        assert_eq!(merged.text, "перше\n\nдруге");
+        assert_eq!(merged.timestamp, earlier.timestamp);
+        assert_eq!(merged.duration_secs, 8.0);
+        assert_ne!(merged.id, earlier.id);
+        assert_ne!(merged.id, later.id);
+    }
+
+    #[test]
+    fn test_merged_deduplicates_speakers() {
+        let a = entry_at("a", 10, 1.0, &["Ви", "Учасник"]);
+        let b = entry_at("b", 11, 1.0, &["Учасник", "Спікер 3"]);
+        let merged = HistoryEntry::merged(&[&a, &b], " ").unwrap();
+        assert_eq!(merged.speakers, vec!["Ви", "Учасник", "Спікер 3"]);
+    }
+
+    #[test]
+    fn test_merged_refuses_several_recordings() {
+        let mut a = entry_at("a", 10, 1.0, &[]);
+        let mut b = entry_at("b", 11, 1.0, &[]);
+        a.recording_path = Some("/tmp/a.wav".to_string());
+        let merged = HistoryEntry::merged(&[&a, &b], " ").unwrap();
+        assert_eq!(merged.recording_path, Some("/tmp/a.wav".to_string()));
+
+        b.recording_path = Some("/tmp/b.wav".to_string());
+        assert!(HistoryEntry::merged(&[&a, &b], " ").is_none());
+    }
+
+    #[test]
+    fn test_speaker_turns_and_reassign_turn() {
+        let mut entry = entry_at(
+            "Вступ. [Спікер 1] Добрий день. [Спікер 2] [перекриття] Вітаю. [Спікер 1] Почнемо.",
+            10,
+            5.0,
+            &["Спікер 1", "Спікер 2"],
+        );
+        entry.speaker_confidence = vec![0.9, 0.4, 0.8];
+        assert_eq!(
+            entry.speaker_turns(),
+            vec![
+                ("Спікер 1".to_string(), "Добрий день.".to_string()),
+                ("Спікер 2".to_string(), "[перекриття] Вітаю.".to_string()),
+                ("Спікер 1".to_string(), "Почнемо.".to_string()),
+            ]
+        );
+
+        assert!(entry.reassign_turn(1, "Спікер 1"));
+        assert_eq!(
+            entry.text,
+            "Вступ. [Спікер 1] Добрий день. [Спікер 1] [перекриття] Вітаю. [Спікер 1] Почнемо."
+        );
+        assert_eq!(entry.speaker_confidence, vec![0.9, 1.0, 0.8]);
+        // Confirming the same speaker only raises the confidence
+        assert!(entry.reassign_turn(2, "Спікер 1"));
+        assert!(!entry.reassign_turn(2, "Спікер 1"));
+        assert!(!entry.reassign_turn(3, "Спікер 2"));
+
+        assert!(entry.reassign_turn(0, "Олена"));
+        assert!(entry.text.starts_with("Вступ. [Олена] Добрий день."));
+        assert_eq!(entry.speakers, vec!["Спікер 1", "Спікер 2", "Олена"]);
+    }
+
+    #[test]
+    fn test_speaker_confidence_merged_only_when_aligned() {
+        let mut a = entry_at("[Спікер 1] Так.", 10, 1.0, &["Спікер 1"]);
+        a.speaker_confidence = vec![0.5];
+        let mut b = entry_at("[Спікер 2] Ні.", 11, 1.0, &["Спікер 2"]);
+        b.speaker_confidence = vec![0.9];
+        let merged = HistoryEntry::merged(&[&b, &a], " ").unwrap();
+        assert_eq!(merged.speaker_confidence, vec![0.5, 0.9]);
+
+        let c = entry_at("[Ви] Добре.", 12, 1.0, &["Ви"]);
+        let merged = HistoryEntry::merged(&[&a, &b, &c], " ").unwrap();
+        assert!(merged.speaker_confidence.is_empty());
+    }
+
+    #[test]
+    fn test_speaker_stats_summed_on_merge_and_rename() {
+        let stats = |speaker: &str, talk_secs: f64, turns: usize| SpeakerStats {
+            speaker: speaker.to_string(),
+            talk_secs,
+            turns,
+        };
+        let mut a = entry_at("a", 10, 1.0, &[]);
+        a.speaker_stats = vec![stats("Спікер 1", 10.0, 2), stats("Спікер 2", 5.0, 1)];
+        let mut b = entry_at("b", 11, 1.0, &[]);
+        b.speaker_stats = vec![stats("Спікер 1", 4.0, 1)];
+
+        let mut merged = HistoryEntry::merged(&[&a, &b], " ").unwrap();
+        assert_eq!(
+            merged.speaker_stats,
+            vec![stats("Спікер 1", 14.0, 3), stats("Спікер 2", 5.0, 1)]
+        );
+
+        let renames = vec![
+            ("Спікер 1".to_string(), "Олена".to_string()),
+            ("Спікер 2".to_string(), "Олена".to_string()),
+        ];
+        assert!(merged.rename_speakers(&renames));
+        assert_eq!(merged.speaker_stats, vec![stats("Олена", 19.0, 4)]);
+    }
+
+    #[test]
+    fn test_merged_empty_is_none() {
+        assert!(HistoryEntry::merged(&[], " ").is_none());
+    }
+
+    #[test]
+    fn test_rename_speaker_labels_swaps_and_skips_spoken_words() {
+        let renames = vec![
+            ("Спікер 1".to_string(), "Олена".to_string()),
+            ("Спікер 2".to_string(), "Спікер 1".to_string()),
+        ];
+        assert_eq!(
+            rename_speaker_labels("[Спікер 1] Спікер 1 тут. [Спікер 2] [так] Так.", &renames),
+            "[Олена] Спікер 1 тут. [Спікер 1] [так] Так."
+        );
+        assert_eq!(rename_speaker_labels("[ [Спікер 1] ok [", &renames), "[ [Олена] ok [");
+    }
+
+    #[test]
+    fn test_rename_speakers_updates_text_speakers_and_assignees() {
+        let mut entry = entry_at(
+            "[Спікер 1] Привіт. [Спікер 2] Добре.",
+            10,
+            5.0,
+            &["Спікер 1", "Спікер 2"],
+        );
+        entry.action_items.push(ActionItem {
+            kind: ActionItemKind::Action,
+            text: "Надіслати звіт".to_string(),
+            assignee: Some("Спікер 2".to_string()),
+            due: None,
+        });
+
+        let renames = vec![
+            ("Спікер 1".to_string(), "Олена".to_string()),
+            ("Спікер 2".to_string(), "Олена".to_string()),
+        ];
+        assert!(entry.rename_speakers(&renames));
+        assert_eq!(entry.text, "[Олена] Привіт. [Олена] Добре.");
+        assert_eq!(entry.speakers, vec!["Олена"]);
+        assert_eq!(entry.action_items[0].assignee.as_deref(), Some("Олена"));
+
+        assert!(!entry.rename_speakers(&[("Спікер 3".to_string(), "Іван".to_string())]));
+    }
+
+    #[test]
+    fn test_segments_follow_speaker_changes() {
+        let segment = |speaker: &str, text: &str, start: f64, end: f64| TranscriptSegment {
+            speaker: speaker.to_string(),
+            text: text.to_string(),
+            start,
+            end,
+        };
+        let segments = vec![
+            segment("Спікер 1", "Привіт.", 0.0, 1.5),
+            segment("Спікер 2", "Добрий день.", 1.5, 3.0),
+        ];
+        let mut entry = entry_at(&join_segments(&segments), 10, 3.0, &["Спікер 1", "Спікер 2"]);
+        entry.segments = segments;
+        assert_eq!(entry.text, "[Спікер 1] Привіт. [Спікер 2] Добрий день.");
+
+        assert!(entry.rename_speakers(&[("Спікер 1".to_string(), "Олена".to_string())]));
+        assert_eq!(entry.segments[0].speaker, "Олена");
+        assert!(entry.reassign_turn(1, "Олена"));
+        assert_eq!(entry.segments[1], segment("Олена", "Добрий день.", 1.5, 3.0));
+
+        // Once the text no longer has a turn per segment, they stay as transcribed
+        assert!(entry.edit_text("Вступ. [Олена] Привіт. [Олена] Добрий день. [Іван] Так."));
+        entry.speakers.push("Іван".to_string());
+        assert!(entry.reassign_turn(0, "Іван"));
+        assert_eq!(entry.segments[0].speaker, "Олена");
+    }
+
+    #[test]
+    fn test_segments_default_to_empty_in_old_entries() {
+        let json =
+            r#"{"id":"1","text":"Привіт","timestamp":"2025-03-15T10:00:00Z","duration_secs":1.0,"language":"uk"}"#;
+        let entry: HistoryEntry = serde_json::from_str(json).unwrap();
+        assert!(entry.segments.is_empty());
+    }
+
+    #[test]
+    fn test_set_tags_cleans_up_and_reports_changes() {
+        let mut entry = HistoryEntry::new("text".to_string(), 1.0, "uk".to_string());
+        assert!(entry.set_tags(&parse_tags(" Клієнт А, звіт,, клієнт а ,")));
+        assert_eq!(entry.tags, vec!["Клієнт А", "звіт"]);
+        assert!(entry.has_tag("ЗВІТ"));
+        assert!(!entry.has_tag("зві"));
+
+        assert!(!entry.set_tags(&["Клієнт А".to_string(), "звіт".to_string()]));
+        assert!(entry.set_tags(&[]));
+        assert!(entry.tags.is_empty());
+    }
+
+    #[test]
+    fn test_edit_text_keeps_the_transcribed_text() {
+        let mut entry = HistoryEntry::new("привіт світ".to_string(), 1.0, "uk".to_string());
+        assert!(!entry.edit_text("привіт світ"));
+        assert_eq!(entry.original_text, None);
+
+        assert!(entry.edit_text("Привіт, світ"));
+        assert!(entry.edit_text("Привіт, світе!"));
+        assert_eq!(entry.text, "Привіт, світе!");
+        assert_eq!(entry.original_text.as_deref(), Some("привіт світ"));
+
+        // Back to the transcription: nothing left to keep
+        assert!(entry.edit_text("привіт світ"));
+        assert_eq!(entry.original_text, None);
+    }
+
+    #[test]
+    fn test_replace_transcription_keeps_versions() {
+        let mut entry = HistoryEntry::new("[Ви] привіт [Учасник] добрий день".to_string(), 1.0, "uk".to_string());
+        entry.speakers = vec!["Ви".to_string(), "Учасник".to_string()];
+        entry.speaker_confidence = vec![0.9, 0.4];
+        assert!(entry.edit_text("[Ви] Привіт! [Учасник] Добрий день."));
+
+        let speakers = vec!["Ви".to_string(), "Учасник".to_string()];
+        assert!(entry.replace_transcription("[Ви] Привіт. [Учасник] Добрий день!", &speakers, &[], "Large v3"));
+        assert_eq!(entry.text, "[Ви] Привіт. [Учасник] Добрий день!");
+        assert_eq!(entry.model.as_deref(), Some("Large v3"));
+        assert_eq!(entry.original_text, None);
+        assert!(entry.speaker_confidence.is_empty());
+        // The corrected text is what gets kept
+        assert_eq!(entry.previous_versions.len(), 1);
+        assert_eq!(entry.previous_versions[0].text, "[Ви] Привіт! [Учасник] Добрий день.");
+        assert_eq!(entry.previous_versions[0].model, None);
+
+        assert!(!entry.replace_transcription("[Ви] Привіт. [Учасник] Добрий день!", &speakers, &[], "Large v3"));
+        assert!(entry.replace_transcription("інше", &[], &[], "Base"));
+        assert_eq!(entry.previous_versions[1].model.as_deref(), Some("Large v3"));
+        assert!(entry.speakers.is_empty());
+    }
 }
@@ -1,41 +1,209 @@
+//! Export of history entries to plain text, Markdown, JSON or CSV.
+
 use anyhow::{Context, Result};
 use chrono::Utc;
+use std::fmt::Write as _;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use super::HistoryEntry;
 
+/// File format of a history export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ExportFormat {
+    /// Plain text, as read by people
+    #[default]
+    Text,
+    /// One heading per entry
+    Markdown,
+    /// The entries with every field, as stored
+    Json,
+    /// One row per entry, for spreadsheets
+    Csv,
+}
+
+impl ExportFormat {
+    pub const ALL: [Self; 4] = [Self::Text, Self::Markdown, Self::Json, Self::Csv];
+
+    /// File extension, also used as the format's ID.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Text => "txt",
+            Self::Markdown => "md",
+            Self::Json => "json",
+            Self::Csv => "csv",
+        }
+    }
+
+    /// Name shown in the export dialog.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Text => "Текст",
+            Self::Markdown => "Markdown",
+            Self::Json => "JSON",
+            Self::Csv => "CSV (таблиця)",
+        }
+    }
+
+    /// Format with the given extension; unknown ones mean plain text.
+    pub fn from_extension(extension: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|f| f.extension().eq_ignore_ascii_case(extension))
+            .unwrap_or_default()
+    }
+}
+
+/// Columns of the CSV export
+const CSV_HEADER: &str = "id,date,duration_secs,language,title,tags,speakers,words,text";
+
 /// Export history entries to a text file.
 ///
 /// This is a standalone function (not a method on History) because
 /// file I/O is an infrastructure concern, not domain logic.
 pub fn export_to_text(entries: &[&HistoryEntry], path: &PathBuf) -> Result<()> {
-    use std::io::Write;
+    fs::write(path, format_text(entries)).with_context(|| format!("Не вдалося створити файл: {}", path.display()))
+}
+
+/// Plain text with a block of details per entry, then its text.
+pub fn format_text(entries: &[&HistoryEntry]) -> String {
+    let mut out = String::from("# Історія диктовок\n");
+    let _ = writeln!(out, "# Експортовано: {}", Utc::now().format("%Y-%m-%d %H:%M:%S"));
+    out.push('\n');
+
+    for entry in entries {
+        out.push_str("---\n");
+        if let Some(title) = &entry.title {
+            let _ = writeln!(out, "Назва: {}", title);
+        }
+        let _ = writeln!(out, "Дата: {}", local_date(entry));
+        let _ = writeln!(out, "Тривалість: {}", entry.formatted_duration());
+        let _ = match entry.words_per_minute() {
+            Some(wpm) => writeln!(out, "Слів: {} ({:.0} сл/хв)", entry.word_count(), wpm),
+            None => writeln!(out, "Слів: {}", entry.word_count()),
+        };
+        let _ = writeln!(out, "Мова: {}", entry.language);
+        if !entry.tags.is_empty() {
+            let _ = writeln!(out, "Теги: {}", entry.tags.join(", "));
+        }
+        if let Some(quality) = &entry.audio_quality {
+            let _ = writeln!(out, "Якість звуку: {}", quality.note());
+        }
+        out.push('\n');
+        if !entry.action_items.is_empty() {
+            out.push_str("Завдання та рішення:\n");
+            for item in &entry.action_items {
+                let _ = writeln!(out, "- {}", item.formatted());
+            }
+            out.push('\n');
+        }
+        let _ = writeln!(out, "{}\n", entry.text);
+    }
+    out
+}
 
-    let mut file = fs::File::create(path).with_context(|| format!("Не вдалося створити файл: {}", path.display()))?;
+/// Entries formatted as `format`.
+pub fn format_history(entries: &[&HistoryEntry], format: ExportFormat) -> Result<String> {
+    Ok(match format {
+        ExportFormat::Text => format_text(entries),
+        ExportFormat::Markdown => format_markdown(entries),
+        ExportFormat::Json => format_json(entries)?,
+        ExportFormat::Csv => format_csv(entries),
+    })
+}
+
+/// Export history entries to `path` in `format`.
+pub fn export_history(entries: &[&HistoryEntry], path: &Path, format: ExportFormat) -> Result<()> {
+    let content = format_history(entries, format)?;
+    fs::write(path, content).with_context(|| format!("Не вдалося записати файл: {}", path.display()))
+}
 
-    writeln!(file, "# Історія диктовок").context("Не вдалося записати заголовок")?;
-    writeln!(file, "# Експортовано: {}", Utc::now().format("%Y-%m-%d %H:%M:%S"))
-        .context("Не вдалося записати дату експорту")?;
-    writeln!(file).context("Не вдалося записати порожній рядок")?;
+/// Markdown document with one second-level heading per entry: its title,
+/// or the date without one.
+pub fn format_markdown(entries: &[&HistoryEntry]) -> String {
+    let mut out = String::from("# Історія диктовок\n\n");
+    let _ = writeln!(out, "_Експортовано: {}_", Utc::now().format("%Y-%m-%d %H:%M:%S"));
 
     for entry in entries {
-        let local_time = entry.timestamp.with_timezone(&chrono::Local);
-        writeln!(file, "---").context("Не вдалося записати роздільник")?;
-        writeln!(file, "Дата: {}", local_time.format("%Y-%m-%d %H:%M:%S")).context("Не вдалося записати дату")?;
-        writeln!(file, "Тривалість: {}", entry.formatted_duration()).context("Не вдалося записати тривалість")?;
-        writeln!(file, "Мова: {}", entry.language).context("Не вдалося записати мову")?;
-        writeln!(file).context("Не вдалося записати порожній рядок")?;
-        writeln!(file, "{}", entry.text).context("Не вдалося записати текст")?;
-        writeln!(file).context("Не вдалося записати порожній рядок")?;
+        let date = local_date(entry);
+        out.push('\n');
+        match &entry.title {
+            Some(title) => {
+                let _ = writeln!(out, "## {}\n", title);
+                let _ = writeln!(out, "- **Дата:** {}", date);
+            }
+            None => {
+                let _ = writeln!(out, "## {}\n", date);
+            }
+        }
+        let _ = writeln!(out, "- **Тривалість:** {}", entry.formatted_duration());
+        let _ = writeln!(out, "- **Слів:** {}", entry.word_count());
+        let _ = writeln!(out, "- **Мова:** {}", entry.language);
+        if !entry.speakers.is_empty() {
+            let _ = writeln!(out, "- **Мовці:** {}", entry.speakers.join(", "));
+        }
+        if !entry.tags.is_empty() {
+            let _ = writeln!(out, "- **Теги:** {}", entry.tags.join(", "));
+        }
+        if !entry.action_items.is_empty() {
+            out.push_str("\n### Завдання та рішення\n\n");
+            for item in &entry.action_items {
+                let _ = writeln!(out, "- {}", item.formatted());
+            }
+        }
+        let _ = writeln!(out, "\n{}", entry.text.trim());
+    }
+    out
+}
+
+/// JSON array of the entries with every field, as in the JSON history file.
+pub fn format_json(entries: &[&HistoryEntry]) -> Result<String> {
+    serde_json::to_string_pretty(entries).context("Не вдалося серіалізувати історію")
+}
+
+/// CSV with a header row (see [`CSV_HEADER`]), quoted as in RFC 4180.
+pub fn format_csv(entries: &[&HistoryEntry]) -> String {
+    let mut out = String::from(CSV_HEADER);
+    out.push_str("\r\n");
+    for entry in entries {
+        let fields = [
+            entry.id.clone(),
+            local_date(entry),
+            format!("{:.1}", entry.duration_secs),
+            entry.language.clone(),
+            entry.title.clone().unwrap_or_default(),
+            entry.tags.join(", "),
+            entry.speakers.join(", "),
+            entry.word_count().to_string(),
+            entry.text.clone(),
+        ];
+        let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+        out.push_str(&row.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
     }
+}
 
-    Ok(())
+fn local_date(entry: &HistoryEntry) -> String {
+    entry
+        .timestamp
+        .with_timezone(&chrono::Local)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::types::{ActionItem, ActionItemKind, AudioQuality};
     use chrono::TimeZone;
 
     fn entry_at(text: &str, timestamp: chrono::DateTime<Utc>) -> HistoryEntry {
@@ -47,6 +215,19 @@ mod tests {
             language: "uk".to_string(),
             recording_path: None,
             speakers: Vec::new(),
+            action_items: Vec::new(),
+            speaker_stats: Vec::new(),
+            speaker_confidence: Vec::new(),
+            title: None,
+            tags: Vec::new(),
+            audio_quality: None,
+            pinned: false,
+            original_text: None,
+            model: None,
+            processing_secs: None,
+            previous_versions: Vec::new(),
+            segments: Vec::new(),
+            deleted_at: None,
         }
     }
 
@@ -67,9 +248,122 @@ mod tests {
         assert!(content.contains("First dictation"));
         assert!(content.contains("Second dictation"));
         assert!(content.contains("---"));
+        assert!(content.contains("Слів: 2 (24 сл/хв)"));
 
         // Cleanup
         let _ = fs::remove_file(&path);
         let _ = fs::remove_dir(&dir);
     }
+
+    #[test]
+    fn test_export_notes_audio_quality() {
+        let mut entry = entry_at("Тихо", Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap());
+        entry.audio_quality = Some(AudioQuality {
+            clipped_percent: None,
+            too_quiet_dbfs: Some(-52.0),
+        });
+
+        let dir = std::env::temp_dir().join(format!("s2t_test_export_{}", uuid::Uuid::new_v4()));
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("quality.txt");
+        export_to_text(&[&entry], &path).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("Якість звуку: Тихий запис (найгучніше -52 дБFS)"));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_export_lists_action_items_before_text() {
+        let mut entry = entry_at(
+            "Обговорили звіт.",
+            Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap(),
+        );
+        entry.action_items = vec![ActionItem {
+            kind: ActionItemKind::Action,
+            text: "Андрій підготує звіт до п'ятниці.".to_string(),
+            assignee: Some("Андрій".to_string()),
+            due: Some("до п'ятниці".to_string()),
+        }];
+
+        let dir = std::env::temp_dir().join(format!("s2t_test_export_{}", uuid::Uuid::new_v4()));
+        let _ = fs::create_dir_all(&dir);
+        let path = dir.join("actions.txt");
+        export_to_text(&[&entry], &path).unwrap();
+
+        let content = fs::read_to_string(&path).unwrap();
+        let items = content.find("- Завдання: Андрій підготує звіт до п'ятниці. [Андрій, до п'ятниці]");
+        assert!(items.is_some());
+        assert!(items < content.find("Обговорили звіт."));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_export_format_from_extension() {
+        assert_eq!(ExportFormat::from_extension("MD"), ExportFormat::Markdown);
+        assert_eq!(ExportFormat::from_extension("csv"), ExportFormat::Csv);
+        assert_eq!(ExportFormat::from_extension("doc"), ExportFormat::Text);
+        for format in ExportFormat::ALL {
+            assert_eq!(ExportFormat::from_extension(format.extension()), format);
+        }
+    }
+
+    #[test]
+    fn test_format_markdown_heading_per_entry() {
+        let mut titled = entry_at(
+            "Обговорили звіт.",
+            Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap(),
+        );
+        titled.title = Some("Планірка".to_string());
+        titled.tags = vec!["звіт".to_string()];
+        let plain = entry_at("Купити молоко", Utc.with_ymd_and_hms(2025, 1, 16, 14, 0, 0).unwrap());
+
+        let markdown = format_markdown(&[&titled, &plain]);
+        assert!(markdown.starts_with("# Історія диктовок\n"));
+        assert!(markdown.contains("## Планірка\n\n- **Дата:** "));
+        assert!(markdown.contains("- **Теги:** звіт\n\nОбговорили звіт.\n"));
+        assert_eq!(markdown.matches("\n## ").count(), 2);
+        assert!(markdown.trim_end().ends_with("Купити молоко"));
+    }
+
+    #[test]
+    fn test_format_json_roundtrips_entries() {
+        let mut entry = entry_at("Привіт", Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap());
+        entry.tags = vec!["тест".to_string()];
+        let json = format_json(&[&entry]).unwrap();
+        let parsed: Vec<HistoryEntry> = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].id, entry.id);
+        assert_eq!(parsed[0].tags, entry.tags);
+    }
+
+    #[test]
+    fn test_format_csv_quotes_fields() {
+        let mut entry = entry_at(
+            "Так, \"звісно\"\nДругий рядок",
+            Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap(),
+        );
+        entry.tags = vec!["а".to_string(), "б".to_string()];
+        let csv = format_csv(&[&entry]);
+
+        let (header, row) = csv.split_once("\r\n").unwrap();
+        assert_eq!(header, CSV_HEADER);
+        assert!(row.starts_with(&format!("{},", entry.id)));
+        assert!(row.contains(",5.0,uk,,\"а, б\",,4,"));
+        assert!(row.ends_with("\"Так, \"\"звісно\"\"\nДругий рядок\"\r\n"));
+    }
+
+    #[test]
+    fn test_export_history_writes_chosen_format() {
+        let entry = entry_at("Текст", Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap());
+        let dir = std::env::temp_dir().join(format!("s2t_test_export_{}", uuid::Uuid::new_v4()));
+        let _ = fs::create_dir_all(&dir);
+
+        for format in ExportFormat::ALL {
+            let path = dir.join(format!("history.{}", format.extension()));
+            export_history(&[&entry], &path, format).unwrap();
+            assert!(fs::read_to_string(&path).unwrap().contains("Текст"));
+        }
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
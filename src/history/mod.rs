@@ -1,41 +1,144 @@
+mod anki;
+mod backup;
 mod entry;
 mod export;
 mod persistence;
+mod search;
+mod stats;
+mod sync;
 
+pub use anki::{export_to_anki, AnkiTemplate};
+pub use backup::{backup_store, list_backups};
 pub use entry::HistoryEntry;
-pub use export::export_to_text;
-pub use persistence::{load_history, save_history};
+pub use export::{export_history, export_to_text, format_history, ExportFormat};
+pub use persistence::{
+    convert_history, create_store, create_store_in, load_history, read_history_file, save_history, HistoryBackend,
+    HistoryStore,
+};
+pub use stats::HistoryStats;
 
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 
 use crate::domain::traits::HistoryRepository;
+use crate::domain::types::{HistoryOrder, TranscriptSegment};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct History {
     pub entries: Vec<HistoryEntry>,
+    /// Deleted entries, most recently deleted first
+    #[serde(default)]
+    pub trash: Vec<HistoryEntry>,
+    /// Backend the history was loaded from and is saved to
+    #[serde(skip)]
+    pub backend: HistoryBackend,
+    /// Kiosk mode: entries are neither added nor removed, and nothing is saved
+    #[serde(skip)]
+    pub read_only: bool,
+    /// Backups of the store kept, one made before each save; 0 makes none
+    #[serde(skip)]
+    pub backups: usize,
+    /// IDs of the entries removed since loading, so a save does not take them
+    /// back from the store (see [`save_history`])
+    #[serde(skip)]
+    pub removed: HashSet<String>,
 }
 
 impl History {
-    /// Trim history to max_entries, keeping newest
+    /// Empty history kept in `backend`.
+    pub fn with_backend(backend: HistoryBackend) -> Self {
+        Self {
+            entries: Vec::new(),
+            trash: Vec::new(),
+            backend,
+            read_only: false,
+            backups: 0,
+            removed: HashSet::new(),
+        }
+    }
+
+    /// Trim history to max_entries, keeping newest and every pinned entry
+    ///
+    /// Pinned entries count towards the limit, so the oldest unpinned ones
+    /// go first; if the pinned ones alone exceed it, only they are kept.
     pub fn trim_to_limit(&mut self, max_entries: usize) {
-        if self.entries.len() > max_entries {
-            self.entries.truncate(max_entries);
+        if self.read_only || self.entries.len() <= max_entries {
+            return;
         }
+        let pinned = self.entries.iter().filter(|e| e.pinned).count();
+        let mut unpinned_left = max_entries.saturating_sub(pinned);
+        retain_noting(&mut self.entries, &mut self.removed, |e| {
+            if e.pinned {
+                return true;
+            }
+            let keep = unpinned_left > 0;
+            unpinned_left = unpinned_left.saturating_sub(1);
+            keep
+        });
     }
 
-    /// Remove entries older than max_age_days
+    /// Remove unpinned entries older than max_age_days
     pub fn cleanup_old_entries(&mut self, max_age_days: i64) {
+        if self.read_only {
+            return;
+        }
+        let cutoff = Utc::now() - Duration::days(max_age_days);
+        retain_noting(&mut self.entries, &mut self.removed, |e| {
+            e.pinned || e.timestamp > cutoff
+        });
+    }
+
+    /// Drop entries trashed more than max_age_days ago
+    pub fn cleanup_trash(&mut self, max_age_days: i64) {
+        if self.read_only {
+            return;
+        }
         let cutoff = Utc::now() - Duration::days(max_age_days);
-        self.entries.retain(|e| e.timestamp > cutoff);
+        retain_noting(&mut self.trash, &mut self.removed, |e| {
+            e.deleted_at.is_some_and(|deleted| deleted > cutoff)
+        });
+    }
+
+    /// Insert an entry where it belongs by time, entries being newest first.
+    fn insert_by_time(&mut self, entry: HistoryEntry) {
+        let position = self
+            .entries
+            .iter()
+            .position(|e| e.timestamp < entry.timestamp)
+            .unwrap_or(self.entries.len());
+        self.entries.insert(position, entry);
+    }
+
+    /// Pinned entries, newest first.
+    pub fn pinned_entries(&self) -> impl Iterator<Item = &HistoryEntry> {
+        self.entries.iter().filter(|e| e.pinned)
     }
 }
 
+/// Keep the entries of `list` for which `keep` holds, noting the IDs of the
+/// others in `removed`.
+fn retain_noting(
+    list: &mut Vec<HistoryEntry>,
+    removed: &mut HashSet<String>,
+    mut keep: impl FnMut(&HistoryEntry) -> bool,
+) {
+    list.retain(|e| {
+        let kept = keep(e);
+        if !kept {
+            removed.insert(e.id.clone());
+        }
+        kept
+    });
+}
+
 impl HistoryRepository for History {
     type Entry = HistoryEntry;
 
     fn add(&mut self, entry: HistoryEntry) {
-        self.entries.insert(0, entry);
+        if !self.read_only {
+            self.entries.insert(0, entry);
+        }
     }
 
     fn entries(&self) -> &[HistoryEntry] {
@@ -43,11 +146,7 @@ impl HistoryRepository for History {
     }
 
     fn search(&self, query: &str) -> Vec<&HistoryEntry> {
-        let query_lower = query.to_lowercase();
-        self.entries
-            .iter()
-            .filter(|e| e.text.to_lowercase().contains(&query_lower))
-            .collect()
+        search::search(&self.entries, query)
     }
 
     fn cleanup_old(&mut self, max_age_days: u32) -> usize {
@@ -57,12 +156,9 @@ impl HistoryRepository for History {
     }
 
     fn trim_to_limit(&mut self, max_entries: usize) -> usize {
-        if self.entries.len() <= max_entries {
-            return 0;
-        }
-        let removed = self.entries.len() - max_entries;
-        self.entries.truncate(max_entries);
-        removed
+        let before = self.entries.len();
+        History::trim_to_limit(self, max_entries);
+        before - self.entries.len()
     }
 
     fn save(&self) -> anyhow::Result<()> {
@@ -70,7 +166,87 @@ impl HistoryRepository for History {
     }
 
     fn remove(&mut self, id: &str) {
-        self.entries.retain(|e| e.id != id);
+        if self.read_only {
+            return;
+        }
+        retain_noting(&mut self.entries, &mut self.removed, |e| e.id != id);
+    }
+
+    fn trash(&mut self, id: &str) -> bool {
+        if self.read_only {
+            return false;
+        }
+        let Some(position) = self.entries.iter().position(|e| e.id == id) else {
+            return false;
+        };
+        let mut entry = self.entries.remove(position);
+        entry.deleted_at = Some(Utc::now());
+        self.trash.insert(0, entry);
+        true
+    }
+
+    fn restore(&mut self, id: &str) -> bool {
+        if self.read_only {
+            return false;
+        }
+        let Some(position) = self.trash.iter().position(|e| e.id == id) else {
+            return false;
+        };
+        let mut entry = self.trash.remove(position);
+        entry.deleted_at = None;
+        self.insert_by_time(entry);
+        true
+    }
+
+    fn import(&mut self, entries: Vec<HistoryEntry>) -> usize {
+        if self.read_only {
+            return 0;
+        }
+        let mut known: HashSet<String> = self.entries.iter().chain(&self.trash).map(|e| e.id.clone()).collect();
+        let mut added = 0;
+        for entry in entries {
+            if !known.insert(entry.id.clone()) {
+                continue;
+            }
+            if entry.deleted_at.is_some() {
+                self.trash.push(entry);
+            } else {
+                self.insert_by_time(entry);
+            }
+            added += 1;
+        }
+        self.trash.sort_by(|a, b| b.deleted_at.cmp(&a.deleted_at));
+        added
+    }
+
+    fn replace_entries(&mut self, entries: Vec<HistoryEntry>) -> bool {
+        if self.read_only {
+            return false;
+        }
+        let kept: HashSet<&str> = entries.iter().map(|e| e.id.as_str()).collect();
+        for entry in self.entries.iter().chain(&self.trash) {
+            if !kept.contains(entry.id.as_str()) {
+                self.removed.insert(entry.id.clone());
+            }
+        }
+        (self.trash, self.entries) = entries.into_iter().partition(|e| e.deleted_at.is_some());
+        true
+    }
+
+    fn trashed(&self) -> &[HistoryEntry] {
+        &self.trash
+    }
+
+    fn purge_trash(&mut self, max_age_days: u32) -> usize {
+        let before = self.trash.len();
+        self.cleanup_trash(max_age_days as i64);
+        before - self.trash.len()
+    }
+
+    fn sorted(&self, order: HistoryOrder) -> Vec<&HistoryEntry> {
+        let mut entries: Vec<&HistoryEntry> = self.entries.iter().collect();
+        order.sort(&mut entries);
+        entries
     }
 
     fn filter_by_date_range(&self, from: Option<DateTime<Utc>>, to: Option<DateTime<Utc>>) -> Vec<&HistoryEntry> {
@@ -91,6 +267,103 @@ impl HistoryRepository for History {
             })
             .collect()
     }
+
+    fn merge(&mut self, ids: &[String], separator: &str) -> Option<String> {
+        if self.read_only {
+            return None;
+        }
+        let selected: Vec<&HistoryEntry> = self.entries.iter().filter(|e| ids.contains(&e.id)).collect();
+        if selected.len() < 2 {
+            return None;
+        }
+        let merged = HistoryEntry::merged(&selected, separator)?;
+        let merged_id = merged.id.clone();
+
+        // Keep the merged entry where the newest original was
+        let position = self.entries.iter().position(|e| ids.contains(&e.id)).unwrap_or(0);
+        retain_noting(&mut self.entries, &mut self.removed, |e| !ids.contains(&e.id));
+        self.entries.insert(position.min(self.entries.len()), merged);
+        Some(merged_id)
+    }
+
+    fn rename_speakers(&mut self, id: &str, renames: &[(String, String)]) -> bool {
+        self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .is_some_and(|e| e.rename_speakers(renames))
+    }
+
+    fn reassign_turn(&mut self, id: &str, index: usize, speaker: &str) -> bool {
+        self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .is_some_and(|e| e.reassign_turn(index, speaker))
+    }
+
+    fn set_tags(&mut self, id: &str, tags: &[String]) -> bool {
+        if self.read_only {
+            return false;
+        }
+        self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .is_some_and(|e| e.set_tags(tags))
+    }
+
+    fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for tag in self.entries.iter().flat_map(|e| &e.tags) {
+            if !tags.iter().any(|t| t.to_lowercase() == tag.to_lowercase()) {
+                tags.push(tag.clone());
+            }
+        }
+        tags.sort_by_key(|t| t.to_lowercase());
+        tags
+    }
+
+    fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    fn set_text(&mut self, id: &str, text: &str) -> bool {
+        if self.read_only {
+            return false;
+        }
+        self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .is_some_and(|e| e.edit_text(text))
+    }
+
+    fn replace_transcription(
+        &mut self,
+        id: &str,
+        text: &str,
+        speakers: &[String],
+        segments: &[TranscriptSegment],
+        model: &str,
+    ) -> bool {
+        if self.read_only {
+            return false;
+        }
+        self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .is_some_and(|e| e.replace_transcription(text, speakers, segments, model))
+    }
+
+    fn set_pinned(&mut self, id: &str, pinned: bool) -> bool {
+        if self.read_only {
+            return false;
+        }
+        match self.entries.iter_mut().find(|e| e.id == id) {
+            Some(entry) if entry.pinned != pinned => {
+                entry.pinned = pinned;
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -108,6 +381,19 @@ mod tests {
             language: "uk".to_string(),
             recording_path: None,
             speakers: Vec::new(),
+            action_items: Vec::new(),
+            speaker_stats: Vec::new(),
+            speaker_confidence: Vec::new(),
+            title: None,
+            tags: Vec::new(),
+            audio_quality: None,
+            pinned: false,
+            original_text: None,
+            model: None,
+            processing_secs: None,
+            previous_versions: Vec::new(),
+            segments: Vec::new(),
+            deleted_at: None,
         }
     }
 
@@ -124,6 +410,23 @@ mod tests {
         assert!(history.entries.is_empty());
     }
 
+    #[test]
+    fn test_read_only_history_is_not_changed() {
+        let mut history = History::default();
+        history.add(entry_at("old", Utc::now() - Duration::days(100)));
+        history.add(HistoryEntry::new("First".to_string(), 5.0, "uk".to_string()));
+        let ids: Vec<String> = history.entries.iter().map(|e| e.id.clone()).collect();
+        history.read_only = true;
+
+        history.add(HistoryEntry::new("Second".to_string(), 5.0, "uk".to_string()));
+        history.remove(&ids[0]);
+        assert_eq!(history.merge(&ids, " "), None);
+        history.cleanup_old_entries(30);
+        assert_eq!(HistoryRepository::trim_to_limit(&mut history, 1), 0);
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.entries[0].text, "First");
+    }
+
     #[test]
     fn test_history_add_inserts_at_front() {
         let mut history = History::default();
@@ -157,6 +460,29 @@ mod tests {
         assert_eq!(history.entries.len(), 2);
     }
 
+    #[test]
+    fn test_sorted_orders() {
+        let mut history = History::default();
+        let jan = |day| Utc.with_ymd_and_hms(2025, 1, day, 12, 0, 0).unwrap();
+        let mut short = entry_at("short en", jan(1));
+        short.duration_secs = 2.0;
+        short.language = "en".to_string();
+        let mut meeting = entry_at("meeting", jan(2));
+        meeting.duration_secs = 60.0;
+        meeting.speakers = vec!["Спікер 1".to_string(), "Спікер 2".to_string()];
+        let newest = entry_at("newest", jan(3));
+        history.add(short);
+        history.add(meeting);
+        history.add(newest);
+
+        let texts = |order| -> Vec<String> { history.sorted(order).iter().map(|e| e.text.clone()).collect() };
+        assert_eq!(texts(HistoryOrder::Newest), ["newest", "meeting", "short en"]);
+        assert_eq!(texts(HistoryOrder::Oldest), ["short en", "meeting", "newest"]);
+        assert_eq!(texts(HistoryOrder::Longest), ["meeting", "newest", "short en"]);
+        assert_eq!(texts(HistoryOrder::Language), ["short en", "newest", "meeting"]);
+        assert_eq!(texts(HistoryOrder::Speakers), ["meeting", "newest", "short en"]);
+    }
+
     #[test]
     fn test_remove_nonexistent_id_is_noop() {
         let mut history = History::default();
@@ -166,6 +492,94 @@ mod tests {
         assert_eq!(history.entries.len(), 1);
     }
 
+    #[test]
+    fn test_trash_and_restore_keep_time_order() {
+        let mut history = History::default();
+        for day in 1..=3 {
+            history.add(entry_at(
+                &format!("day {}", day),
+                Utc.with_ymd_and_hms(2025, 1, day, 12, 0, 0).unwrap(),
+            ));
+        }
+        let id = history.entries[1].id.clone();
+
+        assert!(history.trash(&id));
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.trashed().len(), 1);
+        assert!(history.trash[0].deleted_at.is_some());
+        assert!(history.search("day 2").is_empty());
+        assert!(!history.trash(&id));
+
+        assert!(history.restore(&id));
+        assert!(history.trash.is_empty());
+        let texts: Vec<&str> = history.entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["day 3", "day 2", "day 1"]);
+        assert_eq!(history.entries[1].deleted_at, None);
+        assert!(!history.restore(&id));
+    }
+
+    #[test]
+    fn test_import_adds_unknown_entries_by_time() {
+        let mut history = History::default();
+        let jan = |day| Utc.with_ymd_and_hms(2025, 1, day, 12, 0, 0).unwrap();
+        history.add(entry_at("day 1", jan(1)));
+        history.add(entry_at("day 3", jan(3)));
+        let known = history.entries[0].clone();
+        let mut trashed = entry_at("trashed", jan(4));
+        trashed.deleted_at = Some(Utc::now());
+        let new = entry_at("day 2", jan(2));
+
+        let added = history.import(vec![known, new.clone(), new, trashed]);
+        assert_eq!(added, 2);
+        let texts: Vec<&str> = history.entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["day 3", "day 2", "day 1"]);
+        assert_eq!(history.trash[0].text, "trashed");
+
+        let again: Vec<HistoryEntry> = history.entries.iter().chain(&history.trash).cloned().collect();
+        assert_eq!(history.import(again), 0);
+    }
+
+    #[test]
+    fn test_replace_entries_puts_deleted_ones_in_trash() {
+        let mut history = History::default();
+        history.add(HistoryEntry::new("current".to_string(), 5.0, "uk".to_string()));
+        let mut trashed = HistoryEntry::new("trashed".to_string(), 5.0, "uk".to_string());
+        trashed.deleted_at = Some(Utc::now());
+        let kept = HistoryEntry::new("kept".to_string(), 5.0, "uk".to_string());
+
+        assert!(history.replace_entries(vec![kept, trashed]));
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].text, "kept");
+        assert_eq!(history.trash[0].text, "trashed");
+
+        history.read_only = true;
+        assert!(!history.replace_entries(Vec::new()));
+        assert_eq!(history.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_purge_trash_drops_old_deletions() {
+        let mut history = History::default();
+        history.add(HistoryEntry::new("old".to_string(), 5.0, "uk".to_string()));
+        history.add(HistoryEntry::new("recent".to_string(), 5.0, "uk".to_string()));
+        let ids: Vec<String> = history.entries.iter().map(|e| e.id.clone()).collect();
+        for id in &ids {
+            history.trash(id);
+        }
+        history.trash[1].deleted_at = Some(Utc::now() - Duration::days(40));
+
+        assert_eq!(history.purge_trash(30), 1);
+        assert_eq!(history.trash[0].text, "recent");
+        assert_eq!(history.purge_trash(0), 1);
+        assert!(history.trash.is_empty());
+
+        history.add(HistoryEntry::new("kept".to_string(), 5.0, "uk".to_string()));
+        let kept = history.entries[0].id.clone();
+        history.read_only = true;
+        assert!(!history.trash(&kept));
+        assert_eq!(history.entries.len(), 1);
+    }
+
     #[test]
     fn test_filter_by_date_range_both_bounds() {
         let mut history = History::default();
@@ -231,6 +645,32 @@ mod tests {
         assert_eq!(history.entries[0].text, "recent");
     }
 
+    #[test]
+    fn test_limits_keep_pinned_entries() {
+        let mut history = History::default();
+        history.add(entry_at("old pinned", Utc::now() - Duration::days(100)));
+        history.add(entry_at("old", Utc::now() - Duration::days(100)));
+        for i in 0..5 {
+            history.add(HistoryEntry::new(format!("Entry {}", i), 5.0, "uk".to_string()));
+        }
+        let pinned_id = history.entries[6].id.clone();
+        assert!(history.set_pinned(&pinned_id, true));
+        assert!(!history.set_pinned(&pinned_id, true));
+
+        history.cleanup_old_entries(30);
+        assert_eq!(history.entries.len(), 6);
+        assert_eq!(history.entries[5].text, "old pinned");
+
+        assert_eq!(HistoryRepository::trim_to_limit(&mut history, 3), 3);
+        let texts: Vec<&str> = history.entries.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, vec!["Entry 4", "Entry 3", "old pinned"]);
+        assert_eq!(history.pinned_entries().count(), 1);
+
+        history.trim_to_limit(0);
+        assert_eq!(history.entries.len(), 1);
+        assert!(history.entries[0].pinned);
+    }
+
     #[test]
     fn test_cleanup_old_entries_keeps_all_when_recent() {
         let mut history = History::default();
@@ -344,4 +784,90 @@ mod tests {
         assert_eq!(history.entries[0].text, "recent2");
         assert_eq!(history.entries[1].text, "recent1");
     }
+
+    #[test]
+    fn test_merge_replaces_selected_entries() {
+        let mut history = History::default();
+        let base = Utc.with_ymd_and_hms(2025, 1, 1, 10, 0, 0).unwrap();
+        history.add(entry_at("one", base));
+        history.add(entry_at("two", base + Duration::minutes(1)));
+        history.add(entry_at("three", base + Duration::minutes(2)));
+
+        // entries: [three, two, one]
+        let ids = vec![history.entries[2].id.clone(), history.entries[1].id.clone()];
+        let merged_id = history.merge(&ids, " | ").unwrap();
+
+        assert_eq!(history.entries.len(), 2);
+        assert_eq!(history.entries[0].text, "three");
+        assert_eq!(history.entries[1].id, merged_id);
+        assert_eq!(history.entries[1].text, "one | two");
+        assert_eq!(history.entries[1].duration_secs, 10.0);
+    }
+
+    #[test]
+    fn test_merge_requires_two_entries() {
+        let mut history = History::default();
+        history.add(HistoryEntry::new("only".to_string(), 1.0, "uk".to_string()));
+        let ids = vec![history.entries[0].id.clone(), "missing".to_string()];
+
+        assert!(history.merge(&ids, " ").is_none());
+        assert_eq!(history.entries.len(), 1);
+        assert_eq!(history.entries[0].text, "only");
+    }
+
+    #[test]
+    fn test_rename_speakers_updates_entry() {
+        let mut history = History::default();
+        history.add(HistoryEntry::new_with_recording(
+            "[Спікер 1] Так. [Спікер 2] Ні.".to_string(),
+            3.0,
+            "uk".to_string(),
+            None,
+            vec!["Спікер 1".to_string(), "Спікер 2".to_string()],
+        ));
+        let id = history.entries[0].id.clone();
+        let renames = vec![("Спікер 2".to_string(), "Іван".to_string())];
+
+        assert!(history.rename_speakers(&id, &renames));
+        assert_eq!(history.entries[0].text, "[Спікер 1] Так. [Іван] Ні.");
+        assert_eq!(history.entries[0].speakers, vec!["Спікер 1", "Іван"]);
+        assert!(!history.rename_speakers("missing", &renames));
+    }
+
+    #[test]
+    fn test_set_tags_and_all_tags() {
+        let mut history = History::default();
+        history.add(HistoryEntry::new("one".to_string(), 1.0, "uk".to_string()));
+        history.add(HistoryEntry::new("two".to_string(), 1.0, "uk".to_string()));
+        let ids: Vec<String> = history.entries.iter().map(|e| e.id.clone()).collect();
+
+        assert!(history.set_tags(&ids[0], &["звіт".to_string(), "Клієнт".to_string()]));
+        assert!(history.set_tags(&ids[1], &["клієнт".to_string(), "архів".to_string()]));
+        assert!(!history.set_tags("missing", &["x".to_string()]));
+        assert_eq!(history.all_tags(), vec!["архів", "звіт", "Клієнт"]);
+
+        history.read_only = true;
+        assert!(!history.set_tags(&ids[0], &[]));
+        assert_eq!(history.entries[0].tags.len(), 2);
+    }
+
+    #[test]
+    fn test_set_text_keeps_original() {
+        let mut history = History::default();
+        history.add(HistoryEntry::new(
+            "зустріч о десятій".to_string(),
+            1.0,
+            "uk".to_string(),
+        ));
+        let id = history.entries[0].id.clone();
+
+        assert!(history.set_text(&id, "Зустріч о 10:00."));
+        assert!(!history.set_text("missing", "x"));
+        assert_eq!(history.entries[0].text, "Зустріч о 10:00.");
+        assert_eq!(history.entries[0].original_text.as_deref(), Some("зустріч о десятій"));
+
+        history.read_only = true;
+        assert!(!history.set_text(&id, "інше"));
+        assert_eq!(history.entries[0].text, "Зустріч о 10:00.");
+    }
 }
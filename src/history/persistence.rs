@@ -1,42 +1,485 @@
-use anyhow::{Context, Result};
+//! History storage backends.
+//!
+//! The history can be kept as one JSON file (default), as JSON Lines (one
+//! entry per line, easy to grep and diff), in an SQLite database or as an
+//! encrypted JSON file. [`create_store`] picks the backend named by the
+//! `history_backend` config value; `voice-dictation history convert` moves
+//! the history from one backend to another.
+//!
+//! Files are written to a temporary file that then replaces the store, so
+//! a crash or a sync tool never sees half of a history; saves merge with
+//! changes synced from other devices (see [`super::sync`]).
+
+use anyhow::{bail, Context, Result};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
-use super::History;
+use super::sync::{conflict_copies, merge_stored};
+use super::{backup_store, History, HistoryEntry};
+use crate::app::config::set_owner_only_permissions;
+use crate::domain::traits::HistoryRepository;
+use crate::infrastructure::failures::{self, Failure};
 
-pub fn history_path() -> PathBuf {
+/// Length of the random nonce stored in front of the encrypted history.
+const NONCE_LEN: usize = 12;
+
+/// Where and how the history is stored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum HistoryBackend {
+    /// One pretty-printed JSON file
+    #[default]
+    Json,
+    /// One JSON object per line
+    Jsonl,
+    /// SQLite database
+    Sqlite,
+    /// JSON encrypted with ChaCha20-Poly1305, key in the config directory
+    EncryptedJson,
+}
+
+impl HistoryBackend {
+    /// All backends, in the order they are listed to the user.
+    pub const ALL: [Self; 4] = [Self::Json, Self::Jsonl, Self::Sqlite, Self::EncryptedJson];
+
+    /// Parse a `history_backend` config value; unknown values mean `Json`.
+    pub fn parse(s: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|backend| backend.as_str() == s)
+            .unwrap_or_default()
+    }
+
+    /// Config value of the backend.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Jsonl => "jsonl",
+            Self::Sqlite => "sqlite",
+            Self::EncryptedJson => "encrypted-json",
+        }
+    }
+
+    fn file_name(&self) -> &'static str {
+        match self {
+            Self::Json => "history.json",
+            Self::Jsonl => "history.jsonl",
+            Self::Sqlite => "history.sqlite",
+            Self::EncryptedJson => "history.json.enc",
+        }
+    }
+}
+
+/// Loads and saves the whole history in one storage format.
+pub trait HistoryStore {
+    /// Read the history; a missing store is an empty history.
+    fn load(&self) -> Result<Vec<HistoryEntry>>;
+
+    /// Replace the stored history with `entries`.
+    fn save(&self, entries: &[HistoryEntry]) -> Result<()>;
+
+    /// File the history is kept in.
+    fn path(&self) -> &Path;
+}
+
+/// Create the store for `backend` in the default data directory.
+pub fn create_store(backend: HistoryBackend) -> Box<dyn HistoryStore> {
+    create_store_in(backend, &history_dir(), &crate::app::config::config_dir())
+}
+
+/// Create the store for `backend` with its file in `dir`.
+///
+/// The encryption key of the encrypted backend is kept in `key_dir`.
+pub fn create_store_in(backend: HistoryBackend, dir: &Path, key_dir: &Path) -> Box<dyn HistoryStore> {
+    let path = dir.join(backend.file_name());
+    match backend {
+        HistoryBackend::Json => Box::new(JsonStore { path }),
+        HistoryBackend::Jsonl => Box::new(JsonlStore { path }),
+        HistoryBackend::Sqlite => Box::new(SqliteStore { path }),
+        HistoryBackend::EncryptedJson => Box::new(EncryptedJsonStore {
+            path,
+            key_path: key_dir.join("history.key"),
+        }),
+    }
+}
+
+fn history_dir() -> PathBuf {
     dirs::data_local_dir()
         .unwrap_or_else(|| PathBuf::from("."))
         .join("voice-dictation")
-        .join("history.json")
 }
 
-pub fn load_history() -> Result<History> {
-    let path = history_path();
+pub fn history_path() -> PathBuf {
+    history_dir().join(HistoryBackend::Json.file_name())
+}
+
+/// Load the history from the store of `backend`.
+///
+/// Trashed entries are kept in the same store, marked by `deleted_at`.
+/// Entries of conflict copies left by a sync tool are merged in.
+pub fn load_history(backend: HistoryBackend) -> Result<History> {
+    let store = create_store(backend);
+    let (trash, entries) = store.load()?.into_iter().partition(|e| e.deleted_at.is_some());
+    let mut history = History {
+        entries,
+        trash,
+        backend,
+        read_only: false,
+        backups: 0,
+        removed: Default::default(),
+    };
+    let (conflicting, _) = read_conflict_copies(store.path());
+    history.import(conflicting);
+    Ok(history)
+}
 
+/// Save the history to the store it was loaded from, backing the store up
+/// first at most once an hour (see [`History::backups`]). A read-only
+/// history is not written.
+///
+/// Entries another device added to the store or to a conflict copy since
+/// loading are kept; the merged conflict copies are then deleted.
+pub fn save_history(history: &History) -> Result<()> {
+    if history.read_only {
+        return Ok(());
+    }
+    let store = create_store(history.backend);
+    let loaded = store.load();
+    if let Err(e) = backup_store(store.as_ref(), history.backups, loaded.is_ok()) {
+        eprintln!("Помилка резервного копіювання історії: {}", e);
+    }
+    // An unreadable store is simply overwritten
+    let mut stored = loaded.unwrap_or_default();
+    let (conflicting, copies) = read_conflict_copies(store.path());
+    stored.extend(conflicting);
+    let result = store.save(&merge_stored(history, stored));
+    match &result {
+        Ok(()) => {
+            for copy in copies {
+                if let Err(e) = fs::remove_file(&copy) {
+                    eprintln!("Не вдалося видалити конфліктну копію {}: {}", copy.display(), e);
+                }
+            }
+        }
+        Err(_) => failures::record(Failure::HistorySave),
+    }
+    result
+}
+
+/// Entries of the readable conflict copies of a store, with their paths.
+fn read_conflict_copies(store_path: &Path) -> (Vec<HistoryEntry>, Vec<PathBuf>) {
+    let mut entries = Vec::new();
+    let mut copies = Vec::new();
+    for copy in conflict_copies(store_path) {
+        match read_history_file(&copy) {
+            Ok(read) => {
+                entries.extend(read);
+                copies.push(copy);
+            }
+            Err(e) => eprintln!("Не вдалося прочитати конфліктну копію {}: {}", copy.display(), e),
+        }
+    }
+    (entries, copies)
+}
+
+/// Read a history file of any backend, e.g. from another machine or a
+/// backup, or a JSON export.
+///
+/// The format is told by the extension: `.jsonl`, `.sqlite` or `.db`, `.enc`
+/// (decrypted with the key of this machine), otherwise JSON.
+pub fn read_history_file(path: &Path) -> Result<Vec<HistoryEntry>> {
+    if !path.is_file() {
+        bail!("Файл історії не знайдено: {}", path.display());
+    }
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+    let path = path.to_path_buf();
+    match extension.as_str() {
+        "jsonl" => JsonlStore { path }.load(),
+        "sqlite" | "db" => SqliteStore { path }.load(),
+        "enc" => EncryptedJsonStore {
+            path,
+            key_path: crate::app::config::config_dir().join("history.key"),
+        }
+        .load(),
+        _ => {
+            let content =
+                fs::read(&path).with_context(|| format!("Не вдалося прочитати історію: {}", path.display()))?;
+            // A JSON export is a bare array of entries
+            match serde_json::from_slice::<Vec<HistoryEntry>>(&content) {
+                Ok(entries) => Ok(entries),
+                Err(_) => entries_from_json(&content),
+            }
+        }
+    }
+}
+
+/// Copy the history from one backend to another.
+///
+/// The target store is overwritten; the source is left in place. Returns
+/// the number of entries copied.
+pub fn convert_history(from: &dyn HistoryStore, to: &dyn HistoryStore) -> Result<usize> {
+    let entries = from.load()?;
+    to.save(&entries)?;
+    Ok(entries.len())
+}
+
+/// Create the parent directory of a store file.
+fn ensure_parent(path: &Path) -> Result<()> {
+    let dir = path.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(dir).with_context(|| format!("Не вдалося створити директорію: {}", dir.display()))
+}
+
+/// Write a store file readable by the owner only.
+///
+/// The content goes to a hidden temporary file next to it first, which
+/// then replaces the file in one rename.
+fn write_private(path: &Path, content: &[u8]) -> Result<()> {
+    ensure_parent(path)?;
+    let name = path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let temp = path.with_file_name(format!(".{}.tmp", name));
+    let result = write_synced(&temp, content)
+        .and_then(|()| set_owner_only_permissions(&temp))
+        .and_then(|()| {
+            fs::rename(&temp, path).with_context(|| format!("Не вдалося записати історію: {}", path.display()))
+        });
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    result
+}
+
+/// Write a file and wait until it is on disk.
+fn write_synced(path: &Path, content: &[u8]) -> Result<()> {
+    let mut file =
+        fs::File::create(path).with_context(|| format!("Не вдалося записати історію: {}", path.display()))?;
+    file.write_all(content)
+        .and_then(|()| file.sync_all())
+        .with_context(|| format!("Не вдалося записати історію: {}", path.display()))
+}
+
+fn read_existing(path: &Path) -> Result<Option<Vec<u8>>> {
     if !path.exists() {
-        return Ok(History::default());
+        return Ok(None);
     }
+    fs::read(path)
+        .map(Some)
+        .with_context(|| format!("Не вдалося прочитати історію: {}", path.display()))
+}
 
-    let content =
-        fs::read_to_string(&path).with_context(|| format!("Не вдалося прочитати історію: {}", path.display()))?;
+/// The history as a JSON object (`{"entries": [...]}`).
+#[derive(Serialize, Deserialize)]
+struct JsonHistory {
+    entries: Vec<HistoryEntry>,
+}
 
-    serde_json::from_str(&content).with_context(|| "Не вдалося розпарсити історію")
+fn entries_to_json(entries: &[HistoryEntry]) -> Result<Vec<u8>> {
+    let json = JsonHistory {
+        entries: entries.to_vec(),
+    };
+    serde_json::to_vec_pretty(&json).context("Не вдалося серіалізувати історію")
 }
 
-pub fn save_history(history: &History) -> Result<()> {
-    let path = history_path();
-    let dir = path.parent().unwrap();
+fn entries_from_json(content: &[u8]) -> Result<Vec<HistoryEntry>> {
+    let json: JsonHistory = serde_json::from_slice(content).context("Не вдалося розпарсити історію")?;
+    Ok(json.entries)
+}
+
+struct JsonStore {
+    path: PathBuf,
+}
+
+impl HistoryStore for JsonStore {
+    fn load(&self) -> Result<Vec<HistoryEntry>> {
+        match read_existing(&self.path)? {
+            Some(content) => entries_from_json(&content),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn save(&self, entries: &[HistoryEntry]) -> Result<()> {
+        write_private(&self.path, &entries_to_json(entries)?)
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+struct JsonlStore {
+    path: PathBuf,
+}
+
+impl HistoryStore for JsonlStore {
+    fn load(&self) -> Result<Vec<HistoryEntry>> {
+        let Some(content) = read_existing(&self.path)? else {
+            return Ok(Vec::new());
+        };
+        let content = String::from_utf8(content).context("Не вдалося розпарсити історію")?;
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line).with_context(|| format!("Не вдалося розпарсити рядок {} історії", i + 1))
+            })
+            .collect()
+    }
+
+    fn save(&self, entries: &[HistoryEntry]) -> Result<()> {
+        let mut content = String::new();
+        for entry in entries {
+            content.push_str(&serde_json::to_string(entry).context("Не вдалося серіалізувати історію")?);
+            content.push('\n');
+        }
+        write_private(&self.path, content.as_bytes())
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+/// Entries are stored as JSON in `data`, newest first by `position`; `id`,
+/// `timestamp` and `text` are copied out for querying the database directly.
+struct SqliteStore {
+    path: PathBuf,
+}
+
+impl SqliteStore {
+    fn open(&self) -> Result<Connection> {
+        ensure_parent(&self.path)?;
+        let conn = Connection::open(&self.path)
+            .with_context(|| format!("Не вдалося відкрити базу історії: {}", self.path.display()))?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS entries (
+                position INTEGER PRIMARY KEY,
+                id TEXT NOT NULL,
+                timestamp TEXT NOT NULL,
+                text TEXT NOT NULL,
+                data TEXT NOT NULL
+            )",
+        )
+        .context("Не вдалося створити таблицю історії")?;
+        set_owner_only_permissions(&self.path)?;
+        Ok(conn)
+    }
+}
 
-    fs::create_dir_all(dir).with_context(|| format!("Не вдалося створити директорію: {}", dir.display()))?;
+impl HistoryStore for SqliteStore {
+    fn load(&self) -> Result<Vec<HistoryEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let conn = self.open()?;
+        let mut stmt = conn
+            .prepare("SELECT data FROM entries ORDER BY position")
+            .context("Не вдалося прочитати історію")?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))
+            .context("Не вдалося прочитати історію")?;
+        let mut entries = Vec::new();
+        for data in rows {
+            let data = data.context("Не вдалося прочитати історію")?;
+            entries.push(serde_json::from_str(&data).context("Не вдалося розпарсити історію")?);
+        }
+        Ok(entries)
+    }
 
-    let content = serde_json::to_string_pretty(history).context("Не вдалося серіалізувати історію")?;
+    fn save(&self, entries: &[HistoryEntry]) -> Result<()> {
+        let mut conn = self.open()?;
+        let tx = conn.transaction().context("Не вдалося записати історію")?;
+        tx.execute("DELETE FROM entries", [])
+            .context("Не вдалося записати історію")?;
+        {
+            let mut stmt = tx
+                .prepare("INSERT INTO entries (position, id, timestamp, text, data) VALUES (?1, ?2, ?3, ?4, ?5)")
+                .context("Не вдалося записати історію")?;
+            for (position, entry) in entries.iter().enumerate() {
+                let data = serde_json::to_string(entry).context("Не вдалося серіалізувати історію")?;
+                stmt.execute(params![
+                    position as i64,
+                    entry.id,
+                    entry.timestamp.to_rfc3339(),
+                    entry.text,
+                    data
+                ])
+                .context("Не вдалося записати історію")?;
+            }
+        }
+        tx.commit().context("Не вдалося записати історію")
+    }
 
-    fs::write(&path, &content).with_context(|| format!("Не вдалося записати історію: {}", path.display()))?;
+    fn path(&self) -> &Path {
+        &self.path
+    }
+}
 
-    crate::app::config::set_owner_only_permissions(&path)?;
+/// JSON history encrypted with ChaCha20-Poly1305.
+///
+/// The file holds a fresh random nonce followed by the ciphertext. The key is
+/// generated on first save and kept next to the config, readable by the owner
+/// only: it protects copies of the history (backups, synced folders), not
+/// against someone who can read the user's files.
+struct EncryptedJsonStore {
+    path: PathBuf,
+    key_path: PathBuf,
+}
 
-    Ok(())
+impl EncryptedJsonStore {
+    fn cipher(&self, create: bool) -> Result<ChaCha20Poly1305> {
+        if let Some(key) = read_existing(&self.key_path)? {
+            if key.len() != 32 {
+                bail!("Пошкоджений ключ історії: {}", self.key_path.display());
+            }
+            return Ok(ChaCha20Poly1305::new(Key::from_slice(&key)));
+        }
+        if !create {
+            bail!("Не знайдено ключ історії: {}", self.key_path.display());
+        }
+        let key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        write_private(&self.key_path, &key)?;
+        Ok(ChaCha20Poly1305::new(&key))
+    }
+}
+
+impl HistoryStore for EncryptedJsonStore {
+    fn load(&self) -> Result<Vec<HistoryEntry>> {
+        let Some(content) = read_existing(&self.path)? else {
+            return Ok(Vec::new());
+        };
+        if content.len() < NONCE_LEN {
+            bail!("Пошкоджений файл історії: {}", self.path.display());
+        }
+        let (nonce, ciphertext) = content.split_at(NONCE_LEN);
+        let plaintext = self
+            .cipher(false)?
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| anyhow::anyhow!("Не вдалося розшифрувати історію: невірний ключ або пошкоджений файл"))?;
+        entries_from_json(&plaintext)
+    }
+
+    fn save(&self, entries: &[HistoryEntry]) -> Result<()> {
+        let cipher = self.cipher(true)?;
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let ciphertext = cipher
+            .encrypt(&nonce, entries_to_json(entries)?.as_slice())
+            .map_err(|_| anyhow::anyhow!("Не вдалося зашифрувати історію"))?;
+        let mut content = nonce.to_vec();
+        content.extend_from_slice(&ciphertext);
+        write_private(&self.path, &content)
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
 }
 
 #[cfg(test)]
@@ -112,4 +555,103 @@ mod tests {
         let _ = fs::remove_file(&path);
         let _ = fs::remove_dir(&dir);
     }
+
+    #[test]
+    fn test_history_backend_parse() {
+        for backend in HistoryBackend::ALL {
+            assert_eq!(HistoryBackend::parse(backend.as_str()), backend);
+        }
+        assert_eq!(HistoryBackend::parse("postgres"), HistoryBackend::Json);
+    }
+
+    #[test]
+    fn test_stores_round_trip_and_convert() {
+        let dir = std::env::temp_dir().join("s2t_test_history_stores");
+        let _ = fs::remove_dir_all(&dir);
+
+        let entries = vec![
+            HistoryEntry::new("Другий запис".to_string(), 3.0, "uk".to_string()),
+            HistoryEntry::new("First\nentry".to_string(), 5.0, "en".to_string()),
+        ];
+
+        for backend in HistoryBackend::ALL {
+            let store = create_store_in(backend, &dir, &dir);
+            assert!(store.load().unwrap().is_empty(), "{:?}: missing store", backend);
+            store.save(&entries).unwrap();
+            let loaded = store.load().unwrap();
+            assert_eq!(loaded.len(), 2, "{:?}", backend);
+            assert_eq!(loaded[0].id, entries[0].id);
+            assert_eq!(loaded[1].text, "First\nentry");
+            // Saving replaces the stored history
+            store.save(&entries[1..]).unwrap();
+            assert_eq!(store.load().unwrap().len(), 1, "{:?}", backend);
+        }
+
+        let json = create_store_in(HistoryBackend::Json, &dir, &dir);
+        let jsonl = create_store_in(HistoryBackend::Jsonl, &dir, &dir);
+        json.save(&entries).unwrap();
+        assert_eq!(convert_history(json.as_ref(), jsonl.as_ref()).unwrap(), 2);
+        assert_eq!(jsonl.load().unwrap()[0].text, "Другий запис");
+        // One line per entry
+        assert_eq!(fs::read_to_string(jsonl.path()).unwrap().lines().count(), 2);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_json_store_reads_existing_history_file() {
+        let dir = std::env::temp_dir().join("s2t_test_history_json_compat");
+        let _ = fs::create_dir_all(&dir);
+        let mut history = History::default();
+        history
+            .entries
+            .push(HistoryEntry::new("Test".to_string(), 5.0, "uk".to_string()));
+        fs::write(
+            dir.join("history.json"),
+            serde_json::to_string_pretty(&history).unwrap(),
+        )
+        .unwrap();
+
+        let loaded = create_store_in(HistoryBackend::Json, &dir, &dir).load().unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].text, "Test");
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_write_private_replaces_file_without_leftovers() {
+        let dir = std::env::temp_dir().join("s2t_test_history_atomic");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("history.jsonl");
+
+        write_private(&path, b"old").unwrap();
+        write_private(&path, b"new").unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+        let names: Vec<_> = fs::read_dir(&dir).unwrap().map(|e| e.unwrap().file_name()).collect();
+        assert_eq!(names, ["history.jsonl"]);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_read_history_file_of_any_format() {
+        let dir = std::env::temp_dir().join("s2t_test_history_import");
+        let _ = fs::remove_dir_all(&dir);
+        let entries = vec![HistoryEntry::new("Запис".to_string(), 3.0, "uk".to_string())];
+
+        for backend in [HistoryBackend::Json, HistoryBackend::Jsonl, HistoryBackend::Sqlite] {
+            let store = create_store_in(backend, &dir, &dir);
+            store.save(&entries).unwrap();
+            let read = read_history_file(store.path()).unwrap();
+            assert_eq!(read[0].id, entries[0].id, "{:?}", backend);
+        }
+
+        let export = dir.join("export.json");
+        fs::write(&export, serde_json::to_string(&entries).unwrap()).unwrap();
+        assert_eq!(read_history_file(&export).unwrap()[0].text, "Запис");
+        assert!(read_history_file(&dir.join("missing.json")).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
 }
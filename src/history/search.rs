@@ -0,0 +1,214 @@
+//! Ranked full-text search over history.
+//!
+//! The history is held in memory whatever the storage backend, so it is
+//! searched there too: every query scores all entries with BM25 over the
+//! words of the text, title and tags, titles and tags counting extra.
+//! An entry must contain every word of the query; a query word also
+//! matches longer words it begins, so results show up while typing.
+//!
+//! Words are compared in a normalized form: lowercase, without stress
+//! marks, with every apostrophe variant ("п'ять", "п’ять", "пʼять") and
+//! "ґ"/"г" treated alike, and with a common Ukrainian inflection ending
+//! removed, so "зустрічі" finds "зустріч" and "зустрічами".
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::HistoryEntry;
+
+/// BM25 term frequency saturation
+const K1: f32 = 1.2;
+
+/// BM25 document length normalization
+const B: f32 = 0.75;
+
+/// Weight of a title or tag word against a word of the text
+const TITLE_WEIGHT: f32 = 3.0;
+
+/// Weight of a word that only begins with the query word
+const PREFIX_WEIGHT: f32 = 0.5;
+
+/// Inflection endings, longest first; only one is removed
+const ENDINGS: &[&str] = &[
+    "ами", "ями", "ові", "еві", "ого", "ому", "ими", "іми", "ій", "ий", "ою", "ею", "ах", "ях", "ів", "їв", "ам", "ям",
+    "ом", "ем", "им", "ім", "ти", "ть", "а", "я", "о", "е", "у", "ю", "і", "ї", "и", "ь",
+];
+
+/// Shortest stem an ending is removed down to
+const MIN_STEM_CHARS: usize = 3;
+
+/// Normalized words of `text` (see the module documentation).
+pub fn search_terms(text: &str) -> Vec<String> {
+    text.unicode_words()
+        .map(normalize_word)
+        .filter(|w| !w.is_empty())
+        .collect()
+}
+
+fn normalize_word(word: &str) -> String {
+    let folded: String = word
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !matches!(c, '\'' | '’' | 'ʼ' | '`' | '\u{301}'))
+        .map(|c| if c == 'ґ' { 'г' } else { c })
+        .collect();
+    stem(&folded).to_string()
+}
+
+fn stem(word: &str) -> &str {
+    if !word.chars().any(is_cyrillic) {
+        return word;
+    }
+    for ending in ENDINGS {
+        if let Some(stem) = word.strip_suffix(ending) {
+            if stem.chars().count() >= MIN_STEM_CHARS {
+                return stem;
+            }
+        }
+    }
+    word
+}
+
+fn is_cyrillic(c: char) -> bool {
+    ('\u{400}'..='\u{4ff}').contains(&c)
+}
+
+/// Words of one entry with their weights.
+struct Document {
+    words: Vec<(String, f32)>,
+    length: f32,
+}
+
+impl Document {
+    fn new(entry: &HistoryEntry) -> Self {
+        let mut words: Vec<(String, f32)> = search_terms(&entry.text).into_iter().map(|w| (w, 1.0)).collect();
+        let extra = entry.title.iter().chain(&entry.tags);
+        words.extend(extra.flat_map(|t| search_terms(t)).map(|w| (w, TITLE_WEIGHT)));
+        let length = words.iter().map(|(_, weight)| weight).sum();
+        Self { words, length }
+    }
+
+    /// Weighted number of occurrences of `term`, prefix matches counting less.
+    fn frequency(&self, term: &str) -> f32 {
+        self.words
+            .iter()
+            .map(|(word, weight)| {
+                if word == term {
+                    *weight
+                } else if word.starts_with(term) {
+                    weight * PREFIX_WEIGHT
+                } else {
+                    0.0
+                }
+            })
+            .sum()
+    }
+}
+
+/// Entries containing every word of `query`, best match first.
+///
+/// Equally good matches keep their order in `entries`; a query without
+/// words matches everything.
+pub fn search<'a>(entries: &'a [HistoryEntry], query: &str) -> Vec<&'a HistoryEntry> {
+    let terms = search_terms(query);
+    if terms.is_empty() {
+        return entries.iter().collect();
+    }
+
+    let documents: Vec<Document> = entries.iter().map(Document::new).collect();
+    let average_length = documents.iter().map(|d| d.length).sum::<f32>() / documents.len().max(1) as f32;
+    // Term frequencies per document, one row per term
+    let frequencies: Vec<Vec<f32>> = terms
+        .iter()
+        .map(|term| documents.iter().map(|d| d.frequency(term)).collect())
+        .collect();
+
+    let total = documents.len() as f32;
+    let mut scored: Vec<(usize, f32)> = (0..documents.len())
+        .filter(|&i| frequencies.iter().all(|row| row[i] > 0.0))
+        .map(|i| {
+            let norm = K1 * (1.0 - B + B * documents[i].length / average_length.max(1.0));
+            let score = frequencies
+                .iter()
+                .map(|row| {
+                    let containing = row.iter().filter(|&&f| f > 0.0).count() as f32;
+                    let idf = (1.0 + (total - containing + 0.5) / (containing + 0.5)).ln();
+                    idf * row[i] * (K1 + 1.0) / (row[i] + norm)
+                })
+                .sum();
+            (i, score)
+        })
+        .collect();
+    scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+    scored.into_iter().map(|(i, _)| &entries[i]).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(text: &str) -> HistoryEntry {
+        HistoryEntry::new(text.to_string(), 5.0, "uk".to_string())
+    }
+
+    fn texts<'a>(results: &[&'a HistoryEntry]) -> Vec<&'a str> {
+        results.iter().map(|e| e.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_search_terms_normalize_ukrainian() {
+        assert_eq!(search_terms("П’ять м'ят"), search_terms("пʼять мят"));
+        assert_eq!(search_terms("Ґанок"), search_terms("ганок"));
+        assert_eq!(search_terms("зу\u{301}стріч"), vec!["зустріч"]);
+        assert_eq!(search_terms("зустрічі зустрічами"), vec!["зустріч", "зустріч"]);
+        // Short words and other scripts keep their endings
+        assert_eq!(search_terms("та apples"), vec!["та", "apples"]);
+    }
+
+    #[test]
+    fn test_search_finds_inflected_forms() {
+        let entries = vec![entry("Після зустрічі з клієнтом"), entry("Кава"), entry("Три зустрічі")];
+        let results = search(&entries, "зустріч");
+        assert_eq!(texts(&results), vec!["Три зустрічі", "Після зустрічі з клієнтом"]);
+    }
+
+    #[test]
+    fn test_search_matches_prefixes() {
+        let entries = vec![entry("Бюджет на квартал"), entry("Кава")];
+        assert_eq!(texts(&search(&entries, "бюдж")), vec!["Бюджет на квартал"]);
+    }
+
+    #[test]
+    fn test_search_needs_every_word() {
+        let entries = vec![entry("звіт за березень"), entry("звіт за квітень")];
+        assert_eq!(texts(&search(&entries, "звіт квітень")), vec!["звіт за квітень"]);
+    }
+
+    #[test]
+    fn test_search_ranks_better_matches_first() {
+        let mut titled = entry("Обговорили плани");
+        titled.title = Some("Бюджет".to_string());
+        let entries = vec![
+            entry("Кава, потім трохи про бюджет і ще багато інших справ дня"),
+            titled,
+            entry("Бюджет, бюджет і ще раз бюджет"),
+        ];
+        let results = search(&entries, "бюджет");
+        assert_eq!(results.len(), 3);
+        assert_eq!(
+            results[2].text,
+            "Кава, потім трохи про бюджет і ще багато інших справ дня"
+        );
+    }
+
+    #[test]
+    fn test_search_exact_word_beats_prefix() {
+        let entries = vec![entry("planning"), entry("plan")];
+        assert_eq!(texts(&search(&entries, "plan")), vec!["plan", "planning"]);
+    }
+
+    #[test]
+    fn test_search_without_words_matches_everything() {
+        let entries = vec![entry("один"), entry("два")];
+        assert_eq!(search(&entries, "  ,. ").len(), 2);
+    }
+}
@@ -0,0 +1,154 @@
+//! Usage statistics over the history: dictation time, words per day, the
+//! languages and models used and how fast transcription ran.
+//!
+//! The real-time factor is the transcription time over the audio time of
+//! the entries it was measured for; older entries and re-transcribed ones
+//! have no time and are left out of it.
+
+use chrono::{Local, NaiveDate};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use super::HistoryEntry;
+
+/// Totals and rankings computed from history entries.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct HistoryStats {
+    pub entries: usize,
+    /// Recorded audio, in seconds
+    pub total_secs: f64,
+    pub total_words: usize,
+    /// Words dictated on each local day with entries, oldest first
+    pub words_per_day: Vec<(NaiveDate, usize)>,
+    /// Entries per language, most used first
+    pub languages: Vec<(String, usize)>,
+    /// Entries per model, most used first; entries without a model are left out
+    pub models: Vec<(String, usize)>,
+    /// Transcription time over audio time, if measured for any entry
+    pub average_rtf: Option<f64>,
+    /// Entries the real-time factor was measured for
+    pub measured_entries: usize,
+}
+
+impl HistoryStats {
+    pub fn from_entries<'a>(entries: impl IntoIterator<Item = &'a HistoryEntry>) -> Self {
+        let mut stats = Self::default();
+        let mut days: BTreeMap<NaiveDate, usize> = BTreeMap::new();
+        let mut languages: HashMap<&str, usize> = HashMap::new();
+        let mut models: HashMap<&str, usize> = HashMap::new();
+        let mut measured_audio = 0.0;
+        let mut processing = 0.0;
+
+        for entry in entries {
+            let words = entry.word_count();
+            stats.entries += 1;
+            stats.total_secs += entry.duration_secs as f64;
+            stats.total_words += words;
+            *days
+                .entry(entry.timestamp.with_timezone(&Local).date_naive())
+                .or_default() += words;
+            *languages.entry(&entry.language).or_default() += 1;
+            if let Some(model) = &entry.model {
+                *models.entry(model).or_default() += 1;
+            }
+            if let Some(secs) = entry.processing_secs.filter(|_| entry.duration_secs > 0.0) {
+                measured_audio += entry.duration_secs as f64;
+                processing += secs as f64;
+                stats.measured_entries += 1;
+            }
+        }
+
+        stats.words_per_day = days.into_iter().collect();
+        stats.languages = ranked(languages);
+        stats.models = ranked(models);
+        stats.average_rtf = (measured_audio > 0.0).then(|| processing / measured_audio);
+        stats
+    }
+
+    /// Total recorded time as H:MM:SS.
+    pub fn formatted_total_time(&self) -> String {
+        let secs = self.total_secs.round() as u64;
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    }
+
+    /// Average words over the days with entries.
+    pub fn average_words_per_day(&self) -> Option<f64> {
+        (!self.words_per_day.is_empty()).then(|| self.total_words as f64 / self.words_per_day.len() as f64)
+    }
+
+    pub fn most_used_language(&self) -> Option<&(String, usize)> {
+        self.languages.first()
+    }
+
+    pub fn most_used_model(&self) -> Option<&(String, usize)> {
+        self.models.first()
+    }
+
+    /// Words per day of the last `days` days with entries.
+    pub fn recent_days(&self, days: usize) -> &[(NaiveDate, usize)] {
+        &self.words_per_day[self.words_per_day.len().saturating_sub(days)..]
+    }
+}
+
+/// Counts by name, highest first, ties by name.
+fn ranked(counts: HashMap<&str, usize>) -> Vec<(String, usize)> {
+    let mut ranked: Vec<(String, usize)> = counts.into_iter().map(|(name, n)| (name.to_string(), n)).collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn entry(text: &str, day: u32, duration_secs: f32, language: &str, model: Option<&str>) -> HistoryEntry {
+        let mut entry = HistoryEntry::new(text.to_string(), duration_secs, language.to_string());
+        entry.timestamp = Utc.with_ymd_and_hms(2025, 3, day, 12, 0, 0).unwrap();
+        entry.model = model.map(str::to_string);
+        entry
+    }
+
+    #[test]
+    fn test_stats_totals_and_rankings() {
+        let mut measured = entry("раз два три", 14, 10.0, "uk", Some("Base"));
+        measured.processing_secs = Some(2.0);
+        let mut also_measured = entry("чотири п'ять", 15, 30.0, "uk", Some("Large v3"));
+        also_measured.processing_secs = Some(18.0);
+        let entries = vec![
+            measured,
+            also_measured,
+            entry("one two", 15, 20.0, "en", Some("Base")),
+            entry("шість", 17, 5.0, "uk", None),
+        ];
+
+        let stats = HistoryStats::from_entries(&entries);
+        assert_eq!(stats.entries, 4);
+        assert_eq!(stats.total_secs, 65.0);
+        assert_eq!(stats.formatted_total_time(), "0:01:05");
+        assert_eq!(stats.total_words, 8);
+        assert_eq!(stats.words_per_day.len(), 3);
+        assert_eq!(stats.words_per_day.iter().map(|(_, words)| words).sum::<usize>(), 8);
+        assert!(stats.words_per_day.windows(2).all(|days| days[0].0 < days[1].0));
+        assert_eq!(stats.average_words_per_day(), Some(8.0 / 3.0));
+        assert_eq!(stats.recent_days(2), &stats.words_per_day[1..]);
+
+        assert_eq!(stats.most_used_language(), Some(&("uk".to_string(), 3)));
+        assert_eq!(stats.languages[1], ("en".to_string(), 1));
+        assert_eq!(stats.most_used_model(), Some(&("Base".to_string(), 2)));
+        assert_eq!(stats.models.len(), 2);
+
+        // 20 s of transcription for 40 s of measured audio
+        assert_eq!(stats.average_rtf, Some(0.5));
+        assert_eq!(stats.measured_entries, 2);
+    }
+
+    #[test]
+    fn test_stats_of_empty_history() {
+        let stats = HistoryStats::from_entries(std::iter::empty());
+        assert_eq!(stats, HistoryStats::default());
+        assert_eq!(stats.average_words_per_day(), None);
+        assert_eq!(stats.average_rtf, None);
+        assert!(stats.recent_days(7).is_empty());
+    }
+}
@@ -0,0 +1,138 @@
+//! Keeping the history whole when its directory is synced between devices
+//! (Syncthing, Nextcloud and the like).
+//!
+//! Entries are matched by ID. Before a save the store on disk is read
+//! again: entries another device added since it was loaded are kept, and
+//! entries removed here stay removed. When both devices changed the same
+//! entry, the one saving wins. Conflict copies the sync tool leaves next to
+//! the store (`history.sync-conflict-….json`,
+//! `history (conflicted copy …).json`) are merged the same way and deleted
+//! once the merged history is saved.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::{History, HistoryEntry};
+use crate::domain::traits::HistoryRepository;
+
+/// Markers sync tools put into the names of conflict copies
+const CONFLICT_MARKERS: [&str; 2] = [".sync-conflict-", "conflicted copy"];
+
+/// Conflict copies of the store file, sorted by name.
+///
+/// A copy has the stem and the last extension of the store in its name,
+/// so copies of the other backends are left alone.
+pub fn conflict_copies(store_path: &Path) -> Vec<PathBuf> {
+    let Some(name) = store_path.file_name().and_then(|name| name.to_str()) else {
+        return Vec::new();
+    };
+    let stem = name.split_once('.').map_or(name, |(stem, _)| stem);
+    let extension = name.rsplit_once('.').map_or("", |(_, extension)| extension);
+    let Ok(dir) = fs::read_dir(store_path.parent().unwrap_or(Path::new("."))) else {
+        return Vec::new();
+    };
+    let mut copies: Vec<PathBuf> = dir
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            let file_name = entry.file_name();
+            let Some(file_name) = file_name.to_str() else {
+                return false;
+            };
+            file_name != name
+                && file_name.starts_with(stem)
+                && file_name.ends_with(&format!(".{}", extension))
+                && CONFLICT_MARKERS.iter().any(|marker| file_name.contains(marker))
+        })
+        .map(|entry| entry.path())
+        .collect();
+    copies.sort();
+    copies
+}
+
+/// The entries to write for `history`: its own, plus those of `stored`
+/// (read from the store and conflict copies) it neither has nor removed.
+pub(super) fn merge_stored(history: &History, stored: Vec<HistoryEntry>) -> Vec<HistoryEntry> {
+    let own: HashSet<&str> = history
+        .entries
+        .iter()
+        .chain(&history.trash)
+        .map(|e| e.id.as_str())
+        .collect();
+    let added: Vec<HistoryEntry> = stored
+        .into_iter()
+        .filter(|e| !own.contains(e.id.as_str()) && !history.removed.contains(&e.id))
+        .collect();
+
+    let mut merged = History {
+        entries: history.entries.clone(),
+        trash: history.trash.clone(),
+        ..History::default()
+    };
+    if !added.is_empty() {
+        merged.import(added);
+    }
+    merged.entries.into_iter().chain(merged.trash).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{TimeZone, Utc};
+
+    fn entry_at(text: &str, hour: u32) -> HistoryEntry {
+        let mut entry = HistoryEntry::new(text.to_string(), 1.0, "uk".to_string());
+        entry.timestamp = Utc.with_ymd_and_hms(2025, 3, 14, hour, 0, 0).unwrap();
+        entry
+    }
+
+    #[test]
+    fn test_merge_stored_keeps_other_devices_entries() {
+        let shared = entry_at("Спільний", 9);
+        let removed_here = entry_at("Видалений тут", 10);
+        let mut history = History::default();
+        history.import(vec![shared.clone(), removed_here.clone(), entry_at("Новий тут", 12)]);
+        history.remove(&removed_here.id);
+
+        let mut edited = shared.clone();
+        edited.text = "Змінений деінде".to_string();
+        let from_other_device = entry_at("Новий деінде", 11);
+        let stored = vec![edited, removed_here, from_other_device.clone()];
+
+        let merged = merge_stored(&history, stored);
+        let texts: Vec<&str> = merged.iter().map(|e| e.text.as_str()).collect();
+        assert_eq!(texts, ["Новий тут", "Новий деінде", "Спільний"]);
+        assert_eq!(merged[1].id, from_other_device.id);
+    }
+
+    #[test]
+    fn test_conflict_copies_of_the_store_only() {
+        let dir = std::env::temp_dir().join("s2t_test_history_conflicts");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "history.json",
+            "history.sync-conflict-20250314-120000-ABCDEFG.json",
+            "history (conflicted copy 2025-03-14 120000).json",
+            "history.sync-conflict-20250314-120000-ABCDEFG.jsonl",
+            ".history.json.tmp",
+            "notes (conflicted copy).json",
+        ] {
+            fs::write(dir.join(name), "[]").unwrap();
+        }
+
+        let copies: Vec<String> = conflict_copies(&dir.join("history.json"))
+            .iter()
+            .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            copies,
+            [
+                "history (conflicted copy 2025-03-14 120000).json",
+                "history.sync-conflict-20250314-120000-ABCDEFG.json"
+            ]
+        );
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
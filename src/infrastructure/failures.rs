@@ -0,0 +1,123 @@
+//! Counters of failures the app only logs.
+//!
+//! A paste that did not happen, a history save that failed or a segment
+//! dropped on a full channel do not stop dictation: they are written to
+//! stderr and the session goes on. Nobody reads stderr of a tray app, so each
+//! such path also bumps a counter here. The counters live for the session and
+//! are shown in the model dialog and by `doctor`, which asks the running app
+//! for them over the local socket.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Failure paths that are counted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Failure {
+    /// Automatic paste into the active window
+    Paste,
+    HistorySave,
+    /// Segment not handed to the transcription queue
+    SegmentSend,
+    /// No display to put the text on the clipboard
+    Clipboard,
+}
+
+static PASTE: AtomicU64 = AtomicU64::new(0);
+static HISTORY_SAVE: AtomicU64 = AtomicU64::new(0);
+static SEGMENT_SEND: AtomicU64 = AtomicU64::new(0);
+static CLIPBOARD: AtomicU64 = AtomicU64::new(0);
+
+impl Failure {
+    fn counter(self) -> &'static AtomicU64 {
+        match self {
+            Self::Paste => &PASTE,
+            Self::HistorySave => &HISTORY_SAVE,
+            Self::SegmentSend => &SEGMENT_SEND,
+            Self::Clipboard => &CLIPBOARD,
+        }
+    }
+}
+
+/// Count one occurrence of `failure`.
+pub fn record(failure: Failure) {
+    failure.counter().fetch_add(1, Ordering::Relaxed);
+}
+
+/// Counters since the app started.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FailureCounts {
+    pub paste: u64,
+    pub history_save: u64,
+    pub segment_send: u64,
+    pub clipboard: u64,
+}
+
+impl FailureCounts {
+    pub fn total(&self) -> u64 {
+        self.paste + self.history_save + self.segment_send + self.clipboard
+    }
+
+    /// Non-zero counters with their names, for display.
+    pub fn lines(&self) -> Vec<(&'static str, u64)> {
+        [
+            ("вставка", self.paste),
+            ("збереження історії", self.history_save),
+            ("передача сегментів", self.segment_send),
+            ("буфер обміну", self.clipboard),
+        ]
+        .into_iter()
+        .filter(|&(_, count)| count > 0)
+        .collect()
+    }
+
+    /// One line for the model dialog.
+    pub fn summary(&self) -> String {
+        if self.total() == 0 {
+            return "Прихованих збоїв за сеанс не було".to_string();
+        }
+        let parts: Vec<String> = self
+            .lines()
+            .iter()
+            .map(|(name, count)| format!("{} — {}", name, count))
+            .collect();
+        format!("Приховані збої за сеанс: {}", parts.join(", "))
+    }
+}
+
+pub fn snapshot() -> FailureCounts {
+    FailureCounts {
+        paste: PASTE.load(Ordering::Relaxed),
+        history_save: HISTORY_SAVE.load(Ordering::Relaxed),
+        segment_send: SEGMENT_SEND.load(Ordering::Relaxed),
+        clipboard: CLIPBOARD.load(Ordering::Relaxed),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_increments_counter() {
+        // Other tests may record too; only the difference is checked
+        let before = snapshot().clipboard;
+        record(Failure::Clipboard);
+        record(Failure::Clipboard);
+        assert!(snapshot().clipboard >= before + 2);
+    }
+
+    #[test]
+    fn test_summary_lists_nonzero_counters() {
+        assert_eq!(FailureCounts::default().summary(), "Прихованих збоїв за сеанс не було");
+        let counts = FailureCounts {
+            paste: 3,
+            segment_send: 1,
+            ..Default::default()
+        };
+        assert_eq!(counts.total(), 4);
+        assert_eq!(
+            counts.summary(),
+            "Приховані збої за сеанс: вставка — 3, передача сегментів — 1"
+        );
+    }
+}
@@ -0,0 +1,254 @@
+//! Host hardware detection for model recommendations and build diagnostics.
+
+use crate::infrastructure::models::ModelFamily;
+use std::fs;
+
+const GB: u64 = 1024 * 1024 * 1024;
+
+/// Detected host resources relevant to model selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HardwareInfo {
+    /// Total physical memory in bytes (0 if unknown).
+    pub total_ram_bytes: u64,
+    /// Number of logical CPU cores available to the process.
+    pub cpu_cores: usize,
+}
+
+impl HardwareInfo {
+    /// Detect RAM (from /proc/meminfo) and available CPU parallelism.
+    pub fn detect() -> Self {
+        let total_ram_bytes = fs::read_to_string("/proc/meminfo")
+            .ok()
+            .and_then(|content| parse_meminfo_total(&content))
+            .unwrap_or(0);
+        let cpu_cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+        Self {
+            total_ram_bytes,
+            cpu_cores,
+        }
+    }
+
+    /// Recommended Whisper model filename for this machine.
+    ///
+    /// Picks the largest quantized model the machine can run comfortably;
+    /// unknown RAM falls back to the default balanced model.
+    pub fn recommended_model(&self) -> &'static str {
+        if self.total_ram_bytes == 0 {
+            return "ggml-base-q5_1.bin";
+        }
+        match (self.total_ram_bytes, self.cpu_cores) {
+            (ram, cores) if ram >= 16 * GB && cores >= 8 => "ggml-large-v3-turbo-q5_0.bin",
+            (ram, cores) if ram >= 8 * GB && cores >= 4 => "ggml-small-q5_1.bin",
+            (ram, _) if ram >= 4 * GB => "ggml-base-q5_1.bin",
+            _ => "ggml-tiny-q5_1.bin",
+        }
+    }
+
+    /// Whether a model family is likely too heavy for this machine.
+    pub fn is_too_heavy(&self, family: ModelFamily) -> bool {
+        if self.total_ram_bytes == 0 {
+            return false;
+        }
+        let min_ram = match family {
+            ModelFamily::Tiny | ModelFamily::Base => 0,
+            ModelFamily::Small => 4 * GB,
+            ModelFamily::Medium | ModelFamily::LargeTurbo | ModelFamily::Distil => 8 * GB,
+            ModelFamily::Large => 12 * GB,
+        };
+        self.total_ram_bytes < min_ram
+    }
+
+    /// Short human-readable summary, e.g. "16.0 GB RAM, 8 ядер".
+    pub fn summary(&self) -> String {
+        if self.total_ram_bytes == 0 {
+            return format!("{} ядер", self.cpu_cores);
+        }
+        format!(
+            "{:.1} GB RAM, {} ядер",
+            self.total_ram_bytes as f64 / GB as f64,
+            self.cpu_cores
+        )
+    }
+}
+
+/// SIMD extensions that speed up whisper.cpp inference.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CpuFeatures {
+    pub avx: bool,
+    pub avx2: bool,
+    pub avx512: bool,
+    pub fma: bool,
+    pub f16c: bool,
+    pub neon: bool,
+}
+
+impl CpuFeatures {
+    /// Extensions supported by the processor running the app.
+    pub fn detect() -> Self {
+        #[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+        {
+            Self {
+                avx: is_x86_feature_detected!("avx"),
+                avx2: is_x86_feature_detected!("avx2"),
+                avx512: is_x86_feature_detected!("avx512f"),
+                fma: is_x86_feature_detected!("fma"),
+                f16c: is_x86_feature_detected!("f16c"),
+                neon: false,
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        {
+            Self {
+                neon: std::arch::is_aarch64_feature_detected!("neon"),
+                ..Self::default()
+            }
+        }
+        #[cfg(not(any(target_arch = "x86", target_arch = "x86_64", target_arch = "aarch64")))]
+        {
+            Self::default()
+        }
+    }
+
+    /// Extensions whisper.cpp was compiled with, from its system info line
+    /// (`"AVX = 1 | AVX2 = 1 | AVX512 = 0 | FMA = 1 | NEON = 0 | ..."`).
+    pub fn from_system_info(info: &str) -> Self {
+        let enabled = |name: &str| {
+            info.split('|')
+                .filter_map(|item| item.split_once('='))
+                .any(|(key, value)| key.trim() == name && value.trim() == "1")
+        };
+        Self {
+            avx: enabled("AVX"),
+            avx2: enabled("AVX2"),
+            avx512: enabled("AVX512"),
+            fma: enabled("FMA"),
+            f16c: enabled("F16C"),
+            neon: enabled("NEON"),
+        }
+    }
+
+    fn flags(&self) -> [(bool, &'static str); 6] {
+        [
+            (self.avx, "AVX"),
+            (self.avx2, "AVX2"),
+            (self.avx512, "AVX-512"),
+            (self.fma, "FMA"),
+            (self.f16c, "F16C"),
+            (self.neon, "NEON"),
+        ]
+    }
+
+    /// Names of the available extensions.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.flags()
+            .into_iter()
+            .filter_map(|(available, name)| available.then_some(name))
+            .collect()
+    }
+
+    /// Extensions available here but missing from `build`, i.e. speed the
+    /// build leaves unused on this processor.
+    pub fn unused_by(&self, build: &CpuFeatures) -> Vec<&'static str> {
+        self.flags()
+            .into_iter()
+            .zip(build.flags())
+            .filter_map(|((available, name), (built, _))| (available && !built).then_some(name))
+            .collect()
+    }
+
+    /// Comma-separated names, or "базові інструкції" without any extension.
+    pub fn summary(&self) -> String {
+        let names = self.names();
+        if names.is_empty() {
+            "базові інструкції".to_string()
+        } else {
+            names.join(", ")
+        }
+    }
+}
+
+/// Parse the `MemTotal:` line of /proc/meminfo (value in kB) into bytes.
+fn parse_meminfo_total(content: &str) -> Option<u64> {
+    content
+        .lines()
+        .find(|line| line.starts_with("MemTotal:"))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kb| kb.parse::<u64>().ok())
+        .map(|kb| kb * 1024)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::models::get_available_models;
+
+    fn is_in_catalog(filename: &str) -> bool {
+        get_available_models().iter().any(|m| m.filename == filename)
+    }
+
+    fn hw(ram_gb: u64, cores: usize) -> HardwareInfo {
+        HardwareInfo {
+            total_ram_bytes: ram_gb * GB,
+            cpu_cores: cores,
+        }
+    }
+
+    #[test]
+    fn test_parse_meminfo_total() {
+        let content = "MemTotal:       16318480 kB\nMemFree:         1234 kB\n";
+        assert_eq!(parse_meminfo_total(content), Some(16_318_480 * 1024));
+        assert_eq!(parse_meminfo_total("garbage"), None);
+    }
+
+    #[test]
+    fn test_recommendation_scales_with_hardware() {
+        assert_eq!(hw(2, 2).recommended_model(), "ggml-tiny-q5_1.bin");
+        assert_eq!(hw(4, 2).recommended_model(), "ggml-base-q5_1.bin");
+        assert_eq!(hw(8, 4).recommended_model(), "ggml-small-q5_1.bin");
+        assert_eq!(hw(32, 16).recommended_model(), "ggml-large-v3-turbo-q5_0.bin");
+    }
+
+    #[test]
+    fn test_recommendation_unknown_ram() {
+        assert_eq!(hw(0, 8).recommended_model(), "ggml-base-q5_1.bin");
+    }
+
+    #[test]
+    fn test_recommendations_exist_in_catalog() {
+        for info in [hw(0, 1), hw(2, 2), hw(4, 2), hw(8, 4), hw(32, 16)] {
+            assert!(is_in_catalog(info.recommended_model()));
+        }
+    }
+
+    #[test]
+    fn test_cpu_features_from_system_info() {
+        let info = "AVX = 1 | AVX2 = 1 | AVX512 = 0 | FMA = 1 | NEON = 0 | ARM_FMA = 0 | F16C = 1 | FP16_VA = 0 | ";
+        let build = CpuFeatures::from_system_info(info);
+        assert_eq!(build.names(), vec!["AVX", "AVX2", "FMA", "F16C"]);
+        assert_eq!(CpuFeatures::from_system_info("garbage"), CpuFeatures::default());
+    }
+
+    #[test]
+    fn test_cpu_features_unused_by_build() {
+        let cpu = CpuFeatures {
+            avx: true,
+            avx2: true,
+            avx512: true,
+            fma: true,
+            f16c: true,
+            neon: false,
+        };
+        let build = CpuFeatures { avx512: false, ..cpu };
+        assert_eq!(cpu.unused_by(&build), vec!["AVX-512"]);
+        assert!(build.unused_by(&cpu).is_empty());
+        assert_eq!(CpuFeatures::default().summary(), "базові інструкції");
+    }
+
+    #[test]
+    fn test_is_too_heavy() {
+        assert!(hw(4, 4).is_too_heavy(ModelFamily::Large));
+        assert!(!hw(16, 8).is_too_heavy(ModelFamily::Large));
+        assert!(!hw(1, 1).is_too_heavy(ModelFamily::Tiny));
+        assert!(!hw(0, 1).is_too_heavy(ModelFamily::Large));
+    }
+}
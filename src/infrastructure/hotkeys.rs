@@ -1,5 +1,7 @@
 use crate::app::config::Config;
+use crate::infrastructure::keygrab::ScancodeGrab;
 use anyhow::{Context, Result};
+use async_channel::Sender;
 use global_hotkey::{
     hotkey::{Code, HotKey, Modifiers},
     GlobalHotKeyManager,
@@ -8,15 +10,22 @@ use global_hotkey::{
 pub struct HotkeyManager {
     manager: GlobalHotKeyManager,
     current_hotkey: Option<HotKey>,
+    /// Physical-key grab used when `hotkey_layout_independent` is on.
+    scancode_grab: Option<ScancodeGrab>,
+    /// Signalled on press of a layout-independent hotkey.
+    /// (Layout-dependent hotkeys report through `GlobalHotKeyEvent`.)
+    toggle_tx: Sender<()>,
 }
 
 impl HotkeyManager {
-    pub fn new() -> Result<Self> {
+    pub fn new(toggle_tx: Sender<()>) -> Result<Self> {
         let manager = GlobalHotKeyManager::new().context("Не вдалося створити менеджер гарячих клавіш")?;
 
         Ok(Self {
             manager,
             current_hotkey: None,
+            scancode_grab: None,
+            toggle_tx,
         })
     }
 
@@ -25,15 +34,26 @@ impl HotkeyManager {
         if let Some(hotkey) = self.current_hotkey.take() {
             let _ = self.manager.unregister(hotkey);
         }
+        self.scancode_grab = None;
 
         if !config.hotkey_enabled {
             return Ok(());
         }
 
         // Parse hotkey string (e.g., "Control+Shift+Space")
-        let (modifiers, code) = parse_hotkey(&config.hotkey).context("Не вдалося розпарсити гарячу клавішу")?;
+        let (modifiers, key) = parse_hotkey(&config.hotkey).context("Не вдалося розпарсити гарячу клавішу")?;
 
-        let hotkey = HotKey::new(Some(modifiers), code);
+        if config.hotkey_layout_independent {
+            let toggle_tx = self.toggle_tx.clone();
+            let grab = ScancodeGrab::start(key.scancode, modifiers, move || {
+                let _ = toggle_tx.try_send(());
+            })
+            .context("Не вдалося зареєструвати гарячу клавішу")?;
+            self.scancode_grab = Some(grab);
+            return Ok(());
+        }
+
+        let hotkey = HotKey::new(Some(modifiers), key.code);
         self.manager
             .register(hotkey)
             .context("Не вдалося зареєструвати гарячу клавішу")?;
@@ -46,6 +66,7 @@ impl HotkeyManager {
     /// before registering a new hotkey, and by `Drop`. Available for explicit cleanup.
     #[allow(dead_code)]
     pub fn unregister(&mut self) -> Result<()> {
+        self.scancode_grab = None;
         if let Some(hotkey) = self.current_hotkey.take() {
             self.manager
                 .unregister(hotkey)
@@ -63,9 +84,143 @@ impl Drop for HotkeyManager {
     }
 }
 
-fn parse_hotkey(hotkey_str: &str) -> Result<(Modifiers, Code)> {
+/// A physical key and what it types in the supported layouts.
+pub struct PhysicalKey {
+    pub code: Code,
+    /// Linux evdev scancode (layout-independent).
+    pub scancode: u32,
+    /// Canonical name, as stored in the config (US layout label).
+    pub name: &'static str,
+    /// Label in the Ukrainian (ЙЦУКЕН) layout, if it differs.
+    pub ukrainian: Option<&'static str>,
+}
+
+const fn key(code: Code, scancode: u32, name: &'static str, ukrainian: Option<&'static str>) -> PhysicalKey {
+    PhysicalKey {
+        code,
+        scancode,
+        name,
+        ukrainian,
+    }
+}
+
+/// Keys that can be used in a hotkey.
+static PHYSICAL_KEYS: &[PhysicalKey] = &[
+    key(Code::Escape, 1, "Escape", None),
+    key(Code::Digit1, 2, "1", None),
+    key(Code::Digit2, 3, "2", None),
+    key(Code::Digit3, 4, "3", None),
+    key(Code::Digit4, 5, "4", None),
+    key(Code::Digit5, 6, "5", None),
+    key(Code::Digit6, 7, "6", None),
+    key(Code::Digit7, 8, "7", None),
+    key(Code::Digit8, 9, "8", None),
+    key(Code::Digit9, 10, "9", None),
+    key(Code::Digit0, 11, "0", None),
+    key(Code::Backspace, 14, "Backspace", None),
+    key(Code::Tab, 15, "Tab", None),
+    key(Code::KeyQ, 16, "Q", Some("Й")),
+    key(Code::KeyW, 17, "W", Some("Ц")),
+    key(Code::KeyE, 18, "E", Some("У")),
+    key(Code::KeyR, 19, "R", Some("К")),
+    key(Code::KeyT, 20, "T", Some("Е")),
+    key(Code::KeyY, 21, "Y", Some("Н")),
+    key(Code::KeyU, 22, "U", Some("Г")),
+    key(Code::KeyI, 23, "I", Some("Ш")),
+    key(Code::KeyO, 24, "O", Some("Щ")),
+    key(Code::KeyP, 25, "P", Some("З")),
+    key(Code::BracketLeft, 26, "[", Some("Х")),
+    key(Code::BracketRight, 27, "]", Some("Ї")),
+    key(Code::Enter, 28, "Enter", None),
+    key(Code::KeyA, 30, "A", Some("Ф")),
+    key(Code::KeyS, 31, "S", Some("І")),
+    key(Code::KeyD, 32, "D", Some("В")),
+    key(Code::KeyF, 33, "F", Some("А")),
+    key(Code::KeyG, 34, "G", Some("П")),
+    key(Code::KeyH, 35, "H", Some("Р")),
+    key(Code::KeyJ, 36, "J", Some("О")),
+    key(Code::KeyK, 37, "K", Some("Л")),
+    key(Code::KeyL, 38, "L", Some("Д")),
+    key(Code::Semicolon, 39, ";", Some("Ж")),
+    key(Code::Quote, 40, "'", Some("Є")),
+    key(Code::Backquote, 41, "`", None),
+    key(Code::Backslash, 43, "\\", Some("Ґ")),
+    key(Code::KeyZ, 44, "Z", Some("Я")),
+    key(Code::KeyX, 45, "X", Some("Ч")),
+    key(Code::KeyC, 46, "C", Some("С")),
+    key(Code::KeyV, 47, "V", Some("М")),
+    key(Code::KeyB, 48, "B", Some("И")),
+    key(Code::KeyN, 49, "N", Some("Т")),
+    key(Code::KeyM, 50, "M", Some("Ь")),
+    key(Code::Comma, 51, ",", Some("Б")),
+    key(Code::Period, 52, ".", Some("Ю")),
+    key(Code::Slash, 53, "/", None),
+    key(Code::Space, 57, "Space", None),
+    key(Code::F1, 59, "F1", None),
+    key(Code::F2, 60, "F2", None),
+    key(Code::F3, 61, "F3", None),
+    key(Code::F4, 62, "F4", None),
+    key(Code::F5, 63, "F5", None),
+    key(Code::F6, 64, "F6", None),
+    key(Code::F7, 65, "F7", None),
+    key(Code::F8, 66, "F8", None),
+    key(Code::F9, 67, "F9", None),
+    key(Code::F10, 68, "F10", None),
+    key(Code::F11, 87, "F11", None),
+    key(Code::F12, 88, "F12", None),
+    key(Code::Home, 102, "Home", None),
+    key(Code::ArrowUp, 103, "Up", None),
+    key(Code::PageUp, 104, "PageUp", None),
+    key(Code::ArrowLeft, 105, "Left", None),
+    key(Code::ArrowRight, 106, "Right", None),
+    key(Code::End, 107, "End", None),
+    key(Code::ArrowDown, 108, "Down", None),
+    key(Code::PageDown, 109, "PageDown", None),
+    key(Code::Delete, 111, "Delete", None),
+];
+
+/// Find a physical key by its evdev scancode.
+pub fn key_for_scancode(scancode: u32) -> Option<&'static PhysicalKey> {
+    PHYSICAL_KEYS.iter().find(|k| k.scancode == scancode)
+}
+
+/// Build a canonical hotkey string (e.g. "Control+Shift+A") from a captured key press.
+///
+/// Returns `None` for keys that cannot be used in a hotkey.
+pub fn format_hotkey(modifiers: Modifiers, scancode: u32) -> Option<String> {
+    let key = key_for_scancode(scancode)?;
+    let mut parts = modifier_names(modifiers);
+    parts.push(key.name);
+    Some(parts.join("+"))
+}
+
+/// Describe a hotkey in both layouts, e.g. "Control+Shift+A (укр. Control+Shift+Ф)".
+pub fn describe_hotkey(hotkey_str: &str) -> Result<String> {
+    let (modifiers, key) = parse_hotkey(hotkey_str)?;
+    let mods = modifier_names(modifiers);
+    let with_key = |name: &str| mods.iter().copied().chain([name]).collect::<Vec<_>>().join("+");
+    Ok(match key.ukrainian {
+        Some(ukrainian) => format!("{} (укр. {})", with_key(key.name), with_key(ukrainian)),
+        None => with_key(key.name),
+    })
+}
+
+fn modifier_names(modifiers: Modifiers) -> Vec<&'static str> {
+    [
+        (Modifiers::CONTROL, "Control"),
+        (Modifiers::SHIFT, "Shift"),
+        (Modifiers::ALT, "Alt"),
+        (Modifiers::META, "Super"),
+    ]
+    .iter()
+    .filter(|(m, _)| modifiers.contains(*m))
+    .map(|(_, name)| *name)
+    .collect()
+}
+
+fn parse_hotkey(hotkey_str: &str) -> Result<(Modifiers, &'static PhysicalKey)> {
     let parts: Vec<&str> = hotkey_str.split('+').map(|s| s.trim()).collect();
-    if parts.is_empty() {
+    if parts.is_empty() || parts.iter().any(|p| p.is_empty()) {
         anyhow::bail!("Порожня гаряча клавіша");
     }
 
@@ -84,76 +239,87 @@ fn parse_hotkey(hotkey_str: &str) -> Result<(Modifiers, Code)> {
         }
     }
 
-    let code = parse_key_code(&code_str)?;
+    let key = parse_key(&code_str)?;
+
+    Ok((modifiers, key))
+}
 
-    Ok((modifiers, code))
+/// Resolve a key by canonical name, alias, Ukrainian label or W3C code (e.g. "KeyA").
+fn parse_key(code_str: &str) -> Result<&'static PhysicalKey> {
+    let lower = code_str.to_lowercase();
+    let name = match lower.as_str() {
+        "return" => "enter",
+        "esc" => "escape",
+        "del" => "delete",
+        "page_up" => "pageup",
+        "page_down" => "pagedown",
+        other => other,
+    };
+    PHYSICAL_KEYS
+        .iter()
+        .find(|k| {
+            k.name.to_lowercase() == name
+                || k.ukrainian.is_some_and(|u| u.to_lowercase() == name)
+                || k.code.to_string().to_lowercase() == name
+        })
+        .ok_or_else(|| anyhow::anyhow!("Невідомий код клавіші: {}", code_str))
 }
 
-fn parse_key_code(code_str: &str) -> Result<Code> {
-    let code_str_lower = code_str.to_lowercase();
-    match code_str_lower.as_str() {
-        "space" => Ok(Code::Space),
-        "enter" | "return" => Ok(Code::Enter),
-        "tab" => Ok(Code::Tab),
-        "escape" | "esc" => Ok(Code::Escape),
-        "backspace" => Ok(Code::Backspace),
-        "delete" | "del" => Ok(Code::Delete),
-        "up" => Ok(Code::ArrowUp),
-        "down" => Ok(Code::ArrowDown),
-        "left" => Ok(Code::ArrowLeft),
-        "right" => Ok(Code::ArrowRight),
-        "home" => Ok(Code::Home),
-        "end" => Ok(Code::End),
-        "pageup" | "page_up" => Ok(Code::PageUp),
-        "pagedown" | "page_down" => Ok(Code::PageDown),
-        "f1" => Ok(Code::F1),
-        "f2" => Ok(Code::F2),
-        "f3" => Ok(Code::F3),
-        "f4" => Ok(Code::F4),
-        "f5" => Ok(Code::F5),
-        "f6" => Ok(Code::F6),
-        "f7" => Ok(Code::F7),
-        "f8" => Ok(Code::F8),
-        "f9" => Ok(Code::F9),
-        "f10" => Ok(Code::F10),
-        "f11" => Ok(Code::F11),
-        "f12" => Ok(Code::F12),
-        "a" => Ok(Code::KeyA),
-        "b" => Ok(Code::KeyB),
-        "c" => Ok(Code::KeyC),
-        "d" => Ok(Code::KeyD),
-        "e" => Ok(Code::KeyE),
-        "f" => Ok(Code::KeyF),
-        "g" => Ok(Code::KeyG),
-        "h" => Ok(Code::KeyH),
-        "i" => Ok(Code::KeyI),
-        "j" => Ok(Code::KeyJ),
-        "k" => Ok(Code::KeyK),
-        "l" => Ok(Code::KeyL),
-        "m" => Ok(Code::KeyM),
-        "n" => Ok(Code::KeyN),
-        "o" => Ok(Code::KeyO),
-        "p" => Ok(Code::KeyP),
-        "q" => Ok(Code::KeyQ),
-        "r" => Ok(Code::KeyR),
-        "s" => Ok(Code::KeyS),
-        "t" => Ok(Code::KeyT),
-        "u" => Ok(Code::KeyU),
-        "v" => Ok(Code::KeyV),
-        "w" => Ok(Code::KeyW),
-        "x" => Ok(Code::KeyX),
-        "y" => Ok(Code::KeyY),
-        "z" => Ok(Code::KeyZ),
-        "0" => Ok(Code::Digit0),
-        "1" => Ok(Code::Digit1),
-        "2" => Ok(Code::Digit2),
-        "3" => Ok(Code::Digit3),
-        "4" => Ok(Code::Digit4),
-        "5" => Ok(Code::Digit5),
-        "6" => Ok(Code::Digit6),
-        "7" => Ok(Code::Digit7),
-        "8" => Ok(Code::Digit8),
-        "9" => Ok(Code::Digit9),
-        _ => anyhow::bail!("Невідомий код клавіші: {}", code_str),
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hotkey_latin_and_ukrainian_match() {
+        let (mods, latin) = parse_hotkey("Control+Shift+a").unwrap();
+        let (_, ukrainian) = parse_hotkey("Control+Shift+ф").unwrap();
+        assert_eq!(mods, Modifiers::CONTROL | Modifiers::SHIFT);
+        assert_eq!(latin.code, Code::KeyA);
+        assert_eq!(ukrainian.scancode, latin.scancode);
+    }
+
+    #[test]
+    fn test_parse_hotkey_aliases_and_code_names() {
+        assert_eq!(parse_hotkey("Ctrl+Esc").unwrap().1.code, Code::Escape);
+        assert_eq!(parse_hotkey("Alt+KeyR").unwrap().1.code, Code::KeyR);
+        assert_eq!(parse_hotkey("Super+page_down").unwrap().1.code, Code::PageDown);
+        assert_eq!(parse_hotkey("Control+Shift+Space").unwrap().1.scancode, 57);
+    }
+
+    #[test]
+    fn test_parse_hotkey_errors() {
+        assert!(parse_hotkey("").is_err());
+        assert!(parse_hotkey("Control+").is_err());
+        assert!(parse_hotkey("Hyper+A").is_err());
+        assert!(parse_hotkey("Control+Ы").is_err());
+    }
+
+    #[test]
+    fn test_format_hotkey_from_scancode() {
+        assert_eq!(
+            format_hotkey(Modifiers::CONTROL | Modifiers::ALT, 30).as_deref(),
+            Some("Control+Alt+A")
+        );
+        assert_eq!(format_hotkey(Modifiers::empty(), 9999), None);
+    }
+
+    #[test]
+    fn test_describe_hotkey_shows_both_layouts() {
+        assert_eq!(
+            describe_hotkey("Control+Shift+ф").unwrap(),
+            "Control+Shift+A (укр. Control+Shift+Ф)"
+        );
+        assert_eq!(describe_hotkey("Control+Shift+Space").unwrap(), "Control+Shift+Space");
+    }
+
+    #[test]
+    fn test_scancodes_unique() {
+        for (i, a) in PHYSICAL_KEYS.iter().enumerate() {
+            assert!(
+                PHYSICAL_KEYS[i + 1..].iter().all(|b| b.scancode != a.scancode),
+                "{}",
+                a.name
+            );
+        }
     }
 }
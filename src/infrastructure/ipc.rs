@@ -0,0 +1,412 @@
+//! Local socket through which CLI commands use the running app.
+//!
+//! The GUI keeps the STT model in memory and owns the history file. A CLI
+//! command started next to it would load a second copy of the model and
+//! could overwrite history the GUI saves at the same moment. Instead the GUI
+//! listens on an owner-only Unix socket in the runtime directory; the CLI asks
+//! it to transcribe samples with the loaded model, to hand out a snapshot
+//! of the history or the session's failure counters, and falls back to working alone when nobody listens.
+//!
+//! One request per connection. A request is a JSON line; `transcribe` is
+//! followed by the samples as little-endian `f32`. The response is a JSON line.
+
+use crate::app::config::{ipc_socket_path, set_owner_only_permissions};
+use crate::domain::traits::Transcription;
+use crate::domain::types::HistoryEntry;
+use crate::history::History;
+use crate::infrastructure::failures::{self, FailureCounts};
+use crate::recording::split::SplitConfig;
+use crate::transcription::chunker::{AudioChunker, ChunkerConfig};
+use crate::transcription::service::BackendType;
+use crate::transcription::TranscriptionService;
+use anyhow::{bail, Context, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Largest transcription request accepted: 30 minutes of 16 kHz audio.
+pub const MAX_REQUEST_SAMPLES: usize = 16000 * 60 * 30;
+
+/// Longest piece of a request transcribed under one lock of the model, so
+/// the GUI waits seconds, not the whole request, to use it.
+const WINDOW_SECS: u32 = 30;
+
+/// A stalled client must not keep a connection thread forever.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum Request {
+    Status,
+    /// Followed by `samples` little-endian `f32` values.
+    Transcribe {
+        language: String,
+        samples: usize,
+    },
+    History,
+    Failures,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Response {
+    Status(AppStatus),
+    Text { text: String },
+    History { entries: Vec<HistoryEntry> },
+    Failures(FailureCounts),
+    Error { message: String },
+}
+
+/// Model currently loaded in the running app.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AppStatus {
+    /// "whisper" or "tdt"; `None` while no model is loaded.
+    pub backend: Option<String>,
+    /// Model path as reported by the backend.
+    pub model: Option<String>,
+}
+
+/// Serve CLI requests from the GUI's model and history in the background.
+pub fn spawn_server(transcription: Arc<Mutex<TranscriptionService>>, history: Arc<Mutex<History>>) -> Result<()> {
+    spawn_server_at(&ipc_socket_path(), transcription, history)
+}
+
+fn spawn_server_at(
+    path: &Path,
+    transcription: Arc<Mutex<TranscriptionService>>,
+    history: Arc<Mutex<History>>,
+) -> Result<()> {
+    if path.exists() {
+        if UnixStream::connect(path).is_ok() {
+            bail!("Сокет уже використовує інший процес: {}", path.display());
+        }
+        // Left over from a crashed session
+        fs::remove_file(path).with_context(|| format!("Не вдалося видалити старий сокет: {}", path.display()))?;
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("Не вдалося створити директорію: {}", parent.display()))?;
+    }
+    let listener =
+        UnixListener::bind(path).with_context(|| format!("Не вдалося відкрити сокет: {}", path.display()))?;
+    set_owner_only_permissions(path)?;
+
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(e) => {
+                    eprintln!("Локальний сокет: {}", e);
+                    continue;
+                }
+            };
+            let transcription = transcription.clone();
+            let history = history.clone();
+            // A long transcription must not hold up status or history requests
+            std::thread::spawn(move || {
+                if let Err(e) = handle_connection(stream, &transcription, &history) {
+                    eprintln!("Локальний сокет: {}", e);
+                }
+            });
+        }
+    });
+    Ok(())
+}
+
+fn handle_connection(
+    stream: UnixStream,
+    transcription: &Mutex<TranscriptionService>,
+    history: &Mutex<History>,
+) -> Result<()> {
+    stream.set_read_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        // `IpcClient::connect` only checks that the app is listening
+        return Ok(());
+    }
+
+    let response = match serde_json::from_str::<Request>(line.trim()) {
+        Ok(request) => respond(request, &mut reader, transcription, history),
+        Err(e) => Response::Error {
+            message: format!("Некоректний запит: {}", e),
+        },
+    };
+    write_line(&stream, &response)
+}
+
+fn respond(
+    request: Request,
+    reader: &mut impl Read,
+    transcription: &Mutex<TranscriptionService>,
+    history: &Mutex<History>,
+) -> Response {
+    let result = match request {
+        Request::Status => {
+            let service = transcription.lock();
+            Ok(Response::Status(AppStatus {
+                backend: service.backend_type().map(|b| backend_name(b).to_string()),
+                model: Transcription::model_name(&*service),
+            }))
+        }
+        Request::Transcribe { language, samples } => read_samples(reader, samples)
+            .and_then(|samples| transcribe_in_windows(&samples, &language, transcription))
+            .map(|text| Response::Text { text }),
+        Request::History => Ok(Response::History {
+            entries: history.lock().entries.clone(),
+        }),
+        Request::Failures => Ok(Response::Failures(failures::snapshot())),
+    };
+    result.unwrap_or_else(|e| Response::Error {
+        message: format!("{:#}", e),
+    })
+}
+
+/// Transcribe the samples piece by piece, releasing the model between pieces.
+fn transcribe_in_windows(
+    samples: &[f32],
+    language: &str,
+    transcription: &Mutex<TranscriptionService>,
+) -> Result<String> {
+    let chunker = AudioChunker::new(ChunkerConfig {
+        split: SplitConfig {
+            max_segment_secs: WINDOW_SECS,
+            ..SplitConfig::default()
+        },
+        ..ChunkerConfig::default()
+    });
+    let mut texts = Vec::new();
+    for chunk in chunker.segment(samples)? {
+        let text = transcription
+            .lock()
+            .transcribe(&samples[chunk.start_sample..chunk.end_sample], language)?;
+        if !text.trim().is_empty() {
+            texts.push(text.trim().to_string());
+        }
+    }
+    Ok(texts.join(" "))
+}
+
+fn backend_name(backend: BackendType) -> &'static str {
+    match backend {
+        BackendType::Whisper => "whisper",
+        BackendType::Tdt => "tdt",
+    }
+}
+
+fn write_line(stream: &UnixStream, message: &impl Serialize) -> Result<()> {
+    let mut writer = BufWriter::new(stream);
+    serde_json::to_writer(&mut writer, message)?;
+    writer.write_all(b"\n")?;
+    writer.flush()?;
+    Ok(())
+}
+
+fn write_samples(writer: &mut impl Write, samples: &[f32]) -> Result<()> {
+    for sample in samples {
+        writer.write_all(&sample.to_le_bytes())?;
+    }
+    Ok(())
+}
+
+fn read_samples(reader: &mut impl Read, count: usize) -> Result<Vec<f32>> {
+    if count > MAX_REQUEST_SAMPLES {
+        bail!("Аудіо задовге: {} семплів", count);
+    }
+    let mut bytes = vec![0u8; count * 4];
+    reader.read_exact(&mut bytes).context("Аудіо передано не повністю")?;
+    Ok(bytes
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect())
+}
+
+/// Connection to the running app.
+#[derive(Debug, Clone)]
+pub struct IpcClient {
+    path: PathBuf,
+}
+
+impl IpcClient {
+    /// Client for the running app, or `None` when the app is not running.
+    pub fn connect() -> Option<Self> {
+        Self::connect_at(ipc_socket_path())
+    }
+
+    fn connect_at(path: PathBuf) -> Option<Self> {
+        UnixStream::connect(&path).ok()?;
+        Some(Self { path })
+    }
+
+    pub fn status(&self) -> Result<AppStatus> {
+        match self.request(&Request::Status, &[])? {
+            Response::Status(status) => Ok(status),
+            other => bail!("Неочікувана відповідь застосунку: {:?}", other),
+        }
+    }
+
+    /// Transcribe 16 kHz mono samples with the app's model.
+    pub fn transcribe(&self, samples: &[f32], language: &str) -> Result<String> {
+        let request = Request::Transcribe {
+            language: language.to_string(),
+            samples: samples.len(),
+        };
+        match self.request(&request, samples)? {
+            Response::Text { text } => Ok(text),
+            other => bail!("Неочікувана відповідь застосунку: {:?}", other),
+        }
+    }
+
+    /// Snapshot of the history as the app currently holds it.
+    pub fn history(&self) -> Result<Vec<HistoryEntry>> {
+        match self.request(&Request::History, &[])? {
+            Response::History { entries } => Ok(entries),
+            other => bail!("Неочікувана відповідь застосунку: {:?}", other),
+        }
+    }
+
+    /// Failures the app has counted since it started.
+    pub fn failures(&self) -> Result<FailureCounts> {
+        match self.request(&Request::Failures, &[])? {
+            Response::Failures(counts) => Ok(counts),
+            other => bail!("Неочікувана відповідь застосунку: {:?}", other),
+        }
+    }
+
+    fn request(&self, request: &Request, samples: &[f32]) -> Result<Response> {
+        let stream = UnixStream::connect(&self.path).context("Застосунок не відповідає")?;
+        {
+            let mut writer = BufWriter::new(&stream);
+            serde_json::to_writer(&mut writer, request)?;
+            writer.write_all(b"\n")?;
+            write_samples(&mut writer, samples)?;
+            writer.flush()?;
+        }
+
+        let mut line = String::new();
+        BufReader::new(&stream).read_line(&mut line)?;
+        match serde_json::from_str(line.trim()).context("Некоректна відповідь застосунку")?
+        {
+            Response::Error { message } => bail!(message),
+            response => Ok(response),
+        }
+    }
+}
+
+/// `Transcription` backed by the model loaded in the running app.
+pub struct RemoteTranscriber {
+    client: IpcClient,
+    model: Option<String>,
+}
+
+impl RemoteTranscriber {
+    pub fn new(client: IpcClient, status: AppStatus) -> Self {
+        Self {
+            client,
+            model: status.model,
+        }
+    }
+}
+
+impl Transcription for RemoteTranscriber {
+    fn transcribe(&self, samples: &[f32], language: &str) -> Result<String> {
+        self.client.transcribe(samples, language)
+    }
+
+    fn is_loaded(&self) -> bool {
+        true
+    }
+
+    fn model_name(&self) -> Option<String> {
+        self.model.clone()
+    }
+
+    fn load_model(&mut self, _path: &Path) -> Result<()> {
+        bail!("Модель завантажує застосунок, а не CLI")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn socket_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("s2t-ipc-{}-{}.sock", name, std::process::id()))
+    }
+
+    fn serve(name: &str, history: History) -> IpcClient {
+        let path = socket_path(name);
+        let _ = fs::remove_file(&path);
+        spawn_server_at(
+            &path,
+            Arc::new(Mutex::new(TranscriptionService::new())),
+            Arc::new(Mutex::new(history)),
+        )
+        .unwrap();
+        IpcClient::connect_at(path).unwrap()
+    }
+
+    #[test]
+    fn test_status_without_model() {
+        let client = serve("status", History::default());
+        assert_eq!(client.status().unwrap(), AppStatus::default());
+    }
+
+    #[test]
+    fn test_transcribe_reports_app_error() {
+        let client = serve("transcribe", History::default());
+        let err = client.transcribe(&[0.0; 1600], "uk").unwrap_err();
+        assert!(err.to_string().contains("Модель не завантажено"));
+    }
+
+    #[test]
+    fn test_history_snapshot() {
+        let mut history = History::default();
+        history
+            .entries
+            .push(HistoryEntry::new("Привіт".to_string(), 2.0, "uk".to_string()));
+        let client = serve("history", history);
+
+        let entries = client.history().unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].text, "Привіт");
+    }
+
+    #[test]
+    fn test_failure_counters() {
+        let client = serve("failures", History::default());
+        failures::record(failures::Failure::Paste);
+        assert!(client.failures().unwrap().paste >= 1);
+    }
+
+    #[test]
+    fn test_stale_socket_is_replaced() {
+        let path = socket_path("stale");
+        let _ = fs::remove_file(&path);
+        drop(UnixListener::bind(&path).unwrap());
+        assert!(IpcClient::connect_at(path.clone()).is_none());
+
+        spawn_server_at(
+            &path,
+            Arc::new(Mutex::new(TranscriptionService::new())),
+            Arc::new(Mutex::new(History::default())),
+        )
+        .unwrap();
+        assert!(IpcClient::connect_at(path).is_some());
+    }
+
+    #[test]
+    fn test_samples_roundtrip() {
+        let samples = [0.0, -1.0, 0.5, f32::MIN_POSITIVE];
+        let mut bytes = Vec::new();
+        write_samples(&mut bytes, &samples).unwrap();
+        assert_eq!(bytes.len(), 16);
+        assert_eq!(read_samples(&mut bytes.as_slice(), 4).unwrap(), samples);
+        assert!(read_samples(&mut bytes.as_slice(), 5).is_err());
+        assert!(read_samples(&mut bytes.as_slice(), MAX_REQUEST_SAMPLES + 1).is_err());
+    }
+}
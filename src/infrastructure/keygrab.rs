@@ -0,0 +1,207 @@
+//! Layout-independent global hotkey grab on X11.
+//!
+//! `global-hotkey` resolves keys through keysyms, so a letter hotkey is
+//! looked up in the active keyboard layout and stops working (or never
+//! registers) while the Ukrainian layout is active. This module grabs the
+//! physical key by its hardware keycode instead, which is the same in
+//! every layout.
+
+use anyhow::{anyhow, bail, Result};
+use global_hotkey::hotkey::Modifiers;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use x11_dl::xlib;
+
+/// X11 (and GDK hardware) keycodes are evdev scancodes offset by 8.
+pub const X11_KEYCODE_OFFSET: u32 = 8;
+
+/// Lock modifiers that must not prevent the hotkey from firing.
+const IGNORED_MODS: [u32; 4] = [
+    0,
+    xlib::Mod2Mask, // NumLock
+    xlib::LockMask, // CapsLock
+    xlib::Mod2Mask | xlib::LockMask,
+];
+
+/// Modifiers compared when matching a key press.
+const RELEVANT_MODS: u32 = xlib::ControlMask | xlib::ShiftMask | xlib::Mod1Mask | xlib::Mod4Mask;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Set when the X server refuses a grab on the grab's display.
+static GRAB_FAILED: AtomicBool = AtomicBool::new(false);
+
+/// Convert hotkey modifiers to an X11 modifier mask.
+pub fn x11_modifiers(modifiers: Modifiers) -> u32 {
+    let mut mask = 0;
+    if modifiers.contains(Modifiers::CONTROL) {
+        mask |= xlib::ControlMask;
+    }
+    if modifiers.contains(Modifiers::SHIFT) {
+        mask |= xlib::ShiftMask;
+    }
+    if modifiers.contains(Modifiers::ALT) {
+        mask |= xlib::Mod1Mask;
+    }
+    if modifiers.contains(Modifiers::META) {
+        mask |= xlib::Mod4Mask;
+    }
+    mask
+}
+
+/// Active grab of one physical key. Released on drop.
+pub struct ScancodeGrab {
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ScancodeGrab {
+    /// Grab the key with evdev `scancode` plus `modifiers`, calling `on_press` on each press.
+    ///
+    /// Returns once the grab is in place, or with an error if the display
+    /// is unavailable or another client already owns the combination.
+    pub fn start<F>(scancode: u32, modifiers: Modifiers, on_press: F) -> Result<Self>
+    where
+        F: Fn() + Send + 'static,
+    {
+        let keycode = scancode + X11_KEYCODE_OFFSET;
+        let mask = x11_modifiers(modifiers);
+        let running = Arc::new(AtomicBool::new(true));
+        let (ready_tx, ready_rx) = mpsc::channel::<Result<()>>();
+
+        let running_for_thread = running.clone();
+        let handle = std::thread::spawn(move || grab_loop(keycode, mask, &running_for_thread, ready_tx, on_press));
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(Self {
+                running,
+                handle: Some(handle),
+            }),
+            Ok(Err(e)) => {
+                let _ = handle.join();
+                Err(e)
+            }
+            Err(_) => {
+                let _ = handle.join();
+                bail!("Потік гарячих клавіш несподівано завершився")
+            }
+        }
+    }
+}
+
+impl Drop for ScancodeGrab {
+    fn drop(&mut self) {
+        self.running.store(false, Ordering::SeqCst);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn grab_loop<F: Fn()>(keycode: u32, mask: u32, running: &AtomicBool, ready_tx: mpsc::Sender<Result<()>>, on_press: F) {
+    let xlib = match xlib::Xlib::open() {
+        Ok(xlib) => xlib,
+        Err(e) => {
+            let _ = ready_tx.send(Err(anyhow!("Не вдалося завантажити Xlib: {}", e)));
+            return;
+        }
+    };
+
+    // SAFETY: the display connection is opened, used and closed on this
+    // thread only; the event union is read only after checking its type.
+    unsafe {
+        let display = (xlib.XOpenDisplay)(ptr::null());
+        if display.is_null() {
+            let _ = ready_tx.send(Err(anyhow!(
+                "Не вдалося підключитися до X11 (потрібен X11 або XWayland)"
+            )));
+            return;
+        }
+        let root = (xlib.XDefaultRootWindow)(display);
+
+        // Report a single press per keystroke, not auto-repeat
+        let mut supported = 0;
+        (xlib.XkbSetDetectableAutoRepeat)(display, 1, &mut supported);
+
+        // BadAccess arrives asynchronously, so sync to catch it. The hook
+        // belongs to this display only: the process-wide error handler is
+        // GTK's and is left alone
+        GRAB_FAILED.store(false, Ordering::SeqCst);
+        (xlib.XESetWireToError)(display, xlib::BadAccess as i32, Some(on_bad_access));
+        for m in IGNORED_MODS {
+            (xlib.XGrabKey)(
+                display,
+                keycode as i32,
+                mask | m,
+                root,
+                0,
+                xlib::GrabModeAsync,
+                xlib::GrabModeAsync,
+            );
+        }
+        (xlib.XSync)(display, 0);
+
+        if GRAB_FAILED.load(Ordering::SeqCst) {
+            for m in IGNORED_MODS {
+                (xlib.XUngrabKey)(display, keycode as i32, mask | m, root);
+            }
+            (xlib.XCloseDisplay)(display);
+            let _ = ready_tx.send(Err(anyhow!("Комбінація клавіш уже зайнята іншою програмою")));
+            return;
+        }
+
+        (xlib.XSelectInput)(display, root, xlib::KeyPressMask);
+        let _ = ready_tx.send(Ok(()));
+
+        let mut event: xlib::XEvent = std::mem::zeroed();
+        while running.load(Ordering::SeqCst) {
+            while (xlib.XPending)(display) > 0 {
+                (xlib.XNextEvent)(display, &mut event);
+                if event.get_type() == xlib::KeyPress
+                    && event.key.keycode == keycode
+                    && event.key.state & RELEVANT_MODS == mask
+                {
+                    on_press();
+                }
+            }
+            std::thread::sleep(POLL_INTERVAL);
+        }
+
+        for m in IGNORED_MODS {
+            (xlib.XUngrabKey)(display, keycode as i32, mask | m, root);
+        }
+        (xlib.XCloseDisplay)(display);
+    }
+}
+
+/// BadAccess on the grab's display: note it and keep it from Xlib's error
+/// handler (returning 0 drops the error).
+unsafe extern "C" fn on_bad_access(
+    _display: *mut xlib::Display,
+    _event: *mut xlib::XErrorEvent,
+    _wire: *mut xlib::xError,
+) -> i32 {
+    GRAB_FAILED.store(true, Ordering::SeqCst);
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_x11_modifiers() {
+        assert_eq!(x11_modifiers(Modifiers::empty()), 0);
+        assert_eq!(
+            x11_modifiers(Modifiers::CONTROL | Modifiers::SHIFT),
+            xlib::ControlMask | xlib::ShiftMask
+        );
+        assert_eq!(
+            x11_modifiers(Modifiers::ALT | Modifiers::META),
+            xlib::Mod1Mask | xlib::Mod4Mask
+        );
+    }
+}
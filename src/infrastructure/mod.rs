@@ -1,5 +1,13 @@
+pub mod failures;
+pub mod hardware;
 pub mod hotkeys;
+pub mod ipc;
+pub mod keygrab;
 pub mod models;
 pub mod paste;
+pub mod perf;
 pub mod recordings;
+pub mod remote;
 pub mod tray;
+pub mod webdav;
+pub mod workspace;
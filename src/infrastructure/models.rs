@@ -1,6 +1,7 @@
 use crate::app::config::models_dir;
 use anyhow::{bail, Context, Result};
 use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -27,12 +28,16 @@ pub struct ModelInfo {
     pub sha256: Option<String>,
 }
 
-fn verify_checksum(path: &Path, expected: &str) -> Result<()> {
+fn compute_sha256(path: &Path) -> Result<String> {
     let mut file =
         fs::File::open(path).with_context(|| format!("Не вдалося відкрити файл для перевірки: {}", path.display()))?;
     let mut hasher = Sha256::new();
     std::io::copy(&mut file, &mut hasher).context("Помилка при обчисленні контрольної суми")?;
-    let hash = format!("{:x}", hasher.finalize());
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn verify_checksum(path: &Path, expected: &str) -> Result<()> {
+    let hash = compute_sha256(path)?;
     if hash != expected {
         bail!(
             "Контрольна сума не збігається для {}: очікувано {}, отримано {}",
@@ -127,15 +132,123 @@ pub fn get_available_models() -> Vec<ModelInfo> {
             description: "Найвища точність".to_string(),
             sha256: Some("64d182b440b98d5203c4f9bd541544d84c605196c4f7b845dfa11fb23594d1e2".to_string()),
         },
+        // Large v3 Turbo (pruned decoder, ~8x faster than Large v3)
+        ModelInfo {
+            filename: "ggml-large-v3-turbo-q5_0.bin".to_string(),
+            display_name: "Large v3 Turbo Q5".to_string(),
+            size_bytes: 574_041_195,
+            description: "Турбо квантована, якість близька до Large v3".to_string(),
+            sha256: Some("394221709cd5ad1f40c46e6031ca61bce88931e6e088c188294c6d5a55ffa7e2".to_string()),
+        },
+        ModelInfo {
+            filename: "ggml-large-v3-turbo-q8_0.bin".to_string(),
+            display_name: "Large v3 Turbo Q8".to_string(),
+            size_bytes: 874_188_075,
+            description: "Турбо квантована, висока якість".to_string(),
+            sha256: Some("317eb69c11673c9de1e1f0d459b253999804ec71ac4c23c17ecf5fbe24e259a1".to_string()),
+        },
+        ModelInfo {
+            filename: "ggml-large-v3-turbo.bin".to_string(),
+            display_name: "Large v3 Turbo".to_string(),
+            size_bytes: 1_624_555_275,
+            description: "Майже точність Large v3, значно швидша".to_string(),
+            sha256: Some("1fc70f774d38eb169993ac391eea357ef47c88757ef72ee5943879b7e8e2bc69".to_string()),
+        },
+        // Distil-Whisper (English-focused distilled models, hosted separately)
+        ModelInfo {
+            filename: "ggml-distil-large-v3.bin".to_string(),
+            display_name: "Distil Large v3 (EN)".to_string(),
+            size_bytes: 1_519_521_155,
+            description: "Дистильована, ~6x швидше, лише англійська".to_string(),
+            sha256: None, // Third-party repo, hash TBD
+        },
     ]
 }
 
+/// Model family used to group the catalog in the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ModelFamily {
+    Tiny,
+    Base,
+    Small,
+    Medium,
+    Large,
+    LargeTurbo,
+    Distil,
+}
+
+impl ModelFamily {
+    /// Determine the family from a ggml model filename.
+    pub fn from_filename(filename: &str) -> Self {
+        if filename.contains("distil") {
+            ModelFamily::Distil
+        } else if filename.contains("large-v3-turbo") {
+            ModelFamily::LargeTurbo
+        } else if filename.contains("large") {
+            ModelFamily::Large
+        } else if filename.contains("medium") {
+            ModelFamily::Medium
+        } else if filename.contains("small") {
+            ModelFamily::Small
+        } else if filename.contains("base") {
+            ModelFamily::Base
+        } else {
+            ModelFamily::Tiny
+        }
+    }
+
+    /// Human-readable group title.
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            ModelFamily::Tiny => "Tiny",
+            ModelFamily::Base => "Base",
+            ModelFamily::Small => "Small",
+            ModelFamily::Medium => "Medium",
+            ModelFamily::Large => "Large v3",
+            ModelFamily::LargeTurbo => "Large v3 Turbo",
+            ModelFamily::Distil => "Distil-Whisper",
+        }
+    }
+}
+
+/// Group the catalog by family, preserving catalog order within each group.
+pub fn group_models_by_family(models: Vec<ModelInfo>) -> Vec<(ModelFamily, Vec<ModelInfo>)> {
+    let mut groups: Vec<(ModelFamily, Vec<ModelInfo>)> = Vec::new();
+    for model in models {
+        let family = ModelFamily::from_filename(&model.filename);
+        match groups.iter_mut().find(|(f, _)| *f == family) {
+            Some((_, list)) => list.push(model),
+            None => groups.push((family, vec![model])),
+        }
+    }
+    groups.sort_by_key(|(family, _)| *family);
+    groups
+}
+
+/// Download URL for a Whisper model file.
+///
+/// Distil-Whisper models live in per-model repositories; everything else
+/// comes from the whisper.cpp repository.
+fn whisper_model_url(filename: &str) -> String {
+    if let Some(name) = filename
+        .strip_prefix("ggml-distil-")
+        .and_then(|rest| rest.strip_suffix(".bin"))
+    {
+        return format!(
+            "https://huggingface.co/distil-whisper/distil-{}-ggml/resolve/main/{}",
+            name, filename
+        );
+    }
+    format!("{}{}", HUGGINGFACE_BASE_URL, filename)
+}
+
 pub fn list_downloaded_models() -> Vec<ModelInfo> {
     let dir = models_dir();
     let available = get_available_models();
 
     available
         .into_iter()
+        .chain(load_custom_models().iter().map(CustomModel::to_model_info))
         .filter(|model| {
             let path = dir.join(&model.filename);
             path.exists()
@@ -143,6 +256,204 @@ pub fn list_downloaded_models() -> Vec<ModelInfo> {
         .collect()
 }
 
+/// Display name of a Whisper model file, or the file name if it is unknown.
+pub fn model_display_name(filename: &str) -> String {
+    get_available_models()
+        .into_iter()
+        .chain(load_custom_models().iter().map(CustomModel::to_model_info))
+        .find(|model| model.filename == filename)
+        .map_or_else(|| filename.to_string(), |model| model.display_name)
+}
+
+// Custom (user-added) Whisper models
+
+/// A model added by the user from a URL or a local file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CustomModel {
+    pub filename: String,
+    pub display_name: String,
+    /// Original URL or local path the model was added from.
+    pub source: String,
+    pub size_bytes: u64,
+    /// SHA256 computed when the model was registered.
+    pub sha256: String,
+}
+
+impl CustomModel {
+    pub fn to_model_info(&self) -> ModelInfo {
+        ModelInfo {
+            filename: self.filename.clone(),
+            display_name: self.display_name.clone(),
+            size_bytes: self.size_bytes,
+            description: format!("Користувацька модель ({})", self.source),
+            sha256: Some(self.sha256.clone()),
+        }
+    }
+
+    fn is_remote(&self) -> bool {
+        self.source.starts_with("https://") || self.source.starts_with("http://")
+    }
+}
+
+fn custom_models_path() -> PathBuf {
+    models_dir().join("custom_models.json")
+}
+
+/// Load the registry of user-added models. Missing or unreadable registry yields an empty list.
+pub fn load_custom_models() -> Vec<CustomModel> {
+    fs::read_to_string(custom_models_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_custom_models(models: &[CustomModel]) -> Result<()> {
+    let dir = models_dir();
+    fs::create_dir_all(&dir).with_context(|| format!("Не вдалося створити директорію: {}", dir.display()))?;
+    let path = custom_models_path();
+    let content = serde_json::to_string_pretty(models).context("Не вдалося серіалізувати список моделей")?;
+    fs::write(&path, content).with_context(|| format!("Не вдалося записати {}", path.display()))
+}
+
+fn register_custom_model(model: CustomModel) -> Result<()> {
+    let mut models = load_custom_models();
+    models.retain(|m| m.filename != model.filename);
+    models.push(model);
+    save_custom_models(&models)
+}
+
+fn unregister_custom_model(filename: &str) -> Result<()> {
+    let mut models = load_custom_models();
+    let before = models.len();
+    models.retain(|m| m.filename != filename);
+    if models.len() != before {
+        save_custom_models(&models)?;
+    }
+    Ok(())
+}
+
+/// Check that a custom model filename is safe, has a model extension and
+/// does not shadow a catalog model.
+fn validate_custom_filename(filename: &str) -> Result<()> {
+    sanitize_model_filename(filename)?;
+    if !(filename.ends_with(".bin") || filename.ends_with(".gguf")) {
+        bail!("Підтримуються лише файли .bin (GGML) або .gguf: {}", filename);
+    }
+    if get_available_models().iter().any(|m| m.filename == filename) {
+        bail!("Модель {} вже є в каталозі", filename);
+    }
+    Ok(())
+}
+
+/// Check the GGML/GGUF magic bytes at the start of a model file.
+fn has_model_magic(path: &Path) -> Result<bool> {
+    use std::io::Read;
+    let mut file = fs::File::open(path).with_context(|| format!("Не вдалося відкрити файл: {}", path.display()))?;
+    let mut magic = [0u8; 4];
+    if file.read_exact(&mut magic).is_err() {
+        return Ok(false);
+    }
+    // "ggml" is stored little-endian as "lmgg"; GGUF files start with "GGUF"
+    Ok(&magic == b"lmgg" || &magic == b"GGUF")
+}
+
+/// Verify that whisper.cpp can actually load the model file.
+pub fn validate_whisper_model(path: &Path) -> Result<()> {
+    if !has_model_magic(path)? {
+        bail!("Файл не є моделлю GGML/GGUF: {}", path.display());
+    }
+    crate::transcription::WhisperSTT::new(&path.to_string_lossy())
+        .map(|_| ())
+        .with_context(|| format!("whisper.cpp не може завантажити модель: {}", path.display()))
+}
+
+/// Default display name derived from a filename ("ggml-foo.bin" -> "ggml-foo").
+fn display_name_from_filename(filename: &str) -> String {
+    Path::new(filename)
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| filename.to_string())
+}
+
+/// Register a validated file that already sits in the models directory.
+fn finish_custom_model(filename: &str, display_name: Option<&str>, source: String) -> Result<ModelInfo> {
+    let path = models_dir().join(filename);
+    if let Err(e) = validate_whisper_model(&path) {
+        let _ = fs::remove_file(&path);
+        return Err(e);
+    }
+
+    let model = CustomModel {
+        filename: filename.to_string(),
+        display_name: display_name
+            .map(str::to_string)
+            .unwrap_or_else(|| display_name_from_filename(filename)),
+        source,
+        size_bytes: fs::metadata(&path).map(|m| m.len()).unwrap_or(0),
+        sha256: compute_sha256(&path)?,
+    };
+    let info = model.to_model_info();
+    register_custom_model(model)?;
+    Ok(info)
+}
+
+/// Copy a local GGML/GGUF file into `models_dir()` and register it.
+///
+/// The file is validated by loading it with whisper.cpp; on failure the copy is removed.
+pub fn import_local_model(source: &Path, display_name: Option<&str>) -> Result<ModelInfo> {
+    let filename = source
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .with_context(|| format!("Некоректний шлях: {}", source.display()))?;
+    validate_custom_filename(&filename)?;
+    if !has_model_magic(source)? {
+        bail!("Файл не є моделлю GGML/GGUF: {}", source.display());
+    }
+
+    let dir = models_dir();
+    fs::create_dir_all(&dir).with_context(|| format!("Не вдалося створити директорію: {}", dir.display()))?;
+
+    let final_path = dir.join(&filename);
+    if final_path.exists() {
+        bail!("Модель {} вже існує", filename);
+    }
+    let temp_path = dir.join(format!("{}.importing", filename));
+    fs::copy(source, &temp_path).with_context(|| format!("Не вдалося скопіювати {}", source.display()))?;
+    fs::rename(&temp_path, &final_path).with_context(|| format!("Не вдалося перейменувати {}", temp_path.display()))?;
+
+    let canonical = source.canonicalize().unwrap_or_else(|_| source.to_path_buf());
+    finish_custom_model(&filename, display_name, canonical.display().to_string())
+}
+
+/// Extract the model filename from a download URL (last path segment, no query).
+fn filename_from_url(url: &str) -> Option<String> {
+    let path = url.split(['?', '#']).next()?;
+    let name = path.rsplit('/').next()?;
+    if name.is_empty() {
+        None
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// Download a GGML/GGUF model from an arbitrary URL and register it.
+pub async fn download_custom_model<F>(url: &str, display_name: Option<&str>, progress_callback: F) -> Result<ModelInfo>
+where
+    F: Fn(u64, u64) + Send + Sync + 'static,
+{
+    if !(url.starts_with("https://") || url.starts_with("http://")) {
+        bail!("Підтримуються лише HTTP(S) посилання: {}", url);
+    }
+    let filename = filename_from_url(url).with_context(|| format!("Не вдалося визначити ім'я файлу з {}", url))?;
+    validate_custom_filename(&filename)?;
+    if models_dir().join(&filename).exists() {
+        bail!("Модель {} вже існує", filename);
+    }
+
+    download_file(url, &models_dir(), &filename, None, 0, 0, &progress_callback).await?;
+    finish_custom_model(&filename, display_name, url.to_string())
+}
+
 pub fn is_model_downloaded(filename: &str) -> bool {
     if sanitize_model_filename(filename).is_err() {
         return false;
@@ -158,6 +469,10 @@ pub fn get_model_path(filename: &str) -> PathBuf {
     models_dir().join(filename)
 }
 
+/// Delete a downloaded model file.
+///
+/// A custom model stays registered, so it can be downloaded again from its
+/// source; [`remove_custom_model`] drops it from the list.
 pub fn delete_model(filename: &str) -> Result<()> {
     sanitize_model_filename(filename)?;
     let path = models_dir().join(filename);
@@ -171,6 +486,19 @@ pub fn delete_model(filename: &str) -> Result<()> {
     Ok(())
 }
 
+/// Remove a custom model from the list, deleting its file if present.
+pub fn remove_custom_model(filename: &str) -> Result<()> {
+    sanitize_model_filename(filename)?;
+    if !load_custom_models().iter().any(|m| m.filename == filename) {
+        bail!("Користувацьку модель не знайдено: {}", filename);
+    }
+    let path = models_dir().join(filename);
+    if path.exists() {
+        fs::remove_file(&path).with_context(|| format!("Не вдалося видалити модель: {}", path.display()))?;
+    }
+    unregister_custom_model(filename)
+}
+
 /// Download a single file via HTTP with progress reporting, checksum verification,
 /// and atomic rename from temp to final path.
 async fn download_file(
@@ -249,12 +577,34 @@ where
 {
     sanitize_model_filename(filename)?;
 
+    // Custom models added from a URL are re-downloaded from their source
+    if let Some(custom) = load_custom_models().into_iter().find(|m| m.filename == filename) {
+        if !custom.is_remote() {
+            bail!(
+                "Модель {} імпортовано з файлу {}; імпортуйте її знову",
+                filename,
+                custom.source
+            );
+        }
+        download_file(
+            &custom.source,
+            &models_dir(),
+            filename,
+            Some(&custom.sha256),
+            0,
+            0,
+            &progress_callback,
+        )
+        .await?;
+        return Ok(());
+    }
+
     let expected_sha256 = get_available_models()
         .iter()
         .find(|m| m.filename == filename)
         .and_then(|m| m.sha256.clone());
 
-    let url = format!("{}{}", HUGGINGFACE_BASE_URL, filename);
+    let url = whisper_model_url(filename);
 
     if expected_sha256.is_none() {
         eprintln!(
@@ -344,8 +694,110 @@ where
     Ok(())
 }
 
+// Silero VAD model management
+pub fn get_silero_model_info() -> ModelInfo {
+    ModelInfo {
+        filename: "silero_vad.onnx".to_string(),
+        display_name: "Silero VAD v5".to_string(),
+        size_bytes: 2_327_524,
+        description: "Нейромережевий детектор мовлення для неперервного режиму".to_string(),
+        sha256: Some("2623a2953f6ff3d2c1e61740c6cdb7168133479b267dfef114a4a3cc5bdd788f".to_string()),
+    }
+}
+
+pub fn get_silero_model_path() -> PathBuf {
+    crate::app::config::vad_models_dir().join(get_silero_model_info().filename)
+}
+
+pub fn is_silero_model_downloaded() -> bool {
+    get_silero_model_path().exists()
+}
+
+/// Silero model to load: the configured file, else the downloaded one.
+///
+/// `None` means the copy built into `voice_activity_detector`.
+pub fn resolve_silero_model_path(configured: Option<&str>) -> Option<PathBuf> {
+    match configured {
+        Some(path) => Some(PathBuf::from(path)),
+        None => Some(get_silero_model_path()).filter(|path| path.exists()),
+    }
+}
+
+pub fn delete_silero_model() -> Result<()> {
+    let path = get_silero_model_path();
+    if path.exists() {
+        fs::remove_file(&path).with_context(|| format!("Не вдалося видалити модель: {}", path.display()))?;
+    }
+    Ok(())
+}
+
+pub async fn download_silero_model<F>(progress_callback: F) -> Result<()>
+where
+    F: Fn(u64, u64) + Send + Sync + 'static,
+{
+    // Pinned to the release the built-in copy comes from, so the checksum holds
+    const URL: &str = "https://github.com/snakers4/silero-vad/raw/v5.1.2/src/silero_vad/data/silero_vad.onnx";
+
+    let info = get_silero_model_info();
+    download_file(
+        URL,
+        &crate::app::config::vad_models_dir(),
+        &info.filename,
+        info.sha256.as_deref(),
+        0,
+        0,
+        &progress_callback,
+    )
+    .await?;
+
+    Ok(())
+}
+
+// Self-test sample
+
+/// Sample recording the onboarding self-test transcribes.
+pub fn get_self_test_sample_info() -> ModelInfo {
+    ModelInfo {
+        filename: "jfk.wav".to_string(),
+        display_name: "Зразок для самоперевірки".to_string(),
+        size_bytes: 352_078,
+        description: "11 секунд англійської промови".to_string(),
+        sha256: None, // Sample from the whisper.cpp repo, hash TBD
+    }
+}
+
+pub fn get_self_test_sample_path() -> PathBuf {
+    crate::app::config::samples_dir().join(get_self_test_sample_info().filename)
+}
+
+pub fn is_self_test_sample_downloaded() -> bool {
+    get_self_test_sample_path().exists()
+}
+
+pub async fn download_self_test_sample<F>(progress_callback: F) -> Result<PathBuf>
+where
+    F: Fn(u64, u64) + Send + Sync + 'static,
+{
+    const URL: &str = "https://github.com/ggerganov/whisper.cpp/raw/master/samples/jfk.wav";
+
+    let info = get_self_test_sample_info();
+    download_file(
+        URL,
+        &crate::app::config::samples_dir(),
+        &info.filename,
+        info.sha256.as_deref(),
+        0,
+        0,
+        &progress_callback,
+    )
+    .await
+}
+
 // TDT model management
 
+/// Name of the Parakeet TDT model shown next to the Whisper models.
+pub const TDT_DISPLAY_NAME: &str = "Parakeet TDT";
+
 /// TDT model file information.
 #[derive(Debug, Clone)]
 pub struct TdtModelFiles {
@@ -497,7 +949,7 @@ mod tests {
     #[test]
     fn test_get_available_models_count() {
         let models = get_available_models();
-        assert_eq!(models.len(), 11); // 6 quantized + 5 full precision
+        assert_eq!(models.len(), 15); // 8 quantized + 7 full precision
     }
 
     #[test]
@@ -540,7 +992,11 @@ mod tests {
     #[test]
     fn test_all_whisper_models_have_sha256() {
         let models = get_available_models();
-        for model in &models {
+        // Distil-Whisper models are hosted in third-party repos without pinned hashes yet
+        for model in models
+            .iter()
+            .filter(|m| ModelFamily::from_filename(&m.filename) != ModelFamily::Distil)
+        {
             assert!(
                 model.sha256.is_some(),
                 "Model {} is missing SHA256 hash",
@@ -561,6 +1017,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_model_family_from_filename() {
+        assert_eq!(ModelFamily::from_filename("ggml-tiny-q5_1.bin"), ModelFamily::Tiny);
+        assert_eq!(ModelFamily::from_filename("ggml-base.bin"), ModelFamily::Base);
+        assert_eq!(ModelFamily::from_filename("ggml-small-q8_0.bin"), ModelFamily::Small);
+        assert_eq!(ModelFamily::from_filename("ggml-medium.bin"), ModelFamily::Medium);
+        assert_eq!(ModelFamily::from_filename("ggml-large-v3.bin"), ModelFamily::Large);
+        assert_eq!(
+            ModelFamily::from_filename("ggml-large-v3-turbo-q5_0.bin"),
+            ModelFamily::LargeTurbo
+        );
+        assert_eq!(
+            ModelFamily::from_filename("ggml-distil-large-v3.bin"),
+            ModelFamily::Distil
+        );
+    }
+
+    #[test]
+    fn test_group_models_by_family_covers_catalog() {
+        let groups = group_models_by_family(get_available_models());
+        let total: usize = groups.iter().map(|(_, models)| models.len()).sum();
+        assert_eq!(total, get_available_models().len());
+        assert_eq!(groups.first().map(|(f, _)| *f), Some(ModelFamily::Tiny));
+        assert_eq!(groups.last().map(|(f, _)| *f), Some(ModelFamily::Distil));
+        assert!(groups.iter().any(|(f, _)| *f == ModelFamily::LargeTurbo));
+    }
+
+    #[test]
+    fn test_whisper_model_url() {
+        assert_eq!(
+            whisper_model_url("ggml-base.bin"),
+            "https://huggingface.co/ggerganov/whisper.cpp/resolve/main/ggml-base.bin"
+        );
+        assert_eq!(
+            whisper_model_url("ggml-distil-large-v3.bin"),
+            "https://huggingface.co/distil-whisper/distil-large-v3-ggml/resolve/main/ggml-distil-large-v3.bin"
+        );
+    }
+
+    #[test]
+    fn test_filename_from_url() {
+        assert_eq!(
+            filename_from_url("https://example.com/models/ggml-uk.bin?download=true"),
+            Some("ggml-uk.bin".to_string())
+        );
+        assert_eq!(filename_from_url("https://example.com/"), None);
+    }
+
+    #[test]
+    fn test_validate_custom_filename() {
+        assert!(validate_custom_filename("ggml-custom.bin").is_ok());
+        assert!(validate_custom_filename("whisper-uk.gguf").is_ok());
+        assert!(validate_custom_filename("model.onnx").is_err());
+        assert!(validate_custom_filename("../evil.bin").is_err());
+        // Must not shadow catalog models
+        assert!(validate_custom_filename("ggml-base.bin").is_err());
+    }
+
+    #[test]
+    fn test_has_model_magic() {
+        let dir = std::env::temp_dir().join("s2t_test_model_magic");
+        let _ = fs::create_dir_all(&dir);
+
+        let ggml = dir.join("ggml.bin");
+        fs::write(&ggml, b"lmgg\x00\x00").unwrap();
+        assert!(has_model_magic(&ggml).unwrap());
+
+        let gguf = dir.join("model.gguf");
+        fs::write(&gguf, b"GGUF\x03\x00").unwrap();
+        assert!(has_model_magic(&gguf).unwrap());
+
+        let junk = dir.join("junk.bin");
+        fs::write(&junk, b"<html>").unwrap();
+        assert!(!has_model_magic(&junk).unwrap());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_custom_model_to_model_info() {
+        let custom = CustomModel {
+            filename: "ggml-uk.bin".to_string(),
+            display_name: "Ukrainian fine-tune".to_string(),
+            source: "https://example.com/ggml-uk.bin".to_string(),
+            size_bytes: 1000,
+            sha256: "ab".repeat(32),
+        };
+        let info = custom.to_model_info();
+        assert_eq!(info.filename, "ggml-uk.bin");
+        assert_eq!(info.sha256, Some("ab".repeat(32)));
+        assert!(info.description.contains("example.com"));
+        assert!(custom.is_remote());
+    }
+
+    #[test]
+    fn test_import_rejects_non_model_extension() {
+        let result = import_local_model(Path::new("/tmp/model.txt"), None);
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_verify_checksum_valid() {
         let dir = std::env::temp_dir().join("s2t_test_checksum");
@@ -610,6 +1166,28 @@ mod tests {
         assert!(path.to_string_lossy().ends_with("ggml-base.bin"));
     }
 
+    #[test]
+    fn test_silero_model_info() {
+        let info = get_silero_model_info();
+        assert!(sanitize_model_filename(&info.filename).is_ok());
+        let hash = info.sha256.unwrap();
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert!(get_silero_model_path().to_string_lossy().contains("vad"));
+    }
+
+    #[test]
+    fn test_resolve_silero_model_path_prefers_configured() {
+        assert_eq!(
+            resolve_silero_model_path(Some("/models/silero.onnx")),
+            Some(PathBuf::from("/models/silero.onnx"))
+        );
+        assert_eq!(
+            resolve_silero_model_path(None),
+            Some(get_silero_model_path()).filter(|path| path.exists())
+        );
+    }
+
     #[test]
     fn test_tdt_model_info_has_three_files() {
         let info = get_tdt_model_info();
@@ -671,6 +1249,11 @@ mod tests {
         assert!(!is_model_downloaded("../../../etc/passwd"));
     }
 
+    #[test]
+    fn test_remove_custom_model_rejects_traversal() {
+        assert!(remove_custom_model("../../../etc/passwd").is_err());
+    }
+
     #[test]
     fn test_delete_model_rejects_traversal() {
         let result = delete_model("../../../etc/passwd");
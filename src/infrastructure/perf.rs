@@ -0,0 +1,151 @@
+//! Process performance measurements (timing, real-time factor, memory).
+
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+/// Peak resident set size of the current process in bytes (`VmHWM`).
+///
+/// Returns `None` on platforms without `/proc/self/status`.
+pub fn peak_rss_bytes() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    parse_status_kb(&status, "VmHWM:").map(|kb| kb * 1024)
+}
+
+/// Reset the peak RSS counter to the current RSS (Linux >= 4.0).
+///
+/// Lets successive measurements in one process report their own peak.
+/// Returns false if the kernel does not support resetting.
+pub fn reset_peak_rss() -> bool {
+    std::fs::write("/proc/self/clear_refs", "5").is_ok()
+}
+
+/// Parse a `Key:   <value> kB` line from a /proc status file.
+fn parse_status_kb(content: &str, key: &str) -> Option<u64> {
+    content
+        .lines()
+        .find(|line| line.starts_with(key))
+        .and_then(|line| line.split_whitespace().nth(1))
+        .and_then(|kb| kb.parse().ok())
+}
+
+/// Real-time factor: processing time divided by audio duration (lower is faster).
+pub fn real_time_factor(elapsed: Duration, audio_secs: f64) -> f64 {
+    if audio_secs <= 0.0 {
+        return 0.0;
+    }
+    elapsed.as_secs_f64() / audio_secs
+}
+
+/// Run `f` and return its result along with the elapsed wall time.
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+/// Real-time factor and backlog of a session that transcribes segments while recording.
+///
+/// The factor sums the processing time of the transcribed segments over their
+/// audio; the backlog is how long ago the oldest segment still in the queue
+/// finished recording, i.e. how far the transcript trails the speaker.
+#[derive(Debug, Default)]
+pub struct LiveThroughput {
+    /// Recording end of each segment not transcribed yet.
+    pending: BTreeMap<usize, Instant>,
+    audio_secs: f64,
+    processing: Duration,
+}
+
+impl LiveThroughput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A segment that ended recording at `end_time` was queued for transcription.
+    pub fn segment_sent(&mut self, segment_id: usize, end_time: Instant) {
+        self.pending.insert(segment_id, end_time);
+    }
+
+    /// A segment was transcribed; `processing` excludes the time spent waiting for the model.
+    pub fn segment_done(&mut self, segment_id: usize, audio_secs: f64, processing: Duration) {
+        self.pending.remove(&segment_id);
+        self.audio_secs += audio_secs;
+        self.processing += processing;
+    }
+
+    /// `None` until the first segment is transcribed.
+    pub fn real_time_factor(&self) -> Option<f64> {
+        (self.audio_secs > 0.0).then(|| real_time_factor(self.processing, self.audio_secs))
+    }
+
+    /// How far the transcript trails the recording at `now`.
+    pub fn backlog(&self, now: Instant) -> Duration {
+        self.pending
+            .values()
+            .min()
+            .map(|&end| now.saturating_duration_since(end))
+            .unwrap_or_default()
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_status_kb() {
+        let status = "Name:\ttest\nVmPeak:\t  200 kB\nVmHWM:\t   1234 kB\n";
+        assert_eq!(parse_status_kb(status, "VmHWM:"), Some(1234));
+        assert_eq!(parse_status_kb(status, "VmRSS:"), None);
+    }
+
+    #[test]
+    fn test_real_time_factor() {
+        assert_eq!(real_time_factor(Duration::from_secs(5), 10.0), 0.5);
+        assert_eq!(real_time_factor(Duration::from_secs(5), 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_live_throughput() {
+        let start = Instant::now();
+        let mut throughput = LiveThroughput::new();
+        assert_eq!(throughput.real_time_factor(), None);
+        assert_eq!(throughput.backlog(start), Duration::ZERO);
+
+        throughput.segment_sent(1, start);
+        throughput.segment_sent(2, start + Duration::from_secs(10));
+        assert_eq!(
+            throughput.backlog(start + Duration::from_secs(35)),
+            Duration::from_secs(35)
+        );
+
+        throughput.segment_done(1, 10.0, Duration::from_secs(4));
+        assert_eq!(throughput.real_time_factor(), Some(0.4));
+        assert_eq!(
+            throughput.backlog(start + Duration::from_secs(35)),
+            Duration::from_secs(25)
+        );
+        assert_eq!(throughput.pending_count(), 1);
+
+        throughput.segment_done(2, 10.0, Duration::from_secs(16));
+        assert_eq!(throughput.real_time_factor(), Some(1.0));
+        assert_eq!(throughput.backlog(start + Duration::from_secs(40)), Duration::ZERO);
+    }
+
+    #[test]
+    fn test_timed_returns_result() {
+        let (value, elapsed) = timed(|| 42);
+        assert_eq!(value, 42);
+        assert!(elapsed < Duration::from_secs(1));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_peak_rss_available_on_linux() {
+        assert!(peak_rss_bytes().unwrap_or(0) > 0);
+    }
+}
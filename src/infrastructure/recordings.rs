@@ -1,53 +1,444 @@
-use anyhow::{Context, Result};
-use chrono::Utc;
-use hound::{WavSpec, WavWriter};
+use anyhow::{bail, Context, Result};
+use chrono::{Local, NaiveDateTime};
+use hound::{WavReader, WavSpec, WavWriter};
 use std::fs;
 use std::path::{Path, PathBuf};
 
+use crate::recording::loudness::Normalization;
+use crate::recording::speaker_tracks::{collect_tracks, SpeakerTrack};
+use crate::recording::spool::SpooledRecording;
+use crate::transcription::rttm::{format_rttm, parse_rttm, rttm_file_id, SpeakerTurn};
+
 const SAMPLE_RATE: u32 = 16000;
 
-/// Save stereo WAV file with mic (left) and loopback (right) channels
-pub fn save_recording(mic_samples: &[f32], loopback_samples: &[f32], output_path: &Path) -> Result<()> {
+/// Samples per channel a spooled recording is saved in at a time (one minute)
+const SPOOLED_CHUNK_SAMPLES: usize = 60 * SAMPLE_RATE as usize;
+
+/// Container of saved recordings (`recording_format`).
+///
+/// WAV is written directly; FLAC and Opus are encoded by ffmpeg, which also
+/// decodes them when a recording is loaded again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingFormat {
+    Wav,
+    Flac,
+    Opus,
+}
+
+/// Bitrate of Opus recordings: plenty for speech at 16 kHz.
+const OPUS_BITRATE: &str = "32k";
+
+impl RecordingFormat {
+    /// Accepted values of `recording_format`
+    pub const NAMES: &'static [&'static str] = &["wav", "flac", "opus"];
+
+    /// Parse a config value; unknown values fall back to WAV.
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "flac" => Self::Flac,
+            "opus" => Self::Opus,
+            _ => Self::Wav,
+        }
+    }
+
+    /// Format of an existing recording, by its extension.
+    pub fn from_path(path: &Path) -> Self {
+        Self::parse(
+            &path
+                .extension()
+                .map(|e| e.to_string_lossy().to_lowercase())
+                .unwrap_or_default(),
+        )
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Wav => "wav",
+            Self::Flac => "flac",
+            Self::Opus => "opus",
+        }
+    }
+
+    /// ffmpeg arguments selecting the encoder.
+    fn encoder_args(self) -> &'static [&'static str] {
+        match self {
+            Self::Wav => &["-c:a", "pcm_f32le"],
+            Self::Flac => &["-c:a", "flac", "-compression_level", "8"],
+            Self::Opus => &["-c:a", "libopus", "-b:a", OPUS_BITRATE, "-application", "voip"],
+        }
+    }
+}
+
+/// Save stereo recording with mic (left) and loopback (right) channels,
+/// in the format given by the extension of `output_path`.
+///
+/// `normalization` scales only the written file; the caller's samples are
+/// left as recorded for transcription.
+pub fn save_recording(
+    mic_samples: &[f32],
+    loopback_samples: &[f32],
+    output_path: &Path,
+    normalization: Normalization,
+) -> Result<()> {
+    let gain = normalization.gain(&[mic_samples, loopback_samples]);
+
+    let interleaved = interleave(
+        mic_samples,
+        loopback_samples,
+        mic_samples.len().max(loopback_samples.len()),
+        gain,
+    );
+    write_audio(&interleaved, 2, output_path)
+}
+
+/// Save a spooled conference recording like `save_recording`, reading it
+/// from disk a minute at a time.
+///
+/// Measuring loudness would need the whole recording at once, so loudness
+/// normalization is replaced by peak normalization to the same ceiling.
+pub fn save_spooled_recording(
+    recording: &mut SpooledRecording,
+    output_path: &Path,
+    normalization: Normalization,
+) -> Result<()> {
+    let normalization = match normalization {
+        Normalization::Loudness { ceiling_dbfs, .. } => Normalization::Peak { ceiling_dbfs },
+        other => other,
+    };
+    let len = recording.len();
+    let mut peak = 0.0_f32;
+    for start in (0..len).step_by(SPOOLED_CHUNK_SAMPLES) {
+        let (mic, loopback) = recording.read(start..start + SPOOLED_CHUNK_SAMPLES)?;
+        peak = mic.iter().chain(&loopback).fold(peak, |max, s| max.max(s.abs()));
+    }
+    // The peak alone decides a peak gain
+    let gain = normalization.gain(&[&[peak]]);
+
+    let chunks = (0..len).step_by(SPOOLED_CHUNK_SAMPLES).map(|start| {
+        let end = (start + SPOOLED_CHUNK_SAMPLES).min(len);
+        let (mic, loopback) = recording.read(start..end)?;
+        Ok(interleave(&mic, &loopback, end - start, gain))
+    });
+    write_audio_chunks(chunks, 2, output_path)
+}
+
+/// `len` frames of [mic, loopback, mic, loopback, ...], silence where a channel ends.
+fn interleave(mic_samples: &[f32], loopback_samples: &[f32], len: usize, gain: f32) -> Vec<f32> {
+    let mut interleaved = Vec::with_capacity(len * 2);
+    for i in 0..len {
+        interleaved.push(mic_samples.get(i).copied().unwrap_or(0.0) * gain);
+        interleaved.push(loopback_samples.get(i).copied().unwrap_or(0.0) * gain);
+    }
+    interleaved
+}
+
+/// Save a mono dictation recording, in the format given by the extension of `output_path`.
+pub fn save_mono_recording(samples: &[f32], output_path: &Path) -> Result<()> {
+    write_audio(samples, 1, output_path)
+}
+
+/// Write interleaved 16 kHz samples as WAV or through the ffmpeg encoder.
+fn write_audio(interleaved: &[f32], channels: u16, output_path: &Path) -> Result<()> {
+    write_audio_chunks(std::iter::once(Ok(interleaved)), channels, output_path)
+}
+
+/// Like `write_audio`, for audio that arrives in consecutive chunks.
+fn write_audio_chunks<C: AsRef<[f32]>>(
+    chunks: impl Iterator<Item = Result<C>>,
+    channels: u16,
+    output_path: &Path,
+) -> Result<()> {
     // Ensure directory exists
     if let Some(parent) = output_path.parent() {
         fs::create_dir_all(parent).with_context(|| format!("Не вдалося створити директорію: {}", parent.display()))?;
     }
 
-    // Create WAV writer
+    let format = RecordingFormat::from_path(output_path);
+    if format != RecordingFormat::Wav {
+        return encode_with_ffmpeg(chunks, channels, output_path, format);
+    }
+
     let spec = WavSpec {
-        channels: 2, // Stereo: left = mic, right = loopback
+        channels,
         sample_rate: SAMPLE_RATE,
         bits_per_sample: 32,
         sample_format: hound::SampleFormat::Float,
     };
-
     let mut writer = WavWriter::create(output_path, spec)
         .with_context(|| format!("Не вдалося створити WAV файл: {}", output_path.display()))?;
+    for chunk in chunks {
+        for &sample in chunk?.as_ref() {
+            writer
+                .write_sample(sample)
+                .context("Не вдалося записати зразок аудіо")?;
+        }
+    }
+    writer.finalize().context("Не вдалося завершити запис WAV файлу")?;
 
-    // Interleave samples: [mic, loopback, mic, loopback, ...]
-    let max_len = mic_samples.len().max(loopback_samples.len());
+    Ok(())
+}
 
-    for i in 0..max_len {
-        let mic_sample = mic_samples.get(i).copied().unwrap_or(0.0);
-        let loopback_sample = loopback_samples.get(i).copied().unwrap_or(0.0);
+/// ffmpeg arguments encoding raw f32 samples from stdin into `output_path`.
+fn encode_args(channels: u16, output_path: &Path, format: RecordingFormat) -> Vec<std::ffi::OsString> {
+    let mut args: Vec<std::ffi::OsString> = ["-y", "-loglevel", "error", "-f", "f32le", "-ar"]
+        .iter()
+        .map(Into::into)
+        .collect();
+    args.push(SAMPLE_RATE.to_string().into());
+    args.push("-ac".into());
+    args.push(channels.to_string().into());
+    args.extend(["-i", "pipe:0"].iter().map(Into::into));
+    args.extend(format.encoder_args().iter().map(Into::into));
+    args.push(output_path.into());
+    args
+}
 
-        writer
-            .write_sample(mic_sample)
-            .context("Не вдалося записати зразок мікрофона")?;
-        writer
-            .write_sample(loopback_sample)
-            .context("Не вдалося записати зразок системного аудіо")?;
+fn encode_with_ffmpeg<C: AsRef<[f32]>>(
+    chunks: impl Iterator<Item = Result<C>>,
+    channels: u16,
+    output_path: &Path,
+    format: RecordingFormat,
+) -> Result<()> {
+    let mut child = std::process::Command::new("ffmpeg")
+        .args(encode_args(channels, output_path, format))
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .context("Не вдалося запустити ffmpeg. Переконайтеся, що ffmpeg встановлено (sudo dnf install ffmpeg-free)")?;
+
+    {
+        let mut stdin = child.stdin.take().context("Не вдалося передати аудіо ffmpeg")?;
+        for chunk in chunks {
+            let bytes: Vec<u8> = chunk?.as_ref().iter().flat_map(|s| s.to_le_bytes()).collect();
+            std::io::Write::write_all(&mut stdin, &bytes).context("Не вдалося передати аудіо ffmpeg")?;
+        }
+    }
+    let output = child.wait_with_output().context("ffmpeg не завершився")?;
+    if !output.status.success() {
+        bail!(
+            "ffmpeg не зміг записати {}: {}",
+            output_path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
     }
+    Ok(())
+}
 
-    writer.finalize().context("Не вдалося завершити запис WAV файлу")?;
+/// Decode a FLAC or Opus recording to interleaved 16 kHz samples and its channel count.
+fn decode_with_ffmpeg(path: &Path) -> Result<(Vec<f32>, u16)> {
+    let probe = std::process::Command::new("ffprobe")
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "a:0",
+            "-show_entries",
+            "stream=channels",
+            "-of",
+            "csv=p=0",
+        ])
+        .arg(path)
+        .output()
+        .context("Не вдалося запустити ffprobe. Переконайтеся, що ffmpeg встановлено (sudo dnf install ffmpeg-free)")?;
+    let channels: u16 = String::from_utf8_lossy(&probe.stdout)
+        .trim()
+        .parse()
+        .with_context(|| format!("Не вдалося визначити кількість каналів: {}", path.display()))?;
 
-    Ok(())
+    let output = std::process::Command::new("ffmpeg")
+        .args(["-loglevel", "error", "-i"])
+        .arg(path)
+        .args([
+            "-f",
+            "f32le",
+            "-ar",
+            &SAMPLE_RATE.to_string(),
+            "-ac",
+            &channels.to_string(),
+            "pipe:1",
+        ])
+        .output()
+        .context("Не вдалося запустити ffmpeg")?;
+    if !output.status.success() {
+        bail!(
+            "ffmpeg не зміг прочитати {}: {}",
+            path.display(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let samples = output
+        .stdout
+        .chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect();
+    Ok((samples, channels))
+}
+
+/// Load a recording saved by [`save_recording`] as (mic, loopback) channels.
+///
+/// A dictation recording saved by [`save_mono_recording`] has an empty
+/// loopback. FLAC and Opus recordings are decoded by ffmpeg.
+pub fn load_recording(path: &Path) -> Result<(Vec<f32>, Vec<f32>)> {
+    let (samples, channels) = if RecordingFormat::from_path(path) == RecordingFormat::Wav {
+        let reader = WavReader::open(path).with_context(|| format!("Не вдалося відкрити запис: {}", path.display()))?;
+        let spec = reader.spec();
+        if spec.sample_format != hound::SampleFormat::Float {
+            bail!("Непідтримуваний формат запису: {}", path.display());
+        }
+        let samples: Vec<f32> = reader
+            .into_samples::<f32>()
+            .collect::<Result<_, _>>()
+            .context("Не вдалося прочитати запис")?;
+        (samples, spec.channels)
+    } else {
+        decode_with_ffmpeg(path)?
+    };
+
+    match channels {
+        1 => Ok((samples, Vec::new())),
+        2 => {
+            let mic = samples.iter().step_by(2).copied().collect();
+            let loopback = samples.iter().skip(1).step_by(2).copied().collect();
+            Ok((mic, loopback))
+        }
+        _ => bail!("Непідтримуваний формат запису: {}", path.display()),
+    }
 }
 
-/// Generate filename for recording based on current timestamp
-pub fn generate_recording_filename() -> String {
-    let now = Utc::now();
-    format!("conference_{}.wav", now.format("%Y-%m-%d_%H-%M-%S"))
+/// Speaker turns saved next to a recording (`<name>.rttm`)
+pub fn turns_path(recording: &Path) -> PathBuf {
+    recording.with_extension("rttm")
+}
+
+/// Save Sortformer speaker turns next to the recording
+pub fn save_turns(recording: &Path, turns: &[SpeakerTurn]) -> Result<()> {
+    let path = turns_path(recording);
+    fs::write(&path, format_rttm(&rttm_file_id(recording), turns))
+        .with_context(|| format!("Не вдалося записати файл: {}", path.display()))
+}
+
+/// Speaker turns saved next to a recording, or `None` if there are none
+pub fn load_turns(recording: &Path) -> Result<Option<Vec<SpeakerTurn>>> {
+    let turns_file = turns_path(recording);
+    if !turns_file.exists() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(&turns_file)
+        .with_context(|| format!("Не вдалося прочитати файл: {}", turns_file.display()))?;
+    Ok(Some(parse_rttm(&content)))
+}
+
+/// One audio track per speaker of a saved conference recording.
+///
+/// Uses the saved Sortformer turns if there are any; otherwise the
+/// recording was diarized by channel, so each non-silent channel is a speaker.
+pub fn speaker_tracks(recording: &Path) -> Result<Vec<SpeakerTrack>> {
+    let (mic, loopback) = load_recording(recording)?;
+
+    if let Some(turns) = load_turns(recording)? {
+        let mixed: Vec<f32> = mic.iter().zip(&loopback).map(|(m, l)| (m + l) / 2.0).collect();
+        return Ok(collect_tracks(&mixed, &turns, SAMPLE_RATE));
+    }
+
+    Ok([("Ви", mic), ("Учасник", loopback)]
+        .into_iter()
+        .filter(|(_, samples)| samples.iter().any(|&s| s != 0.0))
+        .map(|(speaker, samples)| SpeakerTrack {
+            speaker: speaker.to_string(),
+            samples,
+        })
+        .collect())
+}
+
+/// Placeholders understood in `recording_filename_template`
+pub const FILENAME_PLACEHOLDERS: &[&str] = &["{date}", "{time}", "{mode}", "{title}"];
+
+/// Longest session title kept in a filename, in characters
+const MAX_TITLE_CHARS: usize = 80;
+
+/// Generate a filename for a new recording from `template`, not yet taken in
+/// the recordings directory.
+///
+/// `mode` is the recording mode ("conference", "dictation", ...), `title`
+/// the session title if there is one.
+pub fn generate_recording_filename(template: &str, mode: &str, title: Option<&str>, format: RecordingFormat) -> String {
+    let filename = render_recording_filename(template, Local::now().naive_local(), mode, title, format);
+    unique_filename(&crate::app::config::recordings_dir(), &filename)
+}
+
+/// Fill in the placeholders of `template`. A placeholder without a value
+/// (no title) disappears together with the separator before it, and the
+/// name always ends in the extension of `format`.
+pub fn render_recording_filename(
+    template: &str,
+    at: NaiveDateTime,
+    mode: &str,
+    title: Option<&str>,
+    format: RecordingFormat,
+) -> String {
+    let title: String = title
+        .map(filename_part)
+        .unwrap_or_default()
+        .chars()
+        .take(MAX_TITLE_CHARS)
+        .collect();
+    let name = template
+        .replace("{date}", &at.format("%Y-%m-%d").to_string())
+        .replace("{time}", &at.format("%H-%M-%S").to_string())
+        .replace("{mode}", &filename_part(mode))
+        .replace("{title}", &title);
+    let stem = RecordingFormat::NAMES
+        .iter()
+        .find_map(|extension| name.strip_suffix(&format!(".{}", extension)))
+        .unwrap_or(&name);
+
+    // "{date}_{title}_{mode}" without a title leaves "__" and trailing "_"
+    let mut tidy = String::with_capacity(stem.len());
+    for c in stem.chars() {
+        if !(matches!(c, '_' | ' ') && tidy.ends_with(c)) {
+            tidy.push(c);
+        }
+    }
+    let tidy = tidy.trim_matches(['_', '-', ' ', '.']);
+    let stem = if tidy.is_empty() { "recording" } else { tidy };
+    format!("{}.{}", stem, format.extension())
+}
+
+/// `text` usable inside a filename: path separators and characters other
+/// systems reject become `_`, whitespace runs become one `_`.
+fn filename_part(text: &str) -> String {
+    let mut part = String::with_capacity(text.len());
+    for c in text.trim().chars() {
+        let c =
+            if c.is_whitespace() || c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+            {
+                '_'
+            } else {
+                c
+            };
+        if !(c == '_' && part.ends_with('_')) {
+            part.push(c);
+        }
+    }
+    part
+}
+
+/// `filename`, or `<stem>_2.<ext>`, `<stem>_3.<ext>`, ... if it already exists in `dir`
+pub fn unique_filename(dir: &Path, filename: &str) -> String {
+    if !dir.join(filename).exists() {
+        return filename.to_string();
+    }
+    let path = Path::new(filename);
+    let stem = path.file_stem().map(|s| s.to_string_lossy()).unwrap_or_default();
+    let extension = path
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+    (2..)
+        .map(|n| format!("{}_{}{}", stem, n, extension))
+        .find(|candidate| !dir.join(candidate).exists())
+        .expect("unbounded range")
 }
 
 /// Get full path for a recording file
@@ -61,3 +452,169 @@ pub fn ensure_recordings_dir() -> Result<()> {
     fs::create_dir_all(&dir).with_context(|| format!("Не вдалося створити директорію записів: {}", dir.display()))?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at() -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2026, 3, 9)
+            .unwrap()
+            .and_hms_opt(14, 5, 7)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_template_is_filled_in() {
+        let template = "{date}_{time}_{mode}_{title}.wav";
+        assert_eq!(
+            render_recording_filename(
+                template,
+                at(),
+                "conference",
+                Some("Синк 2026-03-09"),
+                RecordingFormat::Wav
+            ),
+            "2026-03-09_14-05-07_conference_Синк_2026-03-09.wav"
+        );
+        assert_eq!(
+            render_recording_filename("{mode}-{date}", at(), "dictation", None, RecordingFormat::Wav),
+            "dictation-2026-03-09.wav"
+        );
+    }
+
+    #[test]
+    fn test_missing_title_leaves_no_separators() {
+        assert_eq!(
+            render_recording_filename(
+                "{date}_{time}_{mode}_{title}.wav",
+                at(),
+                "conference",
+                None,
+                RecordingFormat::Wav
+            ),
+            "2026-03-09_14-05-07_conference.wav"
+        );
+        assert_eq!(
+            render_recording_filename(
+                "{date}_{title}_{mode}",
+                at(),
+                "conference",
+                Some("  "),
+                RecordingFormat::Wav
+            ),
+            "2026-03-09_conference.wav"
+        );
+        assert_eq!(
+            render_recording_filename("{title}", at(), "conference", None, RecordingFormat::Wav),
+            "recording.wav"
+        );
+    }
+
+    #[test]
+    fn test_title_cannot_escape_the_directory() {
+        let name = render_recording_filename(
+            "{title}",
+            at(),
+            "conference",
+            Some("../Q1: план/звіт?"),
+            RecordingFormat::Wav,
+        );
+        assert_eq!(name, "Q1_план_звіт.wav");
+        assert!(!name.contains('/'));
+    }
+
+    #[test]
+    fn test_filename_takes_extension_of_format() {
+        let template = "{date}_{mode}.wav";
+        assert_eq!(
+            render_recording_filename(template, at(), "dictation", None, RecordingFormat::Flac),
+            "2026-03-09_dictation.flac"
+        );
+        assert_eq!(
+            render_recording_filename("{date}_{mode}", at(), "dictation", None, RecordingFormat::Opus),
+            "2026-03-09_dictation.opus"
+        );
+    }
+
+    #[test]
+    fn test_recording_format_names_and_paths() {
+        for name in RecordingFormat::NAMES {
+            assert_eq!(RecordingFormat::parse(name).extension(), *name);
+        }
+        assert_eq!(RecordingFormat::parse("mp3"), RecordingFormat::Wav);
+        assert_eq!(
+            RecordingFormat::from_path(Path::new("/r/a.FLAC")),
+            RecordingFormat::Flac
+        );
+        assert_eq!(RecordingFormat::from_path(Path::new("/r/a")), RecordingFormat::Wav);
+    }
+
+    #[test]
+    fn test_encode_args_read_raw_samples_from_stdin() {
+        let args: Vec<String> = encode_args(2, Path::new("/r/a.opus"), RecordingFormat::Opus)
+            .iter()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        let joined = args.join(" ");
+        assert!(joined.contains("-f f32le -ar 16000 -ac 2 -i pipe:0"));
+        assert!(joined.contains("-c:a libopus"));
+        assert_eq!(args.last().unwrap(), "/r/a.opus");
+    }
+
+    #[test]
+    fn test_mono_recording_loads_without_loopback() {
+        let path = std::env::temp_dir().join(format!("dictation_mono_{}.wav", std::process::id()));
+        save_mono_recording(&[0.25, -0.5, 0.0], &path).unwrap();
+        let (mic, loopback) = load_recording(&path).unwrap();
+        assert_eq!(mic, vec![0.25, -0.5, 0.0]);
+        assert!(loopback.is_empty());
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_spooled_recording_saves_like_in_memory_one() {
+        use crate::recording::spool::SampleSpool;
+
+        let mic: Vec<f32> = (0..SPOOLED_CHUNK_SAMPLES + 500)
+            .map(|i| (i % 100) as f32 / 200.0)
+            .collect();
+        let loopback = vec![-0.25; 700];
+        let mut recording = SpooledRecording {
+            mic: SampleSpool::create().unwrap(),
+            loopback: SampleSpool::create().unwrap(),
+        };
+        recording.mic.append(&mic).unwrap();
+        recording.loopback.append(&loopback).unwrap();
+
+        let path = std::env::temp_dir().join(format!("dictation_spooled_{}.wav", std::process::id()));
+        let normalization = Normalization::Loudness {
+            target_lufs: -16.0,
+            ceiling_dbfs: 0.0,
+        };
+        save_spooled_recording(&mut recording, &path, normalization).unwrap();
+        let (saved_mic, saved_loopback) = load_recording(&path).unwrap();
+        let _ = fs::remove_file(&path);
+
+        // Peak of 0.495 brought to 0 dBFS
+        let gain = 1.0 / 0.495;
+        assert_eq!(saved_mic.len(), mic.len());
+        assert_eq!(saved_loopback.len(), mic.len());
+        assert!((saved_mic[99] - 0.495 * gain).abs() < 1e-5);
+        assert!((saved_loopback[0] + 0.25 * gain).abs() < 1e-5);
+        assert_eq!(saved_loopback[700], 0.0);
+    }
+
+    #[test]
+    fn test_unique_filename_adds_counter() {
+        let dir = std::env::temp_dir().join(format!("recordings_unique_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(unique_filename(&dir, "a.wav"), "a.wav");
+        fs::write(dir.join("a.wav"), b"").unwrap();
+        assert_eq!(unique_filename(&dir, "a.wav"), "a_2.wav");
+        fs::write(dir.join("a_2.wav"), b"").unwrap();
+        assert_eq!(unique_filename(&dir, "a.wav"), "a_3.wav");
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
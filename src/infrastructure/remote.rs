@@ -0,0 +1,418 @@
+//! Remote control from a phone browser over the local network.
+//!
+//! When enabled, a small HTTP server listens on the configured port. It serves
+//! a page with start/stop buttons, the recording state and the last result,
+//! which polls a JSON API once a second. Every API call must carry the pairing
+//! token, either as `?token=` or as an `Authorization: Bearer` header; the
+//! token is generated once and kept in the config directory, and the pairing
+//! URL with it is shown as a QR code in the settings. The token is re-read
+//! for every request, so regenerating it cuts off paired phones at once.
+//!
+//! The server speaks plain HTTP: the token and the results travel
+//! unencrypted, so it is meant for a trusted local network only.
+//!
+//! Requests are handled one at a time on a background thread. Commands are
+//! forwarded to the GTK main loop, which applies them as if the record button
+//! was pressed.
+
+use crate::app::config::{remote_token_path, set_owner_only_permissions};
+use crate::domain::types::AppState;
+use anyhow::{Context, Result};
+use async_channel::Sender;
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::{IpAddr, Ipv4Addr, TcpListener, TcpStream, UdpSocket};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Largest request head that is read; the API has no request bodies.
+const MAX_REQUEST_BYTES: usize = 8 * 1024;
+
+/// A slow or stalled client must not block the other requests for long.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Commands a remote can send.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RemoteCommand {
+    Start,
+    Stop,
+}
+
+/// What the remote page shows, kept up to date by the GUI.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RemoteStatus {
+    /// "loading", "idle", "recording" or "processing"
+    pub state: &'static str,
+    pub elapsed_secs: Option<u64>,
+    /// Microphone peak level on the meter's dBFS scale, 0 to 1, so the
+    /// presenter can see the mic picks them up
+    pub level: f32,
+    pub last_result: Option<String>,
+    pub last_result_at: Option<DateTime<Utc>>,
+}
+
+impl RemoteStatus {
+    pub fn set_state(&mut self, state: AppState, elapsed_secs: Option<u64>, level: f32) {
+        self.state = match state {
+            AppState::Loading => "loading",
+            AppState::Idle => "idle",
+            AppState::Recording => "recording",
+            AppState::Processing => "processing",
+        };
+        self.elapsed_secs = elapsed_secs;
+        self.level = level;
+    }
+
+    pub fn set_result(&mut self, text: &str) {
+        self.last_result = Some(text.to_string());
+        self.last_result_at = Some(Utc::now());
+    }
+}
+
+pub type SharedRemoteStatus = Arc<Mutex<RemoteStatus>>;
+
+/// The pairing token, generated on first use.
+pub fn load_or_create_token() -> Result<String> {
+    let path = remote_token_path();
+    if let Ok(token) = fs::read_to_string(&path) {
+        let token = token.trim();
+        if !token.is_empty() {
+            return Ok(token.to_string());
+        }
+    }
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("Не вдалося створити директорію: {}", dir.display()))?;
+    }
+    fs::write(&path, &token).with_context(|| format!("Не вдалося записати токен: {}", path.display()))?;
+    set_owner_only_permissions(&path)?;
+    Ok(token)
+}
+
+/// Replace the pairing token, so phones paired with the old one lose access.
+pub fn regenerate_token() -> Result<String> {
+    let path = remote_token_path();
+    match fs::remove_file(&path) {
+        Ok(()) => {}
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+        Err(e) => return Err(e).with_context(|| format!("Не вдалося видалити токен: {}", path.display())),
+    }
+    load_or_create_token()
+}
+
+/// Address of this machine on the local network, as seen by other devices.
+///
+/// Connecting a UDP socket sends nothing; it only picks the outgoing interface.
+pub fn local_ip() -> IpAddr {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0))
+        .and_then(|socket| {
+            socket.connect((Ipv4Addr::new(192, 0, 2, 1), 80))?;
+            socket.local_addr()
+        })
+        .map(|addr| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST))
+}
+
+/// URL to open on the phone, with the token included.
+pub fn pairing_url(ip: IpAddr, port: u16, token: &str) -> String {
+    format!("http://{}:{}/?token={}", ip, port, token)
+}
+
+/// The pairing URL as a QR code drawn with Unicode block characters.
+pub fn pairing_qr(url: &str) -> Result<String> {
+    let code = qrcode::QrCode::new(url.as_bytes()).context("Не вдалося створити QR-код")?;
+    Ok(code
+        .render::<qrcode::render::unicode::Dense1x2>()
+        .dark_color(qrcode::render::unicode::Dense1x2::Light)
+        .light_color(qrcode::render::unicode::Dense1x2::Dark)
+        .build())
+}
+
+/// Start the server on `port` (all interfaces) in a background thread.
+pub fn spawn_server(port: u16, status: SharedRemoteStatus, commands: Sender<RemoteCommand>) -> Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::UNSPECIFIED, port))
+        .with_context(|| format!("Не вдалося відкрити порт {} для віддаленого керування", port))?;
+    std::thread::spawn(move || {
+        for stream in listener.incoming() {
+            let result = stream
+                .map_err(anyhow::Error::from)
+                .and_then(|stream| Ok((stream, load_or_create_token()?)))
+                .and_then(|(stream, token)| handle_connection(stream, &token, &status, &commands));
+            if let Err(e) = result {
+                eprintln!("Віддалене керування: {}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+fn handle_connection(
+    mut stream: TcpStream,
+    token: &str,
+    status: &SharedRemoteStatus,
+    commands: &Sender<RemoteCommand>,
+) -> Result<()> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+    let mut head = Vec::new();
+    let mut buf = [0u8; 1024];
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") && head.len() < MAX_REQUEST_BYTES {
+        let n = stream.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        head.extend_from_slice(&buf[..n]);
+    }
+
+    let response = match parse_request(&String::from_utf8_lossy(&head)) {
+        Some(request) => route(&request, token, status, commands),
+        None => Response::error(400, "bad request"),
+    };
+    stream.write_all(&response.to_bytes())?;
+    Ok(())
+}
+
+/// The parts of an HTTP request the API looks at.
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    token: Option<String>,
+}
+
+fn parse_request(head: &str) -> Option<Request> {
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next()?.split_whitespace();
+    let method = request_line.next()?.to_string();
+    let target = request_line.next()?;
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut token = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("token="))
+        .map(str::to_string);
+    for line in lines {
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("authorization") {
+                if let Some(bearer) = value.trim().strip_prefix("Bearer ") {
+                    token = Some(bearer.trim().to_string());
+                }
+            }
+        }
+    }
+
+    Some(Request {
+        method,
+        path: path.to_string(),
+        token,
+    })
+}
+
+/// Compare tokens without returning early on the first differing byte.
+fn token_matches(given: &str, expected: &str) -> bool {
+    given.len() == expected.len() && given.bytes().zip(expected.bytes()).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+fn route(request: &Request, token: &str, status: &SharedRemoteStatus, commands: &Sender<RemoteCommand>) -> Response {
+    if request.method == "GET" && request.path == "/" {
+        return Response::new(200, "text/html; charset=utf-8", PAGE.to_string());
+    }
+    if !request.path.starts_with("/api/") {
+        return Response::error(404, "not found");
+    }
+    if !request
+        .token
+        .as_deref()
+        .is_some_and(|given| token_matches(given, token))
+    {
+        return Response::error(401, "invalid token");
+    }
+
+    let command = match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/api/status") => return Response::json(200, &*status.lock()),
+        ("GET", "/api/last") => {
+            let status = status.lock();
+            return Response::json(
+                200,
+                &serde_json::json!({ "text": status.last_result, "timestamp": status.last_result_at }),
+            );
+        }
+        ("POST", "/api/start") => RemoteCommand::Start,
+        ("POST", "/api/stop") => RemoteCommand::Stop,
+        _ => return Response::error(404, "not found"),
+    };
+    // The GUI picks commands up between frames; a second one while it is busy is dropped
+    let accepted = commands.try_send(command).is_ok();
+    Response::json(202, &serde_json::json!({ "accepted": accepted }))
+}
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn new(status: u16, content_type: &'static str, body: String) -> Self {
+        Self {
+            status,
+            content_type,
+            body,
+        }
+    }
+
+    fn json(status: u16, value: &impl Serialize) -> Self {
+        let body = serde_json::to_string(value).unwrap_or_else(|_| "{}".to_string());
+        Self::new(status, "application/json", body)
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Self::json(status, &serde_json::json!({ "error": message }))
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let reason = match self.status {
+            200 => "OK",
+            202 => "Accepted",
+            400 => "Bad Request",
+            401 => "Unauthorized",
+            _ => "Not Found",
+        };
+        format!(
+            "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason,
+            self.content_type,
+            self.body.len(),
+            self.body
+        )
+        .into_bytes()
+    }
+}
+
+/// Remote control page; reads the token from its own URL.
+const PAGE: &str = r#"<!DOCTYPE html>
+<html lang="uk">
+<head>
+<meta charset="utf-8">
+<meta name="viewport" content="width=device-width, initial-scale=1">
+<title>Голосова диктовка</title>
+<style>
+body { font-family: sans-serif; margin: 1.5em; }
+button { font-size: 1.4em; padding: 0.6em 1.2em; margin: 0.3em 0.3em 0.3em 0; }
+#state { font-size: 1.6em; font-weight: bold; }
+#level { width: 100%; height: 1em; }
+#result { white-space: pre-wrap; border-top: 1px solid #ccc; padding-top: 0.5em; }
+</style>
+</head>
+<body>
+<div id="state">…</div>
+<meter id="level" min="0" max="1" value="0"></meter>
+<div>
+<button id="start">Почати запис</button>
+<button id="stop">Зупинити</button>
+</div>
+<div id="result"></div>
+<script>
+const token = new URLSearchParams(location.search).get("token") || "";
+const headers = { "Authorization": "Bearer " + token };
+const names = { loading: "Завантаження моделі", idle: "Очікування", recording: "Запис", processing: "Розпізнавання" };
+async function refresh() {
+  try {
+    const r = await fetch("/api/status", { headers });
+    if (!r.ok) { document.getElementById("state").textContent = "Немає доступу: відскануйте QR-код ще раз"; return; }
+    const s = await r.json();
+    let state = names[s.state] || s.state;
+    if (s.elapsed_secs !== null) state += " " + Math.floor(s.elapsed_secs / 60) + ":" + String(s.elapsed_secs % 60).padStart(2, "0");
+    document.getElementById("state").textContent = state;
+    document.getElementById("level").value = s.level;
+    document.getElementById("result").textContent = s.last_result || "";
+  } catch (e) {
+    document.getElementById("state").textContent = "Немає зв'язку";
+  }
+}
+async function send(command) {
+  await fetch("/api/" + command, { method: "POST", headers });
+  refresh();
+}
+document.getElementById("start").onclick = () => send("start");
+document.getElementById("stop").onclick = () => send("stop");
+setInterval(refresh, 1000);
+refresh();
+</script>
+</body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, path: &str, token: Option<&str>) -> Request {
+        Request {
+            method: method.to_string(),
+            path: path.to_string(),
+            token: token.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_request_token_from_query_or_header() {
+        let parsed = parse_request("GET /api/status?token=abc HTTP/1.1\r\nHost: x\r\n\r\n").unwrap();
+        assert_eq!(parsed, request("GET", "/api/status", Some("abc")));
+
+        let parsed = parse_request("POST /api/start HTTP/1.1\r\nauthorization: Bearer xyz\r\n\r\n").unwrap();
+        assert_eq!(parsed, request("POST", "/api/start", Some("xyz")));
+
+        assert_eq!(parse_request("GET / HTTP/1.1\r\n\r\n").unwrap().token, None);
+        assert!(parse_request("").is_none());
+    }
+
+    #[test]
+    fn test_route_requires_token_and_forwards_commands() {
+        let status: SharedRemoteStatus = Arc::default();
+        let (tx, rx) = async_channel::bounded(1);
+
+        assert_eq!(route(&request("GET", "/", None), "secret", &status, &tx).status, 200);
+        assert_eq!(
+            route(&request("GET", "/api/status", None), "secret", &status, &tx).status,
+            401
+        );
+        assert_eq!(
+            route(&request("POST", "/api/start", Some("secreT")), "secret", &status, &tx).status,
+            401
+        );
+        assert!(rx.try_recv().is_err());
+
+        assert_eq!(
+            route(&request("POST", "/api/start", Some("secret")), "secret", &status, &tx).status,
+            202
+        );
+        assert_eq!(rx.try_recv().unwrap(), RemoteCommand::Start);
+        assert_eq!(
+            route(&request("GET", "/api/nope", Some("secret")), "secret", &status, &tx).status,
+            404
+        );
+
+        status.lock().set_state(AppState::Recording, Some(12), 0.5);
+        status.lock().set_result("Привіт");
+        let response = route(&request("GET", "/api/status", Some("secret")), "secret", &status, &tx);
+        let json: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(json["state"], "recording");
+        assert_eq!(json["elapsed_secs"], 12);
+        assert_eq!(json["last_result"], "Привіт");
+    }
+
+    #[test]
+    fn test_pairing_url_and_token_matches() {
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 20));
+        assert_eq!(pairing_url(ip, 8765, "abc"), "http://192.168.1.20:8765/?token=abc");
+        assert!(token_matches("abc", "abc"));
+        assert!(!token_matches("abd", "abc"));
+        assert!(!token_matches("ab", "abc"));
+    }
+}
@@ -1,5 +1,6 @@
 use crate::app::config::{save_config, Config};
 use crate::domain::traits::Transcription;
+use crate::history::History;
 use crate::infrastructure::models::{get_model_path, list_downloaded_models};
 use crate::transcription::TranscriptionService;
 use async_channel::Sender;
@@ -10,12 +11,18 @@ use ksni::{
 use parking_lot::Mutex;
 use std::sync::Arc;
 
+/// Most pinned entries offered in the tray menu
+const MAX_PINNED_ITEMS: usize = 10;
+
 #[derive(Debug, Clone)]
 pub enum TrayAction {
     OpenWindow,
+    ToggleRecording,
     ManageModels,
     OpenHistory,
     OpenSettings,
+    /// Copy the text of the pinned history entry with this ID
+    CopyPinned(String),
     Quit,
 }
 
@@ -23,6 +30,7 @@ pub struct DictationTray {
     tx: Sender<TrayAction>,
     config: Arc<Mutex<Config>>,
     transcription: Arc<Mutex<TranscriptionService>>,
+    history: Arc<Mutex<History>>,
 }
 
 impl DictationTray {
@@ -30,11 +38,13 @@ impl DictationTray {
         tx: Sender<TrayAction>,
         config: Arc<Mutex<Config>>,
         transcription: Arc<Mutex<TranscriptionService>>,
+        history: Arc<Mutex<History>>,
     ) -> Self {
         Self {
             tx,
             config,
             transcription,
+            history,
         }
     }
 
@@ -42,8 +52,41 @@ impl DictationTray {
         tx: Sender<TrayAction>,
         config: Arc<Mutex<Config>>,
         transcription: Arc<Mutex<TranscriptionService>>,
+        history: Arc<Mutex<History>>,
     ) -> Result<ksni::Handle<Self>, ksni::Error> {
-        Self::new(tx, config, transcription).spawn().await
+        Self::new(tx, config, transcription, history).spawn().await
+    }
+
+    /// Quick-access items copying pinned entries to the clipboard.
+    fn pinned_items(&self) -> Vec<MenuItem<Self>> {
+        let history = self.history.lock();
+        let mut items: Vec<MenuItem<Self>> = history
+            .pinned_entries()
+            .take(MAX_PINNED_ITEMS)
+            .map(|entry| {
+                let id = entry.id.clone();
+                StandardItem {
+                    label: entry.title.clone().unwrap_or_else(|| entry.preview()),
+                    activate: Box::new(move |tray: &mut Self| {
+                        let _ = tray.tx.try_send(TrayAction::CopyPinned(id.clone()));
+                    }),
+                    ..Default::default()
+                }
+                .into()
+            })
+            .collect();
+
+        if items.is_empty() {
+            items.push(
+                StandardItem {
+                    label: "(Немає закріплених записів)".to_string(),
+                    enabled: false,
+                    ..Default::default()
+                }
+                .into(),
+            );
+        }
+        items
     }
 
     fn select_model(&mut self, filename: &str) {
@@ -127,6 +170,20 @@ impl Tray for DictationTray {
                 ..Default::default()
             }
             .into(),
+            StandardItem {
+                label: "Почати/зупинити запис".to_string(),
+                activate: Box::new(|tray: &mut Self| {
+                    let _ = tray.tx.try_send(TrayAction::ToggleRecording);
+                }),
+                ..Default::default()
+            }
+            .into(),
+            SubMenu::<Self> {
+                label: "Закріплені".to_string(),
+                submenu: self.pinned_items(),
+                ..Default::default()
+            }
+            .into(),
             MenuItem::Separator,
             SubMenu::<Self> {
                 label: "Модель".to_string(),
@@ -0,0 +1,497 @@
+//! Upload of finished transcripts and recordings to a WebDAV folder
+//! (Nextcloud, ownCloud or any other WebDAV server).
+//!
+//! Files are not uploaded directly: each session adds them to a persistent
+//! queue, and the queue is then processed in the background. Items that
+//! fail (server down, no network) stay queued and are retried with backoff
+//! after later sessions and on startup. Transcripts are spooled to an
+//! outbox directory until uploaded; recordings are uploaded from where
+//! they are saved.
+//!
+//! The password is kept in the desktop keyring through `secret-tool`
+//! (libsecret), never in the config file.
+
+use crate::app::config::{webdav_outbox_dir, webdav_queue_path, Config};
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Keyring `service` attribute for the stored password.
+const KEYRING_SERVICE: &str = "voice-dictation-webdav";
+
+/// Items are dropped after this many failed attempts.
+const MAX_ATTEMPTS: u32 = 12;
+
+/// First retry delay; doubles with every failure.
+const RETRY_BASE_SECS: i64 = 60;
+
+/// Longest delay between retries.
+const RETRY_MAX_SECS: i64 = 6 * 60 * 60;
+
+/// Recording modes that can upload, as stored in `webdav_modes`.
+pub const WEBDAV_MODES: [&str; 3] = ["dictation", "continuous", "conference"];
+
+/// Serializes queue processing between concurrent sessions.
+static QUEUE_LOCK: Mutex<()> = Mutex::new(());
+
+/// Connection settings taken from the config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebDavSettings {
+    /// Folder URL, e.g. `https://cloud.example.com/remote.php/dav/files/me/Meetings`.
+    pub url: String,
+    pub username: String,
+}
+
+impl WebDavSettings {
+    /// Settings if a server is configured.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        let url = config.webdav_url.as_deref()?.trim();
+        if url.is_empty() {
+            return None;
+        }
+        Some(Self {
+            url: url.to_string(),
+            username: config.webdav_username.clone().unwrap_or_default(),
+        })
+    }
+}
+
+/// What to upload after a session in `mode`, if anything.
+pub fn upload_plan(config: &Config, mode: &str) -> Option<(WebDavSettings, bool, bool)> {
+    let settings = WebDavSettings::from_config(config)?;
+    if !config.webdav_modes.iter().any(|m| m == mode) {
+        return None;
+    }
+    let (transcripts, recordings) = (config.webdav_upload_transcripts, config.webdav_upload_recordings);
+    (transcripts || recordings).then_some((settings, transcripts, recordings))
+}
+
+/// One file waiting to be uploaded.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct UploadItem {
+    pub local_path: PathBuf,
+    /// File name in the remote folder.
+    pub remote_name: String,
+    /// Delete the local file once uploaded (spooled transcripts).
+    #[serde(default)]
+    pub remove_after: bool,
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// Earliest time of the next attempt.
+    #[serde(default)]
+    pub next_attempt: Option<DateTime<Utc>>,
+}
+
+impl UploadItem {
+    pub fn new(local_path: PathBuf, remote_name: String, remove_after: bool) -> Self {
+        Self {
+            local_path,
+            remote_name,
+            remove_after,
+            attempts: 0,
+            last_error: None,
+            next_attempt: None,
+        }
+    }
+
+    fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_attempt.is_none_or(|t| t <= now)
+    }
+}
+
+/// Persistent upload queue.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct UploadQueue {
+    pub items: Vec<UploadItem>,
+}
+
+impl UploadQueue {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content =
+            fs::read_to_string(path).with_context(|| format!("Не вдалося прочитати чергу: {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("Не вдалося розпарсити чергу: {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Не вдалося створити директорію: {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self).context("Не вдалося серіалізувати чергу")?;
+        fs::write(path, content).with_context(|| format!("Не вдалося записати чергу: {}", path.display()))
+    }
+
+    /// Record a failed attempt and schedule the retry.
+    ///
+    /// Returns `false` if the item has run out of attempts and should be dropped.
+    fn record_failure(item: &mut UploadItem, error: String, now: DateTime<Utc>) -> bool {
+        item.attempts += 1;
+        item.last_error = Some(error);
+        item.next_attempt = Some(now + retry_delay(item.attempts));
+        item.attempts < MAX_ATTEMPTS
+    }
+}
+
+/// Delay before the next attempt after `attempts` failures.
+pub fn retry_delay(attempts: u32) -> Duration {
+    let exponent = attempts.saturating_sub(1).min(16);
+    Duration::seconds((RETRY_BASE_SECS << exponent).min(RETRY_MAX_SECS))
+}
+
+/// URL of `remote_name` inside the folder at `base_url`.
+pub fn remote_url(base_url: &str, remote_name: &str) -> String {
+    format!(
+        "{}/{}",
+        base_url.trim_end_matches('/'),
+        encode_path_segment(remote_name)
+    )
+}
+
+/// Percent-encode a single path segment (RFC 3986 unreserved characters kept).
+fn encode_path_segment(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    encoded
+}
+
+/// Remote name for a session transcript: next to its recording if there is one.
+pub fn transcript_name(recording: Option<&Path>, mode: &str, finished: DateTime<Utc>) -> String {
+    match recording.and_then(|p| p.file_stem()) {
+        Some(stem) => format!("{}.txt", stem.to_string_lossy()),
+        None => format!("{}_{}.txt", mode, finished.format("%Y-%m-%d_%H-%M-%S")),
+    }
+}
+
+/// Queue a finished session's transcript and/or recording for upload.
+pub fn enqueue_session(text: Option<&str>, recording: Option<&Path>, mode: &str) -> Result<()> {
+    let mut items = Vec::new();
+
+    if let Some(text) = text.filter(|t| !t.trim().is_empty()) {
+        let name = transcript_name(recording, mode, Utc::now());
+        let outbox = webdav_outbox_dir();
+        fs::create_dir_all(&outbox).with_context(|| format!("Не вдалося створити директорію: {}", outbox.display()))?;
+        let path = outbox.join(&name);
+        fs::write(&path, text).with_context(|| format!("Не вдалося записати файл: {}", path.display()))?;
+        items.push(UploadItem::new(path, name, true));
+    }
+
+    if let Some(recording) = recording {
+        let name = recording
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .context("Запис без імені файлу")?;
+        items.push(UploadItem::new(recording.to_path_buf(), name, false));
+    }
+
+    if items.is_empty() {
+        return Ok(());
+    }
+
+    let _guard = QUEUE_LOCK.lock();
+    let path = webdav_queue_path();
+    let mut queue = UploadQueue::load(&path)?;
+    queue.items.extend(items);
+    queue.save(&path)
+}
+
+/// Summary of one queue run.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct QueueReport {
+    pub uploaded: usize,
+    pub failed: usize,
+    pub dropped: usize,
+    pub pending: usize,
+}
+
+/// Upload all due items. Blocking; run it off the GTK thread.
+pub fn process_queue(settings: &WebDavSettings) -> Result<QueueReport> {
+    let _guard = QUEUE_LOCK.lock();
+    let path = webdav_queue_path();
+    let mut queue = UploadQueue::load(&path)?;
+    let mut report = QueueReport::default();
+    if queue.items.is_empty() {
+        return Ok(report);
+    }
+
+    let password = lookup_password(settings)?.unwrap_or_default();
+    let client = WebDavClient::new(settings, password);
+    let rt = tokio::runtime::Runtime::new().context("Не вдалося створити tokio runtime")?;
+    let now = Utc::now();
+
+    let mut folder_ready = false;
+    let mut remaining = Vec::with_capacity(queue.items.len());
+    for mut item in std::mem::take(&mut queue.items) {
+        if !item.is_due(now) {
+            remaining.push(item);
+            continue;
+        }
+        if !item.local_path.exists() {
+            eprintln!("WebDAV: файл зник, пропускаю: {}", item.local_path.display());
+            report.dropped += 1;
+            continue;
+        }
+
+        let result = rt.block_on(async {
+            if !folder_ready {
+                client.ensure_folder().await?;
+                folder_ready = true;
+            }
+            client.upload(&item.local_path, &item.remote_name).await
+        });
+
+        match result {
+            Ok(()) => {
+                report.uploaded += 1;
+                if item.remove_after {
+                    let _ = fs::remove_file(&item.local_path);
+                }
+            }
+            Err(e) => {
+                eprintln!("WebDAV: не вдалося завантажити {}: {:#}", item.remote_name, e);
+                if UploadQueue::record_failure(&mut item, format!("{:#}", e), now) {
+                    report.failed += 1;
+                    remaining.push(item);
+                } else {
+                    report.dropped += 1;
+                }
+            }
+        }
+    }
+
+    report.pending = remaining.len();
+    queue.items = remaining;
+    queue.save(&path)?;
+    Ok(report)
+}
+
+/// Queue a session's files (if any) and process the queue on a background thread.
+pub fn spawn_upload(settings: WebDavSettings, text: Option<String>, recording: Option<PathBuf>, mode: String) {
+    std::thread::spawn(move || {
+        if let Err(e) = enqueue_session(text.as_deref(), recording.as_deref(), &mode) {
+            eprintln!("WebDAV: не вдалося додати в чергу: {:#}", e);
+        }
+        match process_queue(&settings) {
+            Ok(report) if report.uploaded + report.failed + report.dropped > 0 => eprintln!(
+                "WebDAV: завантажено {}, помилок {}, відкинуто {}, у черзі {}",
+                report.uploaded, report.failed, report.dropped, report.pending
+            ),
+            Ok(_) => {}
+            Err(e) => eprintln!("WebDAV: помилка обробки черги: {:#}", e),
+        }
+    });
+}
+
+/// Minimal WebDAV client: create the folder, PUT files.
+struct WebDavClient {
+    client: reqwest::Client,
+    base_url: String,
+    username: String,
+    password: String,
+}
+
+impl WebDavClient {
+    fn new(settings: &WebDavSettings, password: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            base_url: settings.url.trim_end_matches('/').to_string(),
+            username: settings.username.clone(),
+            password,
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        let request = self.client.request(method, url);
+        if self.username.is_empty() {
+            request
+        } else {
+            request.basic_auth(&self.username, Some(&self.password))
+        }
+    }
+
+    /// Create the target folder; an existing folder (405) is fine.
+    async fn ensure_folder(&self) -> Result<()> {
+        let mkcol = reqwest::Method::from_bytes(b"MKCOL").expect("valid method");
+        let url = format!("{}/", self.base_url);
+        let response = self
+            .request(mkcol, &url)
+            .send()
+            .await
+            .with_context(|| format!("Не вдалося підключитися: {}", self.base_url))?;
+        let status = response.status();
+        if status.is_success() || status == reqwest::StatusCode::METHOD_NOT_ALLOWED {
+            return Ok(());
+        }
+        bail!("Не вдалося створити папку на сервері: HTTP {}", status)
+    }
+
+    async fn upload(&self, path: &Path, remote_name: &str) -> Result<()> {
+        let body = fs::read(path).with_context(|| format!("Не вдалося прочитати файл: {}", path.display()))?;
+        let url = remote_url(&self.base_url, remote_name);
+        let response = self
+            .request(reqwest::Method::PUT, &url)
+            .body(body)
+            .send()
+            .await
+            .with_context(|| format!("Не вдалося підключитися: {}", self.base_url))?;
+        let status = response.status();
+        if !status.is_success() {
+            bail!("HTTP {}", status);
+        }
+        Ok(())
+    }
+}
+
+/// Store the WebDAV password in the desktop keyring.
+pub fn store_password(settings: &WebDavSettings, password: &str) -> Result<()> {
+    let mut child = Command::new("secret-tool")
+        .args(["store", "--label", "Voice Dictation WebDAV"])
+        .args(keyring_attributes(settings))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Не вдалося виконати secret-tool. Встановіть libsecret (sudo dnf install libsecret)")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(password.as_bytes())
+            .context("Не вдалося передати пароль у secret-tool")?;
+    }
+    let output = child.wait_with_output().context("secret-tool не завершився")?;
+    if !output.status.success() {
+        bail!(
+            "secret-tool помилка: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Look up the WebDAV password; `None` if none is stored.
+pub fn lookup_password(settings: &WebDavSettings) -> Result<Option<String>> {
+    let output = Command::new("secret-tool")
+        .arg("lookup")
+        .args(keyring_attributes(settings))
+        .output()
+        .context("Не вдалося виконати secret-tool. Встановіть libsecret (sudo dnf install libsecret)")?;
+    // secret-tool exits with 1 and no output when nothing matches
+    if !output.status.success() || output.stdout.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(
+        String::from_utf8_lossy(&output.stdout)
+            .trim_end_matches('\n')
+            .to_string(),
+    ))
+}
+
+fn keyring_attributes(settings: &WebDavSettings) -> [String; 6] {
+    [
+        "service".to_string(),
+        KEYRING_SERVICE.to_string(),
+        "url".to_string(),
+        settings.url.clone(),
+        "user".to_string(),
+        settings.username.clone(),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn item() -> UploadItem {
+        UploadItem::new(PathBuf::from("/tmp/a.txt"), "a.txt".to_string(), true)
+    }
+
+    #[test]
+    fn test_retry_delay_doubles_and_caps() {
+        assert_eq!(retry_delay(1), Duration::seconds(60));
+        assert_eq!(retry_delay(2), Duration::seconds(120));
+        assert_eq!(retry_delay(4), Duration::seconds(480));
+        assert_eq!(retry_delay(30), Duration::seconds(RETRY_MAX_SECS));
+    }
+
+    #[test]
+    fn test_record_failure_schedules_retry_and_drops_after_max() {
+        let now = Utc.with_ymd_and_hms(2025, 3, 15, 12, 0, 0).unwrap();
+        let mut item = item();
+        assert!(item.is_due(now));
+
+        assert!(UploadQueue::record_failure(&mut item, "HTTP 503".to_string(), now));
+        assert_eq!(item.attempts, 1);
+        assert!(!item.is_due(now));
+        assert!(item.is_due(now + Duration::seconds(60)));
+
+        item.attempts = MAX_ATTEMPTS - 1;
+        assert!(!UploadQueue::record_failure(&mut item, "HTTP 503".to_string(), now));
+    }
+
+    #[test]
+    fn test_remote_url_encodes_name() {
+        assert_eq!(
+            remote_url("https://cloud.example.com/dav/Зустрічі/", "conference 1.txt"),
+            "https://cloud.example.com/dav/Зустрічі/conference%201.txt"
+        );
+        assert_eq!(
+            remote_url("https://h/d", "нотатки.txt"),
+            "https://h/d/%D0%BD%D0%BE%D1%82%D0%B0%D1%82%D0%BA%D0%B8.txt"
+        );
+    }
+
+    #[test]
+    fn test_transcript_name() {
+        let finished = Utc.with_ymd_and_hms(2025, 3, 15, 10, 30, 0).unwrap();
+        assert_eq!(
+            transcript_name(
+                Some(Path::new("/rec/conference_2025-03-15_10-00-00.wav")),
+                "conference",
+                finished
+            ),
+            "conference_2025-03-15_10-00-00.txt"
+        );
+        assert_eq!(
+            transcript_name(None, "dictation", finished),
+            "dictation_2025-03-15_10-30-00.txt"
+        );
+    }
+
+    #[test]
+    fn test_queue_save_load_roundtrip() {
+        let path = std::env::temp_dir().join(format!("s2t_webdav_queue_{}.json", uuid::Uuid::new_v4()));
+        let queue = UploadQueue { items: vec![item()] };
+        queue.save(&path).unwrap();
+        assert_eq!(UploadQueue::load(&path).unwrap(), queue);
+        let _ = fs::remove_file(&path);
+        assert!(UploadQueue::load(&path).unwrap().items.is_empty());
+    }
+
+    #[test]
+    fn test_upload_plan_respects_modes() {
+        let mut config = Config::default();
+        assert!(upload_plan(&config, "conference").is_none());
+
+        config.webdav_url = Some("https://cloud.example.com/dav".to_string());
+        let (settings, transcripts, recordings) = upload_plan(&config, "conference").unwrap();
+        assert_eq!(settings.url, "https://cloud.example.com/dav");
+        assert!(transcripts);
+        assert!(!recordings);
+        assert!(upload_plan(&config, "dictation").is_none());
+    }
+}
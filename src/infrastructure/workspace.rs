@@ -0,0 +1,149 @@
+//! Detection of the active workspace for workspace presets.
+//!
+//! A preset names a workspace; it matches when the name equals one of the
+//! names of where the user is now: the KDE activity, the virtual desktop's
+//! name, or the desktop's number counted from 1. The activity is read from
+//! the KDE activity manager over D-Bus (`gdbus`, shipped with GLib); the
+//! desktop from the EWMH root window properties (`xprop`), which GNOME and
+//! KDE also keep for X11 clients under Wayland.
+
+use std::process::Command;
+
+/// Names of the current activity and virtual desktop.
+///
+/// Empty when neither could be read, e.g. without `gdbus` and `xprop`.
+pub fn current_workspace_names() -> Vec<String> {
+    let mut names = Vec::new();
+    if let Some(activity) = kde_activity_name() {
+        names.push(activity);
+    }
+    let desktop = Command::new("xprop")
+        .args(["-root", "_NET_CURRENT_DESKTOP", "_NET_DESKTOP_NAMES"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).into_owned());
+    if let Some(listing) = desktop {
+        names.extend(desktop_names(&listing));
+    }
+    names
+}
+
+/// Whether `workspace`, as written in a preset, is one of `names`.
+pub fn matches_workspace(workspace: &str, names: &[String]) -> bool {
+    let workspace = workspace.trim().to_lowercase();
+    !workspace.is_empty() && names.iter().any(|name| name.trim().to_lowercase() == workspace)
+}
+
+fn kde_activity_name() -> Option<String> {
+    let id = gdbus_activities_call("CurrentActivity", &[])?;
+    gdbus_activities_call("ActivityName", &[&id])
+}
+
+/// Call a method of the KDE activity manager that returns one string.
+fn gdbus_activities_call(method: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new("gdbus")
+        .args([
+            "call",
+            "--session",
+            "--dest",
+            "org.kde.ActivityManager",
+            "--object-path",
+            "/ActivityManager/Activities",
+            "--method",
+        ])
+        .arg(format!("org.kde.ActivityManager.Activities.{}", method))
+        .args(args)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    parse_gdbus_string(&String::from_utf8_lossy(&output.stdout)).filter(|value| !value.is_empty())
+}
+
+/// Single string of a `gdbus call` reply: `('Work',)`.
+fn parse_gdbus_string(reply: &str) -> Option<String> {
+    let inner = reply.trim().strip_prefix('(')?.strip_suffix(",)")?;
+    quoted_strings(inner).into_iter().next()
+}
+
+/// Desktop name and 1-based number from `xprop -root` output.
+fn desktop_names(listing: &str) -> Vec<String> {
+    let mut current: Option<usize> = None;
+    let mut all_names = Vec::new();
+    for line in listing.lines() {
+        if let Some(value) = line.strip_prefix("_NET_CURRENT_DESKTOP(CARDINAL) = ") {
+            current = value.trim().parse().ok();
+        } else if let Some(value) = line.strip_prefix("_NET_DESKTOP_NAMES(UTF8_STRING) = ") {
+            all_names = quoted_strings(value);
+        }
+    }
+
+    let Some(current) = current else {
+        return Vec::new();
+    };
+    let mut names = Vec::new();
+    if let Some(name) = all_names.get(current).filter(|name| !name.is_empty()) {
+        names.push(name.clone());
+    }
+    names.push((current + 1).to_string());
+    names
+}
+
+/// Contents of the quoted strings in `text`, single- or double-quoted,
+/// with backslash escapes resolved.
+fn quoted_strings(text: &str) -> Vec<String> {
+    let mut strings = Vec::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '"' && c != '\'' {
+            continue;
+        }
+        let quote = c;
+        let mut value = String::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => value.extend(chars.next()),
+                c if c == quote => break,
+                c => value.push(c),
+            }
+        }
+        strings.push(value);
+    }
+    strings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_gdbus_string() {
+        assert_eq!(parse_gdbus_string("('Робота',)\n"), Some("Робота".to_string()));
+        assert_eq!(parse_gdbus_string("(\"Bob's\",)\n"), Some("Bob's".to_string()));
+        assert_eq!(parse_gdbus_string("()\n"), None);
+    }
+
+    #[test]
+    fn test_desktop_names_from_xprop() {
+        let listing =
+            "_NET_CURRENT_DESKTOP(CARDINAL) = 1\n_NET_DESKTOP_NAMES(UTF8_STRING) = \"Main\", \"Work, \\\"calls\\\"\"\n";
+        assert_eq!(
+            desktop_names(listing),
+            vec!["Work, \"calls\"".to_string(), "2".to_string()]
+        );
+
+        let unnamed = "_NET_CURRENT_DESKTOP(CARDINAL) = 0\n_NET_DESKTOP_NAMES:  not found.\n";
+        assert_eq!(desktop_names(unnamed), vec!["1".to_string()]);
+        assert!(desktop_names("").is_empty());
+    }
+
+    #[test]
+    fn test_matches_workspace_ignores_case_and_spaces() {
+        let names = vec!["Робота".to_string(), "Work".to_string(), "2".to_string()];
+        assert!(matches_workspace(" work ", &names));
+        assert!(matches_workspace("РОБОТА", &names));
+        assert!(matches_workspace("2", &names));
+        assert!(!matches_workspace("Home", &names));
+        assert!(!matches_workspace("", &["".to_string()]));
+    }
+}
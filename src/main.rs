@@ -21,9 +21,22 @@ fn main() -> Result<()> {
 
     match cli.command {
         Some(cli::Commands::Transcribe(args)) => cli::transcribe::run(args),
-        Some(cli::Commands::Models) => cli::transcribe::list_models(),
+        Some(cli::Commands::Record(args)) => cli::record::run(args),
+        Some(cli::Commands::Diarize(args)) => cli::diarize::run(args),
+        Some(cli::Commands::Models(args)) => cli::models::run(args),
         Some(cli::Commands::DenoiseEval(args)) => cli::denoise_eval::run(args),
-        None => run_gui(),
+        Some(cli::Commands::VadEval(args)) => cli::vad_eval::run(args),
+        Some(cli::Commands::Bench(args)) => cli::bench::run(args),
+        Some(cli::Commands::Benchmark(args)) => cli::benchmark::run(args),
+        Some(cli::Commands::Script(args)) => cli::script::run(args),
+        Some(cli::Commands::History(args)) => cli::history::run(args),
+        Some(cli::Commands::Stats(args)) => cli::stats::run(args),
+        Some(cli::Commands::AlignSrt(args)) => cli::align_srt::run(args),
+        Some(cli::Commands::Doctor) => cli::doctor::run(),
+        None => run_gui(ui::launch::LaunchRequest {
+            mode: cli.mode.map(|mode| mode.config_name().to_string()),
+            start: cli.start,
+        }),
     }
 }
 
@@ -43,8 +56,14 @@ fn init_transcription_service(
         let tdt_path = tdt_dir.to_string_lossy().to_string();
         println!("Завантаження TDT моделі: {}", tdt_path);
         match TranscriptionService::with_tdt(&tdt_path) {
-            Ok(service) => {
+            Ok(mut service) => {
                 println!("TDT модель завантажено!");
+                let punctuation = config.lock().punctuation_restoration.clone();
+                if let Some(processor) = transcription::postprocess::create_post_processor(&punctuation) {
+                    println!("Відновлення пунктуації: {}", processor.name());
+                    service.add_post_processor(processor);
+                }
+                service.set_verbatim(config.lock().verbatim_transcription);
                 return service;
             }
             Err(e) => {
@@ -55,7 +74,9 @@ fn init_transcription_service(
     }
 
     // Fallback to Whisper
-    load_whisper_model(config)
+    let mut service = load_whisper_model(config);
+    service.set_verbatim(config.lock().verbatim_transcription);
+    service
 }
 
 /// Load Whisper model from config or fallback locations.
@@ -121,10 +142,18 @@ fn load_diarization_model(
     config: &std::sync::Arc<parking_lot::Mutex<app::config::Config>>,
     diarization: &std::sync::Arc<parking_lot::Mutex<transcription::diarization::DiarizationEngine>>,
 ) {
-    use app::config::sortformer_models_dir;
+    use app::config::{sortformer_models_dir, speakers_path};
     use std::path::PathBuf;
 
     let cfg = config.lock();
+    let settings = transcription::diarization::DiarizationSettings {
+        max_speakers: cfg.diarization_max_speakers,
+        onset: cfg.diarization_onset,
+        offset: cfg.diarization_offset,
+    };
+    // Kiosk mode keeps no voices of the people using it
+    let reidentification =
+        (cfg.speaker_reidentification && !cfg.read_only_history).then_some(cfg.speaker_match_threshold);
     let model_path = if let Some(ref path) = cfg.sortformer_model_path {
         Some(PathBuf::from(path))
     } else {
@@ -139,7 +168,11 @@ fn load_diarization_model(
 
     if let Some(path) = model_path {
         let mut engine = diarization.lock();
-        *engine = transcription::diarization::DiarizationEngine::new(Some(path));
+        let mut new_engine = transcription::diarization::DiarizationEngine::new(Some(path)).with_settings(settings);
+        if let Some(threshold) = reidentification {
+            new_engine = new_engine.with_reidentification(speakers_path(), threshold);
+        }
+        *engine = new_engine;
         if let Err(e) = engine.load_model() {
             eprintln!("Не вдалося завантажити модель Sortformer: {}", e);
             eprintln!("Diarization буде використовувати channel-based метод.");
@@ -147,21 +180,39 @@ fn load_diarization_model(
     }
 }
 
-fn run_gui() -> Result<()> {
+fn run_gui(launch: ui::launch::LaunchRequest) -> Result<()> {
     use app::config::{load_config, Config};
     use app::context::AppContext;
     use global_hotkey::{GlobalHotKeyEvent, HotKeyState};
     use gtk4::{glib, prelude::*, Application};
-    use history::{load_history, save_history, History};
+    use history::{load_history, save_history, History, HistoryBackend};
     use infrastructure::hotkeys::HotkeyManager;
     use infrastructure::tray::{DictationTray, TrayAction};
     use parking_lot::Mutex;
     use std::sync::Arc;
     use transcription::diarization::DiarizationEngine;
     use transcription::TranscriptionService;
+    use ui::launch::LAUNCH_ACTION;
 
     gtk4::init()?;
 
+    // A second launch only forwards its request to the running instance
+    let app = Application::builder().application_id(APP_ID).build();
+    if let Err(e) = app.register(gtk4::gio::Cancellable::NONE) {
+        eprintln!("Помилка реєстрації додатку: {}", e);
+    }
+    if app.is_remote() {
+        if launch.is_empty() {
+            app.activate();
+        } else {
+            app.activate_action(LAUNCH_ACTION, Some(&launch.to_variant()));
+        }
+        if let Some(connection) = app.dbus_connection() {
+            let _ = connection.flush_sync(gtk4::gio::Cancellable::NONE);
+        }
+        return Ok(());
+    }
+
     let config = load_config().unwrap_or_else(|e| {
         eprintln!(
             "Помилка завантаження конфігу: {}. Використовую значення за замовчуванням.",
@@ -176,15 +227,32 @@ fn run_gui() -> Result<()> {
         eprintln!("Помилка створення директорії записів: {}", e);
     }
 
+    // Audio spilled to disk by a session that did not stop cleanly
+    let stale = recording::spool::remove_stale();
+    if stale > 0 {
+        eprintln!("Видалено {} залишених файлів тимчасового запису", stale);
+    }
+
+    // Retry uploads left over from earlier sessions
+    if let Some(settings) = infrastructure::webdav::WebDavSettings::from_config(&config.lock()) {
+        if app::config::webdav_queue_path().exists() {
+            infrastructure::webdav::spawn_upload(settings, None, None, String::new());
+        }
+    }
+
     // Load and cleanup history
     let history = {
-        let mut h = load_history().unwrap_or_else(|e| {
+        let backend = HistoryBackend::parse(&config.lock().history_backend);
+        let mut h = load_history(backend).unwrap_or_else(|e| {
             eprintln!("Помилка завантаження історії: {}. Створюю нову.", e);
-            History::default()
+            History::with_backend(backend)
         });
         let cfg = config.lock();
+        h.read_only = cfg.read_only_history;
+        h.backups = cfg.history_backups;
         h.cleanup_old_entries(cfg.history_max_age_days);
         h.trim_to_limit(cfg.history_max_entries);
+        h.cleanup_trash(cfg.history_trash_days);
         drop(cfg);
         if let Err(e) = save_history(&h) {
             eprintln!("Помилка збереження історії: {}", e);
@@ -202,6 +270,32 @@ fn run_gui() -> Result<()> {
             .expect("Failed to create AppContext"),
     );
 
+    // Phone remote control over the local network
+    let remote_port = {
+        let cfg = config.lock();
+        cfg.remote_control_enabled.then_some(cfg.remote_control_port)
+    };
+    if let Some(port) = remote_port {
+        if let Err(e) =
+            infrastructure::remote::spawn_server(port, ctx.remote.clone(), ctx.channels.remote_command_tx().clone())
+        {
+            eprintln!("Помилка запуску віддаленого керування: {}", e);
+        }
+    }
+
+    // Pre-roll: the microphone stays open so recordings catch the words before the hotkey
+    let pre_record_secs = config.lock().pre_record_secs;
+    if pre_record_secs > 0.0 {
+        if let Err(e) = ctx.audio.start_mic_pre_roll(pre_record_secs) {
+            eprintln!("Не вдалося запустити попередній запис: {}", e);
+        }
+    }
+
+    // CLI commands use the loaded model and history through a local socket
+    if let Err(e) = infrastructure::ipc::spawn_server(ctx.transcription.clone(), ctx.history.clone()) {
+        eprintln!("Помилка запуску локального сокета: {}", e);
+    }
+
     // Load models in background thread
     {
         let config_for_loading = config.clone();
@@ -225,10 +319,12 @@ fn run_gui() -> Result<()> {
     // Spawn tray in background thread with its own tokio runtime (ksni 0.3 is async)
     let config_for_tray = config.clone();
     let transcription_for_tray = ctx.transcription.clone();
+    let history_for_tray = history.clone();
     std::thread::spawn(move || {
         let rt = tokio::runtime::Runtime::new().expect("Failed to create tokio runtime for tray");
         rt.block_on(async {
-            match DictationTray::spawn_service(tray_tx, config_for_tray, transcription_for_tray).await {
+            match DictationTray::spawn_service(tray_tx, config_for_tray, transcription_for_tray, history_for_tray).await
+            {
                 Ok(_handle) => {
                     // Keep running until app exits
                     std::future::pending::<()>().await;
@@ -238,8 +334,6 @@ fn run_gui() -> Result<()> {
         });
     });
 
-    let app = Application::builder().application_id(APP_ID).build();
-
     // Set up clean shutdown on SIGINT/SIGTERM
     let (shutdown_tx, shutdown_rx) = async_channel::bounded::<()>(1);
     let history_for_signal = history.clone();
@@ -254,10 +348,13 @@ fn run_gui() -> Result<()> {
     .expect("Failed to set signal handler");
 
     // Initialize hotkey manager
-    let hotkey_manager = Arc::new(Mutex::new(HotkeyManager::new().unwrap_or_else(|e| {
-        eprintln!("Помилка ініціалізації гарячих клавіш: {}", e);
-        std::process::exit(1);
-    })));
+    let toggle_recording_tx_for_grab = ctx.channels.toggle_recording_tx().clone();
+    let hotkey_manager = Arc::new(Mutex::new(
+        HotkeyManager::new(toggle_recording_tx_for_grab).unwrap_or_else(|e| {
+            eprintln!("Помилка ініціалізації гарячих клавіш: {}", e);
+            std::process::exit(1);
+        }),
+    ));
 
     // Register hotkeys from config
     {
@@ -295,8 +392,12 @@ fn run_gui() -> Result<()> {
 
     // Pass AppContext to UI
     let ctx_for_app = ctx.clone();
+    let pending_launch = std::cell::RefCell::new((!launch.is_empty()).then_some(launch));
     app.connect_activate(move |app| {
         ui::build_ui(app, ctx_for_app.clone());
+        if let Some(request) = pending_launch.borrow_mut().take() {
+            app.activate_action(LAUNCH_ACTION, Some(&request.to_variant()));
+        }
     });
 
     // Listen for signal-triggered shutdown and quit the GTK application
@@ -321,6 +422,7 @@ fn run_gui() -> Result<()> {
 
     // Use channels from ctx for tray action handling
     let channels_for_tray = ctx.channels.clone();
+    let history_for_tray_actions = history.clone();
     let app_weak = app.downgrade();
     glib::spawn_future_local(async move {
         while let Ok(action) = tray_rx.recv().await {
@@ -334,6 +436,10 @@ fn run_gui() -> Result<()> {
                         }
                     }
                 }
+                // Same path as the hotkey, so a press during model loading is queued
+                TrayAction::ToggleRecording => {
+                    let _ = channels_for_tray.toggle_recording_tx().try_send(());
+                }
                 TrayAction::ManageModels => {
                     if let Some(app) = app_weak.upgrade() {
                         if let Some(window) = app.active_window() {
@@ -364,6 +470,17 @@ fn run_gui() -> Result<()> {
                         let _ = channels_for_tray.open_settings_tx().try_send(());
                     }
                 }
+                TrayAction::CopyPinned(id) => {
+                    let text = history_for_tray_actions
+                        .lock()
+                        .entries
+                        .iter()
+                        .find(|e| e.id == id)
+                        .map(|e| e.text.clone());
+                    if let Some(text) = text {
+                        ui::copy_to_clipboard(&text);
+                    }
+                }
                 TrayAction::Quit => {
                     if let Some(app) = app_weak.upgrade() {
                         app.quit();
@@ -383,7 +500,8 @@ fn run_gui() -> Result<()> {
         }
     });
 
-    app.run();
+    // Arguments were already parsed by clap; GApplication would reject --mode/--start
+    app.run_with_args::<&str>(&[]);
 
     Ok(())
 }
@@ -1,28 +1,48 @@
-use crate::domain::types::ConferenceRecording;
+use crate::domain::types::{CaptureDeviceStatus, ConferenceRecording, LevelReading};
 use crate::recording::loopback::LoopbackRecorder;
-use crate::recording::microphone::AudioRecorder;
+use crate::recording::microphone::{AudioRecorder, CaptureProcessing};
+use crate::recording::spool::{SampleSpool, SpooledRecording, KEEP_IN_MEMORY};
 use anyhow::{Context, Result};
 use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Instant;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a long recording is checked for audio to move to disk
+const SPILL_INTERVAL: Duration = Duration::from_secs(5);
 
 pub(crate) struct ConferenceRecorder {
     mic_recorder: Arc<AudioRecorder>,
     loopback_recorder: Arc<LoopbackRecorder>,
     start_time: Arc<Mutex<Option<Instant>>>,
+    /// Samples per channel kept in memory before spilling; 0 never spills
+    spill_after: usize,
+    /// Spilled audio of the current recording; locked while samples move
+    spools: Arc<Mutex<Option<SpooledRecording>>>,
+    spilling: Arc<AtomicBool>,
 }
 
 impl ConferenceRecorder {
-    pub fn new() -> Self {
+    /// `processing` applies to the microphone only; call audio is kept as is.
+    ///
+    /// Past `spool_after_minutes` (0: never), the audio moves to disk as
+    /// it is recorded.
+    pub fn new(processing: CaptureProcessing, spool_after_minutes: u32) -> Self {
         Self {
-            mic_recorder: Arc::new(AudioRecorder::new()),
+            mic_recorder: Arc::new(AudioRecorder::with_processing(processing)),
             loopback_recorder: Arc::new(LoopbackRecorder::new()),
             start_time: Arc::new(Mutex::new(None)),
+            spill_after: spool_after_minutes as usize * 60 * 16000,
+            spools: Arc::new(Mutex::new(None)),
+            spilling: Arc::new(AtomicBool::new(false)),
         }
     }
 
     /// Start recording from both microphone and loopback simultaneously
-    pub fn start_conference(&self) -> Result<()> {
+    ///
+    /// `loopback_source` names the monitor to capture; `None` takes the default one.
+    pub fn start_conference(&self, loopback_source: Option<&str>) -> Result<()> {
         let start = Instant::now();
         *self.start_time.lock() = Some(start);
 
@@ -31,35 +51,211 @@ impl ConferenceRecorder {
             .start_recording()
             .context("Не вдалося запустити запис мікрофона")?;
         self.loopback_recorder
-            .start_loopback()
+            .start_loopback(loopback_source)
             .context("Не вдалося запустити запис системного аудіо")?;
 
+        *self.spools.lock() = None;
+        if self.spill_after > 0 {
+            self.spawn_spiller();
+        }
+
         Ok(())
     }
 
-    /// Stop recording and return synchronized samples from both sources
-    pub fn stop_conference(&self) -> ConferenceRecording {
-        let (mic_samples, mic_completion) = self.mic_recorder.stop_recording();
-        let (loopback_samples, loopback_completion) = self.loopback_recorder.stop_loopback();
+    /// Move all but the last minute of each channel to disk once the
+    /// recording is longer than `spill_after`, every few seconds.
+    fn spawn_spiller(&self) {
+        self.spilling.store(true, Ordering::SeqCst);
+        let spilling = self.spilling.clone();
+        let spools = self.spools.clone();
+        let mic = self.mic_recorder.samples().clone();
+        let loopback = self.loopback_recorder.samples().clone();
+        let spill_after = self.spill_after;
+
+        thread::spawn(move || {
+            while spilling.load(Ordering::SeqCst) {
+                thread::sleep(SPILL_INTERVAL);
+                let mut guard = spools.lock();
+                // Checked under the lock: a stop takes the spools right after clearing the flag
+                if !spilling.load(Ordering::SeqCst) {
+                    break;
+                }
+                let spilled = guard.as_ref().map_or(0, SpooledRecording::len);
+                let recorded = spilled + mic.lock().len().max(loopback.lock().len());
+                if recorded < spill_after {
+                    continue;
+                }
+
+                if guard.is_none() {
+                    match SampleSpool::create().and_then(|mic| Ok((mic, SampleSpool::create()?))) {
+                        Ok((mic, loopback)) => *guard = Some(SpooledRecording { mic, loopback }),
+                        Err(e) => {
+                            eprintln!("Не вдалося перенести запис на диск, лишаю в пам'яті: {}", e);
+                            break;
+                        }
+                    }
+                }
+                let Some(spooled) = guard.as_mut() else {
+                    break;
+                };
+                let moved = spill(&mic, &mut spooled.mic).and_then(|_| spill(&loopback, &mut spooled.loopback));
+                if let Err(e) = moved {
+                    // E.g. a full disk: the recording goes on in memory
+                    eprintln!("Помилка перенесення запису на диск, лишаю в пам'яті: {}", e);
+                    unspill(&mic, &mut spooled.mic);
+                    unspill(&loopback, &mut spooled.loopback);
+                    *guard = None;
+                    break;
+                }
+            }
+        });
+    }
+
+    /// Stop recording and return synchronized samples from both sources.
+    ///
+    /// If part of the recording went to disk, the rest joins it there and
+    /// the spooled recording is returned as well, with the samples in the
+    /// `ConferenceRecording` left empty.
+    pub fn stop_conference(&self) -> (ConferenceRecording, Option<SpooledRecording>) {
+        self.spilling.store(false, Ordering::SeqCst);
+        let mut spooled = self.spools.lock().take();
+
+        let (mut mic_samples, mic_completion) = self.mic_recorder.stop_recording();
+        let (mut loopback_samples, loopback_completion) = self.loopback_recorder.stop_loopback();
 
         *self.start_time.lock() = None;
 
-        ConferenceRecording::new(mic_samples, loopback_samples, mic_completion, loopback_completion)
+        // The last minute, still in memory, joins the spool
+        if let Some(recording) = &mut spooled {
+            let appended = recording
+                .mic
+                .append(&mic_samples)
+                .and_then(|_| recording.loopback.append(&loopback_samples));
+            match appended {
+                Ok(()) => {
+                    mic_samples = Vec::new();
+                    loopback_samples = Vec::new();
+                }
+                Err(e) => {
+                    eprintln!("Помилка перенесення запису на диск, лишаю в пам'яті: {}", e);
+                    mic_samples = unspooled(&mut recording.mic, mic_samples);
+                    loopback_samples = unspooled(&mut recording.loopback, loopback_samples);
+                    spooled = None;
+                }
+            }
+        }
+
+        (
+            ConferenceRecording::new(mic_samples, loopback_samples, mic_completion, loopback_completion),
+            spooled,
+        )
+    }
+
+    /// Copy of both channels recorded so far, from sample `from` on.
+    pub fn samples_from(&self, from: usize) -> (Vec<f32>, Vec<f32>) {
+        let mut spools = self.spools.lock();
+        let copy_from = |samples: &Arc<Mutex<Vec<f32>>>, spool: Option<&mut SampleSpool>| {
+            let spilled = spool.as_ref().map_or(0, |spool| spool.len());
+            let mut copy = match spool {
+                Some(spool) if from < spilled => spool.read(from..spilled).unwrap_or_default(),
+                _ => Vec::new(),
+            };
+            let samples = samples.lock();
+            copy.extend_from_slice(samples.get(from.saturating_sub(spilled)..).unwrap_or_default());
+            copy
+        };
+        let (mic_spool, loopback_spool) = match spools.as_mut() {
+            Some(spooled) => (Some(&mut spooled.mic), Some(&mut spooled.loopback)),
+            None => (None, None),
+        };
+        (
+            copy_from(self.mic_recorder.samples(), mic_spool),
+            copy_from(self.loopback_recorder.samples(), loopback_spool),
+        )
+    }
+
+    /// Level of the microphone
+    pub fn get_mic_level(&self) -> LevelReading {
+        self.mic_recorder.level()
     }
 
-    /// Get amplitude from microphone
-    pub fn get_mic_amplitude(&self) -> f32 {
-        self.mic_recorder.get_amplitude()
+    /// Whether the microphone is still there, or which one replaced it
+    pub fn mic_device_status(&self) -> CaptureDeviceStatus {
+        self.mic_recorder.device_status()
     }
 
-    /// Get amplitude from loopback
-    pub fn get_loopback_amplitude(&self) -> f32 {
-        self.loopback_recorder.get_amplitude()
+    /// Level of the system audio
+    pub fn get_loopback_level(&self) -> LevelReading {
+        self.loopback_recorder.level()
     }
 }
 
 impl Default for ConferenceRecorder {
     fn default() -> Self {
-        Self::new()
+        Self::new(CaptureProcessing::default(), 0)
+    }
+}
+
+/// Move all but the last [`KEEP_IN_MEMORY`] samples to `spool`.
+///
+/// Samples that could not be written go back in front of the rest.
+fn spill(samples: &Mutex<Vec<f32>>, spool: &mut SampleSpool) -> Result<()> {
+    let moved: Vec<f32> = {
+        let mut samples = samples.lock();
+        let count = samples.len().saturating_sub(KEEP_IN_MEMORY);
+        samples.drain(..count).collect()
+    };
+    spool.append(&moved).inspect_err(|_| {
+        samples.lock().splice(0..0, moved);
+    })
+}
+
+/// Put what `spool` holds back in front of `samples`.
+fn unspill(samples: &Mutex<Vec<f32>>, spool: &mut SampleSpool) {
+    let spilled = unspooled(spool, Vec::new());
+    samples.lock().splice(0..0, spilled);
+}
+
+/// What `spool` holds followed by `tail`.
+fn unspooled(spool: &mut SampleSpool, tail: Vec<f32>) -> Vec<f32> {
+    let mut samples = spool.read(0..spool.len()).unwrap_or_else(|e| {
+        eprintln!("Не вдалося повернути запис із диска: {}", e);
+        Vec::new()
+    });
+    samples.extend(tail);
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spilled_samples_are_still_read_in_order() {
+        let recorder = ConferenceRecorder::new(CaptureProcessing::default(), 1);
+        let recorded: Vec<f32> = (0..KEEP_IN_MEMORY + 1000).map(|i| i as f32).collect();
+        recorder.mic_recorder.samples().lock().extend(&recorded);
+
+        let mut spooled = SpooledRecording {
+            mic: SampleSpool::create().unwrap(),
+            loopback: SampleSpool::create().unwrap(),
+        };
+        spill(recorder.mic_recorder.samples(), &mut spooled.mic).unwrap();
+        assert_eq!(spooled.mic.len(), 1000);
+        assert_eq!(recorder.mic_recorder.samples().lock().len(), KEEP_IN_MEMORY);
+        *recorder.spools.lock() = Some(spooled);
+
+        let (mic, loopback) = recorder.samples_from(990);
+        assert_eq!(mic, recorded[990..]);
+        assert!(loopback.is_empty());
+    }
+
+    #[test]
+    fn test_unspill_puts_audio_back_in_front() {
+        let samples = Mutex::new(vec![3.0, 4.0]);
+        let mut spool = SampleSpool::create().unwrap();
+        spool.append(&[1.0, 2.0]).unwrap();
+        unspill(&samples, &mut spool);
+        assert_eq!(*samples.lock(), vec![1.0, 2.0, 3.0, 4.0]);
     }
 }
@@ -1,20 +1,13 @@
 use async_channel::Receiver;
 use parking_lot::Mutex;
-use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-pub(crate) const WHISPER_SAMPLE_RATE: u32 = 16000;
+use super::meter::LevelMeter;
+use super::ring_buffer::RingBuffer;
+use crate::domain::types::LevelReading;
 
-/// Calculate normalized RMS amplitude for visualization (0.0 - 1.0).
-pub(crate) fn calculate_rms(samples: &[f32]) -> f32 {
-    if samples.is_empty() {
-        return 0.0;
-    }
-    let sum_squares: f32 = samples.iter().map(|s| s * s).sum();
-    let rms = (sum_squares / samples.len() as f32).sqrt();
-    // Normalize: typical speech is ~0.05-0.15 RMS, scale so it reaches ~50%
-    (rms * 6.0).min(1.0)
-}
+pub(crate) const WHISPER_SAMPLE_RATE: u32 = 16000;
 
 /// Shared recording infrastructure (samples buffer, flags, completion channel).
 ///
@@ -22,18 +15,18 @@ pub(crate) fn calculate_rms(samples: &[f32]) -> f32 {
 /// struct to avoid duplicating the identical field set and lifecycle methods.
 pub(crate) struct RecordingCore {
     pub(crate) samples: Arc<Mutex<Vec<f32>>>,
-    is_recording: Arc<AtomicBool>,
+    pub(crate) is_recording: Arc<AtomicBool>,
     completion_rx: Arc<Mutex<Option<Receiver<()>>>>,
-    /// Current audio amplitude (RMS), stored as u32 bits for atomic access
-    current_amplitude: Arc<AtomicU32>,
+    /// Input level while recording
+    pub(crate) meter: Arc<LevelMeter>,
 }
 
 /// Handles passed to a spawned recording thread so it can write samples,
-/// check the recording flag, update amplitude, and signal completion.
+/// check the recording flag, feed the level meter, and signal completion.
 pub(crate) struct RecordingHandles {
     pub(crate) samples: Arc<Mutex<Vec<f32>>>,
     pub(crate) is_recording: Arc<AtomicBool>,
-    pub(crate) current_amplitude: Arc<AtomicU32>,
+    pub(crate) meter: Arc<LevelMeter>,
     pub(crate) completion_tx: async_channel::Sender<()>,
 }
 
@@ -43,13 +36,13 @@ impl RecordingCore {
             samples: Arc::new(Mutex::new(Vec::new())),
             is_recording: Arc::new(AtomicBool::new(false)),
             completion_rx: Arc::new(Mutex::new(None)),
-            current_amplitude: Arc::new(AtomicU32::new(0)),
+            meter: Arc::new(LevelMeter::new()),
         }
     }
 
-    /// Get current audio amplitude (0.0 - 1.0 range, normalized RMS).
-    pub fn get_amplitude(&self) -> f32 {
-        f32::from_bits(self.current_amplitude.load(Ordering::Relaxed))
+    /// Current input level, silent when not recording.
+    pub fn level(&self) -> LevelReading {
+        self.meter.reading()
     }
 
     /// Check if currently recording.
@@ -62,25 +55,46 @@ impl RecordingCore {
     pub fn prepare_recording(&self) -> RecordingHandles {
         self.samples.lock().clear();
         self.is_recording.store(true, Ordering::SeqCst);
+        self.completion_handles()
+    }
+
+    /// Like `prepare_recording`, but the samples start with what `pre_roll`
+    /// holds.
+    ///
+    /// For a capture that is already running: it decides between the
+    /// pre-roll and the samples under the samples lock, which is held here
+    /// until recording is on, so no audio falls between the two.
+    pub fn prepare_recording_after(&self, pre_roll: &RingBuffer) -> RecordingHandles {
+        let mut samples = self.samples.lock();
+        samples.clear();
+        samples.extend(pre_roll.read_all());
+        self.is_recording.store(true, Ordering::SeqCst);
+        drop(samples);
+        self.completion_handles()
+    }
 
+    fn completion_handles(&self) -> RecordingHandles {
         let (completion_tx, completion_rx) = async_channel::bounded::<()>(1);
         *self.completion_rx.lock() = Some(completion_rx);
 
         RecordingHandles {
             samples: self.samples.clone(),
             is_recording: self.is_recording.clone(),
-            current_amplitude: self.current_amplitude.clone(),
+            meter: self.meter.clone(),
             completion_tx,
         }
     }
 
-    /// Clear recording flag, reset amplitude, and return collected samples
+    /// Clear recording flag, reset the level meter, and return collected samples
     /// plus the completion receiver.
+    ///
+    /// The samples are moved out rather than copied, so a long session is
+    /// never held in memory twice; the buffer is left empty.
     pub fn stop(&self) -> (Vec<f32>, Option<Receiver<()>>) {
         self.is_recording.store(false, Ordering::SeqCst);
-        self.current_amplitude.store(0.0_f32.to_bits(), Ordering::Relaxed);
+        self.meter.reset();
         let completion_rx = self.completion_rx.lock().take();
-        let samples = self.samples.lock().clone();
+        let samples = std::mem::take(&mut *self.samples.lock());
         (samples, completion_rx)
     }
 }
@@ -105,32 +119,31 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_whisper_sample_rate_constant() {
-        assert_eq!(WHISPER_SAMPLE_RATE, 16000);
-    }
+    fn test_prepare_recording_after_starts_with_pre_roll() {
+        let core = RecordingCore::new();
+        core.samples.lock().extend([9.0, 9.0]);
+        let pre_roll = RingBuffer::new(4);
+        pre_roll.write(&[1.0, 2.0, 3.0, 4.0, 5.0]);
 
-    #[test]
-    fn test_calculate_rms_empty() {
-        assert_eq!(calculate_rms(&[]), 0.0);
-    }
+        let _handles = core.prepare_recording_after(&pre_roll);
+        assert!(core.is_recording());
+        core.samples.lock().push(6.0);
 
-    #[test]
-    fn test_calculate_rms_silence() {
-        assert_eq!(calculate_rms(&[0.0; 100]), 0.0);
+        let (samples, _) = core.stop();
+        assert_eq!(samples, vec![2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert!(pre_roll.read_all().is_empty());
     }
 
     #[test]
-    fn test_calculate_rms_normalized() {
-        // Full-scale signal should clamp to 1.0
-        let loud = vec![1.0; 100];
-        assert_eq!(calculate_rms(&loud), 1.0);
+    fn test_whisper_sample_rate_constant() {
+        assert_eq!(WHISPER_SAMPLE_RATE, 16000);
     }
 
     #[test]
     fn test_recording_core_new() {
         let core = RecordingCore::new();
         assert!(!core.is_recording());
-        assert_eq!(core.get_amplitude(), 0.0);
+        assert_eq!(core.level(), LevelReading::SILENT);
         assert!(core.samples.lock().is_empty());
     }
 
@@ -151,13 +164,13 @@ mod tests {
     }
 
     #[test]
-    fn test_stop_resets_amplitude() {
+    fn test_stop_resets_level() {
         let core = RecordingCore::new();
-        core.current_amplitude.store(0.5_f32.to_bits(), Ordering::Relaxed);
-        assert!(core.get_amplitude() > 0.0);
+        core.meter.push(&[0.5; 1600]);
+        assert!(core.level().peak_dbfs > LevelReading::FLOOR_DBFS);
 
         core.stop();
-        assert_eq!(core.get_amplitude(), 0.0);
+        assert_eq!(core.level(), LevelReading::SILENT);
     }
 
     #[test]
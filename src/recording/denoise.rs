@@ -1,28 +1,111 @@
-//! Audio denoising using nnnoiseless (RNNoise).
+//! Audio denoising using nnnoiseless (RNNoise) or DeepFilterNet.
 //!
 //! This module provides noise suppression for cleaner speech recognition.
 //! nnnoiseless/RNNoise operates at 48kHz with 10ms frames (480 samples).
 //! The denoiser accepts 16kHz input and handles resampling internally.
+//! The DeepFilterNet backend lives in `deepfilter` and is chosen with the
+//! `denoise_backend` config value.
 
-#[cfg(test)]
+use super::deepfilter::DeepFilterDenoiser;
+use crate::app::config::Config;
 use crate::domain::traits::AudioDenoising;
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use nnnoiseless::DenoiseState;
 use parking_lot::Mutex;
 use rubato::{FftFixedIn, Resampler};
+use std::path::PathBuf;
 
 /// Input sample rate (Whisper pipeline rate)
-const INPUT_SAMPLE_RATE: usize = 16000;
+pub(super) const INPUT_SAMPLE_RATE: usize = 16000;
 
-/// Sample rate required by nnnoiseless (RNNoise)
-const NNNOISELESS_SAMPLE_RATE: usize = 48000;
+/// Sample rate required by nnnoiseless (RNNoise) and DeepFilterNet
+pub(super) const NNNOISELESS_SAMPLE_RATE: usize = 48000;
 
 /// Frame size in samples (10ms at 48kHz)
-const FRAME_SIZE: usize = 480;
+pub(super) const FRAME_SIZE: usize = 480;
 
 /// Chunk size for rubato resampler
 const RESAMPLE_CHUNK: usize = 1024;
 
+/// Denoiser implementations selectable in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DenoiseBackend {
+    Rnnoise,
+    DeepFilterNet,
+}
+
+impl DenoiseBackend {
+    /// Parse the `denoise_backend` config value; unknown names fall back to RNNoise.
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "deepfilternet" => Self::DeepFilterNet,
+            _ => Self::Rnnoise,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Rnnoise => "rnnoise",
+            Self::DeepFilterNet => "deepfilternet",
+        }
+    }
+}
+
+/// Which denoiser to run, resolved from the config.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DenoiseSettings {
+    pub backend: DenoiseBackend,
+    /// DeepFilterNet ONNX model
+    pub deepfilter_model_path: Option<PathBuf>,
+}
+
+impl DenoiseSettings {
+    /// Backend and model from the config, whether or not denoising is enabled.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            backend: DenoiseBackend::parse(&config.denoise_backend),
+            deepfilter_model_path: config.deepfilter_model_path.as_deref().map(PathBuf::from),
+        }
+    }
+
+    /// Settings when denoising is enabled in the config, else `None`.
+    pub fn if_enabled(config: &Config) -> Option<Self> {
+        config.denoise_enabled.then(|| Self::from_config(config))
+    }
+
+    /// Create a fresh denoiser of the configured backend.
+    pub fn create(&self) -> Result<Box<dyn AudioDenoising>> {
+        match self.backend {
+            DenoiseBackend::Rnnoise => Ok(Box::new(NnnoiselessDenoiser::new())),
+            DenoiseBackend::DeepFilterNet => {
+                let Some(path) = &self.deepfilter_model_path else {
+                    bail!("DeepFilterNet model not configured (deepfilter_model_path)");
+                };
+                Ok(Box::new(DeepFilterDenoiser::new(path)?))
+            }
+        }
+    }
+}
+
+/// Backend name for cache keys and reports, "off" without denoising.
+pub fn denoise_label(settings: Option<&DenoiseSettings>) -> &'static str {
+    settings.map_or("off", |settings| settings.backend.name())
+}
+
+/// Denoise 16kHz audio with a fresh denoiser of the given settings.
+pub fn denoise_samples(settings: &DenoiseSettings, samples: &[f32]) -> Result<Vec<f32>> {
+    settings.create()?.denoise(samples)
+}
+
+/// Cached 16kHz ↔ 48kHz resamplers shared by the denoisers.
+#[derive(Default)]
+pub(super) struct RateConverter {
+    /// Cached 16kHz→48kHz resampler (FFT kernel computed once).
+    upsampler: Option<FftFixedIn<f32>>,
+    /// Cached 48kHz→16kHz resampler (FFT kernel computed once).
+    downsampler: Option<FftFixedIn<f32>>,
+}
+
 /// Internal state protected by a single mutex to prevent ABBA deadlock.
 ///
 /// Resamplers and frame buffers are cached here to avoid repeated FFT kernel
@@ -34,16 +117,13 @@ struct DenoiseInner {
     frame_in: Vec<f32>,
     /// Pre-allocated output frame buffer for RNNoise (480 samples at 48kHz).
     frame_out: Vec<f32>,
-    /// Cached 16kHz→48kHz resampler (FFT kernel computed once).
-    upsampler: Option<FftFixedIn<f32>>,
-    /// Cached 48kHz→16kHz resampler (FFT kernel computed once).
-    downsampler: Option<FftFixedIn<f32>>,
+    rates: RateConverter,
 }
 
-impl DenoiseInner {
+impl RateConverter {
     /// Lazily create resamplers on first use. The FFT kernels are computed once
     /// and reused across all subsequent `denoise_buffer()` calls.
-    fn ensure_resamplers(&mut self) -> Result<()> {
+    pub(super) fn ensure_resamplers(&mut self) -> Result<()> {
         if self.upsampler.is_none() {
             self.upsampler = Some(
                 FftFixedIn::<f32>::new(
@@ -72,7 +152,7 @@ impl DenoiseInner {
     }
 
     /// Upsample 16kHz audio to 48kHz using the cached resampler.
-    fn resample_up(&mut self, samples: &[f32]) -> Result<Vec<f32>> {
+    pub(super) fn resample_up(&mut self, samples: &[f32]) -> Result<Vec<f32>> {
         if samples.is_empty() {
             return Ok(Vec::new());
         }
@@ -105,40 +185,8 @@ impl DenoiseInner {
         Ok(output)
     }
 
-    /// Run RNNoise on 48kHz audio using pre-allocated frame buffers.
-    ///
-    /// Uses `frame_in` and `frame_out` fields to avoid per-frame heap allocations.
-    /// Fields are destructured to satisfy the borrow checker (split borrows).
-    fn process_rnnoise(&mut self, upsampled: &[f32]) -> Vec<f32> {
-        let DenoiseInner {
-            ref mut state,
-            ref mut buffer,
-            ref mut frame_in,
-            ref mut frame_out,
-            ..
-        } = *self;
-
-        let mut denoised = Vec::with_capacity(upsampled.len());
-        buffer.extend_from_slice(upsampled);
-
-        while buffer.len() >= FRAME_SIZE {
-            frame_in.copy_from_slice(&buffer[..FRAME_SIZE]);
-            buffer.drain(..FRAME_SIZE);
-            state.process_frame(frame_out, frame_in);
-            denoised.extend_from_slice(frame_out);
-        }
-
-        // Flush remaining buffered samples (pass through unprocessed)
-        if !buffer.is_empty() {
-            denoised.extend_from_slice(buffer);
-            buffer.clear();
-        }
-
-        denoised
-    }
-
     /// Downsample 48kHz audio back to 16kHz using the cached resampler.
-    fn resample_down(&mut self, samples: &[f32]) -> Result<Vec<f32>> {
+    pub(super) fn resample_down(&mut self, samples: &[f32]) -> Result<Vec<f32>> {
         if samples.is_empty() {
             return Ok(Vec::new());
         }
@@ -174,6 +222,40 @@ impl DenoiseInner {
     }
 }
 
+impl DenoiseInner {
+    /// Run RNNoise on 48kHz audio using pre-allocated frame buffers.
+    ///
+    /// Uses `frame_in` and `frame_out` fields to avoid per-frame heap allocations.
+    /// Fields are destructured to satisfy the borrow checker (split borrows).
+    fn process_rnnoise(&mut self, upsampled: &[f32]) -> Vec<f32> {
+        let DenoiseInner {
+            ref mut state,
+            ref mut buffer,
+            ref mut frame_in,
+            ref mut frame_out,
+            ..
+        } = *self;
+
+        let mut denoised = Vec::with_capacity(upsampled.len());
+        buffer.extend_from_slice(upsampled);
+
+        while buffer.len() >= FRAME_SIZE {
+            frame_in.copy_from_slice(&buffer[..FRAME_SIZE]);
+            buffer.drain(..FRAME_SIZE);
+            state.process_frame(frame_out, frame_in);
+            denoised.extend_from_slice(frame_out);
+        }
+
+        // Flush remaining buffered samples (pass through unprocessed)
+        if !buffer.is_empty() {
+            denoised.extend_from_slice(buffer);
+            buffer.clear();
+        }
+
+        denoised
+    }
+}
+
 /// RNNoise-based denoiser using nnnoiseless.
 ///
 /// Accepts 16kHz audio, resamples to 48kHz for RNNoise processing,
@@ -194,8 +276,7 @@ impl NnnoiselessDenoiser {
                 buffer: Vec::with_capacity(FRAME_SIZE),
                 frame_in: vec![0.0f32; FRAME_SIZE],
                 frame_out: vec![0.0f32; FRAME_SIZE],
-                upsampler: None,
-                downsampler: None,
+                rates: RateConverter::default(),
             }),
         }
     }
@@ -211,16 +292,16 @@ impl NnnoiselessDenoiser {
         }
 
         let mut inner = self.inner.lock();
-        inner.ensure_resamplers()?;
+        inner.rates.ensure_resamplers()?;
 
         // 16kHz → 48kHz
-        let upsampled = inner.resample_up(samples)?;
+        let upsampled = inner.rates.resample_up(samples)?;
 
         // Run RNNoise at 48kHz (uses pre-allocated frame buffers)
         let denoised_48k = inner.process_rnnoise(&upsampled);
 
         // 48kHz → 16kHz
-        inner.resample_down(&denoised_48k)
+        inner.rates.resample_down(&denoised_48k)
     }
 }
 
@@ -230,7 +311,6 @@ impl Default for NnnoiselessDenoiser {
     }
 }
 
-#[cfg(test)]
 impl AudioDenoising for NnnoiselessDenoiser {
     fn denoise(&self, samples: &[f32]) -> Result<Vec<f32>> {
         self.denoise_buffer(samples)
@@ -247,7 +327,6 @@ impl AudioDenoising for NnnoiselessDenoiser {
 
 impl NnnoiselessDenoiser {
     /// Reset internal state for new recording session.
-    #[cfg(test)]
     pub fn reset_state(&self) {
         let mut inner = self.inner.lock();
         inner.buffer.clear();
@@ -257,8 +336,7 @@ impl NnnoiselessDenoiser {
         // Drop cached resamplers so they are re-created fresh on next use.
         // FftFixedIn maintains internal overlap buffers that may contain stale
         // audio from the previous session.
-        inner.upsampler = None;
-        inner.downsampler = None;
+        inner.rates = RateConverter::default();
     }
 }
 
@@ -356,6 +434,31 @@ mod tests {
         assert!(!output.is_empty());
     }
 
+    #[test]
+    fn test_denoise_backend_parse() {
+        assert_eq!(DenoiseBackend::parse("rnnoise"), DenoiseBackend::Rnnoise);
+        assert_eq!(DenoiseBackend::parse("deepfilternet"), DenoiseBackend::DeepFilterNet);
+        assert_eq!(DenoiseBackend::parse("unknown"), DenoiseBackend::Rnnoise);
+        assert_eq!(DenoiseBackend::DeepFilterNet.name(), "deepfilternet");
+    }
+
+    #[test]
+    fn test_deepfilter_without_model_fails() {
+        let settings = DenoiseSettings {
+            backend: DenoiseBackend::DeepFilterNet,
+            deepfilter_model_path: None,
+        };
+        assert!(settings.create().is_err());
+
+        let config = Config {
+            denoise_enabled: true,
+            ..Config::default()
+        };
+        let settings = DenoiseSettings::if_enabled(&config).unwrap();
+        assert_eq!(settings.backend, DenoiseBackend::Rnnoise);
+        assert_eq!(settings.create().unwrap().required_sample_rate(), 16000);
+    }
+
     #[test]
     fn test_noop_denoiser_passthrough() {
         let denoiser = NoOpDenoiser::new();
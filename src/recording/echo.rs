@@ -0,0 +1,397 @@
+//! Acoustic echo cancellation of the microphone in conference mode.
+//!
+//! Without headphones the microphone hears the other participants from the
+//! speakers, so their speech lands in the "Ви" channel a second time and is
+//! transcribed twice. The loopback channel holds exactly what the speakers
+//! played, so the echo can be predicted from it and subtracted.
+//!
+//! The two streams are captured separately and start at slightly different
+//! moments, so first the delay between them is found (GCC-PHAT over the whole
+//! recording). Then a partitioned-block frequency-domain adaptive filter, the
+//! same structure as speexdsp's MDF canceller, learns the path from speakers
+//! to microphone and subtracts its output from the microphone. Adaptation
+//! nearly stops while both sides talk, so the user's own speech is not learned
+//! as echo. When the loopback is not heard in the microphone at all (e.g.
+//! with headphones), the microphone is left as it is.
+
+use realfft::num_complex::Complex;
+use realfft::{ComplexToReal, RealFftPlanner, RealToComplex};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+/// Filter block: 16 ms at 16 kHz.
+const BLOCK: usize = 256;
+
+/// FFT size of the filter (two blocks, overlap-save).
+const FFT_LEN: usize = 2 * BLOCK;
+
+/// Filter length in blocks: 128 ms of echo tail after the bulk delay.
+const PARTITIONS: usize = 8;
+
+/// Adaptation step of the normalized filter.
+const STEP: f32 = 0.5;
+
+/// Smoothing of the per-bin reference power.
+const POWER_SMOOTHING: f32 = 0.9;
+
+/// Regularization of the per-bin normalization.
+const REGULARIZATION: f32 = 5e-4;
+
+/// Reference blocks quieter than this (mean square, -50 dBFS) do not adapt the filter.
+const FAR_END_FLOOR: f32 = 1e-5;
+
+/// Error-to-microphone power ratio above its running minimum, past which the
+/// user is taken to be talking over the far end.
+const DOUBLE_TALK_RATIO: f32 = 4.0;
+
+/// Per-block growth of the running minimum, so a changed echo path (the user
+/// moved the laptop) is learned again after a few seconds.
+const RATIO_FLOOR_GROWTH: f32 = 1.02;
+
+/// Smoothing of the block powers compared for double talk.
+const DOUBLE_TALK_SMOOTHING: f32 = 0.5;
+
+/// Block of the delay estimate: about a second.
+const DELAY_BLOCK: usize = 16384;
+
+/// Largest delay looked for between the two streams: half a second either way.
+const MAX_DELAY: usize = 8000;
+
+/// How far the correlation peak has to stand out for the echo to count as present.
+const DELAY_PEAK_RATIO: f32 = 8.0;
+
+/// Samples of the echo path kept before the estimated delay, so the filter
+/// also covers an echo arriving slightly earlier than the main one.
+const DELAY_MARGIN: isize = 160;
+
+/// Remove the loopback's echo from the microphone. Both are 16 kHz mono and
+/// start together; the result is as long as `mic`.
+///
+/// Returns the microphone unchanged when the loopback is not heard in it.
+pub fn cancel_echo(mic: &[f32], loopback: &[f32]) -> Vec<f32> {
+    let Some(delay) = estimate_echo_delay(mic, loopback) else {
+        return mic.to_vec();
+    };
+    // Loopback shifted so that its echo appears DELAY_MARGIN samples later in the mic
+    let shift = delay - DELAY_MARGIN;
+    let reference = |n: usize| -> f32 {
+        let index = n as isize - shift;
+        if index < 0 {
+            0.0
+        } else {
+            loopback.get(index as usize).copied().unwrap_or(0.0)
+        }
+    };
+
+    let mut canceller = EchoCanceller::new();
+    let mut out = Vec::with_capacity(mic.len() + BLOCK);
+    let mut mic_block = [0.0f32; BLOCK];
+    let mut ref_block = [0.0f32; BLOCK];
+    for start in (0..mic.len()).step_by(BLOCK) {
+        let end = (start + BLOCK).min(mic.len());
+        mic_block.fill(0.0);
+        mic_block[..end - start].copy_from_slice(&mic[start..end]);
+        for (i, sample) in ref_block.iter_mut().enumerate() {
+            *sample = reference(start + i);
+        }
+        out.extend(canceller.process(&mic_block, &ref_block));
+    }
+    out.truncate(mic.len());
+    out
+}
+
+/// Delay of the loopback's echo in the microphone, in samples; negative when
+/// the microphone stream is ahead of the loopback stream.
+///
+/// `None` when the loopback is not heard in the microphone.
+pub fn estimate_echo_delay(mic: &[f32], loopback: &[f32]) -> Option<isize> {
+    let len = mic.len().min(loopback.len());
+    let fft_len = 2 * DELAY_BLOCK;
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(fft_len);
+    let ifft = planner.plan_fft_inverse(fft_len);
+    let mut input = fft.make_input_vec();
+    let mut mic_spectrum = fft.make_output_vec();
+    let mut ref_spectrum = fft.make_output_vec();
+    let mut cross = vec![Complex::new(0.0f32, 0.0); mic_spectrum.len()];
+
+    let mut blocks = 0;
+    for start in (0..len.saturating_sub(DELAY_BLOCK - 1)).step_by(DELAY_BLOCK) {
+        let far = &loopback[start..start + DELAY_BLOCK];
+        if mean_square(far) < FAR_END_FLOOR {
+            continue;
+        }
+        input.fill(0.0);
+        input[..DELAY_BLOCK].copy_from_slice(far);
+        fft.process(&mut input, &mut ref_spectrum).ok()?;
+        input.fill(0.0);
+        input[..DELAY_BLOCK].copy_from_slice(&mic[start..start + DELAY_BLOCK]);
+        fft.process(&mut input, &mut mic_spectrum).ok()?;
+        // Phase transform: every frequency weighs the same
+        for (acc, (m, r)) in cross.iter_mut().zip(mic_spectrum.iter().zip(&ref_spectrum)) {
+            let product = m * r.conj();
+            let norm = product.norm();
+            if norm > 1e-12 {
+                *acc += product / norm;
+            }
+        }
+        blocks += 1;
+    }
+    if blocks == 0 {
+        return None;
+    }
+
+    // DC and Nyquist bins of a real signal's spectrum are real
+    cross[0].im = 0.0;
+    if let Some(last) = cross.last_mut() {
+        last.im = 0.0;
+    }
+    let mut correlation = ifft.make_output_vec();
+    ifft.process(&mut cross, &mut correlation).ok()?;
+
+    // Lag l ≥ 0 sits at index l, negative lags at the end
+    let at_lag = |lag: isize| -> f32 {
+        let index = if lag >= 0 {
+            lag as usize
+        } else {
+            fft_len - lag.unsigned_abs()
+        };
+        correlation[index]
+    };
+    let lags = -(MAX_DELAY as isize)..=MAX_DELAY as isize;
+    let (best_lag, peak) = lags
+        .clone()
+        .map(|lag| (lag, at_lag(lag)))
+        .fold(
+            (0, f32::MIN),
+            |best, candidate| if candidate.1 > best.1 { candidate } else { best },
+        );
+    let mean = lags.map(|lag| at_lag(lag).abs()).sum::<f32>() / (2 * MAX_DELAY + 1) as f32;
+    (peak > DELAY_PEAK_RATIO * mean).then_some(best_lag)
+}
+
+fn mean_square(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s * s).sum::<f32>() / samples.len().max(1) as f32
+}
+
+/// Partitioned-block frequency-domain NLMS filter (overlap-save).
+struct EchoCanceller {
+    fft: Arc<dyn RealToComplex<f32>>,
+    ifft: Arc<dyn ComplexToReal<f32>>,
+    /// Previous reference block, the first half of the FFT input
+    previous_reference: Vec<f32>,
+    /// Spectra of the latest reference blocks, newest first
+    history: VecDeque<Vec<Complex<f32>>>,
+    /// Filter partitions matching `history`
+    weights: Vec<Vec<Complex<f32>>>,
+    /// Smoothed power of the reference per bin
+    power: Vec<f32>,
+    /// Smoothed power of the microphone and of the error
+    mic_power: f32,
+    error_power: f32,
+    /// Running minimum of `error_power / mic_power`: how well the echo is cancelled
+    ratio_floor: f32,
+    time: Vec<f32>,
+    spectrum: Vec<Complex<f32>>,
+}
+
+impl EchoCanceller {
+    fn new() -> Self {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let fft = planner.plan_fft_forward(FFT_LEN);
+        let ifft = planner.plan_fft_inverse(FFT_LEN);
+        let bins = FFT_LEN / 2 + 1;
+        Self {
+            time: fft.make_input_vec(),
+            spectrum: fft.make_output_vec(),
+            fft,
+            ifft,
+            previous_reference: vec![0.0; BLOCK],
+            history: VecDeque::with_capacity(PARTITIONS + 1),
+            weights: vec![vec![Complex::new(0.0, 0.0); bins]; PARTITIONS],
+            power: vec![0.0; bins],
+            mic_power: 0.0,
+            error_power: 0.0,
+            ratio_floor: 1.0,
+        }
+    }
+
+    /// Cancel the echo in one block of the microphone given the same block of the reference.
+    fn process(&mut self, mic: &[f32; BLOCK], reference: &[f32; BLOCK]) -> Vec<f32> {
+        // Reference spectrum of the last two blocks
+        self.time[..BLOCK].copy_from_slice(&self.previous_reference);
+        self.time[BLOCK..].copy_from_slice(reference);
+        self.previous_reference.copy_from_slice(reference);
+        let mut far = self.fft.make_output_vec();
+        let _ = self.fft.process(&mut self.time, &mut far);
+        for (power, x) in self.power.iter_mut().zip(&far) {
+            *power = POWER_SMOOTHING * *power + (1.0 - POWER_SMOOTHING) * x.norm_sqr();
+        }
+        self.history.push_front(far);
+        self.history.truncate(PARTITIONS);
+
+        // Echo estimate: the second half of the filtered reference
+        for (bin, y) in self.spectrum.iter_mut().enumerate() {
+            *y = self
+                .history
+                .iter()
+                .zip(&self.weights)
+                .map(|(x, w)| w[bin] * x[bin])
+                .sum();
+        }
+        self.inverse();
+        let error: Vec<f32> = mic.iter().zip(&self.time[BLOCK..]).map(|(d, y)| d - y).collect();
+
+        if mean_square(reference) >= FAR_END_FLOOR {
+            let step = self.double_talk_step(mic, &error);
+            self.adapt(&error, step);
+        }
+        error
+    }
+
+    /// Adaptation step for this block. When the user talks, the error becomes
+    /// much louder relative to the microphone than the filter has already
+    /// achieved; the step then nearly stops so their speech is not learned as echo.
+    fn double_talk_step(&mut self, mic: &[f32], error: &[f32]) -> f32 {
+        let smooth =
+            |previous: f32, current: f32| DOUBLE_TALK_SMOOTHING * previous + (1.0 - DOUBLE_TALK_SMOOTHING) * current;
+        self.mic_power = smooth(self.mic_power, mean_square(mic));
+        self.error_power = smooth(self.error_power, mean_square(error));
+        let ratio = self.error_power / self.mic_power.max(1e-12);
+        self.ratio_floor = ratio.min(self.ratio_floor * RATIO_FLOOR_GROWTH).min(1.0);
+        STEP * (DOUBLE_TALK_RATIO * self.ratio_floor / ratio.max(1e-12))
+            .min(1.0)
+            .powi(2)
+    }
+
+    /// One NLMS step towards a smaller `error`.
+    fn adapt(&mut self, error: &[f32], step: f32) {
+        self.time[..BLOCK].fill(0.0);
+        self.time[BLOCK..].copy_from_slice(error);
+        let mut error_spectrum = self.fft.make_output_vec();
+        let _ = self.fft.process(&mut self.time, &mut error_spectrum);
+
+        for partition in 0..self.history.len() {
+            let far = &self.history[partition];
+            for (bin, gradient) in self.spectrum.iter_mut().enumerate() {
+                let norm = PARTITIONS as f32 * self.power[bin] + REGULARIZATION;
+                *gradient = far[bin].conj() * error_spectrum[bin] / norm;
+            }
+            // Gradient constraint: keep the filter causal and one block long
+            self.inverse();
+            self.time[BLOCK..].fill(0.0);
+            let _ = self.fft.process(&mut self.time, &mut self.spectrum);
+            for (w, g) in self.weights[partition].iter_mut().zip(&self.spectrum) {
+                *w += g * step;
+            }
+        }
+    }
+
+    /// `spectrum` back to `time`, normalized.
+    fn inverse(&mut self) {
+        self.spectrum[0].im = 0.0;
+        if let Some(last) = self.spectrum.last_mut() {
+            last.im = 0.0;
+        }
+        let _ = self.ifft.process(&mut self.spectrum, &mut self.time);
+        let scale = 1.0 / FFT_LEN as f32;
+        for sample in &mut self.time {
+            *sample *= scale;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic white noise in [-amplitude, amplitude].
+    fn noise(len: usize, seed: u64, amplitude: f32) -> Vec<f32> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                ((state >> 33) as f32 / (1u64 << 31) as f32 - 1.0) * amplitude
+            })
+            .collect()
+    }
+
+    /// Room echo of `far`: a few delayed, attenuated reflections.
+    fn echo_of(far: &[f32], delay: usize) -> Vec<f32> {
+        let taps = [(0, 0.4), (37, -0.2), (150, 0.1), (600, 0.05)];
+        (0..far.len())
+            .map(|n| {
+                taps.iter()
+                    .filter(|(offset, _)| n >= delay + offset)
+                    .map(|(offset, gain)| gain * far[n - delay - offset])
+                    .sum()
+            })
+            .collect()
+    }
+
+    fn power_db(samples: &[f32]) -> f32 {
+        10.0 * mean_square(samples).max(1e-20).log10()
+    }
+
+    #[test]
+    fn test_delay_is_found_both_ways() {
+        let far = noise(16000 * 6, 1, 0.3);
+        let mic = echo_of(&far, 800);
+        assert_eq!(estimate_echo_delay(&mic, &far), Some(800));
+
+        // Microphone stream started 400 samples later than the loopback
+        let early: Vec<f32> = echo_of(&far, 0)[400..].to_vec();
+        assert_eq!(estimate_echo_delay(&early, &far), Some(-400));
+    }
+
+    #[test]
+    fn test_without_echo_mic_is_unchanged() {
+        let far = noise(16000 * 4, 1, 0.3);
+        let mic = noise(16000 * 4, 2, 0.1);
+        assert_eq!(estimate_echo_delay(&mic, &far), None);
+        assert_eq!(cancel_echo(&mic, &far), mic);
+        // A silent loopback has nothing to cancel
+        assert_eq!(cancel_echo(&mic, &vec![0.0; mic.len()]), mic);
+    }
+
+    #[test]
+    fn test_echo_is_removed_and_near_speech_kept() {
+        let len = 16000 * 8;
+        let far = noise(len, 1, 0.3);
+        let echo = echo_of(&far, 500);
+        // The user talks over the far end in the last two seconds
+        let near: Vec<f32> = (0..len)
+            .map(|n| {
+                if n >= len - 32000 {
+                    0.1 * (n as f32 * 0.07).sin()
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let mic: Vec<f32> = echo.iter().zip(&near).map(|(e, s)| e + s).collect();
+
+        let out = cancel_echo(&mic, &far);
+        assert_eq!(out.len(), mic.len());
+
+        // Echo alone, after convergence: at least 20 dB quieter
+        let before = &mic[len - 64000..len - 32000];
+        let after = &out[len - 64000..len - 32000];
+        assert!(
+            power_db(before) - power_db(after) > 20.0,
+            "{} -> {}",
+            power_db(before),
+            power_db(after)
+        );
+
+        // During double talk what remains is the user's speech
+        let residual: Vec<f32> = out[len - 32000..]
+            .iter()
+            .zip(&near[len - 32000..])
+            .map(|(o, s)| o - s)
+            .collect();
+        assert!(power_db(&near[len - 32000..]) - power_db(&residual) > 15.0);
+    }
+}
@@ -0,0 +1,227 @@
+//! Audio file played as a live microphone.
+//!
+//! `FileAudioSource` implements [`AudioRecording`] by appending a file's
+//! audio to the samples buffer in 10 ms chunks at the pace of a real
+//! capture, or faster. Continuous mode, VAD, the level meter and the
+//! whole UI then run as with a microphone, so they can be tested and
+//! demonstrated without audio hardware. After the end of the file the
+//! source delivers silence, like a microphone in a quiet room, until the
+//! recording stops.
+//!
+//! The app uses it instead of the microphone when [`AUDIO_SOURCE_ENV`]
+//! names a file; [`AUDIO_SPEED_ENV`] speeds playback up.
+
+use super::core::{RecordingCore, WHISPER_SAMPLE_RATE};
+use super::levels::LevelMonitor;
+use crate::cli::args::ChannelMode;
+use crate::cli::wav_reader::{prepare_for_whisper, read_audio};
+use crate::domain::traits::AudioRecording;
+use crate::domain::types::{AudioQuality, LevelReading, LevelWarning};
+use anyhow::{bail, Context, Result};
+use async_channel::Receiver;
+use parking_lot::Mutex;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Environment variable naming an audio file to record from instead of the microphone
+pub const AUDIO_SOURCE_ENV: &str = "S2T_AUDIO_SOURCE";
+
+/// Environment variable with the playback speed of [`AUDIO_SOURCE_ENV`] (default 1)
+pub const AUDIO_SPEED_ENV: &str = "S2T_AUDIO_SPEED";
+
+/// Samples appended at a time (10 ms), about what a capture callback delivers
+const CHUNK_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize / 100;
+
+/// Recorder that plays 16 kHz mono audio as if it were being captured.
+pub struct FileAudioSource {
+    core: RecordingCore,
+    audio: Arc<[f32]>,
+    speed: f32,
+    levels: Arc<Mutex<LevelMonitor>>,
+    /// Number of the current playback; a thread whose playback was stopped
+    /// and restarted before it noticed must not carry on next to the new one
+    playback: Arc<AtomicU64>,
+}
+
+impl FileAudioSource {
+    /// Play `audio` (16 kHz mono) at `speed` times real time.
+    pub fn new(audio: Vec<f32>, speed: f32) -> Result<Self> {
+        if !(speed > 0.0 && speed.is_finite()) {
+            bail!("Швидкість відтворення має бути додатним числом: {}", speed);
+        }
+        Ok(Self {
+            core: RecordingCore::new(),
+            audio: audio.into(),
+            speed,
+            levels: Arc::new(Mutex::new(LevelMonitor::new())),
+            playback: Arc::new(AtomicU64::new(0)),
+        })
+    }
+
+    /// Play an audio file in any format the CLI reads, mixed down to mono.
+    pub fn from_file(path: &Path, speed: f32) -> Result<Self> {
+        let audio = read_audio(path)?;
+        let prepared = prepare_for_whisper(&audio, ChannelMode::Mix, None)?;
+        Self::new(prepared.samples, speed)
+    }
+
+    /// The source [`AUDIO_SOURCE_ENV`] asks for, if it is set.
+    pub fn from_env() -> Option<Result<Self>> {
+        let path = std::env::var_os(AUDIO_SOURCE_ENV)?;
+        let speed = match std::env::var(AUDIO_SPEED_ENV) {
+            Ok(speed) => match speed.trim().parse() {
+                Ok(speed) => speed,
+                Err(_) => {
+                    return Some(Err(anyhow::anyhow!(
+                        "Некоректне значення {}: {}",
+                        AUDIO_SPEED_ENV,
+                        speed
+                    )))
+                }
+            },
+            Err(_) => 1.0,
+        };
+        let path = Path::new(&path);
+        Some(
+            Self::from_file(path, speed)
+                .with_context(|| format!("Не вдалося відкрити джерело звуку: {}", path.display())),
+        )
+    }
+
+    /// Get a reference to the shared samples buffer.
+    pub fn samples(&self) -> &Arc<Mutex<Vec<f32>>> {
+        &self.core.samples
+    }
+
+    fn start_playback(&self) -> Result<()> {
+        if self.core.is_recording() {
+            bail!("Запис уже триває");
+        }
+        *self.levels.lock() = LevelMonitor::new();
+        let playback = self.playback.clone();
+        let this_playback = playback.fetch_add(1, Ordering::SeqCst) + 1;
+        let handles = self.core.prepare_recording();
+        let audio = self.audio.clone();
+        let speed = self.speed as f64;
+        let levels = self.levels.clone();
+
+        thread::spawn(move || {
+            let started = Instant::now();
+            let silence = [0.0; CHUNK_SAMPLES];
+            let mut position = 0;
+            loop {
+                let chunk = audio
+                    .get(position..(position + CHUNK_SAMPLES).min(audio.len()))
+                    .filter(|chunk| !chunk.is_empty())
+                    .unwrap_or(&silence);
+                {
+                    // Decided under the lock, so nothing lands after stop
+                    // took the samples and reset the meter
+                    let mut samples = handles.samples.lock();
+                    if !handles.is_recording.load(Ordering::SeqCst) || playback.load(Ordering::SeqCst) != this_playback
+                    {
+                        break;
+                    }
+                    samples.extend_from_slice(chunk);
+                    handles.meter.push(chunk);
+                }
+                levels.lock().push(chunk);
+                position += chunk.len();
+
+                let due = Duration::from_secs_f64(position as f64 / WHISPER_SAMPLE_RATE as f64 / speed);
+                if let Some(wait) = due.checked_sub(started.elapsed()) {
+                    thread::sleep(wait);
+                }
+            }
+            let _ = handles.completion_tx.send_blocking(());
+        });
+        Ok(())
+    }
+}
+
+impl AudioRecording for FileAudioSource {
+    fn start(&self) -> Result<()> {
+        self.start_playback()
+    }
+
+    fn stop(&self) -> (Vec<f32>, Option<Receiver<()>>) {
+        self.core.stop()
+    }
+
+    fn level(&self) -> LevelReading {
+        self.core.level()
+    }
+
+    fn is_recording(&self) -> bool {
+        self.core.is_recording()
+    }
+
+    fn level_warning(&self) -> Option<LevelWarning> {
+        self.levels.lock().warning()
+    }
+
+    fn audio_quality(&self) -> Option<AudioQuality> {
+        self.levels.lock().quality()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(secs: f32) -> Vec<f32> {
+        (0..(16000.0 * secs) as usize)
+            .map(|i| 0.3 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / 16000.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_plays_file_then_silence() {
+        let audio = tone(0.5);
+        let source = FileAudioSource::new(audio.clone(), 20.0).unwrap();
+        source.start().unwrap();
+        assert!(source.is_recording());
+        // 0.5 s at 20x takes 25 ms; wait for some silence after it
+        thread::sleep(Duration::from_millis(60));
+        assert!(source.level().peak_dbfs > LevelReading::FLOOR_DBFS);
+
+        let (samples, completion_rx) = source.stop();
+        completion_rx.unwrap().recv_blocking().unwrap();
+        assert!(!source.is_recording());
+        assert!(samples.len() > audio.len(), "{} samples", samples.len());
+        assert_eq!(&samples[..audio.len()], &audio[..]);
+        assert!(samples[audio.len()..].iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_plays_in_real_time() {
+        let source = FileAudioSource::new(tone(2.0), 1.0).unwrap();
+        source.start().unwrap();
+        thread::sleep(Duration::from_millis(200));
+        let (samples, _) = source.stop();
+        // About 200 ms worth, nowhere near the whole file
+        assert!((2400..=4800).contains(&samples.len()), "{} samples", samples.len());
+    }
+
+    #[test]
+    fn test_restart_plays_from_the_beginning() {
+        let audio = tone(0.2);
+        let source = FileAudioSource::new(audio.clone(), 50.0).unwrap();
+        for _ in 0..2 {
+            source.start().unwrap();
+            thread::sleep(Duration::from_millis(20));
+            let (samples, completion_rx) = source.stop();
+            completion_rx.unwrap().recv_blocking().unwrap();
+            assert_eq!(&samples[..audio.len()], &audio[..]);
+        }
+    }
+
+    #[test]
+    fn test_rejects_bad_speed() {
+        assert!(FileAudioSource::new(Vec::new(), 0.0).is_err());
+        assert!(FileAudioSource::new(Vec::new(), f32::NAN).is_err());
+    }
+}
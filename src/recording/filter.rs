@@ -0,0 +1,105 @@
+//! High-pass filter for the microphone.
+//!
+//! Desk thumps, traffic and fan rumble sit below 80 Hz, and cheap audio
+//! interfaces add a DC offset. Neither carries speech, but both raise the
+//! frame energy the WebRTC VAD looks at, so it takes rumble for speech and
+//! ends segments late. A second-order Butterworth high-pass at the cutoff
+//! removes both before the samples reach the VAD, denoiser and model.
+
+use super::core::WHISPER_SAMPLE_RATE;
+
+/// Second-order Butterworth high-pass of 16 kHz mono audio.
+///
+/// The filter keeps its state between blocks, so blocks must be passed in
+/// order; a new recording needs a new filter.
+pub struct HighPassFilter {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    // Transposed direct form II state
+    z1: f64,
+    z2: f64,
+}
+
+impl HighPassFilter {
+    pub fn new(cutoff_hz: f32) -> Self {
+        let w0 = std::f64::consts::TAU * cutoff_hz as f64 / WHISPER_SAMPLE_RATE as f64;
+        let alpha = w0.sin() / std::f64::consts::SQRT_2; // Q = 1/√2
+        let cos = w0.cos();
+        let a0 = 1.0 + alpha;
+        Self {
+            b0: (1.0 + cos) / 2.0 / a0,
+            b1: -(1.0 + cos) / a0,
+            b2: (1.0 + cos) / 2.0 / a0,
+            a1: -2.0 * cos / a0,
+            a2: (1.0 - alpha) / a0,
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    /// Filter the next block of samples.
+    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+        samples
+            .iter()
+            .map(|&x| {
+                let x = x as f64;
+                let y = self.b0 * x + self.z1;
+                self.z1 = self.b1 * x - self.a1 * y + self.z2;
+                self.z2 = self.b2 * x - self.a2 * y;
+                y as f32
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, secs: f32) -> Vec<f32> {
+        (0..(secs * 16000.0) as usize)
+            .map(|i| 0.5 * (i as f32 * freq * std::f32::consts::TAU / 16000.0).sin())
+            .collect()
+    }
+
+    fn rms(samples: &[f32]) -> f32 {
+        (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt()
+    }
+
+    /// Level change in dB over the last half second, after the filter settled.
+    fn attenuation_db(freq: f32) -> f32 {
+        let input = sine(freq, 2.0);
+        let output = HighPassFilter::new(80.0).process(&input);
+        let tail = input.len() - 8000;
+        20.0 * (rms(&output[tail..]) / rms(&input[tail..])).log10()
+    }
+
+    #[test]
+    fn test_dc_offset_is_removed() {
+        let input: Vec<f32> = sine(440.0, 1.0).iter().map(|s| s + 0.2).collect();
+        let output = HighPassFilter::new(80.0).process(&input);
+        let tail = &output[8000..];
+        let mean = tail.iter().sum::<f32>() / tail.len() as f32;
+        assert!(mean.abs() < 1e-3, "mean {}", mean);
+    }
+
+    #[test]
+    fn test_rumble_is_cut_and_speech_passes() {
+        assert!(attenuation_db(30.0) < -12.0);
+        assert!(attenuation_db(80.0) < -2.5 && attenuation_db(80.0) > -3.5);
+        assert!(attenuation_db(1000.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_state_carries_over_blocks() {
+        let input = sine(200.0, 0.5);
+        let whole = HighPassFilter::new(80.0).process(&input);
+        let mut filter = HighPassFilter::new(80.0);
+        let mut blocks = filter.process(&input[..1234]);
+        blocks.extend(filter.process(&input[1234..]));
+        assert_eq!(whole, blocks);
+    }
+}
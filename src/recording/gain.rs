@@ -0,0 +1,164 @@
+//! Automatic gain control for the microphone.
+//!
+//! Some microphones (laptop arrays, headsets with a low input level) record
+//! speech at -40 dBFS or below. The VAD then hears little more than noise and
+//! Whisper drops quiet words. The AGC measures each 10 ms block and moves its
+//! gain towards the one that brings speech to the target level: down quickly
+//! when the level rises, up slowly so a pause between words is not pumped up.
+//! Blocks below the noise gate keep the gain as it is, and a limiter keeps the
+//! boosted peaks from clipping.
+
+use crate::app::config::Config;
+use crate::domain::traits::GainControl;
+use parking_lot::Mutex;
+
+/// Measurement block: 10 ms at 16 kHz.
+const BLOCK_SAMPLES: usize = 160;
+
+/// Blocks quieter than this are taken for silence and do not move the gain.
+const NOISE_GATE_DBFS: f32 = -60.0;
+
+/// Share of the gap to the wanted gain closed per block when the level rises.
+const ATTACK: f32 = 0.5;
+
+/// Fastest gain increase: 10 dB per second.
+const RELEASE_DB_PER_BLOCK: f32 = 0.1;
+
+/// Peak the limiter lets through.
+const CEILING: f32 = 0.95;
+
+/// Target and limit of the AGC stage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GainSettings {
+    /// RMS level speech is brought to.
+    pub target_dbfs: f32,
+    /// Largest boost applied.
+    pub max_gain_db: f32,
+}
+
+impl GainSettings {
+    /// Settings from the config, or `None` when the AGC is off.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        config.agc_enabled.then_some(Self {
+            target_dbfs: config.agc_target_dbfs,
+            max_gain_db: config.agc_max_gain_db,
+        })
+    }
+}
+
+/// Block-wise AGC of 16 kHz mono audio.
+pub struct AutoGain {
+    settings: GainSettings,
+    gain_db: Mutex<f32>,
+}
+
+impl AutoGain {
+    pub fn new(settings: GainSettings) -> Self {
+        Self {
+            settings,
+            gain_db: Mutex::new(0.0),
+        }
+    }
+
+    /// Gain wanted for a block at `level_dbfs`, never below unity.
+    fn wanted_gain_db(&self, level_dbfs: f32) -> f32 {
+        (self.settings.target_dbfs - level_dbfs).clamp(0.0, self.settings.max_gain_db)
+    }
+}
+
+impl GainControl for AutoGain {
+    fn process(&self, samples: &[f32]) -> Vec<f32> {
+        let mut gain_db = self.gain_db.lock();
+        let mut out = Vec::with_capacity(samples.len());
+        for block in samples.chunks(BLOCK_SAMPLES) {
+            let level = rms_dbfs(block);
+            if level > NOISE_GATE_DBFS {
+                let wanted = self.wanted_gain_db(level);
+                if wanted < *gain_db {
+                    *gain_db += (wanted - *gain_db) * ATTACK;
+                } else {
+                    *gain_db += (wanted - *gain_db).min(RELEASE_DB_PER_BLOCK);
+                }
+            }
+
+            let peak = block.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+            let mut gain = 10.0_f32.powf(*gain_db / 20.0);
+            if peak * gain > CEILING {
+                gain = CEILING / peak;
+            }
+            out.extend(block.iter().map(|s| s * gain));
+        }
+        out
+    }
+
+    fn gain_db(&self) -> f32 {
+        *self.gain_db.lock()
+    }
+
+    fn reset(&self) {
+        *self.gain_db.lock() = 0.0;
+    }
+}
+
+fn rms_dbfs(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return f32::NEG_INFINITY;
+    }
+    let mean_square = samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32;
+    10.0 * mean_square.max(1e-12).log10()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETTINGS: GainSettings = GainSettings {
+        target_dbfs: -20.0,
+        max_gain_db: 30.0,
+    };
+
+    /// 440 Hz tone with the given RMS level.
+    fn tone(level_dbfs: f32, secs: f32) -> Vec<f32> {
+        let amplitude = 10.0_f32.powf(level_dbfs / 20.0) * std::f32::consts::SQRT_2;
+        (0..(secs * 16000.0) as usize)
+            .map(|i| amplitude * (i as f32 * 440.0 * std::f32::consts::TAU / 16000.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_quiet_speech_is_brought_to_target() {
+        let agc = AutoGain::new(SETTINGS);
+        let out = agc.process(&tone(-40.0, 4.0));
+        assert!((agc.gain_db() - 20.0).abs() < 0.5);
+        let tail = &out[out.len() - 16000..];
+        assert!((rms_dbfs(tail) - SETTINGS.target_dbfs).abs() < 1.0);
+    }
+
+    #[test]
+    fn test_gain_is_limited_to_max() {
+        let agc = AutoGain::new(SETTINGS);
+        agc.process(&tone(-58.0, 5.0));
+        assert!((agc.gain_db() - SETTINGS.max_gain_db).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_silence_does_not_move_gain() {
+        let agc = AutoGain::new(SETTINGS);
+        let silence = tone(-70.0, 1.0);
+        assert_eq!(agc.process(&silence), silence);
+        assert_eq!(agc.gain_db(), 0.0);
+    }
+
+    #[test]
+    fn test_loud_burst_after_quiet_speech_does_not_clip() {
+        let agc = AutoGain::new(SETTINGS);
+        agc.process(&tone(-40.0, 3.0));
+        let out = agc.process(&tone(-6.0, 0.5));
+        assert!(out.iter().all(|s| s.abs() <= CEILING + 1e-6));
+        // The gain comes down within a few blocks
+        assert!(agc.gain_db() < 1.0);
+
+        agc.reset();
+        assert_eq!(agc.gain_db(), 0.0);
+    }
+}
@@ -0,0 +1,214 @@
+//! Input level checks while recording.
+//!
+//! Two level problems spoil recognition no matter the model: clipping, when
+//! the input gain is so high that peaks hit full scale and distort, and a
+//! level so low that speech hardly rises above the noise. The monitor looks
+//! at the microphone's audio before the high-pass filter and AGC, in 100 ms
+//! blocks: what it sees right now drives the warning under the level bar,
+//! and what it saw over the whole recording becomes the history entry's
+//! audio-quality note.
+
+use crate::domain::types::{AudioQuality, LevelWarning};
+use std::collections::VecDeque;
+
+/// Samples per block (100 ms at 16 kHz)
+const BLOCK_SAMPLES: usize = 1600;
+
+/// Magnitude at which a sample counts as clipped
+pub(super) const CLIP_LEVEL: f32 = 0.99;
+
+/// Clipped samples a block needs to count as clipping; a lone full-scale
+/// sample is inaudible
+pub(super) const MIN_CLIPPED_SAMPLES: usize = 3;
+
+/// The clipping warning stays up this many blocks after the last clipped one
+const CLIP_HOLD_BLOCKS: usize = 20;
+
+/// Speech whose loudest 100 ms stays below this is hard to recognize
+const USABLE_LEVEL_DBFS: f32 = -40.0;
+
+/// Blocks the level must stay below usable before it is called too quiet;
+/// shorter stretches are just pauses
+const QUIET_WINDOW_BLOCKS: usize = 50;
+
+/// Share of clipped blocks from which the history note mentions clipping
+const NOTED_CLIPPED_RATIO: f32 = 0.01;
+
+/// Level statistics of one recording.
+#[derive(Debug, Clone)]
+pub struct LevelMonitor {
+    block_squares: f64,
+    block_clipped: usize,
+    block_len: usize,
+    blocks: usize,
+    clipped_blocks: usize,
+    last_clipped_block: Option<usize>,
+    /// RMS of the most recent blocks, dBFS, at most `QUIET_WINDOW_BLOCKS`
+    recent_dbfs: VecDeque<f32>,
+    loudest_dbfs: f32,
+}
+
+impl Default for LevelMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LevelMonitor {
+    pub fn new() -> Self {
+        Self {
+            block_squares: 0.0,
+            block_clipped: 0,
+            block_len: 0,
+            blocks: 0,
+            clipped_blocks: 0,
+            last_clipped_block: None,
+            recent_dbfs: VecDeque::with_capacity(QUIET_WINDOW_BLOCKS),
+            loudest_dbfs: f32::NEG_INFINITY,
+        }
+    }
+
+    /// Feed 16 kHz audio as it is recorded.
+    pub fn push(&mut self, samples: &[f32]) {
+        for &sample in samples {
+            self.block_squares += (sample as f64) * (sample as f64);
+            if sample.abs() >= CLIP_LEVEL {
+                self.block_clipped += 1;
+            }
+            self.block_len += 1;
+            if self.block_len == BLOCK_SAMPLES {
+                self.finish_block();
+            }
+        }
+    }
+
+    fn finish_block(&mut self) {
+        let rms = (self.block_squares / self.block_len as f64).sqrt() as f32;
+        let dbfs = 20.0 * rms.max(1e-6).log10();
+
+        if self.block_clipped >= MIN_CLIPPED_SAMPLES {
+            self.clipped_blocks += 1;
+            self.last_clipped_block = Some(self.blocks);
+        }
+        if self.recent_dbfs.len() == QUIET_WINDOW_BLOCKS {
+            self.recent_dbfs.pop_front();
+        }
+        self.recent_dbfs.push_back(dbfs);
+        self.loudest_dbfs = self.loudest_dbfs.max(dbfs);
+        self.blocks += 1;
+
+        self.block_squares = 0.0;
+        self.block_clipped = 0;
+        self.block_len = 0;
+    }
+
+    /// The level problem to warn about now; clipping wins over quietness.
+    pub fn warning(&self) -> Option<LevelWarning> {
+        let recently_clipped = self
+            .last_clipped_block
+            .is_some_and(|block| self.blocks - block <= CLIP_HOLD_BLOCKS);
+        if recently_clipped {
+            return Some(LevelWarning::Clipping);
+        }
+        let quiet = self.recent_dbfs.len() == QUIET_WINDOW_BLOCKS
+            && self.recent_dbfs.iter().all(|&dbfs| dbfs < USABLE_LEVEL_DBFS);
+        quiet.then_some(LevelWarning::TooQuiet)
+    }
+
+    /// Level problems of the whole recording, `None` if it was fine.
+    pub fn quality(&self) -> Option<AudioQuality> {
+        if self.blocks == 0 {
+            return None;
+        }
+        let clipped_ratio = self.clipped_blocks as f32 / self.blocks as f32;
+        let quality = AudioQuality {
+            clipped_percent: (clipped_ratio >= NOTED_CLIPPED_RATIO).then_some(clipped_ratio * 100.0),
+            too_quiet_dbfs: (self.loudest_dbfs < USABLE_LEVEL_DBFS).then_some(self.loudest_dbfs),
+        };
+        (quality.clipped_percent.is_some() || quality.too_quiet_dbfs.is_some()).then_some(quality)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(amplitude: f32, secs: f32) -> Vec<f32> {
+        (0..(16000.0 * secs) as usize)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * 300.0 * i as f32 / 16000.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_normal_speech_level_is_fine() {
+        let mut monitor = LevelMonitor::new();
+        monitor.push(&tone(0.2, 10.0));
+        assert_eq!(monitor.warning(), None);
+        assert_eq!(monitor.quality(), None);
+    }
+
+    #[test]
+    fn test_clipping_warns_and_is_noted() {
+        let mut monitor = LevelMonitor::new();
+        monitor.push(&tone(0.2, 5.0));
+        // Overdriven input flattened at full scale
+        let clipped: Vec<f32> = tone(3.0, 1.0).iter().map(|s| s.clamp(-1.0, 1.0)).collect();
+        monitor.push(&clipped);
+        assert_eq!(monitor.warning(), Some(LevelWarning::Clipping));
+
+        let quality = monitor.quality().unwrap();
+        let percent = quality.clipped_percent.unwrap();
+        assert!((percent - 100.0 / 6.0).abs() < 1.0, "{}", percent);
+        assert_eq!(quality.too_quiet_dbfs, None);
+    }
+
+    #[test]
+    fn test_clipping_warning_clears_after_hold() {
+        let mut monitor = LevelMonitor::new();
+        let clipped: Vec<f32> = tone(3.0, 0.5).iter().map(|s| s.clamp(-1.0, 1.0)).collect();
+        monitor.push(&clipped);
+        monitor.push(&tone(0.2, 1.0));
+        assert_eq!(monitor.warning(), Some(LevelWarning::Clipping));
+        monitor.push(&tone(0.2, 1.5));
+        assert_eq!(monitor.warning(), None);
+    }
+
+    #[test]
+    fn test_single_overs_are_not_clipping() {
+        let mut monitor = LevelMonitor::new();
+        let mut audio = tone(0.2, 2.0);
+        audio[800] = 1.0;
+        audio[5000] = -1.0;
+        monitor.push(&audio);
+        assert_eq!(monitor.warning(), None);
+        assert_eq!(monitor.quality(), None);
+    }
+
+    #[test]
+    fn test_quiet_input_warns_only_when_it_lasts() {
+        let mut monitor = LevelMonitor::new();
+        // -50 dBFS RMS
+        monitor.push(&tone(0.0045, 3.0));
+        assert_eq!(monitor.warning(), None, "a pause is not a problem");
+        monitor.push(&tone(0.0045, 3.0));
+        assert_eq!(monitor.warning(), Some(LevelWarning::TooQuiet));
+
+        let quality = monitor.quality().unwrap();
+        let dbfs = quality.too_quiet_dbfs.unwrap();
+        assert!((dbfs + 50.0).abs() < 1.0, "{}", dbfs);
+        assert_eq!(quality.clipped_percent, None);
+
+        // Speaking up clears the warning, and the recording did reach a usable level
+        monitor.push(&tone(0.2, 1.0));
+        assert_eq!(monitor.warning(), None);
+        assert_eq!(monitor.quality(), None);
+    }
+
+    #[test]
+    fn test_empty_recording_has_no_quality() {
+        let mut monitor = LevelMonitor::new();
+        monitor.push(&[0.0; 100]);
+        assert_eq!(monitor.warning(), None);
+        assert_eq!(monitor.quality(), None);
+    }
+}
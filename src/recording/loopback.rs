@@ -1,9 +1,82 @@
 use anyhow::{Context, Result};
 use async_channel::Receiver;
+use parking_lot::Mutex;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 use std::thread;
 
-use super::core::{calculate_rms, RecordingCore, WHISPER_SAMPLE_RATE};
+use super::core::{RecordingCore, WHISPER_SAMPLE_RATE};
+use crate::domain::types::LevelReading;
+
+/// An output's monitor: a source that carries what is played to that output.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MonitorSource {
+    /// PulseAudio/PipeWire source name, as passed to `parec --device`.
+    pub name: String,
+    /// Human-readable name, e.g. "Monitor of Built-in Audio Analog Stereo".
+    pub description: String,
+}
+
+/// Monitor sources of all outputs, for choosing what conference mode captures.
+///
+/// Empty if `pactl` is missing or fails.
+pub fn list_monitor_sources() -> Vec<MonitorSource> {
+    std::process::Command::new("pactl")
+        // Field names are translated otherwise
+        .env("LC_ALL", "C")
+        .args(["list", "sources"])
+        .output()
+        .map(|output| parse_monitor_sources(&String::from_utf8_lossy(&output.stdout)))
+        .unwrap_or_default()
+}
+
+/// Parse the long `pactl list sources` listing, keeping monitors only.
+fn parse_monitor_sources(listing: &str) -> Vec<MonitorSource> {
+    let mut sources = Vec::new();
+    let mut name: Option<String> = None;
+    for line in listing.lines().map(str::trim) {
+        if line.starts_with("Source #") {
+            name = None;
+        } else if let Some(value) = line.strip_prefix("Name: ") {
+            name = Some(value.to_string());
+        } else if let Some(description) = line.strip_prefix("Description: ") {
+            if let Some(name) = name.take().filter(|name| name.ends_with(".monitor")) {
+                sources.push(MonitorSource {
+                    name,
+                    description: description.to_string(),
+                });
+            }
+        }
+    }
+    sources
+}
+
+/// Source to capture: the configured one while it exists, else the first
+/// monitor in the short `pactl list sources` listing.
+///
+/// Without a listing (`pactl` failed) the configured source is trusted.
+fn choose_source(configured: Option<&str>, short_listing: Option<&str>) -> String {
+    let listed = |name: &str| {
+        short_listing.is_none_or(|listing| listing.lines().any(|line| line.split_whitespace().nth(1) == Some(name)))
+    };
+    match configured {
+        Some(name) if listed(name) => return name.to_string(),
+        Some(name) => eprintln!(
+            "Джерело системного звуку {} недоступне, записую монітор за замовчуванням",
+            name
+        ),
+        None => {}
+    }
+    short_listing
+        .and_then(|listing| {
+            listing
+                .lines()
+                .find(|line| line.contains(".monitor"))
+                .and_then(|line| line.split_whitespace().nth(1))
+                .map(|s| s.to_string())
+        })
+        .unwrap_or_else(|| "@DEFAULT_SOURCE@".to_string())
+}
 
 pub(crate) struct LoopbackRecorder {
     core: RecordingCore,
@@ -16,15 +89,22 @@ impl LoopbackRecorder {
         }
     }
 
-    /// Get current audio amplitude (0.0 - 1.0 range, normalized RMS)
-    pub fn get_amplitude(&self) -> f32 {
-        self.core.get_amplitude()
+    /// Get a reference to the shared samples buffer.
+    pub fn samples(&self) -> &Arc<Mutex<Vec<f32>>> {
+        &self.core.samples
+    }
+
+    /// Current peak and RMS level of the system audio.
+    pub fn level(&self) -> LevelReading {
+        self.core.level()
     }
 
     /// Start recording from PipeWire loopback (system audio monitor)
     /// Note: This is a simplified MVP implementation
     /// For production, proper PipeWire API integration is needed
-    pub fn start_loopback(&self) -> Result<()> {
+    ///
+    /// `source` names the monitor to capture; `None` takes the default one.
+    pub fn start_loopback(&self, source: Option<&str>) -> Result<()> {
         // For MVP, we'll use a fallback: try to use parec command-line tool
         // This is simpler and works on both PipeWire and PulseAudio
         // TODO: Implement proper PipeWire API integration
@@ -33,22 +113,16 @@ impl LoopbackRecorder {
 
         let samples = handles.samples;
         let is_recording_for_loop = handles.is_recording;
-        let current_amplitude = handles.current_amplitude;
+        let meter = handles.meter;
         let completion_tx = handles.completion_tx;
 
-        // Try to get default monitor source using pactl
-        let monitor_source = std::process::Command::new("pactl")
+        // Check the chosen source, or find the default monitor, using pactl
+        let short_listing = std::process::Command::new("pactl")
             .args(["list", "sources", "short"])
             .output()
             .ok()
-            .and_then(|output| {
-                String::from_utf8_lossy(&output.stdout)
-                    .lines()
-                    .find(|line| line.contains(".monitor"))
-                    .and_then(|line| line.split_whitespace().nth(1))
-                    .map(|s| s.to_string())
-            })
-            .unwrap_or_else(|| "@DEFAULT_SOURCE@".to_string());
+            .map(|output| String::from_utf8_lossy(&output.stdout).into_owned());
+        let monitor_source = choose_source(source, short_listing.as_deref());
 
         // Use parec to capture from monitor source
         // This works on both PipeWire and PulseAudio
@@ -82,9 +156,7 @@ impl LoopbackRecorder {
 
                     let f32_samples: Vec<f32> = i16_samples.iter().map(|&sample| sample as f32 / 32768.0).collect();
 
-                    // Calculate RMS amplitude
-                    let amplitude = calculate_rms(&f32_samples);
-                    current_amplitude.store(amplitude.to_bits(), Ordering::Relaxed);
+                    meter.push(&f32_samples);
 
                     // Store samples
                     samples.lock().extend(&f32_samples);
@@ -120,15 +192,15 @@ use crate::domain::traits::AudioRecording;
 
 impl AudioRecording for LoopbackRecorder {
     fn start(&self) -> Result<()> {
-        self.start_loopback()
+        self.start_loopback(None)
     }
 
     fn stop(&self) -> (Vec<f32>, Option<Receiver<()>>) {
         self.stop_loopback()
     }
 
-    fn amplitude(&self) -> f32 {
-        self.get_amplitude()
+    fn level(&self) -> LevelReading {
+        self.core.level()
     }
 
     fn is_recording(&self) -> bool {
@@ -138,6 +210,55 @@ impl AudioRecording for LoopbackRecorder {
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    const SHORT_LISTING: &str = "\
+57\talsa_output.pci-0000_00_1f.3.analog-stereo.monitor\tPipeWire\ts32le 2ch 48000Hz\tSUSPENDED
+58\talsa_input.pci-0000_00_1f.3.analog-stereo\tPipeWire\ts32le 2ch 48000Hz\tSUSPENDED
+71\tbluez_output.AA_BB.1.monitor\tPipeWire\ts16le 2ch 48000Hz\tRUNNING
+";
+
+    #[test]
+    fn test_parse_monitor_sources() {
+        let listing = "\
+Source #57
+\tState: SUSPENDED
+\tName: alsa_output.pci-0000_00_1f.3.analog-stereo.monitor
+\tDescription: Monitor of Built-in Audio Analog Stereo
+Source #58
+\tState: SUSPENDED
+\tName: alsa_input.pci-0000_00_1f.3.analog-stereo
+\tDescription: Built-in Audio Analog Stereo
+Source #71
+\tName: bluez_output.AA_BB.1.monitor
+\tDescription: Monitor of WH-1000XM4
+";
+        let sources = parse_monitor_sources(listing);
+        assert_eq!(sources.len(), 2);
+        assert_eq!(sources[0].name, "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor");
+        assert_eq!(sources[1].description, "Monitor of WH-1000XM4");
+    }
+
+    #[test]
+    fn test_choose_source_prefers_configured_one() {
+        let headset = "bluez_output.AA_BB.1.monitor";
+        assert_eq!(choose_source(Some(headset), Some(SHORT_LISTING)), headset);
+        assert_eq!(
+            choose_source(None, Some(SHORT_LISTING)),
+            "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor"
+        );
+    }
+
+    #[test]
+    fn test_choose_source_falls_back_when_configured_one_is_gone() {
+        assert_eq!(
+            choose_source(Some("usb_headset.monitor"), Some(SHORT_LISTING)),
+            "alsa_output.pci-0000_00_1f.3.analog-stereo.monitor"
+        );
+        assert_eq!(choose_source(Some("usb_headset.monitor"), None), "usb_headset.monitor");
+        assert_eq!(choose_source(None, Some("")), "@DEFAULT_SOURCE@");
+    }
+
     #[test]
     fn test_i16_conversion_even_bytes() {
         let buffer: &[u8] = &[0x00, 0x01, 0xFF, 0x7F]; // 256, 32767
@@ -0,0 +1,251 @@
+//! Level normalization for saved recordings.
+//!
+//! Archived conference recordings vary a lot in level depending on the
+//! microphone and call volume. A single gain is computed for both channels,
+//! keeping their balance, either to bring the peak to a ceiling or to reach
+//! a target integrated loudness (ITU-R BS.1770, K-weighted and gated). The
+//! ceiling always applies, so loudness normalization never clips.
+
+use crate::app::config::Config;
+
+const SAMPLE_RATE: f64 = 16000.0;
+
+/// Gating block of the loudness measurement: 400 ms, hop 100 ms.
+const BLOCK_SECS: f64 = 0.4;
+const HOP_SECS: f64 = 0.1;
+const ABSOLUTE_GATE_LUFS: f64 = -70.0;
+const RELATIVE_GATE_LU: f64 = 10.0;
+
+/// How a recording's level is adjusted before saving.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Normalization {
+    Off,
+    /// Scale so the highest peak sits at `ceiling_dbfs`.
+    Peak {
+        ceiling_dbfs: f32,
+    },
+    /// Scale to `target_lufs`, limited so peaks stay under `ceiling_dbfs`.
+    Loudness {
+        target_lufs: f32,
+        ceiling_dbfs: f32,
+    },
+}
+
+impl Normalization {
+    /// Read `recording_normalization` and its levels from the config.
+    pub fn from_config(config: &Config) -> Self {
+        match config.recording_normalization.as_str() {
+            "peak" => Self::Peak {
+                ceiling_dbfs: config.recording_peak_dbfs,
+            },
+            "lufs" => Self::Loudness {
+                target_lufs: config.recording_target_lufs,
+                ceiling_dbfs: config.recording_peak_dbfs,
+            },
+            _ => Self::Off,
+        }
+    }
+
+    /// Linear gain for a recording made of `channels`.
+    ///
+    /// Returns 1.0 when off or for silent audio.
+    pub fn gain(&self, channels: &[&[f32]]) -> f32 {
+        let peak = channels
+            .iter()
+            .flat_map(|c| c.iter())
+            .fold(0.0_f32, |max, s| max.max(s.abs()));
+        if peak <= 0.0 {
+            return 1.0;
+        }
+        let peak_gain = |ceiling_dbfs: f32| db_to_gain(ceiling_dbfs) / peak;
+
+        match *self {
+            Self::Off => 1.0,
+            Self::Peak { ceiling_dbfs } => peak_gain(ceiling_dbfs),
+            Self::Loudness {
+                target_lufs,
+                ceiling_dbfs,
+            } => match integrated_loudness(channels) {
+                Some(lufs) => db_to_gain(target_lufs - lufs as f32).min(peak_gain(ceiling_dbfs)),
+                None => 1.0,
+            },
+        }
+    }
+}
+
+fn db_to_gain(db: f32) -> f32 {
+    10.0_f32.powf(db / 20.0)
+}
+
+/// Integrated loudness of 16 kHz audio in LUFS (channels weighted equally).
+///
+/// `None` if the audio is shorter than one block or entirely below the
+/// absolute gate.
+pub fn integrated_loudness(channels: &[&[f32]]) -> Option<f64> {
+    let block = (BLOCK_SECS * SAMPLE_RATE) as usize;
+    let hop = (HOP_SECS * SAMPLE_RATE) as usize;
+    let len = channels.iter().map(|c| c.len()).max().unwrap_or(0);
+    if len < block {
+        return None;
+    }
+
+    // Per block: sum over channels of the mean square of K-weighted audio
+    let mut block_powers = vec![0.0_f64; (len - block) / hop + 1];
+    for channel in channels {
+        let weighted = k_weighted(channel);
+        for (i, power) in block_powers.iter_mut().enumerate() {
+            let start = i * hop;
+            let end = (start + block).min(weighted.len());
+            if start < end {
+                *power += weighted[start..end].iter().map(|s| s * s).sum::<f64>() / block as f64;
+            }
+        }
+    }
+
+    let loudness = |power: f64| -0.691 + 10.0 * power.log10();
+    let mean = |powers: &[f64]| powers.iter().sum::<f64>() / powers.len() as f64;
+
+    let above_absolute: Vec<f64> = block_powers
+        .into_iter()
+        .filter(|&p| p > 0.0 && loudness(p) > ABSOLUTE_GATE_LUFS)
+        .collect();
+    if above_absolute.is_empty() {
+        return None;
+    }
+    let relative_gate = loudness(mean(&above_absolute)) - RELATIVE_GATE_LU;
+    let gated: Vec<f64> = above_absolute
+        .into_iter()
+        .filter(|&p| loudness(p) > relative_gate)
+        .collect();
+    Some(loudness(mean(&gated)))
+}
+
+/// BS.1770 K-weighting: a +4 dB high shelf around 1.5 kHz followed by a
+/// 38 Hz high-pass, designed for 16 kHz.
+fn k_weighted(samples: &[f32]) -> Vec<f64> {
+    let shelf = Biquad::high_shelf(1500.0, 4.0, std::f64::consts::FRAC_1_SQRT_2);
+    let high_pass = Biquad::high_pass(38.0, 0.5);
+    let shelved = shelf.apply(samples.iter().map(|&s| s as f64));
+    high_pass.apply(shelved.into_iter())
+}
+
+/// Second-order IIR filter (RBJ audio EQ cookbook), normalized by a0.
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+}
+
+impl Biquad {
+    fn high_shelf(freq: f64, gain_db: f64, q: f64) -> Self {
+        let a = 10.0_f64.powf(gain_db / 40.0);
+        let w0 = 2.0 * std::f64::consts::PI * freq / SAMPLE_RATE;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * q);
+        let sqrt_a_alpha = 2.0 * a.sqrt() * alpha;
+
+        let a0 = (a + 1.0) - (a - 1.0) * cos + sqrt_a_alpha;
+        Self {
+            b: [
+                a * ((a + 1.0) + (a - 1.0) * cos + sqrt_a_alpha) / a0,
+                -2.0 * a * ((a - 1.0) + (a + 1.0) * cos) / a0,
+                a * ((a + 1.0) + (a - 1.0) * cos - sqrt_a_alpha) / a0,
+            ],
+            a: [
+                2.0 * ((a - 1.0) - (a + 1.0) * cos) / a0,
+                ((a + 1.0) - (a - 1.0) * cos - sqrt_a_alpha) / a0,
+            ],
+        }
+    }
+
+    fn high_pass(freq: f64, q: f64) -> Self {
+        let w0 = 2.0 * std::f64::consts::PI * freq / SAMPLE_RATE;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * q);
+
+        let a0 = 1.0 + alpha;
+        Self {
+            b: [(1.0 + cos) / 2.0 / a0, -(1.0 + cos) / a0, (1.0 + cos) / 2.0 / a0],
+            a: [-2.0 * cos / a0, (1.0 - alpha) / a0],
+        }
+    }
+
+    fn apply(&self, input: impl Iterator<Item = f64>) -> Vec<f64> {
+        let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+        input
+            .map(|x| {
+                let y = self.b[0] * x + self.b[1] * x1 + self.b[2] * x2 - self.a[0] * y1 - self.a[1] * y2;
+                (x2, x1, y2, y1) = (x1, x, y1, y);
+                y
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f64, amplitude: f32, secs: f64) -> Vec<f32> {
+        (0..(secs * SAMPLE_RATE) as usize)
+            .map(|i| amplitude * (2.0 * std::f64::consts::PI * freq * i as f64 / SAMPLE_RATE).sin() as f32)
+            .collect()
+    }
+
+    #[test]
+    fn test_integrated_loudness_of_reference_tone() {
+        // BS.1770 calibration: a full-scale 1 kHz sine in one channel is -3.01 LUFS
+        let tone = sine(1000.0, 1.0, 3.0);
+        let lufs = integrated_loudness(&[&tone]).unwrap();
+        assert!((lufs - -3.0).abs() < 0.2, "loudness {}", lufs);
+
+        let quieter: Vec<f32> = tone.iter().map(|s| s * 0.1).collect();
+        let quieter_lufs = integrated_loudness(&[&quieter]).unwrap();
+        assert!((lufs - quieter_lufs - 20.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_integrated_loudness_gates_silence() {
+        assert!(integrated_loudness(&[&vec![0.0; 32000]]).is_none());
+        assert!(integrated_loudness(&[&sine(1000.0, 0.5, 0.2)]).is_none());
+
+        // Silence around the tone is gated out; ungated, the 4 s of silence
+        // would lower the measurement by almost 5 dB. Only the blocks
+        // partly covering the tone edges still count.
+        let tone = sine(1000.0, 0.5, 2.0);
+        let mut padded = vec![0.0; 32000];
+        padded.extend(&tone);
+        padded.extend(vec![0.0; 32000]);
+        let difference = integrated_loudness(&[&tone]).unwrap() - integrated_loudness(&[&padded]).unwrap();
+        assert!(difference.abs() < 1.0, "difference {}", difference);
+    }
+
+    #[test]
+    fn test_peak_gain_reaches_ceiling() {
+        let tone = sine(440.0, 0.25, 1.0);
+        let gain = Normalization::Peak { ceiling_dbfs: -1.0 }.gain(&[&tone, &[]]);
+        assert!((gain * 0.25 - db_to_gain(-1.0)).abs() < 1e-3);
+        assert_eq!(Normalization::Off.gain(&[&tone]), 1.0);
+        assert_eq!(Normalization::Peak { ceiling_dbfs: -1.0 }.gain(&[&[0.0; 10]]), 1.0);
+    }
+
+    #[test]
+    fn test_loudness_gain_limited_by_ceiling() {
+        let tone = sine(1000.0, 0.01, 2.0);
+        let normalization = Normalization::Loudness {
+            target_lufs: -16.0,
+            ceiling_dbfs: -1.0,
+        };
+        let gain = normalization.gain(&[&tone]);
+        let normalized: Vec<f32> = tone.iter().map(|s| s * gain).collect();
+        let lufs = integrated_loudness(&[&normalized]).unwrap();
+        assert!((lufs - -16.0).abs() < 0.1, "loudness {}", lufs);
+
+        // Reaching -3 LUFS would need peaks above the ceiling
+        let loud = Normalization::Loudness {
+            target_lufs: -3.0,
+            ceiling_dbfs: -1.0,
+        };
+        let gain = loud.gain(&[&tone]);
+        assert!((gain * 0.01 - db_to_gain(-1.0)).abs() < 1e-3);
+    }
+}
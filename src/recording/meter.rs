@@ -0,0 +1,202 @@
+//! Input level meter.
+//!
+//! The capture thread pushes each block of audio; the UI polls a reading
+//! every 50 ms. Between the two the meter applies the usual ballistics:
+//! a level rises at once and falls back at a fixed rate, peak slowly so a
+//! loud syllable stays visible, RMS faster so the bar follows the voice.
+//! Full-scale samples light the clipping indicator for a moment, long
+//! enough to be seen between polls.
+
+use super::levels::{CLIP_LEVEL, MIN_CLIPPED_SAMPLES};
+use crate::domain::types::LevelReading;
+use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+
+/// How fast the peak falls back, dB per second
+const PEAK_FALL_DB_PER_SEC: f32 = 12.0;
+
+/// How fast the RMS level falls back, dB per second
+const RMS_FALL_DB_PER_SEC: f32 = 30.0;
+
+/// How long the clipping indicator stays lit
+const CLIP_HOLD: Duration = Duration::from_millis(1500);
+
+/// `clipped_us` of a meter that has not clipped
+const NEVER: u64 = u64::MAX;
+
+/// Peak and RMS level of a capture, shared between its thread and the UI.
+///
+/// Lock-free, so the audio callback can feed it: levels are f32 bits and
+/// times are microseconds since the meter was created.
+#[derive(Debug)]
+pub struct LevelMeter {
+    epoch: Instant,
+    peak_dbfs: AtomicU32,
+    rms_dbfs: AtomicU32,
+    updated_us: AtomicU64,
+    clipped_us: AtomicU64,
+}
+
+impl Default for LevelMeter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LevelMeter {
+    pub fn new() -> Self {
+        Self {
+            epoch: Instant::now(),
+            peak_dbfs: AtomicU32::new(LevelReading::FLOOR_DBFS.to_bits()),
+            rms_dbfs: AtomicU32::new(LevelReading::FLOOR_DBFS.to_bits()),
+            updated_us: AtomicU64::new(0),
+            clipped_us: AtomicU64::new(NEVER),
+        }
+    }
+
+    /// Feed a block of captured audio.
+    pub fn push(&self, samples: &[f32]) {
+        self.push_at(samples, Instant::now());
+    }
+
+    fn push_at(&self, samples: &[f32], now: Instant) {
+        if samples.is_empty() {
+            return;
+        }
+        let peak = samples.iter().fold(0.0_f32, |max, s| max.max(s.abs()));
+        let sum_squares: f32 = samples.iter().map(|s| s * s).sum();
+        let rms = (sum_squares / samples.len() as f32).sqrt();
+        let clipped = samples.iter().filter(|s| s.abs() >= CLIP_LEVEL).count() >= MIN_CLIPPED_SAMPLES;
+
+        let now_us = self.micros(now);
+        let (peak_dbfs, rms_dbfs) = self.decayed(now_us);
+        self.peak_dbfs
+            .store(peak_dbfs.max(to_dbfs(peak)).to_bits(), Ordering::Relaxed);
+        self.rms_dbfs
+            .store(rms_dbfs.max(to_dbfs(rms)).to_bits(), Ordering::Relaxed);
+        self.updated_us.store(now_us, Ordering::Relaxed);
+        if clipped {
+            self.clipped_us.store(now_us, Ordering::Relaxed);
+        }
+    }
+
+    /// The level as of now.
+    pub fn reading(&self) -> LevelReading {
+        self.reading_at(Instant::now())
+    }
+
+    fn reading_at(&self, now: Instant) -> LevelReading {
+        let now_us = self.micros(now);
+        let (peak_dbfs, rms_dbfs) = self.decayed(now_us);
+        let clipped_us = self.clipped_us.load(Ordering::Relaxed);
+        LevelReading {
+            peak_dbfs,
+            rms_dbfs,
+            clipped: clipped_us != NEVER && now_us.saturating_sub(clipped_us) < CLIP_HOLD.as_micros() as u64,
+        }
+    }
+
+    /// Drop back to silence, e.g. when recording stops.
+    pub fn reset(&self) {
+        self.peak_dbfs
+            .store(LevelReading::FLOOR_DBFS.to_bits(), Ordering::Relaxed);
+        self.rms_dbfs
+            .store(LevelReading::FLOOR_DBFS.to_bits(), Ordering::Relaxed);
+        self.clipped_us.store(NEVER, Ordering::Relaxed);
+    }
+
+    fn micros(&self, at: Instant) -> u64 {
+        at.saturating_duration_since(self.epoch).as_micros() as u64
+    }
+
+    /// Peak and RMS after falling back from the last push until `now_us`.
+    fn decayed(&self, now_us: u64) -> (f32, f32) {
+        let secs = now_us.saturating_sub(self.updated_us.load(Ordering::Relaxed)) as f32 / 1e6;
+        let peak_dbfs = f32::from_bits(self.peak_dbfs.load(Ordering::Relaxed));
+        let rms_dbfs = f32::from_bits(self.rms_dbfs.load(Ordering::Relaxed));
+        (
+            (peak_dbfs - PEAK_FALL_DB_PER_SEC * secs).max(LevelReading::FLOOR_DBFS),
+            (rms_dbfs - RMS_FALL_DB_PER_SEC * secs).max(LevelReading::FLOOR_DBFS),
+        )
+    }
+}
+
+fn to_dbfs(amplitude: f32) -> f32 {
+    (20.0 * amplitude.max(1e-6).log10()).max(LevelReading::FLOOR_DBFS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(amplitude: f32, len: usize) -> Vec<f32> {
+        (0..len)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * 300.0 * i as f32 / 16000.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn test_new_meter_reads_silence() {
+        assert_eq!(LevelMeter::new().reading(), LevelReading::SILENT);
+    }
+
+    #[test]
+    fn test_sine_peak_and_rms() {
+        let meter = LevelMeter::new();
+        let now = Instant::now();
+        meter.push_at(&tone(0.5, 1600), now);
+        let reading = meter.reading_at(now);
+        // 0.5 peak is -6 dBFS, its RMS 3 dB lower
+        assert!((reading.peak_dbfs + 6.0).abs() < 0.1, "{}", reading.peak_dbfs);
+        assert!((reading.rms_dbfs + 9.0).abs() < 0.1, "{}", reading.rms_dbfs);
+        assert!(!reading.clipped);
+    }
+
+    #[test]
+    fn test_levels_fall_back_gradually() {
+        let meter = LevelMeter::new();
+        let start = Instant::now();
+        meter.push_at(&tone(0.5, 1600), start);
+
+        let later = meter.reading_at(start + Duration::from_millis(500));
+        assert!((later.peak_dbfs + 12.0).abs() < 0.1, "{}", later.peak_dbfs);
+        assert!((later.rms_dbfs + 24.0).abs() < 0.1, "{}", later.rms_dbfs);
+
+        // Quieter audio does not pull the peak below where it has fallen to
+        meter.push_at(&tone(0.01, 1600), start + Duration::from_millis(500));
+        let reading = meter.reading_at(start + Duration::from_millis(500));
+        assert!((reading.peak_dbfs + 12.0).abs() < 0.1, "{}", reading.peak_dbfs);
+
+        let much_later = meter.reading_at(start + Duration::from_secs(10));
+        assert_eq!(much_later.peak_dbfs, LevelReading::FLOOR_DBFS);
+        assert_eq!(much_later.rms_dbfs, LevelReading::FLOOR_DBFS);
+    }
+
+    #[test]
+    fn test_clipping_is_held_then_clears() {
+        let meter = LevelMeter::new();
+        let start = Instant::now();
+        let clipped: Vec<f32> = tone(3.0, 1600).iter().map(|s| s.clamp(-1.0, 1.0)).collect();
+        meter.push_at(&clipped, start);
+        assert!(meter.reading_at(start + Duration::from_secs(1)).clipped);
+        assert!(!meter.reading_at(start + Duration::from_secs(2)).clipped);
+    }
+
+    #[test]
+    fn test_single_over_does_not_clip() {
+        let meter = LevelMeter::new();
+        let mut audio = tone(0.2, 1600);
+        audio[100] = 1.0;
+        meter.push(&audio);
+        assert!(!meter.reading().clipped);
+    }
+
+    #[test]
+    fn test_reset_returns_to_silence() {
+        let meter = LevelMeter::new();
+        meter.push(&[1.0; 1600]);
+        assert!(meter.reading().clipped);
+        meter.reset();
+        assert_eq!(meter.reading(), LevelReading::SILENT);
+    }
+}
@@ -1,34 +1,25 @@
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use async_channel::Receiver;
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use cpal::SupportedStreamConfig;
 use parking_lot::Mutex;
-use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolationType, WindowFunction};
-use std::sync::atomic::Ordering;
+use std::borrow::Cow;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::thread;
-use std::time::Duration;
-
-use super::core::{calculate_rms, RecordingCore, WHISPER_SAMPLE_RATE};
-
-/// Create a high-quality sinc resampler for converting to 16kHz.
-fn create_resampler(sample_rate: u32) -> Result<SincFixedIn<f32>> {
-    let resample_ratio = WHISPER_SAMPLE_RATE as f64 / sample_rate as f64;
-    let params = SincInterpolationParameters {
-        sinc_len: 256,
-        f_cutoff: 0.95,
-        interpolation: SincInterpolationType::Linear,
-        oversampling_factor: 256,
-        window: WindowFunction::BlackmanHarris2,
-    };
-    SincFixedIn::<f32>::new(
-        resample_ratio,
-        2.0, // max relative ratio (safety margin)
-        params,
-        1024, // chunk size
-        1,    // mono channel
-    )
-    .context("Не вдалося створити ресемплер")
-}
+use std::time::{Duration, Instant};
+
+use super::core::{RecordingCore, WHISPER_SAMPLE_RATE};
+use super::filter::HighPassFilter;
+use super::gain::{AutoGain, GainSettings};
+use super::levels::LevelMonitor;
+use super::meter::LevelMeter;
+use super::monitor;
+use super::resample::{DeviceResampler, ResamplerQuality};
+use super::ring_buffer::RingBuffer;
+use crate::app::config::Config;
+use crate::domain::traits::GainControl;
+use crate::domain::types::{AudioQuality, CaptureDeviceStatus, LevelReading, LevelWarning};
 
 /// Convert multi-channel audio to mono into a pre-allocated buffer (zero allocation).
 ///
@@ -47,176 +38,563 @@ fn to_mono_into(data: &[f32], channels: usize, mono_buf: &mut [f32]) -> usize {
     }
 }
 
-pub(crate) struct AudioRecorder {
-    core: RecordingCore,
+/// Processing of the resampled microphone audio, applied in field order.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CaptureProcessing {
+    /// High-pass cutoff in Hz; `None` keeps rumble and DC offset
+    pub high_pass_hz: Option<f32>,
+    /// AGC; `None` keeps the microphone level
+    pub gain: Option<GainSettings>,
+    /// Conversion of the device rate to 16 kHz, before the stages above
+    pub resampler: ResamplerQuality,
+    /// Volume of the listen-through while recording, of the audio as the
+    /// microphone delivers it; `None` plays nothing
+    pub monitor_volume: Option<f32>,
 }
 
-impl AudioRecorder {
-    pub fn new() -> Self {
+impl CaptureProcessing {
+    pub fn from_config(config: &Config) -> Self {
         Self {
-            core: RecordingCore::new(),
+            high_pass_hz: config.high_pass_enabled.then_some(config.high_pass_cutoff_hz),
+            gain: GainSettings::from_config(config),
+            resampler: ResamplerQuality::parse(&config.resampler_quality),
+            monitor_volume: config.monitor_enabled.then_some(config.monitor_volume),
         }
     }
+}
 
-    /// Get a reference to the shared samples buffer.
-    /// Used by ContinuousRecorder to read accumulated samples.
-    pub fn samples(&self) -> &Arc<Mutex<Vec<f32>>> {
-        &self.core.samples
+/// Level check, filter and AGC state of one recording.
+struct CaptureStages {
+    levels: Arc<Mutex<LevelMonitor>>,
+    high_pass: Option<HighPassFilter>,
+    gain: Option<Box<dyn GainControl>>,
+}
+
+impl CaptureStages {
+    fn new(processing: CaptureProcessing, levels: Arc<Mutex<LevelMonitor>>) -> Self {
+        Self {
+            levels,
+            high_pass: processing.high_pass_hz.map(HighPassFilter::new),
+            gain: processing
+                .gain
+                .map(|settings| Box::new(AutoGain::new(settings)) as Box<dyn GainControl>),
+        }
     }
 
-    /// Get current audio amplitude (0.0 - 1.0 range, normalized RMS)
-    pub fn get_amplitude(&self) -> f32 {
-        self.core.get_amplitude()
+    /// Resampled audio through the enabled stages into the recording, or
+    /// between recordings into the pre-roll.
+    ///
+    /// Levels are checked on the audio as the microphone delivers it, so AGC
+    /// cannot hide a clipping or far too quiet input.
+    fn store(&mut self, target: &CaptureTarget, block: &[f32]) {
+        // Deciding under the samples lock lets a start take the pre-roll without a gap
+        let mut samples = target.samples.lock();
+        let recording = target.is_recording.load(Ordering::SeqCst);
+        if !recording && target.pre_roll.is_none() {
+            return;
+        }
+        if recording {
+            self.levels.lock().push(block);
+        }
+
+        let mut block = Cow::Borrowed(block);
+        if let Some(filter) = &mut self.high_pass {
+            block = Cow::Owned(filter.process(&block));
+        }
+        if let Some(gain) = &self.gain {
+            block = Cow::Owned(gain.process(&block));
+        }
+
+        match &target.pre_roll {
+            Some(pre_roll) if !recording => pre_roll.write(&block),
+            _ => samples.extend_from_slice(&block),
+        }
     }
+}
 
-    pub fn start_recording(&self) -> Result<()> {
-        let host = cpal::default_host();
-        let device = host.default_input_device().context("Не знайдено мікрофон")?;
+/// Where captured audio goes and how long capture runs.
+#[derive(Clone)]
+struct CaptureTarget {
+    samples: Arc<Mutex<Vec<f32>>>,
+    is_recording: Arc<AtomicBool>,
+    meter: Arc<LevelMeter>,
+    /// Capture runs while set: the recording flag itself, or the standby's
+    running: Arc<AtomicBool>,
+    /// Last seconds before a recording, filled while not recording
+    pre_roll: Option<Arc<RingBuffer>>,
+}
 
-        let config = device.default_input_config()?;
-        let sample_rate = config.sample_rate().0;
-        let channels = config.channels() as usize;
+/// Capture kept running between recordings to fill the pre-roll.
+struct Standby {
+    running: Arc<AtomicBool>,
+    pre_roll: Arc<RingBuffer>,
+}
 
-        let handles = self.core.prepare_recording();
+/// A stream that has not called back for this long has lost its device
+const STALL_TIMEOUT: Duration = Duration::from_secs(2);
 
-        let samples = handles.samples;
-        let is_recording = handles.is_recording.clone();
-        let is_recording_for_consumer = handles.is_recording.clone();
-        let is_recording_for_loop = handles.is_recording;
-        let current_amplitude = handles.current_amplitude;
-        let completion_tx = handles.completion_tx;
+/// How often a replacement device is looked for while capture is paused
+const RECONNECT_INTERVAL: Duration = Duration::from_millis(500);
 
-        // Lock-free SPSC ring buffer: CPAL callback (producer) → consumer thread.
-        // 10 seconds of mono audio at native sample rate provides ample headroom.
-        let ring_capacity = sample_rate as usize * 10;
-        let (mut producer, mut consumer) = rtrb::RingBuffer::new(ring_capacity);
+/// Signs of life of one input stream, updated from its callbacks.
+#[derive(Default)]
+struct DeviceWatch {
+    callbacks: AtomicU64,
+    disconnected: AtomicBool,
+}
 
-        // Pre-allocate mono conversion buffer for the CPAL callback.
-        // Sized for the largest expected callback frame (typical: 256–4096 samples).
-        let max_callback_mono = 8192;
-        let mut mono_buf = vec![0.0f32; max_callback_mono];
+impl DeviceWatch {
+    fn heard(&self) {
+        self.callbacks.fetch_add(1, Ordering::Relaxed);
+    }
 
-        thread::spawn(move || {
-            // --- Consumer thread: reads from ring buffer, resamples, stores ---
-            let consumer_handle = thread::spawn(move || {
-                let mut resampler = match create_resampler(sample_rate) {
-                    Ok(r) => r,
-                    Err(e) => {
-                        eprintln!("Не вдалося створити ресемплер: {}", e);
-                        return;
-                    }
-                };
+    fn disconnect(&self) {
+        self.disconnected.store(true, Ordering::SeqCst);
+    }
 
-                let input_frames = resampler.input_frames_next();
-                let mut chunk_buf = vec![0.0f32; input_frames];
-                let mut chunk_pos = 0usize;
+    fn has_called_back(&self) -> bool {
+        self.callbacks.load(Ordering::Relaxed) > 0
+    }
+}
 
-                while is_recording_for_consumer.load(Ordering::SeqCst) {
-                    let available = consumer.slots();
-                    if available == 0 {
-                        thread::sleep(Duration::from_millis(5));
-                        continue;
-                    }
+/// Polls a `DeviceWatch` for a device that went away.
+///
+/// Not every backend reports an unplugged device as an error; some just
+/// stop calling back, so a stream that goes quiet counts as lost too.
+struct StallCheck {
+    seen: u64,
+    since: Instant,
+}
+
+impl StallCheck {
+    fn new(now: Instant) -> Self {
+        Self { seen: 0, since: now }
+    }
+
+    fn device_lost(&mut self, watch: &DeviceWatch, now: Instant) -> bool {
+        if watch.disconnected.load(Ordering::SeqCst) {
+            return true;
+        }
+        let count = watch.callbacks.load(Ordering::Relaxed);
+        if count != self.seen {
+            self.seen = count;
+            self.since = now;
+            return false;
+        }
+        now.duration_since(self.since) >= STALL_TIMEOUT
+    }
+}
+
+/// How capture from one device ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StreamEnd {
+    /// The recording was stopped
+    Stopped,
+    /// The device disappeared mid-recording
+    DeviceLost,
+    /// The stream could not be opened
+    Failed,
+}
 
-                    if let Ok(read_chunk) = consumer.read_chunk(available.min(4096)) {
-                        let (first, second) = read_chunk.as_slices();
-
-                        for slice in [first, second] {
-                            for &sample in slice {
-                                chunk_buf[chunk_pos] = sample;
-                                chunk_pos += 1;
-
-                                if chunk_pos == input_frames {
-                                    let input = vec![std::mem::take(&mut chunk_buf)];
-                                    if let Ok(output) = resampler.process(&input, None) {
-                                        samples.lock().extend(&output[0]);
-                                    }
-                                    chunk_buf = input.into_iter().next().unwrap();
-                                    let next_frames = resampler.input_frames_next();
-                                    chunk_buf.resize(next_frames, 0.0);
-                                    chunk_pos = 0;
-                                }
+fn device_name(device: &cpal::Device) -> String {
+    device.name().unwrap_or_else(|_| "невідомий пристрій".to_string())
+}
+
+/// Wait for a default input device while recording, `None` once stopped.
+fn wait_for_device(is_recording: &AtomicBool) -> Option<(cpal::Device, SupportedStreamConfig)> {
+    while is_recording.load(Ordering::SeqCst) {
+        if let Some(device) = cpal::default_host().default_input_device() {
+            if let Ok(config) = device.default_input_config() {
+                return Some((device, config));
+            }
+        }
+        thread::sleep(RECONNECT_INTERVAL);
+    }
+    None
+}
+
+/// Capture from `device` until the recording stops or the device is lost.
+///
+/// `replacement` is set when the device stands in for a lost one; the
+/// status switches to it once it actually delivers audio.
+fn capture_from(
+    device: &cpal::Device,
+    config: SupportedStreamConfig,
+    target: &CaptureTarget,
+    mut stages: CaptureStages,
+    processing: CaptureProcessing,
+    device_status: &Mutex<CaptureDeviceStatus>,
+    replacement: Option<String>,
+) -> (CaptureStages, StreamEnd) {
+    let sample_rate = config.sample_rate().0;
+    let channels = config.channels() as usize;
+
+    let target_for_consumer = target.clone();
+    let is_recording = target.is_recording.clone();
+    let running = target.running.clone();
+    let meter = target.meter.clone();
+
+    // Runs the consumer for this device only; a replacement gets its own
+    let streaming = Arc::new(AtomicBool::new(true));
+    let streaming_for_consumer = streaming.clone();
+    let watch = Arc::new(DeviceWatch::default());
+    let watch_for_callback = watch.clone();
+    let watch_for_errors = watch.clone();
+
+    // Lock-free SPSC ring buffer: CPAL callback (producer) → consumer thread.
+    // 10 seconds of mono audio at native sample rate provides ample headroom.
+    let ring_capacity = sample_rate as usize * 10;
+    let (mut producer, mut consumer) = rtrb::RingBuffer::new(ring_capacity);
+
+    // Pre-allocate mono conversion buffer for the CPAL callback.
+    // Sized for the largest expected callback frame (typical: 256–4096 samples).
+    let max_callback_mono = 8192;
+    let mut mono_buf = vec![0.0f32; max_callback_mono];
+
+    // Listen-through at the device rate, so it adds no resampling delay;
+    // the output stream lives as long as this device's input stream
+    let (mut monitor_feed, _monitor_stream) = match processing.monitor_volume.map(|v| monitor::open(sample_rate, v)) {
+        Some(Ok((feed, stream))) => (Some(feed), Some(stream)),
+        Some(Err(e)) => {
+            eprintln!("Не вдалося увімкнути прослуховування: {:#}", e);
+            (None, None)
+        }
+        None => (None, None),
+    };
+
+    // --- Consumer thread: reads from ring buffer, resamples, stores ---
+    let consumer_handle = thread::spawn(move || {
+        let mut resampler = match DeviceResampler::new(sample_rate, processing.resampler) {
+            Ok(r) => r,
+            Err(e) => {
+                eprintln!("Не вдалося створити ресемплер: {}", e);
+                return stages;
+            }
+        };
+
+        let input_frames = resampler.input_frames_next();
+        let mut chunk_buf = vec![0.0f32; input_frames];
+        let mut chunk_pos = 0usize;
+
+        while streaming_for_consumer.load(Ordering::SeqCst) {
+            let available = consumer.slots();
+            if available == 0 {
+                thread::sleep(Duration::from_millis(5));
+                continue;
+            }
+
+            if let Ok(read_chunk) = consumer.read_chunk(available.min(4096)) {
+                let (first, second) = read_chunk.as_slices();
+
+                for slice in [first, second] {
+                    for &sample in slice {
+                        chunk_buf[chunk_pos] = sample;
+                        chunk_pos += 1;
+
+                        if chunk_pos == chunk_buf.len() {
+                            if let Ok(output) = resampler.process(&chunk_buf) {
+                                stages.store(&target_for_consumer, &output);
                             }
+                            chunk_buf.resize(resampler.input_frames_next(), 0.0);
+                            chunk_pos = 0;
                         }
-
-                        read_chunk.commit_all();
                     }
                 }
 
-                // Flush remaining partial chunk
-                if chunk_pos > 0 {
-                    let input_len = chunk_buf.len();
-                    chunk_buf[chunk_pos..input_len].fill(0.0);
-                    let input = vec![chunk_buf];
-                    if let Ok(output) = resampler.process(&input, None) {
-                        let output_len =
-                            (chunk_pos as f64 * resampler.output_frames_next() as f64 / input_len as f64) as usize;
-                        samples.lock().extend(&output[0][..output_len.min(output[0].len())]);
-                    }
+                read_chunk.commit_all();
+            }
+        }
+
+        // Flush remaining partial chunk
+        if chunk_pos > 0 {
+            let input_len = chunk_buf.len();
+            chunk_buf[chunk_pos..input_len].fill(0.0);
+            let output_frames = resampler.output_frames_next();
+            if let Ok(output) = resampler.process(&chunk_buf) {
+                let output_len = (chunk_pos as f64 * output_frames as f64 / input_len as f64) as usize;
+                stages.store(&target_for_consumer, &output[..output_len.min(output.len())]);
+            }
+        }
+        stages
+    });
+
+    let finish = |end: StreamEnd| {
+        streaming.store(false, Ordering::SeqCst);
+        let stages = consumer_handle.join().expect("consumer thread panicked");
+        (stages, end)
+    };
+
+    // --- CPAL audio callback: real-time safe (no locks, no allocations) ---
+    let stream = match device.build_input_stream(
+        &config.into(),
+        move |data: &[f32], _: &cpal::InputCallbackInfo| {
+            watch_for_callback.heard();
+            if !running.load(Ordering::SeqCst) {
+                return;
+            }
+
+            // Convert to mono in pre-allocated buffer (zero allocation)
+            let mono_len = to_mono_into(data, channels, &mut mono_buf);
+            let mono = &mono_buf[..mono_len];
+
+            // Feed the level meter for the UI (atomic, lock-free) and the
+            // listen-through (lock-free ring buffer)
+            if is_recording.load(Ordering::Relaxed) {
+                meter.push(mono);
+                if let Some(feed) = &mut monitor_feed {
+                    feed.push(mono);
                 }
-            });
-
-            // --- CPAL audio callback: real-time safe (no locks, no allocations) ---
-            let stream = match device.build_input_stream(
-                &config.into(),
-                move |data: &[f32], _: &cpal::InputCallbackInfo| {
-                    if !is_recording.load(Ordering::SeqCst) {
-                        return;
-                    }
+            }
 
-                    // Convert to mono in pre-allocated buffer (zero allocation)
-                    let mono_len = to_mono_into(data, channels, &mut mono_buf);
-                    let mono = &mono_buf[..mono_len];
-
-                    // Update amplitude for UI visualization (atomic, lock-free)
-                    let amplitude = calculate_rms(mono);
-                    current_amplitude.store(amplitude.to_bits(), Ordering::Relaxed);
-
-                    // Write to lock-free SPSC ring buffer (non-blocking)
-                    if let Ok(mut write_chunk) = producer.write_chunk(mono_len) {
-                        let (first, second) = write_chunk.as_mut_slices();
-                        let first_len = first.len();
-                        first.copy_from_slice(&mono[..first_len]);
-                        if !second.is_empty() {
-                            second.copy_from_slice(&mono[first_len..]);
-                        }
-                        write_chunk.commit_all();
-                    }
-                    // If ring buffer is full, samples are dropped (preferable to blocking)
-                },
-                |err| eprintln!("Помилка запису: {}", err),
-                None,
-            ) {
-                Ok(s) => s,
-                Err(e) => {
-                    eprintln!("Не вдалося створити аудіопотік: {}", e);
-                    is_recording_for_loop.store(false, Ordering::SeqCst);
-                    let _ = consumer_handle.join();
-                    let _ = completion_tx.send_blocking(());
-                    return;
+            // Write to lock-free SPSC ring buffer (non-blocking)
+            if let Ok(mut write_chunk) = producer.write_chunk(mono_len) {
+                let (first, second) = write_chunk.as_mut_slices();
+                let first_len = first.len();
+                first.copy_from_slice(&mono[..first_len]);
+                if !second.is_empty() {
+                    second.copy_from_slice(&mono[first_len..]);
                 }
-            };
+                write_chunk.commit_all();
+            }
+            // If ring buffer is full, samples are dropped (preferable to blocking)
+        },
+        move |err| {
+            eprintln!("Помилка запису: {}", err);
+            if matches!(err, cpal::StreamError::DeviceNotAvailable) {
+                watch_for_errors.disconnect();
+            }
+        },
+        None,
+    ) {
+        Ok(s) => s,
+        Err(e) => {
+            eprintln!("Не вдалося створити аудіопотік: {}", e);
+            return finish(StreamEnd::Failed);
+        }
+    };
 
-            if let Err(e) = stream.play() {
-                eprintln!("Не вдалося запустити аудіопотік: {}", e);
-                is_recording_for_loop.store(false, Ordering::SeqCst);
-                let _ = consumer_handle.join();
-                let _ = completion_tx.send_blocking(());
-                return;
+    if let Err(e) = stream.play() {
+        eprintln!("Не вдалося запустити аудіопотік: {}", e);
+        return finish(StreamEnd::Failed);
+    }
+
+    let mut stall = StallCheck::new(Instant::now());
+    let mut replacement = replacement;
+    let mut end = StreamEnd::Stopped;
+    while target.running.load(Ordering::SeqCst) {
+        thread::sleep(Duration::from_millis(100));
+        if stall.device_lost(&watch, Instant::now()) {
+            end = StreamEnd::DeviceLost;
+            break;
+        }
+        if watch.has_called_back() {
+            if let Some(name) = replacement.take() {
+                eprintln!("Запис продовжено з пристрою «{}»", name);
+                *device_status.lock() = CaptureDeviceStatus::Switched(name);
             }
+        }
+    }
+
+    // Drop the stream first so no more callbacks fire,
+    // then let the consumer drain the remaining ring buffer samples
+    drop(stream);
+    finish(end)
+}
+
+/// Capture from `device` and its replacements until `target` stops running.
+fn run_capture(
+    device: cpal::Device,
+    config: SupportedStreamConfig,
+    target: CaptureTarget,
+    mut stages: CaptureStages,
+    processing: CaptureProcessing,
+    device_status: &Mutex<CaptureDeviceStatus>,
+) {
+    let mut input = Some((device, config));
+    let mut replacement = None;
+    while let Some((device, config)) = input.take() {
+        let (next_stages, end) = capture_from(
+            &device,
+            config,
+            &target,
+            stages,
+            processing,
+            device_status,
+            replacement.take(),
+        );
+        stages = next_stages;
+        // A device that cannot be opened at the start ends the capture;
+        // a replacement that cannot be opened is waited out like a lost one
+        let first_device = matches!(*device_status.lock(), CaptureDeviceStatus::Active);
+        if end == StreamEnd::Failed && first_device {
+            target.running.store(false, Ordering::SeqCst);
+            break;
+        }
+        if end == StreamEnd::Stopped {
+            break;
+        }
+        if end == StreamEnd::Failed {
+            thread::sleep(RECONNECT_INTERVAL);
+        }
+
+        eprintln!("Мікрофон «{}» недоступний, запис призупинено", device_name(&device));
+        *device_status.lock() = CaptureDeviceStatus::Lost;
+        target.meter.reset();
+        input = wait_for_device(&target.running);
+        replacement = input.as_ref().map(|(device, _)| device_name(device));
+    }
+}
+
+pub(crate) struct AudioRecorder {
+    core: RecordingCore,
+    processing: CaptureProcessing,
+    device_status: Arc<Mutex<CaptureDeviceStatus>>,
+    levels: Arc<Mutex<LevelMonitor>>,
+    standby: Mutex<Option<Standby>>,
+}
+
+impl AudioRecorder {
+    pub fn new() -> Self {
+        Self::with_processing(CaptureProcessing::default())
+    }
+
+    pub fn with_processing(processing: CaptureProcessing) -> Self {
+        Self {
+            core: RecordingCore::new(),
+            processing,
+            device_status: Arc::new(Mutex::new(CaptureDeviceStatus::Active)),
+            levels: Arc::new(Mutex::new(LevelMonitor::new())),
+            standby: Mutex::new(None),
+        }
+    }
+
+    /// Get a reference to the shared samples buffer.
+    /// Used by ContinuousRecorder to read accumulated samples.
+    pub fn samples(&self) -> &Arc<Mutex<Vec<f32>>> {
+        &self.core.samples
+    }
+
+    /// Current peak and RMS level of the microphone.
+    pub fn level(&self) -> LevelReading {
+        self.core.level()
+    }
+
+    /// Whether the microphone is still there, or which one replaced it.
+    pub fn device_status(&self) -> CaptureDeviceStatus {
+        self.device_status.lock().clone()
+    }
+
+    /// Clipping or a too quiet input in the last few seconds.
+    pub fn level_warning(&self) -> Option<LevelWarning> {
+        self.levels.lock().warning()
+    }
+
+    /// Level problems of the current or last recording.
+    pub fn audio_quality(&self) -> Option<AudioQuality> {
+        self.levels.lock().quality()
+    }
+
+    fn default_input() -> Result<(cpal::Device, SupportedStreamConfig)> {
+        let host = cpal::default_host();
+        let device = host.default_input_device().context("Не знайдено мікрофон")?;
+        let config = device.default_input_config()?;
+        Ok((device, config))
+    }
+
+    /// Keep the microphone open between recordings, holding its last
+    /// `pre_roll_secs`, which every recording then starts with.
+    ///
+    /// Catches the start of a sentence begun before the hotkey. The
+    /// microphone stays in use (and shown so by the desktop) while the app
+    /// runs. Does nothing if the standby is already running.
+    pub fn start_standby(&self, pre_roll_secs: f32) -> Result<()> {
+        let mut standby = self.standby.lock();
+        if standby.as_ref().is_some_and(|s| s.running.load(Ordering::SeqCst)) {
+            return Ok(());
+        }
+        if pre_roll_secs <= 0.0 {
+            bail!("Тривалість попереднього запису має бути більшою за нуль");
+        }
+        let (device, config) = Self::default_input()?;
+
+        let running = Arc::new(AtomicBool::new(true));
+        let pre_roll = Arc::new(RingBuffer::new((pre_roll_secs * WHISPER_SAMPLE_RATE as f32) as usize));
+        let target = CaptureTarget {
+            samples: self.core.samples.clone(),
+            is_recording: self.core.is_recording.clone(),
+            meter: self.core.meter.clone(),
+            running: running.clone(),
+            pre_roll: Some(pre_roll.clone()),
+        };
+        *self.device_status.lock() = CaptureDeviceStatus::Active;
+        let device_status = self.device_status.clone();
+        let processing = self.processing;
+        let stages = CaptureStages::new(processing, self.levels.clone());
+
+        thread::spawn(move || {
+            run_capture(device, config, target, stages, processing, &device_status);
+        });
+
+        *standby = Some(Standby { running, pre_roll });
+        Ok(())
+    }
+
+    /// Stop the capture started by `start_standby`.
+    pub fn stop_standby(&self) {
+        if let Some(standby) = self.standby.lock().take() {
+            standby.running.store(false, Ordering::SeqCst);
+        }
+    }
 
-            while is_recording_for_loop.load(Ordering::SeqCst) {
-                thread::sleep(Duration::from_millis(100));
+    /// Start capturing from the default input device.
+    ///
+    /// If the device disappears mid-recording (e.g. a Bluetooth headset
+    /// runs out of battery), capture moves to whatever the default input
+    /// is then, or pauses until one appears. What was recorded is kept.
+    ///
+    /// With the standby running, the recording continues its capture and
+    /// starts with the pre-roll.
+    pub fn start_recording(&self) -> Result<()> {
+        if let Some(standby) = self.standby.lock().as_ref() {
+            if standby.running.load(Ordering::SeqCst) {
+                *self.levels.lock() = LevelMonitor::new();
+                let mut status = self.device_status.lock();
+                if matches!(*status, CaptureDeviceStatus::Switched(_)) {
+                    // The replacement is simply the microphone of this recording
+                    *status = CaptureDeviceStatus::Active;
+                }
+                drop(status);
+                // Nothing to wait for at stop: the capture goes on, so the
+                // completion sender is dropped here
+                self.core.prepare_recording_after(&standby.pre_roll);
+                return Ok(());
             }
+        }
 
-            // Drop the stream first so no more callbacks fire
-            drop(stream);
+        let (device, config) = Self::default_input()?;
 
-            // Wait for consumer thread to drain remaining ring buffer samples
-            let _ = consumer_handle.join();
+        let handles = self.core.prepare_recording();
+        *self.device_status.lock() = CaptureDeviceStatus::Active;
+        let device_status = self.device_status.clone();
+
+        // Fresh state for every recording, kept across device switches
+        let processing = self.processing;
+        *self.levels.lock() = LevelMonitor::new();
+        let stages = CaptureStages::new(processing, self.levels.clone());
+
+        let target = CaptureTarget {
+            samples: handles.samples.clone(),
+            is_recording: handles.is_recording.clone(),
+            meter: handles.meter.clone(),
+            running: handles.is_recording.clone(),
+            pre_roll: None,
+        };
+
+        thread::spawn(move || {
+            run_capture(device, config, target, stages, processing, &device_status);
 
             // Signal completion
-            let _ = completion_tx.send_blocking(());
+            let _ = handles.completion_tx.send_blocking(());
         });
 
         Ok(())
@@ -227,6 +605,12 @@ impl AudioRecorder {
     }
 }
 
+impl Drop for AudioRecorder {
+    fn drop(&mut self) {
+        self.stop_standby();
+    }
+}
+
 impl Default for AudioRecorder {
     fn default() -> Self {
         Self::new()
@@ -246,13 +630,29 @@ impl AudioRecording for AudioRecorder {
         self.stop_recording()
     }
 
-    fn amplitude(&self) -> f32 {
-        self.get_amplitude()
+    fn level(&self) -> LevelReading {
+        self.core.level()
     }
 
     fn is_recording(&self) -> bool {
         self.core.is_recording()
     }
+
+    fn device_status(&self) -> CaptureDeviceStatus {
+        AudioRecorder::device_status(self)
+    }
+
+    fn level_warning(&self) -> Option<LevelWarning> {
+        AudioRecorder::level_warning(self)
+    }
+
+    fn audio_quality(&self) -> Option<AudioQuality> {
+        AudioRecorder::audio_quality(self)
+    }
+
+    fn start_pre_roll(&self, secs: f32) -> Result<()> {
+        self.start_standby(secs)
+    }
 }
 
 #[cfg(test)]
@@ -281,9 +681,9 @@ mod tests {
     }
 
     #[test]
-    fn test_audio_recorder_amplitude_initially_zero() {
+    fn test_audio_recorder_level_initially_silent() {
         let recorder = AudioRecorder::new();
-        assert_eq!(recorder.get_amplitude(), 0.0);
+        assert_eq!(recorder.level(), LevelReading::SILENT);
     }
 
     #[test]
@@ -293,10 +693,10 @@ mod tests {
     }
 
     #[test]
-    fn test_audio_recording_trait_amplitude() {
+    fn test_audio_recording_trait_level() {
         use crate::domain::traits::AudioRecording;
         let recorder = AudioRecorder::new();
-        assert_eq!(AudioRecording::amplitude(&recorder), recorder.get_amplitude());
+        assert_eq!(AudioRecording::level(&recorder), recorder.level());
     }
 
     #[test]
@@ -307,14 +707,120 @@ mod tests {
     }
 
     #[test]
-    fn test_stop_resets_amplitude() {
+    fn test_stop_resets_level() {
         let recorder = AudioRecorder::new();
         let handles = recorder.core.prepare_recording();
-        handles.current_amplitude.store(0.5_f32.to_bits(), Ordering::Relaxed);
-        assert!(recorder.get_amplitude() > 0.0);
+        handles.meter.push(&[0.5; 1600]);
+        assert!(recorder.level().peak_dbfs > LevelReading::FLOOR_DBFS);
 
         recorder.stop_recording();
-        assert_eq!(recorder.get_amplitude(), 0.0);
+        assert_eq!(recorder.level(), LevelReading::SILENT);
+    }
+
+    fn recording_target(recording: bool, pre_roll: Option<Arc<RingBuffer>>) -> CaptureTarget {
+        CaptureTarget {
+            samples: Arc::new(Mutex::new(Vec::new())),
+            is_recording: Arc::new(AtomicBool::new(recording)),
+            meter: Arc::new(LevelMeter::new()),
+            running: Arc::new(AtomicBool::new(true)),
+            pre_roll,
+        }
+    }
+
+    #[test]
+    fn test_capture_stages_fill_pre_roll_between_recordings() {
+        let levels = Arc::new(Mutex::new(LevelMonitor::new()));
+        let mut stages = CaptureStages::new(CaptureProcessing::default(), levels.clone());
+        let pre_roll = Arc::new(RingBuffer::new(3200));
+        let target = recording_target(false, Some(pre_roll.clone()));
+
+        for block in 0..4 {
+            stages.store(&target, &[block as f32 * 0.1; 1600]);
+        }
+        assert!(target.samples.lock().is_empty());
+        // Only the last 200 ms are kept, and they do not count as the recording's levels
+        let kept = pre_roll.read_all();
+        assert_eq!(kept.len(), 3200);
+        assert!((kept[0] - 0.2).abs() < 1e-6);
+        assert_eq!(levels.lock().quality(), None);
+
+        target.is_recording.store(true, Ordering::SeqCst);
+        stages.store(&target, &[0.5; 1600]);
+        assert_eq!(target.samples.lock().len(), 1600);
+    }
+
+    #[test]
+    fn test_capture_stages_drop_audio_when_not_recording_without_pre_roll() {
+        let levels = Arc::new(Mutex::new(LevelMonitor::new()));
+        let mut stages = CaptureStages::new(CaptureProcessing::default(), levels);
+        let target = recording_target(false, None);
+        stages.store(&target, &[0.5; 1600]);
+        assert!(target.samples.lock().is_empty());
+    }
+
+    #[test]
+    fn test_standby_needs_a_pre_roll_length() {
+        let recorder = AudioRecorder::new();
+        assert!(recorder.start_standby(0.0).is_err());
+    }
+
+    #[test]
+    fn test_capture_stages_check_levels_before_agc() {
+        let levels = Arc::new(Mutex::new(LevelMonitor::new()));
+        let processing = CaptureProcessing {
+            gain: Some(GainSettings {
+                target_dbfs: -20.0,
+                max_gain_db: 30.0,
+            }),
+            ..Default::default()
+        };
+        let mut stages = CaptureStages::new(processing, levels.clone());
+        let target = recording_target(true, None);
+        // Six seconds at -60 dBFS, which the AGC would lift
+        for _ in 0..60 {
+            stages.store(&target, &[0.001; 1600]);
+        }
+        assert_eq!(target.samples.lock().len(), 96000);
+        assert_eq!(levels.lock().warning(), Some(LevelWarning::TooQuiet));
+        assert!(levels.lock().quality().unwrap().too_quiet_dbfs.is_some());
+    }
+
+    #[test]
+    fn test_stall_check_keeps_a_stream_that_calls_back() {
+        let watch = DeviceWatch::default();
+        let start = Instant::now();
+        let mut stall = StallCheck::new(start);
+        for i in 1..10 {
+            watch.heard();
+            assert!(!stall.device_lost(&watch, start + STALL_TIMEOUT * i));
+        }
+    }
+
+    #[test]
+    fn test_stall_check_loses_a_silent_stream() {
+        let watch = DeviceWatch::default();
+        let start = Instant::now();
+        let mut stall = StallCheck::new(start);
+        watch.heard();
+        assert!(!stall.device_lost(&watch, start + Duration::from_millis(100)));
+        assert!(!stall.device_lost(&watch, start + STALL_TIMEOUT));
+        assert!(stall.device_lost(&watch, start + STALL_TIMEOUT + Duration::from_millis(100)));
+    }
+
+    #[test]
+    fn test_stall_check_loses_a_disconnected_stream_at_once() {
+        let watch = DeviceWatch::default();
+        let start = Instant::now();
+        let mut stall = StallCheck::new(start);
+        watch.heard();
+        watch.disconnect();
+        assert!(stall.device_lost(&watch, start));
+    }
+
+    #[test]
+    fn test_device_status_initially_active() {
+        let recorder = AudioRecorder::new();
+        assert_eq!(recorder.device_status(), CaptureDeviceStatus::Active);
     }
 
     #[test]
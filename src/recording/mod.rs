@@ -1,9 +1,22 @@
 pub mod conference;
 pub mod core;
+pub mod deepfilter;
 pub mod denoise;
+pub mod echo;
+pub mod file_source;
+pub mod filter;
+pub mod gain;
+pub mod levels;
 pub mod loopback;
+pub mod loudness;
+pub mod meter;
 pub mod microphone;
+pub mod monitor;
+pub mod resample;
 pub mod ring_buffer;
 pub mod segmentation;
 pub mod service;
+pub mod speaker_tracks;
+pub mod speech_gate;
 pub mod split;
+pub mod spool;
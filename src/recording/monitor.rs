@@ -0,0 +1,170 @@
+//! Listen-through of the microphone.
+//!
+//! While a recording runs, the capture callback also pushes its mono audio
+//! into a lock-free ring buffer, which an output stream on the default
+//! output device plays back. Neither callback locks or allocates. The
+//! two devices run on separate clocks, so the playback converts the rate
+//! by linear interpolation and keeps at most [`MAX_BACKLOG`] of audio
+//! queued: whatever piles up beyond that is dropped, so the delay a
+//! speaker hears stays short over a long meeting instead of drifting.
+
+use anyhow::{Context, Result};
+use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use std::time::Duration;
+
+/// Longest delay the queued audio may add before it is skipped
+const MAX_BACKLOG: Duration = Duration::from_millis(60);
+
+/// Capture side of the monitor, fed from the input callback.
+pub(crate) struct MonitorFeed {
+    producer: rtrb::Producer<f32>,
+}
+
+impl MonitorFeed {
+    /// Queue captured mono audio; drops it if playback has fallen behind.
+    pub fn push(&mut self, samples: &[f32]) {
+        let n = samples.len().min(self.producer.slots());
+        if let Ok(mut chunk) = self.producer.write_chunk(n) {
+            let (first, second) = chunk.as_mut_slices();
+            let first_len = first.len();
+            first.copy_from_slice(&samples[..first_len]);
+            second.copy_from_slice(&samples[first_len..n]);
+            chunk.commit_all();
+        }
+    }
+}
+
+/// Playback side of the monitor, drained by the output callback.
+struct MonitorPlayback {
+    consumer: rtrb::Consumer<f32>,
+    /// Input samples per output frame
+    step: f64,
+    /// Position between `prev` and `next`; at 1.0 the next sample is due
+    frac: f64,
+    prev: f32,
+    next: f32,
+    volume: f32,
+    max_backlog: usize,
+}
+
+impl MonitorPlayback {
+    fn new(consumer: rtrb::Consumer<f32>, input_rate: u32, output_rate: u32, volume: f32) -> Self {
+        Self {
+            consumer,
+            step: input_rate as f64 / output_rate as f64,
+            // Takes the first sample at once
+            frac: 1.0,
+            prev: 0.0,
+            next: 0.0,
+            volume,
+            max_backlog: input_rate as usize * MAX_BACKLOG.as_millis() as usize / 1000,
+        }
+    }
+
+    /// Fill interleaved `out` with the queued audio on every channel,
+    /// silence where there is none.
+    fn fill(&mut self, out: &mut [f32], channels: usize) {
+        let excess = self.consumer.slots().saturating_sub(self.max_backlog);
+        if let Ok(chunk) = self.consumer.read_chunk(excess) {
+            chunk.commit_all();
+        }
+
+        for frame in out.chunks_mut(channels.max(1)) {
+            while self.frac >= 1.0 {
+                self.frac -= 1.0;
+                self.prev = self.next;
+                self.next = self.consumer.pop().unwrap_or(0.0);
+            }
+            let sample = self.prev + (self.next - self.prev) * self.frac as f32;
+            frame.fill(sample * self.volume);
+            self.frac += self.step;
+        }
+    }
+}
+
+/// Open the default output device to play audio captured at `input_rate`.
+///
+/// The stream must be kept alive for as long as the monitor is heard.
+pub(crate) fn open(input_rate: u32, volume: f32) -> Result<(MonitorFeed, cpal::Stream)> {
+    let device = cpal::default_host()
+        .default_output_device()
+        .context("Не знайдено пристрій відтворення")?;
+    let config = device.default_output_config()?;
+    let output_rate = config.sample_rate().0;
+    let channels = config.channels() as usize;
+
+    // A second of audio: ample, as the playback keeps only MAX_BACKLOG of it
+    let (producer, consumer) = rtrb::RingBuffer::new(input_rate as usize);
+    let mut playback = MonitorPlayback::new(consumer, input_rate, output_rate, volume);
+
+    let stream = device.build_output_stream(
+        &config.into(),
+        move |data: &mut [f32], _: &cpal::OutputCallbackInfo| playback.fill(data, channels),
+        |err| eprintln!("Помилка прослуховування: {}", err),
+        None,
+    )?;
+    stream.play()?;
+    Ok((MonitorFeed { producer }, stream))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(input_rate: u32, output_rate: u32, volume: f32) -> (MonitorFeed, MonitorPlayback) {
+        let (producer, consumer) = rtrb::RingBuffer::new(input_rate as usize);
+        (
+            MonitorFeed { producer },
+            MonitorPlayback::new(consumer, input_rate, output_rate, volume),
+        )
+    }
+
+    #[test]
+    fn test_plays_queued_audio_on_every_channel() {
+        let (mut feed, mut playback) = monitor(48000, 48000, 0.5);
+        feed.push(&[0.2, 0.4, 0.6]);
+        let mut out = [1.0; 8];
+        playback.fill(&mut out, 2);
+        // One frame of delay for the interpolation, then the audio at half volume
+        assert_eq!(out, [0.0, 0.0, 0.1, 0.1, 0.2, 0.2, 0.3, 0.3]);
+    }
+
+    #[test]
+    fn test_plays_silence_without_audio() {
+        let (_feed, mut playback) = monitor(48000, 48000, 1.0);
+        let mut out = [1.0; 16];
+        playback.fill(&mut out, 1);
+        assert!(out.iter().all(|&s| s == 0.0));
+    }
+
+    #[test]
+    fn test_converts_rate() {
+        let (mut feed, mut playback) = monitor(24000, 48000, 1.0);
+        feed.push(&[0.3; 240]);
+        let mut out = [0.0; 480];
+        playback.fill(&mut out, 1);
+        // 10 ms in, 10 ms out, rising from silence over the first sample
+        assert_eq!(&out[..2], &[0.0, 0.15]);
+        assert!(out[2..].iter().all(|&s| s == 0.3));
+        assert_eq!(playback.consumer.slots(), 0);
+    }
+
+    #[test]
+    fn test_skips_a_backlog_beyond_the_limit() {
+        let (mut feed, mut playback) = monitor(48000, 48000, 1.0);
+        // Half a second queued, e.g. after the output device stalled
+        feed.push(&[0.0; 24000]);
+        feed.push(&[0.5; 2880]);
+        let mut out = [0.0; 2];
+        playback.fill(&mut out, 1);
+        assert_eq!(out[1], 0.5);
+        assert!(playback.consumer.slots() < 2880);
+    }
+
+    #[test]
+    fn test_drops_audio_when_full() {
+        let (mut feed, playback) = monitor(100, 100, 1.0);
+        feed.push(&[0.1; 150]);
+        assert_eq!(playback.consumer.slots(), 100);
+    }
+}
@@ -0,0 +1,187 @@
+//! Conversion of the microphone's sample rate to the 16 kHz the models use.
+//!
+//! Two qualities (`resampler_quality`): a windowed-sinc resampler whose
+//! passband reaches almost to 8 kHz, where sibilants live, and which rejects
+//! everything above; and linear interpolation, which costs a fraction of the
+//! CPU but folds the device's content above 8 kHz back into the speech band.
+
+use super::core::WHISPER_SAMPLE_RATE;
+use anyhow::{Context, Result};
+use rubato::{
+    calculate_cutoff, FastFixedIn, PolynomialDegree, Resampler, SincFixedIn, SincInterpolationParameters,
+    SincInterpolationType, WindowFunction,
+};
+
+/// Input frames per resampler call
+const CHUNK_SIZE: usize = 1024;
+
+/// Sinc length of the high-quality resampler; longer gives a steeper cutoff
+const SINC_LEN: usize = 512;
+
+/// Resampling quality for the device → 16 kHz conversion.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResamplerQuality {
+    /// Linear interpolation: cheapest, aliases on 44.1/48 kHz devices
+    Fast,
+    /// Windowed sinc with an anti-aliasing cutoff just below 8 kHz
+    #[default]
+    High,
+}
+
+impl ResamplerQuality {
+    /// Accepted values of `resampler_quality`
+    pub const NAMES: &'static [&'static str] = &["high", "fast"];
+
+    /// Parse a config value; unknown values fall back to high quality.
+    pub fn parse(name: &str) -> Self {
+        match name {
+            "fast" => Self::Fast,
+            _ => Self::High,
+        }
+    }
+}
+
+/// Mono resampler from a device rate to 16 kHz.
+///
+/// rubato's `Resampler` trait is not object safe, so the two kinds are
+/// wrapped here rather than boxed.
+pub enum DeviceResampler {
+    Fast(FastFixedIn<f32>),
+    Sinc(Box<SincFixedIn<f32>>),
+}
+
+impl DeviceResampler {
+    pub fn new(sample_rate: u32, quality: ResamplerQuality) -> Result<Self> {
+        let resample_ratio = WHISPER_SAMPLE_RATE as f64 / sample_rate as f64;
+        let resampler = match quality {
+            ResamplerQuality::Fast => Self::Fast(
+                FastFixedIn::<f32>::new(
+                    resample_ratio,
+                    2.0, // max relative ratio (safety margin)
+                    PolynomialDegree::Linear,
+                    CHUNK_SIZE,
+                    1,
+                )
+                .context("Не вдалося створити ресемплер")?,
+            ),
+            ResamplerQuality::High => {
+                let window = WindowFunction::BlackmanHarris2;
+                let params = SincInterpolationParameters {
+                    sinc_len: SINC_LEN,
+                    f_cutoff: calculate_cutoff(SINC_LEN, window),
+                    interpolation: SincInterpolationType::Cubic,
+                    oversampling_factor: 256,
+                    window,
+                };
+                Self::Sinc(Box::new(
+                    SincFixedIn::<f32>::new(resample_ratio, 2.0, params, CHUNK_SIZE, 1)
+                        .context("Не вдалося створити ресемплер")?,
+                ))
+            }
+        };
+        Ok(resampler)
+    }
+
+    /// Input samples the next `process` call takes.
+    pub fn input_frames_next(&self) -> usize {
+        match self {
+            Self::Fast(r) => r.input_frames_next(),
+            Self::Sinc(r) => r.input_frames_next(),
+        }
+    }
+
+    /// Output samples the next `process` call produces.
+    pub fn output_frames_next(&self) -> usize {
+        match self {
+            Self::Fast(r) => r.output_frames_next(),
+            Self::Sinc(r) => r.output_frames_next(),
+        }
+    }
+
+    /// Resample exactly `input_frames_next()` samples.
+    pub fn process(&mut self, input: &[f32]) -> Result<Vec<f32>> {
+        let output = match self {
+            Self::Fast(r) => r.process(&[input], None),
+            Self::Sinc(r) => r.process(&[input], None),
+        }
+        .context("Помилка ресемплінгу")?;
+        Ok(output.into_iter().next().unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(sample_rate: u32, freq: f32, secs: f32) -> Vec<f32> {
+        let len = (sample_rate as f32 * secs) as usize;
+        (0..len)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * freq * i as f32 / sample_rate as f32).sin())
+            .collect()
+    }
+
+    /// Level of `freq` after resampling from 44.1 kHz, relative to the input (dB).
+    fn gain_db(quality: ResamplerQuality, freq: f32) -> f32 {
+        let input = tone(44100, freq, 1.0);
+        let mut resampler = DeviceResampler::new(44100, quality).unwrap();
+        let mut output = Vec::new();
+        let mut pos = 0;
+        while pos + resampler.input_frames_next() <= input.len() {
+            let n = resampler.input_frames_next();
+            output.extend(resampler.process(&input[pos..pos + n]).unwrap());
+            pos += n;
+        }
+        // Skip the filter's delay and the end
+        let steady = &output[1600..output.len() - 1600];
+        let rms = (steady.iter().map(|x| x * x).sum::<f32>() / steady.len() as f32).sqrt();
+        20.0 * (rms / (0.5 / 2f32.sqrt())).log10()
+    }
+
+    #[test]
+    fn test_parse_quality() {
+        assert_eq!(ResamplerQuality::parse("fast"), ResamplerQuality::Fast);
+        assert_eq!(ResamplerQuality::parse("high"), ResamplerQuality::High);
+        assert_eq!(ResamplerQuality::parse("best"), ResamplerQuality::High);
+        for name in ResamplerQuality::NAMES {
+            assert_eq!(ResamplerQuality::parse(name) == ResamplerQuality::Fast, *name == "fast");
+        }
+    }
+
+    #[test]
+    fn test_high_quality_keeps_sibilants_and_rejects_aliases() {
+        assert!(gain_db(ResamplerQuality::High, 1000.0).abs() < 0.5);
+        assert!(gain_db(ResamplerQuality::High, 7000.0).abs() < 0.5);
+        // 11 kHz would fold back to 5 kHz
+        assert!(gain_db(ResamplerQuality::High, 11000.0) < -40.0);
+    }
+
+    #[test]
+    fn test_fast_aliases_above_nyquist() {
+        assert!(gain_db(ResamplerQuality::Fast, 1000.0).abs() < 0.5);
+        assert!(gain_db(ResamplerQuality::Fast, 11000.0) > -10.0);
+    }
+
+    #[test]
+    fn test_output_rate_is_16k() {
+        for quality in [ResamplerQuality::Fast, ResamplerQuality::High] {
+            let mut resampler = DeviceResampler::new(48000, quality).unwrap();
+            let input = vec![0.0; 48000];
+            let mut produced = 0;
+            let mut pos = 0;
+            while pos + resampler.input_frames_next() <= input.len() {
+                let n = resampler.input_frames_next();
+                produced += resampler.process(&input[pos..pos + n]).unwrap().len();
+                pos += n;
+            }
+            // Within the delay of the sinc filter
+            let expected = pos / 3;
+            assert!(
+                produced.abs_diff(expected) <= SINC_LEN / 4,
+                "{:?}: {} vs {}",
+                quality,
+                produced,
+                expected
+            );
+        }
+    }
+}
@@ -49,21 +49,25 @@ impl RingBuffer {
 
     /// Read all available samples from the buffer (clears buffer)
     pub fn read_all(&self) -> Vec<f32> {
-        let mut state = self.state.lock();
+        self.drain_into()
+    }
 
-        if state.size == 0 {
-            return Vec::new();
-        }
+    /// Read all available samples into a shared slice (clears buffer).
+    ///
+    /// Collects straight into the shared allocation, avoiding the
+    /// intermediate `Vec` copy when the result is handed to several owners.
+    pub fn read_all_shared(&self) -> Arc<[f32]> {
+        self.drain_into()
+    }
 
-        let mut result = Vec::with_capacity(state.size);
+    fn drain_into<C: FromIterator<f32>>(&self) -> C {
+        let mut state = self.state.lock();
 
         // Read from oldest to newest
         let start_pos = if state.size < self.capacity { 0 } else { state.write_pos };
-
-        for i in 0..state.size {
-            let idx = (start_pos + i) % self.capacity;
-            result.push(state.buffer[idx]);
-        }
+        let result = (0..state.size)
+            .map(|i| state.buffer[(start_pos + i) % self.capacity])
+            .collect();
 
         // Clear buffer
         state.size = 0;
@@ -92,6 +96,18 @@ impl RingBuffer {
         result
     }
 
+    /// Read the newest N samples without clearing buffer
+    pub fn peek_tail(&self, n: usize) -> Vec<f32> {
+        let state = self.state.lock();
+
+        let read_size = n.min(state.size);
+        let oldest = if state.size < self.capacity { 0 } else { state.write_pos };
+        let start_pos = oldest + state.size - read_size;
+        (0..read_size)
+            .map(|i| state.buffer[(start_pos + i) % self.capacity])
+            .collect()
+    }
+
     /// Clear the buffer
     pub fn clear(&self) {
         let mut state = self.state.lock();
@@ -146,6 +162,15 @@ mod tests {
         assert!(result.is_empty());
     }
 
+    #[test]
+    fn test_read_all_shared_matches_read_all() {
+        let rb = RingBuffer::new(4);
+        rb.write(&[1.0, 2.0, 3.0, 4.0, 5.0]);
+        let shared = rb.read_all_shared();
+        assert_eq!(&shared[..], &[2.0, 3.0, 4.0, 5.0]);
+        assert!(rb.read_all_shared().is_empty());
+    }
+
     #[test]
     fn test_multiple_writes_accumulate() {
         let rb = RingBuffer::new(100);
@@ -216,6 +241,18 @@ mod tests {
         assert_eq!(peeked, vec![3.0, 4.0]);
     }
 
+    #[test]
+    fn test_peek_tail_reads_newest() {
+        let rb = RingBuffer::new(4);
+        rb.write(&[1.0, 2.0, 3.0]);
+        assert_eq!(rb.peek_tail(2), vec![2.0, 3.0]);
+        rb.write(&[4.0, 5.0, 6.0]);
+        // Buffer contains [3.0, 4.0, 5.0, 6.0] after wrap
+        assert_eq!(rb.peek_tail(3), vec![4.0, 5.0, 6.0]);
+        assert_eq!(rb.peek_tail(10), vec![3.0, 4.0, 5.0, 6.0]);
+        assert!(RingBuffer::new(4).peek_tail(2).is_empty());
+    }
+
     #[test]
     fn test_clear() {
         let rb = RingBuffer::new(100);
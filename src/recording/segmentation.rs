@@ -6,14 +6,20 @@
 
 use crate::domain::traits::VoiceDetection;
 use crate::domain::types::AudioSegment;
+use crate::infrastructure::failures::{self, Failure};
 use crate::recording::core::WHISPER_SAMPLE_RATE;
 use crate::recording::ring_buffer::RingBuffer;
+use crate::recording::speech_gate::SpeechGate;
 use crate::recording::split::SplitConfig;
 use crate::recording::split::SplitFinder;
-use crate::vad::{create_vad, VadConfig, VadEngine};
-use async_channel::Sender;
+use crate::vad::{
+    create_vad, AdaptiveVoiceDetector, NoiseFloorState, NoiseFloorTracker, SharedNoiseFloor, VadConfig, VadEngine,
+    DEFAULT_INITIAL_FLOOR_DBFS, DEFAULT_MARGIN_DB,
+};
+use async_channel::{Receiver, Sender};
 use parking_lot::Mutex;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
@@ -25,8 +31,23 @@ pub struct SegmentationConfig {
     pub vad_min_speech_ms: u32,
     pub vad_engine: VadEngine,
     pub silero_threshold: f32,
+    /// Silero model file; `None` uses the built-in copy.
+    pub silero_model_path: Option<PathBuf>,
+    /// WebRTC VAD aggressiveness, 0–3.
+    pub vad_aggressiveness: u8,
     /// Maximum segment duration in seconds (safety limit).
     pub max_segment_secs: u32,
+    /// Require energy above the running noise floor in addition to the VAD.
+    pub adaptive_noise_floor: bool,
+    /// Noise level to start from, e.g. measured by calibration (dBFS).
+    pub initial_noise_floor_dbfs: Option<f32>,
+    /// Audio kept before the first speech of a VAD segment, ms.
+    pub pre_roll_ms: u32,
+    /// Audio kept after the last speech of a VAD segment, ms.
+    pub post_roll_ms: u32,
+    /// Drop pauses longer than `vad_silence_threshold_ms` from the recording,
+    /// keeping the pre- and post-roll around speech (VAD only).
+    pub speech_gated: bool,
 }
 
 impl Default for SegmentationConfig {
@@ -38,11 +59,28 @@ impl Default for SegmentationConfig {
             vad_min_speech_ms: 500,
             vad_engine: VadEngine::WebRTC,
             silero_threshold: 0.5,
+            silero_model_path: None,
+            vad_aggressiveness: 2,
             max_segment_secs: 300,
+            adaptive_noise_floor: true,
+            initial_noise_floor_dbfs: None,
+            pre_roll_ms: 300,
+            post_roll_ms: 300,
+            speech_gated: false,
         }
     }
 }
 
+/// VAD decision for one frame of the recording.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VadDecision {
+    /// Position of the frame's first sample within the recording.
+    pub start_sample: usize,
+    /// Frame length in samples.
+    pub len: usize,
+    pub is_speech: bool,
+}
+
 /// Monitors a shared samples buffer and produces audio segments.
 ///
 /// Runs a background thread that periodically reads new samples from the
@@ -55,12 +93,22 @@ pub struct SegmentationMonitor {
     segment_counter: Arc<Mutex<usize>>,
     last_segment_time: Arc<Mutex<Option<Instant>>>,
     segment_tx: Arc<Mutex<Option<Sender<AudioSegment>>>>,
+    /// Subscriber to per-frame VAD decisions, for the speech timeline.
+    decision_tx: Arc<Mutex<Option<Sender<VadDecision>>>>,
     is_speech_detected: Arc<AtomicBool>,
+    /// Recorder samples copied into the ring buffer so far.
+    samples_written: Arc<AtomicUsize>,
+    /// Running noise-floor estimate of the current session.
+    noise_floor: SharedNoiseFloor,
     thread_handle: Mutex<Option<std::thread::JoinHandle<()>>>,
 }
 
 impl SegmentationMonitor {
     pub fn new(config: SegmentationConfig) -> Self {
+        let noise_floor = NoiseFloorTracker::new(
+            config.initial_noise_floor_dbfs.unwrap_or(DEFAULT_INITIAL_FLOOR_DBFS),
+            DEFAULT_MARGIN_DB,
+        );
         Self {
             config,
             ring_buffer: Arc::new(RingBuffer::new_30s()),
@@ -68,7 +116,10 @@ impl SegmentationMonitor {
             segment_counter: Arc::new(Mutex::new(0)),
             last_segment_time: Arc::new(Mutex::new(None)),
             segment_tx: Arc::new(Mutex::new(None)),
+            decision_tx: Arc::new(Mutex::new(None)),
             is_speech_detected: Arc::new(AtomicBool::new(false)),
+            samples_written: Arc::new(AtomicUsize::new(0)),
+            noise_floor: Arc::new(Mutex::new(noise_floor)),
             thread_handle: Mutex::new(None),
         }
     }
@@ -78,15 +129,31 @@ impl SegmentationMonitor {
     /// Reads new samples from `samples_buffer` (the recorder's shared buffer),
     /// feeds them into the ring buffer, and produces segments via the channel.
     pub fn start(&self, samples_buffer: Arc<Mutex<Vec<f32>>>, segment_tx: Sender<AudioSegment>) {
+        self.spawn(samples_buffer, Some(segment_tx));
+    }
+
+    /// Start the VAD without segmentation, for the speech indicator of plain dictation.
+    ///
+    /// Keeps `is_speech_detected` and the noise floor up to date like `start`,
+    /// but never cuts segments.
+    pub fn start_speech_detection(&self, samples_buffer: Arc<Mutex<Vec<f32>>>) {
+        self.spawn(samples_buffer, None);
+    }
+
+    fn spawn(&self, samples_buffer: Arc<Mutex<Vec<f32>>>, segment_tx: Option<Sender<AudioSegment>>) {
+        let emits_segments = segment_tx.is_some();
         self.ring_buffer.clear();
-        *self.segment_tx.lock() = Some(segment_tx);
+        *self.segment_tx.lock() = segment_tx;
         self.is_running.store(true, Ordering::SeqCst);
         *self.segment_counter.lock() = 0;
         *self.last_segment_time.lock() = Some(Instant::now());
+        self.samples_written.store(0, Ordering::SeqCst);
+        self.noise_floor.lock().reset();
 
         let ring_buffer = self.ring_buffer.clone();
         let is_running = self.is_running.clone();
         let segment_tx = self.segment_tx.clone();
+        let decision_tx = self.decision_tx.clone();
         let segment_counter = self.segment_counter.clone();
         let last_segment_time = self.last_segment_time.clone();
         let use_vad = self.config.use_vad;
@@ -94,13 +161,29 @@ impl SegmentationMonitor {
         let vad_silence_threshold_ms = self.config.vad_silence_threshold_ms;
         let vad_min_speech_ms = self.config.vad_min_speech_ms;
         let silero_threshold = self.config.silero_threshold;
+        let silero_model_path = self.config.silero_model_path.clone();
+        let vad_aggressiveness = self.config.vad_aggressiveness;
         let segment_interval = Duration::from_secs(self.config.segment_interval_secs as u64);
         let max_segment_secs = self.config.max_segment_secs;
         let is_speech_detected = self.is_speech_detected.clone();
+        let samples_written = self.samples_written.clone();
+        let noise_floor = self.noise_floor.clone();
+        let adaptive_noise_floor = self.config.adaptive_noise_floor;
+        let pre_roll_ms = self.config.pre_roll_ms;
+        let post_roll_ms = self.config.post_roll_ms;
+        // Gating only pays off for segmented sessions, which may run all day
+        let speech_gated = self.config.speech_gated && emits_segments;
 
         let handle = std::thread::spawn(move || {
             let check_interval = Duration::from_millis(500);
             let mut last_samples_len = 0;
+            // End of the audio handed to the ring buffer, in recorder buffer positions
+            let mut ring_end = 0;
+            // Samples recorded so far, before gating; positions of the decision stream
+            let mut recorded = 0;
+            // Samples not yet classified for the decision stream, and where they start
+            let mut undecided: Vec<f32> = Vec::new();
+            let mut undecided_start = 0;
 
             // Create VAD inside thread — VAD implementations are not Send
             let vad: Option<Box<dyn VoiceDetection>> = if use_vad {
@@ -109,8 +192,15 @@ impl SegmentationMonitor {
                     silence_threshold_ms: vad_silence_threshold_ms,
                     min_speech_ms: vad_min_speech_ms,
                     silero_threshold,
+                    webrtc_aggressiveness: vad_aggressiveness,
+                    silero_model_path,
                 };
                 match create_vad(&config) {
+                    Ok(v) if adaptive_noise_floor => Some(Box::new(AdaptiveVoiceDetector::new(
+                        v,
+                        noise_floor.clone(),
+                        vad_silence_threshold_ms,
+                    )) as Box<dyn VoiceDetection>),
                     Ok(v) => Some(v),
                     Err(e) => {
                         eprintln!(
@@ -128,20 +218,52 @@ impl SegmentationMonitor {
             let split_finder = SplitFinder::new(SplitConfig {
                 vad_silence_ms: vad_silence_threshold_ms,
                 max_segment_secs,
+                pre_roll_ms,
+                post_roll_ms,
                 sample_rate: WHISPER_SAMPLE_RATE,
                 ..SplitConfig::default()
             });
 
+            let mut gate = vad.as_ref().filter(|_| speech_gated).map(|_| {
+                let ms = |ms: u32| ms as usize * WHISPER_SAMPLE_RATE as usize / 1000;
+                SpeechGate::new(
+                    vad_engine.frame_samples(),
+                    ms(pre_roll_ms),
+                    ms(post_roll_ms),
+                    ms(vad_silence_threshold_ms),
+                )
+            });
+            let mut gate_speech_ended = false;
+
             while is_running.load(Ordering::SeqCst) {
                 std::thread::sleep(check_interval);
 
                 // Read only new samples from the recorder's shared buffer
                 {
-                    let samples = samples_buffer.lock();
+                    let mut samples = samples_buffer.lock();
                     let current_len = samples.len();
                     if current_len > last_samples_len {
-                        ring_buffer.write(&samples[last_samples_len..]);
-                        last_samples_len = current_len;
+                        noise_floor.lock().update(&samples[last_samples_len..]);
+                        if vad.is_some() && decision_tx.lock().is_some() {
+                            if undecided.is_empty() {
+                                undecided_start = recorded;
+                            }
+                            undecided.extend_from_slice(&samples[last_samples_len..]);
+                        }
+                        recorded += current_len - last_samples_len;
+
+                        if let (Some(gate), Some(vad)) = (gate.as_mut(), vad.as_ref()) {
+                            // Drops long pauses from the recorder's buffer itself
+                            let output = gate.process(&mut samples, vad.as_ref());
+                            ring_buffer.write(&samples[output.committed.clone()]);
+                            ring_end = output.committed.end;
+                            gate_speech_ended |= output.speech_ended;
+                        } else {
+                            ring_buffer.write(&samples[last_samples_len..]);
+                            ring_end = current_len;
+                        }
+                        last_samples_len = samples.len();
+                        samples_written.store(ring_end, Ordering::SeqCst);
                     }
                 }
 
@@ -158,9 +280,21 @@ impl SegmentationMonitor {
                     let speech_now = vad.is_speech(&recent_samples).unwrap_or(false);
                     is_speech_detected.store(speech_now, Ordering::SeqCst);
 
+                    if let Some(ref tx) = *decision_tx.lock() {
+                        send_decisions(
+                            vad.as_ref(),
+                            tx,
+                            &mut undecided,
+                            &mut undecided_start,
+                            vad_engine.frame_samples(),
+                        );
+                    }
+
                     let elapsed = last_segment_time.lock().map(|t| t.elapsed()).unwrap_or(Duration::ZERO);
 
-                    split_finder.should_split_streaming(&samples, vad.as_ref(), elapsed)
+                    // A pause cut by the gate never reaches the ring buffer whole
+                    std::mem::take(&mut gate_speech_ended)
+                        || split_finder.should_split_streaming(&samples, vad.as_ref(), elapsed)
                 } else {
                     // No VAD — always show as "listening"
                     is_speech_detected.store(false, Ordering::SeqCst);
@@ -172,8 +306,30 @@ impl SegmentationMonitor {
                         .unwrap_or(false)
                 };
 
-                if should_segment {
-                    let segment_samples = ring_buffer.read_all();
+                if should_segment && emits_segments {
+                    let buffered = ring_buffer.read_all_shared();
+                    // The ring buffer always ends at the last sample written
+                    let buffered_start = ring_end.saturating_sub(buffered.len());
+
+                    // Drop silence around the speech, keeping the pre- and post-roll
+                    let speech = vad
+                        .as_ref()
+                        .and_then(|vad| split_finder.padded_speech_range(&buffered, vad.as_ref()));
+                    let range = match speech {
+                        Some(range) => range,
+                        // Gated sessions transcribe speech only
+                        None if gate.is_some() => {
+                            *last_segment_time.lock() = Some(Instant::now());
+                            continue;
+                        }
+                        None => 0..buffered.len(),
+                    };
+                    let segment_start = buffered_start + range.start;
+                    let segment_samples: Arc<[f32]> = if range.len() == buffered.len() {
+                        buffered
+                    } else {
+                        Arc::from(&buffered[range])
+                    };
 
                     if segment_samples.len() >= min_samples {
                         let segment_id = {
@@ -186,6 +342,7 @@ impl SegmentationMonitor {
                         let end_time = Instant::now();
 
                         let segment = AudioSegment {
+                            start_sample: segment_start,
                             samples: segment_samples,
                             start_time,
                             end_time,
@@ -195,6 +352,7 @@ impl SegmentationMonitor {
                         if let Some(ref tx) = *segment_tx.lock() {
                             if let Err(e) = tx.try_send(segment) {
                                 eprintln!("Помилка відправки сегменту: {:?}", e);
+                                failures::record(Failure::SegmentSend);
                             }
                         }
 
@@ -226,12 +384,14 @@ impl SegmentationMonitor {
 
         // Fallback: if ring buffer is empty, use last 5s from recorder's samples
         let mut remaining = ring_remaining;
+        let mut remaining_end = self.samples_written.load(Ordering::SeqCst);
         if remaining.is_empty() {
             let recorder_samples = samples_buffer.lock();
             if !recorder_samples.is_empty() {
                 let last_segment_samples = (WHISPER_SAMPLE_RATE as usize) * 5;
                 let start = recorder_samples.len().saturating_sub(last_segment_samples);
                 remaining = recorder_samples[start..].to_vec();
+                remaining_end = recorder_samples.len();
             }
         }
 
@@ -239,15 +399,15 @@ impl SegmentationMonitor {
         let min_samples = (WHISPER_SAMPLE_RATE as usize) / 2;
 
         if remaining.len() >= min_samples {
-            let segment_id = {
-                let mut counter = self.segment_counter.lock();
-                *counter += 1;
-                *counter
-            };
-
             if let Some(ref tx) = *self.segment_tx.lock() {
+                let segment_id = {
+                    let mut counter = self.segment_counter.lock();
+                    *counter += 1;
+                    *counter
+                };
                 let segment = AudioSegment {
-                    samples: remaining,
+                    start_sample: remaining_end.saturating_sub(remaining.len()),
+                    samples: Arc::from(remaining),
                     start_time: self.last_segment_time.lock().unwrap_or_else(Instant::now),
                     end_time: Instant::now(),
                     segment_id,
@@ -256,19 +416,65 @@ impl SegmentationMonitor {
             }
         }
 
-        // Close the channel
+        // Close the channels
         *self.segment_tx.lock() = None;
+        *self.decision_tx.lock() = None;
+    }
+
+    /// Per-frame VAD decisions of the running session, for a speech timeline.
+    ///
+    /// Frames recorded after the call are classified as the monitor reads them
+    /// (every half second). Nothing is sent without VAD; the channel closes
+    /// when the session stops.
+    pub fn subscribe_decisions(&self) -> Receiver<VadDecision> {
+        let (tx, rx) = async_channel::unbounded();
+        *self.decision_tx.lock() = Some(tx);
+        rx
     }
 
     /// Check if speech is currently detected (for UI display).
     pub fn is_speech_detected(&self) -> bool {
         self.is_speech_detected.load(Ordering::SeqCst)
     }
+
+    /// Noise-floor adaptation of the current session, for debugging.
+    pub fn noise_floor(&self) -> NoiseFloorState {
+        self.noise_floor.lock().state()
+    }
+
+    /// Last `max_samples` of the segment currently being recorded (not yet sent).
+    pub fn pending_tail(&self, max_samples: usize) -> Vec<f32> {
+        self.ring_buffer.peek_tail(max_samples)
+    }
+}
+
+/// Classify the complete frames of `pending` and send one decision per frame.
+///
+/// A trailing partial frame stays in `pending` for the next call.
+fn send_decisions(
+    vad: &dyn VoiceDetection,
+    tx: &Sender<VadDecision>,
+    pending: &mut Vec<f32>,
+    start_sample: &mut usize,
+    frame_samples: usize,
+) {
+    let complete = pending.len() / frame_samples * frame_samples;
+    for frame in pending[..complete].chunks_exact(frame_samples) {
+        let decision = VadDecision {
+            start_sample: *start_sample,
+            len: frame_samples,
+            is_speech: vad.is_speech(frame).unwrap_or(false),
+        };
+        let _ = tx.try_send(decision);
+        *start_sample += frame_samples;
+    }
+    pending.drain(..complete);
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::mocks::MockVoiceDetector;
     use std::time::Duration;
 
     #[test]
@@ -279,6 +485,96 @@ mod tests {
         assert_eq!(config.vad_silence_threshold_ms, 1000);
         assert_eq!(config.vad_min_speech_ms, 500);
         assert_eq!(config.silero_threshold, 0.5);
+        assert!(config.adaptive_noise_floor);
+        assert_eq!(config.initial_noise_floor_dbfs, None);
+        assert_eq!(config.pre_roll_ms, 300);
+        assert_eq!(config.post_roll_ms, 300);
+        assert!(!config.speech_gated);
+    }
+
+    #[test]
+    fn test_noise_floor_tracks_recorded_samples() {
+        let config = SegmentationConfig {
+            use_vad: false,
+            segment_interval_secs: 3600,
+            initial_noise_floor_dbfs: Some(-50.0),
+            ..Default::default()
+        };
+        let monitor = SegmentationMonitor::new(config);
+        assert_eq!(monitor.noise_floor().floor_dbfs, -50.0);
+
+        // Constant 0.01 is -40 dBFS; louder than the initial floor, so it stays capped
+        let samples_buffer = Arc::new(Mutex::new(vec![0.01_f32; WHISPER_SAMPLE_RATE as usize]));
+        let (segment_tx, _segment_rx) = async_channel::unbounded::<AudioSegment>();
+        monitor.start(samples_buffer.clone(), segment_tx);
+        std::thread::sleep(Duration::from_millis(700));
+        monitor.stop(&samples_buffer);
+
+        let state = monitor.noise_floor();
+        assert_eq!(state.frames, WHISPER_SAMPLE_RATE as u64 / 512);
+        assert_eq!(state.floor_dbfs, -50.0);
+        assert!((state.level_dbfs - -40.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_speech_detection_cuts_no_segments() {
+        let config = SegmentationConfig {
+            use_vad: false,
+            segment_interval_secs: 0,
+            ..Default::default()
+        };
+        let monitor = SegmentationMonitor::new(config);
+        let samples_buffer = Arc::new(Mutex::new(vec![0.01_f32; WHISPER_SAMPLE_RATE as usize]));
+
+        monitor.start_speech_detection(samples_buffer.clone());
+        std::thread::sleep(Duration::from_millis(700));
+        monitor.stop(&samples_buffer);
+
+        assert_eq!(*monitor.segment_counter.lock(), 0);
+        assert_eq!(monitor.noise_floor().frames, WHISPER_SAMPLE_RATE as u64 / 512);
+    }
+
+    #[test]
+    fn test_send_decisions_carries_partial_frame() {
+        let vad = MockVoiceDetector::detecting_speech();
+        let (tx, rx) = async_channel::unbounded();
+        let mut pending = vec![0.1_f32; 1000];
+        let mut start = 16000;
+
+        send_decisions(&vad, &tx, &mut pending, &mut start, 480);
+        let first = rx.try_recv().unwrap();
+        assert_eq!(
+            first,
+            VadDecision {
+                start_sample: 16000,
+                len: 480,
+                is_speech: true
+            }
+        );
+        assert_eq!(rx.try_recv().unwrap().start_sample, 16480);
+        assert!(rx.try_recv().is_err());
+        assert_eq!(pending.len(), 40);
+        assert_eq!(start, 16960);
+    }
+
+    #[test]
+    fn test_decisions_channel_closes_on_stop() {
+        let config = SegmentationConfig {
+            use_vad: false,
+            segment_interval_secs: 3600,
+            ..Default::default()
+        };
+        let monitor = SegmentationMonitor::new(config);
+        let samples_buffer = Arc::new(Mutex::new(vec![0.01_f32; WHISPER_SAMPLE_RATE as usize]));
+
+        monitor.start_speech_detection(samples_buffer.clone());
+        let decisions = monitor.subscribe_decisions();
+        std::thread::sleep(Duration::from_millis(700));
+        monitor.stop(&samples_buffer);
+
+        // Without VAD there is nothing to report
+        assert!(decisions.try_recv().is_err());
+        assert!(decisions.is_closed());
     }
 
     #[test]
@@ -7,10 +7,13 @@
 
 use crate::domain::traits::AudioRecording;
 use crate::domain::types::AudioSegment;
-use crate::domain::types::ConferenceRecording;
+use crate::domain::types::{AudioQuality, CaptureDeviceStatus, ConferenceRecording, LevelReading, LevelWarning};
 use crate::recording::conference::ConferenceRecorder;
-use crate::recording::microphone::AudioRecorder;
-use crate::recording::segmentation::{SegmentationConfig, SegmentationMonitor};
+use crate::recording::file_source::FileAudioSource;
+use crate::recording::microphone::{AudioRecorder, CaptureProcessing};
+use crate::recording::segmentation::{SegmentationConfig, SegmentationMonitor, VadDecision};
+use crate::recording::spool::SpooledRecording;
+use crate::vad::NoiseFloorState;
 use anyhow::Result;
 use async_channel::{Receiver, Sender};
 use parking_lot::Mutex;
@@ -35,19 +38,44 @@ pub struct AudioService {
 impl AudioService {
     /// Create a new AudioService with the given segmentation configuration.
     ///
-    /// Uses the default `AudioRecorder` for microphone capture.
-    pub fn new(seg_config: SegmentationConfig) -> Result<Self> {
-        let mic = Arc::new(AudioRecorder::new());
+    /// Uses the default `AudioRecorder` for microphone capture, with the
+    /// high-pass and AGC stages `processing` enables. Conference recordings
+    /// longer than `spool_after_minutes` (0: never) move to disk.
+    pub fn new(
+        seg_config: SegmentationConfig,
+        processing: CaptureProcessing,
+        spool_after_minutes: u32,
+    ) -> Result<Self> {
+        let mic = Arc::new(AudioRecorder::with_processing(processing));
         let mic_samples = mic.samples().clone();
 
         Ok(Self {
             mic,
             mic_samples,
             segmentation: Some(Arc::new(SegmentationMonitor::new(seg_config))),
-            conference: Arc::new(ConferenceRecorder::new()),
+            conference: Arc::new(ConferenceRecorder::new(processing, spool_after_minutes)),
         })
     }
 
+    /// Like [`AudioService::new`], but the microphone is replaced by an
+    /// audio file played in real time. Conference mode still records the
+    /// real devices.
+    pub fn with_file_source(
+        source: FileAudioSource,
+        seg_config: SegmentationConfig,
+        processing: CaptureProcessing,
+        spool_after_minutes: u32,
+    ) -> Self {
+        let mic_samples = source.samples().clone();
+
+        Self {
+            mic: Arc::new(source),
+            mic_samples,
+            segmentation: Some(Arc::new(SegmentationMonitor::new(seg_config))),
+            conference: Arc::new(ConferenceRecorder::new(processing, spool_after_minutes)),
+        }
+    }
+
     /// Create with a custom microphone recorder (for testing).
     ///
     /// This constructor enables dependency injection of mock recorders.
@@ -61,7 +89,7 @@ impl AudioService {
             mic,
             mic_samples,
             segmentation: seg_config.map(|c| Arc::new(SegmentationMonitor::new(c))),
-            conference: Arc::new(ConferenceRecorder::new()),
+            conference: Arc::new(ConferenceRecorder::default()),
         })
     }
 
@@ -74,7 +102,7 @@ impl AudioService {
             mic,
             mic_samples,
             segmentation: Some(Arc::new(SegmentationMonitor::new(SegmentationConfig::default()))),
-            conference: Arc::new(ConferenceRecorder::new()),
+            conference: Arc::new(ConferenceRecorder::default()),
         }
     }
 
@@ -85,14 +113,35 @@ impl AudioService {
         self.mic.start()
     }
 
+    /// Keep the microphone open while idle so recordings start with the
+    /// last `secs` before the hotkey.
+    pub fn start_mic_pre_roll(&self, secs: f32) -> Result<()> {
+        self.mic.start_pre_roll(secs)
+    }
+
     /// Stop microphone recording and return captured samples.
     pub fn stop_mic(&self) -> (Vec<f32>, Option<Receiver<()>>) {
         self.mic.stop()
     }
 
-    /// Get current microphone amplitude.
-    pub fn mic_amplitude(&self) -> f32 {
-        self.mic.amplitude()
+    /// Current microphone level.
+    pub fn mic_level(&self) -> LevelReading {
+        self.mic.level()
+    }
+
+    /// Whether the microphone is still there, or which one replaced it.
+    pub fn mic_device_status(&self) -> CaptureDeviceStatus {
+        self.mic.device_status()
+    }
+
+    /// Clipping or a too quiet microphone right now.
+    pub fn mic_level_warning(&self) -> Option<LevelWarning> {
+        self.mic.level_warning()
+    }
+
+    /// Level problems of the last microphone recording, once it has completed.
+    pub fn mic_audio_quality(&self) -> Option<AudioQuality> {
+        self.mic.audio_quality()
     }
 
     /// Check if microphone is currently recording.
@@ -123,31 +172,77 @@ impl AudioService {
         }
     }
 
-    /// Check if speech is currently detected (for segmented mode UI).
+    /// Run the segmentation VAD without cutting segments, so plain dictation
+    /// can show whether it hears speech.
+    ///
+    /// The mic must already be recording before calling this.
+    pub fn start_speech_detection(&self) {
+        if let Some(ref seg) = self.segmentation {
+            seg.start_speech_detection(self.mic_samples.clone());
+        }
+    }
+
+    /// Stop the VAD started by `start_speech_detection`.
+    pub fn stop_speech_detection(&self) {
+        self.stop_segmentation();
+    }
+
+    /// Check if speech is currently detected (for the level and VAD indicators).
     pub fn is_speech_detected(&self) -> bool {
         self.segmentation.as_ref().is_some_and(|seg| seg.is_speech_detected())
     }
 
+    /// Per-frame VAD decisions of the running segmentation, for the speech timeline.
+    pub fn subscribe_vad_decisions(&self) -> Option<Receiver<VadDecision>> {
+        self.segmentation.as_ref().map(|seg| seg.subscribe_decisions())
+    }
+
+    /// Noise-floor adaptation of the segmentation VAD, if segmentation is set up.
+    pub fn noise_floor(&self) -> Option<NoiseFloorState> {
+        self.segmentation.as_ref().map(|seg| seg.noise_floor())
+    }
+
+    /// Last `max_samples` of the in-progress segment, used for partial live captions.
+    pub fn pending_segment_tail(&self, max_samples: usize) -> Vec<f32> {
+        self.segmentation
+            .as_ref()
+            .map(|seg| seg.pending_tail(max_samples))
+            .unwrap_or_default()
+    }
+
     // === Conference Mode (unchanged) ===
 
     /// Start conference recording (mic + loopback).
-    pub fn start_conference(&self) -> Result<()> {
-        self.conference.start_conference()
+    pub fn start_conference(&self, loopback_source: Option<&str>) -> Result<()> {
+        self.conference.start_conference(loopback_source)
     }
 
-    /// Stop conference recording and return both channels.
-    pub fn stop_conference(&self) -> ConferenceRecording {
+    /// Stop conference recording and return both channels, on disk
+    /// instead if the recording outgrew `conference_spool_minutes`.
+    pub fn stop_conference(&self) -> (ConferenceRecording, Option<SpooledRecording>) {
         self.conference.stop_conference()
     }
 
-    /// Get microphone amplitude for conference mode.
-    pub fn get_mic_amplitude(&self) -> f32 {
-        self.conference.get_mic_amplitude()
+    /// Mic and loopback samples recorded so far, from sample `from` on.
+    ///
+    /// Used for live diarization while conference recording is running.
+    pub fn conference_samples_from(&self, from: usize) -> (Vec<f32>, Vec<f32>) {
+        self.conference.samples_from(from)
+    }
+
+    /// Microphone level for conference mode.
+    pub fn get_mic_level(&self) -> LevelReading {
+        self.conference.get_mic_level()
+    }
+
+    /// Microphone device status for conference mode.
+    pub fn get_mic_device_status(&self) -> CaptureDeviceStatus {
+        self.conference.mic_device_status()
     }
 
-    /// Get loopback amplitude for conference mode.
-    pub fn get_loopback_amplitude(&self) -> f32 {
-        self.conference.get_loopback_amplitude()
+    /// System audio level for conference mode.
+    pub fn get_loopback_level(&self) -> LevelReading {
+        self.conference.get_loopback_level()
     }
 }
 
@@ -171,12 +266,17 @@ mod tests {
     }
 
     #[test]
-    fn test_audio_service_amplitude() {
-        let mock = Arc::new(MockAudioRecorder::with_amplitude(0.75));
+    fn test_audio_service_level() {
+        let level = LevelReading {
+            peak_dbfs: -6.0,
+            rms_dbfs: -15.0,
+            clipped: false,
+        };
+        let mock = Arc::new(MockAudioRecorder::with_level(level));
         let samples_buf = mock.samples_buffer();
         let service = AudioService::with_recorder(mock, samples_buf, None).unwrap();
 
-        assert_eq!(service.mic_amplitude(), 0.75);
+        assert_eq!(service.mic_level(), level);
     }
 
     /// Integration test: full dictation start -> stop -> transcribe workflow.
@@ -206,19 +306,24 @@ mod tests {
         assert_eq!(text, "Привіт, світе");
     }
 
-    /// Integration test: verifies amplitude reflects recording state.
+    /// Integration test: verifies the level reflects recording state.
     #[test]
-    fn test_dictation_amplitude_during_recording() {
-        let mock = Arc::new(MockAudioRecorder::with_amplitude(0.6));
+    fn test_dictation_level_during_recording() {
+        let level = LevelReading {
+            peak_dbfs: -10.0,
+            rms_dbfs: -20.0,
+            clipped: false,
+        };
+        let mock = Arc::new(MockAudioRecorder::with_level(level));
         let samples_buf = mock.samples_buffer();
         let service = AudioService::with_recorder(mock, samples_buf, None).unwrap();
 
-        // Amplitude available before recording starts (mock returns constant)
-        assert_eq!(service.mic_amplitude(), 0.6);
+        // Level available before recording starts (mock returns constant)
+        assert_eq!(service.mic_level(), level);
 
-        // Start recording - amplitude still available
+        // Start recording - level still available
         service.start_mic().unwrap();
-        assert_eq!(service.mic_amplitude(), 0.6);
+        assert_eq!(service.mic_level(), level);
     }
 
     #[test]
@@ -0,0 +1,145 @@
+//! Per-speaker audio export.
+//!
+//! Cuts a diarized recording into one track per speaker by concatenating
+//! that speaker's turns, so each participant can be reviewed (or fed to
+//! another tool) separately.
+
+use anyhow::{Context, Result};
+use hound::{WavSpec, WavWriter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::transcription::rttm::SpeakerTurn;
+
+/// Audio of one speaker: their turns in time order, back to back.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerTrack {
+    pub speaker: String,
+    pub samples: Vec<f32>,
+}
+
+/// Concatenate each speaker's turns, in order of the speaker's first turn.
+///
+/// Turns are clamped to the audio; overlapping speech is included in
+/// every speaker's track.
+pub fn collect_tracks(samples: &[f32], turns: &[SpeakerTurn], sample_rate: u32) -> Vec<SpeakerTrack> {
+    let mut sorted: Vec<&SpeakerTurn> = turns.iter().collect();
+    sorted.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let to_sample = |secs: f64| ((secs.max(0.0) * sample_rate as f64) as usize).min(samples.len());
+    let mut tracks: Vec<SpeakerTrack> = Vec::new();
+    for turn in sorted {
+        let (start, end) = (to_sample(turn.start), to_sample(turn.end));
+        if start >= end {
+            continue;
+        }
+        let track = match tracks.iter_mut().position(|t| t.speaker == turn.speaker) {
+            Some(i) => &mut tracks[i],
+            None => {
+                tracks.push(SpeakerTrack {
+                    speaker: turn.speaker.clone(),
+                    samples: Vec::new(),
+                });
+                tracks.last_mut().expect("just pushed")
+            }
+        };
+        track.samples.extend_from_slice(&samples[start..end]);
+    }
+    tracks
+}
+
+/// File name for a speaker's track: `<stem>_<speaker>.wav`, filesystem-safe.
+pub fn track_file_name(stem: &str, speaker: &str) -> String {
+    let speaker: String = speaker
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_whitespace() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("{}_{}.wav", stem, speaker)
+}
+
+/// Write each track as a mono 32-bit float WAV into `dir`. Returns the written paths.
+pub fn write_tracks(tracks: &[SpeakerTrack], dir: &Path, stem: &str, sample_rate: u32) -> Result<Vec<PathBuf>> {
+    fs::create_dir_all(dir).with_context(|| format!("Не вдалося створити директорію: {}", dir.display()))?;
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 32,
+        sample_format: hound::SampleFormat::Float,
+    };
+
+    let mut paths = Vec::with_capacity(tracks.len());
+    for track in tracks {
+        let path = dir.join(track_file_name(stem, &track.speaker));
+        let mut writer = WavWriter::create(&path, spec)
+            .with_context(|| format!("Не вдалося створити WAV файл: {}", path.display()))?;
+        for &sample in &track.samples {
+            writer.write_sample(sample).context("Не вдалося записати зразок")?;
+        }
+        writer.finalize().context("Не вдалося завершити запис WAV файлу")?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(speaker: &str, start: f64, end: f64) -> SpeakerTurn {
+        SpeakerTurn {
+            speaker: speaker.to_string(),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn test_collect_tracks_concatenates_turns_per_speaker() {
+        // 10 samples per second
+        let samples: Vec<f32> = (0..60).map(|i| i as f32).collect();
+        let turns = vec![
+            turn("B", 2.0, 3.0),
+            turn("A", 0.0, 1.0),
+            turn("A", 4.0, 4.5),
+            turn("B", 5.5, 9.0),
+        ];
+        let tracks = collect_tracks(&samples, &turns, 10);
+
+        assert_eq!(tracks.len(), 2);
+        assert_eq!(tracks[0].speaker, "A");
+        assert_eq!(tracks[0].samples.len(), 15);
+        assert_eq!(tracks[0].samples[10], 40.0);
+        assert_eq!(tracks[1].speaker, "B");
+        // Second B turn is clamped to the end of the audio
+        assert_eq!(tracks[1].samples.len(), 15);
+        assert_eq!(tracks[1].samples[14], 59.0);
+    }
+
+    #[test]
+    fn test_track_file_name_is_filesystem_safe() {
+        assert_eq!(track_file_name("call", "Спікер 1"), "call_Спікер_1.wav");
+        assert_eq!(track_file_name("call", "a/b:c"), "call_a_b_c.wav");
+    }
+
+    #[test]
+    fn test_write_tracks_creates_mono_wavs() {
+        let dir = std::env::temp_dir().join(format!("s2t_tracks_{}", uuid::Uuid::new_v4()));
+        let tracks = vec![SpeakerTrack {
+            speaker: "Ви".to_string(),
+            samples: vec![0.1; 160],
+        }];
+        let paths = write_tracks(&tracks, &dir, "meeting", 16000).unwrap();
+
+        let reader = hound::WavReader::open(&paths[0]).unwrap();
+        assert_eq!(reader.spec().channels, 1);
+        assert_eq!(reader.len(), 160);
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,210 @@
+//! Speech gate for all-day continuous sessions.
+//!
+//! A session left running through a working day is mostly silence, yet the
+//! recorder keeps every sample in memory and each silent stretch still ends
+//! up in a segment. The gate classifies the recorder's buffer frame by frame
+//! and removes long silence from it in place, keeping the post-roll after the
+//! last speech and the pre-roll before the next. Everything downstream (ring
+//! buffer, segment positions, the final recording) then sees the gated audio.
+//!
+//! Samples are handed on ("committed") only once the gate knows they stay:
+//! speech right away, silence only up to the post-roll. The rest of a pause
+//! waits until speech resumes or the pause grows long enough to be cut.
+
+use crate::domain::traits::VoiceDetection;
+use std::ops::Range;
+
+/// Result of one [`SpeechGate::process`] call.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GateOutput {
+    /// Newly committed samples, as positions in the buffer after gating.
+    pub committed: Range<usize>,
+    /// A pause after speech became long enough to be cut; the speech before it has ended.
+    pub speech_ended: bool,
+}
+
+/// Streaming gate that drops long silence from a growing buffer.
+pub struct SpeechGate {
+    frame_samples: usize,
+    pre_roll: usize,
+    post_roll: usize,
+    /// Pause that ends speech and gets cut.
+    min_silence: usize,
+    /// Samples of the buffer classified so far.
+    classified: usize,
+    /// Samples of the buffer handed on so far.
+    committed: usize,
+    /// Start of the pause at the end of the buffer, if any.
+    silence_start: Option<usize>,
+    /// Whether the current pause was already cut.
+    silence_cut: bool,
+    /// Whether anything but silence was heard since the gate started.
+    heard_speech: bool,
+    removed: usize,
+}
+
+impl SpeechGate {
+    /// Gate with sizes in samples; pauses longer than `min_silence` are cut to the padding.
+    pub fn new(frame_samples: usize, pre_roll: usize, post_roll: usize, min_silence: usize) -> Self {
+        Self {
+            frame_samples: frame_samples.max(1),
+            pre_roll,
+            post_roll,
+            min_silence: min_silence.max(pre_roll + post_roll),
+            classified: 0,
+            committed: 0,
+            silence_start: None,
+            silence_cut: false,
+            heard_speech: false,
+            removed: 0,
+        }
+    }
+
+    /// Classify the new complete frames of `buffer`, drop long silence from it
+    /// and report which samples are final.
+    ///
+    /// `buffer` must only have grown since the last call.
+    pub fn process(&mut self, buffer: &mut Vec<f32>, vad: &dyn VoiceDetection) -> GateOutput {
+        let committed_from = self.committed;
+        let mut speech_ended = false;
+
+        while self.classified + self.frame_samples <= buffer.len() {
+            let frame = &buffer[self.classified..self.classified + self.frame_samples];
+            let is_speech = vad.is_speech(frame).unwrap_or(false);
+            self.classified += self.frame_samples;
+
+            if is_speech {
+                self.heard_speech = true;
+                self.silence_start = None;
+                self.silence_cut = false;
+                continue;
+            }
+            let start = *self.silence_start.get_or_insert(self.classified - self.frame_samples);
+            let limit = if self.silence_cut {
+                self.pre_roll + self.post_roll
+            } else {
+                self.min_silence
+            };
+            if self.classified - start > limit {
+                // Keep the post-roll after the speech and the latest pre-roll
+                let cut = start + self.post_roll..self.classified - self.pre_roll;
+                let cut_len = cut.len();
+                buffer.drain(cut);
+                self.classified -= cut_len;
+                self.removed += cut_len;
+                speech_ended |= !self.silence_cut && self.heard_speech;
+                self.silence_cut = true;
+            }
+        }
+
+        let commit_to = match self.silence_start {
+            Some(start) => self.classified.min(start + self.post_roll),
+            None => self.classified,
+        };
+        self.committed = self.committed.max(commit_to);
+        GateOutput {
+            committed: committed_from..self.committed,
+            speech_ended,
+        }
+    }
+
+    /// Samples dropped from the buffer so far.
+    pub fn removed(&self) -> usize {
+        self.removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    /// Treats frames of non-zero samples as speech.
+    struct LevelVad;
+
+    impl VoiceDetection for LevelVad {
+        fn is_speech(&self, samples: &[f32]) -> Result<bool> {
+            Ok(samples.iter().any(|&s| s != 0.0))
+        }
+        fn detect_speech_end(&self, _samples: &[f32]) -> Result<bool> {
+            Ok(false)
+        }
+        fn reset(&self) {}
+    }
+
+    fn gate() -> SpeechGate {
+        // Frames of 10, pre-roll 20, post-roll 30, pauses over 100 are cut
+        SpeechGate::new(10, 20, 30, 100)
+    }
+
+    #[test]
+    fn test_short_pause_is_kept() {
+        let mut gate = gate();
+        let mut buffer = vec![1.0; 50];
+        buffer.extend(vec![0.0; 80]);
+        buffer.extend(vec![1.0; 20]);
+
+        let output = gate.process(&mut buffer, &LevelVad);
+        assert_eq!(buffer.len(), 150);
+        assert_eq!(output.committed, 0..150);
+        assert!(!output.speech_ended);
+        assert_eq!(gate.removed(), 0);
+    }
+
+    #[test]
+    fn test_long_pause_is_cut_to_padding() {
+        let mut gate = gate();
+        let mut buffer = vec![1.0; 50];
+        buffer.extend(vec![0.0; 500]);
+
+        let output = gate.process(&mut buffer, &LevelVad);
+        assert!(output.speech_ended);
+        // Speech, post-roll, and at most pre-roll plus a frame of the pause
+        assert!(buffer.len() <= 50 + 30 + 20 + 10);
+        assert_eq!(gate.removed(), 550 - buffer.len());
+        // Only the post-roll of the pause is final
+        assert_eq!(output.committed, 0..80);
+
+        // Speech resumes: the pre-roll before it is committed with it
+        buffer.extend(vec![1.0; 20]);
+        let output = gate.process(&mut buffer, &LevelVad);
+        assert!(!output.speech_ended);
+        assert_eq!(output.committed, 80..buffer.len());
+        assert_eq!(&buffer[buffer.len() - 40..buffer.len() - 20], &[0.0; 20]);
+    }
+
+    #[test]
+    fn test_pause_cut_across_calls() {
+        let mut gate = gate();
+        let mut buffer = vec![1.0; 50];
+        let mut ended = false;
+        for _ in 0..20 {
+            buffer.extend(vec![0.0; 35]);
+            ended |= gate.process(&mut buffer, &LevelVad).speech_ended;
+        }
+        assert!(ended);
+        assert!(buffer.len() <= 50 + 30 + 20 + 35);
+        assert_eq!(gate.removed(), 50 + 20 * 35 - buffer.len());
+    }
+
+    #[test]
+    fn test_partial_frame_waits() {
+        let mut gate = gate();
+        let mut buffer = vec![1.0; 25];
+        let output = gate.process(&mut buffer, &LevelVad);
+        assert_eq!(output.committed, 0..20);
+
+        buffer.extend(vec![1.0; 5]);
+        let output = gate.process(&mut buffer, &LevelVad);
+        assert_eq!(output.committed, 20..30);
+    }
+
+    #[test]
+    fn test_silence_before_any_speech_does_not_end_speech() {
+        let mut gate = gate();
+        let mut buffer = vec![0.0; 500];
+        let output = gate.process(&mut buffer, &LevelVad);
+        assert!(!output.speech_ended);
+        assert!(buffer.len() <= 30 + 20 + 10);
+    }
+}
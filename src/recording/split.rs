@@ -10,6 +10,7 @@
 //! Used by both streaming (`SegmentationMonitor`) and batch (`AudioChunker`) modes.
 
 use crate::domain::traits::VoiceDetection;
+use std::ops::Range;
 use std::time::Duration;
 
 /// A detected silence region in audio.
@@ -43,6 +44,10 @@ pub struct SplitConfig {
     pub min_segment_secs: u32,
     /// Overlap duration for force-splits (to reduce boundary artifacts).
     pub overlap_secs: u32,
+    /// Audio kept before the first detected speech of a segment.
+    pub pre_roll_ms: u32,
+    /// Audio kept after the last detected speech of a segment.
+    pub post_roll_ms: u32,
     /// Audio sample rate.
     pub sample_rate: u32,
 }
@@ -55,6 +60,8 @@ impl Default for SplitConfig {
             max_segment_secs: 300,
             min_segment_secs: 1,
             overlap_secs: 2,
+            pre_roll_ms: 300,
+            post_roll_ms: 300,
             sample_rate: 16000,
         }
     }
@@ -72,6 +79,10 @@ impl SplitConfig {
     fn overlap_samples(&self) -> usize {
         self.overlap_secs as usize * self.sample_rate as usize
     }
+
+    fn ms_to_samples(&self, ms: u32) -> usize {
+        ms as usize * self.sample_rate as usize / 1000
+    }
 }
 
 /// Result of a split decision.
@@ -161,6 +172,27 @@ impl SplitFinder {
         regions
     }
 
+    /// Range of a segment from its first to its last speech frame, widened by
+    /// the pre- and post-roll so soft word onsets and endings the VAD misses
+    /// are kept. `None` when the VAD finds no speech at all.
+    pub fn padded_speech_range(&self, samples: &[f32], vad: &dyn VoiceDetection) -> Option<Range<usize>> {
+        let frame_size = (self.config.sample_rate * VAD_FRAME_MS / 1000) as usize;
+        let frames = samples.len() / frame_size;
+        let is_speech = |i: usize| {
+            vad.is_speech(&samples[i * frame_size..(i + 1) * frame_size])
+                .unwrap_or(false)
+        };
+
+        let first = (0..frames).find(|&i| is_speech(i))?;
+        let last = (first + 1..frames).rev().find(|&i| is_speech(i)).unwrap_or(first);
+        // Frames were scanned out of order; do not leave that in the VAD state
+        vad.reset();
+
+        let start = (first * frame_size).saturating_sub(self.config.ms_to_samples(self.config.pre_roll_ms));
+        let end = ((last + 1) * frame_size + self.config.ms_to_samples(self.config.post_roll_ms)).min(samples.len());
+        Some(start..end)
+    }
+
     /// Find best split point within a sample range using the cascade:
     ///
     /// 1. Longest silence >= `semantic_silence_ms` (prefer later = larger segment)
@@ -508,6 +540,8 @@ mod tests {
         assert_eq!(config.max_segment_secs, 300);
         assert_eq!(config.min_segment_secs, 1);
         assert_eq!(config.overlap_secs, 2);
+        assert_eq!(config.pre_roll_ms, 300);
+        assert_eq!(config.post_roll_ms, 300);
         assert_eq!(config.sample_rate, 16000);
     }
 
@@ -532,4 +566,32 @@ mod tests {
         assert_eq!(silences.len(), 1);
         assert_eq!(silences[0].end_sample, audio.len());
     }
+
+    #[test]
+    fn test_padded_speech_range() {
+        let finder = SplitFinder::new(SplitConfig {
+            pre_roll_ms: 300,
+            post_roll_ms: 600,
+            ..Default::default()
+        });
+        let vad = MockVad::new(0.01);
+        // Frame-aligned: 96 frames of silence, 50 of speech, 100 of silence
+        let mut audio = make_silence(46080);
+        audio.extend(make_speech(24000));
+        audio.extend(make_silence(48000));
+
+        let range = finder.padded_speech_range(&audio, &vad).unwrap();
+        assert_eq!(range, 46080 - 4800..70080 + 9600);
+        assert_eq!(*vad.reset_count.borrow(), 1);
+    }
+
+    #[test]
+    fn test_padded_speech_range_clamps_to_audio() {
+        let finder = SplitFinder::new(SplitConfig::default());
+        let vad = MockVad::new(0.01);
+        let mut audio = make_speech(16000);
+        audio.extend(make_silence(1000));
+        assert_eq!(finder.padded_speech_range(&audio, &vad), Some(0..audio.len()));
+        assert_eq!(finder.padded_speech_range(&make_silence(16000), &vad), None);
+    }
 }
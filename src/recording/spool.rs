@@ -0,0 +1,318 @@
+//! Disk spill of long conference recordings.
+//!
+//! A conference recording keeps both channels in memory as 16 kHz f32,
+//! about 230 MB an hour. Past a configured length the recorder moves
+//! everything but the last minute to files in the cache directory, and
+//! after stopping the recording is saved and transcribed from those files
+//! window by window, so memory stays bounded however long the call runs.
+
+use crate::app::config::spool_dir;
+use anyhow::{Context, Result};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::ops::Range;
+use std::os::unix::fs::{FileExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+
+/// Samples per second of spooled audio.
+const SAMPLE_RATE: usize = 16000;
+
+/// Samples in memory a recorder keeps when spilling: live diarization
+/// still reads the last block
+pub const KEEP_IN_MEMORY: usize = 60 * SAMPLE_RATE;
+
+/// Length of the windows a spooled recording is transcribed in
+const WINDOW_SAMPLES: usize = 10 * 60 * SAMPLE_RATE;
+
+/// Stretch at the end of a window searched for a pause to cut at
+const CUT_SEARCH_SAMPLES: usize = 30 * SAMPLE_RATE;
+
+/// Frame whose energy is compared when looking for the pause (100 ms)
+const CUT_FRAME_SAMPLES: usize = SAMPLE_RATE / 10;
+
+/// Append-only file of f32 samples, removed when dropped.
+pub struct SampleSpool {
+    writer: BufWriter<File>,
+    reader: File,
+    path: PathBuf,
+    len: usize,
+}
+
+impl SampleSpool {
+    /// Create an empty spool in the spool directory.
+    pub fn create() -> Result<Self> {
+        let dir = spool_dir();
+        fs::create_dir_all(&dir).with_context(|| format!("Не вдалося створити директорію: {}", dir.display()))?;
+        let path = dir.join(format!("{}.f32", uuid::Uuid::new_v4()));
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(&path)
+            .with_context(|| format!("Не вдалося створити файл: {}", path.display()))?;
+        let reader = file.try_clone().context("Не вдалося відкрити файл для читання")?;
+        Ok(Self {
+            writer: BufWriter::new(file),
+            reader,
+            path,
+            len: 0,
+        })
+    }
+
+    /// Add samples at the end.
+    pub fn append(&mut self, samples: &[f32]) -> Result<()> {
+        for sample in samples {
+            self.writer
+                .write_all(&sample.to_le_bytes())
+                .with_context(|| format!("Не вдалося записати у файл: {}", self.path.display()))?;
+        }
+        self.len += samples.len();
+        Ok(())
+    }
+
+    /// Number of samples written so far.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Samples in `range`, cut off at the end of the spool.
+    pub fn read(&mut self, range: Range<usize>) -> Result<Vec<f32>> {
+        let end = range.end.min(self.len);
+        if range.start >= end {
+            return Ok(Vec::new());
+        }
+        self.writer.flush().context("Не вдалося дописати файл")?;
+
+        let mut bytes = vec![0u8; (end - range.start) * 4];
+        self.reader
+            .read_exact_at(&mut bytes, (range.start * 4) as u64)
+            .with_context(|| format!("Не вдалося прочитати файл: {}", self.path.display()))?;
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect())
+    }
+}
+
+impl Drop for SampleSpool {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// Remove spool files left behind by a session that crashed or was killed
+/// mid-recording. Call once at startup, before any recording begins.
+pub fn remove_stale() -> usize {
+    remove_stale_in(&spool_dir())
+}
+
+fn remove_stale_in(dir: &Path) -> usize {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "f32"))
+        .filter(|path| fs::remove_file(path).is_ok())
+        .count()
+}
+
+/// Both channels of a conference recording that went to disk.
+pub struct SpooledRecording {
+    pub mic: SampleSpool,
+    pub loopback: SampleSpool,
+}
+
+/// Part of a spooled recording, starting at `start` samples into it.
+pub struct RecordingWindow {
+    pub start: usize,
+    pub mic: Vec<f32>,
+    pub loopback: Vec<f32>,
+}
+
+impl SpooledRecording {
+    pub fn len(&self) -> usize {
+        self.mic.len().max(self.loopback.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn duration_secs(&self) -> f32 {
+        self.len() as f32 / SAMPLE_RATE as f32
+    }
+
+    /// Both channels over `range`.
+    pub fn read(&mut self, range: Range<usize>) -> Result<(Vec<f32>, Vec<f32>)> {
+        Ok((self.mic.read(range.clone())?, self.loopback.read(range)?))
+    }
+
+    /// The recording in consecutive windows of about ten minutes, each
+    /// ending at the quietest moment near its end so no word is cut.
+    pub fn windows(&mut self) -> Windows<'_> {
+        Windows {
+            recording: self,
+            start: 0,
+        }
+    }
+}
+
+/// Iterator over the windows of a [`SpooledRecording`].
+pub struct Windows<'a> {
+    recording: &'a mut SpooledRecording,
+    start: usize,
+}
+
+impl Iterator for Windows<'_> {
+    type Item = Result<RecordingWindow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let total = self.recording.len();
+        if self.start >= total {
+            return None;
+        }
+        let end = (self.start + WINDOW_SAMPLES).min(total);
+        let (mut mic, mut loopback) = match self.recording.read(self.start..end) {
+            Ok(channels) => channels,
+            Err(e) => {
+                // A read error would repeat on every further window
+                self.start = total;
+                return Some(Err(e));
+            }
+        };
+
+        let len = end - self.start;
+        let len = if end < total {
+            quiet_cut(&mic, &loopback, len)
+        } else {
+            len
+        };
+        mic.truncate(len);
+        loopback.truncate(len);
+
+        let window = RecordingWindow {
+            start: self.start,
+            mic,
+            loopback,
+        };
+        self.start += len;
+        Some(Ok(window))
+    }
+}
+
+/// Where to end a window of `len` samples: after the frame with the least
+/// energy in both channels within its last [`CUT_SEARCH_SAMPLES`].
+fn quiet_cut(mic: &[f32], loopback: &[f32], len: usize) -> usize {
+    let energy = |channel: &[f32], frame: Range<usize>| -> f32 {
+        channel
+            .get(frame.start.min(channel.len())..frame.end.min(channel.len()))
+            .map_or(0.0, |samples| samples.iter().map(|s| s * s).sum())
+    };
+
+    let search_from = len.saturating_sub(CUT_SEARCH_SAMPLES);
+    (search_from..len.saturating_sub(CUT_FRAME_SAMPLES - 1))
+        .step_by(CUT_FRAME_SAMPLES)
+        .map(|start| {
+            let frame = start..start + CUT_FRAME_SAMPLES;
+            (start, energy(mic, frame.clone()) + energy(loopback, frame))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        // Cut in the middle of the pause
+        .map_or(len, |(start, _)| start + CUT_FRAME_SAMPLES / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spool_reads_back_what_was_appended() {
+        let mut spool = SampleSpool::create().unwrap();
+        spool.append(&[0.5, -0.25]).unwrap();
+        spool.append(&[1.0]).unwrap();
+        assert_eq!(spool.len(), 3);
+        assert_eq!(spool.read(0..3).unwrap(), vec![0.5, -0.25, 1.0]);
+        assert_eq!(spool.read(1..10).unwrap(), vec![-0.25, 1.0]);
+        assert!(spool.read(5..8).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_spool_file_is_removed_on_drop() {
+        let spool = SampleSpool::create().unwrap();
+        let path = spool.path.clone();
+        assert!(path.exists());
+        drop(spool);
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn test_spool_file_is_private() {
+        use std::os::unix::fs::PermissionsExt;
+        let spool = SampleSpool::create().unwrap();
+        let mode = fs::metadata(&spool.path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn test_remove_stale_deletes_only_spool_files() {
+        let dir = std::env::temp_dir().join(format!("s2t-spool-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("a.f32"), [0u8; 8]).unwrap();
+        fs::write(dir.join("b.f32"), [0u8; 8]).unwrap();
+        fs::write(dir.join("notes.txt"), "keep").unwrap();
+
+        assert_eq!(remove_stale_in(&dir), 2);
+        assert!(dir.join("notes.txt").exists());
+        assert!(!dir.join("a.f32").exists());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_windows_cover_the_recording_and_cut_at_pauses() {
+        // 25 minutes of "speech" with a pause a little before each 10-minute mark
+        let total = 25 * 60 * SAMPLE_RATE;
+        let pause = |i: usize| {
+            let in_window = i % WINDOW_SAMPLES;
+            (WINDOW_SAMPLES - 10 * SAMPLE_RATE..WINDOW_SAMPLES - 10 * SAMPLE_RATE + SAMPLE_RATE).contains(&in_window)
+        };
+        let mut recording = SpooledRecording {
+            mic: SampleSpool::create().unwrap(),
+            loopback: SampleSpool::create().unwrap(),
+        };
+        for chunk_start in (0..total).step_by(SAMPLE_RATE) {
+            let chunk: Vec<f32> = (chunk_start..chunk_start + SAMPLE_RATE)
+                .map(|i| if pause(i) { 0.0 } else { 0.3 })
+                .collect();
+            recording.mic.append(&chunk).unwrap();
+        }
+
+        let windows: Vec<RecordingWindow> = recording.windows().map(Result::unwrap).collect();
+        assert_eq!(windows.len(), 3);
+        let mut expected_start = 0;
+        for window in &windows {
+            assert_eq!(window.start, expected_start);
+            assert!(window.mic.len() <= WINDOW_SAMPLES);
+            assert!(window.loopback.is_empty());
+            expected_start += window.mic.len();
+        }
+        assert_eq!(expected_start, total);
+
+        // The first cut falls into the pause before the 10-minute mark
+        let first_cut = windows[0].mic.len();
+        assert!(pause(first_cut), "cut at {} s", first_cut as f32 / SAMPLE_RATE as f32);
+    }
+
+    #[test]
+    fn test_quiet_cut_without_pause_stays_in_search_range() {
+        let mic = vec![0.3; 2 * CUT_SEARCH_SAMPLES];
+        let cut = quiet_cut(&mic, &[], mic.len());
+        assert!(cut >= mic.len() - CUT_SEARCH_SAMPLES && cut <= mic.len());
+    }
+}
@@ -6,6 +6,7 @@
 use crate::domain::traits::{
     AudioDenoising, AudioRecording, ConfigProvider, HistoryRepository, Transcription, UIStateUpdater, VoiceDetection,
 };
+use crate::domain::types::{HistoryOrder, LevelReading, TranscriptSegment};
 use crate::history::HistoryEntry;
 use anyhow::Result;
 use async_channel::Receiver;
@@ -13,6 +14,13 @@ use parking_lot::Mutex;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+/// Level a mock recorder reports unless told otherwise: ordinary speech.
+const SPEECH_LEVEL: LevelReading = LevelReading {
+    peak_dbfs: -12.0,
+    rms_dbfs: -18.0,
+    clipped: false,
+};
+
 /// Mock audio recorder for testing.
 ///
 /// Returns predefined samples and tracks recording state.
@@ -20,7 +28,7 @@ use std::sync::Arc;
 pub struct MockAudioRecorder {
     is_recording: AtomicBool,
     samples_to_return: Mutex<Vec<f32>>,
-    amplitude: f32,
+    level: LevelReading,
     samples_buffer: Arc<Mutex<Vec<f32>>>,
 }
 
@@ -30,7 +38,7 @@ impl MockAudioRecorder {
         Self {
             is_recording: AtomicBool::new(false),
             samples_to_return: Mutex::new(vec![0.0; 16000]),
-            amplitude: 0.5,
+            level: SPEECH_LEVEL,
             samples_buffer: Arc::new(Mutex::new(Vec::new())),
         }
     }
@@ -40,17 +48,17 @@ impl MockAudioRecorder {
         Self {
             is_recording: AtomicBool::new(false),
             samples_to_return: Mutex::new(samples),
-            amplitude: 0.5,
+            level: SPEECH_LEVEL,
             samples_buffer: Arc::new(Mutex::new(Vec::new())),
         }
     }
 
-    /// Create a mock recorder with custom amplitude.
-    pub fn with_amplitude(amplitude: f32) -> Self {
+    /// Create a mock recorder with a custom level.
+    pub fn with_level(level: LevelReading) -> Self {
         Self {
             is_recording: AtomicBool::new(false),
             samples_to_return: Mutex::new(vec![0.0; 16000]),
-            amplitude,
+            level,
             samples_buffer: Arc::new(Mutex::new(Vec::new())),
         }
     }
@@ -85,8 +93,8 @@ impl AudioRecording for MockAudioRecorder {
         (samples, None)
     }
 
-    fn amplitude(&self) -> f32 {
-        self.amplitude
+    fn level(&self) -> LevelReading {
+        self.level
     }
 
     fn is_recording(&self) -> bool {
@@ -312,13 +320,17 @@ impl AudioDenoising for MockDenoiser {
 #[allow(dead_code)]
 pub struct MockHistoryRepository {
     entries: Vec<HistoryEntry>,
+    trash: Vec<HistoryEntry>,
 }
 
 impl MockHistoryRepository {
     /// Create an empty mock history.
     #[allow(dead_code)]
     pub fn new() -> Self {
-        Self { entries: Vec::new() }
+        Self {
+            entries: Vec::new(),
+            trash: Vec::new(),
+        }
     }
 }
 
@@ -368,6 +380,59 @@ impl HistoryRepository for MockHistoryRepository {
         self.entries.retain(|e| e.id != id);
     }
 
+    fn trash(&mut self, id: &str) -> bool {
+        let Some(position) = self.entries.iter().position(|e| e.id == id) else {
+            return false;
+        };
+        let mut entry = self.entries.remove(position);
+        entry.deleted_at = Some(chrono::Utc::now());
+        self.trash.insert(0, entry);
+        true
+    }
+
+    fn restore(&mut self, id: &str) -> bool {
+        let Some(position) = self.trash.iter().position(|e| e.id == id) else {
+            return false;
+        };
+        let mut entry = self.trash.remove(position);
+        entry.deleted_at = None;
+        self.entries.insert(0, entry);
+        true
+    }
+
+    fn import(&mut self, entries: Vec<HistoryEntry>) -> usize {
+        let before = self.entries.len();
+        for entry in entries {
+            if !self.entries.iter().chain(&self.trash).any(|e| e.id == entry.id) {
+                self.entries.push(entry);
+            }
+        }
+        self.entries.len() - before
+    }
+
+    fn replace_entries(&mut self, entries: Vec<HistoryEntry>) -> bool {
+        (self.trash, self.entries) = entries.into_iter().partition(|e| e.deleted_at.is_some());
+        true
+    }
+
+    fn trashed(&self) -> &[HistoryEntry] {
+        &self.trash
+    }
+
+    fn purge_trash(&mut self, max_age_days: u32) -> usize {
+        let cutoff = chrono::Utc::now() - chrono::Duration::days(max_age_days as i64);
+        let before = self.trash.len();
+        self.trash
+            .retain(|e| e.deleted_at.is_some_and(|deleted| deleted > cutoff));
+        before - self.trash.len()
+    }
+
+    fn sorted(&self, order: HistoryOrder) -> Vec<&HistoryEntry> {
+        let mut entries: Vec<&HistoryEntry> = self.entries.iter().collect();
+        order.sort(&mut entries);
+        entries
+    }
+
     fn filter_by_date_range(
         &self,
         from: Option<chrono::DateTime<chrono::Utc>>,
@@ -390,6 +455,84 @@ impl HistoryRepository for MockHistoryRepository {
             })
             .collect()
     }
+
+    fn merge(&mut self, ids: &[String], separator: &str) -> Option<String> {
+        let selected: Vec<&HistoryEntry> = self.entries.iter().filter(|e| ids.contains(&e.id)).collect();
+        if selected.len() < 2 {
+            return None;
+        }
+        let merged = HistoryEntry::merged(&selected, separator)?;
+        let merged_id = merged.id.clone();
+
+        // Keep the merged entry where the newest original was
+        let position = self.entries.iter().position(|e| ids.contains(&e.id)).unwrap_or(0);
+        self.entries.retain(|e| !ids.contains(&e.id));
+        self.entries.insert(position.min(self.entries.len()), merged);
+        Some(merged_id)
+    }
+
+    fn rename_speakers(&mut self, id: &str, renames: &[(String, String)]) -> bool {
+        self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .is_some_and(|e| e.rename_speakers(renames))
+    }
+
+    fn reassign_turn(&mut self, id: &str, index: usize, speaker: &str) -> bool {
+        self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .is_some_and(|e| e.reassign_turn(index, speaker))
+    }
+
+    fn set_tags(&mut self, id: &str, tags: &[String]) -> bool {
+        self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .is_some_and(|e| e.set_tags(tags))
+    }
+
+    fn all_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self.entries.iter().flat_map(|e| e.tags.clone()).collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    fn is_read_only(&self) -> bool {
+        false
+    }
+
+    fn set_text(&mut self, id: &str, text: &str) -> bool {
+        self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .is_some_and(|e| e.edit_text(text))
+    }
+
+    fn replace_transcription(
+        &mut self,
+        id: &str,
+        text: &str,
+        speakers: &[String],
+        segments: &[TranscriptSegment],
+        model: &str,
+    ) -> bool {
+        self.entries
+            .iter_mut()
+            .find(|e| e.id == id)
+            .is_some_and(|e| e.replace_transcription(text, speakers, segments, model))
+    }
+
+    fn set_pinned(&mut self, id: &str, pinned: bool) -> bool {
+        match self.entries.iter_mut().find(|e| e.id == id) {
+            Some(entry) if entry.pinned != pinned => {
+                entry.pinned = pinned;
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 /// Mock UI state updater for testing recording handlers without GTK.
@@ -488,9 +631,14 @@ mod tests {
     }
 
     #[test]
-    fn test_mock_recorder_amplitude() {
-        let recorder = MockAudioRecorder::with_amplitude(0.75);
-        assert_eq!(recorder.amplitude(), 0.75);
+    fn test_mock_recorder_level() {
+        let level = LevelReading {
+            peak_dbfs: -3.0,
+            rms_dbfs: -9.0,
+            clipped: true,
+        };
+        let recorder = MockAudioRecorder::with_level(level);
+        assert_eq!(recorder.level(), level);
     }
 
     #[test]
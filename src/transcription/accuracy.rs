@@ -0,0 +1,102 @@
+//! Transcription accuracy metrics (WER / CER) against a reference transcript.
+//!
+//! Both texts are normalized before comparison: lowercased, punctuation
+//! removed (apostrophes and hyphens inside words are kept), whitespace
+//! collapsed.
+
+/// Normalize text for accuracy comparison.
+pub fn normalize(text: &str) -> String {
+    let cleaned: String = text
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '\'' || c == '’' || c == '-' {
+                c
+            } else {
+                ' '
+            }
+        })
+        .collect();
+    cleaned
+        .split_whitespace()
+        .map(|w| w.trim_matches(['-', '\'', '’']).to_lowercase())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Levenshtein distance between two token sequences.
+fn edit_distance<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> usize {
+    let mut prev: Vec<usize> = (0..=hypothesis.len()).collect();
+    let mut curr = vec![0; hypothesis.len() + 1];
+
+    for (i, r) in reference.iter().enumerate() {
+        curr[0] = i + 1;
+        for (j, h) in hypothesis.iter().enumerate() {
+            let substitution = prev[j] + usize::from(r != h);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[hypothesis.len()]
+}
+
+fn error_rate<T: PartialEq>(reference: &[T], hypothesis: &[T]) -> f64 {
+    if reference.is_empty() {
+        return if hypothesis.is_empty() { 0.0 } else { 1.0 };
+    }
+    edit_distance(reference, hypothesis) as f64 / reference.len() as f64
+}
+
+/// Word error rate of `hypothesis` against `reference` (0.0 = perfect).
+///
+/// Can exceed 1.0 when the hypothesis has many insertions.
+pub fn word_error_rate(reference: &str, hypothesis: &str) -> f64 {
+    let reference = normalize(reference);
+    let hypothesis = normalize(hypothesis);
+    let ref_words: Vec<&str> = reference.split_whitespace().collect();
+    let hyp_words: Vec<&str> = hypothesis.split_whitespace().collect();
+    error_rate(&ref_words, &hyp_words)
+}
+
+/// Character error rate of `hypothesis` against `reference` (0.0 = perfect).
+pub fn char_error_rate(reference: &str, hypothesis: &str) -> f64 {
+    let ref_chars: Vec<char> = normalize(reference).chars().collect();
+    let hyp_chars: Vec<char> = normalize(hypothesis).chars().collect();
+    error_rate(&ref_chars, &hyp_chars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("Привіт, Світе!  Як справи?"), "привіт світе як справи");
+        assert_eq!(normalize("пам'ять — це будь-що"), "пам'ять це будь-що");
+    }
+
+    #[test]
+    fn test_wer_identical_ignores_case_and_punctuation() {
+        assert_eq!(word_error_rate("Привіт, світе.", "привіт світе"), 0.0);
+    }
+
+    #[test]
+    fn test_wer_substitution_deletion_insertion() {
+        assert_eq!(word_error_rate("a b c d", "a x c d"), 0.25);
+        assert_eq!(word_error_rate("a b c d", "a b c"), 0.25);
+        assert_eq!(word_error_rate("a b", "a b c d"), 1.0);
+    }
+
+    #[test]
+    fn test_cer() {
+        assert_eq!(char_error_rate("кіт", "кит"), 1.0 / 3.0);
+        assert_eq!(char_error_rate("abc", "abc"), 0.0);
+    }
+
+    #[test]
+    fn test_empty_reference() {
+        assert_eq!(word_error_rate("", ""), 0.0);
+        assert_eq!(word_error_rate("", "щось"), 1.0);
+    }
+}
@@ -0,0 +1,529 @@
+//! Action item and decision extraction from meeting transcripts.
+//!
+//! The rule-based detector looks for Ukrainian and English commitment
+//! phrases ("Андрій підготує звіт до п'ятниці", "треба оновити договір",
+//! "домовились перенести реліз") sentence by sentence, keeping track of the
+//! `[Speaker]` labels produced by diarization so first-person promises are
+//! attributed to whoever said them. An OpenAI-compatible chat endpoint
+//! (e.g. a local Ollama server) can be configured instead; the rules are
+//! used whenever it is unavailable.
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+use crate::app::config::Config;
+use crate::domain::types::{ActionItem, ActionItemKind};
+use crate::transcription::diarization::OVERLAP_MARKER;
+
+/// Environment variable with a bearer token for the LLM endpoint.
+pub const LLM_API_KEY_ENV: &str = "S2T_LLM_API_KEY";
+
+/// Stems of verbs that usually describe a task when used in the future tense.
+const UK_ACTION_STEMS: &[&str] = &[
+    "підготу",
+    "зроб",
+    "надішл",
+    "відправ",
+    "напиш",
+    "перевір",
+    "подзвон",
+    "зателефону",
+    "організу",
+    "створ",
+    "онов",
+    "узгод",
+    "передаст",
+    "передам",
+    "внес",
+    "заплану",
+    "розбер",
+    "з'ясу",
+    "допрацю",
+    "заверш",
+    "закінч",
+    "вишл",
+    "скин",
+    "домовит",
+    "нагада",
+    "виправ",
+    "підготов",
+];
+
+/// Words that make the rest of the sentence an obligation.
+const UK_OBLIGATION_MARKERS: &[&str] = &[
+    "треба",
+    "потрібно",
+    "необхідно",
+    "мусимо",
+    "мусить",
+    "повинен",
+    "повинна",
+    "повинні",
+];
+
+/// Forms of "мати" that mean "have to" when followed by an infinitive.
+const UK_HAVE_TO: &[&str] = &["маю", "маєш", "має", "маємо", "маєте", "мають"];
+
+const UK_DECISION_MARKERS: &[&str] = &[
+    "вирішили",
+    "вирішено",
+    "домовились",
+    "домовилися",
+    "погодили",
+    "погодились",
+    "погодилися",
+    "затвердили",
+    "затверджено",
+    "ухвалили",
+    "ухвалено",
+];
+
+const EN_ACTION_MARKERS: &[&str] = &[
+    "will",
+    "'ll",
+    "needs to",
+    "need to",
+    "has to",
+    "have to",
+    "must",
+    "action item",
+];
+
+const EN_DECISION_MARKERS: &[&str] = &["decided", "agreed", "decision is", "we'll go with"];
+
+/// Pronouns and sentence openers that are capitalized but never an assignee.
+const NOT_NAMES: &[&str] = &[
+    "я",
+    "ти",
+    "ви",
+    "ми",
+    "він",
+    "вона",
+    "вони",
+    "воно",
+    "хто",
+    "це",
+    "то",
+    "добре",
+    "гаразд",
+    "тоді",
+    "також",
+    "потім",
+    "далі",
+    "так",
+    "ну",
+    "i",
+    "you",
+    "we",
+    "he",
+    "she",
+    "they",
+    "it",
+    "who",
+    "this",
+    "that",
+    "then",
+    "also",
+    "ok",
+    "so",
+];
+
+const UK_WEEKDAYS: &[&str] = &[
+    "понеділка",
+    "вівторка",
+    "середи",
+    "четверга",
+    "п'ятниці",
+    "суботи",
+    "неділі",
+];
+
+const RELATIVE_DEADLINES: &[&str] = &[
+    "післязавтра",
+    "завтра",
+    "сьогодні",
+    "до кінця дня",
+    "до кінця тижня",
+    "до кінця місяця",
+    "наступного тижня",
+    "на наступному тижні",
+    "tomorrow",
+    "today",
+    "next week",
+    "end of the week",
+    "end of day",
+];
+
+const EN_WEEKDAYS: &[&str] = &[
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+
+/// Extract action items and decisions, preferring the LLM endpoint if configured.
+///
+/// LLM failures are logged and fall back to the rule-based detector.
+pub fn extract_action_items(text: &str, config: &Config) -> Vec<ActionItem> {
+    if let Some(url) = config.action_items_llm_url.as_deref() {
+        let api_key = std::env::var(LLM_API_KEY_ENV).ok();
+        match extract_with_llm(text, url, &config.action_items_llm_model, api_key.as_deref()) {
+            Ok(items) => return items,
+            Err(e) => eprintln!("Помилка LLM для завдань, використовуються правила: {:#}", e),
+        }
+    }
+    detect_action_items(text)
+}
+
+/// Put a summary of the items above the transcript; `text` as is if there are none.
+pub fn prepend_summary(text: &str, items: &[ActionItem]) -> String {
+    if items.is_empty() {
+        return text.to_string();
+    }
+    let mut result = String::from("Завдання та рішення:\n");
+    for item in items {
+        result.push_str(&format!("- {}\n", item.formatted()));
+    }
+    result.push('\n');
+    result.push_str(text);
+    result
+}
+
+/// Rule-based detection over sentences of a (possibly diarized) transcript.
+pub fn detect_action_items(text: &str) -> Vec<ActionItem> {
+    let mut items: Vec<ActionItem> = Vec::new();
+    for (speaker, sentence) in sentences(text) {
+        if sentence.ends_with('?') {
+            continue;
+        }
+        let Some(item) = classify_sentence(&sentence, speaker.as_deref()) else {
+            continue;
+        };
+        if !items.iter().any(|existing| existing.text == item.text) {
+            items.push(item);
+        }
+    }
+    items
+}
+
+/// Split text into sentences, each tagged with the `[Speaker]` label in effect.
+fn sentences(text: &str) -> Vec<(Option<String>, String)> {
+    let mut result = Vec::new();
+    let mut speaker: Option<String> = None;
+    let mut current = String::new();
+    let mut chars = text.chars();
+
+    let mut flush = |current: &mut String, speaker: &Option<String>| {
+        let sentence = current.trim();
+        if !sentence.is_empty() {
+            result.push((speaker.clone(), sentence.to_string()));
+        }
+        current.clear();
+    };
+
+    while let Some(c) = chars.next() {
+        match c {
+            '[' => {
+                let label: String = chars.by_ref().take_while(|&c| c != ']').collect();
+                // The overlap marker annotates the current speaker's text
+                if OVERLAP_MARKER.trim_matches(['[', ']']) == label.trim() {
+                    continue;
+                }
+                flush(&mut current, &speaker);
+                let label = label.trim();
+                speaker = (!label.is_empty()).then(|| label.to_string());
+            }
+            '.' | '!' | '?' | '\n' => {
+                if c != '\n' {
+                    current.push(c);
+                }
+                flush(&mut current, &speaker);
+            }
+            _ => current.push(c),
+        }
+    }
+    flush(&mut current, &speaker);
+    result
+}
+
+/// Lowercase with apostrophe variants unified, for matching.
+fn normalize(text: &str) -> String {
+    text.to_lowercase().replace(['’', 'ʼ', '`'], "'")
+}
+
+/// Words of a sentence with surrounding punctuation removed.
+fn words(sentence: &str) -> Vec<String> {
+    sentence
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c: char| !c.is_alphanumeric() && !matches!(c, '\'' | '’' | 'ʼ')))
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn classify_sentence(sentence: &str, speaker: Option<&str>) -> Option<ActionItem> {
+    let original = words(sentence);
+    let lower: Vec<String> = original.iter().map(|w| normalize(w)).collect();
+    let normalized = normalize(sentence);
+
+    let action_verb = lower.iter().position(|w| is_action_verb(w));
+    let english_marker = EN_ACTION_MARKERS
+        .iter()
+        .find_map(|m| find_phrase(&normalized, &lower, m));
+    let obligation = lower
+        .iter()
+        .enumerate()
+        .any(|(i, w)| is_obligation(w, lower.get(i + 1).map(String::as_str)));
+    let decision = UK_DECISION_MARKERS.iter().any(|m| lower.iter().any(|w| w == m))
+        || EN_DECISION_MARKERS.iter().any(|m| contains_phrase(&normalized, m));
+
+    let assignee = action_verb
+        .and_then(|i| assignee_before(&original, &lower, i, speaker))
+        .or_else(|| english_marker.and_then(|i| assignee_before(&original, &lower, i, speaker)))
+        .or_else(|| {
+            // "Надішлю завтра" — a first-person verb without a pronoun
+            action_verb
+                .filter(|&i| lower[i].ends_with('ю') || lower[i].ends_with('у'))
+                .and(speaker.map(str::to_string))
+        });
+
+    let kind = if assignee.is_some() || (!decision && (action_verb.is_some() || english_marker.is_some() || obligation))
+    {
+        ActionItemKind::Action
+    } else if decision {
+        ActionItemKind::Decision
+    } else {
+        return None;
+    };
+
+    Some(ActionItem {
+        kind,
+        text: sentence.trim().to_string(),
+        assignee,
+        due: find_deadline(sentence),
+    })
+}
+
+fn is_action_verb(word: &str) -> bool {
+    if !UK_ACTION_STEMS.iter().any(|stem| word.starts_with(stem)) {
+        return false;
+    }
+    // Past tense and passive participles report what is already done
+    !["в", "ла", "ли", "ло", "но", "то"]
+        .iter()
+        .any(|end| word.ends_with(end))
+}
+
+fn is_obligation(word: &str, next: Option<&str>) -> bool {
+    if UK_OBLIGATION_MARKERS.contains(&word) {
+        return true;
+    }
+    UK_HAVE_TO.contains(&word) && next.is_some_and(|n| n.ends_with("ти") || n.ends_with("тися") || n.ends_with("тись"))
+}
+
+/// Word index where an English marker phrase starts, if present.
+fn find_phrase(normalized: &str, lower: &[String], phrase: &str) -> Option<usize> {
+    if phrase.starts_with('\'') {
+        return lower.iter().position(|w| w.ends_with(phrase));
+    }
+    if !contains_phrase(normalized, phrase) {
+        return None;
+    }
+    let first = phrase.split_whitespace().next()?;
+    lower.iter().position(|w| w == first)
+}
+
+/// Whole-word phrase match.
+fn contains_phrase(normalized: &str, phrase: &str) -> bool {
+    normalized.match_indices(phrase).any(|(start, _)| {
+        let before = normalized[..start].chars().next_back();
+        let after = normalized[start + phrase.len()..].chars().next();
+        !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+    })
+}
+
+/// The person committing to the task: a name right before the verb, or the
+/// current speaker for "я"/"I".
+fn assignee_before(original: &[String], lower: &[String], index: usize, speaker: Option<&str>) -> Option<String> {
+    if lower[index] == "i'll" {
+        return speaker.map(str::to_string);
+    }
+    let prev = index.checked_sub(1)?;
+    if lower[prev] == "я" || lower[prev] == "i" {
+        return speaker.map(str::to_string);
+    }
+    let candidate = &original[prev];
+    let word = lower[prev].as_str();
+    let capitalized = candidate.chars().next().is_some_and(char::is_uppercase);
+    let is_marker = NOT_NAMES.contains(&word)
+        || UK_OBLIGATION_MARKERS.contains(&word)
+        || UK_HAVE_TO.contains(&word)
+        || UK_DECISION_MARKERS.contains(&word);
+    (capitalized && !is_marker).then(|| candidate.clone())
+}
+
+/// Deadline phrase such as "до п'ятниці", "завтра" or "by Friday".
+fn find_deadline(sentence: &str) -> Option<String> {
+    let lower = words(&normalize(sentence));
+    for (i, word) in lower.iter().enumerate() {
+        let next = lower.get(i + 1);
+        if word == "до" {
+            if let Some(next) = next {
+                if UK_WEEKDAYS.contains(&next.as_str()) || next.chars().all(|c| c.is_ascii_digit()) {
+                    return Some(format!("до {}", next));
+                }
+            }
+        }
+        if word == "by" {
+            if let Some(next) = next {
+                if EN_WEEKDAYS.contains(&next.as_str()) {
+                    return Some(format!("by {}", next));
+                }
+            }
+        }
+    }
+    let normalized = normalize(sentence);
+    RELATIVE_DEADLINES
+        .iter()
+        .find(|phrase| contains_phrase(&normalized, phrase))
+        .map(|phrase| phrase.to_string())
+}
+
+/// Ask an OpenAI-compatible chat completions endpoint for the items.
+pub fn extract_with_llm(text: &str, url: &str, model: &str, api_key: Option<&str>) -> Result<Vec<ActionItem>> {
+    let body = serde_json::json!({
+        "model": model,
+        "temperature": 0,
+        "messages": [
+            {"role": "system", "content": LLM_PROMPT},
+            {"role": "user", "content": text},
+        ],
+    });
+
+    let rt = tokio::runtime::Runtime::new().context("Не вдалося створити tokio runtime")?;
+    let response = rt.block_on(async {
+        let mut request = reqwest::Client::new()
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body.to_string());
+        if let Some(key) = api_key {
+            request = request.bearer_auth(key);
+        }
+        let response = request
+            .send()
+            .await
+            .with_context(|| format!("Не вдалося звернутися до {}", url))?
+            .error_for_status()?;
+        response.text().await.context("Не вдалося прочитати відповідь LLM")
+    })?;
+
+    parse_llm_response(&response)
+}
+
+const LLM_PROMPT: &str = "Extract action items and decisions from the meeting transcript. \
+Lines may start with [Speaker] labels. Reply with a JSON array only, no prose. Each element: \
+{\"kind\": \"action\" or \"decision\", \"text\": the item in the transcript's language, \
+\"assignee\": person or null, \"due\": deadline as spoken or null}. Reply [] if there are none.";
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    content: String,
+}
+
+/// Parse the JSON array out of a chat completion, tolerating code fences.
+fn parse_llm_response(response: &str) -> Result<Vec<ActionItem>> {
+    let chat: ChatResponse = serde_json::from_str(response).context("Неочікувана відповідь LLM")?;
+    let Some(choice) = chat.choices.first() else {
+        bail!("LLM не повернула відповіді");
+    };
+    let content = &choice.message.content;
+    let (Some(start), Some(end)) = (content.find('['), content.rfind(']')) else {
+        bail!("LLM не повернула JSON-масив");
+    };
+    if end < start {
+        bail!("LLM не повернула JSON-масив");
+    }
+    let items: Vec<ActionItem> =
+        serde_json::from_str(&content[start..=end]).context("Не вдалося розібрати завдання від LLM")?;
+    Ok(items.into_iter().filter(|item| !item.text.trim().is_empty()).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_named_assignee_and_deadline() {
+        let items = detect_action_items("[Спікер 1] Добре. Андрій підготує звіт до п'ятниці.");
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].kind, ActionItemKind::Action);
+        assert_eq!(items[0].text, "Андрій підготує звіт до п'ятниці.");
+        assert_eq!(items[0].assignee.as_deref(), Some("Андрій"));
+        assert_eq!(items[0].due.as_deref(), Some("до п'ятниці"));
+    }
+
+    #[test]
+    fn test_first_person_is_attributed_to_speaker() {
+        let items = detect_action_items("[Ви] Я надішлю договір завтра. [Учасник] [перекриття] Надішлю правки.");
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].assignee.as_deref(), Some("Ви"));
+        assert_eq!(items[0].due.as_deref(), Some("завтра"));
+        assert_eq!(items[1].assignee.as_deref(), Some("Учасник"));
+
+        let items = detect_action_items("[Anna] I'll send the slides by Friday.");
+        assert_eq!(items[0].assignee.as_deref(), Some("Anna"));
+        assert_eq!(items[0].due.as_deref(), Some("by friday"));
+    }
+
+    #[test]
+    fn test_detects_decisions_and_obligations() {
+        let items =
+            detect_action_items("Ми вирішили перенести реліз. Треба оновити документацію. Маємо перевірити тести.");
+        assert_eq!(items.len(), 3);
+        assert_eq!(items[0].kind, ActionItemKind::Decision);
+        assert_eq!(items[1].kind, ActionItemKind::Action);
+        assert_eq!(items[1].assignee, None);
+        assert_eq!(items[2].kind, ActionItemKind::Action);
+    }
+
+    #[test]
+    fn test_ignores_past_tense_questions_and_chatter() {
+        let text = "Андрій підготував звіт. Хто зробить презентацію? Погода сьогодні гарна. Це має сенс.";
+        assert!(detect_action_items(text).is_empty());
+    }
+
+    #[test]
+    fn test_prepend_summary() {
+        let items = detect_action_items("Андрій підготує звіт до п'ятниці.");
+        assert_eq!(
+            prepend_summary("текст", &items),
+            "Завдання та рішення:\n- Завдання: Андрій підготує звіт до п'ятниці. [Андрій, до п'ятниці]\n\nтекст"
+        );
+        assert_eq!(prepend_summary("текст", &[]), "текст");
+    }
+
+    #[test]
+    fn test_parse_llm_response_extracts_array() {
+        let response = r#"{"choices":[{"message":{"role":"assistant","content":"```json\n[{\"kind\":\"action\",\"text\":\"Підготувати звіт\",\"assignee\":\"Андрій\",\"due\":\"п'ятниця\"},{\"kind\":\"decision\",\"text\":\"Реліз у травні\",\"assignee\":null,\"due\":null}]\n```"}}]}"#;
+        let items = parse_llm_response(response).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].assignee.as_deref(), Some("Андрій"));
+        assert_eq!(items[1].kind, ActionItemKind::Decision);
+
+        let prose = r#"{"choices":[{"message":{"content":"No items."}}]}"#;
+        assert!(parse_llm_response(prose).is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! On-disk cache of transcription results.
+//!
+//! Results are keyed by a SHA-256 over the audio file contents, the model,
+//! the language and a caller-provided description of every other setting
+//! that affects the output, so re-running the same file with the same
+//! settings (scripts, CI) returns instantly. Entries expire after a TTL
+//! and the oldest ones are evicted once the cache exceeds its size limit.
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use crate::app::config::{transcription_cache_dir, Config};
+
+/// Cache key for transcribing `audio` with the given model, language and settings.
+pub fn cache_key(audio: &Path, model: &str, language: &str, params: &str) -> Result<String> {
+    let mut file = fs::File::open(audio).with_context(|| format!("Failed to open {}", audio.display()))?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).with_context(|| format!("Failed to read {}", audio.display()))?;
+    // Results may change between releases
+    for part in [env!("CARGO_PKG_VERSION"), model, language, params] {
+        hasher.update([0u8]);
+        hasher.update(part.as_bytes());
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Transcription results stored as one JSON file per key.
+pub struct TranscriptionCache {
+    dir: PathBuf,
+    max_bytes: u64,
+    ttl: Duration,
+}
+
+impl TranscriptionCache {
+    pub fn new(dir: PathBuf, max_bytes: u64, ttl: Duration) -> Self {
+        Self { dir, max_bytes, ttl }
+    }
+
+    /// Cache in the data directory with the configured limits; `None` if disabled.
+    pub fn from_config(config: &Config) -> Option<Self> {
+        if config.transcription_cache_max_mb == 0 {
+            return None;
+        }
+        Some(Self::new(
+            transcription_cache_dir(),
+            config.transcription_cache_max_mb * 1024 * 1024,
+            Duration::from_secs(config.transcription_cache_ttl_days as u64 * 24 * 60 * 60),
+        ))
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", key))
+    }
+
+    /// Cached result for `key`, unless missing, expired or unreadable.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        let path = self.entry_path(key);
+        let modified = fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+        if self.is_expired(modified) {
+            let _ = fs::remove_file(&path);
+            return None;
+        }
+        let content = fs::read_to_string(&path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Store a result, then evict expired and excess entries.
+    pub fn put<T: Serialize>(&self, key: &str, value: &T) -> Result<()> {
+        fs::create_dir_all(&self.dir).with_context(|| format!("Failed to create {}", self.dir.display()))?;
+        let content = serde_json::to_string(value).context("Failed to serialize cache entry")?;
+        let path = self.entry_path(key);
+        fs::write(&path, content).with_context(|| format!("Failed to write {}", path.display()))?;
+        self.prune()
+    }
+
+    /// Remove expired entries, then the oldest ones until under the size limit.
+    pub fn prune(&self) -> Result<()> {
+        let Ok(dir) = fs::read_dir(&self.dir) else {
+            return Ok(());
+        };
+
+        let mut entries: Vec<(PathBuf, SystemTime, u64)> = Vec::new();
+        for entry in dir.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            let modified = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            if self.is_expired(modified) {
+                let _ = fs::remove_file(&path);
+            } else {
+                entries.push((path, modified, metadata.len()));
+            }
+        }
+
+        entries.sort_by_key(|(_, modified, _)| *modified);
+        let mut total: u64 = entries.iter().map(|(_, _, size)| size).sum();
+        for (path, _, size) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            fs::remove_file(&path).with_context(|| format!("Failed to remove {}", path.display()))?;
+            total -= size;
+        }
+        Ok(())
+    }
+
+    fn is_expired(&self, modified: SystemTime) -> bool {
+        modified.elapsed().map(|age| age >= self.ttl).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        std::env::temp_dir().join(format!("s2t_cache_{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_cache_key_depends_on_all_inputs() {
+        let dir = temp_dir();
+        fs::create_dir_all(&dir).unwrap();
+        let (a, b) = (dir.join("a.wav"), dir.join("b.wav"));
+        fs::write(&a, b"audio a").unwrap();
+        fs::write(&b, b"audio b").unwrap();
+
+        let key = cache_key(&a, "base", "uk", "chunk=on").unwrap();
+        assert_eq!(key, cache_key(&a, "base", "uk", "chunk=on").unwrap());
+        assert_ne!(key, cache_key(&b, "base", "uk", "chunk=on").unwrap());
+        assert_ne!(key, cache_key(&a, "small", "uk", "chunk=on").unwrap());
+        assert_ne!(key, cache_key(&a, "base", "en", "chunk=on").unwrap());
+        assert_ne!(key, cache_key(&a, "base", "uk", "chunk=off").unwrap());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_put_get_roundtrip_and_expiry() {
+        let dir = temp_dir();
+        let cache = TranscriptionCache::new(dir.clone(), 1024 * 1024, Duration::from_secs(3600));
+        assert_eq!(cache.get::<String>("k"), None);
+        cache.put("k", &"привіт".to_string()).unwrap();
+        assert_eq!(cache.get::<String>("k").as_deref(), Some("привіт"));
+
+        let expired = TranscriptionCache::new(dir.clone(), 1024 * 1024, Duration::ZERO);
+        assert_eq!(expired.get::<String>("k"), None);
+        assert!(!dir.join("k.json").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_prune_evicts_oldest_over_size_limit() {
+        let dir = temp_dir();
+        let cache = TranscriptionCache::new(dir.clone(), 25, Duration::from_secs(3600));
+        fs::create_dir_all(&dir).unwrap();
+        for (i, key) in ["old", "mid", "new"].iter().enumerate() {
+            let path = dir.join(format!("{}.json", key));
+            fs::write(&path, "\"0123456789\"").unwrap(); // 12 bytes
+            let age = Duration::from_secs(100 - i as u64 * 10);
+            let file = fs::File::options().write(true).open(&path).unwrap();
+            file.set_modified(SystemTime::now() - age).unwrap();
+        }
+
+        cache.prune().unwrap();
+        assert!(!dir.join("old.json").exists());
+        assert!(dir.join("mid.json").exists());
+        assert!(dir.join("new.json").exists());
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,177 @@
+//! Live translated captions for continuous mode.
+//!
+//! Final captions come from completed segments and may arrive out of order;
+//! partial captions come from periodically translating the audio of the
+//! segment still being recorded. A partial is tagged with the id of the
+//! segment it previews and is dropped once that segment is finalized, so a
+//! slow partial can never overwrite a newer final caption.
+
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+/// Minimum in-progress audio before a partial caption is requested (1 second).
+pub const MIN_PARTIAL_SAMPLES: usize = 16000;
+
+/// Minimum delay between partial caption requests.
+pub const PARTIAL_INTERVAL: Duration = Duration::from_millis(700);
+
+/// Maximum in-progress audio translated for a partial caption (last 15 seconds).
+pub const MAX_PARTIAL_SAMPLES: usize = 16000 * 15;
+
+/// Number of final caption lines kept on screen.
+pub const DEFAULT_VISIBLE_LINES: usize = 2;
+
+/// Caption text shown in the overlay.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CaptionView {
+    /// Most recent final captions, oldest first.
+    pub lines: Vec<String>,
+    /// Preview of the segment being spoken, if any.
+    pub partial: Option<String>,
+}
+
+/// Accumulates final and partial captions for one recording session.
+#[derive(Debug)]
+pub struct CaptionBuffer {
+    finals: BTreeMap<usize, String>,
+    /// Highest segment id finalized so far.
+    last_final_id: usize,
+    partial: Option<(usize, String)>,
+    visible_lines: usize,
+}
+
+impl CaptionBuffer {
+    pub fn new(visible_lines: usize) -> Self {
+        Self {
+            finals: BTreeMap::new(),
+            last_final_id: 0,
+            partial: None,
+            visible_lines: visible_lines.max(1),
+        }
+    }
+
+    /// Record the translation of a completed segment.
+    pub fn finalize(&mut self, segment_id: usize, text: &str) {
+        let text = text.trim();
+        if !text.is_empty() {
+            self.finals.insert(segment_id, text.to_string());
+        }
+        self.last_final_id = self.last_final_id.max(segment_id);
+        if self.partial.as_ref().is_some_and(|(id, _)| *id <= self.last_final_id) {
+            self.partial = None;
+        }
+    }
+
+    /// Update the preview of the segment being recorded.
+    ///
+    /// Returns false (and ignores the text) if that segment is already final.
+    pub fn set_partial(&mut self, segment_id: usize, text: &str) -> bool {
+        if segment_id <= self.last_final_id {
+            return false;
+        }
+        if self.partial.as_ref().is_some_and(|(id, _)| *id > segment_id) {
+            return false;
+        }
+        let text = text.trim();
+        self.partial = if text.is_empty() {
+            None
+        } else {
+            Some((segment_id, text.to_string()))
+        };
+        true
+    }
+
+    /// Current caption lines to display.
+    pub fn view(&self) -> CaptionView {
+        let skip = self.finals.len().saturating_sub(self.visible_lines);
+        CaptionView {
+            lines: self.finals.values().skip(skip).cloned().collect(),
+            partial: self.partial.as_ref().map(|(_, text)| text.clone()),
+        }
+    }
+
+    /// All final captions joined in segment order.
+    pub fn full_text(&self) -> String {
+        self.finals.values().cloned().collect::<Vec<_>>().join(" ")
+    }
+}
+
+impl Default for CaptionBuffer {
+    fn default() -> Self {
+        Self::new(DEFAULT_VISIBLE_LINES)
+    }
+}
+
+/// Decide whether it is time to request a new partial caption.
+///
+/// Only one partial runs at a time, at most once per `PARTIAL_INTERVAL`.
+/// Checked before the in-progress audio is copied, which is not free.
+pub fn partial_due(in_flight: bool, since_last: Duration) -> bool {
+    !in_flight && since_last >= PARTIAL_INTERVAL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finals_kept_in_segment_order() {
+        let mut buffer = CaptionBuffer::new(5);
+        buffer.finalize(2, "second");
+        buffer.finalize(1, "first");
+        assert_eq!(buffer.view().lines, vec!["first", "second"]);
+        assert_eq!(buffer.full_text(), "first second");
+    }
+
+    #[test]
+    fn test_view_shows_last_lines_only() {
+        let mut buffer = CaptionBuffer::new(2);
+        for (id, text) in ["a", "b", "c"].iter().enumerate() {
+            buffer.finalize(id + 1, text);
+        }
+        assert_eq!(buffer.view().lines, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn test_partial_replaced_by_final() {
+        let mut buffer = CaptionBuffer::default();
+        assert!(buffer.set_partial(1, "hello wor"));
+        assert_eq!(buffer.view().partial.as_deref(), Some("hello wor"));
+
+        buffer.finalize(1, "Hello world.");
+        assert_eq!(buffer.view().partial, None);
+        assert_eq!(buffer.view().lines, vec!["Hello world."]);
+    }
+
+    #[test]
+    fn test_stale_partial_ignored() {
+        let mut buffer = CaptionBuffer::default();
+        buffer.finalize(1, "Done.");
+        assert!(!buffer.set_partial(1, "late preview"));
+        assert_eq!(buffer.view().partial, None);
+    }
+
+    #[test]
+    fn test_older_partial_does_not_replace_newer() {
+        let mut buffer = CaptionBuffer::default();
+        assert!(buffer.set_partial(3, "newer"));
+        assert!(!buffer.set_partial(2, "older"));
+        assert_eq!(buffer.view().partial.as_deref(), Some("newer"));
+    }
+
+    #[test]
+    fn test_empty_final_advances_without_line() {
+        let mut buffer = CaptionBuffer::default();
+        buffer.set_partial(1, "noise");
+        buffer.finalize(1, "  ");
+        assert!(buffer.view().lines.is_empty());
+        assert_eq!(buffer.view().partial, None);
+    }
+
+    #[test]
+    fn test_partial_due() {
+        assert!(partial_due(false, PARTIAL_INTERVAL));
+        assert!(!partial_due(true, PARTIAL_INTERVAL));
+        assert!(!partial_due(false, Duration::from_millis(100)));
+    }
+}
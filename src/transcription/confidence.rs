@@ -0,0 +1,89 @@
+//! Confidence of speaker assignments.
+//!
+//! Sortformer gives no probabilities with its segments, so confidence is
+//! estimated from how cleanly a piece of text falls into its speaker's
+//! segments: pieces in overlapping speech, in gaps between segments or too
+//! short to judge a voice by score low. One value is kept per speaker turn
+//! of the transcript, so the history can point at assignments worth checking.
+
+use crate::transcription::diarization::DiarizationSegment;
+
+/// Turns below this confidence are highlighted for review.
+pub const LOW_SPEAKER_CONFIDENCE: f32 = 0.6;
+
+/// Turns shorter than this are less certain: there is little voice to go by.
+const SHORT_TURN_SECS: f64 = 1.0;
+
+/// Confidence that `start..end` seconds belong to `speaker_id`, from 0 to 1.
+///
+/// The share of the span covered by the speaker's segments, reduced by
+/// speech of other speakers within it and for very short spans.
+pub fn speaker_confidence(segments: &[DiarizationSegment], speaker_id: usize, start: f64, end: f64) -> f32 {
+    let span = end - start;
+    if span <= 0.0 {
+        return 0.0;
+    }
+    let (mut own, mut other) = (0.0, 0.0);
+    for seg in segments {
+        let shared = (seg.end_time.min(end) - seg.start_time.max(start)).max(0.0);
+        if seg.speaker_id == speaker_id {
+            own += shared;
+        } else {
+            other += shared;
+        }
+    }
+    let mut confidence = own.min(span) / (span + other.min(span));
+    if span < SHORT_TURN_SECS {
+        confidence *= 0.5 + 0.5 * span / SHORT_TURN_SECS;
+    }
+    confidence as f32
+}
+
+/// One confidence per speaker turn, for pieces joined into turns.
+///
+/// Consecutive pieces of the same speaker share one label in the text, so
+/// they form one turn that is as confident as its weakest piece.
+pub fn turn_confidence(pieces: &[(usize, f32)]) -> Vec<f32> {
+    let mut turns: Vec<(usize, f32)> = Vec::new();
+    for &(speaker_id, confidence) in pieces {
+        match turns.last_mut() {
+            Some(last) if last.0 == speaker_id => last.1 = last.1.min(confidence),
+            _ => turns.push((speaker_id, confidence)),
+        }
+    }
+    turns.into_iter().map(|(_, confidence)| confidence).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(speaker_id: usize, start_time: f64, end_time: f64) -> DiarizationSegment {
+        DiarizationSegment {
+            speaker_id,
+            start_time,
+            end_time,
+        }
+    }
+
+    #[test]
+    fn test_speaker_confidence() {
+        let segments = vec![seg(0, 0.0, 4.0), seg(1, 3.0, 8.0)];
+        // Clean speech of one speaker
+        assert_eq!(speaker_confidence(&segments, 1, 4.0, 8.0), 1.0);
+        // Overlapping speech: half of the span is shared
+        assert!((speaker_confidence(&segments, 0, 2.0, 4.0) - 2.0 / 3.0).abs() < 1e-6);
+        // Mostly in a gap or with the other speaker
+        assert!(speaker_confidence(&segments, 0, 3.0, 6.0) < LOW_SPEAKER_CONFIDENCE);
+        // Short, otherwise clean turn
+        assert!((speaker_confidence(&segments, 0, 0.0, 0.4) - 0.7).abs() < 1e-6);
+        assert_eq!(speaker_confidence(&segments, 0, 2.0, 2.0), 0.0);
+    }
+
+    #[test]
+    fn test_turn_confidence_joins_consecutive_pieces() {
+        let pieces = vec![(0, 0.9), (0, 0.5), (1, 1.0), (0, 0.8)];
+        assert_eq!(turn_confidence(&pieces), vec![0.5, 1.0, 0.8]);
+        assert!(turn_confidence(&[]).is_empty());
+    }
+}
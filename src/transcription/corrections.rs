@@ -0,0 +1,310 @@
+//! Correction dictionary and inline phonetic hints.
+//!
+//! The dictionary groups alternative spellings of names that the STT model
+//! tends to confuse (e.g. "Кравченко" / "Кравчук"). When hints are enabled,
+//! recognized words from such a group are annotated with the other spellings:
+//! `Кравченко (або Кравчук?)`. The UI can later resolve a hint back to a
+//! single chosen spelling.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Opening marker of an inline hint (between the word and the alternatives).
+const HINT_OPEN: &str = " (або ";
+/// Closing marker of an inline hint.
+const HINT_CLOSE: &str = "?)";
+
+/// A group of spellings for a single name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CorrectionEntry {
+    /// Preferred spelling.
+    pub canonical: String,
+    /// Other spellings the recognizer may produce or the user may mean.
+    #[serde(default)]
+    pub variants: Vec<String>,
+}
+
+impl CorrectionEntry {
+    /// All spellings of this entry, canonical first.
+    pub fn spellings(&self) -> impl Iterator<Item = &str> {
+        std::iter::once(self.canonical.as_str()).chain(self.variants.iter().map(String::as_str))
+    }
+
+    fn matches(&self, word: &str) -> bool {
+        let lower = word.to_lowercase();
+        self.spellings().any(|s| s.to_lowercase() == lower)
+    }
+}
+
+/// User-maintained dictionary of name spellings (`corrections.toml`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct CorrectionDictionary {
+    #[serde(default, rename = "entry")]
+    pub entries: Vec<CorrectionEntry>,
+}
+
+/// An inline hint found in annotated text.
+///
+/// Byte ranges refer to the annotated text the hint was found in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PhoneticHint {
+    /// Byte offset where the recognized word starts.
+    pub start: usize,
+    /// Byte offset just past the closing `?)`.
+    pub end: usize,
+    /// The recognized word.
+    pub word: String,
+    /// Alternative spellings offered in the hint.
+    pub alternatives: Vec<String>,
+}
+
+impl PhoneticHint {
+    /// Recognized word followed by the alternatives, for a quick-pick list.
+    pub fn choices(&self) -> Vec<String> {
+        std::iter::once(self.word.clone())
+            .chain(self.alternatives.iter().cloned())
+            .collect()
+    }
+}
+
+impl CorrectionDictionary {
+    /// Load the dictionary from a TOML file. A missing file yields an empty dictionary.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Не вдалося прочитати словник виправлень: {}", path.display()))?;
+        toml::from_str(&content).context("Не вдалося розпарсити словник виправлень")
+    }
+
+    /// Load the dictionary from the default location in the config directory.
+    pub fn load_default() -> Result<Self> {
+        Self::load(&crate::app::config::corrections_path())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Find the entry containing `word` (case-insensitive), if it has alternatives.
+    fn lookup(&self, word: &str) -> Option<&CorrectionEntry> {
+        self.entries.iter().find(|e| !e.variants.is_empty() && e.matches(word))
+    }
+
+    /// Annotate every known name in `text` with its alternative spellings.
+    ///
+    /// Words that are not in the dictionary are left untouched.
+    pub fn annotate_alternatives(&self, text: &str) -> String {
+        if self.is_empty() {
+            return text.to_string();
+        }
+
+        let mut result = String::with_capacity(text.len());
+        let mut last = 0;
+        for (start, end) in word_ranges(text) {
+            let word = &text[start..end];
+            let Some(entry) = self.lookup(word) else {
+                continue;
+            };
+            let lower = word.to_lowercase();
+            let alternatives: Vec<&str> = entry.spellings().filter(|s| s.to_lowercase() != lower).collect();
+            if alternatives.is_empty() {
+                continue;
+            }
+            result.push_str(&text[last..end]);
+            result.push_str(HINT_OPEN);
+            result.push_str(&alternatives.join(", "));
+            result.push_str(HINT_CLOSE);
+            last = end;
+        }
+        result.push_str(&text[last..]);
+        result
+    }
+}
+
+/// Byte ranges of words in `text` (letters, digits, apostrophes and hyphens).
+fn word_ranges(text: &str) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut start: Option<usize> = None;
+    for (i, c) in text.char_indices() {
+        if is_word_char(c) {
+            start.get_or_insert(i);
+        } else if let Some(s) = start.take() {
+            ranges.push((s, i));
+        }
+    }
+    if let Some(s) = start {
+        ranges.push((s, text.len()));
+    }
+    ranges
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '\'' || c == '’' || c == '-'
+}
+
+/// Find all inline hints in annotated text.
+pub fn find_hints(text: &str) -> Vec<PhoneticHint> {
+    let mut hints = Vec::new();
+    let mut search_from = 0;
+
+    while let Some(rel) = text[search_from..].find(HINT_OPEN) {
+        let open = search_from + rel;
+        let alt_start = open + HINT_OPEN.len();
+        let Some(close_rel) = text[alt_start..].find(HINT_CLOSE) else {
+            break;
+        };
+        let alt_end = alt_start + close_rel;
+        let end = alt_end + HINT_CLOSE.len();
+        search_from = end;
+
+        let word_start = text[..open]
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| is_word_char(*c))
+            .last()
+            .map(|(i, _)| i);
+        let Some(word_start) = word_start else {
+            continue;
+        };
+
+        let alternatives: Vec<String> = text[alt_start..alt_end]
+            .split(", ")
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(str::to_string)
+            .collect();
+        if alternatives.is_empty() {
+            continue;
+        }
+
+        hints.push(PhoneticHint {
+            start: word_start,
+            end,
+            word: text[word_start..open].to_string(),
+            alternatives,
+        });
+    }
+
+    hints
+}
+
+/// Find the hint covering the given byte offset, if any.
+pub fn hint_at(text: &str, offset: usize) -> Option<PhoneticHint> {
+    find_hints(text)
+        .into_iter()
+        .find(|h| offset >= h.start && offset < h.end)
+}
+
+/// Replace a hint with the chosen spelling.
+pub fn resolve_hint(text: &str, hint: &PhoneticHint, choice: &str) -> String {
+    format!("{}{}{}", &text[..hint.start], choice, &text[hint.end..])
+}
+
+/// Remove all hints, keeping the originally recognized words.
+pub fn strip_hints(text: &str) -> String {
+    let mut result = text.to_string();
+    for hint in find_hints(text).iter().rev() {
+        result = resolve_hint(&result, hint, &hint.word);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dict() -> CorrectionDictionary {
+        CorrectionDictionary {
+            entries: vec![CorrectionEntry {
+                canonical: "Кравченко".to_string(),
+                variants: vec!["Кравчук".to_string()],
+            }],
+        }
+    }
+
+    #[test]
+    fn test_annotate_known_name() {
+        let text = dict().annotate_alternatives("Зателефонуй Кравченко завтра.");
+        assert_eq!(text, "Зателефонуй Кравченко (або Кравчук?) завтра.");
+    }
+
+    #[test]
+    fn test_annotate_variant_offers_canonical() {
+        let text = dict().annotate_alternatives("Кравчук прийде");
+        assert_eq!(text, "Кравчук (або Кравченко?) прийде");
+    }
+
+    #[test]
+    fn test_annotate_unknown_words_untouched() {
+        let text = "Привіт, світе";
+        assert_eq!(dict().annotate_alternatives(text), text);
+    }
+
+    #[test]
+    fn test_annotate_empty_dictionary() {
+        let text = "Кравченко";
+        assert_eq!(CorrectionDictionary::default().annotate_alternatives(text), text);
+    }
+
+    #[test]
+    fn test_entry_without_variants_is_ignored() {
+        let d = CorrectionDictionary {
+            entries: vec![CorrectionEntry {
+                canonical: "Київ".to_string(),
+                variants: vec![],
+            }],
+        };
+        assert_eq!(d.annotate_alternatives("Київ"), "Київ");
+    }
+
+    #[test]
+    fn test_find_and_resolve_hint() {
+        let annotated = dict().annotate_alternatives("Дзвонив Кравченко.");
+        let hints = find_hints(&annotated);
+        assert_eq!(hints.len(), 1);
+        assert_eq!(hints[0].word, "Кравченко");
+        assert_eq!(hints[0].alternatives, vec!["Кравчук"]);
+        assert_eq!(hints[0].choices(), vec!["Кравченко", "Кравчук"]);
+
+        let resolved = resolve_hint(&annotated, &hints[0], "Кравчук");
+        assert_eq!(resolved, "Дзвонив Кравчук.");
+    }
+
+    #[test]
+    fn test_hint_at_offset() {
+        let annotated = dict().annotate_alternatives("a Кравченко b");
+        assert!(hint_at(&annotated, 0).is_none());
+        assert!(hint_at(&annotated, 2).is_some());
+        assert!(hint_at(&annotated, annotated.len() - 1).is_none());
+    }
+
+    #[test]
+    fn test_strip_hints() {
+        let d = dict();
+        let original = "Кравченко і Кравчук";
+        let annotated = d.annotate_alternatives(original);
+        assert_eq!(strip_hints(&annotated), original);
+    }
+
+    #[test]
+    fn test_load_missing_file_is_empty() {
+        let d = CorrectionDictionary::load(Path::new("/nonexistent/corrections.toml")).unwrap();
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn test_parse_toml() {
+        let toml_str = r#"
+[[entry]]
+canonical = "Кравченко"
+variants = ["Кравчук", "Кравченка"]
+"#;
+        let d: CorrectionDictionary = toml::from_str(toml_str).unwrap();
+        assert_eq!(d.entries.len(), 1);
+        assert_eq!(d.entries[0].variants.len(), 2);
+    }
+}
@@ -1,10 +1,58 @@
 use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap};
 use std::path::PathBuf;
 
 use parakeet_rs::sortformer::{DiarizationConfig, Sortformer};
 
+use super::speaker_id::{speaker_voiceprints, SpeakerRegistry};
+
 const SAMPLE_RATE: u32 = 16000;
 
+/// Number of speakers the Sortformer model can separate.
+pub const SORTFORMER_MAX_SPEAKERS: usize = 4;
+
+/// Transcript marker for speech where several speakers talk at once.
+pub const OVERLAP_MARKER: &str = "[перекриття]";
+
+/// Shortest simultaneous speech counted as overlap; shorter ones are
+/// usually just segment boundaries touching.
+const MIN_OVERLAP_SECS: f64 = 0.3;
+
+/// Tunable diarization parameters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DiarizationSettings {
+    /// Upper bound on distinct speakers (1..=4). Speakers beyond it are
+    /// merged into the nearest remaining speaker.
+    pub max_speakers: usize,
+    /// Probability threshold to start a speech segment (CallHome preset if `None`).
+    pub onset: Option<f32>,
+    /// Probability threshold to end a speech segment (CallHome preset if `None`).
+    pub offset: Option<f32>,
+}
+
+impl Default for DiarizationSettings {
+    fn default() -> Self {
+        Self {
+            max_speakers: SORTFORMER_MAX_SPEAKERS,
+            onset: None,
+            offset: None,
+        }
+    }
+}
+
+impl DiarizationSettings {
+    fn sortformer_config(&self) -> DiarizationConfig {
+        let mut config = DiarizationConfig::callhome();
+        if let Some(onset) = self.onset {
+            config.onset = onset.clamp(0.0, 1.0);
+        }
+        if let Some(offset) = self.offset {
+            config.offset = offset.clamp(0.0, 1.0);
+        }
+        config
+    }
+}
+
 /// Speaker diarization segment with speaker ID and timestamps
 #[derive(Debug, Clone)]
 pub struct DiarizationSegment {
@@ -13,10 +61,44 @@ pub struct DiarizationSegment {
     pub end_time: f64,
 }
 
+/// Time range where two or more speakers talk simultaneously.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OverlapRegion {
+    pub start_time: f64,
+    pub end_time: f64,
+}
+
+/// Display names for diarized speakers, keyed by speaker id.
+///
+/// Speakers without a name fall back to "Спікер N" by id.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpeakerNames(HashMap<usize, String>);
+
+impl SpeakerNames {
+    pub fn new(names: HashMap<usize, String>) -> Self {
+        Self(names)
+    }
+
+    pub fn label(&self, speaker_id: usize) -> String {
+        self.0
+            .get(&speaker_id)
+            .cloned()
+            .unwrap_or_else(|| speaker_label(speaker_id))
+    }
+}
+
+/// Known-speaker registry used to keep labels stable across sessions.
+struct Reidentification {
+    registry_path: PathBuf,
+    threshold: f32,
+}
+
 /// Diarization engine using NVIDIA Sortformer
 pub struct DiarizationEngine {
     sortformer: Option<Sortformer>,
     model_path: Option<PathBuf>,
+    settings: DiarizationSettings,
+    reidentification: Option<Reidentification>,
 }
 
 impl DiarizationEngine {
@@ -26,9 +108,26 @@ impl DiarizationEngine {
         Self {
             sortformer: None,
             model_path,
+            settings: DiarizationSettings::default(),
+            reidentification: None,
         }
     }
 
+    /// Use custom speaker count and thresholds (applied on `load_model`).
+    pub fn with_settings(mut self, settings: DiarizationSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Match speakers against the voices in `registry_path` (see [`SpeakerRegistry`]).
+    pub fn with_reidentification(mut self, registry_path: PathBuf, threshold: f32) -> Self {
+        self.reidentification = Some(Reidentification {
+            registry_path,
+            threshold,
+        });
+        self
+    }
+
     /// Load the Sortformer model
     pub fn load_model(&mut self) -> Result<()> {
         if let Some(ref model_path) = self.model_path {
@@ -39,7 +138,7 @@ impl DiarizationEngine {
                 );
             }
 
-            let config = DiarizationConfig::callhome();
+            let config = self.settings.sortformer_config();
             self.sortformer = Some(
                 Sortformer::with_config(model_path, None, config)
                     .context("Не вдалося завантажити модель Sortformer")?,
@@ -53,6 +152,11 @@ impl DiarizationEngine {
         self.sortformer.is_some()
     }
 
+    /// Speaker count and thresholds in use.
+    pub fn settings(&self) -> DiarizationSettings {
+        self.settings
+    }
+
     /// Perform diarization on audio samples
     /// Returns segments with speaker IDs and timestamps
     pub fn diarize(&mut self, audio_samples: &[f32]) -> Result<Vec<DiarizationSegment>> {
@@ -62,18 +166,47 @@ impl DiarizationEngine {
                 .diarize(audio_samples.to_vec(), SAMPLE_RATE, 1)
                 .context("Помилка diarization")?;
 
-            Ok(segments
+            let segments: Vec<DiarizationSegment> = segments
                 .into_iter()
                 .map(|seg| DiarizationSegment {
                     speaker_id: seg.speaker_id,
                     start_time: seg.start as f64,
                     end_time: seg.end as f64,
                 })
-                .collect())
+                .collect();
+            Ok(limit_speakers(segments, self.settings.max_speakers))
         } else {
             anyhow::bail!("Diarization не доступна. Завантажте модель Sortformer.")
         }
     }
+
+    /// Names for the speakers in `segments`.
+    ///
+    /// With re-identification enabled, recurring voices get the name they
+    /// had in earlier sessions and new voices are added to the registry.
+    /// Otherwise (or on registry errors) speakers are numbered per recording.
+    pub fn speaker_names(&self, samples: &[f32], segments: &[DiarizationSegment]) -> SpeakerNames {
+        let Some(ref reid) = self.reidentification else {
+            return SpeakerNames::default();
+        };
+        let voiceprints = speaker_voiceprints(samples, segments);
+        if voiceprints.is_empty() {
+            return SpeakerNames::default();
+        }
+
+        let mut registry = match SpeakerRegistry::load(&reid.registry_path) {
+            Ok(registry) => registry,
+            Err(e) => {
+                eprintln!("Не вдалося завантажити реєстр мовців: {:#}", e);
+                return SpeakerNames::default();
+            }
+        };
+        let names = registry.identify(&voiceprints, reid.threshold);
+        if let Err(e) = registry.save(&reid.registry_path) {
+            eprintln!("Не вдалося зберегти реєстр мовців: {:#}", e);
+        }
+        SpeakerNames::new(names)
+    }
 }
 
 impl Default for DiarizationEngine {
@@ -81,3 +214,288 @@ impl Default for DiarizationEngine {
         Self::new(None)
     }
 }
+
+/// Number of distinct speakers in the segments.
+pub fn speaker_count(segments: &[DiarizationSegment]) -> usize {
+    segments.iter().map(|s| s.speaker_id).collect::<BTreeSet<_>>().len()
+}
+
+/// Keep at most `max_speakers` speakers.
+///
+/// The speakers with the most talk time are kept; segments of the others
+/// are reassigned to the kept speaker closest in time. Speaker ids are
+/// then renumbered from 0 in order of first appearance.
+pub fn limit_speakers(mut segments: Vec<DiarizationSegment>, max_speakers: usize) -> Vec<DiarizationSegment> {
+    let max_speakers = max_speakers.max(1);
+
+    let mut talk_time: HashMap<usize, f64> = HashMap::new();
+    for seg in &segments {
+        *talk_time.entry(seg.speaker_id).or_default() += seg.end_time - seg.start_time;
+    }
+
+    if talk_time.len() > max_speakers {
+        let mut ranked: Vec<(usize, f64)> = talk_time.into_iter().collect();
+        ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        let kept: BTreeSet<usize> = ranked.iter().take(max_speakers).map(|(id, _)| *id).collect();
+
+        let anchors: Vec<DiarizationSegment> = segments
+            .iter()
+            .filter(|s| kept.contains(&s.speaker_id))
+            .cloned()
+            .collect();
+        for seg in segments.iter_mut().filter(|s| !kept.contains(&s.speaker_id)) {
+            if let Some(nearest) = anchors.iter().min_by(|a, b| gap(seg, a).total_cmp(&gap(seg, b))) {
+                seg.speaker_id = nearest.speaker_id;
+            }
+        }
+    }
+
+    segments.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+    let mut renumber: HashMap<usize, usize> = HashMap::new();
+    for seg in &mut segments {
+        let next_id = renumber.len();
+        seg.speaker_id = *renumber.entry(seg.speaker_id).or_insert(next_id);
+    }
+    segments
+}
+
+/// Display label for a Sortformer speaker id ("Спікер 1", ...).
+pub fn speaker_label(speaker_id: usize) -> String {
+    format!("Спікер {}", speaker_id + 1)
+}
+
+/// Labels of the given speaker ids, deduplicated and ordered by id.
+pub fn speaker_labels(speaker_ids: impl IntoIterator<Item = usize>, names: &SpeakerNames) -> Vec<String> {
+    speaker_ids
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|id| names.label(id))
+        .collect()
+}
+
+/// Speaker who talks the most within `start..end` seconds, if anyone does.
+pub fn dominant_speaker(segments: &[DiarizationSegment], start: f64, end: f64) -> Option<usize> {
+    let mut overlap: HashMap<usize, f64> = HashMap::new();
+    for seg in segments {
+        let shared = seg.end_time.min(end) - seg.start_time.max(start);
+        if shared > 0.0 {
+            *overlap.entry(seg.speaker_id).or_default() += shared;
+        }
+    }
+    overlap
+        .into_iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+        .map(|(id, _)| id)
+}
+
+/// Regions where segments of different speakers overlap, merged and sorted.
+///
+/// Regions shorter than [`MIN_OVERLAP_SECS`] are dropped.
+pub fn overlap_regions(segments: &[DiarizationSegment]) -> Vec<OverlapRegion> {
+    let mut shared: Vec<OverlapRegion> = Vec::new();
+    for (i, a) in segments.iter().enumerate() {
+        for b in &segments[i + 1..] {
+            if a.speaker_id == b.speaker_id {
+                continue;
+            }
+            let start_time = a.start_time.max(b.start_time);
+            let end_time = a.end_time.min(b.end_time);
+            if end_time > start_time {
+                shared.push(OverlapRegion { start_time, end_time });
+            }
+        }
+    }
+    shared.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+
+    let mut merged: Vec<OverlapRegion> = Vec::new();
+    for region in shared {
+        match merged.last_mut() {
+            Some(last) if region.start_time <= last.end_time => last.end_time = last.end_time.max(region.end_time),
+            _ => merged.push(region),
+        }
+    }
+    merged.retain(|r| r.end_time - r.start_time >= MIN_OVERLAP_SECS);
+    merged
+}
+
+/// Whether `start..end` seconds contain a noticeable part of any overlap region.
+pub fn has_overlap(regions: &[OverlapRegion], start: f64, end: f64) -> bool {
+    regions
+        .iter()
+        .any(|r| r.end_time.min(end) - r.start_time.max(start) >= MIN_OVERLAP_SECS)
+}
+
+/// Prefix `text` with [`OVERLAP_MARKER`] if `start..end` has overlapping speech.
+pub fn mark_overlap(text: String, regions: &[OverlapRegion], start: f64, end: f64) -> String {
+    if has_overlap(regions, start, end) {
+        format!("{} {}", OVERLAP_MARKER, text)
+    } else {
+        text
+    }
+}
+
+/// Join transcribed pieces, prefixing a speaker label whenever the speaker changes.
+///
+/// Pieces without a speaker continue the previous speaker's turn.
+pub fn join_speaker_turns(pieces: &[(Option<usize>, String)], names: &SpeakerNames) -> String {
+    let mut result = String::new();
+    let mut current = None;
+    for (speaker, text) in pieces {
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        if !result.is_empty() {
+            result.push(' ');
+        }
+        if let Some(id) = *speaker {
+            if current != Some(id) {
+                current = Some(id);
+                result.push_str(&format!("[{}] ", names.label(id)));
+            }
+        }
+        result.push_str(text);
+    }
+    result
+}
+
+/// Time between two segments (0 if they overlap).
+fn gap(a: &DiarizationSegment, b: &DiarizationSegment) -> f64 {
+    (b.start_time - a.end_time).max(a.start_time - b.end_time).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(speaker_id: usize, start_time: f64, end_time: f64) -> DiarizationSegment {
+        DiarizationSegment {
+            speaker_id,
+            start_time,
+            end_time,
+        }
+    }
+
+    fn speakers(segments: &[DiarizationSegment]) -> Vec<usize> {
+        segments.iter().map(|s| s.speaker_id).collect()
+    }
+
+    #[test]
+    fn test_limit_speakers_under_limit_renumbers_only() {
+        let segments = vec![seg(2, 0.0, 1.0), seg(0, 1.0, 2.0), seg(2, 2.0, 3.0)];
+        let limited = limit_speakers(segments, 4);
+        assert_eq!(speakers(&limited), vec![0, 1, 0]);
+    }
+
+    #[test]
+    fn test_limit_speakers_merges_minor_speaker_into_nearest() {
+        // Speaker 2 talks least and sits right after speaker 1
+        let segments = vec![seg(0, 0.0, 5.0), seg(1, 10.0, 15.0), seg(2, 15.2, 15.8)];
+        let limited = limit_speakers(segments, 2);
+        assert_eq!(speakers(&limited), vec![0, 1, 1]);
+        assert_eq!(speaker_count(&limited), 2);
+    }
+
+    #[test]
+    fn test_limit_speakers_single() {
+        let segments = vec![seg(0, 0.0, 1.0), seg(1, 1.0, 3.0), seg(2, 3.0, 4.0)];
+        let limited = limit_speakers(segments, 1);
+        assert_eq!(speakers(&limited), vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_settings_override_thresholds() {
+        let settings = DiarizationSettings {
+            onset: Some(0.5),
+            offset: Some(1.5),
+            ..DiarizationSettings::default()
+        };
+        let config = settings.sortformer_config();
+        assert_eq!(config.onset, 0.5);
+        assert_eq!(config.offset, 1.0);
+        assert_eq!(
+            DiarizationSettings::default().sortformer_config().onset,
+            DiarizationConfig::callhome().onset
+        );
+    }
+
+    #[test]
+    fn test_dominant_speaker_by_overlap() {
+        let segments = vec![seg(0, 0.0, 2.0), seg(1, 2.0, 5.0)];
+        assert_eq!(dominant_speaker(&segments, 1.0, 4.0), Some(1));
+        assert_eq!(dominant_speaker(&segments, 0.0, 2.5), Some(0));
+        assert_eq!(dominant_speaker(&segments, 6.0, 7.0), None);
+    }
+
+    #[test]
+    fn test_join_speaker_turns_labels_changes_only() {
+        let pieces = vec![
+            (Some(0), "Привіт.".to_string()),
+            (Some(0), "Як справи?".to_string()),
+            (None, "  ".to_string()),
+            (Some(1), "Добре.".to_string()),
+            (None, "Дякую.".to_string()),
+        ];
+        assert_eq!(
+            join_speaker_turns(&pieces, &SpeakerNames::default()),
+            "[Спікер 1] Привіт. Як справи? [Спікер 2] Добре. Дякую."
+        );
+    }
+
+    #[test]
+    fn test_speaker_labels_sorted_unique() {
+        assert_eq!(
+            speaker_labels([1, 0, 1], &SpeakerNames::default()),
+            vec!["Спікер 1", "Спікер 2"]
+        );
+    }
+
+    #[test]
+    fn test_speaker_names_fall_back_to_numbering() {
+        let names = SpeakerNames::new(HashMap::from([(1, "Спікер 3".to_string())]));
+        assert_eq!(speaker_labels([0, 1], &names), vec!["Спікер 1", "Спікер 3"]);
+
+        let pieces = vec![(Some(1), "Так.".to_string()), (Some(0), "Ні.".to_string())];
+        assert_eq!(join_speaker_turns(&pieces, &names), "[Спікер 3] Так. [Спікер 1] Ні.");
+    }
+
+    #[test]
+    fn test_speaker_count_empty() {
+        assert_eq!(speaker_count(&[]), 0);
+    }
+
+    #[test]
+    fn test_overlap_regions_between_different_speakers() {
+        let segments = vec![
+            seg(0, 0.0, 5.0),
+            seg(1, 4.0, 8.0),
+            seg(2, 4.5, 6.0),
+            // Same speaker overlapping itself is not overlap
+            seg(0, 9.0, 11.0),
+            seg(0, 10.0, 12.0),
+            // Too short to count
+            seg(1, 11.9, 13.0),
+        ];
+        let regions = overlap_regions(&segments);
+        assert_eq!(
+            regions,
+            vec![OverlapRegion {
+                start_time: 4.0,
+                end_time: 6.0
+            }]
+        );
+    }
+
+    #[test]
+    fn test_mark_overlap() {
+        let regions = vec![OverlapRegion {
+            start_time: 4.0,
+            end_time: 6.0,
+        }];
+        assert!(has_overlap(&regions, 5.0, 9.0));
+        assert!(!has_overlap(&regions, 5.9, 9.0));
+        assert_eq!(mark_overlap("так".to_string(), &regions, 3.0, 5.0), "[перекриття] так");
+        assert_eq!(mark_overlap("так".to_string(), &regions, 7.0, 9.0), "так");
+    }
+}
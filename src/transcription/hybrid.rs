@@ -0,0 +1,146 @@
+//! Hybrid diarization: channel split plus Sortformer.
+//!
+//! In a call recording the microphone channel is the local user and the
+//! loopback channel is everyone else. The hybrid method keeps that split,
+//! which never confuses "me" with "others", and runs Sortformer only on the
+//! loopback channel to tell the remote participants apart. Both channels are
+//! transcribed with word timestamps and their pieces interleaved by time.
+
+use crate::domain::types::TimedWord;
+use crate::transcription::word_attribution::{append_word, SpeakerPiece};
+
+/// A pause this long in the local speaker's words starts a new piece, so
+/// remote replies in between land in order.
+const LOCAL_PAUSE_SECS: f64 = 1.5;
+
+/// Who said a piece of a hybrid transcript.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HybridSpeaker {
+    /// The microphone channel.
+    Local,
+    /// A Sortformer speaker of the loopback channel.
+    Remote(usize),
+}
+
+/// Consecutive words of one hybrid speaker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HybridPiece {
+    pub speaker: HybridSpeaker,
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Group the microphone words into pieces, split at pauses.
+pub fn local_pieces(words: &[TimedWord]) -> Vec<HybridPiece> {
+    let mut pieces: Vec<HybridPiece> = Vec::new();
+    for word in words {
+        match pieces.last_mut() {
+            Some(piece) if word.start - piece.end < LOCAL_PAUSE_SECS => {
+                append_word(&mut piece.text, &word.text);
+                piece.end = word.end;
+            }
+            _ => pieces.push(HybridPiece {
+                speaker: HybridSpeaker::Local,
+                text: word.text.clone(),
+                start: word.start,
+                end: word.end,
+            }),
+        }
+    }
+    pieces
+}
+
+/// Merge local and remote pieces in time order.
+///
+/// Adjacent pieces of the same speaker are joined.
+pub fn interleave(local: Vec<HybridPiece>, remote: Vec<SpeakerPiece>) -> Vec<HybridPiece> {
+    let mut all = local;
+    all.extend(remote.into_iter().map(|piece| HybridPiece {
+        speaker: HybridSpeaker::Remote(piece.speaker_id),
+        text: piece.text,
+        start: piece.start,
+        end: piece.end,
+    }));
+    all.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut merged: Vec<HybridPiece> = Vec::new();
+    for piece in all {
+        match merged.last_mut() {
+            Some(last) if last.speaker == piece.speaker => {
+                last.text.push(' ');
+                last.text.push_str(&piece.text);
+                last.end = last.end.max(piece.end);
+            }
+            _ => merged.push(piece),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn word(text: &str, start: f64, end: f64) -> TimedWord {
+        TimedWord {
+            text: text.to_string(),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn test_local_pieces_split_at_pauses() {
+        let words = vec![
+            word("Добрий", 0.0, 0.4),
+            word("день", 0.5, 0.8),
+            word(".", 0.8, 0.8),
+            word("Так", 5.0, 5.3),
+        ];
+        let pieces = local_pieces(&words);
+        assert_eq!(pieces.len(), 2);
+        assert_eq!(pieces[0].text, "Добрий день.");
+        assert_eq!((pieces[0].start, pieces[0].end), (0.0, 0.8));
+        assert_eq!(pieces[1].text, "Так");
+        assert!(local_pieces(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_interleave_orders_by_time_and_joins_same_speaker() {
+        let local = local_pieces(&[word("Привіт", 0.0, 0.5), word("Так", 4.0, 4.3)]);
+        let remote = vec![
+            SpeakerPiece {
+                speaker_id: 0,
+                text: "Вітаю".to_string(),
+                start: 1.0,
+                end: 1.5,
+            },
+            SpeakerPiece {
+                speaker_id: 1,
+                text: "Почнемо?".to_string(),
+                start: 2.0,
+                end: 3.0,
+            },
+            SpeakerPiece {
+                speaker_id: 1,
+                text: "Увімкніть екран".to_string(),
+                start: 3.2,
+                end: 3.8,
+            },
+        ];
+        let merged = interleave(local, remote);
+        let speakers: Vec<HybridSpeaker> = merged.iter().map(|p| p.speaker).collect();
+        assert_eq!(
+            speakers,
+            vec![
+                HybridSpeaker::Local,
+                HybridSpeaker::Remote(0),
+                HybridSpeaker::Remote(1),
+                HybridSpeaker::Local,
+            ]
+        );
+        assert_eq!(merged[2].text, "Почнемо? Увімкніть екран");
+        assert_eq!(merged[2].end, 3.8);
+    }
+}
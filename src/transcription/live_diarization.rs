@@ -0,0 +1,247 @@
+//! Incremental diarization during conference recording.
+//!
+//! Sortformer in parakeet-rs resets its streaming state on every call, so
+//! the recording is diarized in consecutive blocks as it grows. Speaker ids
+//! are per call, so each block's speakers are linked to the speakers heard
+//! so far by voiceprint (see [`speaker_id`](super::speaker_id)). The result
+//! drives a "who is speaking now" indicator, and at stop only the tail after
+//! the last block is left to diarize.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use super::diarization::{DiarizationEngine, DiarizationSegment};
+use super::speaker_id::{cosine_similarity, speaker_voiceprints};
+use anyhow::Result;
+
+const SAMPLE_RATE: f64 = 16000.0;
+
+/// Audio diarized per block.
+pub const LIVE_BLOCK_SECS: f64 = 6.0;
+
+/// A speaker counts as "speaking now" if their segment reaches this close
+/// to the end of the diarized audio.
+const CURRENT_SPEAKER_TOLERANCE_SECS: f64 = 1.0;
+
+/// Segments of the same speaker this close together across a block
+/// boundary are joined.
+const JOIN_GAP_SECS: f64 = 0.1;
+
+/// A shorter tail at stop is not worth a Sortformer run.
+const MIN_TAIL_SECS: f64 = 0.5;
+
+/// Diarization accumulated block by block.
+#[derive(Debug, Clone)]
+pub struct LiveDiarization {
+    segments: Vec<DiarizationSegment>,
+    voiceprints: BTreeMap<usize, Vec<f32>>,
+    processed_samples: usize,
+    threshold: f32,
+}
+
+impl LiveDiarization {
+    /// `threshold` is the voiceprint similarity at which a block speaker
+    /// is taken to be an already known one.
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            segments: Vec::new(),
+            voiceprints: BTreeMap::new(),
+            processed_samples: 0,
+            threshold,
+        }
+    }
+
+    /// Samples of the recording diarized so far.
+    pub fn processed_samples(&self) -> usize {
+        self.processed_samples
+    }
+
+    /// Segments with recording-wide speaker ids and times.
+    pub fn segments(&self) -> &[DiarizationSegment] {
+        &self.segments
+    }
+
+    /// Add a block that follows the audio processed so far.
+    ///
+    /// `block_segments` are the diarizer's segments for `block`, with times
+    /// relative to the block start.
+    pub fn add_block(&mut self, block: &[f32], block_segments: &[DiarizationSegment]) {
+        let offset = self.processed_samples as f64 / SAMPLE_RATE;
+        let block_prints = speaker_voiceprints(block, block_segments);
+        let mut mapping = link_speakers(&self.voiceprints, &block_prints, self.next_speaker_id(), self.threshold);
+        for (block_id, print) in &block_prints {
+            self.voiceprints
+                .entry(mapping[block_id])
+                .or_insert_with(|| print.clone());
+        }
+
+        // Too little audio for a voiceprint: most likely the last speaker going on
+        let last_speaker = self.segments.last().map(|seg| seg.speaker_id);
+        for seg in block_segments {
+            if !mapping.contains_key(&seg.speaker_id) {
+                let id = last_speaker.unwrap_or_else(|| mapping.values().max().map_or(0, |id| id + 1));
+                mapping.insert(seg.speaker_id, id);
+            }
+        }
+
+        let mut block_segments: Vec<&DiarizationSegment> = block_segments.iter().collect();
+        block_segments.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+        for seg in block_segments {
+            let speaker_id = mapping[&seg.speaker_id];
+            let start_time = seg.start_time + offset;
+            let end_time = seg.end_time + offset;
+            let joined = self
+                .segments
+                .iter_mut()
+                .rev()
+                .find(|prev| prev.speaker_id == speaker_id)
+                .filter(|prev| prev.end_time >= offset - JOIN_GAP_SECS && start_time <= offset + JOIN_GAP_SECS);
+            match joined {
+                Some(prev) => prev.end_time = prev.end_time.max(end_time),
+                None => self.segments.push(DiarizationSegment {
+                    speaker_id,
+                    start_time,
+                    end_time,
+                }),
+            }
+        }
+        self.processed_samples += block.len();
+    }
+
+    /// Diarize `block` with `engine` and add it.
+    pub fn diarize_block(&mut self, engine: &mut DiarizationEngine, block: &[f32]) -> Result<()> {
+        let segments = engine.diarize(block)?;
+        self.add_block(block, &segments);
+        Ok(())
+    }
+
+    /// Diarize whatever of `samples` (the whole recording) is not processed
+    /// yet and return all segments.
+    pub fn finish(mut self, engine: &mut DiarizationEngine, samples: &[f32]) -> Result<Vec<DiarizationSegment>> {
+        let tail = samples.len().saturating_sub(self.processed_samples);
+        if tail as f64 >= MIN_TAIL_SECS * SAMPLE_RATE {
+            self.diarize_block(engine, &samples[self.processed_samples..])?;
+        }
+        Ok(self.segments)
+    }
+
+    fn next_speaker_id(&self) -> usize {
+        self.segments
+            .iter()
+            .map(|seg| seg.speaker_id)
+            .chain(self.voiceprints.keys().copied())
+            .max()
+            .map_or(0, |id| id + 1)
+    }
+
+    /// Speaker talking at the end of the diarized audio, if any.
+    pub fn current_speaker(&self) -> Option<usize> {
+        let end = self.processed_samples as f64 / SAMPLE_RATE;
+        self.segments
+            .iter()
+            .filter(|seg| seg.end_time >= end - CURRENT_SPEAKER_TOLERANCE_SECS)
+            .max_by(|a, b| a.end_time.total_cmp(&b.end_time))
+            .map(|seg| seg.speaker_id)
+    }
+}
+
+/// Map a block's speaker ids onto recording-wide ids.
+///
+/// Pairs are matched greedily from the most similar down, each known speaker
+/// at most once per block. Unmatched speakers get new ids from `next_id`.
+fn link_speakers(
+    known: &BTreeMap<usize, Vec<f32>>,
+    block_prints: &BTreeMap<usize, Vec<f32>>,
+    mut next_id: usize,
+    threshold: f32,
+) -> HashMap<usize, usize> {
+    let mut candidates: Vec<(f32, usize, usize)> = Vec::new();
+    for (&block_id, print) in block_prints {
+        for (&known_id, known_print) in known {
+            let similarity = cosine_similarity(print, known_print);
+            if similarity >= threshold {
+                candidates.push((similarity, block_id, known_id));
+            }
+        }
+    }
+    candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+    let mut mapping = HashMap::new();
+    let mut used = HashSet::new();
+    for (_, block_id, known_id) in candidates {
+        if mapping.contains_key(&block_id) || used.contains(&known_id) {
+            continue;
+        }
+        mapping.insert(block_id, known_id);
+        used.insert(known_id);
+    }
+
+    for &block_id in block_prints.keys() {
+        if !mapping.contains_key(&block_id) {
+            mapping.insert(block_id, next_id);
+            next_id += 1;
+        }
+    }
+    mapping
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seg(speaker_id: usize, start_time: f64, end_time: f64) -> DiarizationSegment {
+        DiarizationSegment {
+            speaker_id,
+            start_time,
+            end_time,
+        }
+    }
+
+    fn prints(entries: &[(usize, [f32; 2])]) -> BTreeMap<usize, Vec<f32>> {
+        entries.iter().map(|(id, p)| (*id, p.to_vec())).collect()
+    }
+
+    #[test]
+    fn test_link_speakers_matches_known_voices() {
+        let known = prints(&[(0, [1.0, 0.0]), (1, [0.0, 1.0])]);
+        // Block numbers the voices the other way round and adds a new one
+        let block = prints(&[(0, [0.1, 1.0]), (1, [1.0, 0.1]), (2, [-1.0, 0.0])]);
+        let mapping = link_speakers(&known, &block, 2, 0.9);
+        assert_eq!(mapping[&0], 1);
+        assert_eq!(mapping[&1], 0);
+        assert_eq!(mapping[&2], 2);
+    }
+
+    #[test]
+    fn test_link_speakers_uses_each_known_voice_once() {
+        let known = prints(&[(0, [1.0, 0.0])]);
+        let block = prints(&[(0, [1.0, 0.05]), (1, [1.0, 0.0])]);
+        let mapping = link_speakers(&known, &block, 1, 0.9);
+        assert_eq!(mapping[&1], 0);
+        assert_eq!(mapping[&0], 1);
+    }
+
+    #[test]
+    fn test_add_block_offsets_and_joins_across_boundary() {
+        let mut live = LiveDiarization::new(0.85);
+        let block = vec![0.0; 16000 * 2];
+        live.add_block(&block, &[seg(0, 0.5, 2.0)]);
+        live.add_block(&block, &[seg(0, 0.0, 1.0)]);
+
+        // Silence yields no voiceprints; the speaker is taken to continue
+        assert_eq!(live.segments().len(), 1);
+        assert_eq!(live.segments()[0].start_time, 0.5);
+        assert_eq!(live.segments()[0].end_time, 3.0);
+        assert_eq!(live.processed_samples(), 16000 * 4);
+    }
+
+    #[test]
+    fn test_current_speaker_only_near_end() {
+        let mut live = LiveDiarization::new(0.85);
+        let block = vec![0.0; 16000 * 4];
+        live.add_block(&block, &[seg(0, 0.0, 3.5)]);
+        assert_eq!(live.current_speaker(), Some(0));
+
+        live.add_block(&block, &[]);
+        assert_eq!(live.current_speaker(), None);
+    }
+}
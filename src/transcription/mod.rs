@@ -1,8 +1,24 @@
+pub mod accuracy;
+pub mod action_items;
+pub mod cache;
+pub mod captions;
 pub mod chunker;
+pub mod confidence;
+pub mod corrections;
 pub mod diarization;
+pub mod hybrid;
+pub mod live_diarization;
+pub mod postprocess;
+pub mod rttm;
+pub mod self_test;
 pub mod service;
+pub mod speaker_id;
+pub mod subtitles;
+pub mod talk_time;
 pub mod tdt;
+pub mod verbatim;
 pub mod whisper;
+pub mod word_attribution;
 
 pub use service::TranscriptionService;
 pub(crate) use tdt::ParakeetSTT;
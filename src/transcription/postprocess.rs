@@ -0,0 +1,225 @@
+//! Text post-processing applied to raw STT output.
+//!
+//! Post-processors are pluggable steps registered on `TranscriptionService`
+//! and run in order on every non-empty transcription result. The built-in
+//! rule-based punctuator restores sentence capitalization, commas before
+//! common Ukrainian conjunctions, and terminal punctuation for backends that
+//! return bare lowercase text.
+
+/// A text transformation step run after speech recognition.
+pub trait TextPostProcessor: Send {
+    /// Short identifier used in config and logs.
+    fn name(&self) -> &'static str;
+
+    /// Transform recognized text for the given language code.
+    fn process(&self, text: &str, language: &str) -> String;
+}
+
+/// Conjunctions that are preceded by a comma in Ukrainian subordinate clauses.
+const UK_COMMA_CONJUNCTIONS: &[&str] = &["але", "проте", "однак", "що", "щоб", "якщо", "бо", "тому що", "ніби"];
+
+/// Words that start a question when they open a sentence.
+const UK_QUESTION_WORDS: &[&str] = &[
+    "чи",
+    "хто",
+    "що",
+    "де",
+    "куди",
+    "коли",
+    "чому",
+    "навіщо",
+    "як",
+    "скільки",
+    "який",
+    "яка",
+    "яке",
+    "які",
+];
+
+/// Rule-based punctuation and capitalization restorer.
+///
+/// Comma and question rules apply only to Ukrainian; capitalization and
+/// terminal punctuation apply to every language.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RuleBasedPunctuator;
+
+impl RuleBasedPunctuator {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl TextPostProcessor for RuleBasedPunctuator {
+    fn name(&self) -> &'static str {
+        "rules"
+    }
+
+    fn process(&self, text: &str, language: &str) -> String {
+        let text = collapse_whitespace(text);
+        if text.is_empty() {
+            return text;
+        }
+
+        let text = if language == "uk" {
+            insert_conjunction_commas(&text)
+        } else {
+            text
+        };
+        let text = capitalize_sentences(&text);
+        terminate_sentence(&text, language)
+    }
+}
+
+/// Create a post-processor by config name. Returns `None` for "none" or unknown names.
+pub fn create_post_processor(name: &str) -> Option<Box<dyn TextPostProcessor>> {
+    match name {
+        "rules" => Some(Box::new(RuleBasedPunctuator::new())),
+        _ => None,
+    }
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Insert a comma before known conjunctions unless punctuation is already there.
+fn insert_conjunction_commas(text: &str) -> String {
+    let words: Vec<&str> = text.split(' ').collect();
+    let mut result = String::with_capacity(text.len() + 8);
+
+    for (i, word) in words.iter().enumerate() {
+        if i > 0 {
+            let prev = words[i - 1];
+            let prev_ends_with_punct = prev.ends_with([',', '.', '!', '?', ':', ';', '-', '—']);
+            let prev_is_conjunction_start = is_conjunction_at(&words, i - 1);
+            if !prev_ends_with_punct && !prev_is_conjunction_start && is_conjunction_at(&words, i) {
+                result.push(',');
+            }
+            result.push(' ');
+        }
+        result.push_str(word);
+    }
+
+    result
+}
+
+/// Check whether a conjunction (possibly multi-word) starts at `words[i]`.
+fn is_conjunction_at(words: &[&str], i: usize) -> bool {
+    UK_COMMA_CONJUNCTIONS.iter().any(|conj| {
+        let parts: Vec<&str> = conj.split(' ').collect();
+        parts.len() <= words.len() - i
+            && parts
+                .iter()
+                .zip(&words[i..])
+                .all(|(p, w)| w.trim_end_matches([',', '.', '!', '?']).to_lowercase() == *p)
+    })
+}
+
+/// Uppercase the first letter of the text and of each sentence.
+fn capitalize_sentences(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut capitalize_next = true;
+
+    for c in text.chars() {
+        if capitalize_next && c.is_alphabetic() {
+            result.extend(c.to_uppercase());
+            capitalize_next = false;
+        } else {
+            result.push(c);
+        }
+        if matches!(c, '.' | '!' | '?' | '…') {
+            capitalize_next = true;
+        }
+    }
+
+    result
+}
+
+/// Append terminal punctuation if the text does not end with one.
+fn terminate_sentence(text: &str, language: &str) -> String {
+    if text.ends_with(['.', '!', '?', '…']) {
+        return text.to_string();
+    }
+
+    let last_sentence_start = text.rfind(['.', '!', '?', '…']).map(|i| i + 1).unwrap_or(0);
+    let first_word = text[last_sentence_start..]
+        .split_whitespace()
+        .next()
+        .unwrap_or("")
+        .trim_end_matches(',')
+        .to_lowercase();
+
+    let is_question = language == "uk" && UK_QUESTION_WORDS.contains(&first_word.as_str());
+    let mut result = text.trim_end_matches([',', ';', ':']).to_string();
+    result.push(if is_question { '?' } else { '.' });
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn punctuate(text: &str) -> String {
+        RuleBasedPunctuator::new().process(text, "uk")
+    }
+
+    #[test]
+    fn test_capitalizes_and_terminates() {
+        assert_eq!(punctuate("привіт світе"), "Привіт світе.");
+    }
+
+    #[test]
+    fn test_capitalizes_after_sentence_end() {
+        assert_eq!(
+            punctuate("перше речення. друге речення"),
+            "Перше речення. Друге речення."
+        );
+    }
+
+    #[test]
+    fn test_inserts_comma_before_conjunction() {
+        assert_eq!(punctuate("я знаю що ти прийдеш"), "Я знаю, що ти прийдеш.");
+        assert_eq!(punctuate("хотів піти але не встиг"), "Хотів піти, але не встиг.");
+    }
+
+    #[test]
+    fn test_multiword_conjunction_single_comma() {
+        assert_eq!(
+            punctuate("я пішов тому що був втомлений"),
+            "Я пішов, тому що був втомлений."
+        );
+    }
+
+    #[test]
+    fn test_no_comma_at_sentence_start() {
+        assert_eq!(punctuate("що ти робиш"), "Що ти робиш?");
+    }
+
+    #[test]
+    fn test_existing_punctuation_preserved() {
+        assert_eq!(punctuate("Я знаю, що ти прийдеш!"), "Я знаю, що ти прийдеш!");
+    }
+
+    #[test]
+    fn test_question_detection() {
+        assert_eq!(punctuate("чи ти готовий"), "Чи ти готовий?");
+    }
+
+    #[test]
+    fn test_non_ukrainian_skips_commas() {
+        let result = RuleBasedPunctuator::new().process("i know that you came", "en");
+        assert_eq!(result, "I know that you came.");
+    }
+
+    #[test]
+    fn test_empty_text() {
+        assert_eq!(punctuate("   "), "");
+    }
+
+    #[test]
+    fn test_create_post_processor() {
+        assert_eq!(create_post_processor("rules").unwrap().name(), "rules");
+        assert!(create_post_processor("none").is_none());
+        assert!(create_post_processor("unknown").is_none());
+    }
+}
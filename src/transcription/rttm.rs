@@ -0,0 +1,182 @@
+//! RTTM (Rich Transcription Time Marked) output for diarization.
+//!
+//! Each speaker turn becomes one line:
+//! `SPEAKER <file-id> 1 <onset> <duration> <NA> <NA> <speaker> <NA> <NA>`,
+//! the format expected by standard diarization scoring tools (dscore,
+//! pyannote.metrics, md-eval).
+
+use crate::recording::split::SilenceRegion;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A time span attributed to one speaker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpeakerTurn {
+    pub speaker: String,
+    /// Start time in seconds.
+    pub start: f64,
+    /// End time in seconds.
+    pub end: f64,
+}
+
+/// Format speaker turns as RTTM, sorted by onset.
+///
+/// RTTM fields are whitespace-separated, so whitespace in the file id and
+/// speaker names is replaced with `_`. Empty turns are skipped.
+pub fn format_rttm(file_id: &str, turns: &[SpeakerTurn]) -> String {
+    let file_id = sanitize_field(file_id);
+    let mut sorted: Vec<&SpeakerTurn> = turns.iter().filter(|t| t.end > t.start).collect();
+    sorted.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    sorted
+        .iter()
+        .map(|t| {
+            format!(
+                "SPEAKER {} 1 {:.3} {:.3} <NA> <NA> {} <NA> <NA>\n",
+                file_id,
+                t.start,
+                t.end - t.start,
+                sanitize_field(&t.speaker)
+            )
+        })
+        .collect()
+}
+
+/// Parse RTTM `SPEAKER` lines back into turns. Other and malformed lines are skipped.
+pub fn parse_rttm(content: &str) -> Vec<SpeakerTurn> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 8 || fields[0] != "SPEAKER" {
+                return None;
+            }
+            let start: f64 = fields[3].parse().ok()?;
+            let duration: f64 = fields[4].parse().ok()?;
+            Some(SpeakerTurn {
+                speaker: fields[7].replace('_', " "),
+                start,
+                end: start + duration,
+            })
+        })
+        .collect()
+}
+
+/// RTTM file id for an input file: its name without extension.
+pub fn rttm_file_id(path: &Path) -> String {
+    path.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "audio".to_string())
+}
+
+/// Speech turns of a single speaker: everything between the given silences.
+///
+/// Used for channel diarization, where each channel is one speaker and
+/// only the speech/silence boundaries need to be found.
+pub fn speech_turns(
+    speaker: &str,
+    silences: &[SilenceRegion],
+    total_samples: usize,
+    sample_rate: u32,
+) -> Vec<SpeakerTurn> {
+    let to_secs = |sample: usize| sample as f64 / sample_rate as f64;
+    let mut turns = Vec::new();
+    let mut speech_start = 0;
+
+    for silence in silences {
+        if silence.start_sample > speech_start {
+            turns.push(SpeakerTurn {
+                speaker: speaker.to_string(),
+                start: to_secs(speech_start),
+                end: to_secs(silence.start_sample),
+            });
+        }
+        speech_start = speech_start.max(silence.end_sample);
+    }
+    if total_samples > speech_start {
+        turns.push(SpeakerTurn {
+            speaker: speaker.to_string(),
+            start: to_secs(speech_start),
+            end: to_secs(total_samples),
+        });
+    }
+
+    turns
+}
+
+fn sanitize_field(value: &str) -> String {
+    let sanitized: String = value
+        .trim()
+        .chars()
+        .map(|c| if c.is_whitespace() { '_' } else { c })
+        .collect();
+    if sanitized.is_empty() {
+        "<NA>".to_string()
+    } else {
+        sanitized
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(speaker: &str, start: f64, end: f64) -> SpeakerTurn {
+        SpeakerTurn {
+            speaker: speaker.to_string(),
+            start,
+            end,
+        }
+    }
+
+    fn silence(start_sample: usize, end_sample: usize) -> SilenceRegion {
+        SilenceRegion {
+            start_sample,
+            end_sample,
+            duration_ms: ((end_sample - start_sample) / 16) as u32,
+        }
+    }
+
+    #[test]
+    fn test_format_rttm_sorted_and_sanitized() {
+        let turns = vec![turn("Speaker 2", 3.5, 5.25), turn("Speaker 1", 0.0, 3.5)];
+        let rttm = format_rttm("my meeting", &turns);
+        assert_eq!(
+            rttm,
+            "SPEAKER my_meeting 1 0.000 3.500 <NA> <NA> Speaker_1 <NA> <NA>\n\
+             SPEAKER my_meeting 1 3.500 1.750 <NA> <NA> Speaker_2 <NA> <NA>\n"
+        );
+    }
+
+    #[test]
+    fn test_format_rttm_skips_empty_turns() {
+        assert_eq!(format_rttm("a", &[turn("Mic", 1.0, 1.0)]), "");
+    }
+
+    #[test]
+    fn test_parse_rttm_roundtrip() {
+        let turns = vec![turn("Спікер 1", 0.0, 3.5), turn("Спікер 2", 3.5, 5.25)];
+        let parsed = parse_rttm(&format!("# comment\n{}garbage\n", format_rttm("call", &turns)));
+        assert_eq!(parsed, turns);
+    }
+
+    #[test]
+    fn test_rttm_file_id() {
+        assert_eq!(rttm_file_id(Path::new("/tmp/call.wav")), "call");
+    }
+
+    #[test]
+    fn test_speech_turns_between_silences() {
+        // 0-1s speech, 1-2s silence, 2-3s speech, 3-4s silence
+        let silences = vec![silence(16000, 32000), silence(48000, 64000)];
+        let turns = speech_turns("Mic", &silences, 64000, 16000);
+        assert_eq!(turns, vec![turn("Mic", 0.0, 1.0), turn("Mic", 2.0, 3.0)]);
+    }
+
+    #[test]
+    fn test_speech_turns_leading_silence_and_no_silence() {
+        let turns = speech_turns("Mic", &[silence(0, 8000)], 16000, 16000);
+        assert_eq!(turns, vec![turn("Mic", 0.5, 1.0)]);
+        assert_eq!(speech_turns("Mic", &[], 16000, 16000), vec![turn("Mic", 0.0, 1.0)]);
+    }
+}
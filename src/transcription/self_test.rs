@@ -0,0 +1,118 @@
+//! Recognition self-test for the first run.
+//!
+//! Transcribes a short sample with a known text and compares the result with
+//! it, so a new user learns that the model loads, the audio is decoded and
+//! the transcription path works before the first real dictation. The sample
+//! is the English JFK clip from the whisper.cpp repo; it is transcribed as
+//! English whatever language the user picked, since a multilingual model
+//! asked for another language would translate it.
+
+use super::accuracy::word_error_rate;
+use crate::cli::args::ChannelMode;
+use crate::cli::wav_reader::{prepare_for_whisper, read_wav};
+use crate::domain::traits::Transcription;
+use anyhow::{bail, Result};
+use std::path::Path;
+
+/// What is said in the sample.
+pub const SAMPLE_REFERENCE: &str =
+    "And so my fellow Americans, ask not what your country can do for you, ask what you can do for your country.";
+
+/// Language the sample is transcribed in.
+pub const SAMPLE_LANGUAGE: &str = "en";
+
+/// Highest word error rate at which recognition counts as working.
+///
+/// Even tiny models stay well below it; a wrong language, broken audio or a
+/// damaged model end up far above.
+const MAX_WORD_ERROR_RATE: f64 = 0.3;
+
+/// Outcome of one self-test run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelfTestResult {
+    /// What the model heard.
+    pub text: String,
+    pub word_error_rate: f64,
+}
+
+impl SelfTestResult {
+    pub fn evaluate(text: &str) -> Self {
+        Self {
+            text: text.trim().to_string(),
+            word_error_rate: word_error_rate(SAMPLE_REFERENCE, text),
+        }
+    }
+
+    pub fn passed(&self) -> bool {
+        self.word_error_rate <= MAX_WORD_ERROR_RATE
+    }
+
+    /// Share of the reference recognized correctly, in percent.
+    pub fn accuracy_percent(&self) -> u32 {
+        ((1.0 - self.word_error_rate).max(0.0) * 100.0).round() as u32
+    }
+}
+
+/// Transcribe the sample at `path` and compare it with the reference.
+pub fn run_self_test(transcription: &dyn Transcription, path: &Path) -> Result<SelfTestResult> {
+    if !transcription.is_loaded() {
+        bail!("Модель ще не завантажена");
+    }
+    let prepared = prepare_for_whisper(&read_wav(path)?, ChannelMode::Mix, None)?;
+    let text = transcription.transcribe(&prepared.samples, SAMPLE_LANGUAGE)?;
+    Ok(SelfTestResult::evaluate(&text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::mocks::MockTranscription;
+
+    #[test]
+    fn test_evaluate_accepts_small_mistakes() {
+        let result = SelfTestResult::evaluate(
+            " And so, my fellow Americans, ask not what your country can do for you, ask what you can do for your country.",
+        );
+        assert!(result.passed());
+        assert_eq!(result.accuracy_percent(), 100);
+
+        let result = SelfTestResult::evaluate(
+            "And so my fellow American ask not what a country can do for you ask what you can do for your country",
+        );
+        assert!(result.passed());
+        assert_eq!(result.accuracy_percent(), 91);
+    }
+
+    #[test]
+    fn test_evaluate_rejects_wrong_text() {
+        let result = SelfTestResult::evaluate("І так, мої співвітчизники американці");
+        assert!(!result.passed());
+        assert_eq!(result.accuracy_percent(), 0);
+    }
+
+    #[test]
+    fn test_run_self_test_requires_loaded_model() {
+        let err = run_self_test(&MockTranscription::unloaded(), Path::new("missing.wav")).unwrap_err();
+        assert_eq!(err.to_string(), "Модель ще не завантажена");
+    }
+
+    #[test]
+    fn test_run_self_test_transcribes_sample() {
+        let path = std::env::temp_dir().join(format!("s2t_self_test_{}.wav", std::process::id()));
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 16000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for _ in 0..16000 {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let result = run_self_test(&MockTranscription::returning(SAMPLE_REFERENCE), &path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert!(result.passed());
+    }
+}
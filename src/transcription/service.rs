@@ -4,7 +4,21 @@
 //! supporting multiple backends (Whisper, Parakeet TDT).
 
 use crate::domain::traits::Transcription;
-use crate::transcription::diarization::DiarizationEngine;
+use crate::domain::types::{join_segments, TimedWord, TranscriptSegment};
+use crate::infrastructure::models::{model_display_name, TDT_DISPLAY_NAME};
+use crate::transcription::confidence::{speaker_confidence, turn_confidence};
+use crate::transcription::diarization::{
+    join_speaker_turns, limit_speakers, mark_overlap, overlap_regions, speaker_labels, DiarizationEngine,
+    DiarizationSegment,
+};
+use crate::transcription::hybrid::{interleave, local_pieces, HybridPiece, HybridSpeaker};
+use crate::transcription::live_diarization::LiveDiarization;
+use crate::transcription::postprocess::TextPostProcessor;
+use crate::transcription::rttm::SpeakerTurn;
+use crate::transcription::verbatim::mark_disfluencies;
+use crate::transcription::word_attribution::{
+    attribute_words, transcribe_words_windowed, SpeakerAttribution, SpeakerPiece, DEFAULT_WORD_WINDOW_SECS,
+};
 use crate::transcription::ParakeetSTT;
 use crate::transcription::WhisperSTT;
 use anyhow::{Context, Result};
@@ -18,6 +32,30 @@ pub enum BackendType {
     Tdt,
 }
 
+/// Diarized transcription of a conference recording.
+#[derive(Debug, Clone)]
+pub struct ConferenceTranscript {
+    /// Text of the segments, each with a `[speaker]` label
+    pub text: String,
+    /// Speaker turns found by diarization (empty for channel-based diarization)
+    pub turns: Vec<SpeakerTurn>,
+    /// Confidence of each segment's speaker (empty for channel-based diarization)
+    pub confidence: Vec<f32>,
+    /// Transcribed speaker segments in text order
+    pub segments: Vec<TranscriptSegment>,
+}
+
+impl ConferenceTranscript {
+    fn new(segments: Vec<TranscriptSegment>, turns: Vec<SpeakerTurn>, confidence: Vec<f32>) -> Self {
+        Self {
+            text: join_segments(&segments),
+            turns,
+            confidence,
+            segments,
+        }
+    }
+}
+
 /// Transcription backend variants.
 enum TranscriptionBackend {
     Whisper(WhisperSTT),
@@ -28,6 +66,9 @@ enum TranscriptionBackend {
 /// Unified transcription service supporting multiple STT backends.
 pub struct TranscriptionService {
     backend: TranscriptionBackend,
+    post_processors: Vec<Box<dyn TextPostProcessor>>,
+    /// Keep hesitations and cut-off words, marked up
+    verbatim: bool,
 }
 
 impl TranscriptionService {
@@ -35,6 +76,8 @@ impl TranscriptionService {
     pub fn new() -> Self {
         Self {
             backend: TranscriptionBackend::None,
+            post_processors: Vec::new(),
+            verbatim: false,
         }
     }
 
@@ -43,6 +86,8 @@ impl TranscriptionService {
         let whisper = WhisperSTT::new(model_path)?;
         Ok(Self {
             backend: TranscriptionBackend::Whisper(whisper),
+            post_processors: Vec::new(),
+            verbatim: false,
         })
     }
 
@@ -56,6 +101,8 @@ impl TranscriptionService {
         let tdt = ParakeetSTT::new(model_dir)?;
         Ok(Self {
             backend: TranscriptionBackend::Tdt(Box::new(tdt)),
+            post_processors: Vec::new(),
+            verbatim: false,
         })
     }
 
@@ -69,6 +116,19 @@ impl TranscriptionService {
         }
     }
 
+    /// Name of the loaded model as shown to the user, kept with history entries.
+    pub fn model_label(&self) -> Option<String> {
+        match &self.backend {
+            TranscriptionBackend::Whisper(w) => {
+                let path = Transcription::model_name(w)?;
+                let filename = Path::new(&path).file_name()?.to_string_lossy();
+                Some(model_display_name(&filename))
+            }
+            TranscriptionBackend::Tdt(_) => Some(TDT_DISPLAY_NAME.to_string()),
+            TranscriptionBackend::None => None,
+        }
+    }
+
     /// Check if the backend has built-in punctuation.
     ///
     /// Parakeet TDT includes punctuation and capitalization;
@@ -78,26 +138,83 @@ impl TranscriptionService {
         matches!(&self.backend, TranscriptionBackend::Tdt(_))
     }
 
+    /// Register a text post-processing step, run after every transcription.
+    ///
+    /// Steps run in registration order on non-empty results.
+    pub fn add_post_processor(&mut self, processor: Box<dyn TextPostProcessor>) {
+        self.post_processors.push(processor);
+    }
+
+    /// Keep hesitations and cut-off words with markup (`[еее]`, `сло-`).
+    ///
+    /// Whisper is prompted to transcribe them; the markup runs after the
+    /// other post-processing steps, and on words with timestamps too.
+    pub fn set_verbatim(&mut self, verbatim: bool) {
+        self.verbatim = verbatim;
+    }
+
+    /// Switch to another backend.
+    ///
+    /// The post-processing steps were chosen for the previous backend
+    /// (punctuation for TDT only), so they are dropped with it.
+    fn replace_backend(&mut self, backend: TranscriptionBackend) {
+        self.backend = backend;
+        self.post_processors.clear();
+    }
+
+    /// Run all registered post-processors on recognized text.
+    fn post_process(&self, text: String, language: &str) -> String {
+        if text.trim().is_empty() {
+            return text;
+        }
+        let text = self
+            .post_processors
+            .iter()
+            .fold(text, |acc, processor| processor.process(&acc, language));
+        if self.verbatim {
+            mark_disfluencies(&text)
+        } else {
+            text
+        }
+    }
+
     /// Transcribe conference recording with diarization.
     ///
     /// Works with any loaded backend (Whisper or TDT).
     /// Selects diarization strategy based on method parameter:
     /// - "sortformer": neural speaker diarization (if engine is available)
+    /// - "hybrid": mic = "Ви", Sortformer splits the loopback channel (if available)
     /// - anything else: channel-based (mic = "Ви", loopback = "Учасник")
+    ///
+    /// Returns the labelled text with its segments and, for Sortformer and
+    /// hybrid, the speaker turns and the confidence of each segment (both
+    /// empty for channel-based diarization, where each channel is one speaker).
     pub fn transcribe_conference(
         &self,
         mic_samples: &[f32],
         loopback_samples: &[f32],
         language: &str,
         diarization_method: &str,
+        attribution: SpeakerAttribution,
         diarization_engine: Option<&mut DiarizationEngine>,
-    ) -> Result<String> {
+    ) -> Result<ConferenceTranscript> {
         // Try Sortformer diarization if requested and available
-        if diarization_method == "sortformer" {
-            if let Some(engine) = diarization_engine {
-                if engine.is_available() {
-                    return self.transcribe_with_sortformer(mic_samples, loopback_samples, language, engine);
+        if let Some(engine) = diarization_engine.filter(|engine| engine.is_available()) {
+            match diarization_method {
+                "sortformer" => {
+                    return self.transcribe_with_sortformer(
+                        mic_samples,
+                        loopback_samples,
+                        language,
+                        attribution,
+                        engine,
+                        None,
+                    );
+                }
+                "hybrid" => {
+                    return self.transcribe_hybrid(mic_samples, loopback_samples, language, attribution, engine);
                 }
+                _ => {}
             }
         }
 
@@ -105,91 +222,360 @@ impl TranscriptionService {
         self.transcribe_channel_diarization(mic_samples, loopback_samples, language)
     }
 
+    /// Transcribe a conference recording diarized live while it was recorded.
+    ///
+    /// Continues `live` over the rest of the recording instead of diarizing
+    /// it all again. Falls back to channel-based diarization if Sortformer
+    /// is not loaded.
+    pub fn transcribe_conference_live(
+        &self,
+        mic_samples: &[f32],
+        loopback_samples: &[f32],
+        language: &str,
+        attribution: SpeakerAttribution,
+        engine: &mut DiarizationEngine,
+        live: LiveDiarization,
+    ) -> Result<ConferenceTranscript> {
+        if !engine.is_available() {
+            return self.transcribe_channel_diarization(mic_samples, loopback_samples, language);
+        }
+        self.transcribe_with_sortformer(mic_samples, loopback_samples, language, attribution, engine, Some(live))
+    }
+
     /// Channel-based diarization: transcribe mic and loopback separately.
+    ///
+    /// Each channel becomes one segment spanning the whole channel.
     fn transcribe_channel_diarization(
         &self,
         mic_samples: &[f32],
         loopback_samples: &[f32],
         language: &str,
-    ) -> Result<String> {
-        let mic_text = if !mic_samples.is_empty() {
-            Transcription::transcribe(self, mic_samples, language)?
-        } else {
-            String::new()
-        };
-
-        let loopback_text = if !loopback_samples.is_empty() {
-            Transcription::transcribe(self, loopback_samples, language)?
-        } else {
-            String::new()
-        };
-
-        let mut result = String::new();
-        if !mic_text.is_empty() {
-            result.push_str("[Ви] ");
-            result.push_str(&mic_text);
-        }
-        if !loopback_text.is_empty() {
-            if !result.is_empty() {
-                result.push(' ');
+    ) -> Result<ConferenceTranscript> {
+        let mut segments = Vec::new();
+        for (speaker, samples) in [("Ви", mic_samples), ("Учасник", loopback_samples)] {
+            if samples.is_empty() {
+                continue;
+            }
+            let text = Transcription::transcribe(self, samples, language)?;
+            if !text.is_empty() {
+                segments.push(TranscriptSegment {
+                    speaker: speaker.to_string(),
+                    text,
+                    start: 0.0,
+                    end: samples.len() as f64 / 16000.0,
+                });
             }
-            result.push_str("[Учасник] ");
-            result.push_str(&loopback_text);
         }
-
-        Ok(result)
+        Ok(ConferenceTranscript::new(segments, Vec::new(), Vec::new()))
     }
 
     /// Sortformer-based diarization: mix channels, diarize, transcribe segments.
+    ///
+    /// With `live`, only the audio after its last block is diarized.
     fn transcribe_with_sortformer(
         &self,
         mic_samples: &[f32],
         loopback_samples: &[f32],
         language: &str,
+        attribution: SpeakerAttribution,
         engine: &mut DiarizationEngine,
-    ) -> Result<String> {
-        let max_len = mic_samples.len().max(loopback_samples.len());
-        let mut mixed = Vec::with_capacity(max_len);
-        for i in 0..max_len {
-            let mic_val = mic_samples.get(i).copied().unwrap_or(0.0);
-            let loopback_val = loopback_samples.get(i).copied().unwrap_or(0.0);
-            mixed.push((mic_val + loopback_val) / 2.0);
+        live: Option<LiveDiarization>,
+    ) -> Result<ConferenceTranscript> {
+        let mixed = mix_channels(mic_samples, loopback_samples);
+
+        let segments = match live {
+            Some(live) => limit_speakers(
+                live.finish(engine, &mixed).context("Помилка diarization")?,
+                engine.settings().max_speakers,
+            ),
+            None => engine.diarize(&mixed).context("Помилка diarization")?,
+        };
+
+        let parts = self.transcribe_speaker_segments(&mixed, &segments, language, attribution)?;
+        if parts.is_empty() {
+            return self.transcribe_channel_diarization(mic_samples, loopback_samples, language);
         }
 
-        let segments = engine.diarize(&mixed).context("Помилка diarization")?;
+        let names = engine.speaker_names(&mixed, &segments);
+        let transcript_segments = parts
+            .iter()
+            .map(|part| TranscriptSegment {
+                speaker: names.label(part.speaker_id),
+                text: part.text.clone(),
+                start: part.start,
+                end: part.end,
+            })
+            .collect();
+        let confidence = parts
+            .iter()
+            .map(|part| speaker_confidence(&segments, part.speaker_id, part.start, part.end))
+            .collect();
+        let turns = segments
+            .iter()
+            .map(|seg| SpeakerTurn {
+                speaker: names.label(seg.speaker_id),
+                start: seg.start_time,
+                end: seg.end_time,
+            })
+            .collect();
+        Ok(ConferenceTranscript::new(transcript_segments, turns, confidence))
+    }
 
+    /// Hybrid diarization: the mic channel is "Ви", Sortformer splits the loopback.
+    ///
+    /// With word attribution both channels are transcribed with word
+    /// timestamps and interleaved by time; otherwise the mic text comes
+    /// first, followed by the remote speakers' segments.
+    fn transcribe_hybrid(
+        &self,
+        mic_samples: &[f32],
+        loopback_samples: &[f32],
+        language: &str,
+        attribution: SpeakerAttribution,
+        engine: &mut DiarizationEngine,
+    ) -> Result<ConferenceTranscript> {
+        let segments = engine.diarize(loopback_samples).context("Помилка diarization")?;
         if segments.is_empty() {
             return self.transcribe_channel_diarization(mic_samples, loopback_samples, language);
         }
+        let names = engine.speaker_names(loopback_samples, &segments);
+        let label = |speaker: HybridSpeaker| match speaker {
+            HybridSpeaker::Local => "Ви".to_string(),
+            HybridSpeaker::Remote(id) => names.label(id),
+        };
+
+        if attribution == SpeakerAttribution::Words {
+            let window = DEFAULT_WORD_WINDOW_SECS * 16000;
+            let pieces = self
+                .transcribe_local_words(mic_samples, language, window)
+                .and_then(|local| {
+                    let remote = self.transcribe_attributed_words(loopback_samples, &segments, language, window)?;
+                    Ok(interleave(local, remote))
+                });
+            match pieces {
+                Ok(pieces) => {
+                    let transcript_segments = pieces
+                        .iter()
+                        .map(|piece| TranscriptSegment {
+                            speaker: label(piece.speaker),
+                            text: piece.text.clone(),
+                            start: piece.start,
+                            end: piece.end,
+                        })
+                        .collect();
+                    let confidence = pieces
+                        .iter()
+                        .map(|piece| match piece.speaker {
+                            HybridSpeaker::Local => 1.0,
+                            HybridSpeaker::Remote(id) => speaker_confidence(&segments, id, piece.start, piece.end),
+                        })
+                        .collect();
+                    let mut turns: Vec<SpeakerTurn> = pieces
+                        .iter()
+                        .filter(|piece| piece.speaker == HybridSpeaker::Local)
+                        .map(|piece| SpeakerTurn {
+                            speaker: label(piece.speaker),
+                            start: piece.start,
+                            end: piece.end,
+                        })
+                        .chain(segments.iter().map(|seg| SpeakerTurn {
+                            speaker: label(HybridSpeaker::Remote(seg.speaker_id)),
+                            start: seg.start_time,
+                            end: seg.end_time,
+                        }))
+                        .collect();
+                    turns.sort_by(|a, b| a.start.total_cmp(&b.start));
+                    return Ok(ConferenceTranscript::new(transcript_segments, turns, confidence));
+                }
+                Err(e) => eprintln!("Мітки часу слів недоступні, транскрибую сегменти окремо: {}", e),
+            }
+        }
 
         let mut parts = Vec::new();
-        for seg in segments {
-            let start = (seg.start_time * 16000.0) as usize;
-            let end = (seg.end_time * 16000.0).min(mixed.len() as f64) as usize;
+        let mut confidence = Vec::new();
+        if !mic_samples.is_empty() {
+            let mic_text = Transcription::transcribe(self, mic_samples, language)?;
+            if !mic_text.trim().is_empty() {
+                parts.push(TranscriptSegment {
+                    speaker: label(HybridSpeaker::Local),
+                    text: mic_text.trim().to_string(),
+                    start: 0.0,
+                    end: mic_samples.len() as f64 / 16000.0,
+                });
+                confidence.push(1.0);
+            }
+        }
+        for part in
+            self.transcribe_speaker_segments(loopback_samples, &segments, language, SpeakerAttribution::Segments)?
+        {
+            confidence.push(speaker_confidence(&segments, part.speaker_id, part.start, part.end));
+            parts.push(TranscriptSegment {
+                speaker: label(HybridSpeaker::Remote(part.speaker_id)),
+                text: part.text,
+                start: part.start,
+                end: part.end,
+            });
+        }
+        let turns = segments
+            .iter()
+            .map(|seg| SpeakerTurn {
+                speaker: label(HybridSpeaker::Remote(seg.speaker_id)),
+                start: seg.start_time,
+                end: seg.end_time,
+            })
+            .collect();
+        Ok(ConferenceTranscript::new(parts, turns, confidence))
+    }
 
-            if start >= end || start >= mixed.len() {
-                continue;
+    /// Microphone words grouped into post-processed pieces.
+    fn transcribe_local_words(
+        &self,
+        samples: &[f32],
+        language: &str,
+        window_samples: usize,
+    ) -> Result<Vec<HybridPiece>> {
+        if samples.iter().all(|&s| s == 0.0) {
+            return Ok(Vec::new());
+        }
+        let words = transcribe_words_windowed(self, samples, language, window_samples)?;
+        Ok(local_pieces(&words)
+            .into_iter()
+            .map(|piece| HybridPiece {
+                text: self.post_process(piece.text, language),
+                ..piece
+            })
+            .filter(|piece| !piece.text.trim().is_empty())
+            .collect())
+    }
+
+    /// Transcribe a single-microphone recording with Sortformer speaker labels.
+    ///
+    /// Returns the labelled text, the speakers heard and the confidence of
+    /// each labelled turn. Falls back to plain transcription without speakers
+    /// when diarization finds no speech.
+    pub fn transcribe_diarized(
+        &self,
+        samples: &[f32],
+        language: &str,
+        attribution: SpeakerAttribution,
+        engine: &mut DiarizationEngine,
+    ) -> Result<(String, Vec<String>, Vec<f32>)> {
+        let segments = engine.diarize(samples).context("Помилка diarization")?;
+
+        let parts = self.transcribe_speaker_segments(samples, &segments, language, attribution)?;
+        if parts.is_empty() {
+            return Ok((
+                Transcription::transcribe(self, samples, language)?,
+                Vec::new(),
+                Vec::new(),
+            ));
+        }
+
+        let names = engine.speaker_names(samples, &segments);
+        let speakers = speaker_labels(parts.iter().map(|part| part.speaker_id), &names);
+        let confidence = turn_confidence(
+            &parts
+                .iter()
+                .map(|part| {
+                    let confidence = speaker_confidence(&segments, part.speaker_id, part.start, part.end);
+                    (part.speaker_id, confidence)
+                })
+                .collect::<Vec<_>>(),
+        );
+        let pieces: Vec<(Option<usize>, String)> = parts
+            .into_iter()
+            .map(|part| (Some(part.speaker_id), part.text))
+            .collect();
+        Ok((join_speaker_turns(&pieces, &names), speakers, confidence))
+    }
+
+    /// Transcribe the recording once with word timestamps and split it by speaker.
+    ///
+    /// Returns non-empty, post-processed pieces in time order. Fails if the
+    /// backend has no word timestamps.
+    pub fn transcribe_attributed_words(
+        &self,
+        samples: &[f32],
+        segments: &[DiarizationSegment],
+        language: &str,
+        window_samples: usize,
+    ) -> Result<Vec<SpeakerPiece>> {
+        let words = transcribe_words_windowed(self, samples, language, window_samples)?;
+        Ok(attribute_words(&words, segments)
+            .into_iter()
+            .map(|piece| SpeakerPiece {
+                text: self.post_process(piece.text, language),
+                ..piece
+            })
+            .filter(|piece| !piece.text.trim().is_empty())
+            .collect())
+    }
+
+    /// Transcribe the recording by speaker, returning non-empty pieces in time order.
+    ///
+    /// Word attribution falls back to transcribing each diarization segment
+    /// when the backend has no word timestamps.
+    fn transcribe_speaker_segments(
+        &self,
+        samples: &[f32],
+        segments: &[DiarizationSegment],
+        language: &str,
+        attribution: SpeakerAttribution,
+    ) -> Result<Vec<SpeakerPiece>> {
+        let overlaps = overlap_regions(segments);
+
+        if attribution == SpeakerAttribution::Words {
+            match self.transcribe_attributed_words(samples, segments, language, DEFAULT_WORD_WINDOW_SECS * 16000) {
+                Ok(pieces) => {
+                    return Ok(pieces
+                        .into_iter()
+                        .map(|piece| SpeakerPiece {
+                            text: mark_overlap(piece.text, &overlaps, piece.start, piece.end),
+                            ..piece
+                        })
+                        .collect());
+                }
+                Err(e) => eprintln!("Мітки часу слів недоступні, транскрибую сегменти окремо: {}", e),
             }
+        }
 
-            let segment_samples = &mixed[start..end.min(mixed.len())];
-            if segment_samples.is_empty() {
+        let mut parts = Vec::new();
+        for seg in segments {
+            let start = (seg.start_time * 16000.0) as usize;
+            let end = ((seg.end_time * 16000.0) as usize).min(samples.len());
+
+            if start >= end {
                 continue;
             }
 
-            let text = Transcription::transcribe(self, segment_samples, language)?;
+            let text = Transcription::transcribe(self, &samples[start..end], language)?;
             if !text.is_empty() {
-                parts.push(format!("[Спікер {}] {}", seg.speaker_id + 1, text));
+                parts.push(SpeakerPiece {
+                    speaker_id: seg.speaker_id,
+                    text: mark_overlap(text, &overlaps, seg.start_time, seg.end_time),
+                    start: seg.start_time,
+                    end: seg.end_time,
+                });
             }
         }
-
-        if parts.is_empty() {
-            return self.transcribe_channel_diarization(mic_samples, loopback_samples, language);
-        }
-
-        Ok(parts.join(" "))
+        Ok(parts)
     }
 }
 
+/// Mix mic and loopback into one track for Sortformer.
+///
+/// The shorter channel is padded with silence.
+pub fn mix_channels(mic_samples: &[f32], loopback_samples: &[f32]) -> Vec<f32> {
+    let max_len = mic_samples.len().max(loopback_samples.len());
+    (0..max_len)
+        .map(|i| {
+            let mic_val = mic_samples.get(i).copied().unwrap_or(0.0);
+            let loopback_val = loopback_samples.get(i).copied().unwrap_or(0.0);
+            (mic_val + loopback_val) / 2.0
+        })
+        .collect()
+}
+
 impl Default for TranscriptionService {
     fn default() -> Self {
         Self::new()
@@ -200,13 +586,14 @@ impl Default for TranscriptionService {
 
 impl Transcription for TranscriptionService {
     fn transcribe(&self, samples: &[f32], language: &str) -> Result<String> {
-        match &self.backend {
-            TranscriptionBackend::Whisper(w) => w.transcribe(samples, Some(language)),
-            TranscriptionBackend::Tdt(t) => t.transcribe(samples, Some(language)),
+        let text = match &self.backend {
+            TranscriptionBackend::Whisper(w) => w.transcribe(samples, Some(language), self.verbatim)?,
+            TranscriptionBackend::Tdt(t) => t.transcribe(samples, Some(language))?,
             TranscriptionBackend::None => {
                 anyhow::bail!("Модель не завантажено")
             }
-        }
+        };
+        Ok(self.post_process(text, language))
     }
 
     fn is_loaded(&self) -> bool {
@@ -225,14 +612,44 @@ impl Transcription for TranscriptionService {
         let path_str = path.to_string_lossy();
         let whisper =
             WhisperSTT::new(&path_str).with_context(|| format!("Failed to load Whisper model from {}", path_str))?;
-        self.backend = TranscriptionBackend::Whisper(whisper);
+        self.replace_backend(TranscriptionBackend::Whisper(whisper));
         Ok(())
     }
+
+    fn translate(&self, samples: &[f32], language: &str) -> Result<String> {
+        let text = match &self.backend {
+            TranscriptionBackend::Whisper(w) => w.translate(samples, Some(language))?,
+            TranscriptionBackend::Tdt(_) => {
+                anyhow::bail!("TDT не підтримує переклад; оберіть Whisper")
+            }
+            TranscriptionBackend::None => {
+                anyhow::bail!("Модель не завантажено")
+            }
+        };
+        Ok(self.post_process(text, "en"))
+    }
+
+    fn transcribe_words(&self, samples: &[f32], language: &str) -> Result<Vec<TimedWord>> {
+        let mut words = match &self.backend {
+            TranscriptionBackend::Whisper(w) => w.transcribe_words(samples, Some(language), self.verbatim)?,
+            TranscriptionBackend::Tdt(t) => t.transcribe_words(samples, Some(language))?,
+            TranscriptionBackend::None => {
+                anyhow::bail!("Модель не завантажено")
+            }
+        };
+        if self.verbatim {
+            for word in &mut words {
+                word.text = mark_disfluencies(&word.text);
+            }
+        }
+        Ok(words)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::transcription::postprocess::RuleBasedPunctuator;
 
     #[test]
     fn test_new_creates_unloaded_service() {
@@ -271,9 +688,53 @@ mod tests {
         assert!(service.backend_type().is_none());
     }
 
+    #[test]
+    fn test_replacing_backend_drops_post_processors() {
+        let mut service = TranscriptionService::new();
+        service.add_post_processor(Box::new(RuleBasedPunctuator::new()));
+        assert_eq!(service.post_process("привіт світе".to_string(), "uk"), "Привіт світе.");
+        service.replace_backend(TranscriptionBackend::None);
+        assert_eq!(service.post_process("привіт світе".to_string(), "uk"), "привіт світе");
+    }
+
+    #[test]
+    fn test_post_process_applies_steps() {
+        let mut service = TranscriptionService::new();
+        service.add_post_processor(Box::new(RuleBasedPunctuator::new()));
+        assert_eq!(service.post_process("привіт світе".to_string(), "uk"), "Привіт світе.");
+        assert_eq!(service.post_process(String::new(), "uk"), "");
+    }
+
+    #[test]
+    fn test_verbatim_marks_up_after_punctuation() {
+        let mut service = TranscriptionService::new();
+        service.add_post_processor(Box::new(RuleBasedPunctuator::new()));
+        assert_eq!(
+            service.post_process("еее я хо- хотів".to_string(), "uk"),
+            "Еее я хо- хотів."
+        );
+        service.set_verbatim(true);
+        assert_eq!(
+            service.post_process("еее я хо- хотів".to_string(), "uk"),
+            "[еее] я хо- хотів."
+        );
+    }
+
+    #[test]
+    fn test_translate_fails_when_no_model() {
+        let service = TranscriptionService::new();
+        assert!(service.translate(&[0.0; 100], "uk").is_err());
+    }
+
     #[test]
     fn test_has_builtin_punctuation_false_when_unloaded() {
         let service = TranscriptionService::new();
         assert!(!service.has_builtin_punctuation());
     }
+
+    #[test]
+    fn test_mix_channels_pads_shorter_channel() {
+        assert_eq!(mix_channels(&[0.5, 1.0, 0.2], &[0.5]), vec![0.5, 0.5, 0.1]);
+        assert!(mix_channels(&[], &[]).is_empty());
+    }
 }
@@ -0,0 +1,384 @@
+//! Cross-session speaker re-identification.
+//!
+//! Sortformer numbers speakers per recording, so "Спікер 1" in one meeting
+//! is unrelated to "Спікер 1" in the next. This module keeps a registry of
+//! known voices (`speakers.json` next to the history) and maps each
+//! diarized speaker to a known one when their voiceprints are similar
+//! enough, so recurring participants keep the same label.
+//!
+//! The voiceprint is a lightweight spectral summary — mean and spread of
+//! the liftered MFCCs over voiced frames — not a neural speaker embedding.
+//! It separates clearly different voices recorded on similar equipment;
+//! the match threshold is configurable for everything else.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use realfft::RealFftPlanner;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+use super::diarization::DiarizationSegment;
+
+const SAMPLE_RATE: f32 = 16000.0;
+const FRAME_LEN: usize = 400; // 25 ms
+const HOP_LEN: usize = 160; // 10 ms
+const FFT_LEN: usize = 512;
+const MEL_BANDS: usize = 26;
+const CEPSTRA: usize = 12; // c1..c12, c0 (loudness) is dropped
+const LIFTER: f32 = 22.0;
+
+/// Frames quieter than this (dB below the loudest frame) are not speech.
+const VOICED_RANGE_DB: f32 = 30.0;
+
+/// Minimum voiced audio for a usable voiceprint: 1 second.
+const MIN_VOICED_FRAMES: usize = 100;
+
+/// A voice seen in earlier sessions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct KnownSpeaker {
+    pub name: String,
+    pub voiceprint: Vec<f32>,
+    /// Sessions the voice was heard in; weights the running average.
+    pub sessions: u32,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// Registry of known voices.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SpeakerRegistry {
+    pub speakers: Vec<KnownSpeaker>,
+}
+
+impl SpeakerRegistry {
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Не вдалося прочитати реєстр мовців: {}", path.display()))?;
+        serde_json::from_str(&content)
+            .with_context(|| format!("Не вдалося розпарсити реєстр мовців: {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Не вдалося створити директорію: {}", parent.display()))?;
+        }
+        let content = serde_json::to_string_pretty(self).context("Не вдалося серіалізувати реєстр мовців")?;
+        fs::write(path, content).with_context(|| format!("Не вдалося записати реєстр мовців: {}", path.display()))
+    }
+
+    /// Name each diarized speaker, registering voices not seen before.
+    ///
+    /// Pairs are matched greedily from the most similar down, so every
+    /// known voice is used at most once per recording. Matched voiceprints
+    /// are folded into the stored average; unmatched speakers become new
+    /// entries named "Спікер N" by registry order.
+    pub fn identify(&mut self, voiceprints: &BTreeMap<usize, Vec<f32>>, threshold: f32) -> HashMap<usize, String> {
+        let mut candidates: Vec<(f32, usize, usize)> = Vec::new();
+        for (&speaker_id, print) in voiceprints {
+            for (known_idx, known) in self.speakers.iter().enumerate() {
+                let similarity = cosine_similarity(print, &known.voiceprint);
+                if similarity >= threshold {
+                    candidates.push((similarity, speaker_id, known_idx));
+                }
+            }
+        }
+        candidates.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let now = Utc::now();
+        let mut names = HashMap::new();
+        let mut used_known = HashSet::new();
+        for (_, speaker_id, known_idx) in candidates {
+            if names.contains_key(&speaker_id) || used_known.contains(&known_idx) {
+                continue;
+            }
+            let known = &mut self.speakers[known_idx];
+            known.voiceprint = blend(&known.voiceprint, known.sessions, &voiceprints[&speaker_id]);
+            known.sessions += 1;
+            known.last_seen = now;
+            names.insert(speaker_id, known.name.clone());
+            used_known.insert(known_idx);
+        }
+
+        for (&speaker_id, print) in voiceprints {
+            if names.contains_key(&speaker_id) {
+                continue;
+            }
+            let name = self.next_name();
+            self.speakers.push(KnownSpeaker {
+                name: name.clone(),
+                voiceprint: print.clone(),
+                sessions: 1,
+                last_seen: now,
+            });
+            names.insert(speaker_id, name);
+        }
+
+        names
+    }
+
+    fn next_name(&self) -> String {
+        (self.speakers.len() + 1..)
+            .map(|n| format!("Спікер {}", n))
+            .find(|name| !self.speakers.iter().any(|s| &s.name == name))
+            .unwrap_or_default()
+    }
+}
+
+/// Running average of a stored voiceprint with a new observation.
+fn blend(stored: &[f32], sessions: u32, new: &[f32]) -> Vec<f32> {
+    let weight = sessions as f32;
+    stored
+        .iter()
+        .zip(new)
+        .map(|(s, n)| (s * weight + n) / (weight + 1.0))
+        .collect()
+}
+
+/// Cosine similarity of two voiceprints (0 for mismatched or empty vectors).
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+/// Voiceprint of each diarized speaker, from all of their segments.
+///
+/// Speakers with too little voiced audio are left out.
+pub fn speaker_voiceprints(samples: &[f32], segments: &[DiarizationSegment]) -> BTreeMap<usize, Vec<f32>> {
+    let mut audio: BTreeMap<usize, Vec<f32>> = BTreeMap::new();
+    for seg in segments {
+        let start = ((seg.start_time * SAMPLE_RATE as f64) as usize).min(samples.len());
+        let end = ((seg.end_time * SAMPLE_RATE as f64) as usize).min(samples.len());
+        if start < end {
+            audio
+                .entry(seg.speaker_id)
+                .or_default()
+                .extend_from_slice(&samples[start..end]);
+        }
+    }
+    audio
+        .into_iter()
+        .filter_map(|(speaker_id, samples)| voiceprint(&samples).map(|print| (speaker_id, print)))
+        .collect()
+}
+
+/// Voiceprint of 16 kHz mono audio: mean and standard deviation of the
+/// liftered MFCCs c1..c12 over voiced frames. `None` if under a second is voiced.
+pub fn voiceprint(samples: &[f32]) -> Option<Vec<f32>> {
+    if samples.len() < FRAME_LEN {
+        return None;
+    }
+
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(FFT_LEN);
+    let mut input = fft.make_input_vec();
+    let mut spectrum = fft.make_output_vec();
+    let window: Vec<f32> = (0..FRAME_LEN)
+        .map(|i| 0.5 - 0.5 * (2.0 * std::f32::consts::PI * i as f32 / (FRAME_LEN - 1) as f32).cos())
+        .collect();
+    let filters = mel_filterbank();
+
+    let mut frames: Vec<(f32, [f32; CEPSTRA])> = Vec::new();
+    for start in (0..=samples.len() - FRAME_LEN).step_by(HOP_LEN) {
+        let frame = &samples[start..start + FRAME_LEN];
+        input.fill(0.0);
+        for (dst, (s, w)) in input.iter_mut().zip(frame.iter().zip(&window)) {
+            *dst = s * w;
+        }
+        fft.process(&mut input, &mut spectrum).ok()?;
+        let power: Vec<f32> = spectrum.iter().map(|c| c.norm_sqr()).collect();
+
+        let energy_db = 10.0 * (power.iter().sum::<f32>() + 1e-10).log10();
+        let log_mel: Vec<f32> = filters
+            .iter()
+            .map(|filter| {
+                let energy: f32 = filter.iter().map(|&(bin, weight)| power[bin] * weight).sum();
+                (energy + 1e-10).ln()
+            })
+            .collect();
+        frames.push((energy_db, cepstra(&log_mel)));
+    }
+
+    let loudest = frames.iter().map(|(db, _)| *db).fold(f32::MIN, f32::max);
+    let voiced: Vec<&[f32; CEPSTRA]> = frames
+        .iter()
+        .filter(|(db, _)| *db >= loudest - VOICED_RANGE_DB)
+        .map(|(_, c)| c)
+        .collect();
+    if voiced.len() < MIN_VOICED_FRAMES || loudest < -60.0 {
+        return None;
+    }
+
+    let count = voiced.len() as f32;
+    let mut print = Vec::with_capacity(CEPSTRA * 2);
+    for k in 0..CEPSTRA {
+        print.push(voiced.iter().map(|c| c[k]).sum::<f32>() / count);
+    }
+    for k in 0..CEPSTRA {
+        let mean = print[k];
+        let variance = voiced.iter().map(|c| (c[k] - mean).powi(2)).sum::<f32>() / count;
+        print.push(variance.sqrt());
+    }
+    Some(print)
+}
+
+/// Liftered DCT-II of the log-mel energies, skipping c0.
+fn cepstra(log_mel: &[f32]) -> [f32; CEPSTRA] {
+    let n = log_mel.len() as f32;
+    let mut out = [0.0; CEPSTRA];
+    for (k, value) in out.iter_mut().enumerate() {
+        let order = (k + 1) as f32;
+        let coeff: f32 = log_mel
+            .iter()
+            .enumerate()
+            .map(|(i, &e)| e * (std::f32::consts::PI * order * (i as f32 + 0.5) / n).cos())
+            .sum();
+        let lifter = 1.0 + (LIFTER / 2.0) * (std::f32::consts::PI * order / LIFTER).sin();
+        *value = coeff * lifter;
+    }
+    out
+}
+
+/// Triangular mel filters as (FFT bin, weight) lists.
+fn mel_filterbank() -> Vec<Vec<(usize, f32)>> {
+    let hz_to_mel = |hz: f32| 2595.0 * (1.0 + hz / 700.0).log10();
+    let mel_to_hz = |mel: f32| 700.0 * (10f32.powf(mel / 2595.0) - 1.0);
+    let max_mel = hz_to_mel(SAMPLE_RATE / 2.0);
+    let bins = FFT_LEN / 2 + 1;
+
+    let edges: Vec<f32> = (0..MEL_BANDS + 2)
+        .map(|i| mel_to_hz(max_mel * i as f32 / (MEL_BANDS + 1) as f32) * FFT_LEN as f32 / SAMPLE_RATE)
+        .collect();
+
+    (0..MEL_BANDS)
+        .map(|m| {
+            let (left, center, right) = (edges[m], edges[m + 1], edges[m + 2]);
+            (0..bins)
+                .filter_map(|bin| {
+                    let b = bin as f32;
+                    let weight = if b > left && b <= center {
+                        (b - left) / (center - left)
+                    } else if b > center && b < right {
+                        (right - b) / (right - center)
+                    } else {
+                        0.0
+                    };
+                    (weight > 0.0).then_some((bin, weight))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Synthetic voice: harmonics of `f0` shaped by two formants.
+    fn voice(f0: f32, formants: (f32, f32), secs: f32, phase: f32) -> Vec<f32> {
+        let n = (secs * SAMPLE_RATE) as usize;
+        (0..n)
+            .map(|i| {
+                let t = i as f32 / SAMPLE_RATE;
+                let mut sample = 0.0;
+                let mut harmonic = f0;
+                while harmonic < 7000.0 {
+                    let gain = (-((harmonic - formants.0) / 300.0).powi(2)).exp()
+                        + 0.6 * (-((harmonic - formants.1) / 400.0).powi(2)).exp()
+                        + 0.02;
+                    sample += gain * (2.0 * std::f32::consts::PI * harmonic * t + phase).sin();
+                    harmonic += f0;
+                }
+                0.1 * sample
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_voiceprint_needs_voiced_audio() {
+        assert!(voiceprint(&vec![0.0; 32000]).is_none());
+        assert!(voiceprint(&voice(120.0, (700.0, 1200.0), 0.5, 0.0)).is_none());
+        assert_eq!(voiceprint(&voice(120.0, (700.0, 1200.0), 2.0, 0.0)).unwrap().len(), 24);
+    }
+
+    #[test]
+    fn test_same_voice_more_similar_than_different_voice() {
+        let a1 = voiceprint(&voice(110.0, (700.0, 1100.0), 2.0, 0.0)).unwrap();
+        let a2 = voiceprint(&voice(110.0, (700.0, 1100.0), 2.0, 1.3)).unwrap();
+        let b = voiceprint(&voice(230.0, (400.0, 2600.0), 2.0, 0.0)).unwrap();
+        assert!(cosine_similarity(&a1, &a2) > 0.99);
+        assert!(cosine_similarity(&a1, &b) < cosine_similarity(&a1, &a2));
+    }
+
+    #[test]
+    fn test_cosine_similarity_edge_cases() {
+        assert_eq!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]), 0.0);
+        assert_eq!(cosine_similarity(&[1.0], &[1.0, 2.0]), 0.0);
+        assert!((cosine_similarity(&[1.0, 2.0], &[2.0, 4.0]) - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_identify_registers_then_recognizes() {
+        let mut registry = SpeakerRegistry::default();
+        let first = BTreeMap::from([(0, vec![1.0, 0.0, 0.0]), (1, vec![0.0, 1.0, 0.0])]);
+        let names = registry.identify(&first, 0.9);
+        assert_eq!(names[&0], "Спікер 1");
+        assert_eq!(names[&1], "Спікер 2");
+
+        // Next session: Sortformer numbers them the other way round, plus a newcomer
+        let second = BTreeMap::from([
+            (0, vec![0.05, 1.0, 0.0]),
+            (1, vec![1.0, 0.05, 0.0]),
+            (2, vec![0.0, 0.0, 1.0]),
+        ]);
+        let names = registry.identify(&second, 0.9);
+        assert_eq!(names[&0], "Спікер 2");
+        assert_eq!(names[&1], "Спікер 1");
+        assert_eq!(names[&2], "Спікер 3");
+        assert_eq!(registry.speakers.len(), 3);
+        assert_eq!(registry.speakers[0].sessions, 2);
+    }
+
+    #[test]
+    fn test_identify_uses_each_known_voice_once() {
+        let mut registry = SpeakerRegistry::default();
+        registry.identify(&BTreeMap::from([(0, vec![1.0, 0.0])]), 0.9);
+
+        let names = registry.identify(&BTreeMap::from([(0, vec![1.0, 0.1]), (1, vec![1.0, 0.0])]), 0.9);
+        assert_eq!(names[&1], "Спікер 1");
+        assert_eq!(names[&0], "Спікер 2");
+    }
+
+    #[test]
+    fn test_renamed_speaker_keeps_name_and_new_names_skip_taken() {
+        let mut registry = SpeakerRegistry::default();
+        registry.identify(&BTreeMap::from([(0, vec![1.0, 0.0])]), 0.9);
+        registry.speakers[0].name = "Спікер 2".to_string();
+
+        let names = registry.identify(&BTreeMap::from([(0, vec![1.0, 0.0]), (1, vec![0.0, 1.0])]), 0.9);
+        assert_eq!(names[&0], "Спікер 2");
+        assert_eq!(names[&1], "Спікер 3");
+    }
+
+    #[test]
+    fn test_registry_roundtrip() {
+        let path = std::env::temp_dir().join(format!("s2t_speakers_{}.json", uuid::Uuid::new_v4()));
+        let mut registry = SpeakerRegistry::default();
+        registry.identify(&BTreeMap::from([(0, vec![0.5, 0.5])]), 0.9);
+        registry.save(&path).unwrap();
+        assert_eq!(SpeakerRegistry::load(&path).unwrap(), registry);
+        let _ = fs::remove_file(&path);
+    }
+}
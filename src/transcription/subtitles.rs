@@ -0,0 +1,254 @@
+//! SRT subtitles: reading, writing and re-alignment to the audio.
+//!
+//! Used to correct existing subtitles. The audio is transcribed with word
+//! timestamps, the words of each cue are matched in order against the
+//! recognized ones, and a cue whose words were mostly found takes their
+//! times. Cues that could not be matched (the subtitle text differs too much
+//! from what was heard) keep their original timing.
+
+use crate::domain::types::TimedWord;
+use anyhow::{bail, Result};
+
+/// How far ahead in the recognized words a cue word is looked for.
+const LOOKAHEAD_WORDS: usize = 30;
+
+/// Short words ("і", "в", "the") occur everywhere; they only match nearby.
+const SHORT_WORD_LOOKAHEAD: usize = 3;
+
+/// One subtitle: its text and when it is shown, in seconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleCue {
+    pub start: f64,
+    pub end: f64,
+    /// Cue text; lines are separated by `\n`.
+    pub text: String,
+}
+
+/// Cues re-aligned to the audio.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Alignment {
+    pub cues: Vec<SubtitleCue>,
+    /// Cues that took their timing from the audio.
+    pub aligned: usize,
+}
+
+/// Parse SRT content. Cue numbers are not checked; cues are renumbered on write.
+pub fn parse_srt(content: &str) -> Result<Vec<SubtitleCue>> {
+    let content = content.trim_start_matches('\u{feff}').replace("\r\n", "\n");
+    let mut cues = Vec::new();
+    for (block_index, block) in content.split("\n\n").enumerate() {
+        let mut lines = block
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .peekable();
+        if lines.peek().is_none() {
+            continue;
+        }
+        // The cue number is optional in practice
+        if lines
+            .peek()
+            .is_some_and(|line| line.trim().chars().all(|c| c.is_ascii_digit()))
+        {
+            lines.next();
+        }
+        let Some((start, end)) = lines.next().and_then(parse_timing) else {
+            bail!("Невірний формат SRT: немає часу в репліці {}", block_index + 1);
+        };
+        cues.push(SubtitleCue {
+            start,
+            end,
+            text: lines.collect::<Vec<_>>().join("\n"),
+        });
+    }
+    Ok(cues)
+}
+
+/// Write cues as SRT, numbered from 1.
+pub fn format_srt(cues: &[SubtitleCue]) -> String {
+    let mut out = String::new();
+    for (i, cue) in cues.iter().enumerate() {
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_timestamp(cue.start),
+            format_timestamp(cue.end),
+            cue.text
+        ));
+    }
+    out
+}
+
+/// `HH:MM:SS,mmm`, as SRT writes times.
+pub fn format_timestamp(secs: f64) -> String {
+    let total_ms = (secs.max(0.0) * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02},{:03}",
+        total_ms / 3_600_000,
+        total_ms / 60_000 % 60,
+        total_ms / 1000 % 60,
+        total_ms % 1000
+    )
+}
+
+/// `00:01:02,500 --> 00:01:04,000`, possibly followed by position settings.
+fn parse_timing(line: &str) -> Option<(f64, f64)> {
+    let (start, rest) = line.split_once("-->")?;
+    let end = rest.split_whitespace().next()?;
+    Some((parse_timestamp(start.trim())?, parse_timestamp(end)?))
+}
+
+/// `HH:MM:SS,mmm`; a dot instead of the comma is accepted too.
+fn parse_timestamp(s: &str) -> Option<f64> {
+    let (hms, ms) = s.split_once([',', '.'])?;
+    let mut parts = hms.split(':');
+    let hours: u64 = parts.next()?.parse().ok()?;
+    let minutes: u64 = parts.next()?.parse().ok()?;
+    let seconds: u64 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() {
+        return None;
+    }
+    let millis: u64 = ms.parse().ok()?;
+    Some((hours * 3600 + minutes * 60 + seconds) as f64 + millis as f64 / 1000.0)
+}
+
+/// Word as compared between subtitle and transcript: lowercase, letters and digits only.
+fn normalize(word: &str) -> String {
+    word.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Re-time `cues` by the recognized `words`.
+///
+/// A cue is re-timed when at least half of its words are found; it then
+/// spans from its first to its last matched word.
+pub fn align_cues(cues: &[SubtitleCue], words: &[TimedWord]) -> Alignment {
+    let recognized: Vec<(String, &TimedWord)> = words
+        .iter()
+        .map(|word| (normalize(&word.text), word))
+        .filter(|(text, _)| !text.is_empty())
+        .collect();
+
+    let mut next = 0;
+    let mut aligned = 0;
+    let cues = cues
+        .iter()
+        .map(|cue| {
+            let cue_words: Vec<String> = cue
+                .text
+                .split_whitespace()
+                .map(normalize)
+                .filter(|w| !w.is_empty())
+                .collect();
+            let mut matched: Vec<&TimedWord> = Vec::new();
+            for cue_word in &cue_words {
+                let lookahead = if cue_word.chars().count() < 3 {
+                    SHORT_WORD_LOOKAHEAD
+                } else {
+                    LOOKAHEAD_WORDS
+                };
+                let window = next..recognized.len().min(next + lookahead);
+                if let Some(offset) = recognized[window.clone()].iter().position(|(text, _)| text == cue_word) {
+                    matched.push(recognized[window.start + offset].1);
+                    next = window.start + offset + 1;
+                }
+            }
+
+            match (matched.first(), matched.last()) {
+                (Some(first), Some(last)) if matched.len() * 2 >= cue_words.len() => {
+                    aligned += 1;
+                    SubtitleCue {
+                        start: first.start,
+                        end: last.end.max(first.start),
+                        text: cue.text.clone(),
+                    }
+                }
+                _ => cue.clone(),
+            }
+        })
+        .collect();
+
+    Alignment { cues, aligned }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SRT: &str = "\u{feff}1\r\n00:00:01,000 --> 00:00:02,500\r\nДобрий день\r\n\r\n2\r\n00:00:03,000 --> 00:00:05,000 X1:10\r\nЯк справи?\r\nДобре\r\n\r\n";
+
+    fn word(text: &str, start: f64, end: f64) -> TimedWord {
+        TimedWord {
+            text: text.to_string(),
+            start,
+            end,
+        }
+    }
+
+    fn cue(start: f64, end: f64, text: &str) -> SubtitleCue {
+        SubtitleCue {
+            start,
+            end,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_srt() {
+        let cues = parse_srt(SRT).unwrap();
+        assert_eq!(
+            cues,
+            vec![cue(1.0, 2.5, "Добрий день"), cue(3.0, 5.0, "Як справи?\nДобре")]
+        );
+    }
+
+    #[test]
+    fn test_parse_srt_rejects_missing_timing() {
+        assert!(parse_srt("1\nПросто текст\n").is_err());
+    }
+
+    #[test]
+    fn test_srt_roundtrip() {
+        let cues = parse_srt(SRT).unwrap();
+        assert_eq!(parse_srt(&format_srt(&cues)).unwrap(), cues);
+        assert!(format_srt(&cues).starts_with("1\n00:00:01,000 --> 00:00:02,500\nДобрий день\n\n2\n"));
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(0.0), "00:00:00,000");
+        assert_eq!(format_timestamp(3723.4567), "01:02:03,457");
+        assert_eq!(parse_timestamp("01:02:03.457"), Some(3723.457));
+    }
+
+    #[test]
+    fn test_align_cues_takes_times_of_matched_words() {
+        // Subtitles shifted by ~2 s against the audio
+        let cues = vec![cue(3.0, 4.0, "Добрий день!"), cue(5.0, 6.0, "Як справи?")];
+        let words = vec![
+            word(" Добрий", 1.1, 1.4),
+            word(" день", 1.4, 1.8),
+            word(".", 1.8, 1.8),
+            word(" Як", 2.5, 2.6),
+            word(" справи", 2.6, 3.1),
+        ];
+        let alignment = align_cues(&cues, &words);
+        assert_eq!(alignment.aligned, 2);
+        assert_eq!(
+            alignment.cues,
+            vec![cue(1.1, 1.8, "Добрий день!"), cue(2.5, 3.1, "Як справи?")]
+        );
+    }
+
+    #[test]
+    fn test_align_cues_keeps_timing_of_unmatched_cue() {
+        let cues = vec![cue(1.0, 2.0, "Зовсім інший текст"), cue(3.0, 4.0, "Добрий день")];
+        let words = vec![word("Добрий", 2.9, 3.3), word("день", 3.3, 3.6)];
+        let alignment = align_cues(&cues, &words);
+        assert_eq!(alignment.aligned, 1);
+        assert_eq!(alignment.cues[0], cues[0]);
+        assert_eq!(alignment.cues[1], cue(2.9, 3.6, "Добрий день"));
+    }
+}
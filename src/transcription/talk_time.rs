@@ -0,0 +1,126 @@
+//! Talk-time statistics from diarization.
+//!
+//! How long each speaker talked and how often they took the floor, computed
+//! from speaker turns. Shown under conference transcripts, stored with
+//! history entries and included in CLI JSON output.
+
+use crate::domain::types::SpeakerStats;
+use crate::transcription::rttm::SpeakerTurn;
+
+/// Talk time and turn count per speaker, most talkative first.
+///
+/// Consecutive turns of the same speaker count as one turn. Overlapping
+/// speech counts for every speaker involved.
+pub fn talk_time(turns: &[SpeakerTurn]) -> Vec<SpeakerStats> {
+    let mut sorted: Vec<&SpeakerTurn> = turns.iter().filter(|t| t.end > t.start).collect();
+    sorted.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    let mut stats: Vec<SpeakerStats> = Vec::new();
+    let mut previous: Option<&str> = None;
+    for turn in sorted {
+        let new_turn = previous != Some(turn.speaker.as_str());
+        previous = Some(turn.speaker.as_str());
+        let index = match stats.iter().position(|s| s.speaker == turn.speaker) {
+            Some(index) => index,
+            None => {
+                stats.push(SpeakerStats {
+                    speaker: turn.speaker.clone(),
+                    talk_secs: 0.0,
+                    turns: 0,
+                });
+                stats.len() - 1
+            }
+        };
+        stats[index].talk_secs += turn.end - turn.start;
+        if new_turn {
+            stats[index].turns += 1;
+        }
+    }
+    stats.sort_by(|a, b| b.talk_secs.total_cmp(&a.talk_secs));
+    stats
+}
+
+/// Put a talk-time summary under the transcript; `text` as is without stats.
+pub fn append_talk_time(text: &str, stats: &[SpeakerStats]) -> String {
+    if stats.is_empty() {
+        return text.to_string();
+    }
+    let total: f64 = stats.iter().map(|s| s.talk_secs).sum();
+    let mut result = format!("{}\n\nЧас говоріння:\n", text);
+    for s in stats {
+        let share = if total > 0.0 { s.talk_secs / total * 100.0 } else { 0.0 };
+        let secs = s.talk_secs.round() as u64;
+        result.push_str(&format!(
+            "- {}: {:02}:{:02} ({:.0}%), реплік: {}\n",
+            s.speaker,
+            secs / 60,
+            secs % 60,
+            share,
+            s.turns
+        ));
+    }
+    result.truncate(result.trim_end().len());
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(speaker: &str, start: f64, end: f64) -> SpeakerTurn {
+        SpeakerTurn {
+            speaker: speaker.to_string(),
+            start,
+            end,
+        }
+    }
+
+    #[test]
+    fn test_talk_time_sums_and_counts_turns() {
+        let turns = vec![
+            turn("Спікер 2", 10.0, 12.0),
+            turn("Спікер 1", 0.0, 4.0),
+            turn("Спікер 1", 4.5, 9.0),
+            turn("Спікер 1", 13.0, 14.0),
+            turn("Спікер 2", 15.0, 15.0),
+        ];
+        let stats = talk_time(&turns);
+        assert_eq!(
+            stats,
+            vec![
+                SpeakerStats {
+                    speaker: "Спікер 1".to_string(),
+                    talk_secs: 9.5,
+                    turns: 2,
+                },
+                SpeakerStats {
+                    speaker: "Спікер 2".to_string(),
+                    talk_secs: 2.0,
+                    turns: 1,
+                },
+            ]
+        );
+        assert!(talk_time(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_append_talk_time() {
+        let stats = vec![
+            SpeakerStats {
+                speaker: "Ви".to_string(),
+                talk_secs: 90.0,
+                turns: 3,
+            },
+            SpeakerStats {
+                speaker: "Спікер 1".to_string(),
+                talk_secs: 30.0,
+                turns: 2,
+            },
+        ];
+        assert_eq!(
+            append_talk_time("текст", &stats),
+            "текст\n\nЧас говоріння:\n- Ви: 01:30 (75%), реплік: 3\n- Спікер 1: 00:30 (25%), реплік: 2"
+        );
+        assert_eq!(append_talk_time("текст", &[]), "текст");
+    }
+}
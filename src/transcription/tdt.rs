@@ -8,8 +8,9 @@
 //! - Built-in punctuation and capitalization
 //! - Word-level timestamps
 
+use crate::domain::types::TimedWord;
 use anyhow::{Context, Result};
-use parakeet_rs::{ParakeetTDT, Transcriber};
+use parakeet_rs::{ParakeetTDT, TimestampMode, Transcriber};
 use parking_lot::Mutex;
 
 /// Parakeet TDT speech-to-text backend.
@@ -56,6 +57,26 @@ impl ParakeetSTT {
         Ok(result.text)
     }
 
+    /// Transcribe audio into words with timestamps.
+    pub fn transcribe_words(&self, samples: &[f32], _language: Option<&str>) -> Result<Vec<TimedWord>> {
+        let mut model = self.model.lock();
+
+        let result = model
+            .transcribe_samples(samples.to_vec(), 16000, 1, Some(TimestampMode::Words))
+            .context("Failed to transcribe audio with Parakeet TDT")?;
+
+        Ok(result
+            .tokens
+            .into_iter()
+            .filter(|token| !token.text.trim().is_empty())
+            .map(|token| TimedWord {
+                text: token.text.trim().to_string(),
+                start: token.start as f64,
+                end: token.end as f64,
+            })
+            .collect())
+    }
+
     /// Get the model directory path.
     pub fn model_dir(&self) -> &str {
         &self.model_dir
@@ -83,6 +104,10 @@ impl Transcription for ParakeetSTT {
     fn load_model(&mut self, _path: &Path) -> Result<()> {
         anyhow::bail!("ParakeetSTT does not support runtime model loading; use TranscriptionService")
     }
+
+    fn transcribe_words(&self, samples: &[f32], language: &str) -> Result<Vec<TimedWord>> {
+        ParakeetSTT::transcribe_words(self, samples, Some(language))
+    }
 }
 
 #[cfg(test)]
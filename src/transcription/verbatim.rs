@@ -0,0 +1,160 @@
+//! Verbatim mode: hesitations and cut-off words kept with markup.
+//!
+//! Whisper normally smooths speech over: "еее" and "ммм" disappear and a
+//! restarted word keeps only its finished form, or both come out as plain
+//! words nobody can tell from the rest. Linguistic analysis and verbatim
+//! legal transcripts need them. In verbatim mode decoding starts from a
+//! prompt written the way such speech is transcribed, which makes Whisper
+//! keep them, and the result is marked up: hesitations as `[еее]`, cut-off
+//! words as `сло-`.
+
+/// Prompt for decoding in verbatim mode, for languages that have one.
+pub fn verbatim_prompt(language: &str) -> Option<&'static str> {
+    match language {
+        "uk" => Some("Еее, ну, я хо- хотів сказати, ммм, що це... хм, загалом так."),
+        "ru" => Some("Эээ, ну, я хо- хотел сказать, ммм, что это... хм, в общем так."),
+        "en" => Some("Umm, let me think, uh, I was go- going to say, hmm, that it's... well, like this."),
+        _ => None,
+    }
+}
+
+/// Letters of hesitation sounds with repeats collapsed: "еее" → "е", "hmm" → "hm".
+const HESITATIONS: &[&str] = &[
+    "е", "э", "а", "м", "ем", "эм", "ам", "хм", "гм", "мгм", "uh", "um", "uhm", "ah", "eh", "hm", "m", "mhm",
+];
+
+/// Mark hesitations as `[еее]` and cut-off words as `сло-`.
+///
+/// Cut-off words are recognized by a trailing dash ("сло-", "сло—"), by an
+/// ellipsis followed by the finished word ("хо... хотів") and by a hyphen
+/// joining the start of a word to the word ("хо-хотів").
+pub fn mark_disfluencies(text: &str) -> String {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let mut marked = Vec::with_capacity(tokens.len());
+
+    for (i, token) in tokens.iter().enumerate() {
+        let (lead, core, trail) = split_punctuation(token);
+        if let Some(hesitation) = hesitation(core) {
+            marked.push(format!("{}[{}]{}", lead, hesitation, trail));
+        } else if let Some(stem) = dashed_stem(core, trail) {
+            marked.push(format!("{}{}-", lead, stem));
+        } else if let Some(stem) = trailing_ellipsis_stem(core, trail, tokens.get(i + 1)) {
+            marked.push(format!("{}{}-", lead, stem));
+        } else if let Some((start, word)) = hyphenated_restart(core) {
+            marked.push(format!("{}{}- {}{}", lead, start, word, trail));
+        } else {
+            marked.push(token.to_string());
+        }
+    }
+
+    marked.join(" ")
+}
+
+/// Split a token into leading punctuation, the word and trailing punctuation.
+/// Dashes stay with the word.
+fn split_punctuation(token: &str) -> (&str, &str, &str) {
+    let is_word_char = |c: char| c.is_alphanumeric() || matches!(c, '-' | '–' | '—' | '\'' | '’');
+    let start = token.find(is_word_char).unwrap_or(token.len());
+    let end = token
+        .rfind(is_word_char)
+        .map_or(start, |i| i + token[i..].chars().next().map_or(1, char::len_utf8));
+    (&token[..start], &token[start..end], &token[end..])
+}
+
+/// The hesitation in lowercase without hyphens, if `word` is one.
+fn hesitation(word: &str) -> Option<String> {
+    let sound: String = word
+        .chars()
+        .filter(|c| *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect();
+    let mut letters = String::new();
+    for c in sound.chars() {
+        if !c.is_alphabetic() {
+            return None;
+        }
+        if !letters.ends_with(c) {
+            letters.push(c);
+        }
+    }
+    // One vowel or "м" alone is a word ("а", "e"), not a hesitation
+    let single_letter = letters.chars().count() == 1 && sound.chars().count() < 2;
+    (!single_letter && HESITATIONS.contains(&letters.as_str())).then_some(sound)
+}
+
+/// "сло-", "сло—": the start of the word before a trailing dash.
+fn dashed_stem<'a>(word: &'a str, trail: &str) -> Option<&'a str> {
+    let stem = word.trim_end_matches(['-', '–', '—']);
+    let dashed = stem.len() < word.len() && trail.chars().all(|c| matches!(c, ',' | '.' | '…'));
+    (dashed && stem.chars().last().is_some_and(char::is_alphabetic)).then_some(stem)
+}
+
+/// "хо... хотів": the word before an ellipsis when the next word starts with it.
+fn trailing_ellipsis_stem<'a>(word: &'a str, trail: &str, next: Option<&&str>) -> Option<&'a str> {
+    if !(trail == "..." || trail == "…") || word.is_empty() {
+        return None;
+    }
+    let (_, next_word, _) = split_punctuation(next?);
+    is_restart(word, next_word).then_some(word)
+}
+
+/// "хо-хотів": a hyphen joining the start of a word to the word.
+fn hyphenated_restart(word: &str) -> Option<(&str, &str)> {
+    let (start, rest) = word.split_once('-')?;
+    is_restart(start, rest).then_some((start, rest))
+}
+
+/// Whether `start` is a shorter beginning of `word`.
+fn is_restart(start: &str, word: &str) -> bool {
+    let start = start.to_lowercase();
+    let word = word.to_lowercase();
+    !start.is_empty()
+        && start.chars().all(char::is_alphabetic)
+        && start.chars().count() < word.chars().count()
+        && word.starts_with(&start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hesitations_are_bracketed() {
+        assert_eq!(
+            mark_disfluencies("Еее, я думаю, ммм, що так. Хм."),
+            "[еее], я думаю, [ммм], що так. [хм]."
+        );
+        assert_eq!(mark_disfluencies("Uh, I think, umm, yes"), "[uh], I think, [umm], yes");
+        assert_eq!(mark_disfluencies("е-е-е добре"), "[еее] добре");
+    }
+
+    #[test]
+    fn test_words_are_not_taken_for_hesitations() {
+        let text = "А я знаю, а ти? Hmm is not a word, mama";
+        assert_eq!(mark_disfluencies(text), "А я знаю, а ти? [hmm] is not a word, mama");
+        assert_eq!(mark_disfluencies("ам ем"), "[ам] [ем]");
+        assert_eq!(mark_disfluencies("мама"), "мама");
+    }
+
+    #[test]
+    fn test_cut_off_words_are_marked() {
+        assert_eq!(mark_disfluencies("я хо— хотів"), "я хо- хотів");
+        assert_eq!(mark_disfluencies("я хо... хотів сказати"), "я хо- хотів сказати");
+        assert_eq!(mark_disfluencies("я хо-хотів сказати."), "я хо- хотів сказати.");
+        assert_eq!(mark_disfluencies("і тоді сло-"), "і тоді сло-");
+    }
+
+    #[test]
+    fn test_hyphenated_words_and_pauses_are_kept() {
+        assert_eq!(mark_disfluencies("будь-який варіант"), "будь-який варіант");
+        assert_eq!(mark_disfluencies("я думав... ну так"), "я думав... ну так");
+        assert_eq!(mark_disfluencies("well - maybe"), "well - maybe");
+    }
+
+    #[test]
+    fn test_verbatim_prompt_by_language() {
+        assert!(verbatim_prompt("uk").unwrap().contains("хо- хотів"));
+        assert!(verbatim_prompt("en").is_some());
+        assert!(verbatim_prompt("de").is_none());
+    }
+}
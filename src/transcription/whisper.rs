@@ -1,6 +1,14 @@
+use super::verbatim::verbatim_prompt;
+use crate::domain::types::TimedWord;
+use crate::infrastructure::hardware::CpuFeatures;
 use anyhow::{Context, Result};
 use whisper_rs::{FullParams, SamplingStrategy, WhisperContext, WhisperContextParameters};
 
+/// SIMD extensions the linked whisper.cpp was compiled with.
+pub fn build_cpu_features() -> CpuFeatures {
+    CpuFeatures::from_system_info(whisper_rs::print_system_info())
+}
+
 pub(crate) struct WhisperSTT {
     ctx: WhisperContext,
     #[allow(dead_code)] // Used via Transcription::model_name() trait impl
@@ -18,18 +26,78 @@ impl WhisperSTT {
         })
     }
 
-    pub fn transcribe(&self, samples: &[f32], language: Option<&str>) -> Result<String> {
+    /// Transcribe; `verbatim` keeps hesitations and cut-off words (see `verbatim`).
+    pub fn transcribe(&self, samples: &[f32], language: Option<&str>, verbatim: bool) -> Result<String> {
+        self.run(samples, language, false, verbatim)
+    }
+
+    /// Transcribe and translate to English using Whisper's built-in translate task.
+    pub fn translate(&self, samples: &[f32], language: Option<&str>) -> Result<String> {
+        self.run(samples, language, true, false)
+    }
+
+    /// Transcribe into words with timestamps.
+    ///
+    /// Token timestamps with one-word segments: every segment Whisper
+    /// returns is a single word with its start and end.
+    pub fn transcribe_words(&self, samples: &[f32], language: Option<&str>, verbatim: bool) -> Result<Vec<TimedWord>> {
+        let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
+
+        if let Some(lang) = language {
+            params.set_language(Some(lang));
+        }
+        if let Some(prompt) = language.filter(|_| verbatim).and_then(verbatim_prompt) {
+            params.set_initial_prompt(prompt);
+        }
+
+        params.set_print_special(false);
+        params.set_print_progress(false);
+        params.set_print_realtime(false);
+        params.set_print_timestamps(false);
+        params.set_token_timestamps(true);
+        params.set_split_on_word(true);
+        params.set_max_len(1);
+
+        let mut state = self.ctx.create_state()?;
+        state.full(params, samples)?;
+
+        let num_segments = state.full_n_segments()?;
+        let mut words = Vec::new();
+
+        for i in 0..num_segments {
+            let Ok(text) = state.full_get_segment_text(i) else {
+                continue;
+            };
+            let text = text.trim();
+            if text.is_empty() {
+                continue;
+            }
+            // Segment times are in centiseconds
+            words.push(TimedWord {
+                text: text.to_string(),
+                start: state.full_get_segment_t0(i)? as f64 / 100.0,
+                end: state.full_get_segment_t1(i)? as f64 / 100.0,
+            });
+        }
+
+        Ok(words)
+    }
+
+    fn run(&self, samples: &[f32], language: Option<&str>, translate: bool, verbatim: bool) -> Result<String> {
         let mut params = FullParams::new(SamplingStrategy::Greedy { best_of: 1 });
 
         if let Some(lang) = language {
             params.set_language(Some(lang));
         }
+        if let Some(prompt) = language.filter(|_| verbatim).and_then(verbatim_prompt) {
+            params.set_initial_prompt(prompt);
+        }
 
         params.set_print_special(false);
         params.set_print_progress(false);
         params.set_print_realtime(false);
         params.set_print_timestamps(false);
-        params.set_translate(false);
+        params.set_translate(translate);
 
         let mut state = self.ctx.create_state()?;
         state.full(params, samples)?;
@@ -54,7 +122,7 @@ use crate::domain::traits::Transcription;
 
 impl Transcription for WhisperSTT {
     fn transcribe(&self, samples: &[f32], language: &str) -> anyhow::Result<String> {
-        WhisperSTT::transcribe(self, samples, Some(language))
+        WhisperSTT::transcribe(self, samples, Some(language), false)
     }
 
     fn is_loaded(&self) -> bool {
@@ -69,4 +137,12 @@ impl Transcription for WhisperSTT {
         // WhisperSTT is created with a model; use TranscriptionService for model management
         anyhow::bail!("WhisperSTT does not support runtime model loading; use TranscriptionService")
     }
+
+    fn translate(&self, samples: &[f32], language: &str) -> anyhow::Result<String> {
+        WhisperSTT::translate(self, samples, Some(language))
+    }
+
+    fn transcribe_words(&self, samples: &[f32], language: &str) -> anyhow::Result<Vec<TimedWord>> {
+        WhisperSTT::transcribe_words(self, samples, Some(language), false)
+    }
 }
@@ -0,0 +1,255 @@
+//! Speaker attribution by word timestamps.
+//!
+//! Instead of cutting the audio at diarization boundaries and transcribing
+//! every speaker segment on its own, the whole recording is transcribed once
+//! with word timestamps and each word goes to the speaker whose segments
+//! overlap it most. One pass is faster than many short ones, and words at a
+//! turn boundary are never cut in half.
+
+use crate::domain::traits::Transcription;
+use crate::domain::types::TimedWord;
+use crate::transcription::diarization::DiarizationSegment;
+use anyhow::Result;
+
+/// Window for word transcription of long recordings, matching the default
+/// `max_segment_secs`.
+pub const DEFAULT_WORD_WINDOW_SECS: usize = 300;
+
+/// How transcribed text is matched to diarization speakers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpeakerAttribution {
+    /// Transcribe once with word timestamps, assign words by overlap.
+    Words,
+    /// Transcribe every diarization segment separately.
+    Segments,
+}
+
+impl SpeakerAttribution {
+    /// Parse the `speaker_attribution` config value; unknown values mean `Words`.
+    pub fn from_config(value: &str) -> Self {
+        match value {
+            "segments" => Self::Segments,
+            _ => Self::Words,
+        }
+    }
+}
+
+/// Consecutive words of one speaker.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerPiece {
+    pub speaker_id: usize,
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// Group words into speaker pieces.
+///
+/// Each word goes to the speaker with the most overlap; a word in a gap
+/// between segments goes to the nearest one. Consecutive words of the same
+/// speaker form one piece.
+pub fn attribute_words(words: &[TimedWord], segments: &[DiarizationSegment]) -> Vec<SpeakerPiece> {
+    let mut pieces: Vec<SpeakerPiece> = Vec::new();
+    for word in words {
+        let Some(speaker_id) = speaker_for_word(word, segments) else {
+            continue;
+        };
+        match pieces.last_mut() {
+            Some(piece) if piece.speaker_id == speaker_id => {
+                append_word(&mut piece.text, &word.text);
+                piece.end = word.end;
+            }
+            _ => pieces.push(SpeakerPiece {
+                speaker_id,
+                text: word.text.clone(),
+                start: word.start,
+                end: word.end,
+            }),
+        }
+    }
+    pieces
+}
+
+fn speaker_for_word(word: &TimedWord, segments: &[DiarizationSegment]) -> Option<usize> {
+    let mut overlap_by_speaker: Vec<(usize, f64)> = Vec::new();
+    for seg in segments {
+        let overlap = word.end.min(seg.end_time) - word.start.max(seg.start_time);
+        if overlap <= 0.0 {
+            continue;
+        }
+        match overlap_by_speaker.iter_mut().find(|(id, _)| *id == seg.speaker_id) {
+            Some((_, total)) => *total += overlap,
+            None => overlap_by_speaker.push((seg.speaker_id, overlap)),
+        }
+    }
+    if let Some((speaker_id, _)) = overlap_by_speaker.iter().max_by(|a, b| a.1.total_cmp(&b.1)) {
+        return Some(*speaker_id);
+    }
+
+    let middle = (word.start + word.end) / 2.0;
+    segments
+        .iter()
+        .min_by(|a, b| distance(middle, a).total_cmp(&distance(middle, b)))
+        .map(|seg| seg.speaker_id)
+}
+
+fn distance(time: f64, seg: &DiarizationSegment) -> f64 {
+    (seg.start_time - time).max(time - seg.end_time).max(0.0)
+}
+
+/// Append a word, keeping punctuation tokens attached to the previous word.
+pub(crate) fn append_word(text: &mut String, word: &str) {
+    let attaches = word
+        .chars()
+        .next()
+        .is_some_and(|c| matches!(c, ',' | '.' | '!' | '?' | ';' | ':' | '…' | ')' | '»'));
+    if !attaches && !text.is_empty() {
+        text.push(' ');
+    }
+    text.push_str(word);
+}
+
+/// Transcribe into timed words, `window_samples` at a time.
+///
+/// Long recordings are split into windows to bound memory, as the chunker
+/// does for plain transcription; word times are shifted to the whole
+/// recording. `window_samples == 0` transcribes everything at once.
+pub fn transcribe_words_windowed(
+    transcription: &dyn Transcription,
+    samples: &[f32],
+    language: &str,
+    window_samples: usize,
+) -> Result<Vec<TimedWord>> {
+    if window_samples == 0 || samples.len() <= window_samples {
+        return transcription.transcribe_words(samples, language);
+    }
+
+    let mut words = Vec::new();
+    for (index, window) in samples.chunks(window_samples).enumerate() {
+        let offset = (index * window_samples) as f64 / 16000.0;
+        words.extend(
+            transcription
+                .transcribe_words(window, language)?
+                .into_iter()
+                .map(|word| TimedWord {
+                    start: word.start + offset,
+                    end: word.end + offset,
+                    ..word
+                }),
+        );
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn word(text: &str, start: f64, end: f64) -> TimedWord {
+        TimedWord {
+            text: text.to_string(),
+            start,
+            end,
+        }
+    }
+
+    fn seg(speaker_id: usize, start_time: f64, end_time: f64) -> DiarizationSegment {
+        DiarizationSegment {
+            speaker_id,
+            start_time,
+            end_time,
+        }
+    }
+
+    #[test]
+    fn test_attribute_words_groups_by_speaker() {
+        let words = vec![
+            word("Привіт", 0.0, 0.5),
+            word(",", 0.5, 0.55),
+            word("як", 0.6, 0.8),
+            word("справи", 0.8, 1.2),
+            word("Добре", 1.6, 2.0),
+        ];
+        let segments = vec![seg(0, 0.0, 1.3), seg(1, 1.5, 2.5)];
+        let pieces = attribute_words(&words, &segments);
+        assert_eq!(
+            pieces,
+            vec![
+                SpeakerPiece {
+                    speaker_id: 0,
+                    text: "Привіт, як справи".to_string(),
+                    start: 0.0,
+                    end: 1.2,
+                },
+                SpeakerPiece {
+                    speaker_id: 1,
+                    text: "Добре".to_string(),
+                    start: 1.6,
+                    end: 2.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_word_across_boundary_goes_to_larger_overlap() {
+        let segments = vec![seg(0, 0.0, 1.0), seg(1, 1.0, 3.0)];
+        assert_eq!(speaker_for_word(&word("слово", 0.9, 1.5), &segments), Some(1));
+        assert_eq!(speaker_for_word(&word("слово", 0.5, 1.1), &segments), Some(0));
+    }
+
+    #[test]
+    fn test_word_in_gap_goes_to_nearest_segment() {
+        let segments = vec![seg(0, 0.0, 1.0), seg(1, 3.0, 4.0)];
+        assert_eq!(speaker_for_word(&word("е", 1.2, 1.4), &segments), Some(0));
+        assert_eq!(speaker_for_word(&word("е", 2.6, 2.8), &segments), Some(1));
+        assert_eq!(speaker_for_word(&word("е", 0.0, 1.0), &[]), None);
+    }
+
+    #[test]
+    fn test_speaker_attribution_from_config() {
+        assert_eq!(
+            SpeakerAttribution::from_config("segments"),
+            SpeakerAttribution::Segments
+        );
+        assert_eq!(SpeakerAttribution::from_config("words"), SpeakerAttribution::Words);
+        assert_eq!(SpeakerAttribution::from_config("other"), SpeakerAttribution::Words);
+    }
+
+    /// Returns one word spanning each window it is given.
+    struct WindowWords;
+
+    impl Transcription for WindowWords {
+        fn transcribe(&self, _samples: &[f32], _language: &str) -> Result<String> {
+            Ok(String::new())
+        }
+
+        fn is_loaded(&self) -> bool {
+            true
+        }
+
+        fn model_name(&self) -> Option<String> {
+            None
+        }
+
+        fn load_model(&mut self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+
+        fn transcribe_words(&self, samples: &[f32], _language: &str) -> Result<Vec<TimedWord>> {
+            Ok(vec![word("слово", 0.0, samples.len() as f64 / 16000.0)])
+        }
+    }
+
+    #[test]
+    fn test_transcribe_words_windowed_shifts_times() {
+        let samples = vec![0.0; 16000 * 5];
+        let words = transcribe_words_windowed(&WindowWords, &samples, "uk", 16000 * 2).unwrap();
+        let times: Vec<(f64, f64)> = words.iter().map(|w| (w.start, w.end)).collect();
+        assert_eq!(times, vec![(0.0, 2.0), (2.0, 4.0), (4.0, 5.0)]);
+
+        let whole = transcribe_words_windowed(&WindowWords, &samples, "uk", 0).unwrap();
+        assert_eq!(whole.len(), 1);
+    }
+}
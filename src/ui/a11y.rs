@@ -0,0 +1,61 @@
+//! Accessibility helpers: accessible names and screen-reader announcements.
+//!
+//! Spoken announcements need the `a11y-announce` feature (GTK >= 4.14),
+//! which uses `gtk_accessible_announce`. Without it nothing is spoken when
+//! the state changes: the message only becomes the accessible description
+//! of the status widget, which Orca reads when the widget is focused or
+//! reviewed.
+
+use gtk4::accessible::{Property, Relation};
+use gtk4::prelude::*;
+
+/// Announcement urgency, mapped to GTK announcement priorities.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Urgency {
+    /// Routine state changes (transcription ready, settings saved).
+    Polite,
+    /// Changes the user must notice immediately (recording started/stopped, errors).
+    Assertive,
+}
+
+/// Set the accessible name of a widget.
+pub fn set_name(widget: &impl IsA<gtk4::Accessible>, name: &str) {
+    widget.update_property(&[Property::Label(name)]);
+}
+
+/// Set the accessible name and description of a widget.
+pub fn set_name_and_description(widget: &impl IsA<gtk4::Accessible>, name: &str, description: &str) {
+    widget.update_property(&[Property::Label(name), Property::Description(description)]);
+}
+
+/// Set the accessible description of a widget, keeping its name (for a
+/// label, the text it shows).
+pub fn set_description(widget: &impl IsA<gtk4::Accessible>, description: &str) {
+    widget.update_property(&[Property::Description(description)]);
+}
+
+/// Link a widget to the visible label that names it.
+pub fn set_labelled_by(widget: &impl IsA<gtk4::Accessible>, label: &impl IsA<gtk4::Accessible>) {
+    widget.update_relation(&[Relation::LabelledBy(&[label.upcast_ref()])]);
+}
+
+/// Announce a message to assistive technologies via the given widget.
+///
+/// Spoken only with the `a11y-announce` feature; see the module docs.
+pub fn announce(widget: &impl IsA<gtk4::Accessible>, message: &str, urgency: Urgency) {
+    #[cfg(feature = "a11y-announce")]
+    {
+        use gtk4::AccessibleAnnouncementPriority;
+        let priority = match urgency {
+            Urgency::Polite => AccessibleAnnouncementPriority::Medium,
+            Urgency::Assertive => AccessibleAnnouncementPriority::High,
+        };
+        widget.announce(message, priority);
+    }
+
+    #[cfg(not(feature = "a11y-announce"))]
+    {
+        let _ = urgency;
+        widget.update_property(&[Property::Description(message)]);
+    }
+}
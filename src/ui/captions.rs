@@ -0,0 +1,225 @@
+//! Live translated captions overlay for continuous mode.
+//!
+//! Final captions are produced alongside each segment transcription; partial
+//! captions are refreshed from the in-progress segment audio while the user
+//! keeps speaking. Partials never wait for the model: if a segment is being
+//! transcribed, the partial request is skipped and retried on the next tick.
+
+use crate::app::context::AppContext;
+use crate::domain::traits::Transcription;
+use crate::transcription::captions::{
+    partial_due, CaptionBuffer, CaptionView, MAX_PARTIAL_SAMPLES, MIN_PARTIAL_SAMPLES,
+};
+use crate::ui::a11y;
+use gtk4::prelude::*;
+use gtk4::{glib, Align, Box as GtkBox, Label, Orientation, Window};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Partial caption poll interval.
+const PARTIAL_TICK: Duration = Duration::from_millis(200);
+
+/// Undecorated window showing the latest captions.
+struct CaptionOverlay {
+    window: Window,
+    lines_label: Label,
+    partial_label: Label,
+}
+
+impl CaptionOverlay {
+    fn new(parent: Option<&Window>) -> Self {
+        load_caption_css();
+
+        let window = Window::builder()
+            .title("Субтитри")
+            .decorated(false)
+            .resizable(true)
+            .default_width(720)
+            .default_height(120)
+            .build();
+        if let Some(parent) = parent {
+            window.set_transient_for(Some(parent));
+        }
+        window.add_css_class("caption-overlay");
+
+        let content = GtkBox::new(Orientation::Vertical, 4);
+        content.set_margin_top(12);
+        content.set_margin_bottom(12);
+        content.set_margin_start(16);
+        content.set_margin_end(16);
+
+        let lines_label = Label::new(None);
+        lines_label.set_wrap(true);
+        lines_label.set_halign(Align::Center);
+        lines_label.set_justify(gtk4::Justification::Center);
+        lines_label.add_css_class("caption-final");
+        content.append(&lines_label);
+
+        let partial_label = Label::new(Some("…"));
+        partial_label.set_wrap(true);
+        partial_label.set_halign(Align::Center);
+        partial_label.set_justify(gtk4::Justification::Center);
+        partial_label.add_css_class("caption-partial");
+        content.append(&partial_label);
+
+        a11y::set_name(&window, "Живі субтитри");
+        window.set_child(Some(&content));
+        window.present();
+
+        Self {
+            window,
+            lines_label,
+            partial_label,
+        }
+    }
+
+    fn update(&self, view: &CaptionView) {
+        self.lines_label.set_text(&view.lines.join("\n"));
+        self.lines_label.set_visible(!view.lines.is_empty());
+        self.partial_label.set_text(view.partial.as_deref().unwrap_or(""));
+        self.partial_label.set_visible(view.partial.is_some());
+    }
+
+    fn show_error(&self, message: &str) {
+        self.partial_label.set_text(message);
+        self.partial_label.set_visible(true);
+    }
+}
+
+/// Caption state for one continuous recording session.
+pub struct LiveCaptions {
+    overlay: CaptionOverlay,
+    buffer: RefCell<CaptionBuffer>,
+    partial_in_flight: Cell<bool>,
+    last_partial: Cell<Instant>,
+    /// Id of the segment currently being recorded (last received + 1).
+    current_segment: Cell<usize>,
+    active: Cell<bool>,
+}
+
+impl LiveCaptions {
+    /// Open the overlay and start refreshing partial captions.
+    pub fn start(ctx: &Arc<AppContext>, parent: Option<&Window>) -> Rc<Self> {
+        let captions = Rc::new(Self {
+            overlay: CaptionOverlay::new(parent),
+            buffer: RefCell::new(CaptionBuffer::default()),
+            partial_in_flight: Cell::new(false),
+            last_partial: Cell::new(Instant::now()),
+            current_segment: Cell::new(1),
+            active: Cell::new(true),
+        });
+        captions.start_partial_loop(ctx);
+        captions
+    }
+
+    /// Note that a segment was handed off for final transcription.
+    pub fn segment_sent(&self, segment_id: usize) {
+        self.current_segment.set(self.current_segment.get().max(segment_id + 1));
+    }
+
+    /// Show the translation of a completed segment.
+    pub fn finalize(&self, segment_id: usize, result: Result<String, String>) {
+        match result {
+            Ok(text) => {
+                self.buffer.borrow_mut().finalize(segment_id, &text);
+                self.overlay.update(&self.buffer.borrow().view());
+            }
+            Err(e) => {
+                self.buffer.borrow_mut().finalize(segment_id, "");
+                self.overlay.show_error(&format!("Помилка перекладу: {}", e));
+            }
+        }
+    }
+
+    /// Stop partial updates (final captions may still arrive).
+    pub fn stop_partials(&self) {
+        self.active.set(false);
+    }
+
+    /// Close the overlay window.
+    pub fn close(&self) {
+        self.active.set(false);
+        self.overlay.window.close();
+    }
+
+    fn start_partial_loop(self: &Rc<Self>, ctx: &Arc<AppContext>) {
+        let (tx, rx) = async_channel::unbounded::<Option<(usize, String)>>();
+
+        let captions = self.clone();
+        glib::spawn_future_local(async move {
+            while let Ok(result) = rx.recv().await {
+                captions.partial_in_flight.set(false);
+                if !captions.active.get() {
+                    continue;
+                }
+                if let Some((segment_id, text)) = result {
+                    if captions.buffer.borrow_mut().set_partial(segment_id, &text) {
+                        captions.overlay.update(&captions.buffer.borrow().view());
+                    }
+                }
+            }
+        });
+
+        let captions = self.clone();
+        let ctx = ctx.clone();
+        glib::timeout_add_local(PARTIAL_TICK, move || {
+            if !captions.active.get() {
+                return glib::ControlFlow::Break;
+            }
+
+            if !partial_due(captions.partial_in_flight.get(), captions.last_partial.get().elapsed()) {
+                return glib::ControlFlow::Continue;
+            }
+            let pending = ctx.audio.pending_segment_tail(MAX_PARTIAL_SAMPLES);
+            if pending.len() < MIN_PARTIAL_SAMPLES {
+                return glib::ControlFlow::Continue;
+            }
+
+            captions.partial_in_flight.set(true);
+            captions.last_partial.set(Instant::now());
+
+            let segment_id = captions.current_segment.get();
+            let ctx = ctx.clone();
+            let tx = tx.clone();
+            let language = ctx.language();
+            std::thread::spawn(move || {
+                // Never block final segment transcription for a preview
+                let result = ctx.transcription.try_lock().and_then(|ts| {
+                    ts.translate(&pending, &language)
+                        .map_err(|e| eprintln!("Помилка часткового перекладу: {}", e))
+                        .ok()
+                });
+                let _ = tx.send_blocking(result.map(|text| (segment_id, text)));
+            });
+
+            glib::ControlFlow::Continue
+        });
+    }
+}
+
+fn load_caption_css() {
+    let css_provider = gtk4::CssProvider::new();
+    css_provider.load_from_data(
+        r#"
+        .caption-overlay {
+            background-color: rgba(0, 0, 0, 0.8);
+        }
+        .caption-final {
+            color: #ffffff;
+            font-size: 22px;
+        }
+        .caption-partial {
+            color: #c0c0c0;
+            font-size: 22px;
+            font-style: italic;
+        }
+        "#,
+    );
+    gtk4::style_context_add_provider_for_display(
+        &gtk4::gdk::Display::default().expect("Could not get default display"),
+        &css_provider,
+        gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
+    );
+}
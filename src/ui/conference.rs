@@ -4,16 +4,120 @@
 //! with dual-channel audio (microphone + system loopback) and diarization.
 
 use crate::app::context::AppContext;
+use crate::app::templates::{SessionTemplate, SessionTemplates};
 use crate::domain::traits::UIStateUpdater;
+use crate::domain::types::{ActionItem, TranscriptSegment};
+use crate::history::save_history;
+use crate::infrastructure::perf::timed;
 use crate::infrastructure::recordings::{
-    ensure_recordings_dir, generate_recording_filename, recording_path, save_recording,
+    ensure_recordings_dir, generate_recording_filename, recording_path, save_recording, save_spooled_recording,
+    save_turns, RecordingFormat,
 };
+use crate::recording::denoise::DenoiseSettings;
+use crate::recording::echo::cancel_echo;
+use crate::recording::loudness::Normalization;
+use crate::recording::spool::SpooledRecording;
+use crate::transcription::action_items::{extract_action_items, prepend_summary};
+use crate::transcription::diarization::speaker_label;
+use crate::transcription::live_diarization::{LiveDiarization, LIVE_BLOCK_SECS};
+use crate::transcription::rttm::SpeakerTurn;
+use crate::transcription::service::{mix_channels, ConferenceTranscript};
+use crate::transcription::talk_time::{append_talk_time, talk_time};
+use crate::transcription::word_attribution::SpeakerAttribution;
 use crate::ui::shared::{self, maybe_denoise};
 use gtk4::glib;
+use parking_lot::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use super::state::{ConferenceUI, RecordingContext};
 
+/// How often the live diarization worker checks for a new block.
+const LIVE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Live diarization running in a worker thread during conference recording.
+pub struct LiveDiarizationSession {
+    state: Arc<Mutex<Option<LiveDiarization>>>,
+    running: Arc<AtomicBool>,
+}
+
+impl LiveDiarizationSession {
+    /// Start diarizing the recording in blocks of [`LIVE_BLOCK_SECS`].
+    fn start(ctx: &Arc<AppContext>) -> Self {
+        let threshold = ctx.config.lock().speaker_match_threshold;
+        let state = Arc::new(Mutex::new(Some(LiveDiarization::new(threshold))));
+        let running = Arc::new(AtomicBool::new(true));
+
+        let ctx = ctx.clone();
+        let state_for_thread = state.clone();
+        let running_for_thread = running.clone();
+        std::thread::spawn(move || {
+            let block_samples = (LIVE_BLOCK_SECS * 16000.0) as usize;
+            while running_for_thread.load(Ordering::SeqCst) {
+                std::thread::sleep(LIVE_POLL_INTERVAL);
+                let Some(from) = state_for_thread.lock().as_ref().map(|l| l.processed_samples()) else {
+                    break;
+                };
+                let (mic, loopback) = ctx.audio.conference_samples_from(from);
+                // Wait for both channels unless one of them records nothing at all
+                let ready = if mic.is_empty() || loopback.is_empty() {
+                    mic.len().max(loopback.len())
+                } else {
+                    mic.len().min(loopback.len())
+                };
+                if ready < block_samples {
+                    continue;
+                }
+                let block = mix_channels(
+                    &mic[..block_samples.min(mic.len())],
+                    &loopback[..block_samples.min(loopback.len())],
+                );
+
+                // Lock ordering: diarization before the live state
+                let mut engine = ctx.diarization.lock();
+                let mut guard = state_for_thread.lock();
+                let Some(live) = guard.as_mut() else {
+                    break;
+                };
+                if !running_for_thread.load(Ordering::SeqCst) {
+                    break;
+                }
+                if let Err(e) = live.diarize_block(&mut engine, &block) {
+                    eprintln!("Помилка живої діаризації: {}", e);
+                    break;
+                }
+            }
+        });
+
+        Self { state, running }
+    }
+
+    /// Stop the worker; the blocks diarized so far stay in the returned state.
+    fn stop(self) -> Arc<Mutex<Option<LiveDiarization>>> {
+        self.running.store(false, Ordering::SeqCst);
+        self.state
+    }
+}
+
+/// Update the "who is speaking now" label while recording.
+fn start_speaker_indicator_loop(rec: &RecordingContext, ui: &ConferenceUI, state: Arc<Mutex<Option<LiveDiarization>>>) {
+    let rec = rec.clone();
+    let ui = ui.clone();
+    glib::timeout_add_local(LIVE_POLL_INTERVAL, move || {
+        if !rec.is_recording() {
+            return glib::ControlFlow::Break;
+        }
+        // The worker holds the state while diarizing a block; skip this tick then
+        if let Some(guard) = state.try_lock() {
+            if let Some(live) = guard.as_ref().filter(|l| l.processed_samples() > 0) {
+                ui.set_current_speaker(live.current_speaker().map(speaker_label).as_deref());
+            }
+        }
+        glib::ControlFlow::Continue
+    });
+}
+
 /// Start conference recording (mic + loopback)
 pub fn handle_start(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &ConferenceUI) {
     // Check if model is loaded
@@ -22,15 +126,26 @@ pub fn handle_start(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Conferen
         return;
     }
 
-    match ctx.audio.start_conference() {
+    match ctx.audio.start_conference(ctx.loopback_source().as_deref()) {
         Ok(()) => {
             rec.start_recording();
+            *ui.session_template.borrow_mut() = ui
+                .template_combo
+                .active_id()
+                .and_then(|name| SessionTemplates::load_default().find(&name).cloned());
 
             ui.base.set_recording("Запис конференції...");
             ui.show_level_bars();
 
-            shared::start_timer_loop(rec, &ui.base);
+            shared::start_timer_loop(ctx, rec, &ui.base);
             shared::start_conference_level_loop(ctx, rec, ui);
+
+            let live_available = ctx.live_diarization() && ctx.diarization.lock().is_available();
+            if live_available {
+                let session = LiveDiarizationSession::start(ctx);
+                start_speaker_indicator_loop(rec, ui, session.state.clone());
+                *ui.live_diarization.borrow_mut() = Some(session);
+            }
         }
         Err(e) => {
             ui.base.set_status(&format!("Помилка: {}", e));
@@ -46,10 +161,17 @@ pub fn handle_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Conferenc
     ui.base.set_processing("Обробка...");
     ui.hide_level_bars();
 
-    let recording = ctx.audio.stop_conference();
+    let live_state = ui
+        .live_diarization
+        .borrow_mut()
+        .take()
+        .map(LiveDiarizationSession::stop);
+    let (recording, spooled) = ctx.audio.stop_conference();
 
     // Calculate duration using shared type's method
-    let duration_secs = recording.duration_secs();
+    let duration_secs = spooled
+        .as_ref()
+        .map_or_else(|| recording.duration_secs(), SpooledRecording::duration_secs);
     let duration_mins = (duration_secs / 60.0).floor() as u32;
     let duration_remaining_secs = (duration_secs % 60.0).floor() as u32;
     ui.base.set_status(&format!(
@@ -57,18 +179,26 @@ pub fn handle_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Conferenc
         duration_mins, duration_remaining_secs
     ));
 
-    // Ensure recordings directory exists
-    if let Err(e) = ensure_recordings_dir() {
-        eprintln!("Помилка створення директорії записів: {}", e);
+    // Kiosk mode keeps no audio on disk
+    let keep_recording = !ctx.read_only_history();
+    if keep_recording {
+        if let Err(e) = ensure_recordings_dir() {
+            eprintln!("Помилка створення директорії записів: {}", e);
+        }
     }
 
     // Clone what we need for the async block
     let ctx = ctx.clone();
     let rec = rec.clone();
     let ui = ui.clone();
-    let language = ctx.language();
+    let template = ui.session_template.borrow_mut().take();
+    let language = template
+        .as_ref()
+        .and_then(|t| t.language.clone())
+        .unwrap_or_else(|| ctx.language());
     let diarization_method = ctx.diarization_method();
-    let denoise_enabled = ctx.denoise_enabled();
+    let echo_cancellation = ctx.echo_cancellation();
+    let denoise = ctx.denoise_settings();
 
     glib::spawn_future_local(async move {
         // Wait for both recording threads to finish
@@ -80,45 +210,141 @@ pub fn handle_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Conferenc
         }
 
         // Save audio file
-        let filename = generate_recording_filename();
+        let title = template
+            .as_ref()
+            .and_then(|t| t.title_for(chrono::Local::now().date_naive()));
+        let (filename_template, format) = {
+            let cfg = ctx.config.lock();
+            (
+                cfg.recording_filename_template.clone(),
+                RecordingFormat::parse(&cfg.recording_format),
+            )
+        };
+        let filename = generate_recording_filename(&filename_template, "conference", title.as_deref(), format);
         let file_path = recording_path(&filename);
-        if let Err(e) = save_recording(&recording.mic_samples, &recording.loopback_samples, &file_path) {
-            eprintln!("Помилка збереження аудіо файлу: {}", e);
+        let normalization = Normalization::from_config(&ctx.config.lock());
+        // A spooled recording is saved by the worker, reading it from disk
+        if keep_recording && spooled.is_none() {
+            if let Err(e) = save_recording(
+                &recording.mic_samples,
+                &recording.loopback_samples,
+                &file_path,
+                normalization,
+            ) {
+                eprintln!("Помилка збереження аудіо файлу: {}", e);
+            }
         }
 
         // Transcribe with diarization
-        let (tx, rx) = async_channel::bounded::<anyhow::Result<String>>(1);
+        let (tx, rx) = async_channel::bounded::<anyhow::Result<(ConferenceTranscript, Vec<ActionItem>, Duration)>>(1);
 
         let ctx_for_thread = ctx.clone();
         let mic_samples = recording.mic_samples;
         let loopback_samples = recording.loopback_samples;
         let language_for_thread = language.clone();
         let diarization_method_for_thread = diarization_method.clone();
+        let template_for_thread = template.clone();
+        let file_path_for_thread = file_path.clone();
 
         std::thread::spawn(move || {
-            let mic_samples = maybe_denoise(&mic_samples, denoise_enabled);
-            let loopback_samples = maybe_denoise(&loopback_samples, denoise_enabled);
-            // Lock ordering: diarization before transcription.
-            // This ensures consistent ordering across the codebase.
-            let mut engine_guard = ctx_for_thread.diarization.lock();
-            let ts = ctx_for_thread.transcription.lock();
-            let result = ts.transcribe_conference(
-                &mic_samples,
-                &loopback_samples,
-                &language_for_thread,
-                &diarization_method_for_thread,
-                Some(&mut *engine_guard),
-            );
+            let (result, processing) = match spooled {
+                Some(mut spooled) => {
+                    if keep_recording {
+                        if let Err(e) = save_spooled_recording(&mut spooled, &file_path_for_thread, normalization) {
+                            eprintln!("Помилка збереження аудіо файлу: {}", e);
+                        }
+                    }
+                    // Diarized live or not, each window is diarized on its own
+                    drop(live_state);
+                    timed(|| {
+                        transcribe_spooled(
+                            &ctx_for_thread,
+                            &mut spooled,
+                            &language_for_thread,
+                            &diarization_method_for_thread,
+                            echo_cancellation,
+                            denoise.as_ref(),
+                        )
+                    })
+                }
+                None => {
+                    // Speaker output heard by the mic would be transcribed as "Ви" again
+                    let mic_samples = if echo_cancellation {
+                        cancel_echo(&mic_samples, &loopback_samples)
+                    } else {
+                        mic_samples
+                    };
+                    let mic_samples = maybe_denoise(&mic_samples, denoise.as_ref());
+                    let loopback_samples = maybe_denoise(&loopback_samples, denoise.as_ref());
+                    let attribution =
+                        SpeakerAttribution::from_config(&ctx_for_thread.config.lock().speaker_attribution);
+                    let live = live_state.and_then(|state| state.lock().take());
+                    // Lock ordering: diarization before transcription.
+                    // This ensures consistent ordering across the codebase.
+                    let mut engine_guard = ctx_for_thread.diarization.lock();
+                    let ts = ctx_for_thread.transcription.lock();
+                    timed(|| match live {
+                        Some(live) => ts.transcribe_conference_live(
+                            &mic_samples,
+                            &loopback_samples,
+                            &language_for_thread,
+                            attribution,
+                            &mut *engine_guard,
+                            live,
+                        ),
+                        None => ts.transcribe_conference(
+                            &mic_samples,
+                            &loopback_samples,
+                            &language_for_thread,
+                            &diarization_method_for_thread,
+                            attribution,
+                            Some(&mut *engine_guard),
+                        ),
+                    })
+                }
+            };
+            // Models are released first: an LLM request may take a while
+            let config = ctx_for_thread.config.lock().clone();
+            let result = result.map(|mut transcript| {
+                if let Some(template) = &template_for_thread {
+                    template.apply_participants(&mut transcript);
+                }
+                let action_items = extract_action_items(&transcript.text, &config);
+                (transcript, action_items, processing)
+            });
             let _ = tx.send_blocking(result);
         });
 
         if let Ok(result) = rx.recv().await {
             match result {
-                Ok(text) => {
+                Ok((transcript, action_items, processing)) => {
+                    let ConferenceTranscript {
+                        text,
+                        turns,
+                        confidence: speaker_confidence,
+                        segments,
+                    } = transcript;
                     if text.is_empty() {
                         ui.base.set_status("Не вдалося розпізнати мову");
                     } else {
-                        let speakers = vec!["Ви".to_string(), "Учасник".to_string()];
+                        // Keep Sortformer turns with the recording for per-speaker export
+                        let speakers = if turns.is_empty() {
+                            vec!["Ви".to_string(), "Учасник".to_string()]
+                        } else {
+                            if keep_recording {
+                                if let Err(e) = save_turns(&file_path, &turns) {
+                                    eprintln!("Помилка збереження реплік мовців: {}", e);
+                                }
+                            }
+                            let mut speakers: Vec<String> = Vec::new();
+                            for turn in &turns {
+                                if !speakers.contains(&turn.speaker) {
+                                    speakers.push(turn.speaker.clone());
+                                }
+                            }
+                            speakers
+                        };
+                        let speaker_stats = talk_time(&turns);
                         shared::handle_post_transcription(
                             &ctx,
                             &ui.base,
@@ -127,8 +353,20 @@ pub fn handle_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Conferenc
                             duration_secs,
                             Some(file_path.to_string_lossy().to_string()),
                             speakers,
+                            action_items.clone(),
+                            speaker_stats.clone(),
+                            speaker_confidence,
+                            None,
+                            Some(processing),
                         )
                         .await;
+                        store_segments(&ctx, &ui, segments);
+                        if let Some(template) = &template {
+                            apply_template_metadata(&ctx, &ui, template);
+                        }
+                        let recording = file_path.exists().then_some(file_path.as_path());
+                        let transcript = append_talk_time(&prepend_summary(&text, &action_items), &speaker_stats);
+                        shared::queue_webdav_upload(&ctx, "conference", Some(&transcript), recording);
                     }
                 }
                 Err(e) => {
@@ -142,3 +380,99 @@ pub fn handle_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Conferenc
         ui.base.set_idle();
     });
 }
+
+/// Transcribe a recording that went to disk, window by window.
+///
+/// Each window is transcribed like a whole recording and its speaker turns
+/// shifted to the window's place; the models are released between windows.
+/// Sortformer numbers speakers per window, so the same person may get
+/// different labels in different windows unless their voice is enrolled.
+fn transcribe_spooled(
+    ctx: &Arc<AppContext>,
+    spooled: &mut SpooledRecording,
+    language: &str,
+    diarization_method: &str,
+    echo_cancellation: bool,
+    denoise: Option<&DenoiseSettings>,
+) -> anyhow::Result<ConferenceTranscript> {
+    let attribution = SpeakerAttribution::from_config(&ctx.config.lock().speaker_attribution);
+    let mut texts = Vec::new();
+    let mut turns = Vec::new();
+    let mut confidence = Vec::new();
+    let mut segments = Vec::new();
+
+    for window in spooled.windows() {
+        let window = window?;
+        let mic = if echo_cancellation {
+            cancel_echo(&window.mic, &window.loopback)
+        } else {
+            window.mic
+        };
+        let mic = maybe_denoise(&mic, denoise);
+        let loopback = maybe_denoise(&window.loopback, denoise);
+
+        let transcript = {
+            // Lock ordering: diarization before transcription
+            let mut engine_guard = ctx.diarization.lock();
+            let ts = ctx.transcription.lock();
+            ts.transcribe_conference(
+                &mic,
+                &loopback,
+                language,
+                diarization_method,
+                attribution,
+                Some(&mut *engine_guard),
+            )?
+        };
+        let offset = window.start as f64 / 16000.0;
+        turns.extend(transcript.turns.into_iter().map(|turn| SpeakerTurn {
+            start: turn.start + offset,
+            end: turn.end + offset,
+            ..turn
+        }));
+        segments.extend(transcript.segments.into_iter().map(|segment| TranscriptSegment {
+            start: segment.start + offset,
+            end: segment.end + offset,
+            ..segment
+        }));
+        confidence.extend(transcript.confidence);
+        if !transcript.text.is_empty() {
+            texts.push(transcript.text);
+        }
+    }
+
+    Ok(ConferenceTranscript {
+        text: texts.join(" "),
+        turns,
+        confidence,
+        segments,
+    })
+}
+
+/// Keep the speaker segments of the transcript in the new history entry.
+fn store_segments(ctx: &Arc<AppContext>, ui: &ConferenceUI, segments: Vec<TranscriptSegment>) {
+    let Some(id) = ui.base.result_entry_id.borrow().clone() else {
+        return;
+    };
+    let mut h = ctx.history.lock();
+    if let Some(entry) = h.entries.iter_mut().find(|e| e.id == id) {
+        entry.segments = segments;
+        if let Err(e) = save_history(&h) {
+            eprintln!("Помилка збереження історії: {}", e);
+        }
+    }
+}
+
+/// Add the title and tags of the session template to the new history entry.
+fn apply_template_metadata(ctx: &Arc<AppContext>, ui: &ConferenceUI, template: &SessionTemplate) {
+    let Some(id) = ui.base.result_entry_id.borrow().clone() else {
+        return;
+    };
+    let mut h = ctx.history.lock();
+    if let Some(entry) = h.entries.iter_mut().find(|e| e.id == id) {
+        template.apply_metadata(entry);
+        if let Err(e) = save_history(&h) {
+            eprintln!("Помилка збереження історії: {}", e);
+        }
+    }
+}
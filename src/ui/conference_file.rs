@@ -7,8 +7,11 @@
 use crate::app::context::AppContext;
 use crate::domain::traits::UIStateUpdater;
 use crate::infrastructure::recordings::{
-    ensure_recordings_dir, generate_recording_filename, recording_path, save_recording,
+    ensure_recordings_dir, generate_recording_filename, recording_path, save_recording, save_spooled_recording,
+    RecordingFormat,
 };
+use crate::recording::loudness::Normalization;
+use crate::recording::spool::SpooledRecording;
 use crate::ui::shared;
 use gtk4::glib;
 use std::sync::Arc;
@@ -17,14 +20,19 @@ use super::state::{ConferenceUI, RecordingContext};
 
 /// Start conference file recording (mic + loopback)
 pub fn handle_start(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &ConferenceUI) {
-    match ctx.audio.start_conference() {
+    // Recording to a file is all this mode does
+    if ctx.read_only_history() {
+        ui.base.set_status("Запис у файл вимкнено: історія лише для читання");
+        return;
+    }
+    match ctx.audio.start_conference(ctx.loopback_source().as_deref()) {
         Ok(()) => {
             rec.start_recording();
 
             ui.base.set_recording("Запис у файл...");
             ui.show_level_bars();
 
-            shared::start_timer_loop(rec, &ui.base);
+            shared::start_timer_loop(ctx, rec, &ui.base);
             shared::start_conference_level_loop(ctx, rec, ui);
         }
         Err(e) => {
@@ -41,10 +49,12 @@ pub fn handle_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Conferenc
     ui.base.set_processing("Збереження...");
     ui.hide_level_bars();
 
-    let recording = ctx.audio.stop_conference();
+    let (recording, spooled) = ctx.audio.stop_conference();
 
     // Calculate duration
-    let duration_secs = recording.duration_secs();
+    let duration_secs = spooled
+        .as_ref()
+        .map_or_else(|| recording.duration_secs(), SpooledRecording::duration_secs);
     let duration_mins = (duration_secs / 60.0).floor() as u32;
     let duration_remaining_secs = (duration_secs % 60.0).floor() as u32;
 
@@ -57,6 +67,7 @@ pub fn handle_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Conferenc
         return;
     }
 
+    let ctx = ctx.clone();
     let rec = rec.clone();
     let ui = ui.clone();
 
@@ -70,10 +81,37 @@ pub fn handle_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Conferenc
         }
 
         // Save audio file
-        let filename = generate_recording_filename();
+        let (filename_template, format) = {
+            let cfg = ctx.config.lock();
+            (
+                cfg.recording_filename_template.clone(),
+                RecordingFormat::parse(&cfg.recording_format),
+            )
+        };
+        let filename = generate_recording_filename(&filename_template, "conference_file", None, format);
         let file_path = recording_path(&filename);
 
-        match save_recording(&recording.mic_samples, &recording.loopback_samples, &file_path) {
+        let normalization = Normalization::from_config(&ctx.config.lock());
+        let saved = match spooled {
+            // Reading hours of audio back from disk would freeze the window
+            Some(mut spooled) => {
+                let (tx, rx) = async_channel::bounded(1);
+                let file_path = file_path.clone();
+                std::thread::spawn(move || {
+                    let _ = tx.send_blocking(save_spooled_recording(&mut spooled, &file_path, normalization));
+                });
+                rx.recv()
+                    .await
+                    .unwrap_or_else(|_| Err(anyhow::anyhow!("Збереження перервано")))
+            }
+            None => save_recording(
+                &recording.mic_samples,
+                &recording.loopback_samples,
+                &file_path,
+                normalization,
+            ),
+        };
+        match saved {
             Ok(()) => {
                 let status = format!(
                     "Збережено {:02}:{:02} -> {}",
@@ -83,6 +121,7 @@ pub fn handle_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Conferenc
                 );
                 ui.base.set_status(&status);
                 ui.base.set_result_text(&format!("Файл: {}", file_path.display()));
+                shared::queue_webdav_upload(&ctx, "conference", None, Some(&file_path));
             }
             Err(e) => {
                 ui.base.set_status(&format!("Помилка збереження: {}", e));
@@ -4,10 +4,14 @@
 //! that was previously duplicated in the button handler and hotkey handler.
 
 use crate::app::context::AppContext;
+use crate::domain::traits::UIStateUpdater;
+use crate::ui::a11y::{self, Urgency};
+use gtk4::prelude::*;
+use gtk4::{gio, ApplicationWindow};
 use std::sync::Arc;
 
-use super::state::{AppState, ConferenceUI, MicUI, RecordingContext, RecordingMode};
-use super::{conference, conference_file, mic};
+use super::state::{AppState, ConferenceUI, MicUI, RecordingContext, RecordingMode, UIContext};
+use super::{conference, conference_file, launch, mic};
 
 /// All mode-specific UI types bundled for dispatch.
 #[derive(Clone)]
@@ -16,16 +20,27 @@ pub struct ModeUIs {
     pub conference: ConferenceUI,
 }
 
+/// Status shown while the model loads and no start is queued.
+pub const LOADING_STATUS: &str = "Завантаження моделі...";
+
 /// Toggle recording: start if idle, stop if recording, ignore if processing.
 ///
-/// This is the single entry point for both the record button and the hotkey.
-/// It resolves the current mode from the combo box and config, then dispatches
-/// to the appropriate handler.
+/// This is the single entry point for the record button, the hotkey and the
+/// tray. It resolves the current mode from the combo box and config, then
+/// dispatches to the appropriate handler. While the model is loading, a toggle
+/// queues the start (or cancels the queued one) instead of being lost or
+/// firing later.
 pub fn toggle_recording(ctx: &Arc<AppContext>, rec: &RecordingContext, uis: &ModeUIs, mode_combo: &gtk4::ComboBoxText) {
     let mode = RecordingMode::resolve(mode_combo, ctx);
 
     match rec.state.get() {
-        AppState::Idle => start_recording(ctx, rec, uis, mode),
+        AppState::Loading => toggle_queued_start(ctx, rec, &uis.mic.base),
+        // The microphone is busy with a short dictation into a text field
+        AppState::Idle if rec.field_dictation.get() => {}
+        AppState::Idle => {
+            let mode = apply_workspace_preset(ctx, &uis.mic.base, mode_combo).unwrap_or(mode);
+            start_recording(ctx, rec, uis, mode);
+        }
         AppState::Recording => stop_recording(ctx, rec, uis, mode),
         AppState::Processing => {
             // Ignore toggle while processing
@@ -33,6 +48,63 @@ pub fn toggle_recording(ctx: &Arc<AppContext>, rec: &RecordingContext, uis: &Mod
     }
 }
 
+fn toggle_queued_start(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &UIContext) {
+    if !ctx.start_after_model_load() {
+        notify_loading(ui, "Модель ще завантажується. Спробуйте за мить");
+        return;
+    }
+    if rec.start_queued.replace(!rec.start_queued.get()) {
+        ui.set_status(LOADING_STATUS);
+        ui.button.set_label("Почати запис");
+        a11y::announce(&ui.status_label, "Запуск запису скасовано", Urgency::Polite);
+    } else {
+        ui.button.set_label("Скасувати запуск");
+        notify_loading(ui, "Модель ще завантажується. Запис почнеться автоматично");
+    }
+}
+
+/// Tell the user the model is not ready, also outside the window (e.g. after a hotkey).
+fn notify_loading(ui: &UIContext, message: &str) {
+    notify(ui, "model-loading", message);
+}
+
+/// Show `message` in the status line and, while the window is in the
+/// background, as a desktop notification replacing the previous `id` one.
+pub(super) fn notify(ui: &UIContext, id: &str, message: &str) {
+    ui.set_status(message);
+    a11y::announce(&ui.status_label, message, Urgency::Assertive);
+
+    let Some(window) = ui.button.root().and_downcast::<ApplicationWindow>() else {
+        return;
+    };
+    if window.is_active() {
+        return;
+    }
+    if let Some(app) = window.application() {
+        let notification = gio::Notification::new("Голосова диктовка");
+        notification.set_body(Some(message));
+        app.send_notification(Some(id), &notification);
+    }
+}
+
+/// Make the active workspace's preset current before a start.
+///
+/// Returns the mode the preset switched the combo box to, if it names one.
+fn apply_workspace_preset(
+    ctx: &Arc<AppContext>,
+    ui: &UIContext,
+    mode_combo: &gtk4::ComboBoxText,
+) -> Option<RecordingMode> {
+    let preset = ctx.select_workspace_preset()?;
+    let message = format!("Пресет робочого простору «{}»", preset.workspace);
+    eprintln!("{}", message);
+    a11y::announce(&ui.status_label, &message, Urgency::Polite);
+
+    let index = preset.recording_mode.as_deref().and_then(launch::mode_index)?;
+    mode_combo.set_active(Some(index));
+    Some(RecordingMode::resolve(mode_combo, ctx))
+}
+
 fn start_recording(ctx: &Arc<AppContext>, rec: &RecordingContext, uis: &ModeUIs, mode: RecordingMode) {
     match mode {
         RecordingMode::Mic => mic::handle_start(ctx, rec, &uis.mic),
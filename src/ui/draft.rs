@@ -0,0 +1,53 @@
+//! Draft of the result text.
+//!
+//! The result view is editable, and corrections made there are lost if the
+//! app quits before they are copied. Text set by the app leaves the buffer
+//! unmodified, so a modified buffer means the user's own edits: those are
+//! written to disk when the window closes or the app quits, and restored with
+//! a short notice when the window is built again.
+
+use crate::app::config::draft_path;
+use crate::app::draft::{clear_draft, load_draft, save_draft};
+use crate::ui::a11y::{self, Urgency};
+use gtk4::prelude::*;
+use gtk4::{Application, Label, TextView};
+
+/// Restore the saved draft into `result_text_view` and keep it saved on quit.
+pub fn setup_draft(app: &Application, result_text_view: &TextView, notice: &Label) {
+    let buffer = result_text_view.buffer();
+    match load_draft(&draft_path()) {
+        Ok(Some(text)) => {
+            buffer.set_text(&text);
+            // Still unsaved: it is written again on the next close
+            buffer.set_modified(true);
+            notice.set_visible(true);
+            a11y::announce(result_text_view, "Чернетку відновлено", Urgency::Polite);
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("{:#}", e),
+    }
+
+    let notice = notice.clone();
+    buffer.connect_changed(move |_| notice.set_visible(false));
+
+    let result_text_view = result_text_view.downgrade();
+    app.connect_shutdown(move |_| {
+        if let Some(result_text_view) = result_text_view.upgrade() {
+            persist_draft(&result_text_view);
+        }
+    });
+}
+
+/// Save the user's edits of the result, or drop the draft if there are none.
+pub fn persist_draft(result_text_view: &TextView) {
+    let buffer = result_text_view.buffer();
+    let result = if buffer.is_modified() {
+        let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+        save_draft(&draft_path(), &text)
+    } else {
+        clear_draft(&draft_path())
+    };
+    if let Err(e) = result {
+        eprintln!("{:#}", e);
+    }
+}
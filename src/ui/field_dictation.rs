@@ -0,0 +1,184 @@
+//! "Dictate here" for the app's own text fields.
+//!
+//! The history search and the speaker names would otherwise be the only text
+//! in the app that has to be typed. A microphone icon at the end of such a
+//! field records a short phrase with the loaded model and inserts the text at
+//! the cursor. The recording is transient: it is not saved, copied, pasted or
+//! added to history, and it stops by itself after [`MAX_SECS`] seconds.
+
+use crate::app::context::AppContext;
+use crate::domain::traits::Transcription;
+use crate::recording::core::WHISPER_SAMPLE_RATE;
+use crate::ui::a11y::{self, Urgency};
+use gtk4::prelude::*;
+use gtk4::{glib, Entry, EntryIconPosition};
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::state::{AppState, RecordingContext};
+
+/// A field takes a phrase, not a dictation.
+const MAX_SECS: u64 = 15;
+
+/// Shorter recordings are a click by mistake.
+const MIN_SAMPLES: usize = WHISPER_SAMPLE_RATE as usize / 2;
+
+const IDLE_ICON: &str = "audio-input-microphone-symbolic";
+const RECORDING_ICON: &str = "media-playback-stop-symbolic";
+const PROCESSING_ICON: &str = "content-loading-symbolic";
+
+const IDLE_TOOLTIP: &str = "Надиктувати сюди";
+
+/// Short recordings into text fields, sharing the microphone with the main recorder.
+#[derive(Clone)]
+pub struct FieldDictation {
+    ctx: Arc<AppContext>,
+    rec: RecordingContext,
+    /// Field being dictated into.
+    active: Rc<RefCell<Option<Entry>>>,
+    /// Bumped per recording, so the time limit does not stop a later one.
+    session: Rc<Cell<u64>>,
+}
+
+impl FieldDictation {
+    pub fn new(ctx: Arc<AppContext>, rec: RecordingContext) -> Self {
+        Self {
+            ctx,
+            rec,
+            active: Rc::new(RefCell::new(None)),
+            session: Rc::new(Cell::new(0)),
+        }
+    }
+
+    /// Add the "dictate here" icon to `entry`.
+    pub fn attach(&self, entry: &Entry) {
+        entry.set_secondary_icon_name(Some(IDLE_ICON));
+        entry.set_secondary_icon_tooltip_text(Some(IDLE_TOOLTIP));
+        entry.set_secondary_icon_activatable(true);
+
+        let dictation = self.clone();
+        entry.connect_icon_press(move |entry, position| {
+            if position == EntryIconPosition::Secondary {
+                dictation.toggle(entry);
+            }
+        });
+    }
+
+    fn toggle(&self, entry: &Entry) {
+        if self.rec.field_dictation.get() {
+            self.stop();
+        } else {
+            self.start(entry);
+        }
+    }
+
+    fn start(&self, entry: &Entry) {
+        if self.rec.state.get() != AppState::Idle {
+            report(entry, "Мікрофон зайнятий записом");
+            return;
+        }
+        if !self.ctx.is_model_loaded() {
+            report(entry, "Модель ще не завантажена");
+            return;
+        }
+        if let Err(e) = self.ctx.audio.start_mic() {
+            report(entry, &format!("Не вдалося увімкнути мікрофон: {}", e));
+            return;
+        }
+
+        self.rec.field_dictation.set(true);
+        self.active.replace(Some(entry.clone()));
+        let session = self.session.get() + 1;
+        self.session.set(session);
+        entry.set_secondary_icon_name(Some(RECORDING_ICON));
+        entry.set_secondary_icon_tooltip_text(Some("Зупинити диктовку"));
+        a11y::announce(entry, "Диктовка в поле почалася", Urgency::Assertive);
+
+        let dictation = self.clone();
+        glib::timeout_add_local_once(Duration::from_secs(MAX_SECS), move || {
+            if dictation.session.get() == session && dictation.active.borrow().is_some() {
+                dictation.stop();
+            }
+        });
+    }
+
+    fn stop(&self) {
+        let Some(entry) = self.active.take() else {
+            return;
+        };
+        let (samples, completion_rx) = self.ctx.audio.stop_mic();
+        entry.set_secondary_icon_name(Some(PROCESSING_ICON));
+        entry.set_secondary_icon_tooltip_text(Some("Розпізнавання..."));
+        entry.set_secondary_icon_activatable(false);
+
+        let ctx = self.ctx.clone();
+        let rec = self.rec.clone();
+        let language = ctx.language();
+        glib::spawn_future_local(async move {
+            if let Some(rx) = completion_rx {
+                let _ = rx.recv().await;
+            }
+
+            let (tx, rx) = async_channel::bounded::<anyhow::Result<String>>(1);
+            let ctx_for_thread = ctx.clone();
+            std::thread::spawn(move || {
+                let result = if samples.len() < MIN_SAMPLES {
+                    Err(anyhow::anyhow!("Запис закороткий"))
+                } else {
+                    ctx_for_thread.transcription.lock().transcribe(&samples, &language)
+                };
+                let _ = tx.send_blocking(result);
+            });
+
+            let result = rx.recv().await;
+            rec.field_dictation.set(false);
+            entry.set_secondary_icon_name(Some(IDLE_ICON));
+            entry.set_secondary_icon_tooltip_text(Some(IDLE_TOOLTIP));
+            entry.set_secondary_icon_activatable(true);
+
+            match result.map(|r| r.map(|text| field_text(&text))) {
+                Ok(Ok(text)) if text.is_empty() => report(&entry, "Не вдалося розпізнати мову"),
+                Ok(Ok(text)) => insert_at_cursor(&entry, &text),
+                Ok(Err(e)) => report(&entry, &format!("Помилка: {}", e)),
+                Err(_) => {}
+            }
+        });
+    }
+}
+
+/// Transcript as a field value: a search query or a name is not a sentence.
+fn field_text(transcript: &str) -> String {
+    transcript
+        .trim()
+        .trim_end_matches(['.', '!', '?', '…'])
+        .trim_end()
+        .to_string()
+}
+
+/// Insert `text` at the cursor, replacing the selection, separated by a
+/// space from a preceding word.
+fn insert_at_cursor(entry: &Entry, text: &str) {
+    if let Some((start, end)) = entry.selection_bounds() {
+        entry.delete_text(start, end);
+        entry.set_position(start);
+    }
+    let mut position = entry.position();
+    let before: String = entry.text().chars().take(position.max(0) as usize).collect();
+    let text = if before.is_empty() || before.ends_with(char::is_whitespace) {
+        text.to_string()
+    } else {
+        format!(" {}", text)
+    };
+    entry.insert_text(&text, &mut position);
+    entry.set_position(position);
+    entry.grab_focus_without_selecting();
+}
+
+/// Explain a failure in the icon tooltip, where the user clicked.
+fn report(entry: &Entry, message: &str) {
+    entry.error_bell();
+    entry.set_secondary_icon_tooltip_text(Some(message));
+    a11y::announce(entry, message, Urgency::Assertive);
+}
@@ -0,0 +1,63 @@
+//! Quick-pick popover for inline phonetic hints in the result view.
+//!
+//! Clicking on a hinted name (`Кравченко (або Кравчук?)`) opens a popover
+//! listing all spellings; choosing one replaces the whole hint.
+
+use crate::transcription::corrections::{hint_at, resolve_hint};
+use gtk4::prelude::*;
+use gtk4::{gdk, glib, Box as GtkBox, Button, GestureClick, Orientation, Popover, TextView};
+
+/// Attach the hint quick-pick handler to the result text view.
+pub fn setup_hint_picker(text_view: &TextView) {
+    let gesture = GestureClick::new();
+    let text_view_weak = text_view.downgrade();
+    gesture.connect_released(move |_, _, x, y| {
+        let text_view_weak = text_view_weak.clone();
+        // Let the click move the cursor first, then look for a hint under it.
+        glib::idle_add_local_once(move || {
+            if let Some(text_view) = text_view_weak.upgrade() {
+                show_picker_at_cursor(&text_view, x, y);
+            }
+        });
+    });
+    text_view.add_controller(gesture);
+}
+
+fn show_picker_at_cursor(text_view: &TextView, x: f64, y: f64) {
+    let buffer = text_view.buffer();
+    let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false).to_string();
+    let cursor = buffer.cursor_position().max(0) as usize;
+    let Some((byte_offset, _)) = text.char_indices().nth(cursor) else {
+        return;
+    };
+    let Some(hint) = hint_at(&text, byte_offset) else {
+        return;
+    };
+
+    let popover = Popover::new();
+    popover.set_parent(text_view);
+    popover.set_pointing_to(Some(&gdk::Rectangle::new(x as i32, y as i32, 1, 1)));
+
+    let choices_box = GtkBox::new(Orientation::Vertical, 4);
+    for choice in hint.choices() {
+        let button = Button::with_label(&choice);
+        button.add_css_class("flat");
+        let buffer = buffer.clone();
+        let hint = hint.clone();
+        let popover_weak = popover.downgrade();
+        button.connect_clicked(move |_| {
+            let current = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false).to_string();
+            // Text may have been edited since the popover opened
+            if current.get(hint.start..hint.end).is_some() && hint_at(&current, hint.start).as_ref() == Some(&hint) {
+                buffer.set_text(&resolve_hint(&current, &hint, &choice));
+            }
+            if let Some(p) = popover_weak.upgrade() {
+                p.popdown();
+            }
+        });
+        choices_box.append(&button);
+    }
+    popover.set_child(Some(&choices_box));
+    popover.connect_closed(|p| p.unparent());
+    popover.popup();
+}
@@ -0,0 +1,91 @@
+//! Launch requests from the command line.
+//!
+//! `voice-dictation --mode conference --start` opens the window in the given
+//! mode and starts recording, e.g. from a "Record meeting now" launcher. When
+//! the app is already running, the second process forwards the request to it
+//! as the `app.launch` action over D-Bus and exits.
+
+use super::state::{AppState, RecordingContext};
+use gtk4::prelude::*;
+use gtk4::{gio, glib, Application, ApplicationWindow, Button, ComboBoxText};
+
+/// Name of the application action carrying a [`LaunchRequest`].
+pub const LAUNCH_ACTION: &str = "launch";
+
+/// Mode and start flag requested on the command line.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LaunchRequest {
+    /// `recording_mode` config name; `None` keeps the current mode.
+    pub mode: Option<String>,
+    pub start: bool,
+}
+
+impl LaunchRequest {
+    /// Whether the command line asked for anything beyond opening the window.
+    pub fn is_empty(&self) -> bool {
+        self.mode.is_none() && !self.start
+    }
+
+    /// Action parameter: `(mode, start)`, with an empty mode for "keep".
+    pub fn to_variant(&self) -> glib::Variant {
+        (self.mode.clone().unwrap_or_default(), self.start).to_variant()
+    }
+
+    pub fn from_variant(variant: &glib::Variant) -> Option<Self> {
+        let (mode, start) = variant.get::<(String, bool)>()?;
+        Some(Self {
+            mode: (!mode.is_empty()).then_some(mode),
+            start,
+        })
+    }
+}
+
+/// Position of a `recording_mode` name in the mode combo box.
+pub(super) fn mode_index(mode: &str) -> Option<u32> {
+    match mode {
+        "dictation" => Some(0),
+        "conference" => Some(1),
+        "conference_file" => Some(2),
+        _ => None,
+    }
+}
+
+/// Register the `app.launch` action on the primary instance.
+///
+/// The action shows the window, switches the mode and, if asked, starts
+/// recording; while the model is still loading, the start is queued like a
+/// press of the record button. Requests arriving while recording are ignored,
+/// since switching modes mid-recording would stop the wrong recorder.
+pub fn setup_launch_action(
+    app: &Application,
+    window: &ApplicationWindow,
+    mode_combo: &ComboBoxText,
+    record_button: &Button,
+    rec_ctx: &RecordingContext,
+) {
+    let action = gio::SimpleAction::new(LAUNCH_ACTION, Some(&<(String, bool)>::static_variant_type()));
+    let window_weak = window.downgrade();
+    let mode_combo = mode_combo.clone();
+    let record_button = record_button.clone();
+    let rec_ctx = rec_ctx.clone();
+    action.connect_activate(move |_, parameter| {
+        let Some(request) = parameter.and_then(LaunchRequest::from_variant) else {
+            return;
+        };
+        if let Some(window) = window_weak.upgrade() {
+            window.present();
+        }
+        if matches!(rec_ctx.state.get(), AppState::Recording | AppState::Processing) {
+            eprintln!("Запит запуску проігноровано: запис уже триває");
+            return;
+        }
+        if let Some(index) = request.mode.as_deref().and_then(mode_index) {
+            mode_combo.set_active(Some(index));
+        }
+        // A second request during loading must not cancel the queued start
+        if request.start && !rec_ctx.start_queued.get() {
+            record_button.emit_clicked();
+        }
+    });
+    app.add_action(&action);
+}
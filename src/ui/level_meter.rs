@@ -0,0 +1,142 @@
+//! Calibrated input level meter.
+//!
+//! A level bar on a dBFS scale from [`LevelReading::FLOOR_DBFS`] to full
+//! scale, the peak level in numbers next to it, and a clipping indicator
+//! that lights up when the input hits 0 dBFS. The bar follows the peak,
+//! which falls back slowly, so short loud syllables stay visible.
+
+use crate::domain::types::LevelReading;
+use crate::ui::a11y;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Label, LevelBar, Orientation};
+use std::cell::Cell;
+
+/// Below this the bar shows the "too quiet" color; same limit as the level warning.
+const QUIET_DBFS: f32 = -40.0;
+
+/// Above this the bar shows the "close to clipping" color.
+const HOT_DBFS: f32 = -6.0;
+
+/// Clipping indicator class while lit.
+const CLIP_ACTIVE_CLASS: &str = "level-clip-active";
+
+thread_local! {
+    static CSS_LOADED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Level bar with a dBFS readout and a clipping indicator.
+#[derive(Clone)]
+pub struct LevelMeterView {
+    row: GtkBox,
+    bar: LevelBar,
+    dbfs_label: Label,
+    clip_label: Label,
+}
+
+impl LevelMeterView {
+    /// `name` is the accessible name of the bar, e.g. "Рівень звуку мікрофона".
+    pub fn new(name: &str) -> Self {
+        load_meter_css();
+
+        let bar = LevelBar::new();
+        bar.set_min_value(0.0);
+        bar.set_max_value(1.0);
+        bar.set_value(0.0);
+        bar.set_hexpand(true);
+        bar.set_valign(Align::Center);
+        // Color zones at fixed levels instead of fractions of the bar
+        bar.add_offset_value("low", LevelReading::scale(QUIET_DBFS) as f64);
+        bar.add_offset_value("high", LevelReading::scale(HOT_DBFS) as f64);
+        bar.set_tooltip_text(Some(
+            "Піковий рівень від −60 до 0 дБFS. Мовлення зазвичай між −30 і −10 дБFS",
+        ));
+        a11y::set_name(&bar, name);
+
+        let dbfs_label = Label::new(None);
+        dbfs_label.add_css_class("monospace");
+        dbfs_label.set_width_chars(7);
+        dbfs_label.set_xalign(1.0);
+        a11y::set_name(&dbfs_label, "Піковий рівень, дБFS");
+
+        let clip_label = Label::new(Some("CLIP"));
+        clip_label.add_css_class("level-clip");
+        clip_label.set_tooltip_text(Some("Світиться, коли звук досягає 0 дБFS і спотворюється"));
+        a11y::set_name(&clip_label, "Індикатор перевантаження");
+
+        let row = GtkBox::new(Orientation::Horizontal, 6);
+        row.set_size_request(200, -1);
+        row.append(&bar);
+        row.append(&dbfs_label);
+        row.append(&clip_label);
+
+        let meter = Self {
+            row,
+            bar,
+            dbfs_label,
+            clip_label,
+        };
+        meter.set(LevelReading::SILENT);
+        meter
+    }
+
+    pub fn widget(&self) -> &GtkBox {
+        &self.row
+    }
+
+    /// The bar itself, for coloring it by VAD decision.
+    pub fn bar(&self) -> &LevelBar {
+        &self.bar
+    }
+
+    pub fn set_visible(&self, visible: bool) {
+        self.row.set_visible(visible);
+    }
+
+    pub fn set(&self, reading: LevelReading) {
+        self.bar.set_value(LevelReading::scale(reading.peak_dbfs) as f64);
+        let text = if reading.peak_dbfs <= LevelReading::FLOOR_DBFS {
+            "—".to_string()
+        } else {
+            format!("{:.0} дБ", reading.peak_dbfs)
+        };
+        if self.dbfs_label.text() != text {
+            self.dbfs_label.set_text(&text);
+        }
+        self.dbfs_label.set_tooltip_text(Some(&format!(
+            "Пік {:.0} дБFS, RMS {:.0} дБFS",
+            reading.peak_dbfs, reading.rms_dbfs
+        )));
+        if reading.clipped {
+            self.clip_label.add_css_class(CLIP_ACTIVE_CLASS);
+        } else {
+            self.clip_label.remove_css_class(CLIP_ACTIVE_CLASS);
+        }
+    }
+}
+
+fn load_meter_css() {
+    if CSS_LOADED.with(|loaded| loaded.replace(true)) {
+        return;
+    }
+    let css_provider = gtk4::CssProvider::new();
+    css_provider.load_from_data(
+        r#"
+        .level-clip {
+            font-size: 10px;
+            font-weight: bold;
+            padding: 1px 4px;
+            border-radius: 3px;
+            color: alpha(currentColor, 0.3);
+        }
+        .level-clip.level-clip-active {
+            background-color: #cc0000;
+            color: #ffffff;
+        }
+        "#,
+    );
+    gtk4::style_context_add_provider_for_display(
+        &gtk4::gdk::Display::default().expect("Could not get default display"),
+        &css_provider,
+        gtk4::STYLE_PROVIDER_PRIORITY_APPLICATION,
+    );
+}
@@ -6,13 +6,24 @@
 
 use crate::app::context::AppContext;
 use crate::domain::traits::{HistoryRepository, Transcription, UIStateUpdater};
-use crate::domain::types::AudioSegment;
+use crate::domain::types::{AudioSegment, CaptureDeviceStatus};
 use crate::history::{save_history, HistoryEntry};
+use crate::infrastructure::perf::{timed, LiveThroughput};
+use crate::recording::denoise::DenoiseSettings;
+use crate::transcription::diarization::{
+    dominant_speaker, join_speaker_turns, mark_overlap, overlap_regions, speaker_labels,
+};
+use crate::transcription::word_attribution::SpeakerAttribution;
+use crate::transcription::TranscriptionService;
+use crate::ui::a11y::{self, Urgency};
+use crate::ui::captions::LiveCaptions;
 use crate::ui::shared::{self, maybe_denoise};
+use crate::vad::{create_vad, speech_at_start, trim_silence, SilenceTrim, VadConfig};
+use gtk4::glib;
 use gtk4::prelude::*;
-use gtk4::{glib, Label};
 use std::cell::{Cell, RefCell};
 use std::collections::{BTreeMap, HashMap};
+use std::ops::Range;
 use std::rc::Rc;
 use std::sync::Arc;
 
@@ -21,17 +32,28 @@ use super::state::{MicUI, RecordingContext};
 const WHISPER_SAMPLE_RATE: usize = 16000;
 const MIN_RECORDING_SAMPLES: usize = WHISPER_SAMPLE_RATE; // 1 second
 
-/// Segment indicator symbols
-const SEGMENT_PROCESSING: &str = "◐";
-const SEGMENT_COMPLETED: &str = "●";
+/// Shown when a dictation starts in the middle of speech
+const ONSET_CLIPPED_WARNING: &str =
+    "Готово! Схоже, перші слова обрізано — починайте говорити трохи після натискання клавіші";
+
+/// Transcribed segment text and its sample range in the recording.
+type SegmentText = (Range<usize>, String);
 
 // Thread-local counters for tracking segment completion
 thread_local! {
     static SEGMENTS_SENT: Cell<usize> = const { Cell::new(0) };
     static SEGMENTS_COMPLETED: Cell<usize> = const { Cell::new(0) };
+    /// Time spent transcribing the completed segments, stored with the entry
+    static SEGMENTS_PROCESSING: Cell<std::time::Duration> = const { Cell::new(std::time::Duration::ZERO) };
     static PROCESSING_CANCELLED: Cell<bool> = const { Cell::new(false) };
+    static LIVE_CAPTIONS: RefCell<Option<Rc<LiveCaptions>>> = const { RefCell::new(None) };
+    /// Segment texts in order, for speaker labelling after stop
+    static SEGMENT_TEXTS: RefCell<Vec<SegmentText>> = const { RefCell::new(Vec::new()) };
 }
 
+/// How long the caption overlay stays open after recording stops.
+const CAPTIONS_LINGER: std::time::Duration = std::time::Duration::from_secs(3);
+
 /// Start microphone recording (dictation or segmented depending on config).
 pub fn handle_start(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &MicUI) {
     // Check if model is loaded
@@ -54,6 +76,8 @@ pub fn handle_start(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &MicUI) {
                 // Reset segment completion counters
                 SEGMENTS_SENT.with(|c| c.set(0));
                 SEGMENTS_COMPLETED.with(|c| c.set(0));
+                SEGMENTS_PROCESSING.with(|c| c.set(std::time::Duration::ZERO));
+                SEGMENT_TEXTS.with(|t| t.borrow_mut().clear());
 
                 // Start segmentation monitor
                 let (segment_tx, segment_rx) = async_channel::unbounded::<AudioSegment>();
@@ -62,13 +86,29 @@ pub fn handle_start(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &MicUI) {
                     return;
                 }
 
-                spawn_segment_pipeline(ctx, ui, segment_rx);
+                // Timeline of recent VAD decisions under the level bar
+                if ctx.config.lock().use_vad {
+                    if let Some(decisions) = ctx.audio.subscribe_vad_decisions() {
+                        ui.speech_timeline.show();
+                        ui.speech_timeline.follow(decisions);
+                    }
+                }
+
+                let captions = ctx.translation_captions().then(|| {
+                    let parent = ui.base.button.root().and_downcast::<gtk4::Window>();
+                    LiveCaptions::start(ctx, parent.as_ref())
+                });
+                LIVE_CAPTIONS.with(|c| *c.borrow_mut() = captions.clone());
+
+                spawn_segment_pipeline(ctx, ui, segment_rx, captions);
             } else {
                 ui.base.set_recording("Запис...");
                 ui.show_level_bar();
+                // VAD only colors the level bar here; the recording is transcribed whole
+                ctx.audio.start_speech_detection();
             }
 
-            shared::start_timer_loop(rec, &ui.base);
+            shared::start_timer_loop(ctx, rec, &ui.base);
             start_level_loop(ctx, rec, ui);
 
             if use_segmentation {
@@ -100,12 +140,17 @@ fn start_level_loop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &MicUI) {
     let ctx_clone = ctx.clone();
     let rec_clone = rec.clone();
     let ui_clone = ui.clone();
+    let use_vad = ctx.config.lock().use_vad;
+    let device_status = RefCell::new(CaptureDeviceStatus::Active);
     glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
         if !rec_clone.is_recording() {
             return glib::ControlFlow::Break;
         }
-        let amplitude = ctx_clone.audio.mic_amplitude();
-        ui_clone.update_level(amplitude as f64);
+        let level = ctx_clone.audio.mic_level();
+        let is_speech = use_vad.then(|| ctx_clone.audio.is_speech_detected());
+        ui_clone.update_level(level, is_speech);
+        shared::report_device_status(&ui_clone.base, &device_status, ctx_clone.audio.mic_device_status());
+        ui_clone.update_level_warning(ctx_clone.audio.mic_level_warning());
         glib::ControlFlow::Continue
     });
 }
@@ -120,125 +165,390 @@ fn start_vad_loop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &MicUI) {
         }
         let is_speech = ctx_clone.audio.is_speech_detected();
         ui_clone.update_vad_indicator(is_speech);
+        if let Some(state) = ctx_clone.audio.noise_floor() {
+            ui_clone.update_noise_floor(&state);
+        }
         glib::ControlFlow::Continue
     });
 }
 
 /// Spawn the parallel segment transcription pipeline.
-fn spawn_segment_pipeline(ctx: &Arc<AppContext>, ui: &MicUI, segment_rx: async_channel::Receiver<AudioSegment>) {
+fn spawn_segment_pipeline(
+    ctx: &Arc<AppContext>,
+    ui: &MicUI,
+    segment_rx: async_channel::Receiver<AudioSegment>,
+    captions: Option<Rc<LiveCaptions>>,
+) {
     let language = ctx.language();
-    let denoise_enabled = ctx.denoise_enabled();
-
-    // Channel for transcription results: (segment_id, Result<text>)
-    let (result_tx, result_rx) = async_channel::unbounded::<(usize, Result<String, String>)>();
-
-    // Shared storage for segment indicator labels
-    let segment_labels: Rc<RefCell<HashMap<usize, Label>>> = Rc::new(RefCell::new(HashMap::new()));
+    let retries = ctx.config.lock().segment_retry_attempts;
+    let session = SegmentSession::new(ctx, ui, &language, ctx.denoise_settings());
+
+    // Channel for transcription results: (segment_id, sample range, Result<text>, processing time)
+    let (result_tx, result_rx) =
+        async_channel::unbounded::<(usize, Range<usize>, Result<String, String>, std::time::Duration)>();
+
+    // Channel for translated captions of completed segments
+    let (caption_tx, caption_rx) = async_channel::unbounded::<(usize, Result<String, String>)>();
+    let translate_segments = captions.is_some();
+    if let Some(captions) = captions.clone() {
+        glib::spawn_future_local(async move {
+            while let Ok((segment_id, result)) = caption_rx.recv().await {
+                captions.finalize(segment_id, result);
+            }
+        });
+    }
 
     // Spawn segment receiver that launches parallel transcriptions
     let ctx_for_segments = ctx.clone();
     let language_for_segments = language.clone();
     let result_tx_for_segments = result_tx.clone();
     let ui_for_segments = ui.clone();
-    let segment_labels_for_receiver = segment_labels.clone();
+    let session_for_segments = session.clone();
+    let receiving = Rc::new(Cell::new(true));
+    let receiving_for_segments = receiving.clone();
 
     glib::spawn_future_local(async move {
         while let Ok(segment) = segment_rx.recv().await {
             let segment_id = segment.segment_id;
             let segment_samples = segment.samples.clone();
+            let span = segment.start_sample..segment.start_sample + segment.samples.len();
             let ctx = ctx_for_segments.clone();
             let lang = language_for_segments.clone();
             let tx = result_tx_for_segments.clone();
+            let caption_tx = caption_tx.clone();
+            let denoise = session_for_segments.denoise.clone();
 
             SEGMENTS_SENT.with(|c| c.set(c.get() + 1));
+            session_for_segments
+                .throughput
+                .borrow_mut()
+                .segment_sent(segment_id, segment.end_time);
+            if let Some(ref captions) = captions {
+                captions.segment_sent(segment_id);
+            }
 
             let duration_secs = segment.end_time.duration_since(segment.start_time).as_secs_f32();
-            let duration_text = format!("{:.1}s", duration_secs);
-
-            let indicator = Label::new(Some(&format!("{} {}", SEGMENT_PROCESSING, duration_text)));
-            indicator.add_css_class("segment-processing");
-            ui_for_segments.segment_indicators_box.append(&indicator);
-            segment_labels_for_receiver.borrow_mut().insert(segment_id, indicator);
+            ui_for_segments.segment_progress.add(segment_id, duration_secs);
+            session_for_segments
+                .audio
+                .borrow_mut()
+                .insert(segment_id, segment.samples.clone());
 
             ui_for_segments.base.set_status(&format!("Сегмент {}...", segment_id));
 
             std::thread::spawn(move || {
-                let segment_samples = maybe_denoise(&segment_samples, denoise_enabled);
+                let segment_samples = maybe_denoise(&segment_samples, denoise.as_ref());
                 let ts = ctx.transcription.lock();
-                let result = ts.transcribe(&segment_samples, &lang).map_err(|e| e.to_string());
-                if let Ok(ref text) = result {
-                    if text.is_empty() {
-                        eprintln!(
-                            "Сегмент {} повернув порожній результат ({} семплів)",
-                            segment_id,
-                            segment_samples.len()
-                        );
-                    }
+                let (result, processing) =
+                    timed(|| transcribe_segment(&ts, segment_id, &segment_samples, &lang, retries));
+                let _ = tx.send_blocking((segment_id, span, result, processing));
+
+                if translate_segments {
+                    let translation = ts.translate(&segment_samples, &lang).map_err(|e| e.to_string());
+                    let _ = caption_tx.send_blocking((segment_id, translation));
                 }
-                let _ = tx.send_blocking((segment_id, result));
             });
         }
+        receiving_for_segments.set(false);
+    });
+
+    // Refresh the backlog between results, until the last segment is transcribed
+    let session_for_throughput = session.clone();
+    glib::timeout_add_local(std::time::Duration::from_secs(1), move || {
+        let throughput = session_for_throughput.throughput.borrow();
+        session_for_throughput.ui.update_throughput(
+            throughput.real_time_factor(),
+            throughput.backlog(std::time::Instant::now()),
+        );
+        if receiving.get() || throughput.pending_count() > 0 {
+            glib::ControlFlow::Continue
+        } else {
+            glib::ControlFlow::Break
+        }
     });
 
     // Process results in order using BTreeMap
-    let segment_labels_for_results = segment_labels.clone();
-    let ui_for_results = ui.clone();
     glib::spawn_future_local(async move {
-        let mut accumulated_text = String::new();
         let mut next_segment_id: usize = 1;
-        let mut pending_results: BTreeMap<usize, Result<String, String>> = BTreeMap::new();
-        let mut completed_count: usize = 0;
-        let mut failed_count: usize = 0;
+        let mut pending_results: BTreeMap<usize, (Range<usize>, Result<String, String>)> = BTreeMap::new();
 
-        while let Ok((segment_id, result)) = result_rx.recv().await {
-            completed_count += 1;
+        while let Ok((segment_id, span, result, processing)) = result_rx.recv().await {
+            session.completed_count.set(session.completed_count.get() + 1);
             SEGMENTS_COMPLETED.with(|c| c.set(c.get() + 1));
-
-            let is_error = result.is_err();
-
-            if let Some(label) = segment_labels_for_results.borrow().get(&segment_id) {
-                let current_text = label.text();
-                let duration = current_text.split_whitespace().last().unwrap_or("");
-                if is_error {
-                    label.set_label(&format!("✗ {}", duration));
-                    label.remove_css_class("segment-processing");
-                    label.add_css_class("segment-error");
-                } else {
-                    label.set_label(&format!("{} {}", SEGMENT_COMPLETED, duration));
-                    label.remove_css_class("segment-processing");
-                    label.add_css_class("segment-completed");
+            SEGMENTS_PROCESSING.with(|c| c.set(c.get() + processing));
+            session.throughput.borrow_mut().segment_done(
+                segment_id,
+                span.len() as f64 / WHISPER_SAMPLE_RATE as f64,
+                processing,
+            );
+
+            match &result {
+                Ok(_) => {
+                    session.ui.segment_progress.complete(segment_id);
+                    session.audio.borrow_mut().remove(&segment_id);
                 }
+                Err(err) => session.ui.segment_progress.fail(segment_id, err),
             }
 
-            pending_results.insert(segment_id, result);
+            pending_results.insert(segment_id, (span, result));
 
-            while let Some(result) = pending_results.remove(&next_segment_id) {
+            while let Some((span, result)) = pending_results.remove(&next_segment_id) {
                 match result {
-                    Ok(text) if !text.is_empty() => {
-                        accumulated_text.push_str(&text);
-                        accumulated_text.push(' ');
-                        ui_for_results.base.set_result_text(&accumulated_text);
-                    }
+                    Ok(text) if !text.is_empty() => session.push_text(span, text),
                     Err(ref err) => {
-                        failed_count += 1;
                         eprintln!("Помилка транскрипції сегменту {}: {}", next_segment_id, err);
+                        session.push_failed(next_segment_id, span);
                     }
                     _ => {} // Ok but empty — already logged by the worker thread
                 }
                 next_segment_id += 1;
             }
 
-            let status = if failed_count > 0 {
-                format!(
-                    "Транскрибовано: {} сегментів ({} з помилками)",
-                    completed_count, failed_count
-                )
-            } else {
-                format!("Транскрибовано: {} сегментів", completed_count)
+            session.update_status();
+        }
+    });
+}
+
+/// Transcript marker for a segment whose text is missing.
+fn failed_segment_marker(segment_id: usize) -> String {
+    format!("[сегмент {} не розпізнано]", segment_id)
+}
+
+/// Transcribe one segment, retrying up to `retries` more times on error.
+fn transcribe_segment(
+    ts: &TranscriptionService,
+    segment_id: usize,
+    samples: &[f32],
+    language: &str,
+    retries: u32,
+) -> Result<String, String> {
+    let mut attempt = 0;
+    loop {
+        match ts.transcribe(samples, language) {
+            Ok(text) => {
+                if text.is_empty() {
+                    eprintln!(
+                        "Сегмент {} повернув порожній результат ({} семплів)",
+                        segment_id,
+                        samples.len()
+                    );
+                }
+                return Ok(text);
+            }
+            Err(e) if attempt < retries => {
+                attempt += 1;
+                eprintln!(
+                    "Сегмент {}: помилка транскрипції, повтор {}/{}: {}",
+                    segment_id, attempt, retries, e
+                );
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+/// Continuous-mode transcript being assembled, shared with the retry action.
+///
+/// A failed segment leaves a marker in the text; a successful retry
+/// replaces the marker with the segment's text.
+#[derive(Clone)]
+struct SegmentSession {
+    ctx: Arc<AppContext>,
+    ui: MicUI,
+    language: String,
+    denoise: Option<DenoiseSettings>,
+    text: Rc<RefCell<String>>,
+    completed_count: Rc<Cell<usize>>,
+    failed_count: Rc<Cell<usize>>,
+    /// Audio of segments not transcribed yet, kept for retries
+    audio: Rc<RefCell<HashMap<usize, Vec<f32>>>>,
+    /// Real-time factor and backlog, shown next to the segments
+    throughput: Rc<RefCell<LiveThroughput>>,
+}
+
+impl SegmentSession {
+    fn new(ctx: &Arc<AppContext>, ui: &MicUI, language: &str, denoise: Option<DenoiseSettings>) -> Self {
+        Self {
+            ctx: ctx.clone(),
+            ui: ui.clone(),
+            language: language.to_string(),
+            denoise,
+            text: Rc::new(RefCell::new(String::new())),
+            completed_count: Rc::new(Cell::new(0)),
+            failed_count: Rc::new(Cell::new(0)),
+            audio: Rc::new(RefCell::new(HashMap::new())),
+            throughput: Rc::new(RefCell::new(LiveThroughput::new())),
+        }
+    }
+
+    fn push_text(&self, span: Range<usize>, text: String) {
+        let mut accumulated = self.text.borrow_mut();
+        accumulated.push_str(&text);
+        accumulated.push(' ');
+        self.ui.base.set_result_text(&accumulated);
+        SEGMENT_TEXTS.with(|t| t.borrow_mut().push((span, text)));
+    }
+
+    fn push_failed(&self, segment_id: usize, span: Range<usize>) {
+        self.failed_count.set(self.failed_count.get() + 1);
+        self.push_text(span.clone(), failed_segment_marker(segment_id));
+        self.offer_retry(segment_id, span);
+    }
+
+    fn offer_retry(&self, segment_id: usize, span: Range<usize>) {
+        let session = self.clone();
+        self.ui
+            .segment_progress
+            .set_retry(segment_id, move || session.retry(segment_id, span.clone()));
+    }
+
+    /// Transcribe a failed segment again, once.
+    fn retry(&self, segment_id: usize, span: Range<usize>) {
+        let Some(samples) = self.audio.borrow().get(&segment_id).cloned() else {
+            return;
+        };
+        SEGMENTS_SENT.with(|c| c.set(c.get() + 1));
+        self.ui.base.set_status(&format!("Повтор сегменту {}...", segment_id));
+
+        let (tx, rx) = async_channel::bounded(1);
+        let ctx = self.ctx.clone();
+        let language = self.language.clone();
+        let denoise = self.denoise.clone();
+        std::thread::spawn(move || {
+            let samples = maybe_denoise(&samples, denoise.as_ref());
+            let ts = ctx.transcription.lock();
+            let _ = tx.send_blocking(transcribe_segment(&ts, segment_id, &samples, &language, 0));
+        });
+
+        let session = self.clone();
+        glib::spawn_future_local(async move {
+            let Ok(result) = rx.recv().await else {
+                return;
             };
-            ui_for_results.base.set_status(&status);
+            SEGMENTS_COMPLETED.with(|c| c.set(c.get() + 1));
+            match result {
+                Ok(text) => {
+                    session.ui.segment_progress.complete(segment_id);
+                    session.audio.borrow_mut().remove(&segment_id);
+                    session.failed_count.set(session.failed_count.get().saturating_sub(1));
+                    session.replace_marker(segment_id, &span, text);
+                }
+                Err(err) => {
+                    eprintln!("Повтор сегменту {} не вдався: {}", segment_id, err);
+                    session.ui.segment_progress.fail(segment_id, &err);
+                    session.offer_retry(segment_id, span);
+                }
+            }
+            session.update_status();
+        });
+    }
+
+    fn replace_marker(&self, segment_id: usize, span: &Range<usize>, text: String) {
+        let marker = format!("{} ", failed_segment_marker(segment_id));
+        let replacement = if text.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", text)
+        };
+        let mut accumulated = self.text.borrow_mut();
+        *accumulated = accumulated.replacen(&marker, &replacement, 1);
+        self.ui.base.set_result_text(&accumulated);
+
+        SEGMENT_TEXTS.with(|t| {
+            let mut texts = t.borrow_mut();
+            if let Some(index) = texts.iter().position(|(s, _)| s == span) {
+                if text.is_empty() {
+                    texts.remove(index);
+                } else {
+                    texts[index].1 = text;
+                }
+            }
+        });
+    }
+
+    fn update_status(&self) {
+        let completed = self.completed_count.get();
+        let failed = self.failed_count.get();
+        let status = if failed > 0 {
+            format!("Транскрибовано: {} сегментів ({} з помилками)", completed, failed)
+        } else {
+            format!("Транскрибовано: {} сегментів", completed)
+        };
+        self.ui.base.set_status(&status);
+    }
+}
+
+/// Transcribe a whole mic recording, labelled by speaker if enabled and Sortformer is loaded.
+///
+/// Returns the text, the speakers heard and the confidence of each speaker
+/// label (both empty without diarization).
+fn transcribe_recording(
+    ctx: &AppContext,
+    samples: &[f32],
+    language: &str,
+    diarize: bool,
+) -> anyhow::Result<(String, Vec<String>, Vec<f32>)> {
+    if diarize {
+        let attribution = SpeakerAttribution::from_config(&ctx.config.lock().speaker_attribution);
+        // Lock ordering: diarization before transcription.
+        let mut engine = ctx.diarization.lock();
+        if engine.is_available() {
+            let ts = ctx.transcription.lock();
+            return ts.transcribe_diarized(samples, language, attribution, &mut engine);
         }
+    }
+    let ts = ctx.transcription.lock();
+    Ok((ts.transcribe(samples, language)?, Vec::new(), Vec::new()))
+}
+
+/// Label continuous-mode text by speaker.
+///
+/// Diarizes the whole recording and attributes each transcribed segment to
+/// the speaker who talks most within it. Returns `None` if Sortformer is not
+/// loaded or finds no speakers, leaving the unlabelled text as is.
+async fn label_segments_by_speaker(ctx: &Arc<AppContext>, samples: Vec<f32>) -> Option<(String, Vec<String>)> {
+    let (tx, rx) = async_channel::bounded(1);
+    let ctx_for_thread = ctx.clone();
+    std::thread::spawn(move || {
+        let mut engine = ctx_for_thread.diarization.lock();
+        let result = if engine.is_available() {
+            engine.diarize(&samples).map(|segments| {
+                let names = engine.speaker_names(&samples, &segments);
+                Some((segments, names))
+            })
+        } else {
+            Ok(None)
+        };
+        let _ = tx.send_blocking(result);
     });
+
+    let (segments, names) = match rx.recv().await {
+        Ok(Ok(Some(result))) => result,
+        Ok(Err(e)) => {
+            eprintln!("Помилка diarization: {}", e);
+            return None;
+        }
+        _ => return None,
+    };
+
+    let to_secs = |sample: usize| sample as f64 / WHISPER_SAMPLE_RATE as f64;
+    let overlaps = overlap_regions(&segments);
+    let pieces: Vec<(Option<usize>, String)> = SEGMENT_TEXTS.with(|t| {
+        t.borrow()
+            .iter()
+            .map(|(span, text)| {
+                let (start, end) = (to_secs(span.start), to_secs(span.end));
+                let speaker = dominant_speaker(&segments, start, end);
+                (speaker, mark_overlap(text.clone(), &overlaps, start, end))
+            })
+            .collect()
+    });
+
+    let speakers = speaker_labels(pieces.iter().filter_map(|(speaker, _)| *speaker), &names);
+    if speakers.is_empty() {
+        return None;
+    }
+    Some((join_speaker_turns(&pieces, &names), speakers))
 }
 
 /// Handle stop for plain dictation (no segmentation).
@@ -246,6 +556,7 @@ fn handle_simple_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &MicUI)
     ui.base.set_processing("Обробка...");
     ui.hide_level_bar();
 
+    ctx.audio.stop_speech_detection();
     let (samples, completion_rx) = ctx.audio.stop_mic();
 
     let duration_secs = samples.len() as f32 / WHISPER_SAMPLE_RATE as f32;
@@ -260,44 +571,75 @@ fn handle_simple_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &MicUI)
     let rec = rec.clone();
     let ui = ui.clone();
     let language = ctx.language();
-    let denoise_enabled = ctx.denoise_enabled();
+    let denoise = ctx.denoise_settings();
+    let mic_diarization = ctx.mic_diarization();
+    let raw_samples = ctx.save_dictation_recordings().then(|| samples.clone());
 
     glib::spawn_future_local(async move {
         if let Some(rx) = completion_rx {
             let _ = rx.recv().await;
         }
 
-        let (tx, rx) = async_channel::bounded::<anyhow::Result<String>>(1);
+        let (tx, rx) = async_channel::bounded::<(
+            anyhow::Result<(String, Vec<String>, Vec<f32>)>,
+            bool,
+            std::time::Duration,
+        )>(1);
 
         let ctx_for_thread = ctx.clone();
         let language_for_thread = language.clone();
+        let vad_config = ctx.vad_config();
+        let silence_trim = ctx.dictation_silence_trim();
         std::thread::spawn(move || {
-            let result = if samples.len() < MIN_RECORDING_SAMPLES {
-                Err(anyhow::anyhow!("Запис закороткий"))
+            let onset_clipped = clipped_onset(&vad_config, &samples);
+            let (result, processing) = if samples.len() < MIN_RECORDING_SAMPLES {
+                (Err(anyhow::anyhow!("Запис закороткий")), std::time::Duration::ZERO)
             } else {
-                let samples = maybe_denoise(&samples, denoise_enabled);
-                let ts = ctx_for_thread.transcription.lock();
-                ts.transcribe(&samples, &language_for_thread)
+                let samples = match silence_trim {
+                    Some(trim) => without_silence(&vad_config, samples, &trim),
+                    None => samples,
+                };
+                let samples = maybe_denoise(&samples, denoise.as_ref());
+                timed(|| transcribe_recording(&ctx_for_thread, &samples, &language_for_thread, mic_diarization))
             };
-            let _ = tx.send_blocking(result);
+            let _ = tx.send_blocking((result, onset_clipped, processing));
         });
 
-        if let Ok(result) = rx.recv().await {
+        if let Ok((result, onset_clipped, processing)) = rx.recv().await {
             match result {
-                Ok(text) => {
+                Ok((text, speakers, speaker_confidence)) => {
                     if text.is_empty() {
                         ui.base.set_status("Не вдалося розпізнати мову");
                     } else {
+                        let recording_file = match raw_samples {
+                            Some(samples) => shared::save_dictation_recording(&ctx, samples, "dictation").await,
+                            None => None,
+                        };
                         shared::handle_post_transcription(
                             &ctx,
                             &ui.base,
                             &text,
                             &language,
                             duration_secs,
-                            None,
-                            vec![],
+                            recording_file.clone(),
+                            speakers,
+                            Vec::new(),
+                            Vec::new(),
+                            speaker_confidence,
+                            ctx.audio.mic_audio_quality(),
+                            Some(processing),
                         )
                         .await;
+                        shared::queue_webdav_upload(
+                            &ctx,
+                            "dictation",
+                            Some(&text),
+                            recording_file.as_deref().map(std::path::Path::new),
+                        );
+                        if onset_clipped {
+                            ui.base.set_status(ONSET_CLIPPED_WARNING);
+                            a11y::announce(&ui.base.status_label, ONSET_CLIPPED_WARNING, Urgency::Polite);
+                        }
                     }
                 }
                 Err(e) => {
@@ -311,11 +653,43 @@ fn handle_simple_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &MicUI)
     });
 }
 
+/// Whether the dictation starts in the middle of speech.
+///
+/// Runs in a worker thread: VAD detectors are not `Send`. A failed check
+/// only costs the warning.
+fn clipped_onset(vad_config: &VadConfig, samples: &[f32]) -> bool {
+    let check = create_vad(vad_config).and_then(|vad| speech_at_start(vad.as_ref(), samples));
+    match check {
+        Ok(clipped) => clipped,
+        Err(e) => {
+            eprintln!("Перевірка початку запису не вдалася: {}", e);
+            false
+        }
+    }
+}
+
+/// The dictation without long silence, or as recorded if the VAD fails.
+///
+/// Runs in a worker thread, like [`clipped_onset`].
+fn without_silence(vad_config: &VadConfig, samples: Vec<f32>, trim: &SilenceTrim) -> Vec<f32> {
+    match create_vad(vad_config).and_then(|vad| trim_silence(vad.as_ref(), &samples, trim)) {
+        Ok(trimmed) => trimmed,
+        Err(e) => {
+            eprintln!("Не вдалося прибрати тишу із запису: {}", e);
+            samples
+        }
+    }
+}
+
 /// Handle stop for segmented (continuous) mode.
 fn handle_segmented_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &MicUI) {
     // Reset cancel flag
     PROCESSING_CANCELLED.with(|c| c.set(false));
 
+    if let Some(captions) = LIVE_CAPTIONS.with(|c| c.borrow().clone()) {
+        captions.stop_partials();
+    }
+
     // Show cancel button instead of disabling
     ui.base.button.set_label("Скасувати очікування");
     ui.base.button.remove_css_class("destructive-action");
@@ -340,6 +714,7 @@ fn handle_segmented_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Mic
     let (final_samples, completion_rx) = ctx.audio.stop_mic();
 
     let duration_secs = final_samples.len() as f32 / WHISPER_SAMPLE_RATE as f32;
+    let raw_samples = ctx.save_dictation_recordings().then(|| final_samples.clone());
 
     let ctx = ctx.clone();
     let rec = rec.clone();
@@ -393,7 +768,17 @@ fn handle_segmented_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Mic
         ui.base.button.disconnect(cancel_handler_id);
         ui.base.button.remove_css_class("warning");
 
-        let final_text = ui.base.get_result_text();
+        let mut final_text = ui.base.get_result_text();
+        let mut speakers = Vec::new();
+
+        if !final_text.is_empty() && !was_cancelled && ctx.mic_diarization() {
+            ui.base.set_status("Розпізнавання мовців...");
+            if let Some((labelled, found)) = label_segments_by_speaker(&ctx, final_samples).await {
+                ui.base.set_result_text(&labelled);
+                final_text = labelled;
+                speakers = found;
+            }
+        }
 
         if !final_text.is_empty() {
             if was_cancelled {
@@ -408,9 +793,29 @@ fn handle_segmented_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Mic
                     .set_status(&format!("Тайм-аут обробки (оброблено {}/{})", completed, sent));
             } else {
                 ui.base.set_status("Готово!");
+                a11y::announce(&ui.base.status_label, "Транскрипцію готово", Urgency::Polite);
             }
 
-            let entry = HistoryEntry::new(final_text, duration_secs, language);
+            let recording_file = match raw_samples {
+                Some(samples) => shared::save_dictation_recording(&ctx, samples, "continuous").await,
+                None => None,
+            };
+            shared::queue_webdav_upload(
+                &ctx,
+                "continuous",
+                Some(&final_text),
+                recording_file.as_deref().map(std::path::Path::new),
+            );
+
+            let mut entry =
+                HistoryEntry::new_with_recording(final_text, duration_secs, language, recording_file, speakers);
+            entry.audio_quality = ctx.audio.mic_audio_quality();
+            entry.model = shared::loaded_model_label(&ctx);
+            // Segments left untranscribed would understate the time
+            entry.processing_secs =
+                (!was_cancelled && !was_timed_out).then(|| SEGMENTS_PROCESSING.with(|c| c.get()).as_secs_f32());
+            ui.base
+                .set_result_entry((!entry.speakers.is_empty()).then(|| entry.id.clone()));
             let mut h = ctx.history.lock();
             h.add(entry);
             if let Err(e) = save_history(&h) {
@@ -429,5 +834,10 @@ fn handle_segmented_stop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &Mic
 
         ui.segment_row.set_visible(false);
         ui.clear_segment_indicators();
+
+        // Keep the last captions readable for a moment before closing the overlay
+        if let Some(captions) = LIVE_CAPTIONS.with(|c| c.borrow_mut().take()) {
+            glib::timeout_add_local_once(CAPTIONS_LINGER, move || captions.close());
+        }
     });
 }
@@ -1,19 +1,37 @@
+pub(crate) mod a11y;
+mod captions;
 pub mod conference;
 pub mod conference_file;
 mod dispatch;
+mod draft;
+pub mod field_dictation;
+mod hints;
+pub mod launch;
+pub(crate) mod level_meter;
 pub mod mic;
+mod segment_progress;
 pub(crate) mod shared;
+mod speech_timeline;
 pub mod state;
 mod widgets;
 
 use dispatch::ModeUIs;
-use state::{ConferenceUI, MicUI, RecordingContext, UIContext};
+use field_dictation::FieldDictation;
+use state::{AppState, ConferenceUI, MicUI, RecordingContext, UIContext};
 use widgets::build_main_widgets;
 
 use crate::app::context::AppContext;
-use crate::dialogs::{show_history_dialog, show_model_dialog, show_settings_dialog};
+use crate::dialogs::help::topics::HelpLink;
+use crate::dialogs::settings::show_settings_dialog_at;
+use crate::dialogs::{
+    show_help_dialog, show_history_dialog, show_model_dialog, show_onboarding_dialog, show_rename_speakers_dialog,
+    show_settings_dialog, show_subtitle_dialog,
+};
 use crate::domain::traits::Transcription;
-use crate::domain::types::SharedHistory;
+use crate::domain::types::{rename_speaker_labels, LevelReading, SharedHistory};
+use crate::infrastructure::failures::{self, Failure};
+use crate::infrastructure::remote::RemoteCommand;
+use crate::transcription::corrections::strip_hints;
 use gtk4::prelude::*;
 use gtk4::{glib, Application, ApplicationWindow, Button, TextView};
 use parking_lot::Mutex;
@@ -63,21 +81,32 @@ pub fn build_ui(app: &Application, ctx: Arc<AppContext>) {
 
     // Create context structs for recording modes
     let rec_ctx = RecordingContext::new();
+    let field_dictation = FieldDictation::new(ctx.clone(), rec_ctx.clone());
     let ui_ctx = UIContext::new(
         w.record_button.clone(),
         w.status_label.clone(),
         w.result_text_view.clone(),
         w.timer_label.clone(),
         w.spinner.clone(),
+        w.speakers_button.clone(),
     );
     let mic_ui = MicUI::new(
         ui_ctx.clone(),
         w.level_bar.clone(),
+        w.level_warning_label.clone(),
+        w.speech_timeline.clone(),
         w.vad_indicator.clone(),
-        w.segment_indicators_box.clone(),
+        w.segment_progress.clone(),
         w.segment_row.clone(),
+        w.throughput_label.clone(),
+    );
+    let conference_ui = ConferenceUI::new(
+        ui_ctx,
+        w.mic_level_bar.clone(),
+        w.loopback_level_bar.clone(),
+        w.speaker_now_label.clone(),
+        w.template_combo.clone(),
     );
-    let conference_ui = ConferenceUI::new(ui_ctx, w.mic_level_bar.clone(), w.loopback_level_bar.clone());
 
     let mode_uis = ModeUIs {
         mic: mic_ui.clone(),
@@ -85,8 +114,11 @@ pub fn build_ui(app: &Application, ctx: Arc<AppContext>) {
     };
 
     setup_record_button(ctx.clone(), rec_ctx.clone(), mode_uis.clone(), w.mode_combo.clone());
+    launch::setup_launch_action(app, &window, &w.mode_combo, &w.record_button, &rec_ctx);
 
     setup_copy_button(&w.copy_button, &w.result_text_view);
+    setup_speakers_button(&window, &history, &mic_ui.base, &field_dictation);
+    hints::setup_hint_picker(&w.result_text_view);
 
     // Models button
     let window_weak = window.downgrade();
@@ -101,29 +133,89 @@ pub fn build_ui(app: &Application, ctx: Arc<AppContext>) {
     // History button
     let window_weak = window.downgrade();
     let history_for_button = history.clone();
+    let config_for_history = config.clone();
+    let dictation_for_history = field_dictation.clone();
     w.history_button.connect_clicked(move |_| {
         if let Some(window) = window_weak.upgrade() {
-            show_history_dialog(&window, history_for_button.clone());
+            show_history_dialog(
+                &window,
+                history_for_button.clone(),
+                config_for_history.clone(),
+                &dictation_for_history,
+            );
+        }
+    });
+
+    // Subtitles button
+    let window_weak = window.downgrade();
+    let ctx_for_subtitles = ctx.clone();
+    w.subtitles_button.connect_clicked(move |_| {
+        if let Some(window) = window_weak.upgrade() {
+            show_subtitle_dialog(&window, ctx_for_subtitles.clone());
         }
     });
 
     // Settings button
     let window_weak = window.downgrade();
     let config_for_settings = config.clone();
+    let history_for_settings = history.clone();
     let reload_hotkeys_tx_for_settings = reload_hotkeys_tx.clone();
     w.settings_button.connect_clicked(move |_| {
         if let Some(window) = window_weak.upgrade() {
             show_settings_dialog(
                 &window,
                 config_for_settings.clone(),
+                history_for_settings.clone(),
                 reload_hotkeys_tx_for_settings.clone(),
             );
         }
     });
 
+    // Help button: topic buttons open settings or models
+    let window_weak = window.downgrade();
+    let config_for_help = config.clone();
+    let history_for_help = history.clone();
+    let transcription_for_help = transcription.clone();
+    let reload_hotkeys_tx_for_help = reload_hotkeys_tx.clone();
+    w.help_button.connect_clicked(move |_| {
+        let Some(window) = window_weak.upgrade() else {
+            return;
+        };
+        let window_for_link = window.downgrade();
+        let config = config_for_help.clone();
+        let history = history_for_help.clone();
+        let transcription = transcription_for_help.clone();
+        let reload_hotkeys_tx = reload_hotkeys_tx_for_help.clone();
+        show_help_dialog(&window, move |link| {
+            let Some(window) = window_for_link.upgrade() else {
+                return;
+            };
+            match link {
+                HelpLink::Settings(section) => show_settings_dialog_at(
+                    &window,
+                    config.clone(),
+                    history.clone(),
+                    reload_hotkeys_tx.clone(),
+                    Some(section),
+                ),
+                HelpLink::Models => show_model_dialog(&window, config.clone(), transcription.clone()),
+            }
+        });
+    });
+
     window.set_child(Some(&w.main_box));
+    setup_keyboard_shortcuts(&window, &w);
 
-    window.connect_close_request(|window| {
+    // Kiosk mode leaves no transcripts on disk, drafts included
+    let keep_draft = !config.lock().read_only_history;
+    if keep_draft {
+        draft::setup_draft(app, &w.result_text_view, &w.draft_label);
+    }
+    let result_text_view_for_close = w.result_text_view.clone();
+    window.connect_close_request(move |window| {
+        if keep_draft {
+            draft::persist_draft(&result_text_view_for_close);
+        }
         window.hide();
         glib::Propagation::Stop
     });
@@ -143,10 +235,17 @@ pub fn build_ui(app: &Application, ctx: Arc<AppContext>) {
     // Listen for "open history dialog" signal from tray
     let window_for_history = window.downgrade();
     let history_for_tray = history.clone();
+    let config_for_history_tray = config.clone();
+    let dictation_for_history_tray = field_dictation.clone();
     glib::spawn_future_local(async move {
         while open_history_rx.recv().await.is_ok() {
             if let Some(window) = window_for_history.upgrade() {
-                show_history_dialog(&window, history_for_tray.clone());
+                show_history_dialog(
+                    &window,
+                    history_for_tray.clone(),
+                    config_for_history_tray.clone(),
+                    &dictation_for_history_tray,
+                );
             }
         }
     });
@@ -154,11 +253,17 @@ pub fn build_ui(app: &Application, ctx: Arc<AppContext>) {
     // Listen for "open settings dialog" signal from tray
     let window_for_settings = window.downgrade();
     let config_for_tray = config.clone();
+    let history_for_tray = history.clone();
     let reload_hotkeys_tx_for_tray = reload_hotkeys_tx.clone();
     glib::spawn_future_local(async move {
         while open_settings_rx.recv().await.is_ok() {
             if let Some(window) = window_for_settings.upgrade() {
-                show_settings_dialog(&window, config_for_tray.clone(), reload_hotkeys_tx_for_tray.clone());
+                show_settings_dialog(
+                    &window,
+                    config_for_tray.clone(),
+                    history_for_tray.clone(),
+                    reload_hotkeys_tx_for_tray.clone(),
+                );
             }
         }
     });
@@ -182,10 +287,61 @@ pub fn build_ui(app: &Application, ctx: Arc<AppContext>) {
         }
     });
 
-    // Set initial loading state — model loads in background thread
+    // Phone remote control: apply its commands and publish the recording state
+    if ctx.config.lock().remote_control_enabled {
+        let remote_command_rx = ctx.channels.remote_command_rx().clone();
+        let ctx_for_remote = ctx.clone();
+        let rec_ctx_for_remote = rec_ctx.clone();
+        let mode_uis_for_remote = ModeUIs {
+            mic: mic_ui.clone(),
+            conference: conference_ui.clone(),
+        };
+        let mode_combo_for_remote = w.mode_combo.clone();
+        glib::spawn_future_local(async move {
+            while let Ok(command) = remote_command_rx.recv().await {
+                let state = rec_ctx_for_remote.state.get();
+                let applies = match command {
+                    // While loading, a start is queued, but a repeated one must not cancel it
+                    RemoteCommand::Start => {
+                        state == AppState::Idle
+                            || (state == AppState::Loading && !rec_ctx_for_remote.start_queued.get())
+                    }
+                    RemoteCommand::Stop => state == AppState::Recording,
+                };
+                if applies {
+                    dispatch::toggle_recording(
+                        &ctx_for_remote,
+                        &rec_ctx_for_remote,
+                        &mode_uis_for_remote,
+                        &mode_combo_for_remote,
+                    );
+                }
+            }
+        });
+
+        let ctx_for_status = ctx.clone();
+        let rec_ctx_for_status = rec_ctx.clone();
+        glib::timeout_add_local(std::time::Duration::from_millis(250), move || {
+            let peak_dbfs = ctx_for_status
+                .audio
+                .mic_level()
+                .peak_dbfs
+                .max(ctx_for_status.audio.get_mic_level().peak_dbfs);
+            let level = LevelReading::scale(peak_dbfs);
+            ctx_for_status.remote.lock().set_state(
+                rec_ctx_for_status.state.get(),
+                rec_ctx_for_status.elapsed_secs(),
+                level,
+            );
+            glib::ControlFlow::Continue
+        });
+    }
+
+    // Set initial loading state — model loads in background thread.
+    // The button stays enabled so a press queues the start instead of being lost.
     if !ctx.is_model_loaded() {
-        mic_ui.base.status_label.set_text("Завантаження моделі...");
-        mic_ui.base.button.set_sensitive(false);
+        rec_ctx.start_loading();
+        mic_ui.base.status_label.set_text(dispatch::LOADING_STATUS);
         mic_ui.base.spinner.set_visible(true);
         mic_ui.base.spinner.start();
     }
@@ -194,13 +350,26 @@ pub fn build_ui(app: &Application, ctx: Arc<AppContext>) {
     let model_ready_rx = ctx.channels.model_ready_rx().clone();
     let mic_ui_for_model = mic_ui.clone();
     let ctx_for_model = ctx.clone();
+    let rec_ctx_for_model = rec_ctx.clone();
+    let mode_uis_for_model = mode_uis.clone();
+    let mode_combo_for_model = w.mode_combo.clone();
     glib::spawn_future_local(async move {
         if let Ok(_success) = model_ready_rx.recv().await {
+            let start_queued = rec_ctx_for_model.finish_loading();
             mic_ui_for_model.base.spinner.stop();
             mic_ui_for_model.base.spinner.set_visible(false);
+            mic_ui_for_model.base.button.set_label("Почати запис");
             if ctx_for_model.is_model_loaded() {
                 mic_ui_for_model.base.status_label.set_text("Готово до запису");
                 mic_ui_for_model.base.button.set_sensitive(true);
+                if start_queued {
+                    dispatch::toggle_recording(
+                        &ctx_for_model,
+                        &rec_ctx_for_model,
+                        &mode_uis_for_model,
+                        &mode_combo_for_model,
+                    );
+                }
             } else {
                 mic_ui_for_model
                     .base
@@ -211,6 +380,21 @@ pub fn build_ui(app: &Application, ctx: Arc<AppContext>) {
     });
 
     window.present();
+
+    // First launch: walk through language, model, microphone, hotkey and auto-copy
+    let onboarding_completed = config.lock().onboarding_completed;
+    if !onboarding_completed {
+        let mic_ui_for_onboarding = mic_ui.clone();
+        let ctx_for_onboarding = ctx.clone();
+        show_onboarding_dialog(&window, ctx.clone(), move || {
+            if ctx_for_onboarding.is_model_loaded() {
+                mic_ui_for_onboarding.base.spinner.stop();
+                mic_ui_for_onboarding.base.spinner.set_visible(false);
+                mic_ui_for_onboarding.base.status_label.set_text("Готово до запису");
+                mic_ui_for_onboarding.base.button.set_sensitive(true);
+            }
+        });
+    }
 }
 
 fn setup_record_button(
@@ -225,15 +409,89 @@ fn setup_record_button(
     });
 }
 
+/// Window-level keyboard shortcuts so every main action is reachable without a mouse.
+fn setup_keyboard_shortcuts(window: &ApplicationWindow, w: &widgets::MainWidgets) {
+    let controller = gtk4::ShortcutController::new();
+
+    let bindings: [(&str, &Button); 6] = [
+        ("<Control>r", &w.record_button),
+        ("<Control><Shift>c", &w.copy_button),
+        ("<Control>m", &w.models_button),
+        ("<Control>h", &w.history_button),
+        ("<Control>comma", &w.settings_button),
+        ("F1", &w.help_button),
+    ];
+
+    for (accel, button) in bindings {
+        let Some(trigger) = gtk4::ShortcutTrigger::parse_string(accel) else {
+            continue;
+        };
+        let button = button.clone();
+        let action = gtk4::CallbackAction::new(move |_, _| {
+            if button.is_sensitive() {
+                button.emit_clicked();
+            }
+            glib::Propagation::Stop
+        });
+        controller.add_shortcut(gtk4::Shortcut::new(Some(trigger), Some(action)));
+    }
+
+    window.add_controller(controller);
+}
+
 pub(crate) fn copy_to_clipboard(text: &str) {
     if let Some(display) = gtk4::gdk::Display::default() {
         let clipboard = display.clipboard();
         if !text.is_empty() {
             clipboard.set_text(text);
         }
+    } else {
+        eprintln!("Буфер обміну недоступний: немає дисплея");
+        failures::record(Failure::Clipboard);
     }
 }
 
+/// Rename speakers of the shown result in the text view and in history.
+fn setup_speakers_button(
+    window: &ApplicationWindow,
+    history: &SharedHistory,
+    ui: &UIContext,
+    field_dictation: &FieldDictation,
+) {
+    let window_weak = window.downgrade();
+    let history = history.clone();
+    let ui = ui.clone();
+    let field_dictation = field_dictation.clone();
+    ui.speakers_button.clone().connect_clicked(move |_| {
+        let Some(window) = window_weak.upgrade() else {
+            return;
+        };
+        let Some(id) = ui.result_entry_id.borrow().clone() else {
+            return;
+        };
+        let speakers = match history.lock().entries().iter().find(|e| e.id == id) {
+            Some(entry) => entry.speakers.clone(),
+            None => return,
+        };
+
+        let history = history.clone();
+        let result_text_view = ui.result_text_view.clone();
+        show_rename_speakers_dialog(&window, speakers, Some(&field_dictation), move |renames| {
+            {
+                let mut h = history.lock();
+                if h.rename_speakers(&id, &renames) {
+                    if let Err(e) = h.save() {
+                        eprintln!("Помилка збереження історії: {}", e);
+                    }
+                }
+            }
+            let buffer = result_text_view.buffer();
+            let text = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+            buffer.set_text(&rename_speaker_labels(&text, &renames));
+        });
+    });
+}
+
 fn setup_copy_button(button: &Button, result_text_view: &TextView) {
     let result_text_view_clone = result_text_view.clone();
 
@@ -242,6 +500,8 @@ fn setup_copy_button(button: &Button, result_text_view: &TextView) {
         let start = buffer.start_iter();
         let end = buffer.end_iter();
         let text = buffer.text(&start, &end, false).to_string();
-        copy_to_clipboard(&text);
+        copy_to_clipboard(&strip_hints(&text));
+        // The edits are in use now; no draft to keep
+        buffer.set_modified(false);
     });
 }
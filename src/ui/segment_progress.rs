@@ -0,0 +1,273 @@
+//! Segment progress of continuous mode.
+//!
+//! The first segments are shown as a row of indicators. A long session
+//! would turn that row into an endless strip, so past [`COMPACT_AFTER`]
+//! segments it collapses into a summary ("42/45 готово") with a small
+//! progress bar. A "Деталі" popover keeps the per-segment status and errors,
+//! with a "Повторити" button for failed segments.
+
+use crate::ui::a11y;
+use gtk4::prelude::*;
+use gtk4::{Align, Box as GtkBox, Button, Label, MenuButton, Orientation, Popover, ProgressBar, ScrolledWindow};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::rc::Rc;
+
+/// More segments than this are shown as a summary.
+const COMPACT_AFTER: usize = 20;
+
+/// Segment indicator symbols
+const SEGMENT_PROCESSING: &str = "◐";
+const SEGMENT_COMPLETED: &str = "●";
+const SEGMENT_FAILED: &str = "✗";
+
+#[derive(Debug, Clone, PartialEq)]
+enum SegmentStatus {
+    Processing,
+    Completed,
+    Failed(String),
+}
+
+struct SegmentEntry {
+    duration_text: String,
+    status: SegmentStatus,
+    indicator: Label,
+    detail_row: GtkBox,
+    detail: Label,
+    retry_button: Button,
+    retry: Option<Box<dyn Fn()>>,
+}
+
+impl SegmentEntry {
+    fn symbol(&self) -> &'static str {
+        match self.status {
+            SegmentStatus::Processing => SEGMENT_PROCESSING,
+            SegmentStatus::Completed => SEGMENT_COMPLETED,
+            SegmentStatus::Failed(_) => SEGMENT_FAILED,
+        }
+    }
+
+    fn css_class(&self) -> &'static str {
+        match self.status {
+            SegmentStatus::Processing => "segment-processing",
+            SegmentStatus::Completed => "segment-completed",
+            SegmentStatus::Failed(_) => "segment-error",
+        }
+    }
+
+    fn refresh(&self, segment_id: usize) {
+        self.indicator
+            .set_label(&format!("{} {}", self.symbol(), self.duration_text));
+        let detail = match &self.status {
+            SegmentStatus::Failed(err) => {
+                format!("{} #{} · {} — {}", self.symbol(), segment_id, self.duration_text, err)
+            }
+            _ => format!("{} #{} · {}", self.symbol(), segment_id, self.duration_text),
+        };
+        self.detail.set_label(&detail);
+        self.retry_button.set_visible(self.retry.is_some());
+        for label in [&self.indicator, &self.detail] {
+            for class in ["segment-processing", "segment-completed", "segment-error"] {
+                label.remove_css_class(class);
+            }
+            label.add_css_class(self.css_class());
+        }
+    }
+}
+
+/// Indicators, summary and details list of the segments of one session.
+#[derive(Clone)]
+pub struct SegmentProgress {
+    root: GtkBox,
+    indicators_scroll: ScrolledWindow,
+    indicators_box: GtkBox,
+    summary_box: GtkBox,
+    summary_label: Label,
+    summary_bar: ProgressBar,
+    details_list: GtkBox,
+    segments: Rc<RefCell<BTreeMap<usize, SegmentEntry>>>,
+}
+
+impl Default for SegmentProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SegmentProgress {
+    pub fn new() -> Self {
+        let root = GtkBox::new(Orientation::Horizontal, 8);
+        root.set_hexpand(true);
+
+        let indicators_box = GtkBox::new(Orientation::Horizontal, 4);
+        indicators_box.set_halign(Align::Start);
+
+        let indicators_scroll = ScrolledWindow::new();
+        indicators_scroll.set_policy(gtk4::PolicyType::Automatic, gtk4::PolicyType::Never);
+        indicators_scroll.set_hexpand(true);
+        indicators_scroll.set_child(Some(&indicators_box));
+        root.append(&indicators_scroll);
+
+        // Compact summary, shown once there are too many indicators
+        let summary_box = GtkBox::new(Orientation::Horizontal, 8);
+        summary_box.set_visible(false);
+
+        let summary_label = Label::new(None);
+        summary_label.add_css_class("monospace");
+        summary_box.append(&summary_label);
+
+        let summary_bar = ProgressBar::new();
+        summary_bar.set_size_request(120, -1);
+        summary_bar.set_valign(Align::Center);
+        a11y::set_name(&summary_bar, "Прогрес сегментів");
+        summary_box.append(&summary_bar);
+        root.append(&summary_box);
+
+        // Per-segment list, available in both views
+        let details_list = GtkBox::new(Orientation::Vertical, 2);
+        details_list.set_margin_top(6);
+        details_list.set_margin_bottom(6);
+        details_list.set_margin_start(6);
+        details_list.set_margin_end(6);
+
+        let details_scroll = ScrolledWindow::new();
+        details_scroll.set_policy(gtk4::PolicyType::Never, gtk4::PolicyType::Automatic);
+        details_scroll.set_propagate_natural_height(true);
+        details_scroll.set_max_content_height(300);
+        details_scroll.set_child(Some(&details_list));
+
+        let popover = Popover::new();
+        popover.set_child(Some(&details_scroll));
+
+        let details_button = MenuButton::new();
+        details_button.set_label("Деталі");
+        details_button.set_popover(Some(&popover));
+        details_button.set_tooltip_text(Some("Стан кожного сегменту та помилки"));
+        root.append(&details_button);
+
+        Self {
+            root,
+            indicators_scroll,
+            indicators_box,
+            summary_box,
+            summary_label,
+            summary_bar,
+            details_list,
+            segments: Rc::new(RefCell::new(BTreeMap::new())),
+        }
+    }
+
+    /// Widget to place in the segment row.
+    pub fn widget(&self) -> &GtkBox {
+        &self.root
+    }
+
+    /// Add a segment that has just been sent for transcription.
+    pub fn add(&self, segment_id: usize, duration_secs: f32) {
+        let entry = SegmentEntry {
+            duration_text: format!("{:.1}s", duration_secs),
+            status: SegmentStatus::Processing,
+            indicator: Label::new(None),
+            detail_row: GtkBox::new(Orientation::Horizontal, 8),
+            detail: Label::new(None),
+            retry_button: Button::with_label("Повторити"),
+            retry: None,
+        };
+        entry.detail.set_halign(Align::Start);
+        entry.detail.set_hexpand(true);
+        entry.detail.set_wrap(true);
+        entry.detail_row.append(&entry.detail);
+
+        entry.retry_button.add_css_class("flat");
+        a11y::set_name(&entry.retry_button, &format!("Повторити сегмент {}", segment_id));
+        let progress = self.clone();
+        entry
+            .retry_button
+            .connect_clicked(move |_| progress.run_retry(segment_id));
+        entry.detail_row.append(&entry.retry_button);
+
+        entry.refresh(segment_id);
+        self.indicators_box.append(&entry.indicator);
+        self.details_list.append(&entry.detail_row);
+        self.segments.borrow_mut().insert(segment_id, entry);
+        self.update_summary();
+    }
+
+    pub fn complete(&self, segment_id: usize) {
+        self.set_status(segment_id, SegmentStatus::Completed);
+    }
+
+    pub fn fail(&self, segment_id: usize, error: &str) {
+        self.set_status(segment_id, SegmentStatus::Failed(error.to_string()));
+    }
+
+    /// Offer a "Повторити" action for a failed segment.
+    ///
+    /// The segment shows as processing again when `retry` is started.
+    pub fn set_retry(&self, segment_id: usize, retry: impl Fn() + 'static) {
+        if let Some(entry) = self.segments.borrow_mut().get_mut(&segment_id) {
+            entry.retry = Some(Box::new(retry));
+            entry.refresh(segment_id);
+        }
+    }
+
+    /// Remove all segments and return to the indicator row.
+    pub fn clear(&self) {
+        for entry in std::mem::take(&mut *self.segments.borrow_mut()).into_values() {
+            self.indicators_box.remove(&entry.indicator);
+            self.details_list.remove(&entry.detail_row);
+        }
+        self.update_summary();
+    }
+
+    fn set_status(&self, segment_id: usize, status: SegmentStatus) {
+        if let Some(entry) = self.segments.borrow_mut().get_mut(&segment_id) {
+            entry.status = status;
+            entry.refresh(segment_id);
+        }
+        self.update_summary();
+    }
+
+    fn run_retry(&self, segment_id: usize) {
+        let retry = {
+            let mut segments = self.segments.borrow_mut();
+            let Some(entry) = segments.get_mut(&segment_id) else {
+                return;
+            };
+            let retry = entry.retry.take();
+            entry.status = SegmentStatus::Processing;
+            entry.refresh(segment_id);
+            retry
+        };
+        self.update_summary();
+        // Called without the borrow held: the retry updates this view
+        if let Some(retry) = retry {
+            retry();
+        }
+    }
+
+    fn update_summary(&self) {
+        let segments = self.segments.borrow();
+        let total = segments.len();
+        let done = segments
+            .values()
+            .filter(|e| e.status != SegmentStatus::Processing)
+            .count();
+        let failed = segments
+            .values()
+            .filter(|e| matches!(e.status, SegmentStatus::Failed(_)))
+            .count();
+
+        let compact = total > COMPACT_AFTER;
+        self.indicators_scroll.set_visible(!compact);
+        self.summary_box.set_visible(compact);
+
+        let mut summary = format!("{}/{} готово", done, total);
+        if failed > 0 {
+            summary.push_str(&format!(", {} з помилками", failed));
+        }
+        self.summary_label.set_text(&summary);
+        self.summary_bar
+            .set_fraction(if total == 0 { 0.0 } else { done as f64 / total as f64 });
+    }
+}
@@ -1,77 +1,222 @@
 //! Shared UI helpers extracted from mic.rs, conference.rs, conference_file.rs.
 //!
 //! These functions eliminate duplication across recording mode handlers:
-//! - Timer update loop (was identical in all 3 modules)
+//! - Timer update loop (was identical in all 3 modules), with the duration limit
 //! - Conference level bar loop (was identical in conference.rs and conference_file.rs)
+//! - Microphone loss warnings (mic and conference modes)
 //! - Post-transcription actions (auto-copy, auto-paste, history save)
 //! - Audio denoising wrapper
 
 use crate::app::context::AppContext;
 use crate::domain::traits::{HistoryRepository, UIStateUpdater};
+use crate::domain::types::{ActionItem, AudioQuality, CaptureDeviceStatus, SpeakerStats};
 use crate::history::{save_history, HistoryEntry};
-use crate::recording::denoise::NnnoiselessDenoiser;
+use crate::infrastructure::failures::{self, Failure};
+use crate::infrastructure::recordings::{
+    generate_recording_filename, recording_path, save_mono_recording, RecordingFormat,
+};
+use crate::infrastructure::webdav;
+use crate::recording::denoise::{denoise_samples, DenoiseSettings};
+use crate::transcription::action_items::prepend_summary;
+use crate::transcription::corrections::CorrectionDictionary;
+use crate::transcription::talk_time::append_talk_time;
 use gtk4::glib;
+use gtk4::prelude::*;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::path::Path;
 use std::sync::Arc;
+use std::time::Duration;
 
+use super::dispatch;
 use super::state::{ConferenceUI, RecordingContext, UIContext};
 
 /// Start a 1-second timer update loop on the GTK main thread.
 ///
 /// Updates the timer label with elapsed seconds. Stops automatically
-/// when recording ends (rec.is_recording() returns false).
-pub fn start_timer_loop(rec: &RecordingContext, base: &UIContext) {
+/// when recording ends (rec.is_recording() returns false). A recording
+/// that reaches `max_recording_minutes` is stopped as if by the user, so
+/// what was captured is transcribed and saved.
+pub fn start_timer_loop(ctx: &Arc<AppContext>, rec: &RecordingContext, base: &UIContext) {
     let rec = rec.clone();
     let base = base.clone();
+    let max_minutes = ctx.config.lock().max_recording_minutes;
     glib::timeout_add_local(std::time::Duration::from_secs(1), move || {
         if !rec.is_recording() {
             return glib::ControlFlow::Break;
         }
         if let Some(secs) = rec.elapsed_secs() {
             base.update_timer(secs);
+            if max_minutes > 0 && secs >= u64::from(max_minutes) * 60 {
+                stop_at_limit(&base, max_minutes);
+                return glib::ControlFlow::Break;
+            }
         }
         glib::ControlFlow::Continue
     });
 }
 
+/// Stop a recording that ran into the duration limit and say why.
+fn stop_at_limit(base: &UIContext, max_minutes: u32) {
+    eprintln!("Запис зупинено після {} хв", max_minutes);
+    // The record button stops in whichever mode is recording
+    base.button.emit_clicked();
+
+    let limit = if max_minutes % 60 == 0 {
+        format!("{} год", max_minutes / 60)
+    } else {
+        format!("{} хв", max_minutes)
+    };
+    dispatch::notify(
+        base,
+        "recording-limit",
+        &format!(
+            "Запис автоматично зупинено через {}: схоже, його забули вимкнути. Записане обробляється як звичайно",
+            limit
+        ),
+    );
+}
+
 /// Start a 50ms level bar update loop for conference mode (dual mic + loopback).
 ///
-/// Reads amplitudes from AudioService and updates both level bars.
+/// Reads levels from AudioService and updates both level meters.
 /// Stops when recording ends.
 pub fn start_conference_level_loop(ctx: &Arc<AppContext>, rec: &RecordingContext, ui: &ConferenceUI) {
     let ctx = ctx.clone();
     let rec = rec.clone();
     let ui = ui.clone();
+    let device_status = RefCell::new(CaptureDeviceStatus::Active);
     glib::timeout_add_local(std::time::Duration::from_millis(50), move || {
         if !rec.is_recording() {
             return glib::ControlFlow::Break;
         }
-        let mic_amplitude = ctx.audio.get_mic_amplitude();
-        let loopback_amplitude = ctx.audio.get_loopback_amplitude();
-        ui.update_levels(mic_amplitude as f64, loopback_amplitude as f64);
+        ui.update_levels(ctx.audio.get_mic_level(), ctx.audio.get_loopback_level());
+        report_device_status(&ui.base, &device_status, ctx.audio.get_mic_device_status());
         glib::ControlFlow::Continue
     });
 }
 
-/// Apply denoising if enabled, returning original samples on failure.
-pub fn maybe_denoise(samples: &[f32], enabled: bool) -> Vec<f32> {
-    if !enabled {
-        return samples.to_vec();
+/// Warn when the microphone disappears mid-recording or capture moves to another one.
+///
+/// Polled from the level loops; `last` holds the status already reported,
+/// so each change is announced once.
+pub fn report_device_status(base: &UIContext, last: &RefCell<CaptureDeviceStatus>, status: CaptureDeviceStatus) {
+    if *last.borrow() == status {
+        return;
+    }
+    let message = match &status {
+        CaptureDeviceStatus::Active => None,
+        CaptureDeviceStatus::Switched(name) => {
+            Some(format!("Мікрофон від'єднано. Запис продовжено з пристрою «{}»", name))
+        }
+        CaptureDeviceStatus::Lost => Some(
+            "Мікрофон від'єднано, запис призупинено. Підключіть мікрофон, щоб продовжити, \
+             або зупиніть запис — уже записане збережеться"
+                .to_string(),
+        ),
+    };
+    *last.borrow_mut() = status;
+    if let Some(message) = message {
+        dispatch::notify(base, "microphone-lost", &message);
     }
-    let denoiser = NnnoiselessDenoiser::new();
-    match denoiser.denoise_buffer(samples) {
-        Ok(denoised) => denoised,
+}
+
+/// Apply denoising if enabled, returning original samples on failure.
+///
+/// The original samples are borrowed, not copied, when denoising is off.
+pub fn maybe_denoise<'a>(samples: &'a [f32], denoise: Option<&DenoiseSettings>) -> Cow<'a, [f32]> {
+    let Some(settings) = denoise else {
+        return Cow::Borrowed(samples);
+    };
+    match denoise_samples(settings, samples) {
+        Ok(denoised) => Cow::Owned(denoised),
         Err(e) => {
             eprintln!("Denoising failed, using original: {}", e);
-            samples.to_vec()
+            Cow::Borrowed(samples)
+        }
+    }
+}
+
+/// Upload the session's transcript and/or recording to WebDAV in the background.
+///
+/// `mode` is "dictation", "continuous" or "conference"; nothing happens unless
+/// a server is configured and uploads are enabled for that mode, nor in kiosk
+/// mode, which leaves no transcripts behind.
+pub fn queue_webdav_upload(ctx: &Arc<AppContext>, mode: &str, text: Option<&str>, recording_file: Option<&Path>) {
+    if ctx.read_only_history() {
+        return;
+    }
+    let Some((settings, transcripts, recordings)) = webdav::upload_plan(&ctx.config.lock(), mode) else {
+        return;
+    };
+    let text = text.filter(|_| transcripts).map(str::to_string);
+    let recording = recording_file.filter(|_| recordings).map(Path::to_path_buf);
+    webdav::spawn_upload(settings, text, recording, mode.to_string());
+}
+
+/// Save the audio of a dictation session to the recordings directory.
+///
+/// `mode` is "dictation" or "continuous". Returns the path for the history
+/// entry, or `None` if saving failed.
+pub async fn save_dictation_recording(ctx: &Arc<AppContext>, samples: Vec<f32>, mode: &str) -> Option<String> {
+    let (template, format) = {
+        let cfg = ctx.config.lock();
+        (
+            cfg.recording_filename_template.clone(),
+            RecordingFormat::parse(&cfg.recording_format),
+        )
+    };
+    let path = recording_path(&generate_recording_filename(&template, mode, None, format));
+
+    let (tx, rx) = async_channel::bounded(1);
+    let path_for_thread = path.clone();
+    std::thread::spawn(move || {
+        let _ = tx.send_blocking(save_mono_recording(&samples, &path_for_thread));
+    });
+    match rx.recv().await {
+        Ok(Ok(())) => Some(path.to_string_lossy().into_owned()),
+        Ok(Err(e)) => {
+            eprintln!("Помилка збереження аудіо файлу: {}", e);
+            None
+        }
+        Err(_) => None,
+    }
+}
+
+/// Add alternative-spelling hints to the displayed text if enabled.
+///
+/// Only the on-screen result is annotated; clipboard, paste and history
+/// always receive the plain transcript.
+fn annotate_for_display(ctx: &Arc<AppContext>, text: &str) -> String {
+    if !ctx.phonetic_hints() {
+        return text.to_string();
+    }
+    match CorrectionDictionary::load_default() {
+        Ok(dict) => dict.annotate_alternatives(text),
+        Err(e) => {
+            eprintln!("Помилка словника виправлень: {}", e);
+            text.to_string()
         }
     }
 }
 
+/// Name of the loaded model for a new history entry; `None` while a
+/// transcription still holds the model, rather than blocking the UI.
+pub fn loaded_model_label(ctx: &AppContext) -> Option<String> {
+    ctx.transcription.try_lock()?.model_label()
+}
+
 /// Handle post-transcription actions: auto-copy, auto-paste, and history save.
 ///
 /// This is the shared "success path" after transcription produces text.
 /// Conference mode passes additional recording metadata via `recording_file`
-/// and `speakers`.
+/// (dictation too when its audio is saved) and `speakers`, plus the `action_items` found in the meeting, which are
+/// shown above the transcript, and per-speaker `speaker_stats`, shown below
+/// it. Both are stored with the entry, as is the `speaker_confidence` of
+/// each speaker label for review in the history, the `audio_quality`
+/// note when the microphone clipped or was too quiet, and the model and
+/// `processing` time of the transcription for the usage statistics.
+#[allow(clippy::too_many_arguments)]
 pub async fn handle_post_transcription(
     ctx: &Arc<AppContext>,
     base: &UIContext,
@@ -80,9 +225,19 @@ pub async fn handle_post_transcription(
     duration_secs: f32,
     recording_file: Option<String>,
     speakers: Vec<String>,
+    action_items: Vec<ActionItem>,
+    speaker_stats: Vec<SpeakerStats>,
+    speaker_confidence: Vec<f32>,
+    audio_quality: Option<AudioQuality>,
+    processing: Option<Duration>,
 ) {
     base.set_status("Готово!");
-    base.set_result_text(text);
+    base.set_result_text(&append_talk_time(
+        &prepend_summary(&annotate_for_display(ctx, text), &action_items),
+        &speaker_stats,
+    ));
+    super::a11y::announce(&base.status_label, "Транскрипцію готово", super::a11y::Urgency::Polite);
+    ctx.remote.lock().set_result(text);
 
     let auto_copy = ctx.auto_copy();
     let auto_paste = ctx.auto_paste();
@@ -102,25 +257,36 @@ pub async fn handle_post_transcription(
         });
         if let Ok(Some(err)) = paste_rx.recv().await {
             eprintln!("Помилка автоматичної вставки: {}", err);
+            failures::record(Failure::Paste);
             base.set_status(&format!("Готово! (помилка вставки: {})", err));
         }
     }
 
     if speakers.is_empty() && recording_file.is_none() {
-        let entry = HistoryEntry::new(text.to_string(), duration_secs, language.to_string());
+        let mut entry = HistoryEntry::new(text.to_string(), duration_secs, language.to_string());
+        entry.audio_quality = audio_quality;
+        entry.model = loaded_model_label(ctx);
+        entry.processing_secs = processing.map(|d| d.as_secs_f32());
         let mut h = ctx.history.lock();
         h.add(entry);
         if let Err(e) = save_history(&h) {
             eprintln!("Помилка збереження історії: {}", e);
         }
     } else {
-        let entry = HistoryEntry::new_with_recording(
+        let mut entry = HistoryEntry::new_with_recording(
             text.to_string(),
             duration_secs,
             language.to_string(),
             recording_file,
             speakers,
         );
+        entry.action_items = action_items;
+        entry.speaker_stats = speaker_stats;
+        entry.speaker_confidence = speaker_confidence;
+        entry.audio_quality = audio_quality;
+        entry.model = loaded_model_label(ctx);
+        entry.processing_secs = processing.map(|d| d.as_secs_f32());
+        base.set_result_entry((!entry.speakers.is_empty()).then(|| entry.id.clone()));
         let mut h = ctx.history.lock();
         h.add(entry);
         if let Err(e) = save_history(&h) {
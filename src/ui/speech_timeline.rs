@@ -0,0 +1,122 @@
+//! Speech/silence timeline of continuous mode.
+//!
+//! Shows the VAD decisions of the last [`WINDOW_SECS`] seconds as a strip
+//! under the level bar: green where the detector heard speech, grey where it
+//! did not. Unlike the "Говорить/Тиша" indicator it shows the recent past,
+//! so the user sees whether a pause was long enough to end a segment or
+//! whether background noise keeps being taken for speech.
+
+use crate::recording::core::WHISPER_SAMPLE_RATE;
+use crate::recording::segmentation::VadDecision;
+use crate::ui::a11y;
+use async_channel::Receiver;
+use gtk4::prelude::*;
+use gtk4::{glib, DrawingArea};
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::rc::Rc;
+
+/// Length of the visible history.
+const WINDOW_SECS: usize = 30;
+
+/// Same colors as the level bar classes.
+const SPEECH_RGB: (f64, f64, f64) = (0.2, 0.667, 0.2);
+const SILENCE_RGB: (f64, f64, f64) = (0.6, 0.6, 0.6);
+
+/// Consecutive frames with the same decision.
+#[derive(Debug, Clone, Copy)]
+struct Run {
+    start: usize,
+    end: usize,
+    is_speech: bool,
+}
+
+/// Strip of recent VAD decisions, newest on the right.
+#[derive(Clone)]
+pub struct SpeechTimeline {
+    area: DrawingArea,
+    runs: Rc<RefCell<VecDeque<Run>>>,
+}
+
+impl Default for SpeechTimeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SpeechTimeline {
+    pub fn new() -> Self {
+        let area = DrawingArea::new();
+        area.set_content_height(12);
+        area.set_size_request(200, -1);
+        area.set_visible(false);
+        a11y::set_name(&area, "Хронологія мовлення");
+
+        let runs: Rc<RefCell<VecDeque<Run>>> = Rc::new(RefCell::new(VecDeque::new()));
+        let runs_for_draw = runs.clone();
+        area.set_draw_func(move |_, cr, width, height| {
+            let runs = runs_for_draw.borrow();
+            let Some(last) = runs.back() else {
+                return;
+            };
+            let window = (WINDOW_SECS * WHISPER_SAMPLE_RATE as usize) as f64;
+            let window_start = last.end as f64 - window;
+            let scale = width as f64 / window;
+            for run in runs.iter() {
+                let (r, g, b) = if run.is_speech { SPEECH_RGB } else { SILENCE_RGB };
+                let x = ((run.start as f64 - window_start) * scale).max(0.0);
+                let end_x = (run.end as f64 - window_start) * scale;
+                cr.set_source_rgb(r, g, b);
+                cr.rectangle(x, 0.0, end_x - x, height as f64);
+                let _ = cr.fill();
+            }
+        });
+
+        Self { area, runs }
+    }
+
+    pub fn widget(&self) -> &DrawingArea {
+        &self.area
+    }
+
+    /// Empty the strip and show it for a new session.
+    pub fn show(&self) {
+        self.runs.borrow_mut().clear();
+        self.area.queue_draw();
+        self.area.set_visible(true);
+    }
+
+    pub fn hide(&self) {
+        self.area.set_visible(false);
+    }
+
+    /// Draw decisions from `rx` until the session closes the channel.
+    pub fn follow(&self, rx: Receiver<VadDecision>) {
+        let timeline = self.clone();
+        glib::spawn_future_local(async move {
+            while let Ok(decision) = rx.recv().await {
+                timeline.push(decision);
+            }
+        });
+    }
+
+    fn push(&self, decision: VadDecision) {
+        let end = decision.start_sample + decision.len;
+        {
+            let mut runs = self.runs.borrow_mut();
+            match runs.back_mut() {
+                Some(run) if run.is_speech == decision.is_speech && run.end == decision.start_sample => run.end = end,
+                _ => runs.push_back(Run {
+                    start: decision.start_sample,
+                    end,
+                    is_speech: decision.is_speech,
+                }),
+            }
+            let window_start = end.saturating_sub(WINDOW_SECS * WHISPER_SAMPLE_RATE as usize);
+            while runs.front().is_some_and(|run| run.end <= window_start) {
+                runs.pop_front();
+            }
+        }
+        self.area.queue_draw();
+    }
+}
@@ -4,13 +4,22 @@
 //! recording mode selection logic.
 
 use crate::app::context::AppContext;
+use crate::app::templates::SessionTemplate;
 use crate::domain::traits::UIStateUpdater;
+use crate::domain::types::{LevelReading, LevelWarning};
+use crate::ui::a11y::{self, Urgency};
+use crate::vad::NoiseFloorState;
 use gtk4::prelude::*;
-use gtk4::{Box as GtkBox, Button, Label, LevelBar, Spinner, TextView};
-use std::cell::Cell;
+use gtk4::{Box as GtkBox, Button, Label, Spinner, TextView};
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use std::sync::Arc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+
+use super::conference::LiveDiarizationSession;
+use super::level_meter::LevelMeterView;
+use super::segment_progress::SegmentProgress;
+use super::speech_timeline::SpeechTimeline;
 
 // Re-export AppState from types module (domain type, not UI-specific)
 pub use crate::domain::types::AppState;
@@ -45,6 +54,10 @@ impl RecordingMode {
 pub struct RecordingContext {
     pub state: Rc<Cell<AppState>>,
     pub start_time: Rc<Cell<Option<Instant>>>,
+    /// Start requested while the model was loading.
+    pub start_queued: Rc<Cell<bool>>,
+    /// The microphone is dictating into one of the app's text fields.
+    pub field_dictation: Rc<Cell<bool>>,
 }
 
 impl RecordingContext {
@@ -52,6 +65,8 @@ impl RecordingContext {
         Self {
             state: Rc::new(Cell::new(AppState::Idle)),
             start_time: Rc::new(Cell::new(None)),
+            start_queued: Rc::new(Cell::new(false)),
+            field_dictation: Rc::new(Cell::new(false)),
         }
     }
 
@@ -59,6 +74,19 @@ impl RecordingContext {
         self.state.get() == AppState::Recording
     }
 
+    pub fn start_loading(&self) {
+        self.state.set(AppState::Loading);
+        self.start_queued.set(false);
+    }
+
+    /// Leave the loading state; returns whether a start was queued meanwhile.
+    pub fn finish_loading(&self) -> bool {
+        if self.state.get() == AppState::Loading {
+            self.state.set(AppState::Idle);
+        }
+        self.start_queued.replace(false)
+    }
+
     pub fn start_recording(&self) {
         self.state.set(AppState::Recording);
         self.start_time.set(Some(Instant::now()));
@@ -90,6 +118,8 @@ impl Clone for RecordingContext {
         Self {
             state: Rc::clone(&self.state),
             start_time: Rc::clone(&self.start_time),
+            start_queued: Rc::clone(&self.start_queued),
+            field_dictation: Rc::clone(&self.field_dictation),
         }
     }
 }
@@ -102,6 +132,10 @@ pub struct UIContext {
     pub result_text_view: TextView,
     pub timer_label: Label,
     pub spinner: Spinner,
+    /// Opens the speaker renaming dialog for the shown result.
+    pub speakers_button: Button,
+    /// History ID of the entry shown in the result view, if it has speakers.
+    pub result_entry_id: Rc<RefCell<Option<String>>>,
 }
 
 impl UIContext {
@@ -111,6 +145,7 @@ impl UIContext {
         result_text_view: TextView,
         timer_label: Label,
         spinner: Spinner,
+        speakers_button: Button,
     ) -> Self {
         Self {
             button,
@@ -118,8 +153,16 @@ impl UIContext {
             result_text_view,
             timer_label,
             spinner,
+            speakers_button,
+            result_entry_id: Rc::new(RefCell::new(None)),
         }
     }
+
+    /// Remember the history entry behind the result so its speakers can be renamed.
+    pub fn set_result_entry(&self, id: Option<String>) {
+        self.speakers_button.set_sensitive(id.is_some());
+        *self.result_entry_id.borrow_mut() = id;
+    }
 }
 
 impl UIStateUpdater for UIContext {
@@ -128,16 +171,25 @@ impl UIStateUpdater for UIContext {
     }
 
     fn set_recording(&self, status_text: &str) {
+        a11y::announce(&self.status_label, "Запис розпочато", Urgency::Assertive);
         self.button.set_label("Зупинити запис");
         self.button.remove_css_class("suggested-action");
         self.button.add_css_class("destructive-action");
         self.status_label.set_text(status_text);
-        self.result_text_view.buffer().set_text("");
+        let buffer = self.result_text_view.buffer();
+        buffer.set_text("");
+        buffer.set_modified(false);
+        self.set_result_entry(None);
         self.timer_label.set_text("00:00");
         self.timer_label.set_visible(true);
     }
 
     fn set_processing(&self, status_text: &str) {
+        a11y::announce(
+            &self.status_label,
+            &format!("Запис зупинено. {}", status_text),
+            Urgency::Assertive,
+        );
         self.button.set_label("Обробка...");
         self.button.remove_css_class("destructive-action");
         self.button.remove_css_class("suggested-action");
@@ -171,10 +223,24 @@ impl UIStateUpdater for UIContext {
     }
 
     fn set_result_text(&self, text: &str) {
-        self.result_text_view.buffer().set_text(text);
+        // Only the user's own edits make a draft
+        let buffer = self.result_text_view.buffer();
+        buffer.set_text(text);
+        buffer.set_modified(false);
     }
 }
 
+/// Level bar class while the VAD hears speech.
+const LEVEL_SPEECH_CLASS: &str = "level-speech";
+/// Level bar class for sound the VAD does not treat as speech.
+const LEVEL_NOISE_CLASS: &str = "level-noise";
+/// RMS level below which non-speech is left uncolored.
+const AUDIBLE_DBFS: f32 = -42.0;
+/// Throughput label class when transcription is slower than real time.
+const THROUGHPUT_BEHIND_CLASS: &str = "throughput-behind";
+/// Backlog shown next to the real-time factor; shorter is the normal segment delay.
+const BACKLOG_NOTICE: Duration = Duration::from_secs(5);
+
 /// Microphone mode UI widgets (covers both dictation and segmented recording).
 ///
 /// Segmentation-specific widgets (VAD indicator, segment indicators) are
@@ -182,41 +248,97 @@ impl UIStateUpdater for UIContext {
 #[derive(Clone)]
 pub struct MicUI {
     pub base: UIContext,
-    pub level_bar: LevelBar,
+    pub level_bar: LevelMeterView,
+    /// Clipping or too quiet warning under the level bar
+    pub level_warning_label: Label,
     // Segmentation-specific (hidden when not segmenting)
+    pub speech_timeline: SpeechTimeline,
     pub vad_indicator: Label,
-    pub segment_indicators_box: GtkBox,
+    pub segment_progress: SegmentProgress,
     pub segment_row: GtkBox,
+    pub throughput_label: Label,
 }
 
 impl MicUI {
     pub fn new(
         base: UIContext,
-        level_bar: LevelBar,
+        level_bar: LevelMeterView,
+        level_warning_label: Label,
+        speech_timeline: SpeechTimeline,
         vad_indicator: Label,
-        segment_indicators_box: GtkBox,
+        segment_progress: SegmentProgress,
         segment_row: GtkBox,
+        throughput_label: Label,
     ) -> Self {
         Self {
             base,
             level_bar,
+            level_warning_label,
+            speech_timeline,
             vad_indicator,
-            segment_indicators_box,
+            segment_progress,
             segment_row,
+            throughput_label,
         }
     }
 
     pub fn show_level_bar(&self) {
-        self.level_bar.set_value(0.0);
+        self.level_bar.set(LevelReading::SILENT);
+        self.set_level_class(None);
         self.level_bar.set_visible(true);
+        self.level_warning_label.set_visible(false);
     }
 
     pub fn hide_level_bar(&self) {
         self.level_bar.set_visible(false);
+        self.level_warning_label.set_visible(false);
+    }
+
+    /// Show or hide the warning about the microphone level.
+    ///
+    /// Screen readers hear a warning once when it appears, not on every poll.
+    pub fn update_level_warning(&self, warning: Option<LevelWarning>) {
+        let text = match warning {
+            Some(LevelWarning::Clipping) => {
+                "⚠ Звук перевантажений: зменшіть підсилення мікрофона або відсуньтеся від нього"
+            }
+            Some(LevelWarning::TooQuiet) => {
+                "⚠ Мікрофон майже не чути: збільште підсилення, говоріть ближче або увімкніть AGC"
+            }
+            None => {
+                self.level_warning_label.set_visible(false);
+                return;
+            }
+        };
+        if self.level_warning_label.is_visible() && self.level_warning_label.text() == text {
+            return;
+        }
+        self.level_warning_label.set_text(text);
+        self.level_warning_label.set_visible(true);
+        a11y::announce(&self.level_warning_label, text, Urgency::Polite);
+    }
+
+    /// Show the level, colored by the VAD decision when `is_speech` is known:
+    /// green for speech, grey for sound that will not be treated as speech.
+    pub fn update_level(&self, level: LevelReading, is_speech: Option<bool>) {
+        self.level_bar.set(level);
+        let class = match is_speech {
+            Some(true) => Some(LEVEL_SPEECH_CLASS),
+            Some(false) if level.rms_dbfs >= AUDIBLE_DBFS => Some(LEVEL_NOISE_CLASS),
+            _ => None,
+        };
+        self.set_level_class(class);
     }
 
-    pub fn update_level(&self, amplitude: f64) {
-        self.level_bar.set_value(amplitude);
+    fn set_level_class(&self, class: Option<&str>) {
+        for c in [LEVEL_SPEECH_CLASS, LEVEL_NOISE_CLASS] {
+            if Some(c) != class {
+                self.level_bar.bar().remove_css_class(c);
+            }
+        }
+        if let Some(class) = class {
+            self.level_bar.bar().add_css_class(class);
+        }
     }
 
     /// Show segmentation-specific UI (VAD indicator + segment row).
@@ -224,18 +346,19 @@ impl MicUI {
         self.vad_indicator.set_text("🔇 Тиша");
         self.vad_indicator.set_visible(true);
         self.clear_segment_indicators();
+        self.throughput_label.set_text("");
+        self.throughput_label.remove_css_class(THROUGHPUT_BEHIND_CLASS);
         self.segment_row.set_visible(true);
     }
 
     /// Hide segmentation-specific UI.
     pub fn hide_segmentation_ui(&self) {
         self.vad_indicator.set_visible(false);
+        self.speech_timeline.hide();
     }
 
     pub fn clear_segment_indicators(&self) {
-        while let Some(child) = self.segment_indicators_box.first_child() {
-            self.segment_indicators_box.remove(&child);
-        }
+        self.segment_progress.clear();
     }
 
     pub fn update_vad_indicator(&self, is_speech: bool) {
@@ -245,39 +368,99 @@ impl MicUI {
             self.vad_indicator.set_text("🔇 Тиша");
         }
     }
+
+    /// Show how fast segments are transcribed and how far the text trails the speaker.
+    pub fn update_throughput(&self, real_time_factor: Option<f64>, backlog: Duration) {
+        let Some(rtf) = real_time_factor else {
+            return;
+        };
+        let mut text = format!("RTF {:.2}", rtf);
+        if backlog >= BACKLOG_NOTICE {
+            text.push_str(&format!(" · відстає на {} с", backlog.as_secs()));
+        }
+        self.throughput_label.set_text(&text);
+        if rtf > 1.0 {
+            self.throughput_label.add_css_class(THROUGHPUT_BEHIND_CLASS);
+            self.throughput_label.set_tooltip_text(Some(
+                "Модель розпізнає повільніше, ніж ви говорите, і відставання зростатиме. \
+                 Оберіть меншу модель у меню 'Моделі'.",
+            ));
+        } else {
+            self.throughput_label.remove_css_class(THROUGHPUT_BEHIND_CLASS);
+            self.throughput_label.set_tooltip_text(Some(
+                "Час розпізнавання відносно тривалості аудіо (менше 1 — встигає за мовленням)",
+            ));
+        }
+    }
+
+    /// Show the VAD noise-floor adaptation in the indicator tooltip.
+    pub fn update_noise_floor(&self, state: &NoiseFloorState) {
+        self.vad_indicator.set_tooltip_text(Some(&format!(
+            "Рівень шуму: {:.0} дБFS, поріг мовлення: {:.0} дБFS, зараз: {:.0} дБFS",
+            state.floor_dbfs, state.threshold_dbfs, state.level_dbfs
+        )));
+    }
 }
 
 /// Conference mode specific UI widgets
 #[derive(Clone)]
 pub struct ConferenceUI {
     pub base: UIContext,
-    pub mic_level_bar: LevelBar,
-    pub loopback_level_bar: LevelBar,
+    pub mic_level_bar: LevelMeterView,
+    pub loopback_level_bar: LevelMeterView,
+    /// "Who is speaking now" from live diarization.
+    pub speaker_now_label: Label,
+    /// Live diarization of the running recording, if enabled.
+    pub live_diarization: Rc<RefCell<Option<LiveDiarizationSession>>>,
+    /// Session template picker ("Без шаблону" first).
+    pub template_combo: gtk4::ComboBoxText,
+    /// Template picked when the running recording started.
+    pub session_template: Rc<RefCell<Option<SessionTemplate>>>,
 }
 
 impl ConferenceUI {
-    pub fn new(base: UIContext, mic_level_bar: LevelBar, loopback_level_bar: LevelBar) -> Self {
+    pub fn new(
+        base: UIContext,
+        mic_level_bar: LevelMeterView,
+        loopback_level_bar: LevelMeterView,
+        speaker_now_label: Label,
+        template_combo: gtk4::ComboBoxText,
+    ) -> Self {
         Self {
             base,
             mic_level_bar,
             loopback_level_bar,
+            speaker_now_label,
+            live_diarization: Rc::new(RefCell::new(None)),
+            template_combo,
+            session_template: Rc::new(RefCell::new(None)),
         }
     }
 
     pub fn show_level_bars(&self) {
-        self.mic_level_bar.set_value(0.0);
+        self.mic_level_bar.set(LevelReading::SILENT);
         self.mic_level_bar.set_visible(true);
-        self.loopback_level_bar.set_value(0.0);
+        self.loopback_level_bar.set(LevelReading::SILENT);
         self.loopback_level_bar.set_visible(true);
     }
 
     pub fn hide_level_bars(&self) {
         self.mic_level_bar.set_visible(false);
         self.loopback_level_bar.set_visible(false);
+        self.speaker_now_label.set_visible(false);
+    }
+
+    /// Show the current speaker, or that nobody is speaking.
+    pub fn set_current_speaker(&self, speaker: Option<&str>) {
+        self.speaker_now_label.set_visible(true);
+        match speaker {
+            Some(name) => self.speaker_now_label.set_text(&format!("Говорить: {}", name)),
+            None => self.speaker_now_label.set_text("Говорить: —"),
+        }
     }
 
-    pub fn update_levels(&self, mic_amplitude: f64, loopback_amplitude: f64) {
-        self.mic_level_bar.set_value(mic_amplitude);
-        self.loopback_level_bar.set_value(loopback_amplitude);
+    pub fn update_levels(&self, mic: LevelReading, loopback: LevelReading) {
+        self.mic_level_bar.set(mic);
+        self.loopback_level_bar.set(loopback);
     }
 }
@@ -1,6 +1,11 @@
 use crate::app::config::Config;
+use crate::app::templates::SessionTemplates;
+use crate::ui::a11y;
+use crate::ui::level_meter::LevelMeterView;
+use crate::ui::segment_progress::SegmentProgress;
+use crate::ui::speech_timeline::SpeechTimeline;
 use gtk4::prelude::*;
-use gtk4::{Align, Box as GtkBox, Button, Label, LevelBar, Orientation, ScrolledWindow, Spinner, TextView};
+use gtk4::{Align, Box as GtkBox, Button, Label, Orientation, ScrolledWindow, Spinner, TextView};
 use parking_lot::Mutex;
 use std::sync::Arc;
 
@@ -11,19 +16,28 @@ pub struct MainWidgets {
     pub spinner: Spinner,
     pub timer_label: Label,
     pub mode_combo: gtk4::ComboBoxText,
-    pub level_bar: LevelBar,
+    pub level_bar: LevelMeterView,
+    pub level_warning_label: Label,
+    pub speech_timeline: SpeechTimeline,
     pub level_bars_box: GtkBox,
     pub vad_indicator: Label,
     pub segment_row: GtkBox,
-    pub segment_indicators_box: GtkBox,
-    pub mic_level_bar: LevelBar,
-    pub loopback_level_bar: LevelBar,
+    pub segment_progress: SegmentProgress,
+    pub throughput_label: Label,
+    pub mic_level_bar: LevelMeterView,
+    pub loopback_level_bar: LevelMeterView,
+    pub speaker_now_label: Label,
+    pub template_combo: gtk4::ComboBoxText,
     pub result_text_view: TextView,
+    pub draft_label: Label,
     pub record_button: Button,
     pub copy_button: Button,
+    pub speakers_button: Button,
     pub models_button: Button,
     pub history_button: Button,
+    pub subtitles_button: Button,
     pub settings_button: Button,
+    pub help_button: Button,
 }
 
 /// Creates all main window widgets and assembles the layout.
@@ -44,6 +58,7 @@ pub fn build_main_widgets(config: &Arc<Mutex<Config>>) -> MainWidgets {
 
     let spinner = Spinner::new();
     spinner.set_visible(false);
+    a11y::set_name(&spinner, "Обробка");
 
     status_box.append(&status_label);
     status_box.append(&spinner);
@@ -52,6 +67,8 @@ pub fn build_main_widgets(config: &Arc<Mutex<Config>>) -> MainWidgets {
     let timer_label = Label::new(Some(""));
     timer_label.add_css_class("monospace");
     timer_label.set_visible(false);
+    // A name would replace the elapsed time the screen reader speaks
+    a11y::set_description(&timer_label, "Тривалість запису");
 
     // Mode selector
     let mode_combo = gtk4::ComboBoxText::new();
@@ -76,20 +93,28 @@ pub fn build_main_widgets(config: &Arc<Mutex<Config>>) -> MainWidgets {
     mode_label.set_halign(Align::Start);
     mode_row.append(&mode_label);
     mode_row.append(&mode_combo);
+    a11y::set_labelled_by(&mode_combo, &mode_label);
     mode_row.set_halign(Align::Start);
 
-    // Dictation audio level indicator
-    let level_bar = LevelBar::new();
-    level_bar.set_min_value(0.0);
-    level_bar.set_max_value(1.0);
-    level_bar.set_value(0.0);
+    // Dictation audio level meter
+    let level_bar = LevelMeterView::new("Рівень звуку мікрофона");
     level_bar.set_visible(false);
-    level_bar.set_size_request(200, -1);
+
+    // Clipping or too quiet microphone, under the level bar while recording
+    let level_warning_label = Label::new(None);
+    level_warning_label.add_css_class("level-warning");
+    level_warning_label.set_wrap(true);
+    level_warning_label.set_visible(false);
+    a11y::set_name(&level_warning_label, "Попередження про рівень звуку");
+
+    // Recent VAD decisions under the level bar in continuous mode
+    let speech_timeline = SpeechTimeline::new();
 
     // VAD indicator for continuous mode
     let vad_indicator = Label::new(Some(""));
     vad_indicator.set_visible(false);
     vad_indicator.set_halign(Align::Start);
+    a11y::set_name(&vad_indicator, "Індикатор мовлення");
 
     // Segment progress indicators for continuous mode
     let segment_row = GtkBox::new(Orientation::Horizontal, 8);
@@ -98,15 +123,16 @@ pub fn build_main_widgets(config: &Arc<Mutex<Config>>) -> MainWidgets {
     let segment_label = Label::new(Some("Сегменти:"));
     segment_row.append(&segment_label);
 
-    let segment_indicators_box = GtkBox::new(Orientation::Horizontal, 4);
-    segment_indicators_box.set_halign(Align::Start);
+    let segment_progress = SegmentProgress::new();
+    a11y::set_labelled_by(segment_progress.widget(), &segment_label);
 
-    let segment_scroll = ScrolledWindow::new();
-    segment_scroll.set_policy(gtk4::PolicyType::Automatic, gtk4::PolicyType::Never);
-    segment_scroll.set_hexpand(true);
-    segment_scroll.set_child(Some(&segment_indicators_box));
+    segment_row.append(segment_progress.widget());
 
-    segment_row.append(&segment_scroll);
+    // Real-time factor and transcription backlog of the session
+    let throughput_label = Label::new(None);
+    throughput_label.add_css_class("monospace");
+    a11y::set_name(&throughput_label, "Швидкість розпізнавання");
+    segment_row.append(&throughput_label);
     segment_row.set_hexpand(true);
     segment_row.set_visible(false);
 
@@ -114,30 +140,47 @@ pub fn build_main_widgets(config: &Arc<Mutex<Config>>) -> MainWidgets {
     load_segment_css();
 
     // Conference mode: mic + loopback level bars
-    let mic_level_bar = LevelBar::new();
-    mic_level_bar.set_min_value(0.0);
-    mic_level_bar.set_max_value(1.0);
-    mic_level_bar.set_value(0.0);
+    let mic_level_bar = LevelMeterView::new("Рівень звуку мікрофона");
     mic_level_bar.set_visible(false);
-    mic_level_bar.set_size_request(200, -1);
 
-    let loopback_level_bar = LevelBar::new();
-    loopback_level_bar.set_min_value(0.0);
-    loopback_level_bar.set_max_value(1.0);
-    loopback_level_bar.set_value(0.0);
+    let loopback_level_bar = LevelMeterView::new("Рівень системного аудіо");
     loopback_level_bar.set_visible(false);
-    loopback_level_bar.set_size_request(200, -1);
 
     let level_bars_box = GtkBox::new(Orientation::Vertical, 4);
+    // Session template for the next conference recording (hidden without templates.toml)
+    let template_row = GtkBox::new(Orientation::Horizontal, 8);
+    let template_label = Label::new(Some("Шаблон сесії:"));
+    let template_combo = gtk4::ComboBoxText::new();
+    template_combo.append(None, "Без шаблону");
+    let templates = SessionTemplates::load_default();
+    for template in &templates.templates {
+        template_combo.append(Some(&template.name), &template.name);
+    }
+    template_combo.set_active(Some(0));
+    template_combo.set_tooltip_text(Some("Назва, теги й учасники для запису з templates.toml"));
+    a11y::set_labelled_by(&template_combo, &template_label);
+    template_row.append(&template_label);
+    template_row.append(&template_combo);
+    template_row.set_margin_bottom(6);
+    template_row.set_visible(!templates.templates.is_empty());
+    level_bars_box.append(&template_row);
     let mic_label = Label::new(Some("Мікрофон:"));
     mic_label.set_halign(Align::Start);
     level_bars_box.append(&mic_label);
-    level_bars_box.append(&mic_level_bar);
+    level_bars_box.append(mic_level_bar.widget());
+    a11y::set_labelled_by(mic_level_bar.bar(), &mic_label);
     let loopback_label = Label::new(Some("Системний аудіо:"));
     loopback_label.set_halign(Align::Start);
     loopback_label.set_margin_top(6);
     level_bars_box.append(&loopback_label);
-    level_bars_box.append(&loopback_level_bar);
+    level_bars_box.append(loopback_level_bar.widget());
+    a11y::set_labelled_by(loopback_level_bar.bar(), &loopback_label);
+    // Live diarization: who is speaking now (shown only while it runs)
+    let speaker_now_label = Label::new(None);
+    speaker_now_label.set_halign(Align::Start);
+    speaker_now_label.set_margin_top(6);
+    speaker_now_label.set_visible(false);
+    level_bars_box.append(&speaker_now_label);
     level_bars_box.set_visible(false);
 
     // Editable result display
@@ -146,6 +189,19 @@ pub fn build_main_widgets(config: &Arc<Mutex<Config>>) -> MainWidgets {
     result_text_view.set_editable(true);
     result_text_view.set_cursor_visible(true);
     result_text_view.set_vexpand(true);
+    // Tab moves focus out of the result instead of inserting a tab character
+    result_text_view.set_accepts_tab(false);
+    a11y::set_name_and_description(
+        &result_text_view,
+        "Результат розпізнавання",
+        "Розпізнаний текст, доступний для редагування",
+    );
+
+    // Shown while the result holds a draft restored from the last session
+    let draft_label = Label::new(Some("Чернетку відновлено"));
+    draft_label.add_css_class("dim-label");
+    draft_label.set_halign(Align::Start);
+    draft_label.set_visible(false);
 
     let result_scrolled = ScrolledWindow::new();
     result_scrolled.set_min_content_height(100);
@@ -155,6 +211,7 @@ pub fn build_main_widgets(config: &Arc<Mutex<Config>>) -> MainWidgets {
     let record_button = Button::with_label("Почати запис");
     record_button.add_css_class("suggested-action");
     record_button.add_css_class("pill");
+    record_button.set_tooltip_text(Some("Почати або зупинити запис (Ctrl+R)"));
 
     // Set initial visibility based on saved mode
     // Both conference modes use dual level bars
@@ -166,24 +223,41 @@ pub fn build_main_widgets(config: &Arc<Mutex<Config>>) -> MainWidgets {
     let copy_button = Button::with_label("Копіювати");
     let models_button = Button::with_label("Моделі");
     let history_button = Button::with_label("Історія");
+    let subtitles_button = Button::with_label("Субтитри");
     let settings_button = Button::with_label("Налаштування");
+    let help_button = Button::with_label("Довідка");
+    let speakers_button = Button::with_label("Мовці");
+    speakers_button.set_sensitive(false);
+    speakers_button.set_tooltip_text(Some("Перейменувати мовців у результаті"));
+    copy_button.set_tooltip_text(Some("Копіювати результат (Ctrl+Shift+C)"));
+    models_button.set_tooltip_text(Some("Керування моделями (Ctrl+M)"));
+    history_button.set_tooltip_text(Some("Історія диктовок (Ctrl+H)"));
+    subtitles_button.set_tooltip_text(Some("Виправити субтитри SRT за аудіо"));
+    settings_button.set_tooltip_text(Some("Налаштування (Ctrl+,)"));
+    help_button.set_tooltip_text(Some("Довідка й вирішення проблем (F1)"));
 
     let button_box = GtkBox::new(Orientation::Horizontal, 12);
     button_box.set_halign(Align::Center);
     button_box.append(&record_button);
     button_box.append(&copy_button);
+    button_box.append(&speakers_button);
     button_box.append(&models_button);
     button_box.append(&history_button);
+    button_box.append(&subtitles_button);
     button_box.append(&settings_button);
+    button_box.append(&help_button);
 
     // Assemble layout
     main_box.append(&status_box);
     main_box.append(&mode_row);
     main_box.append(&timer_label);
-    main_box.append(&level_bar);
+    main_box.append(level_bar.widget());
+    main_box.append(&level_warning_label);
+    main_box.append(speech_timeline.widget());
     main_box.append(&vad_indicator);
     main_box.append(&segment_row);
     main_box.append(&level_bars_box);
+    main_box.append(&draft_label);
     main_box.append(&result_scrolled);
     main_box.append(&button_box);
 
@@ -194,18 +268,27 @@ pub fn build_main_widgets(config: &Arc<Mutex<Config>>) -> MainWidgets {
         timer_label,
         mode_combo,
         level_bar,
+        level_warning_label,
+        speech_timeline,
         level_bars_box,
         vad_indicator,
         segment_row,
-        segment_indicators_box,
+        segment_progress,
+        throughput_label,
         mic_level_bar,
         loopback_level_bar,
+        speaker_now_label,
+        template_combo,
         result_text_view,
+        draft_label,
         record_button,
         copy_button,
+        speakers_button,
         models_button,
         history_button,
+        subtitles_button,
         settings_button,
+        help_button,
     }
 }
 
@@ -221,6 +304,25 @@ fn load_segment_css() {
             color: #00aa00;
             font-size: 16px;
         }
+        .segment-error {
+            color: #cc0000;
+            font-size: 16px;
+        }
+        .throughput-behind {
+            color: #cc0000;
+        }
+        levelbar.level-speech block.filled {
+            background-color: #33aa33;
+        }
+        levelbar.level-noise block.filled {
+            background-color: #999999;
+        }
+        .level-warning {
+            background-color: #fff3cd;
+            color: #664d03;
+            border-radius: 6px;
+            padding: 6px 10px;
+        }
         "#,
     );
     gtk4::style_context_add_provider_for_display(
@@ -0,0 +1,234 @@
+//! VAD calibration from a short recording of ambient noise and speech.
+//!
+//! The noise sample gives the floor the detector must ignore, the speech
+//! sample the level and Silero probabilities it must catch, and the pauses
+//! inside the speech sample show how long the user stops mid-sentence.
+//! From these the calibration recommends the Silero threshold, the silence
+//! duration that ends a segment and the WebRTC aggressiveness.
+
+use crate::domain::traits::VoiceDetection;
+use anyhow::{bail, Result};
+
+use super::silero::{SileroVoiceDetector, CHUNK_SIZE};
+
+const SAMPLE_RATE_HZ: usize = 16000;
+
+/// Level reported for digital silence.
+const SILENCE_DBFS: f32 = -100.0;
+
+/// Minimum gap between speech and noise level to calibrate at all.
+const MIN_SNR_DB: f32 = 6.0;
+
+/// Range of recommended Silero thresholds.
+const MIN_SILERO_THRESHOLD: f32 = 0.15;
+const MAX_SILERO_THRESHOLD: f32 = 0.9;
+
+/// Range of recommended silence thresholds.
+const MIN_SILENCE_MS: u32 = 500;
+const MAX_SILENCE_MS: u32 = 2500;
+
+/// Added to the longest natural pause so a segment is not cut mid-sentence.
+const PAUSE_MARGIN_MS: u32 = 300;
+
+/// Silence threshold when the speech sample has no pauses.
+const FALLBACK_SILENCE_MS: u32 = 800;
+
+/// Shortest recording accepted for either phase.
+const MIN_SAMPLE_SECS: f32 = 1.0;
+
+/// Measured levels and recommended VAD settings.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VadCalibration {
+    /// Median level of the ambient noise, dBFS.
+    pub noise_dbfs: f32,
+    /// Level of active speech, dBFS.
+    pub speech_dbfs: f32,
+    pub silero_threshold: f32,
+    pub silence_threshold_ms: u32,
+    pub webrtc_aggressiveness: u8,
+}
+
+impl VadCalibration {
+    /// Difference between speech and noise level, dB.
+    pub fn snr_db(&self) -> f32 {
+        self.speech_dbfs - self.noise_dbfs
+    }
+}
+
+/// Calibrate from 16 kHz mono recordings of ambient noise and speech.
+///
+/// Runs Silero over both samples, so call it off the GTK main thread.
+pub fn calibrate(noise: &[f32], speech: &[f32]) -> Result<VadCalibration> {
+    let min_samples = (MIN_SAMPLE_SECS * SAMPLE_RATE_HZ as f32) as usize;
+    if noise.len() < min_samples || speech.len() < min_samples {
+        bail!("Запис закороткий для калібрування");
+    }
+    let detector = SileroVoiceDetector::new()?;
+    let noise_probs = detector.speech_probabilities(noise);
+    detector.reset();
+    let speech_probs = detector.speech_probabilities(speech);
+    recommend(noise, speech, &noise_probs, &speech_probs)
+}
+
+/// Derive the calibration from the samples and their per-chunk Silero probabilities.
+fn recommend(noise: &[f32], speech: &[f32], noise_probs: &[f32], speech_probs: &[f32]) -> Result<VadCalibration> {
+    let noise_dbfs = percentile(&frame_dbfs(noise), 0.5);
+    // Speech sample includes breaths and pauses; its loud part is the speech
+    let speech_dbfs = percentile(&frame_dbfs(speech), 0.9);
+    if speech_dbfs - noise_dbfs < MIN_SNR_DB {
+        bail!(
+            "Мовлення майже не відрізняється від шуму ({:.0} дБ проти {:.0} дБ)",
+            speech_dbfs,
+            noise_dbfs
+        );
+    }
+
+    // Midway between the loudest noise and typical speech probabilities
+    let noise_high = percentile(noise_probs, 0.95);
+    let speech_typical = percentile(speech_probs, 0.75);
+    let silero_threshold = ((noise_high + speech_typical) / 2.0).clamp(MIN_SILERO_THRESHOLD, MAX_SILERO_THRESHOLD);
+
+    let silence_threshold_ms = longest_pause_ms(speech_probs, silero_threshold)
+        .map(|pause| round_to_100(pause + PAUSE_MARGIN_MS))
+        .unwrap_or(FALLBACK_SILENCE_MS)
+        .clamp(MIN_SILENCE_MS, MAX_SILENCE_MS);
+
+    let snr = speech_dbfs - noise_dbfs;
+    let webrtc_aggressiveness = if snr >= 30.0 {
+        1
+    } else if snr >= 15.0 {
+        2
+    } else {
+        3
+    };
+
+    Ok(VadCalibration {
+        noise_dbfs,
+        speech_dbfs,
+        silero_threshold,
+        silence_threshold_ms,
+        webrtc_aggressiveness,
+    })
+}
+
+/// RMS level of each Silero-sized chunk, dBFS.
+fn frame_dbfs(samples: &[f32]) -> Vec<f32> {
+    samples
+        .chunks_exact(CHUNK_SIZE)
+        .map(|frame| {
+            let rms = (frame.iter().map(|s| s * s).sum::<f32>() / frame.len() as f32).sqrt();
+            if rms > 0.0 {
+                (20.0 * rms.log10()).max(SILENCE_DBFS)
+            } else {
+                SILENCE_DBFS
+            }
+        })
+        .collect()
+}
+
+/// Value below which `p` (0.0–1.0) of `values` fall.
+fn percentile(values: &[f32], p: f32) -> f32 {
+    if values.is_empty() {
+        return 0.0;
+    }
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let index = ((sorted.len() - 1) as f32 * p).round() as usize;
+    sorted[index]
+}
+
+/// Longest run of non-speech chunks between two speech chunks.
+fn longest_pause_ms(probs: &[f32], threshold: f32) -> Option<u32> {
+    let first = probs.iter().position(|&p| p >= threshold)?;
+    let last = probs.iter().rposition(|&p| p >= threshold)?;
+    let chunk_ms = (CHUNK_SIZE * 1000 / SAMPLE_RATE_HZ) as u32;
+
+    let mut longest = 0;
+    let mut current = 0;
+    for &p in &probs[first..=last] {
+        if p >= threshold {
+            longest = longest.max(current);
+            current = 0;
+        } else {
+            current += 1;
+        }
+    }
+    (longest > 0).then_some(longest * chunk_ms)
+}
+
+fn round_to_100(ms: u32) -> u32 {
+    ms.div_ceil(100) * 100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(amplitude: f32, secs: f32) -> Vec<f32> {
+        let len = (secs * SAMPLE_RATE_HZ as f32) as usize;
+        (0..len)
+            .map(|i| amplitude * (i as f32 * 440.0 * std::f32::consts::TAU / SAMPLE_RATE_HZ as f32).sin())
+            .collect()
+    }
+
+    /// Probabilities of speech chunks with a pause of `pause_chunks` in the middle.
+    fn speech_probs(pause_chunks: usize) -> Vec<f32> {
+        let mut probs = vec![0.9; 40];
+        probs.extend(std::iter::repeat(0.1).take(pause_chunks));
+        probs.extend(std::iter::repeat(0.85).take(40));
+        probs
+    }
+
+    #[test]
+    fn test_recommend_quiet_room() {
+        let noise = tone(0.001, 2.0);
+        let speech = tone(0.2, 3.0);
+        let calibration = recommend(&noise, &speech, &[0.05; 60], &speech_probs(20)).unwrap();
+
+        assert!((calibration.noise_dbfs - -63.0).abs() < 1.0);
+        assert!((calibration.speech_dbfs - -17.0).abs() < 1.0);
+        assert!(calibration.silero_threshold > 0.4 && calibration.silero_threshold < 0.5);
+        // 20 chunks of 32 ms plus the margin
+        assert_eq!(calibration.silence_threshold_ms, 1000);
+        assert_eq!(calibration.webrtc_aggressiveness, 1);
+    }
+
+    #[test]
+    fn test_recommend_noisy_room() {
+        let noise = tone(0.05, 2.0);
+        let speech = tone(0.15, 3.0);
+        let calibration = recommend(&noise, &speech, &[0.3; 60], &[0.95; 80]).unwrap();
+
+        assert_eq!(calibration.webrtc_aggressiveness, 3);
+        assert!(calibration.silero_threshold > 0.6);
+        assert_eq!(calibration.silence_threshold_ms, FALLBACK_SILENCE_MS);
+    }
+
+    #[test]
+    fn test_recommend_rejects_speech_at_noise_level() {
+        let noise = tone(0.1, 2.0);
+        let speech = tone(0.12, 2.0);
+        assert!(recommend(&noise, &speech, &[0.1; 60], &[0.9; 60]).is_err());
+    }
+
+    #[test]
+    fn test_silence_threshold_is_clamped() {
+        let noise = tone(0.001, 2.0);
+        let speech = tone(0.2, 3.0);
+        let calibration = recommend(&noise, &speech, &[0.05; 60], &speech_probs(200)).unwrap();
+        assert_eq!(calibration.silence_threshold_ms, MAX_SILENCE_MS);
+    }
+
+    #[test]
+    fn test_longest_pause_ignores_leading_and_trailing_silence() {
+        let probs = [0.0, 0.0, 0.9, 0.1, 0.1, 0.9, 0.0, 0.0, 0.0];
+        assert_eq!(longest_pause_ms(&probs, 0.5), Some(64));
+        assert_eq!(longest_pause_ms(&[0.9, 0.9], 0.5), None);
+        assert_eq!(longest_pause_ms(&[0.1, 0.1], 0.5), None);
+    }
+
+    #[test]
+    fn test_calibrate_rejects_short_recording() {
+        assert!(calibrate(&[0.0; 1000], &tone(0.2, 2.0)).is_err());
+    }
+}
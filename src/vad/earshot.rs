@@ -0,0 +1,129 @@
+//! Earshot-based Voice Activity Detection.
+//!
+//! Uses the earshot crate: a pure-Rust VAD with no native dependencies or
+//! model files. Faster than Silero and more robust to noise than WebRTC.
+
+use crate::domain::traits::VoiceDetection;
+use ::earshot::{VoiceActivityDetector, VoiceActivityProfile};
+use anyhow::Result;
+use std::cell::RefCell;
+
+const SAMPLE_RATE_HZ: u32 = 16000;
+const FRAME_SIZE_MS: u32 = 30; // 30ms frames for VAD
+const FRAME_SIZE_SAMPLES: usize = (SAMPLE_RATE_HZ as usize * FRAME_SIZE_MS as usize) / 1000;
+
+/// Earshot-based Voice Activity Detector.
+///
+/// # Thread Safety
+///
+/// Keeps detector state in a `RefCell`, so it is `!Sync`. Create a new
+/// instance for each thread that needs VAD functionality.
+pub struct EarshotVoiceDetector {
+    vad: RefCell<VoiceActivityDetector>,
+    silence_threshold_ms: u32,
+}
+
+impl EarshotVoiceDetector {
+    /// Create a new VAD instance with default thresholds.
+    pub fn new() -> Result<Self> {
+        Self::with_thresholds(1000, 500)
+    }
+
+    /// Create a new VAD instance with custom thresholds.
+    ///
+    /// # Arguments
+    /// * `silence_threshold_ms` - Duration of silence to trigger speech end
+    /// * `_min_speech_duration_ms` - Minimum speech duration (currently unused)
+    pub fn with_thresholds(silence_threshold_ms: u32, _min_speech_duration_ms: u32) -> Result<Self> {
+        Ok(Self {
+            vad: RefCell::new(VoiceActivityDetector::new(VoiceActivityProfile::AGGRESSIVE)),
+            silence_threshold_ms,
+        })
+    }
+}
+
+impl Default for EarshotVoiceDetector {
+    fn default() -> Self {
+        Self::new().expect("Failed to initialize Earshot VAD")
+    }
+}
+
+impl VoiceDetection for EarshotVoiceDetector {
+    fn is_speech(&self, samples: &[f32]) -> Result<bool> {
+        if samples.len() < FRAME_SIZE_SAMPLES {
+            return Ok(false);
+        }
+
+        let frame: Vec<i16> = samples[..FRAME_SIZE_SAMPLES]
+            .iter()
+            .map(|&s| (s * 32767.0).clamp(-32768.0, 32767.0) as i16)
+            .collect();
+
+        self.vad
+            .borrow_mut()
+            .predict_16khz(&frame)
+            .map_err(|e| anyhow::anyhow!("Earshot VAD error: {:?}", e))
+    }
+
+    fn detect_speech_end(&self, recent_samples: &[f32]) -> Result<bool> {
+        let silence_needed = (self.silence_threshold_ms * SAMPLE_RATE_HZ / 1000) as usize;
+        let mut consecutive_silence = 0;
+        let mut had_speech = false;
+
+        // Process frames in reverse order (most recent first)
+        for chunk in recent_samples.chunks(FRAME_SIZE_SAMPLES).rev() {
+            if chunk.len() < FRAME_SIZE_SAMPLES {
+                continue;
+            }
+            if self.is_speech(chunk)? {
+                had_speech = true;
+                break;
+            }
+            consecutive_silence += chunk.len();
+        }
+
+        Ok(had_speech && consecutive_silence >= silence_needed)
+    }
+
+    fn reset(&self) {
+        *self.vad.borrow_mut() = VoiceActivityDetector::new(VoiceActivityProfile::AGGRESSIVE);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_earshot_vad_default() {
+        let vad = EarshotVoiceDetector::default();
+        assert_eq!(vad.silence_threshold_ms, 1000);
+    }
+
+    #[test]
+    fn test_earshot_vad_silence_not_speech() {
+        let vad = EarshotVoiceDetector::new().unwrap();
+        let silence = vec![0.0f32; FRAME_SIZE_SAMPLES];
+        assert!(!vad.is_speech(&silence).unwrap());
+        assert!(!vad.is_speech(&silence[..FRAME_SIZE_SAMPLES - 1]).unwrap());
+        assert!(!vad.is_speech(&[]).unwrap());
+    }
+
+    #[test]
+    fn test_earshot_vad_detect_speech_end_pure_silence() {
+        let vad = EarshotVoiceDetector::with_thresholds(500, 200).unwrap();
+        let silence = vec![0.0f32; FRAME_SIZE_SAMPLES * 100];
+        assert!(
+            !vad.detect_speech_end(&silence).unwrap(),
+            "Pure silence should not trigger speech end (no speech preceded it)"
+        );
+    }
+
+    #[test]
+    fn test_earshot_vad_reset() {
+        let vad = EarshotVoiceDetector::new().unwrap();
+        vad.reset();
+        let silence = vec![0.0f32; FRAME_SIZE_SAMPLES];
+        assert!(!vad.is_speech(&silence).unwrap());
+    }
+}
@@ -3,19 +3,37 @@
 //! Provides multiple VAD implementations:
 //! - WebRTC VAD: Fast, energy-based, good for quiet environments
 //! - Silero VAD: Neural network-based, more accurate in noisy environments
+//! - Earshot VAD: Pure Rust, lighter than Silero, more robust than WebRTC
+//!
+//! Any of them can be wrapped in `AdaptiveVoiceDetector`, which also requires
+//! energy above a running noise-floor estimate.
 //!
 //! Use `create_vad()` factory function to create the appropriate detector
 //! based on configuration.
 
+mod calibration;
+mod earshot;
+mod noise_floor;
+mod onset;
 mod silero;
+mod trim;
 mod webrtc;
 
+pub use self::earshot::EarshotVoiceDetector;
+pub use calibration::{calibrate, VadCalibration};
+pub use noise_floor::{
+    AdaptiveVoiceDetector, NoiseFloorState, NoiseFloorTracker, SharedNoiseFloor, DEFAULT_INITIAL_FLOOR_DBFS,
+    DEFAULT_MARGIN_DB,
+};
+pub use onset::speech_at_start;
 pub use silero::SileroVoiceDetector;
+pub use trim::{trim_silence, SilenceTrim};
 pub use webrtc::WebRtcVoiceDetector;
 
 use crate::domain::traits::VoiceDetection;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 /// VAD engine selection.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -26,6 +44,8 @@ pub enum VadEngine {
     WebRTC,
     /// Silero VAD (neural network, more accurate)
     Silero,
+    /// Earshot VAD (pure Rust, lightweight)
+    Earshot,
 }
 
 impl VadEngine {
@@ -33,16 +53,26 @@ impl VadEngine {
     pub fn parse(s: &str) -> Self {
         match s.to_lowercase().as_str() {
             "silero" => VadEngine::Silero,
+            "earshot" => VadEngine::Earshot,
             _ => VadEngine::WebRTC,
         }
     }
 
+    /// Frame length the engine classifies at 16 kHz: a Silero chunk, 30 ms otherwise.
+    pub fn frame_samples(&self) -> usize {
+        match self {
+            VadEngine::Silero => 512,
+            VadEngine::WebRTC | VadEngine::Earshot => 480,
+        }
+    }
+
     /// Convert to string representation.
     #[cfg(test)]
     pub fn as_str(&self) -> &'static str {
         match self {
             VadEngine::WebRTC => "webrtc",
             VadEngine::Silero => "silero",
+            VadEngine::Earshot => "earshot",
         }
     }
 }
@@ -53,6 +83,10 @@ pub struct VadConfig {
     pub silence_threshold_ms: u32,
     pub min_speech_ms: u32,
     pub silero_threshold: f32,
+    /// WebRTC aggressiveness, 0–3 (ignored by other engines)
+    pub webrtc_aggressiveness: u8,
+    /// Silero model file; `None` uses the copy built into the crate
+    pub silero_model_path: Option<PathBuf>,
 }
 
 impl Default for VadConfig {
@@ -62,6 +96,8 @@ impl Default for VadConfig {
             silence_threshold_ms: 1000,
             min_speech_ms: 500,
             silero_threshold: 0.5,
+            webrtc_aggressiveness: webrtc::DEFAULT_AGGRESSIVENESS,
+            silero_model_path: None,
         }
     }
 }
@@ -77,17 +113,26 @@ impl Default for VadConfig {
 pub fn create_vad(config: &VadConfig) -> Result<Box<dyn VoiceDetection>> {
     match config.engine {
         VadEngine::WebRTC => {
-            let vad = WebRtcVoiceDetector::with_thresholds(config.silence_threshold_ms, config.min_speech_ms)?;
+            let vad = WebRtcVoiceDetector::with_aggressiveness(
+                config.silence_threshold_ms,
+                config.min_speech_ms,
+                config.webrtc_aggressiveness,
+            )?;
             Ok(Box::new(vad))
         }
         VadEngine::Silero => {
-            let vad = SileroVoiceDetector::with_thresholds(
+            let vad = SileroVoiceDetector::with_model(
+                config.silero_model_path.as_deref(),
                 config.silero_threshold,
                 config.silence_threshold_ms,
                 config.min_speech_ms,
             )?;
             Ok(Box::new(vad))
         }
+        VadEngine::Earshot => {
+            let vad = EarshotVoiceDetector::with_thresholds(config.silence_threshold_ms, config.min_speech_ms)?;
+            Ok(Box::new(vad))
+        }
     }
 }
 
@@ -101,6 +146,7 @@ mod tests {
         assert_eq!(VadEngine::parse("WebRTC"), VadEngine::WebRTC);
         assert_eq!(VadEngine::parse("silero"), VadEngine::Silero);
         assert_eq!(VadEngine::parse("Silero"), VadEngine::Silero);
+        assert_eq!(VadEngine::parse("earshot"), VadEngine::Earshot);
         assert_eq!(VadEngine::parse("unknown"), VadEngine::WebRTC);
     }
 
@@ -108,6 +154,7 @@ mod tests {
     fn test_vad_engine_as_str() {
         assert_eq!(VadEngine::WebRTC.as_str(), "webrtc");
         assert_eq!(VadEngine::Silero.as_str(), "silero");
+        assert_eq!(VadEngine::Earshot.as_str(), "earshot");
     }
 
     #[test]
@@ -117,6 +164,8 @@ mod tests {
         assert_eq!(config.silence_threshold_ms, 1000);
         assert_eq!(config.min_speech_ms, 500);
         assert_eq!(config.silero_threshold, 0.5);
+        assert_eq!(config.webrtc_aggressiveness, 2);
+        assert!(config.silero_model_path.is_none());
     }
 
     #[test]
@@ -129,6 +178,26 @@ mod tests {
         assert!(vad.is_ok());
     }
 
+    #[test]
+    fn test_create_vad_webrtc_aggressiveness() {
+        for webrtc_aggressiveness in 0..=3 {
+            let config = VadConfig {
+                webrtc_aggressiveness,
+                ..Default::default()
+            };
+            assert!(create_vad(&config).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_create_vad_earshot() {
+        let config = VadConfig {
+            engine: VadEngine::Earshot,
+            ..Default::default()
+        };
+        assert!(create_vad(&config).is_ok());
+    }
+
     #[test]
     fn test_create_vad_silero() {
         let config = VadConfig {
@@ -0,0 +1,318 @@
+//! Running noise-floor estimate and adaptive energy gate for VAD.
+//!
+//! Background noise changes during long sessions: air conditioning turns on,
+//! a window opens. A fixed detector then either treats the new hum as speech
+//! and never ends a segment, or misses quiet speech once the room calms down.
+//!
+//! `NoiseFloorTracker` follows the noise with minimum statistics: the floor
+//! is the quietest smoothed frame level of the last few seconds. Speech always
+//! has short pauses, so it does not lift the floor, while a lasting change in
+//! the noise moves it within one window. `AdaptiveVoiceDetector` wraps any
+//! detector and only accepts frames that are louder than the floor by a margin.
+
+use crate::domain::traits::VoiceDetection;
+use anyhow::Result;
+use parking_lot::Mutex;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+const SAMPLE_RATE_HZ: usize = 16000;
+
+/// Frame length for level analysis (32 ms, a full Silero chunk).
+pub const FRAME_SAMPLES: usize = 512;
+
+/// How far back the quietest frame is searched.
+const WINDOW_SECS: f32 = 5.0;
+
+/// Smoothing of frame levels, so a single quiet frame does not drag the floor down.
+const LEVEL_SMOOTHING: f32 = 0.3;
+
+/// Speech must be this much louder than the floor.
+pub const DEFAULT_MARGIN_DB: f32 = 10.0;
+
+/// Floor assumed before any audio (a quiet room).
+pub const DEFAULT_INITIAL_FLOOR_DBFS: f32 = -60.0;
+
+/// Level reported for digital silence.
+const MIN_DBFS: f32 = -100.0;
+
+/// Snapshot of the adaptation for debugging and UI.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NoiseFloorState {
+    /// Current noise floor estimate, dBFS.
+    pub floor_dbfs: f32,
+    /// Energy a frame needs to count as speech, dBFS.
+    pub threshold_dbfs: f32,
+    /// Level of the last analyzed frame, dBFS.
+    pub level_dbfs: f32,
+    /// Frames analyzed since the start of the session.
+    pub frames: u64,
+}
+
+/// Minimum-statistics noise floor estimate.
+pub struct NoiseFloorTracker {
+    margin_db: f32,
+    initial_floor_dbfs: f32,
+    floor_dbfs: f32,
+    smoothed_dbfs: Option<f32>,
+    level_dbfs: f32,
+    window: VecDeque<f32>,
+    window_frames: usize,
+    /// Samples of an incomplete frame carried to the next update.
+    pending: Vec<f32>,
+    frames: u64,
+}
+
+/// Tracker shared between the segmentation thread and its observers.
+pub type SharedNoiseFloor = Arc<Mutex<NoiseFloorTracker>>;
+
+impl NoiseFloorTracker {
+    /// Start from `initial_floor_dbfs`, e.g. the calibrated noise level.
+    pub fn new(initial_floor_dbfs: f32, margin_db: f32) -> Self {
+        let window_frames = (WINDOW_SECS * SAMPLE_RATE_HZ as f32 / FRAME_SAMPLES as f32).ceil() as usize;
+        Self {
+            margin_db,
+            initial_floor_dbfs,
+            floor_dbfs: initial_floor_dbfs,
+            smoothed_dbfs: None,
+            level_dbfs: MIN_DBFS,
+            window: VecDeque::with_capacity(window_frames),
+            window_frames,
+            pending: Vec::new(),
+            frames: 0,
+        }
+    }
+
+    /// Feed newly recorded samples (each sample exactly once).
+    pub fn update(&mut self, samples: &[f32]) {
+        self.pending.extend_from_slice(samples);
+        let complete = self.pending.len() / FRAME_SAMPLES * FRAME_SAMPLES;
+        let frames: Vec<f32> = self.pending[..complete]
+            .chunks_exact(FRAME_SAMPLES)
+            .map(level_dbfs)
+            .collect();
+        self.pending.drain(..complete);
+        for level in frames {
+            self.push_level(level);
+        }
+    }
+
+    fn push_level(&mut self, level: f32) {
+        let smoothed = match self.smoothed_dbfs {
+            Some(prev) => prev + LEVEL_SMOOTHING * (level - prev),
+            None => level,
+        };
+        self.smoothed_dbfs = Some(smoothed);
+        self.level_dbfs = level;
+        self.frames += 1;
+
+        if self.window.len() == self.window_frames {
+            self.window.pop_front();
+        }
+        self.window.push_back(smoothed);
+        // Until a full window is seen, the initial floor caps the estimate
+        let quietest = self.window.iter().copied().fold(f32::INFINITY, f32::min);
+        self.floor_dbfs = if self.window.len() < self.window_frames {
+            quietest.min(self.initial_floor_dbfs.max(self.floor_dbfs))
+        } else {
+            quietest
+        };
+    }
+
+    pub fn threshold_dbfs(&self) -> f32 {
+        self.floor_dbfs + self.margin_db
+    }
+
+    /// Whether any frame of `samples` is louder than the threshold.
+    pub fn is_above_threshold(&self, samples: &[f32]) -> bool {
+        let threshold = self.threshold_dbfs();
+        if samples.len() < FRAME_SAMPLES {
+            return level_dbfs(samples) >= threshold;
+        }
+        samples
+            .chunks_exact(FRAME_SAMPLES)
+            .any(|frame| level_dbfs(frame) >= threshold)
+    }
+
+    pub fn state(&self) -> NoiseFloorState {
+        NoiseFloorState {
+            floor_dbfs: self.floor_dbfs,
+            threshold_dbfs: self.threshold_dbfs(),
+            level_dbfs: self.level_dbfs,
+            frames: self.frames,
+        }
+    }
+
+    /// Forget the session and start again from the initial floor.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.initial_floor_dbfs, self.margin_db);
+    }
+}
+
+/// RMS level of `samples`, dBFS.
+fn level_dbfs(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return MIN_DBFS;
+    }
+    let rms = (samples.iter().map(|s| s * s).sum::<f32>() / samples.len() as f32).sqrt();
+    if rms > 0.0 {
+        (20.0 * rms.log10()).max(MIN_DBFS)
+    } else {
+        MIN_DBFS
+    }
+}
+
+/// Detector that also requires energy above the running noise floor.
+///
+/// The tracker is fed by the owner of the audio stream; the detector only reads it.
+pub struct AdaptiveVoiceDetector {
+    inner: Box<dyn VoiceDetection>,
+    noise_floor: SharedNoiseFloor,
+    silence_threshold_ms: u32,
+}
+
+impl AdaptiveVoiceDetector {
+    pub fn new(inner: Box<dyn VoiceDetection>, noise_floor: SharedNoiseFloor, silence_threshold_ms: u32) -> Self {
+        Self {
+            inner,
+            noise_floor,
+            silence_threshold_ms,
+        }
+    }
+}
+
+impl VoiceDetection for AdaptiveVoiceDetector {
+    fn is_speech(&self, samples: &[f32]) -> Result<bool> {
+        if !self.noise_floor.lock().is_above_threshold(samples) {
+            return Ok(false);
+        }
+        self.inner.is_speech(samples)
+    }
+
+    fn detect_speech_end(&self, recent_samples: &[f32]) -> Result<bool> {
+        let silence_needed = self.silence_threshold_ms as usize * SAMPLE_RATE_HZ / 1000;
+        let mut consecutive_silence = 0;
+        let mut had_speech = false;
+
+        // Process frames in reverse order (most recent first)
+        for frame in recent_samples.chunks(FRAME_SAMPLES).rev() {
+            if frame.len() < FRAME_SAMPLES {
+                continue;
+            }
+            if self.is_speech(frame)? {
+                had_speech = true;
+                break;
+            }
+            consecutive_silence += frame.len();
+        }
+
+        Ok(had_speech && consecutive_silence >= silence_needed)
+    }
+
+    fn reset(&self) {
+        self.inner.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sine frames at the given RMS level.
+    fn tone(level_dbfs: f32, secs: f32) -> Vec<f32> {
+        let amplitude = 10f32.powf(level_dbfs / 20.0) * std::f32::consts::SQRT_2;
+        let len = (secs * SAMPLE_RATE_HZ as f32) as usize;
+        (0..len)
+            .map(|i| amplitude * (i as f32 * 440.0 * std::f32::consts::TAU / SAMPLE_RATE_HZ as f32).sin())
+            .collect()
+    }
+
+    /// Treats every frame as speech, so only the energy gate decides.
+    struct AlwaysSpeech;
+
+    impl VoiceDetection for AlwaysSpeech {
+        fn is_speech(&self, _samples: &[f32]) -> Result<bool> {
+            Ok(true)
+        }
+        fn detect_speech_end(&self, _samples: &[f32]) -> Result<bool> {
+            Ok(false)
+        }
+        fn reset(&self) {}
+    }
+
+    #[test]
+    fn test_floor_follows_rising_noise() {
+        let mut tracker = NoiseFloorTracker::new(DEFAULT_INITIAL_FLOOR_DBFS, DEFAULT_MARGIN_DB);
+        tracker.update(&tone(-60.0, 6.0));
+        assert!((tracker.state().floor_dbfs - -60.0).abs() < 0.5);
+
+        // Air conditioning turns on
+        tracker.update(&tone(-40.0, 6.0));
+        let state = tracker.state();
+        assert!((state.floor_dbfs - -40.0).abs() < 0.5);
+        assert!((state.threshold_dbfs - -30.0).abs() < 0.5);
+    }
+
+    #[test]
+    fn test_floor_ignores_speech_with_pauses() {
+        let mut tracker = NoiseFloorTracker::new(DEFAULT_INITIAL_FLOOR_DBFS, DEFAULT_MARGIN_DB);
+        tracker.update(&tone(-55.0, 2.0));
+        for _ in 0..5 {
+            tracker.update(&tone(-20.0, 2.0));
+            tracker.update(&tone(-55.0, 0.4));
+        }
+        assert!(tracker.state().floor_dbfs < -50.0);
+    }
+
+    #[test]
+    fn test_floor_drops_immediately() {
+        let mut tracker = NoiseFloorTracker::new(DEFAULT_INITIAL_FLOOR_DBFS, DEFAULT_MARGIN_DB);
+        tracker.update(&tone(-40.0, 6.0));
+        tracker.update(&tone(-65.0, 0.5));
+        assert!(tracker.state().floor_dbfs < -60.0);
+    }
+
+    #[test]
+    fn test_initial_floor_caps_estimate_at_start() {
+        let mut tracker = NoiseFloorTracker::new(-60.0, DEFAULT_MARGIN_DB);
+        // Speech right at the start must not become the floor
+        tracker.update(&tone(-20.0, 1.0));
+        assert_eq!(tracker.state().floor_dbfs, -60.0);
+        assert_eq!(tracker.state().frames, 31);
+    }
+
+    #[test]
+    fn test_update_carries_partial_frames() {
+        let mut tracker = NoiseFloorTracker::new(DEFAULT_INITIAL_FLOOR_DBFS, DEFAULT_MARGIN_DB);
+        tracker.update(&vec![0.0; FRAME_SAMPLES / 2]);
+        assert_eq!(tracker.state().frames, 0);
+        tracker.update(&vec![0.0; FRAME_SAMPLES / 2]);
+        assert_eq!(tracker.state().frames, 1);
+    }
+
+    #[test]
+    fn test_adaptive_detector_gates_noise() {
+        let noise_floor: SharedNoiseFloor = Arc::new(Mutex::new(NoiseFloorTracker::new(-60.0, DEFAULT_MARGIN_DB)));
+        noise_floor.lock().update(&tone(-40.0, 6.0));
+        let vad = AdaptiveVoiceDetector::new(Box::new(AlwaysSpeech), noise_floor.clone(), 500);
+
+        assert!(!vad.is_speech(&tone(-38.0, 0.1)).unwrap());
+        assert!(vad.is_speech(&tone(-20.0, 0.1)).unwrap());
+
+        let mut recent = tone(-20.0, 1.0);
+        recent.extend(tone(-40.0, 0.6));
+        assert!(vad.detect_speech_end(&recent).unwrap());
+        recent.extend(tone(-20.0, 0.1));
+        assert!(!vad.detect_speech_end(&recent).unwrap());
+    }
+
+    #[test]
+    fn test_reset_restores_initial_floor() {
+        let mut tracker = NoiseFloorTracker::new(-55.0, DEFAULT_MARGIN_DB);
+        tracker.update(&tone(-30.0, 6.0));
+        tracker.reset();
+        let state = tracker.state();
+        assert_eq!(state.floor_dbfs, -55.0);
+        assert_eq!(state.frames, 0);
+    }
+}
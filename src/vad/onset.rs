@@ -0,0 +1,75 @@
+//! Check for a clipped start of a dictation.
+//!
+//! When the hotkey is pressed a moment after the user starts talking, the
+//! recording begins in the middle of a word and the first words are lost.
+//! After stop, the first frames of the recording are run through the VAD:
+//! if they are already speech, the start was most likely cut off.
+
+use crate::domain::traits::VoiceDetection;
+use anyhow::Result;
+
+/// Frame size for the check (32 ms at 16kHz, accepted by every VAD engine).
+const FRAME_SAMPLES: usize = 512;
+
+/// How many leading frames are checked (~190 ms).
+const ONSET_FRAMES: usize = 6;
+
+/// Speech frames among the leading ones that count as a clipped start.
+const MIN_SPEECH_FRAMES: usize = 4;
+
+/// Whether speech is already going on in the first frames of `samples`.
+///
+/// Recordings too short to hold the leading frames are never reported.
+pub fn speech_at_start(vad: &dyn VoiceDetection, samples: &[f32]) -> Result<bool> {
+    if samples.len() < FRAME_SAMPLES * ONSET_FRAMES {
+        return Ok(false);
+    }
+    vad.reset();
+    let mut speech_frames = 0;
+    for frame in samples.chunks_exact(FRAME_SAMPLES).take(ONSET_FRAMES) {
+        if vad.is_speech(frame)? {
+            speech_frames += 1;
+        }
+    }
+    Ok(speech_frames >= MIN_SPEECH_FRAMES)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Treats loud frames as speech.
+    struct LevelVad;
+
+    impl VoiceDetection for LevelVad {
+        fn is_speech(&self, samples: &[f32]) -> Result<bool> {
+            Ok(samples.iter().any(|s| s.abs() > 0.1))
+        }
+
+        fn detect_speech_end(&self, _samples: &[f32]) -> Result<bool> {
+            Ok(false)
+        }
+
+        fn reset(&self) {}
+    }
+
+    fn recording(leading_silence: usize, total: usize) -> Vec<f32> {
+        (0..total)
+            .map(|i| if i < leading_silence { 0.0 } else { 0.5 })
+            .collect()
+    }
+
+    #[test]
+    fn test_speech_at_start_detects_clipped_onset() {
+        assert!(speech_at_start(&LevelVad, &recording(0, 16000)).unwrap());
+        // Speech begins after a short pause: the start is intact
+        assert!(!speech_at_start(&LevelVad, &recording(4000, 16000)).unwrap());
+        // Speech begins within the first frames only partly
+        assert!(!speech_at_start(&LevelVad, &recording(FRAME_SAMPLES * 3, 16000)).unwrap());
+    }
+
+    #[test]
+    fn test_speech_at_start_ignores_short_recordings() {
+        assert!(!speech_at_start(&LevelVad, &recording(0, FRAME_SAMPLES * 2)).unwrap());
+    }
+}
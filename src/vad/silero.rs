@@ -1,16 +1,43 @@
 //! Silero VAD - Neural network-based Voice Activity Detection.
 //!
-//! Uses the voice_activity_detector crate which bundles the Silero ONNX model.
+//! Uses the voice_activity_detector crate, which bundles the Silero ONNX
+//! model; a downloaded or configured model file is used instead when given.
 //! More accurate than WebRTC VAD, especially in noisy environments.
 
 use crate::domain::traits::VoiceDetection;
 use anyhow::Result;
+use ort::session::Session;
+use parking_lot::Mutex;
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock};
 use voice_activity_detector::VoiceActivityDetector as SileroVad;
 
 const SAMPLE_RATE_HZ: u32 = 16000;
 /// Chunk size for Silero VAD at 16kHz (must be 512 samples per V5 model requirements)
-const CHUNK_SIZE: usize = 512;
+pub(super) const CHUNK_SIZE: usize = 512;
+
+type SharedSession = Arc<std::sync::Mutex<Session>>;
+
+/// Sessions of the model files loaded so far. Each detector keeps its own
+/// recurrent state, so detectors of one file share the session.
+static SESSIONS: LazyLock<Mutex<HashMap<PathBuf, SharedSession>>> = LazyLock::new(Default::default);
+
+fn load_session(path: &Path) -> Result<SharedSession> {
+    let mut sessions = SESSIONS.lock();
+    if let Some(session) = sessions.get(path) {
+        return Ok(session.clone());
+    }
+    let session = Session::builder()
+        .and_then(|builder| builder.with_intra_threads(1))
+        .and_then(|builder| builder.with_inter_threads(1))
+        .and_then(|builder| builder.commit_from_file(path))
+        .map_err(|e| anyhow::anyhow!("Failed to load Silero model {}: {}", path.display(), e))?;
+    let session = Arc::new(std::sync::Mutex::new(session));
+    sessions.insert(path.to_path_buf(), session.clone());
+    Ok(session)
+}
 
 /// Silero-based Voice Activity Detector.
 ///
@@ -40,12 +67,24 @@ impl SileroVoiceDetector {
     /// * `threshold` - Speech probability threshold (0.0-1.0), default 0.5
     /// * `silence_threshold_ms` - Duration of silence to trigger speech end
     /// * `_min_speech_duration_ms` - Minimum speech duration (currently unused)
-    pub fn with_thresholds(threshold: f32, silence_threshold_ms: u32, _min_speech_duration_ms: u32) -> Result<Self> {
-        let vad = SileroVad::builder()
-            .sample_rate(SAMPLE_RATE_HZ)
-            .chunk_size(CHUNK_SIZE)
-            .build()
-            .map_err(|e| anyhow::anyhow!("Failed to create Silero VAD: {}", e))?;
+    pub fn with_thresholds(threshold: f32, silence_threshold_ms: u32, min_speech_duration_ms: u32) -> Result<Self> {
+        Self::with_model(None, threshold, silence_threshold_ms, min_speech_duration_ms)
+    }
+
+    /// Like [`Self::with_thresholds`], with the model loaded from `model_path`
+    /// (`None` uses the built-in copy).
+    pub fn with_model(
+        model_path: Option<&Path>,
+        threshold: f32,
+        silence_threshold_ms: u32,
+        _min_speech_duration_ms: u32,
+    ) -> Result<Self> {
+        let builder = SileroVad::builder().sample_rate(SAMPLE_RATE_HZ).chunk_size(CHUNK_SIZE);
+        let vad = match model_path {
+            Some(path) => builder.session(load_session(path)?).build(),
+            None => builder.build(),
+        }
+        .map_err(|e| anyhow::anyhow!("Failed to create Silero VAD: {}", e))?;
 
         Ok(Self {
             vad: RefCell::new(vad),
@@ -54,6 +93,15 @@ impl SileroVoiceDetector {
         })
     }
 
+    /// Speech probability of each full chunk of `samples`.
+    pub fn speech_probabilities(&self, samples: &[f32]) -> Vec<f32> {
+        let mut vad = self.vad.borrow_mut();
+        samples
+            .chunks_exact(CHUNK_SIZE)
+            .map(|chunk| vad.predict(chunk.iter().copied()))
+            .collect()
+    }
+
     /// Get the configured speech probability threshold.
     #[cfg(test)]
     pub fn threshold(&self) -> f32 {
@@ -139,6 +187,12 @@ mod tests {
         assert_eq!(vad.unwrap().threshold(), 0.7);
     }
 
+    #[test]
+    fn test_silero_vad_missing_model_file() {
+        let path = Path::new("/nonexistent/silero_vad.onnx");
+        assert!(SileroVoiceDetector::with_model(Some(path), 0.5, 1000, 500).is_err());
+    }
+
     #[test]
     fn test_silero_vad_default() {
         let vad = SileroVoiceDetector::default();
@@ -0,0 +1,130 @@
+//! Silence trimming for one-shot dictation.
+//!
+//! A short note dictated with the hotkey often holds seconds of silence: the
+//! pause before the first word, the time it takes to reach for the stop key,
+//! and the thinking in between. Whisper spends as long on those as on speech.
+//! Before transcription the recording is run through the VAD once, and only
+//! the speech with its padding is kept; pauses up to `max_pause` stay whole,
+//! longer ones are shortened to the padding around them.
+
+use crate::domain::traits::VoiceDetection;
+use anyhow::Result;
+use std::ops::Range;
+
+const SAMPLE_RATE_HZ: usize = 16000;
+
+/// Frame size for classification (32 ms at 16kHz, accepted by every VAD engine).
+const FRAME_SAMPLES: usize = 512;
+
+/// Padding and pause limit of [`trim_silence`], in milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SilenceTrim {
+    pub pre_roll_ms: u32,
+    pub post_roll_ms: u32,
+    /// Longer pauses inside the recording are cut to the padding.
+    pub max_pause_ms: u32,
+}
+
+impl SilenceTrim {
+    fn samples(ms: u32) -> usize {
+        ms as usize * SAMPLE_RATE_HZ / 1000
+    }
+}
+
+/// `samples` without the silence around and inside the speech.
+///
+/// A recording in which the VAD hears no speech at all is returned whole:
+/// the detector may have missed quiet speech, and the transcriber is better
+/// placed to decide.
+pub fn trim_silence(vad: &dyn VoiceDetection, samples: &[f32], trim: &SilenceTrim) -> Result<Vec<f32>> {
+    let pre_roll = SilenceTrim::samples(trim.pre_roll_ms);
+    let post_roll = SilenceTrim::samples(trim.post_roll_ms);
+    let max_pause = SilenceTrim::samples(trim.max_pause_ms).max(pre_roll + post_roll);
+
+    vad.reset();
+    let mut speech: Vec<Range<usize>> = Vec::new();
+    for (i, frame) in samples.chunks_exact(FRAME_SAMPLES).enumerate() {
+        if !vad.is_speech(frame)? {
+            continue;
+        }
+        let start = i * FRAME_SAMPLES;
+        match speech.last_mut() {
+            // Pauses up to the limit are part of the speech
+            Some(last) if start - last.end <= max_pause => last.end = start + FRAME_SAMPLES,
+            _ => speech.push(start..start + FRAME_SAMPLES),
+        }
+    }
+    if speech.is_empty() {
+        return Ok(samples.to_vec());
+    }
+
+    let mut trimmed = Vec::with_capacity(samples.len());
+    let mut kept_to = 0;
+    for run in speech {
+        let start = run.start.saturating_sub(pre_roll).max(kept_to);
+        let end = (run.end + post_roll).min(samples.len());
+        trimmed.extend_from_slice(&samples[start..end]);
+        kept_to = end;
+    }
+    Ok(trimmed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Treats frames of non-zero samples as speech.
+    struct LevelVad;
+
+    impl VoiceDetection for LevelVad {
+        fn is_speech(&self, samples: &[f32]) -> Result<bool> {
+            Ok(samples.iter().any(|&s| s != 0.0))
+        }
+
+        fn detect_speech_end(&self, _samples: &[f32]) -> Result<bool> {
+            Ok(false)
+        }
+
+        fn reset(&self) {}
+    }
+
+    /// 100 ms padding, pauses over 1 s are cut.
+    const TRIM: SilenceTrim = SilenceTrim {
+        pre_roll_ms: 100,
+        post_roll_ms: 100,
+        max_pause_ms: 1000,
+    };
+
+    /// Recording of `(is_speech, frames)` parts.
+    fn recording(parts: &[(bool, usize)]) -> Vec<f32> {
+        parts
+            .iter()
+            .flat_map(|&(is_speech, frames)| vec![if is_speech { 0.5 } else { 0.0 }; frames * FRAME_SAMPLES])
+            .collect()
+    }
+
+    #[test]
+    fn test_leading_and_trailing_silence_is_trimmed_to_padding() {
+        let samples = recording(&[(false, 100), (true, 20), (false, 100)]);
+        let trimmed = trim_silence(&LevelVad, &samples, &TRIM).unwrap();
+        let padding = SilenceTrim::samples(100);
+        assert_eq!(trimmed.len(), 20 * FRAME_SAMPLES + 2 * padding);
+        assert!(trimmed[..padding].iter().all(|&s| s == 0.0));
+        assert!(trimmed[padding..padding + 20 * FRAME_SAMPLES].iter().all(|&s| s == 0.5));
+    }
+
+    #[test]
+    fn test_short_pause_is_kept_and_long_pause_is_cut() {
+        // ~0.5 s pause stays, ~3 s pause is cut
+        let samples = recording(&[(true, 10), (false, 15), (true, 10), (false, 100), (true, 10)]);
+        let trimmed = trim_silence(&LevelVad, &samples, &TRIM).unwrap();
+        let padding = SilenceTrim::samples(100);
+        assert_eq!(trimmed.len(), (10 + 15 + 10 + 10) * FRAME_SAMPLES + 2 * padding);
+    }
+
+    #[test]
+    fn test_recording_without_speech_is_kept_whole() {
+        let samples = recording(&[(false, 50)]);
+        assert_eq!(trim_silence(&LevelVad, &samples, &TRIM).unwrap(), samples);
+    }
+}
@@ -1,17 +1,39 @@
 //! WebRTC-based Voice Activity Detection.
 //!
 //! Uses the webrtc-vad crate for energy-based VAD.
-//! Fast and lightweight, works well in quiet environments.
+//! Fast and lightweight, works well in quiet environments. The aggressiveness
+//! level (0–3) trades missed speech for false alarms: higher levels reject
+//! more noise, which helps in loud offices but may clip quiet speech.
 
 use crate::domain::traits::VoiceDetection;
 use anyhow::Result;
 use std::cell::RefCell;
-use webrtc_vad::{Vad, VadMode};
+use webrtc_vad::{SampleRate, Vad, VadMode};
 
 const SAMPLE_RATE_HZ: u32 = 16000;
 const FRAME_SIZE_MS: u32 = 30; // 30ms frames for VAD
 const FRAME_SIZE_SAMPLES: usize = (SAMPLE_RATE_HZ as usize * FRAME_SIZE_MS as usize) / 1000;
 
+/// Aggressiveness used when none is configured (`VadMode::Aggressive`).
+pub const DEFAULT_AGGRESSIVENESS: u8 = 2;
+
+/// Highest aggressiveness level (`VadMode::VeryAggressive`).
+pub const MAX_AGGRESSIVENESS: u8 = 3;
+
+/// WebRTC mode for an aggressiveness level; levels above 3 mean 3.
+fn vad_mode(aggressiveness: u8) -> VadMode {
+    match aggressiveness {
+        0 => VadMode::Quality,
+        1 => VadMode::LowBitrate,
+        2 => VadMode::Aggressive,
+        _ => VadMode::VeryAggressive,
+    }
+}
+
+fn new_vad(aggressiveness: u8) -> Vad {
+    Vad::new_with_rate_and_mode(SampleRate::Rate16kHz, vad_mode(aggressiveness))
+}
+
 /// WebRTC-based Voice Activity Detector.
 ///
 /// # Thread Safety
@@ -22,6 +44,7 @@ const FRAME_SIZE_SAMPLES: usize = (SAMPLE_RATE_HZ as usize * FRAME_SIZE_MS as us
 pub struct WebRtcVoiceDetector {
     vad: RefCell<Vad>,
     silence_threshold_ms: u32,
+    aggressiveness: u8,
 }
 
 impl WebRtcVoiceDetector {
@@ -35,13 +58,26 @@ impl WebRtcVoiceDetector {
     /// # Arguments
     /// * `silence_threshold_ms` - Duration of silence to trigger speech end
     /// * `_min_speech_duration_ms` - Minimum speech duration (currently unused)
-    pub fn with_thresholds(silence_threshold_ms: u32, _min_speech_duration_ms: u32) -> Result<Self> {
-        use webrtc_vad::SampleRate;
-        let vad = Vad::new_with_rate_and_mode(SampleRate::Rate16kHz, VadMode::Aggressive);
+    pub fn with_thresholds(silence_threshold_ms: u32, min_speech_duration_ms: u32) -> Result<Self> {
+        Self::with_aggressiveness(silence_threshold_ms, min_speech_duration_ms, DEFAULT_AGGRESSIVENESS)
+    }
 
+    /// Create a new VAD instance with custom thresholds and aggressiveness.
+    ///
+    /// # Arguments
+    /// * `silence_threshold_ms` - Duration of silence to trigger speech end
+    /// * `_min_speech_duration_ms` - Minimum speech duration (currently unused)
+    /// * `aggressiveness` - 0 (least likely to miss speech) to 3 (most noise rejection)
+    pub fn with_aggressiveness(
+        silence_threshold_ms: u32,
+        _min_speech_duration_ms: u32,
+        aggressiveness: u8,
+    ) -> Result<Self> {
+        let aggressiveness = aggressiveness.min(MAX_AGGRESSIVENESS);
         Ok(Self {
-            vad: RefCell::new(vad),
+            vad: RefCell::new(new_vad(aggressiveness)),
             silence_threshold_ms,
+            aggressiveness,
         })
     }
 }
@@ -94,8 +130,7 @@ impl VoiceDetection for WebRtcVoiceDetector {
     }
 
     fn reset(&self) {
-        use webrtc_vad::SampleRate;
-        *self.vad.borrow_mut() = Vad::new_with_rate_and_mode(SampleRate::Rate16kHz, VadMode::Aggressive);
+        *self.vad.borrow_mut() = new_vad(self.aggressiveness);
     }
 }
 
@@ -119,6 +154,21 @@ mod tests {
     fn test_webrtc_vad_default() {
         let vad = WebRtcVoiceDetector::default();
         assert_eq!(vad.silence_threshold_ms, 1000);
+        assert_eq!(vad.aggressiveness, DEFAULT_AGGRESSIVENESS);
+    }
+
+    #[test]
+    fn test_webrtc_vad_aggressiveness_levels() {
+        let silence = vec![0.0f32; FRAME_SIZE_SAMPLES];
+        for level in 0..=MAX_AGGRESSIVENESS {
+            let vad = WebRtcVoiceDetector::with_aggressiveness(1000, 500, level).unwrap();
+            assert_eq!(vad.aggressiveness, level);
+            assert!(!vad.is_speech(&silence).unwrap());
+            vad.reset();
+            assert!(!vad.is_speech(&silence).unwrap());
+        }
+        let vad = WebRtcVoiceDetector::with_aggressiveness(1000, 500, 9).unwrap();
+        assert_eq!(vad.aggressiveness, MAX_AGGRESSIVENESS);
     }
 
     #[test]
@@ -119,6 +119,85 @@ fn cli_models_subcommand() {
     );
 }
 
+/// `models import` of a nonexistent file fails without panicking.
+#[test]
+fn cli_models_import_nonexistent_file() {
+    let output = voice_dictation_cmd()
+        .args(["models", "import", "/tmp/definitely_nonexistent_model_s2t_test.bin"])
+        .output()
+        .expect("failed to execute");
+
+    assert!(!output.status.success(), "should fail with nonexistent model file");
+}
+
+/// `models import` rejects files that are not GGML/GGUF models.
+#[test]
+fn cli_models_import_rejects_invalid_model() {
+    let path = std::env::temp_dir().join("s2t_cli_invalid_model.bin");
+    std::fs::write(&path, b"not a model").unwrap();
+
+    let output = voice_dictation_cmd()
+        .args(["models", "import", path.to_str().unwrap()])
+        .output()
+        .expect("failed to execute");
+    let _ = std::fs::remove_file(&path);
+
+    assert!(!output.status.success(), "should reject non-model file");
+}
+
+/// `models add` rejects non-HTTP URLs.
+#[test]
+fn cli_models_add_rejects_non_http_url() {
+    let output = voice_dictation_cmd()
+        .args(["models", "add", "ftp://example.com/ggml-custom.bin"])
+        .output()
+        .expect("failed to execute");
+
+    assert!(!output.status.success(), "should reject non-HTTP URL");
+}
+
+/// `bench` with a nonexistent input fails without panicking.
+#[test]
+fn cli_bench_nonexistent_file() {
+    let output = voice_dictation_cmd()
+        .args(["bench", "/tmp/definitely_nonexistent_file_s2t_test.wav"])
+        .output()
+        .expect("failed to execute");
+
+    assert!(!output.status.success(), "should fail with nonexistent file");
+    assert!(output.status.code().is_some(), "should exit cleanly, not crash");
+}
+
+/// `benchmark` with a nonexistent input fails without panicking.
+#[test]
+fn cli_benchmark_nonexistent_file() {
+    let output = voice_dictation_cmd()
+        .args(["benchmark", "/tmp/definitely_nonexistent_file_s2t_test.wav"])
+        .output()
+        .expect("failed to execute");
+
+    assert!(!output.status.success(), "should fail with nonexistent file");
+    assert!(output.status.code().is_some(), "should exit cleanly, not crash");
+}
+
+/// RTTM output without a diarization method is rejected before reading audio.
+#[test]
+fn cli_transcribe_rttm_requires_diarization() {
+    let output = voice_dictation_cmd()
+        .args([
+            "transcribe",
+            "/tmp/definitely_nonexistent_file_s2t_test.wav",
+            "--format",
+            "rttm",
+        ])
+        .output()
+        .expect("failed to execute");
+
+    assert!(!output.status.success(), "should fail without diarization");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("require diarization"), "stderr: {}", stderr);
+}
+
 /// Invalid subcommand produces an error.
 #[test]
 fn cli_invalid_subcommand() {
@@ -4,9 +4,10 @@
 //! read back, and deserialized with all fields preserved. Also tests
 //! serde default behavior for partial configs.
 
+use std::collections::BTreeMap;
 use std::fs;
 
-use voice_dictation::app::config::Config;
+use voice_dictation::app::config::{Config, VadProfile, WorkspacePreset};
 
 /// Full round-trip: default Config → TOML → file → TOML → Config.
 #[test]
@@ -26,24 +27,90 @@ fn config_save_load_roundtrip() {
     assert_eq!(loaded.language, original.language);
     assert_eq!(loaded.history_max_entries, original.history_max_entries);
     assert_eq!(loaded.history_max_age_days, original.history_max_age_days);
+    assert_eq!(loaded.history_backend, original.history_backend);
+    assert_eq!(loaded.read_only_history, original.read_only_history);
     assert_eq!(loaded.auto_copy, original.auto_copy);
     assert_eq!(loaded.hotkey_enabled, original.hotkey_enabled);
     assert_eq!(loaded.hotkey, original.hotkey);
+    assert_eq!(loaded.hotkey_layout_independent, original.hotkey_layout_independent);
     assert_eq!(loaded.auto_paste, original.auto_paste);
+    assert_eq!(loaded.start_after_model_load, original.start_after_model_load);
     assert_eq!(loaded.recording_mode, original.recording_mode);
     assert_eq!(loaded.diarization_method, original.diarization_method);
     assert_eq!(loaded.sortformer_model_path, original.sortformer_model_path);
+    assert_eq!(loaded.diarization_max_speakers, original.diarization_max_speakers);
+    assert_eq!(loaded.diarization_onset, original.diarization_onset);
+    assert_eq!(loaded.diarization_offset, original.diarization_offset);
+    assert_eq!(loaded.speaker_attribution, original.speaker_attribution);
+    assert_eq!(loaded.live_diarization, original.live_diarization);
+    assert_eq!(loaded.mic_diarization, original.mic_diarization);
+    assert_eq!(loaded.speaker_reidentification, original.speaker_reidentification);
+    assert_eq!(loaded.speaker_match_threshold, original.speaker_match_threshold);
     assert_eq!(loaded.continuous_mode, original.continuous_mode);
     assert_eq!(loaded.segment_interval_secs, original.segment_interval_secs);
+    assert_eq!(loaded.segment_retry_attempts, original.segment_retry_attempts);
     assert_eq!(loaded.use_vad, original.use_vad);
     assert_eq!(loaded.vad_silence_threshold_ms, original.vad_silence_threshold_ms);
     assert_eq!(loaded.vad_min_speech_ms, original.vad_min_speech_ms);
+    assert_eq!(loaded.vad_language_profiles, original.vad_language_profiles);
     assert_eq!(loaded.denoise_enabled, original.denoise_enabled);
+    assert_eq!(loaded.denoise_backend, original.denoise_backend);
+    assert_eq!(loaded.deepfilter_model_path, original.deepfilter_model_path);
+    assert_eq!(loaded.high_pass_enabled, original.high_pass_enabled);
+    assert_eq!(loaded.high_pass_cutoff_hz, original.high_pass_cutoff_hz);
+    assert_eq!(loaded.resampler_quality, original.resampler_quality);
+    assert_eq!(loaded.pre_record_secs, original.pre_record_secs);
+    assert_eq!(loaded.max_recording_minutes, original.max_recording_minutes);
+    assert_eq!(loaded.conference_spool_minutes, original.conference_spool_minutes);
+    assert_eq!(loaded.echo_cancellation, original.echo_cancellation);
+    assert_eq!(loaded.agc_enabled, original.agc_enabled);
+    assert_eq!(loaded.agc_target_dbfs, original.agc_target_dbfs);
+    assert_eq!(loaded.agc_max_gain_db, original.agc_max_gain_db);
+    assert_eq!(loaded.loopback_source, original.loopback_source);
+    assert_eq!(loaded.recording_normalization, original.recording_normalization);
+    assert_eq!(loaded.recording_peak_dbfs, original.recording_peak_dbfs);
+    assert_eq!(loaded.recording_target_lufs, original.recording_target_lufs);
+    assert_eq!(loaded.recording_filename_template, original.recording_filename_template);
+    assert_eq!(loaded.save_recordings, original.save_recordings);
+    assert_eq!(loaded.recording_format, original.recording_format);
     assert_eq!(loaded.vad_engine, original.vad_engine);
     assert_eq!(loaded.silero_threshold, original.silero_threshold);
+    assert_eq!(loaded.silero_model_path, original.silero_model_path);
+    assert_eq!(loaded.vad_aggressiveness, original.vad_aggressiveness);
+    assert_eq!(loaded.vad_adaptive_noise_floor, original.vad_adaptive_noise_floor);
+    assert_eq!(loaded.vad_noise_floor_dbfs, original.vad_noise_floor_dbfs);
+    assert_eq!(loaded.vad_speech_level_dbfs, original.vad_speech_level_dbfs);
+    assert_eq!(loaded.vad_pre_roll_ms, original.vad_pre_roll_ms);
+    assert_eq!(loaded.vad_post_roll_ms, original.vad_post_roll_ms);
+    assert_eq!(loaded.speech_gated_recording, original.speech_gated_recording);
+    assert_eq!(loaded.trim_dictation_silence, original.trim_dictation_silence);
+    assert_eq!(loaded.dictation_max_pause_ms, original.dictation_max_pause_ms);
     assert_eq!(loaded.stt_backend, original.stt_backend);
     assert_eq!(loaded.tdt_model_path, original.tdt_model_path);
     assert_eq!(loaded.max_segment_secs, original.max_segment_secs);
+    assert_eq!(loaded.phonetic_hints, original.phonetic_hints);
+    assert_eq!(loaded.punctuation_restoration, original.punctuation_restoration);
+    assert_eq!(loaded.verbatim_transcription, original.verbatim_transcription);
+    assert_eq!(loaded.translation_captions, original.translation_captions);
+    assert_eq!(loaded.webdav_url, original.webdav_url);
+    assert_eq!(loaded.webdav_username, original.webdav_username);
+    assert_eq!(loaded.webdav_modes, original.webdav_modes);
+    assert_eq!(loaded.webdav_upload_transcripts, original.webdav_upload_transcripts);
+    assert_eq!(loaded.webdav_upload_recordings, original.webdav_upload_recordings);
+    assert_eq!(loaded.remote_control_enabled, original.remote_control_enabled);
+    assert_eq!(loaded.remote_control_port, original.remote_control_port);
+    assert_eq!(loaded.anki_front_template, original.anki_front_template);
+    assert_eq!(loaded.anki_back_template, original.anki_back_template);
+    assert_eq!(loaded.transcription_cache_max_mb, original.transcription_cache_max_mb);
+    assert_eq!(
+        loaded.transcription_cache_ttl_days,
+        original.transcription_cache_ttl_days
+    );
+    assert_eq!(loaded.action_items_llm_url, original.action_items_llm_url);
+    assert_eq!(loaded.action_items_llm_model, original.action_items_llm_model);
+    assert_eq!(loaded.onboarding_completed, original.onboarding_completed);
+    assert_eq!(loaded.workspace_presets_enabled, original.workspace_presets_enabled);
+    assert_eq!(loaded.workspace_presets, original.workspace_presets);
 
     let _ = fs::remove_file(&path);
     let _ = fs::remove_dir(&dir);
@@ -57,24 +124,99 @@ fn config_custom_values_roundtrip() {
         language: "en".to_string(),
         history_max_entries: 100,
         history_max_age_days: 30,
+        history_backend: "sqlite".to_string(),
+        read_only_history: true,
         auto_copy: true,
         hotkey_enabled: true,
         hotkey: "Alt+R".to_string(),
+        hotkey_layout_independent: false,
         auto_paste: true,
+        start_after_model_load: false,
         recording_mode: "conference".to_string(),
         diarization_method: "sortformer".to_string(),
         sortformer_model_path: Some("/models/sortformer.onnx".to_string()),
+        diarization_max_speakers: 2,
+        diarization_onset: Some(0.5),
+        diarization_offset: Some(0.4),
+        speaker_attribution: "segments".to_string(),
+        live_diarization: false,
+        mic_diarization: true,
+        speaker_reidentification: true,
+        speaker_match_threshold: 0.9,
         continuous_mode: true,
         segment_interval_secs: 10,
+        segment_retry_attempts: 0,
         use_vad: false,
         vad_silence_threshold_ms: 2000,
         vad_min_speech_ms: 1000,
+        vad_language_profiles: BTreeMap::from([(
+            "uk".to_string(),
+            VadProfile {
+                silence_threshold_ms: Some(1500),
+                min_speech_ms: None,
+            },
+        )]),
         denoise_enabled: true,
+        denoise_backend: "deepfilternet".to_string(),
+        deepfilter_model_path: Some("/models/denoiser_model.onnx".to_string()),
+        high_pass_enabled: false,
+        high_pass_cutoff_hz: 120.0,
+        resampler_quality: "fast".to_string(),
+        pre_record_secs: 1.5,
+        max_recording_minutes: 90,
+        conference_spool_minutes: 45,
+        echo_cancellation: false,
+        agc_enabled: true,
+        agc_target_dbfs: -18.0,
+        agc_max_gain_db: 24.0,
+        loopback_source: Some("bluez_output.AA_BB.1.monitor".to_string()),
+        recording_normalization: "lufs".to_string(),
+        recording_peak_dbfs: -2.0,
+        recording_target_lufs: -18.0,
+        recording_filename_template: "{mode}_{date}_{time}.wav".to_string(),
+        save_recordings: true,
+        recording_format: "flac".to_string(),
         vad_engine: "silero".to_string(),
         silero_threshold: 0.8,
+        silero_model_path: Some("/models/silero_vad.onnx".to_string()),
+        vad_aggressiveness: 3,
+        vad_adaptive_noise_floor: false,
+        vad_noise_floor_dbfs: Some(-55.0),
+        vad_speech_level_dbfs: Some(-22.5),
+        vad_pre_roll_ms: 150,
+        vad_post_roll_ms: 500,
+        speech_gated_recording: true,
+        trim_dictation_silence: true,
+        dictation_max_pause_ms: 3000,
         stt_backend: "tdt".to_string(),
         tdt_model_path: Some("/models/tdt".to_string()),
         max_segment_secs: 600,
+        phonetic_hints: true,
+        punctuation_restoration: "rules".to_string(),
+        verbatim_transcription: true,
+        translation_captions: true,
+        webdav_url: Some("https://cloud.example.com/remote.php/dav/files/me/Meetings".to_string()),
+        webdav_username: Some("me".to_string()),
+        webdav_modes: vec!["dictation".to_string(), "conference".to_string()],
+        webdav_upload_transcripts: false,
+        webdav_upload_recordings: true,
+        remote_control_enabled: true,
+        remote_control_port: 9000,
+        anki_front_template: "<b>{definition}</b>".to_string(),
+        anki_back_template: "{term} ({date})".to_string(),
+        transcription_cache_max_mb: 0,
+        transcription_cache_ttl_days: 7,
+        action_items_llm_url: Some("http://localhost:11434/v1/chat/completions".to_string()),
+        action_items_llm_model: "qwen2.5".to_string(),
+        onboarding_completed: false,
+        workspace_presets_enabled: true,
+        workspace_presets: vec![WorkspacePreset {
+            workspace: "Робота".to_string(),
+            recording_mode: Some("conference".to_string()),
+            auto_copy: None,
+            auto_paste: Some(false),
+            language: Some("en".to_string()),
+        }],
     };
 
     let toml_str = toml::to_string_pretty(&original).expect("serialize");
@@ -83,25 +225,104 @@ fn config_custom_values_roundtrip() {
     assert_eq!(loaded.default_model, "ggml-large-v3.bin");
     assert_eq!(loaded.language, "en");
     assert_eq!(loaded.history_max_entries, 100);
+    assert!(loaded.read_only_history);
     assert!(loaded.auto_copy);
     assert!(loaded.hotkey_enabled);
     assert_eq!(loaded.hotkey, "Alt+R");
+    assert!(!loaded.hotkey_layout_independent);
     assert!(loaded.auto_paste);
+    assert!(!loaded.start_after_model_load);
     assert_eq!(loaded.recording_mode, "conference");
     assert_eq!(loaded.diarization_method, "sortformer");
     assert_eq!(
         loaded.sortformer_model_path,
         Some("/models/sortformer.onnx".to_string())
     );
+    assert_eq!(loaded.diarization_max_speakers, 2);
+    assert_eq!(loaded.diarization_onset, Some(0.5));
+    assert_eq!(loaded.diarization_offset, Some(0.4));
+    assert_eq!(loaded.speaker_attribution, "segments");
+    assert!(!loaded.live_diarization);
+    assert!(loaded.mic_diarization);
+    assert!(loaded.speaker_reidentification);
+    assert_eq!(loaded.speaker_match_threshold, 0.9);
     assert!(loaded.continuous_mode);
     assert_eq!(loaded.segment_interval_secs, 10);
+    assert_eq!(loaded.segment_retry_attempts, 0);
     assert!(!loaded.use_vad);
     assert_eq!(loaded.vad_silence_threshold_ms, 2000);
+    assert_eq!(loaded.vad_language_profiles["uk"].silence_threshold_ms, Some(1500));
+    assert_eq!(loaded.vad_language_profiles["uk"].min_speech_ms, None);
     assert!(loaded.denoise_enabled);
+    assert_eq!(loaded.denoise_backend, "deepfilternet");
+    assert_eq!(
+        loaded.deepfilter_model_path.as_deref(),
+        Some("/models/denoiser_model.onnx")
+    );
+    assert!(!loaded.high_pass_enabled);
+    assert_eq!(loaded.high_pass_cutoff_hz, 120.0);
+    assert_eq!(loaded.resampler_quality, "fast");
+    assert_eq!(loaded.pre_record_secs, 1.5);
+    assert_eq!(loaded.max_recording_minutes, 90);
+    assert_eq!(loaded.conference_spool_minutes, 45);
+    assert!(!loaded.echo_cancellation);
+    assert!(loaded.agc_enabled);
+    assert_eq!(loaded.agc_target_dbfs, -18.0);
+    assert_eq!(loaded.agc_max_gain_db, 24.0);
+    assert_eq!(loaded.loopback_source.as_deref(), Some("bluez_output.AA_BB.1.monitor"));
+    assert_eq!(loaded.recording_normalization, "lufs");
+    assert_eq!(loaded.recording_peak_dbfs, -2.0);
+    assert_eq!(loaded.recording_target_lufs, -18.0);
+    assert_eq!(loaded.recording_filename_template, "{mode}_{date}_{time}.wav");
+    assert!(loaded.save_recordings);
+    assert_eq!(loaded.recording_format, "flac");
     assert_eq!(loaded.vad_engine, "silero");
+    assert_eq!(loaded.history_backend, "sqlite");
     assert_eq!(loaded.silero_threshold, 0.8);
+    assert_eq!(loaded.silero_model_path.as_deref(), Some("/models/silero_vad.onnx"));
+    assert_eq!(loaded.vad_aggressiveness, 3);
+    assert!(!loaded.vad_adaptive_noise_floor);
+    assert_eq!(loaded.vad_noise_floor_dbfs, Some(-55.0));
+    assert_eq!(loaded.vad_speech_level_dbfs, Some(-22.5));
+    assert_eq!(loaded.vad_pre_roll_ms, 150);
+    assert_eq!(loaded.vad_post_roll_ms, 500);
+    assert!(loaded.speech_gated_recording);
+    assert!(loaded.trim_dictation_silence);
+    assert_eq!(loaded.dictation_max_pause_ms, 3000);
     assert_eq!(loaded.stt_backend, "tdt");
     assert_eq!(loaded.tdt_model_path, Some("/models/tdt".to_string()));
+    assert!(loaded.phonetic_hints);
+    assert_eq!(loaded.punctuation_restoration, "rules");
+    assert!(loaded.verbatim_transcription);
+    assert!(loaded.translation_captions);
+    assert_eq!(
+        loaded.webdav_url,
+        Some("https://cloud.example.com/remote.php/dav/files/me/Meetings".to_string())
+    );
+    assert_eq!(loaded.webdav_username, Some("me".to_string()));
+    assert_eq!(loaded.webdav_modes, vec!["dictation", "conference"]);
+    assert!(!loaded.webdav_upload_transcripts);
+    assert!(loaded.webdav_upload_recordings);
+    assert!(loaded.remote_control_enabled);
+    assert_eq!(loaded.remote_control_port, 9000);
+    assert_eq!(loaded.anki_front_template, "<b>{definition}</b>");
+    assert_eq!(loaded.anki_back_template, "{term} ({date})");
+    assert_eq!(loaded.transcription_cache_max_mb, 0);
+    assert_eq!(loaded.transcription_cache_ttl_days, 7);
+    assert_eq!(
+        loaded.action_items_llm_url.as_deref(),
+        Some("http://localhost:11434/v1/chat/completions")
+    );
+    assert_eq!(loaded.action_items_llm_model, "qwen2.5");
+    assert!(!loaded.onboarding_completed);
+    assert!(loaded.workspace_presets_enabled);
+    assert_eq!(loaded.workspace_presets[0].workspace, "Робота");
+    assert_eq!(
+        loaded.workspace_presets[0].recording_mode.as_deref(),
+        Some("conference")
+    );
+    assert_eq!(loaded.workspace_presets[0].auto_copy, None);
+    assert_eq!(loaded.workspace_presets[0].auto_paste, Some(false));
 }
 
 /// Partial TOML config fills missing fields with serde defaults.
@@ -0,0 +1,437 @@
+//! Integration test: end-to-end continuous dictation pipeline.
+//!
+//! Runs the path of a continuous-mode session without a microphone or GUI:
+//! a simulated recorder fills the shared samples buffer in real time, the
+//! `SegmentationMonitor` cuts it into segments, a transcription backend turns
+//! the segments into text, and the result becomes a history entry with a
+//! saved recording and an export. Entries and exports are compared with the
+//! golden files in `tests/golden/`.
+//!
+//! The backend is a mock that "recognizes" tones: every word of the script is
+//! a tone of its own frequency, so the expected text does not depend on where
+//! the segments are cut. Set `S2T_E2E_MODEL` (a Whisper model) and
+//! `S2T_E2E_AUDIO` (a WAV file with speech) to run the same path with a real
+//! model as well.
+//!
+//! Sessions run in real time, about ten seconds each, so the tests only build
+//! with the feature: `cargo test --features e2e`. Regenerate the golden files
+//! after an intended change with `S2T_UPDATE_GOLDEN=1 cargo test --features e2e`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use chrono::{NaiveDate, TimeZone, Utc};
+use parking_lot::Mutex;
+
+use voice_dictation::cli::args::ChannelMode;
+use voice_dictation::cli::wav_reader::{prepare_for_whisper, read_wav};
+use voice_dictation::domain::traits::{HistoryRepository, Transcription};
+use voice_dictation::domain::types::AudioSegment;
+use voice_dictation::history::{export_to_text, History, HistoryEntry};
+use voice_dictation::infrastructure::recordings::{
+    load_recording, render_recording_filename, save_mono_recording, RecordingFormat,
+};
+use voice_dictation::recording::segmentation::{SegmentationConfig, SegmentationMonitor};
+use voice_dictation::transcription::TranscriptionService;
+
+const SAMPLE_RATE: usize = 16000;
+
+/// Words the mock backend knows, with the tone that stands for each.
+const TONES: &[(&str, f32)] = &[
+    ("привіт", 500.0),
+    ("світ", 750.0),
+    ("це", 1000.0),
+    ("тест", 1250.0),
+    ("кінець", 1500.0),
+];
+
+/// Utterances of the script, each spoken at once, with pauses between them.
+const UTTERANCES: &[&[&str]] = &[&["привіт", "світ"], &["це", "тест"], &["кінець"]];
+
+const EXPECTED_TEXT: &str = "привіт світ це тест кінець";
+
+/// Analysis frame of the mock backend: 20 ms, a whole number of periods of every tone.
+const FRAME: usize = SAMPLE_RATE / 50;
+
+// === Simulated recorder ===
+
+/// What the simulated microphone hears, in the order it is recorded.
+enum Chunk {
+    Silence(Duration),
+    Utterance(&'static [&'static str]),
+    /// Recorded audio, e.g. read from a file
+    Audio(Vec<f32>),
+}
+
+/// The script of a session: a pause, the utterances with pauses between them,
+/// and a long tail of silence.
+///
+/// The tail is longer than the 5 s the monitor falls back to when its ring
+/// buffer is empty at stop, so that fallback never repeats an utterance.
+fn script() -> Vec<Chunk> {
+    let mut chunks = vec![Chunk::Silence(Duration::from_millis(500))];
+    for (i, utterance) in UTTERANCES.iter().enumerate() {
+        if i > 0 {
+            chunks.push(Chunk::Silence(Duration::from_millis(1200)));
+        }
+        chunks.push(Chunk::Utterance(utterance));
+    }
+    chunks.push(Chunk::Silence(Duration::from_millis(5500)));
+    chunks
+}
+
+/// A word: 300 ms of its tone with 10 ms fades, then 100 ms of silence.
+fn word_samples(word: &str) -> Vec<f32> {
+    let freq = TONES.iter().find(|(w, _)| *w == word).expect("word without a tone").1;
+    let tone_len = SAMPLE_RATE * 3 / 10;
+    let fade = SAMPLE_RATE / 100;
+    let mut samples: Vec<f32> = (0..tone_len)
+        .map(|i| {
+            let envelope = (i.min(tone_len - 1 - i) as f32 / fade as f32).min(1.0);
+            let t = i as f32 / SAMPLE_RATE as f32;
+            0.3 * envelope * (2.0 * std::f32::consts::PI * freq * t).sin()
+        })
+        .collect();
+    samples.resize(tone_len + SAMPLE_RATE / 10, 0.0);
+    samples
+}
+
+/// Stand-in for the microphone recorder: appends to the shared samples
+/// buffer in real time, the way the capture thread does.
+///
+/// Silence and audio arrive in 100 ms chunks; an utterance is appended at
+/// once and then waited out, so a segment boundary never falls inside one.
+fn play(script: Vec<Chunk>, buffer: Arc<Mutex<Vec<f32>>>) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        let append = |samples: &[f32]| {
+            buffer.lock().extend_from_slice(samples);
+            std::thread::sleep(Duration::from_secs_f64(samples.len() as f64 / SAMPLE_RATE as f64));
+        };
+        for item in script {
+            match item {
+                Chunk::Silence(duration) => {
+                    let len = (duration.as_secs_f64() * SAMPLE_RATE as f64).round() as usize;
+                    vec![0.0; len].chunks(SAMPLE_RATE / 10).for_each(append);
+                }
+                Chunk::Utterance(words) => {
+                    let samples: Vec<f32> = words.iter().flat_map(|w| word_samples(w)).collect();
+                    append(&samples);
+                }
+                Chunk::Audio(samples) => samples.chunks(SAMPLE_RATE / 10).for_each(append),
+            }
+        }
+    })
+}
+
+// === Mock backend ===
+
+/// Transcription backend that recognizes the tones of [`TONES`].
+///
+/// Each 20 ms frame above the silence level is attributed to the loudest
+/// tone; a run of at least three frames of one tone is its word.
+struct ToneTranscription;
+
+/// Power of `freq` in `frame` (Goertzel).
+fn tone_power(frame: &[f32], freq: f32) -> f32 {
+    let coeff = 2.0 * (2.0 * std::f32::consts::PI * freq / SAMPLE_RATE as f32).cos();
+    let (mut s1, mut s2) = (0.0f32, 0.0f32);
+    for &x in frame {
+        let s = x + coeff * s1 - s2;
+        s2 = s1;
+        s1 = s;
+    }
+    s1 * s1 + s2 * s2 - coeff * s1 * s2
+}
+
+impl Transcription for ToneTranscription {
+    fn transcribe(&self, samples: &[f32], _language: &str) -> anyhow::Result<String> {
+        let frame_words = samples.chunks_exact(FRAME).map(|frame| {
+            let rms = (frame.iter().map(|x| x * x).sum::<f32>() / FRAME as f32).sqrt();
+            (rms > 0.05).then(|| {
+                TONES
+                    .iter()
+                    .max_by(|a, b| tone_power(frame, a.1).total_cmp(&tone_power(frame, b.1)))
+                    .map(|(word, _)| *word)
+                    .expect("tones")
+            })
+        });
+
+        let mut words: Vec<&str> = Vec::new();
+        let mut run: Option<(&str, usize)> = None;
+        for word in frame_words.chain(std::iter::once(None)) {
+            match (run, word) {
+                (Some((current, len)), Some(w)) if current == w => run = Some((current, len + 1)),
+                _ => {
+                    if let Some((current, len)) = run {
+                        if len >= 3 {
+                            words.push(current);
+                        }
+                    }
+                    run = word.map(|w| (w, 1));
+                }
+            }
+        }
+        Ok(words.join(" "))
+    }
+
+    fn is_loaded(&self) -> bool {
+        true
+    }
+
+    fn model_name(&self) -> Option<String> {
+        Some("tone-mock".to_string())
+    }
+
+    fn load_model(&mut self, _path: &Path) -> anyhow::Result<()> {
+        Ok(())
+    }
+}
+
+// === Pipeline ===
+
+/// What a session produced.
+struct Session {
+    /// Everything the recorder captured
+    recorded: Vec<f32>,
+    segments: Vec<AudioSegment>,
+    /// Segment texts joined in segment order, as the result view shows them
+    text: String,
+}
+
+/// Record `script` through a segmentation monitor and transcribe each segment
+/// on a worker thread as it arrives, like a continuous-mode session.
+fn run_session(script: Vec<Chunk>, config: SegmentationConfig, backend: Arc<dyn Transcription>) -> Session {
+    let buffer = Arc::new(Mutex::new(Vec::new()));
+    let monitor = SegmentationMonitor::new(config);
+    let (segment_tx, segment_rx) = async_channel::unbounded::<AudioSegment>();
+    monitor.start(buffer.clone(), segment_tx);
+
+    let worker = std::thread::spawn(move || {
+        let mut results = Vec::new();
+        while let Ok(segment) = segment_rx.recv_blocking() {
+            let text = backend.transcribe(&segment.samples, "uk").expect("transcribe segment");
+            results.push((segment, text));
+        }
+        results
+    });
+
+    play(script, buffer.clone()).join().expect("recorder thread");
+    // Segmentation stops before the recorder, as in the app
+    monitor.stop(&buffer);
+    let mut results = worker.join().expect("transcription thread");
+
+    results.sort_by_key(|(segment, _)| segment.segment_id);
+    let text = results
+        .iter()
+        .map(|(_, text)| text.as_str())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+    let recorded = buffer.lock().clone();
+    Session {
+        recorded,
+        segments: results.into_iter().map(|(segment, _)| segment).collect(),
+        text,
+    }
+}
+
+/// Fresh temp directory for one test.
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).expect("create temp dir");
+    dir
+}
+
+/// Save the session's recording and turn it into a history entry, like the
+/// end of a dictation with `save_recordings` on.
+fn finish_session(session: &Session, dir: &Path) -> HistoryEntry {
+    let at = NaiveDate::from_ymd_opt(2026, 1, 30)
+        .and_then(|d| d.and_hms_opt(9, 15, 0))
+        .expect("date");
+    let filename = render_recording_filename(
+        "{date}_{time}_{mode}_{title}.wav",
+        at,
+        "continuous",
+        None,
+        RecordingFormat::Wav,
+    );
+    let path = dir.join(filename);
+    save_mono_recording(&session.recorded, &path).expect("save recording");
+
+    HistoryEntry::new_with_recording(
+        session.text.clone(),
+        session.recorded.len() as f32 / SAMPLE_RATE as f32,
+        "uk".to_string(),
+        Some(path.to_string_lossy().to_string()),
+        Vec::new(),
+    )
+}
+
+// === Golden files ===
+
+/// Compare `actual` with `tests/golden/<name>`, or rewrite the file when
+/// `S2T_UPDATE_GOLDEN` is set.
+fn assert_golden(name: &str, actual: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden").join(name);
+    if std::env::var_os("S2T_UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().expect("golden dir")).expect("create golden dir");
+        fs::write(&path, format!("{}\n", actual.trim_end())).expect("write golden file");
+        return;
+    }
+    let expected = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    assert_eq!(
+        actual.trim_end(),
+        expected.trim_end(),
+        "{} differs; if the change is intended, rerun with S2T_UPDATE_GOLDEN=1",
+        name
+    );
+}
+
+/// The entry as JSON, without what changes from run to run: the ID, the
+/// time of the session and the temp directory of the recording.
+fn normalized_entry_json(entry: &HistoryEntry) -> String {
+    let mut entry = entry.clone();
+    entry.id = "<id>".to_string();
+    entry.timestamp = Utc.with_ymd_and_hms(2026, 1, 30, 9, 15, 0).unwrap();
+    entry.recording_path = entry
+        .recording_path
+        .as_deref()
+        .and_then(|p| Path::new(p).file_name())
+        .map(|name| name.to_string_lossy().to_string());
+    serde_json::to_string_pretty(&entry).expect("serialize entry")
+}
+
+/// The export without the export time and the local-time entry dates.
+fn normalized_export(export: &str) -> String {
+    export
+        .lines()
+        .map(|line| {
+            if line.starts_with("# Експортовано:") {
+                "# Експортовано: <time>"
+            } else if line.starts_with("Дата:") {
+                "Дата: <time>"
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// === Tests ===
+
+/// Fixed-interval segmentation: segments tile the recording, every word is
+/// transcribed once, and the entry, its recording and the export match the
+/// golden files.
+#[test]
+fn e2e_fixed_interval_session_matches_golden() {
+    let dir = temp_dir("s2t_integ_e2e_fixed");
+    let config = SegmentationConfig {
+        use_vad: false,
+        segment_interval_secs: 1,
+        ..SegmentationConfig::default()
+    };
+    let session = run_session(script(), config, Arc::new(ToneTranscription));
+
+    assert!(session.segments.len() >= 2, "got {} segments", session.segments.len());
+    for (i, segment) in session.segments.iter().enumerate() {
+        assert_eq!(segment.segment_id, i + 1);
+        let end = segment.start_sample + segment.samples.len();
+        assert!(end <= session.recorded.len());
+        assert_eq!(
+            &session.recorded[segment.start_sample..end],
+            &segment.samples[..],
+            "segment {} is not the recorded audio at its position",
+            segment.segment_id
+        );
+    }
+    assert_eq!(session.text, EXPECTED_TEXT);
+
+    let entry = finish_session(&session, &dir);
+    let mut history = History::default();
+    history.add(entry);
+
+    // The history file round-trips
+    let history_path = dir.join("history.json");
+    fs::write(
+        &history_path,
+        serde_json::to_string_pretty(&history).expect("serialize"),
+    )
+    .expect("write history");
+    let loaded: History = serde_json::from_str(&fs::read_to_string(&history_path).expect("read")).expect("parse");
+    let entry = &loaded.entries()[0];
+    assert_golden("e2e_dictation_entry.json", &normalized_entry_json(entry));
+
+    // The saved recording is the session and transcribes the same again
+    let recording = entry.recording_path.as_deref().expect("recording path");
+    let (mic, loopback) = load_recording(Path::new(recording)).expect("load recording");
+    assert_eq!(mic, session.recorded);
+    assert!(loopback.is_empty());
+    assert_eq!(ToneTranscription.transcribe(&mic, "uk").unwrap(), EXPECTED_TEXT);
+
+    let export_path = dir.join("export.txt");
+    export_to_text(&[entry], &export_path).expect("export");
+    let export = fs::read_to_string(&export_path).expect("read export");
+    assert_golden("e2e_dictation_export.txt", &normalized_export(&export));
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+/// VAD segmentation: where WebRTC VAD cuts synthetic tones varies, so only
+/// properties that hold for any cut are checked: segments come in order and
+/// lie inside the recording, and no word is invented, repeated or reordered.
+#[test]
+fn e2e_vad_session_keeps_word_order() {
+    let session = run_session(script(), SegmentationConfig::default(), Arc::new(ToneTranscription));
+
+    assert!(!session.segments.is_empty());
+    for (i, segment) in session.segments.iter().enumerate() {
+        assert_eq!(segment.segment_id, i + 1);
+        assert!(segment.start_sample + segment.samples.len() <= session.recorded.len());
+    }
+
+    let mut expected = EXPECTED_TEXT.split(' ');
+    for word in session.text.split_whitespace() {
+        assert!(
+            expected.any(|w| w == word),
+            "\"{}\" is not a subsequence of \"{}\"",
+            session.text,
+            EXPECTED_TEXT
+        );
+    }
+}
+
+/// The same pipeline with a real Whisper model, when one is configured:
+/// `S2T_E2E_MODEL=<ggml model> S2T_E2E_AUDIO=<speech.wav>`. The file is
+/// played in real time, so keep it short.
+///
+/// Real output varies between models, so only its presence is checked.
+#[test]
+fn e2e_real_model_session() {
+    let (Some(model), Some(audio)) = (std::env::var_os("S2T_E2E_MODEL"), std::env::var_os("S2T_E2E_AUDIO")) else {
+        eprintln!("S2T_E2E_MODEL and S2T_E2E_AUDIO are not set, skipping");
+        return;
+    };
+    let dir = temp_dir("s2t_integ_e2e_model");
+    let wav = read_wav(Path::new(&audio)).expect("read audio");
+    let prepared = prepare_for_whisper(&wav, ChannelMode::Mix, None).expect("prepare audio");
+    let service = TranscriptionService::with_model(&model.to_string_lossy()).expect("load model");
+
+    let script = vec![
+        Chunk::Audio(prepared.samples),
+        Chunk::Silence(Duration::from_millis(1500)),
+    ];
+    let session = run_session(script, SegmentationConfig::default(), Arc::new(service));
+    assert!(!session.text.trim().is_empty(), "the model recognized nothing");
+
+    let entry = finish_session(&session, &dir);
+    let (mic, _) =
+        load_recording(Path::new(entry.recording_path.as_deref().expect("recording path"))).expect("load recording");
+    assert_eq!(mic.len(), session.recorded.len());
+
+    let _ = fs::remove_dir_all(&dir);
+}
@@ -22,6 +22,19 @@ fn entry_at(text: &str, timestamp: chrono::DateTime<Utc>) -> HistoryEntry {
         language: "uk".to_string(),
         recording_path: None,
         speakers: Vec::new(),
+        action_items: Vec::new(),
+        speaker_stats: Vec::new(),
+        speaker_confidence: Vec::new(),
+        title: None,
+        tags: Vec::new(),
+        audio_quality: None,
+        pinned: false,
+        original_text: None,
+        model: None,
+        processing_secs: None,
+        previous_versions: Vec::new(),
+        segments: Vec::new(),
+        deleted_at: None,
     }
 }
 